@@ -0,0 +1,83 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::absolute_layout::AbsoluteLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::{Layout, LayoutPosition};
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+    CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_points_origin, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render absolute layout demo", 400, 300)
+        .position_centered()
+        .resizable()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = AbsoluteLayout::new(0, 0, 400, 300, PaddingConstraint::new(0, 0, 0, 0, 0));
+
+    // This screen is designed in fixed pixels for a 400x300 window.
+    let mut widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        20,
+        TextJustify::Center,
+        String::from("Resize the window"),
+        make_points(20, 20),
+        make_size(360, 40),
+    );
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(220, 220, 220));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut widget2 = PushButtonWidget::new(
+        make_points(20, 240),
+        make_size(170, 40),
+        String::from("Cancel"),
+        16,
+    );
+
+    widget2.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget2.set_numeric(CONFIG_BORDER_WIDTH, 1);
+
+    let mut widget3 = PushButtonWidget::new(
+        make_points_origin(),
+        make_size(0, 0),
+        String::from("OK"),
+        16,
+    );
+
+    widget3.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget3.set_numeric(CONFIG_BORDER_WIDTH, 1);
+
+    let widget1_id = engine
+        .add_widget(Box::new(widget1), String::from("widget1"))
+        .id();
+    let widget2_id = engine
+        .add_widget(Box::new(widget2), String::from("widget2"))
+        .id();
+    let widget3_id = engine
+        .add_widget(Box::new(widget3), String::from("widget3"))
+        .id();
+
+    // The first two Widgets keep the bounds they were created with; the third is given its
+    // design-time bounds explicitly.
+    layout.insert_widget(widget1_id, LayoutPosition::new(0, 0));
+    layout.insert_widget(widget2_id, LayoutPosition::new(0, 0));
+    layout.insert_widget_at(widget3_id, 210, 240, 170, 40);
+    layout.set_track_window(true);
+    engine.add_layout(Box::new(layout));
+
+    engine.run(sdl_context, window);
+}
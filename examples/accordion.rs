@@ -0,0 +1,89 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_BORDER_WIDTH, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::accordion_widget::AccordionWidget;
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render accordion demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+
+    // The contents of every section are placed as if the section started below the first header.
+    let mut text_widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("This is the general section."),
+        make_points(30, 58),
+        make_size(340, 30),
+    );
+
+    text_widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut text_widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("This is the advanced section."),
+        make_points(30, 58),
+        make_size(340, 30),
+    );
+
+    text_widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut button1 = PushButtonWidget::new(
+        make_points(30, 93),
+        make_size(150, 30),
+        String::from("Reset"),
+        16,
+    );
+
+    button1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    button1.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    button1.on_click(|_x, _widgets, _layouts| {
+        eprintln!("Reset clicked");
+    });
+
+    let text_handle1 = engine.add_widget(Box::new(text_widget1), String::from("text1"));
+    let text_handle2 = engine.add_widget(Box::new(text_widget2), String::from("text2"));
+    let button_handle1 = engine.add_widget(Box::new(button1), String::from("button1"));
+    let mut widget1 = AccordionWidget::new(make_points(20, 20), make_size(360, 260));
+
+    widget1.add_section("General", 50, vec![text_handle1.id()]);
+    widget1.add_section("Advanced", 85, vec![text_handle2.id(), button_handle1.id()]);
+    widget1.add_section("About", 0, vec![]);
+    widget1.set_section_open(0, true);
+    widget1.set_exclusive(true);
+
+    widget1.on_section_toggled(|x, _widgets, _layout, section, open| {
+        eprintln!(
+            "Section {} {}",
+            x.get_section_title(section).unwrap(),
+            if open { "opened" } else { "closed" }
+        );
+    });
+
+    let accordion_handle1 = engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    // The contents of each section are drawn above the accordion widget.
+    for handle in &[text_handle1, text_handle2, button_handle1] {
+        engine.set_parent(*handle, accordion_handle1).unwrap();
+    }
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,63 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::grid_layout::GridLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::{Layout, LayoutAlignment};
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+};
+use pushrod::render::{make_points_origin, make_size};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render aligned layout demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = GridLayout::new(
+        20,
+        20,
+        360,
+        260,
+        vec![3, 3],
+        PaddingConstraint::new(0, 0, 0, 0, 4),
+    );
+    let alignments = [
+        LayoutAlignment::Start,
+        LayoutAlignment::Center,
+        LayoutAlignment::End,
+    ];
+
+    // Each small widget is aligned in its cell by its column and row, so the widgets gather at
+    // the corners, edges and center of the grid.
+    for (row, vertical) in alignments.iter().enumerate() {
+        for (column, horizontal) in alignments.iter().enumerate() {
+            let mut widget = BaseWidget::new(make_points_origin(), make_size(50, 30));
+
+            widget
+                .get_config()
+                .set_color(CONFIG_COLOR_BASE, Color::RGB(200, 200, 255));
+            widget
+                .get_config()
+                .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            widget.get_config().set_numeric(CONFIG_BORDER_WIDTH, 1);
+
+            let widget_id = engine
+                .add_widget(Box::new(widget), format!("widget{}", row * 3 + column + 1))
+                .id();
+
+            layout.append_widget(widget_id);
+            layout.set_widget_alignment(widget_id, *horizontal, *vertical);
+        }
+    }
+
+    engine.add_layout(Box::new(layout));
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,94 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::anchor_layout::{AnchorLayout, HorizontalAnchor, VerticalAnchor};
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+    CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points_origin, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render anchor layout demo", 400, 300)
+        .position_centered()
+        .resizable()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = AnchorLayout::new(0, 0, 400, 300, PaddingConstraint::new(10, 10, 10, 10, 0));
+    let mut widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        20,
+        TextJustify::Center,
+        String::from("Resize the window"),
+        make_points_origin(),
+        make_size(240, 30),
+    );
+
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Left,
+        String::from("Status: ready"),
+        make_points_origin(),
+        make_size(0, 24),
+    );
+
+    widget2.set_color(CONFIG_COLOR_BASE, Color::RGB(220, 220, 220));
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut widget3 = PushButtonWidget::new(
+        make_points_origin(),
+        make_size(80, 30),
+        String::from("OK"),
+        16,
+    );
+
+    widget3.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget3.set_numeric(CONFIG_BORDER_WIDTH, 1);
+
+    let widget1_id = engine
+        .add_widget(Box::new(widget1), String::from("widget1"))
+        .id();
+    let widget2_id = engine
+        .add_widget(Box::new(widget2), String::from("widget2"))
+        .id();
+    let widget3_id = engine
+        .add_widget(Box::new(widget3), String::from("widget3"))
+        .id();
+
+    // The title stays centered, the status bar spans the bottom, and the button stays just above
+    // the status bar in the bottom right corner.
+    layout.insert_widget_anchored(
+        widget1_id,
+        HorizontalAnchor::Center(0),
+        VerticalAnchor::Center(0),
+    );
+    layout.insert_widget_anchored(
+        widget2_id,
+        HorizontalAnchor::Stretch(0, 0),
+        VerticalAnchor::Bottom(0),
+    );
+    layout.insert_widget_anchored(
+        widget3_id,
+        HorizontalAnchor::Right(0),
+        VerticalAnchor::Bottom(34),
+    );
+    layout.set_track_window(true);
+    engine.add_layout(Box::new(layout));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,38 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::animated_image_widget::*;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render animated image demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let widget1 = AnimatedImageWidget::from_sprite_sheet(
+        String::from("assets/spinner_sheet.png"),
+        make_points(50, 100),
+        make_size(100, 100),
+        false,
+        make_size(32, 32),
+        8,
+        12,
+    );
+    let widget2 = AnimatedImageWidget::from_gif(
+        String::from("assets/spinner.gif"),
+        make_points(250, 100),
+        make_size(100, 100),
+        true,
+    );
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
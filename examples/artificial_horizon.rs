@@ -0,0 +1,48 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::artificial_horizon_widget::*;
+use pushrod::widgets::timer_widget::*;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render artificial horizon demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let widget1 = ArtificialHorizonWidget::new(make_points(70, 20), make_size(260, 260));
+
+    let mut step = 0.0_f64;
+    let mut timer = TimerWidget::new(30, true);
+    timer.on_timeout(move |_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+
+        step += 0.02;
+        cast!(_widgets, widget1_id, ArtificialHorizonWidget)
+            .set_attitude((step * 0.7).sin() * 15.0, step.sin() * 40.0);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,44 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::audio_level_widget::*;
+use std::thread;
+use std::time::Duration;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render audio level demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let widget1 = AudioLevelWidget::new(make_points(150, 20), make_size(40, 260), 24);
+    let widget2 = AudioLevelWidget::new(make_points(210, 20), make_size(40, 260), 24);
+    let left = widget1.get_handle();
+    let right = widget2.get_handle();
+
+    // This stands in for an audio thread, which measures the level of each channel as it is
+    // processed.
+    thread::spawn(move || {
+        let mut time = 0.0f32;
+
+        loop {
+            let beat = (time * 2.0).sin().abs();
+
+            left.set_level(beat * (0.6 + 0.4 * (time * 0.3).sin().abs()));
+            right.set_level(beat * (0.6 + 0.4 * (time * 0.7).cos().abs()));
+            time += 0.01;
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,89 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_HOVER, CONFIG_COLOR_TEXT, CONFIG_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::auto_complete_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+const COUNTRIES: [&str; 16] = [
+    "Argentina",
+    "Australia",
+    "Austria",
+    "Belgium",
+    "Brazil",
+    "Canada",
+    "Chile",
+    "Denmark",
+    "Finland",
+    "France",
+    "Germany",
+    "Greece",
+    "Iceland",
+    "India",
+    "Ireland",
+    "Italy",
+];
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render auto complete demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = AutoCompleteWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        16,
+        make_points(20, 20),
+        make_size(360, 30),
+    );
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_HOVER, Color::RGB(0x90, 0x90, 0xFF));
+    widget1.set_provider(|text| {
+        let text = text.to_lowercase();
+
+        COUNTRIES
+            .iter()
+            .filter(|country| country.to_lowercase().starts_with(&text))
+            .map(|country| String::from(*country))
+            .collect()
+    });
+    widget1.on_text_committed(|_, _widgets, _layouts, text| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+        _widgets[widget2_id]
+            .widget
+            .borrow_mut()
+            .set_text(CONFIG_TEXT, format!("Committed: {}", text));
+    });
+
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("Type the name of a country."),
+        make_points(20, 250),
+        make_size(360, 30),
+    );
+
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,31 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::calendar_widget::{CalendarDate, CalendarWidget};
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render calendar demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = CalendarWidget::new(make_points(20, 20), make_size(280, 260));
+
+    widget1.set_selected_date(Some(CalendarDate::today()));
+    widget1.on_date_selected(|_x, _widgets, _layout, date| {
+        eprintln!(
+            "Date selected: {}-{:02}-{:02}",
+            date.year, date.month, date.day
+        );
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,54 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::canvas_helper::{fill_circle, point_at_angle};
+use pushrod::render::clock;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::canvas_widget::*;
+use pushrod::widgets::timer_widget::*;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render canvas demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = CanvasWidget::new(make_points(20, 20), make_size(360, 260));
+
+    widget1.set_draw_fn(Box::new(|c, bounds: &Rect| {
+        let angle = (clock::now_millis() % 3600) as f64 / 10.0;
+
+        c.set_draw_color(Color::RGB(0, 0, 0));
+        c.draw_rect(*bounds).unwrap();
+
+        for arm in 0..6 {
+            let angle = angle + arm as f64 * 60.0;
+
+            c.set_draw_color(Color::RGB(40 * arm, 110, 220 - 30 * arm));
+            fill_circle(c, point_at_angle(bounds.center(), 90.0, angle), 12);
+        }
+    }));
+
+    let mut timer = TimerWidget::new(16, true);
+    timer.on_timeout(|_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+
+        _widgets[widget1_id]
+            .widget
+            .borrow_mut()
+            .set_invalidated(true);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,102 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::card_layout::CardLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::Layout;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+    CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_points_origin, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+/// Creates a card showing one step of a wizard.
+fn make_card(text: &str, color: Color) -> TextWidget {
+    let mut widget = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        24,
+        TextJustify::Center,
+        String::from(text),
+        make_points_origin(),
+        make_size(0, 0),
+    );
+
+    widget.set_color(CONFIG_COLOR_BASE, color);
+    widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget
+}
+
+/// Creates a button that moves between the cards of the `CardLayout`.
+fn make_button(x: i32, text: &str, forward: bool) -> PushButtonWidget {
+    let mut widget = PushButtonWidget::new(
+        make_points(x, 250),
+        make_size(80, 30),
+        String::from(text),
+        16,
+    );
+
+    widget.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.set_numeric(CONFIG_BORDER_WIDTH, 1);
+    widget.on_click(move |_, _widgets, _layouts| {
+        let mut layout = _layouts[0].layout.borrow_mut();
+        let cards = layout
+            .as_any()
+            .unwrap()
+            .downcast_mut::<CardLayout>()
+            .unwrap();
+
+        if forward {
+            cards.next_card();
+        } else {
+            cards.previous_card();
+        }
+    });
+
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render card layout demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = CardLayout::new(20, 20, 360, 220, PaddingConstraint::new(0, 0, 0, 0, 0));
+    let cards = [
+        ("Step 1: Welcome", Color::RGB(200, 200, 255)),
+        ("Step 2: Options", Color::RGB(200, 255, 200)),
+        ("Step 3: Finish", Color::RGB(255, 255, 200)),
+    ];
+
+    for (i, (text, color)) in cards.iter().enumerate() {
+        let widget_id = engine
+            .add_widget(
+                Box::new(make_card(text, *color)),
+                format!("widget{}", i + 1),
+            )
+            .id();
+
+        layout.append_widget(widget_id);
+    }
+
+    engine.add_layout(Box::new(layout));
+    engine.add_widget(
+        Box::new(make_button(200, "< Back", false)),
+        String::from("widget4"),
+    );
+    engine.add_widget(
+        Box::new(make_button(300, "Next >", true)),
+        String::from("widget5"),
+    );
+
+    engine.run(sdl_context, window);
+}
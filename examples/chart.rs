@@ -0,0 +1,71 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::chart_widget::*;
+use pushrod::widgets::timer_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render chart demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = ChartWidget::new(make_points(10, 10), make_size(380, 135), ChartKind::Line);
+
+    widget1.add_series(
+        vec![3.0, 5.5, 4.0, 7.5, 6.0, 9.0, 8.5],
+        Color::RGB(40, 110, 220),
+    );
+    widget1.add_series(
+        vec![1.0, 2.0, 2.5, 2.0, 4.0, 3.5, 5.0],
+        Color::RGB(220, 60, 60),
+    );
+
+    let mut widget2 = ChartWidget::new(make_points(10, 155), make_size(380, 135), ChartKind::Bar);
+
+    widget2.add_series(vec![12.0, 19.0, 7.0, 15.0], Color::RGB(76, 200, 100));
+    widget2.add_series(vec![8.0, 11.0, 14.0, 10.0], Color::RGB(240, 200, 60));
+    widget2.set_labels(vec![
+        String::from("Q1"),
+        String::from("Q2"),
+        String::from("Q3"),
+        String::from("Q4"),
+    ]);
+
+    let mut timer = TimerWidget::new(1000, true);
+    timer.on_timeout(|_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        let mut data = cast!(_widgets, widget1_id, ChartWidget)
+            .get_data(0)
+            .to_vec();
+
+        data.rotate_left(1);
+        cast!(_widgets, widget1_id, ChartWidget).set_data(0, data);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
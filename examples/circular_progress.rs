@@ -0,0 +1,40 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::CONFIG_COLOR_SECONDARY;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::circular_progress_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render circular progress demo", 400, 180)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 180, 60);
+    let mut widget1 = CircularProgressWidget::new(make_points(20, 30), make_size(110, 110), 25);
+
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
+
+    let mut widget2 = CircularProgressWidget::new(make_points(145, 30), make_size(110, 110), 50);
+
+    widget2.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
+    widget2.set_thickness(20);
+
+    let mut widget3 = CircularProgressWidget::new(make_points(270, 30), make_size(110, 110), 75);
+
+    widget3.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
+    widget3.set_start_angle(-90.0);
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(widget3), String::from("widget3"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,40 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_SECONDARY, CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::code_editor_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render code editor demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = CodeEditorWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        13,
+        make_points(10, 10),
+        make_size(380, 280),
+    );
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(160, 160, 160));
+    widget1.set_tokenizer(Box::new(RustTokenizer));
+    widget1.set_text(include_str!("code_editor.rs"));
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
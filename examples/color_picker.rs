@@ -0,0 +1,49 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget_config::{CONFIG_COLOR_TEXT, CONFIG_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::color_picker_widget::ColorPickerWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render color picker demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = ColorPickerWidget::new(
+        make_points(20, 20),
+        make_size(240, 260),
+        Color::RGB(0x40, 0x80, 0xC0),
+    );
+    let widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("#4080C0"),
+        make_points(280, 20),
+        make_size(100, 30),
+    );
+
+    widget1.on_color_selected(|_x, _widgets, _layout, color| {
+        let text = format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b);
+
+        let mut label = _widgets[2].widget.borrow_mut();
+
+        label.set_text(CONFIG_TEXT, text);
+        label.set_color(CONFIG_COLOR_TEXT, color);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,70 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_TEXT, CONFIG_ROTATION};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::compass_widget::*;
+use pushrod::widgets::text_widget::*;
+use pushrod::widgets::timer_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render compass demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let widget1 = CompassWidget::new(make_points(20, 20), make_size(260, 260));
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Rotated"),
+        make_points(290, 135),
+        make_size(100, 30),
+    );
+
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut heading = 0.0_f64;
+    let mut timer = TimerWidget::new(30, true);
+    timer.on_timeout(move |_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+        heading += 1.5;
+        cast!(_widgets, widget1_id, CompassWidget).set_heading(heading);
+
+        // The label is turned by the `Engine` as it is copied to the screen.
+        let mut widget2 = _widgets[widget2_id].widget.borrow_mut();
+
+        widget2.set_numeric(CONFIG_ROTATION, -heading as i32);
+        widget2.set_invalidated(true);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,137 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::constraint_layout::{
+    Attribute, Constraint, ConstraintLayout, Relation, Strength, CONSTRAINT_LAYOUT,
+};
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points_origin, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+/// Creates a right-justified label for a row of the form.
+fn make_label(text: &str) -> TextWidget {
+    let mut widget = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Right,
+        String::from(text),
+        make_points_origin(),
+        make_size(0, 30),
+    );
+
+    widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget
+}
+
+/// Creates a bordered `BaseWidget` standing in for a field of the form.
+fn make_field() -> BaseWidget {
+    let mut widget = BaseWidget::new(make_points_origin(), make_size(0, 30));
+
+    widget
+        .get_config()
+        .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.get_config().set_numeric(CONFIG_BORDER_WIDTH, 1);
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render constraint layout demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout =
+        ConstraintLayout::new(0, 0, 400, 300, PaddingConstraint::new(20, 20, 20, 20, 0));
+    let widget1 = engine
+        .add_widget(Box::new(make_label("Name")), String::from("widget1"))
+        .id();
+    let widget2 = engine
+        .add_widget(Box::new(make_field()), String::from("widget2"))
+        .id();
+    let widget3 = engine
+        .add_widget(Box::new(make_label("Email")), String::from("widget3"))
+        .id();
+    let widget4 = engine
+        .add_widget(Box::new(make_field()), String::from("widget4"))
+        .id();
+    let mut button = PushButtonWidget::new(
+        make_points_origin(),
+        make_size(80, 30),
+        String::from("Save"),
+        16,
+    );
+
+    button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    button.set_numeric(CONFIG_BORDER_WIDTH, 1);
+
+    let widget5 = engine
+        .add_widget(Box::new(button), String::from("widget5"))
+        .id();
+
+    // The labels share a column at the left, each field starts 8 pixels after its label and runs
+    // to the right edge, and the rows are 10 pixels apart.
+    for (label, field) in [(widget1, widget2), (widget3, widget4)].iter() {
+        layout.add_constraint(
+            Constraint::new(*label, Attribute::Left, Relation::Equal)
+                .to(CONSTRAINT_LAYOUT, Attribute::Left),
+        );
+        layout.add_constraint(
+            Constraint::new(*label, Attribute::Width, Relation::Equal).constant(80.0),
+        );
+        layout.add_constraint(
+            Constraint::new(*field, Attribute::Left, Relation::Equal)
+                .to(*label, Attribute::Right)
+                .constant(8.0),
+        );
+        layout.add_constraint(
+            Constraint::new(*field, Attribute::Right, Relation::Equal)
+                .to(CONSTRAINT_LAYOUT, Attribute::Right),
+        );
+        layout.add_constraint(
+            Constraint::new(*field, Attribute::Top, Relation::Equal).to(*label, Attribute::Top),
+        );
+    }
+
+    layout.add_constraint(
+        Constraint::new(widget1, Attribute::Top, Relation::Equal)
+            .to(CONSTRAINT_LAYOUT, Attribute::Top),
+    );
+    layout.add_constraint(
+        Constraint::new(widget3, Attribute::Top, Relation::Equal)
+            .to(widget1, Attribute::Bottom)
+            .constant(10.0),
+    );
+
+    // The button sits in the bottom right corner, and would like to be a third of the width.
+    layout.add_constraint(
+        Constraint::new(widget5, Attribute::Right, Relation::Equal)
+            .to(CONSTRAINT_LAYOUT, Attribute::Right),
+    );
+    layout.add_constraint(
+        Constraint::new(widget5, Attribute::Bottom, Relation::Equal)
+            .to(CONSTRAINT_LAYOUT, Attribute::Bottom),
+    );
+    layout.add_constraint(
+        Constraint::new(widget5, Attribute::Width, Relation::Equal)
+            .to(CONSTRAINT_LAYOUT, Attribute::Width)
+            .multiplier(1.0 / 3.0)
+            .strength(Strength::Weak),
+    );
+    layout.add_constraint(
+        Constraint::new(widget5, Attribute::Width, Relation::LessOrEqual).constant(100.0),
+    );
+    engine.add_layout(Box::new(layout));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,82 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::CONFIG_COLOR_TEXT;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+use std::thread;
+use std::time::Duration;
+
+/// These are the application's own events, which are delivered along with mouse and keyboard
+/// events.
+enum AppEvent {
+    Countdown(u32),
+    Liftoff,
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render custom event demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        28,
+        TextJustify::Center,
+        String::from("Ready"),
+        make_points(20, 120),
+        make_size(360, 60),
+    );
+
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let widget1_id = engine
+        .add_widget(Box::new(widget1), String::from("widget1"))
+        .id();
+
+    engine
+        .register_event_type::<AppEvent>(&sdl_context)
+        .unwrap();
+
+    engine.on_custom_event(move |engine, event| {
+        let text = match event.downcast_ref::<AppEvent>() {
+            Some(AppEvent::Countdown(seconds)) => format!("{}", seconds),
+            Some(AppEvent::Liftoff) => String::from("Liftoff!"),
+            None => return,
+        };
+
+        engine
+            .get_widget_cache()
+            .get_container_by_id(widget1_id)
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<TextWidget>()
+            .unwrap()
+            .set_text(text);
+    });
+
+    // Custom events can be pushed to the SDL2 event queue from any thread.
+    let sender = sdl_context.event().unwrap().event_sender();
+
+    thread::spawn(move || {
+        for seconds in (1..=5).rev() {
+            thread::sleep(Duration::from_secs(1));
+            let _ = sender.push_custom_event(AppEvent::Countdown(seconds));
+        }
+
+        thread::sleep(Duration::from_secs(1));
+        let _ = sender.push_custom_event(AppEvent::Liftoff);
+    });
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,61 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::dock_layout::{DockLayout, DockRegion};
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+};
+use pushrod::render::{make_points_origin, make_size};
+use sdl2::pixels::Color;
+
+/// Creates a bordered `BaseWidget` with a preferred size of `w` by `h`, standing in for a part of
+/// an application shell.
+fn make_region(w: u32, h: u32, color: Color) -> BaseWidget {
+    let mut widget = BaseWidget::new(make_points_origin(), make_size(w, h));
+
+    widget.get_config().set_color(CONFIG_COLOR_BASE, color);
+    widget
+        .get_config()
+        .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.get_config().set_numeric(CONFIG_BORDER_WIDTH, 1);
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render dock layout demo", 400, 300)
+        .position_centered()
+        .resizable()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = DockLayout::new(0, 0, 400, 300, PaddingConstraint::new(4, 4, 4, 4, 4));
+    let regions = [
+        (0, 40, Color::RGB(200, 200, 255), DockRegion::North),
+        (0, 24, Color::RGB(220, 220, 220), DockRegion::South),
+        (100, 0, Color::RGB(200, 255, 200), DockRegion::West),
+        (0, 0, Color::RGB(255, 255, 255), DockRegion::Center),
+    ];
+
+    // A toolbar along the top, a status bar along the bottom, a sidebar at the left, and the
+    // content filling the rest.  The layout follows the window as it is resized.
+    for (i, (w, h, color, region)) in regions.iter().enumerate() {
+        let widget_id = engine
+            .add_widget(
+                Box::new(make_region(*w, *h, *color)),
+                format!("widget{}", i + 1),
+            )
+            .id();
+
+        layout.insert_widget_docked(widget_id, *region);
+    }
+
+    layout.set_track_window(true);
+    engine.add_layout(Box::new(layout));
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,85 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::drawing_pad_widget::*;
+use pushrod::widgets::push_button_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render drawing pad demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = DrawingPadWidget::new(make_points(20, 20), make_size(360, 210));
+    let mut widget2 = PushButtonWidget::new(
+        make_points(20, 245),
+        make_size(110, 32),
+        String::from("Undo"),
+        16,
+    );
+    let mut widget3 = PushButtonWidget::new(
+        make_points(145, 245),
+        make_size(110, 32),
+        String::from("Clear"),
+        16,
+    );
+    let mut widget4 = PushButtonWidget::new(
+        make_points(270, 245),
+        make_size(110, 32),
+        String::from("Accept"),
+        16,
+    );
+
+    widget1.set_pen_width(3);
+    widget1.set_pen_color(Color::RGB(0, 0, 128));
+
+    widget2.on_click(|_button, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+
+        cast!(_widgets, widget1_id, DrawingPadWidget).undo_stroke();
+    });
+
+    widget3.on_click(|_button, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+
+        cast!(_widgets, widget1_id, DrawingPadWidget).clear();
+    });
+
+    widget4.on_click(|_button, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        let strokes = cast!(_widgets, widget1_id, DrawingPadWidget).export_strokes();
+
+        eprintln!(
+            "Accepted {} strokes with {} points",
+            strokes.len(),
+            strokes.iter().map(|stroke| stroke.len()).sum::<usize>()
+        );
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(widget3), String::from("widget3"));
+    engine.add_widget(Box::new(widget4), String::from("widget4"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,50 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_HOVER, CONFIG_COLOR_SELECTED,
+    CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::dropdown_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render dropdown demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = DropdownWidget::new(make_points(20, 20), make_size(200, 30));
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_HOVER, Color::RGB(0x90, 0x90, 0xFF));
+    widget1.set_color(CONFIG_COLOR_SELECTED, Color::RGB(0xD0, 0xD0, 0xD0));
+
+    widget1.set_items(
+        (1..=12)
+            .map(|item| format!("Choice {}", item))
+            .collect::<Vec<String>>(),
+    );
+    widget1.set_selected_index(0);
+
+    widget1.on_selection_changed(|x, _widgets, _layout, selected_item| {
+        eprintln!(
+            "Selected: {} ({})",
+            selected_item,
+            x.get_selected_item().unwrap_or("")
+        );
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
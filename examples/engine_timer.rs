@@ -0,0 +1,76 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::CONFIG_COLOR_TEXT;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+use std::time::Duration;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render engine timer demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        20,
+        TextJustify::Center,
+        String::from("Elapsed: 0 seconds"),
+        make_points(20, 100),
+        make_size(360, 40),
+    );
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Center,
+        String::from("Waiting for 10 seconds"),
+        make_points(20, 160),
+        make_size(360, 30),
+    );
+
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    // The first timer repeats every second, and the second fires once, after 10 seconds.
+    let mut seconds = 0;
+    engine.add_timer(Duration::from_secs(1), true, move |_widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+
+        seconds += 1;
+        cast!(_widgets, widget1_id, TextWidget).set_text(format!("Elapsed: {} seconds", seconds));
+    });
+
+    engine.add_timer(Duration::from_secs(10), false, |_widgets, _layouts| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(String::from("10 seconds have passed"));
+    });
+
+    engine.run(sdl_context, window);
+}
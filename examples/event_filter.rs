@@ -0,0 +1,64 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::event_filter::FilterResult;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::CONFIG_COLOR_TEXT;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_widget::*;
+use sdl2::event::Event;
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render event filter demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Ctrl-Q quits; F11 and F12 are disabled"),
+        make_points(20, 130),
+        make_size(360, 40),
+    );
+
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    // Ctrl-Q is turned into a request to quit, and the keys that open the Inspector and design mode
+    // are swallowed, as a kiosk application would.
+    engine.set_event_filter(|event| match event {
+        Event::KeyDown {
+            keycode: Some(Keycode::Q),
+            keymod,
+            timestamp,
+            ..
+        } if keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) => {
+            FilterResult::Replace(Event::Quit {
+                timestamp: *timestamp,
+            })
+        }
+        Event::KeyDown {
+            keycode: Some(Keycode::F11),
+            ..
+        }
+        | Event::KeyDown {
+            keycode: Some(Keycode::F12),
+            ..
+        } => FilterResult::Swallow,
+        _ => FilterResult::Pass,
+    });
+
+    engine.on_exit(|_| true);
+
+    engine.run(sdl_context, window);
+}
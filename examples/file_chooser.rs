@@ -0,0 +1,49 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_BORDER_WIDTH, CONFIG_COLOR_BORDER};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::file_chooser_dialog::{FileChooserDialog, FileChooserMode, FileFilter};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render file chooser demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut button = PushButtonWidget::new(
+        make_points(20, 20),
+        make_size(200, 40),
+        String::from("Background"),
+        20,
+    );
+
+    button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+
+    engine.add_widget(Box::new(button), String::from("button"));
+
+    let mut chooser = FileChooserDialog::new(FileChooserMode::Open, ".");
+
+    chooser.add_filter(FileFilter::new("Rust Sources", &["rs"]));
+    chooser.add_filter(FileFilter::new("Images", &["png", "jpg"]));
+    chooser.add_filter(FileFilter::new("All Files", &[]));
+    chooser.on_file_chosen(|_widgets, _layouts, path| {
+        eprintln!("File chosen: {}", path.display());
+    });
+
+    engine.on_dialog_closed(|_engine, _dialog, result| {
+        eprintln!("File chooser closed with result {}", result);
+    });
+
+    chooser.show(&mut engine, make_points(10, 10), make_size(380, 280));
+    engine.run(sdl_context, window);
+}
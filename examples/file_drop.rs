@@ -0,0 +1,75 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{CONFIG_COLOR_BASE, CONFIG_COLOR_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render file drop demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = BaseWidget::new(make_points(20, 20), make_size(360, 200));
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Center,
+        String::from("Drop a file or text on the box"),
+        make_points(20, 240),
+        make_size(360, 30),
+    );
+
+    widget1
+        .get_config()
+        .set_color(CONFIG_COLOR_BASE, Color::RGB(224, 232, 240));
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    widget1
+        .get_callbacks()
+        .on_file_dropped(|_widget, _widgets, _layouts, path| {
+            let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+            cast!(_widgets, widget2_id, TextWidget).set_text(format!("File: {}", path));
+        });
+
+    widget1
+        .get_callbacks()
+        .on_text_dropped(|_widget, _widgets, _layouts, text| {
+            let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+            cast!(_widgets, widget2_id, TextWidget).set_text(format!("Text: {}", text));
+        });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    // Files dropped outside of the box are handled by the Engine.
+    engine.on_file_dropped(|_engine, path| {
+        eprintln!("Dropped outside of the box: {}", path);
+    });
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,60 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::flow_layout::FlowLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::Layout;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BORDER};
+use pushrod::render::{make_points_origin, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render flow layout demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = FlowLayout::new(20, 20, 360, 260, PaddingConstraint::new(4, 4, 4, 4, 8));
+    let tags = [
+        "Rust",
+        "SDL2",
+        "Widgets",
+        "Layouts",
+        "Events",
+        "Themes",
+        "Callbacks",
+        "Textures",
+        "Fonts",
+        "Buttons",
+        "Sliders",
+        "Menus",
+    ];
+
+    // Each button is sized to its label, and the layout wraps them into rows.
+    for (i, tag) in tags.iter().enumerate() {
+        let mut button = PushButtonWidget::new(
+            make_points_origin(),
+            make_size(tag.len() as u32 * 10 + 20, 30),
+            String::from(*tag),
+            16,
+        );
+
+        button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+        button.set_numeric(CONFIG_BORDER_WIDTH, 1);
+
+        let widget_id = engine
+            .add_widget(Box::new(button), format!("widget{}", i + 1))
+            .id();
+
+        layout.append_widget(widget_id);
+    }
+
+    engine.add_layout(Box::new(layout));
+    engine.run(sdl_context, window);
+}
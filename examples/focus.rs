@@ -0,0 +1,72 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_TEXT, CONFIG_TAB_ORDER};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::search_field_widget::SearchFieldWidget;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render focus demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget4 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Press Tab or Shift-Tab to move the focus"),
+        make_points(20, 240),
+        make_size(360, 30),
+    );
+
+    widget4.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    // The fields are visited from the bottom up, as set by their tab order.
+    for i in 1..=3 {
+        let mut widget = SearchFieldWidget::new(
+            String::from("assets/OpenSans-Regular.ttf"),
+            16,
+            make_points(20, 20 + (i - 1) * 60),
+            make_size(360, 40),
+        );
+
+        widget.set_numeric(CONFIG_TAB_ORDER, 4 - i);
+
+        widget
+            .get_callbacks()
+            .on_focus_gained(move |_widget, _widgets, _layouts| {
+                let widget4_id = widget_id_for_name(_widgets, String::from("widget4"));
+
+                cast!(_widgets, widget4_id, TextWidget).set_text(format!("Field {} has focus", i));
+            });
+
+        engine.add_widget(Box::new(widget), format!("widget{}", i));
+    }
+
+    engine.add_widget(Box::new(widget4), String::from("widget4"));
+
+    engine.run(sdl_context, window);
+}
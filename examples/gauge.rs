@@ -0,0 +1,51 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::gauge_widget::*;
+use pushrod::widgets::timer_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render gauge demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = GaugeWidget::new(make_points(50, 60), make_size(300, 180), 0.0, 100.0, 20.0);
+
+    widget1.add_zone(0.0, 60.0, Color::RGB(76, 200, 100));
+    widget1.add_zone(60.0, 85.0, Color::RGB(240, 200, 60));
+    widget1.add_zone(85.0, 100.0, Color::RGB(220, 60, 60));
+
+    let mut timer = TimerWidget::new(1000, true);
+    timer.on_timeout(|_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        let value = (cast!(_widgets, widget1_id, GaugeWidget).get_value() + 37.0) % 100.0;
+
+        cast!(_widgets, widget1_id, GaugeWidget).set_value(value);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
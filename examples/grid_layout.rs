@@ -0,0 +1,86 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::grid_layout::GridLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::{Layout, LayoutPosition};
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+};
+use pushrod::render::{make_points_origin, make_size};
+use sdl2::pixels::Color;
+
+/// Creates a bordered `BaseWidget` to be placed by the `GridLayout`.
+fn make_cell(color: Color) -> BaseWidget {
+    let mut widget = BaseWidget::new(make_points_origin(), make_size(0, 0));
+
+    widget.get_config().set_color(CONFIG_COLOR_BASE, color);
+    widget
+        .get_config()
+        .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.get_config().set_numeric(CONFIG_BORDER_WIDTH, 2);
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render grid layout demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = GridLayout::new(
+        20,
+        20,
+        360,
+        260,
+        vec![3, 3],
+        PaddingConstraint::new(4, 4, 4, 4, 6),
+    );
+    let widget1_id = engine
+        .add_widget(
+            Box::new(make_cell(Color::RGB(255, 200, 200))),
+            String::from("widget1"),
+        )
+        .id();
+    let widget2_id = engine
+        .add_widget(
+            Box::new(make_cell(Color::RGB(200, 255, 200))),
+            String::from("widget2"),
+        )
+        .id();
+    let widget3_id = engine
+        .add_widget(
+            Box::new(make_cell(Color::RGB(200, 200, 255))),
+            String::from("widget3"),
+        )
+        .id();
+    let widget4_id = engine
+        .add_widget(
+            Box::new(make_cell(Color::RGB(255, 255, 200))),
+            String::from("widget4"),
+        )
+        .id();
+    let widget5_id = engine
+        .add_widget(
+            Box::new(make_cell(Color::RGB(255, 200, 255))),
+            String::from("widget5"),
+        )
+        .id();
+
+    // The first widget spans the top two columns, and the second the right two rows.
+    layout.insert_widget_spanning(widget1_id, LayoutPosition::new(0, 0), 2, 1);
+    layout.insert_widget_spanning(widget2_id, LayoutPosition::new(2, 0), 1, 2);
+
+    // The rest fill the free cells in order.
+    layout.append_widget(widget3_id);
+    layout.append_widget(widget4_id);
+    layout.append_widget(widget5_id);
+    engine.add_layout(Box::new(layout));
+
+    engine.run(sdl_context, window);
+}
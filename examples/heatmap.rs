@@ -0,0 +1,68 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::heatmap_widget::*;
+use pushrod::widgets::text_widget::*;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render heatmap demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let values: Vec<Vec<f64>> = (0..16)
+        .map(|row| {
+            (0..24)
+                .map(|col| ((row as f64 / 3.0).sin() + (col as f64 / 4.0).cos()) * 50.0)
+                .collect()
+        })
+        .collect();
+    let mut widget1 = HeatmapWidget::new(make_points(20, 20), make_size(360, 220), values);
+    let widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Center,
+        String::from("Click a cell to inspect it."),
+        make_points(20, 255),
+        make_size(360, 30),
+    );
+
+    widget1.set_grid_lines(true);
+
+    widget1.on_cell_clicked(|heatmap, _widgets, _layouts, row, col| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+        let text = format!(
+            "Row {}, column {}: {:.2}",
+            row,
+            col,
+            heatmap.get_value(row, col).unwrap_or(0.0)
+        );
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(text);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,62 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_TEXT, CONFIG_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::hyperlink_label_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render hyperlink label demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = HyperlinkLabelWidget::new(
+        make_points(20, 40),
+        make_size(360, 30),
+        String::from("Show this link's URL below"),
+        String::from("https://www.github.com/KenSuenobu/rust-pushrod"),
+    );
+
+    widget1.on_link_clicked(|_, _widgets, _layouts, url| {
+        _widgets[3]
+            .widget
+            .borrow_mut()
+            .set_text(CONFIG_TEXT, format!("Clicked {}", url));
+    });
+
+    let mut widget2 = HyperlinkLabelWidget::new(
+        make_points(20, 90),
+        make_size(360, 30),
+        String::from("Open the Pushrod project page"),
+        String::from("https://www.github.com/KenSuenobu/rust-pushrod"),
+    );
+
+    widget2.set_open_in_browser(true);
+
+    let mut text_widget = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Left,
+        String::from(""),
+        make_points(20, 240),
+        make_size(360, 30),
+    );
+
+    text_widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(text_widget), String::from("text1"));
+
+    engine.run(sdl_context, window);
+}
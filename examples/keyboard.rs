@@ -0,0 +1,87 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{CONFIG_COLOR_BASE, CONFIG_COLOR_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render keyboard demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = BaseWidget::new(make_points(20, 20), make_size(360, 200));
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Press a key"),
+        make_points(20, 240),
+        make_size(360, 30),
+    );
+
+    widget1
+        .get_config()
+        .set_color(CONFIG_COLOR_BASE, Color::RGB(224, 232, 240));
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    widget1.get_callbacks().on_key_pressed(
+        move |_widget, _widgets, _layouts, keycode, keymod, repeat| {
+            let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+            let text = format!(
+                "Pressed: {}{}{}",
+                keycode.name(),
+                if keymod.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({:?})", keymod)
+                },
+                if repeat { " (repeat)" } else { "" }
+            );
+
+            cast!(_widgets, widget2_id, TextWidget).set_text(text);
+        },
+    );
+
+    widget1.get_callbacks().on_key_released(
+        move |_widget, _widgets, _layouts, keycode, _keymod, _repeat| {
+            let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+            cast!(_widgets, widget2_id, TextWidget)
+                .set_text(format!("Released: {}", keycode.name()));
+        },
+    );
+
+    let widget1_id = engine
+        .add_widget(Box::new(widget1), String::from("widget1"))
+        .id();
+
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    // Keyboard events are sent to the focused Widget.
+    engine.set_focus(widget1_id);
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,69 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::keypad_widget::*;
+use pushrod::widgets::text_widget::*;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render keypad demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        28,
+        TextJustify::Right,
+        String::new(),
+        make_points(100, 10),
+        make_size(200, 40),
+    );
+    let mut widget2 = KeypadWidget::new(make_points(100, 60), make_size(200, 200));
+    let widget3 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Center,
+        String::from("Enter an amount, then press Enter."),
+        make_points(20, 266),
+        make_size(360, 24),
+    );
+
+    widget2.set_target("widget1");
+    widget2.set_max_length(8);
+
+    widget2.on_value_committed(|keypad, _widgets, _layouts, value| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        let widget3_id = widget_id_for_name(_widgets, String::from("widget3"));
+
+        cast!(_widgets, widget3_id, TextWidget).set_text(format!("Charged: ${}", value));
+        cast!(_widgets, widget1_id, TextWidget).set_text(String::new());
+        keypad.clear();
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(widget3), String::from("widget3"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,39 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::CONFIG_COLOR_SELECTED;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::knob_widget::{KnobDragMode, KnobWidget};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render knob demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = KnobWidget::new(make_points(40, 80), make_size(120, 120), 0, 100, 25);
+    let mut widget2 = KnobWidget::new(make_points(240, 80), make_size(120, 120), -50, 50, 0);
+
+    widget1.on_value_changed(|_x, _widgets, _layout, value| {
+        eprintln!("Volume: {}", value);
+    });
+
+    widget2.set_drag_mode(KnobDragMode::Circular);
+    widget2.set_step(5);
+    widget2.set_color(CONFIG_COLOR_SELECTED, Color::RGB(0xC0, 0x40, 0x40));
+    widget2.on_value_changed(|_x, _widgets, _layout, value| {
+        eprintln!("Pan: {}", value);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
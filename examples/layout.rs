@@ -71,10 +71,18 @@ pub fn main() {
         .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
     widget4.get_config().set_numeric(CONFIG_BORDER_WIDTH, 2);
 
-    let widget1_id = engine.add_widget(Box::new(widget1), String::from("widget1"));
-    let widget2_id = engine.add_widget(Box::new(widget2), String::from("widget2"));
-    let widget3_id = engine.add_widget(Box::new(widget3), String::from("widget3"));
-    let widget4_id = engine.add_widget(Box::new(widget4), String::from("widget4"));
+    let widget1_id = engine
+        .add_widget(Box::new(widget1), String::from("widget1"))
+        .id();
+    let widget2_id = engine
+        .add_widget(Box::new(widget2), String::from("widget2"))
+        .id();
+    let widget3_id = engine
+        .add_widget(Box::new(widget3), String::from("widget3"))
+        .id();
+    let widget4_id = engine
+        .add_widget(Box::new(widget4), String::from("widget4"))
+        .id();
 
     layout.append_widget(widget1_id);
     layout.append_widget(widget2_id);
@@ -0,0 +1,102 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::layout_file::LayoutFormat;
+
+/// The widgets and layouts of the screen: a toolbar row with a fixed button, a strut, and a
+/// stretching field, above a grid with a cell spanning two columns.
+const LAYOUT: &str = r#"(
+    widgets: [
+        (
+            name: "widget1",
+            widget_type: "base",
+            config: {
+                "CONFIG_COLOR_BASE": Color(255, 200, 200, 255),
+                "CONFIG_COLOR_BORDER": Color(0, 0, 0, 255),
+                "CONFIG_BORDER_WIDTH": Numeric(1),
+            },
+        ),
+        (
+            name: "widget2",
+            widget_type: "base",
+            config: {
+                "CONFIG_COLOR_BASE": Color(255, 255, 255, 255),
+                "CONFIG_COLOR_BORDER": Color(0, 0, 0, 255),
+                "CONFIG_BORDER_WIDTH": Numeric(1),
+            },
+        ),
+        (
+            name: "widget3",
+            widget_type: "base",
+            config: {
+                "CONFIG_COLOR_BASE": Color(200, 200, 255, 255),
+                "CONFIG_COLOR_BORDER": Color(0, 0, 0, 255),
+                "CONFIG_BORDER_WIDTH": Numeric(1),
+            },
+        ),
+        (
+            name: "widget4",
+            widget_type: "base",
+            config: {
+                "CONFIG_COLOR_BASE": Color(200, 255, 200, 255),
+                "CONFIG_COLOR_BORDER": Color(0, 0, 0, 255),
+                "CONFIG_BORDER_WIDTH": Numeric(1),
+            },
+        ),
+        (
+            name: "widget5",
+            widget_type: "base",
+            config: {
+                "CONFIG_COLOR_BASE": Color(255, 255, 200, 255),
+                "CONFIG_COLOR_BORDER": Color(0, 0, 0, 255),
+                "CONFIG_BORDER_WIDTH": Numeric(1),
+            },
+        ),
+    ],
+    layouts: [
+        (
+            id: 0,
+            layout_type: "horizontal",
+            bounds: (0, 0, 400, 50),
+            padding: (top: 5, bottom: 5, left: 5, right: 5, spacing: 5),
+            items: [
+                (widget: "widget1", position: (0, 0), size: Fixed(80)),
+                (position: (0, 1), size: Fixed(20)),
+                (widget: "widget2", position: (0, 2)),
+            ],
+        ),
+        (
+            id: 1,
+            layout_type: "grid",
+            bounds: (0, 50, 400, 250),
+            grid: (2, 2),
+            padding: (top: 5, bottom: 5, left: 5, right: 5, spacing: 5),
+            items: [
+                (widget: "widget3", position: (0, 0), span: (2, 1)),
+                (widget: "widget4", position: (0, 1)),
+                (widget: "widget5", position: (1, 1)),
+            ],
+        ),
+    ],
+)
+"#;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render layout loader demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    // The widgets are created from the text, along with the layouts that place them.
+    engine.import_layout(LAYOUT, LayoutFormat::Ron).unwrap();
+
+    // The exported layout includes the widgets' types, and the layouts' types, bounds and items.
+    println!("{}", engine.export_layout(LayoutFormat::Ron).unwrap());
+
+    engine.run(sdl_context, window);
+}
@@ -28,16 +28,18 @@ pub fn main() {
     widget1.set_color(CONFIG_COLOR_HOVER, Color::RGB(0x90, 0x90, 0xFF));
     widget1.set_numeric(CONFIG_BORDER_WIDTH, 1);
 
-    widget1.add_item(String::from("Item 1"));
-    widget1.add_item(String::from("Item 2"));
-    widget1.add_item(String::from("Item 3"));
-    widget1.add_item(String::from("Item 4"));
-    widget1.add_item(String::from("Item 5"));
+    for i in 1..=20 {
+        widget1.add_item(format!("Item {}", i));
+    }
 
     widget1.on_selected(|x, _widgets, _layout, selected_item| {
         eprintln!("Selected: {}", selected_item);
     });
 
+    widget1.on_item_selected(|x, _widgets, _layout, item| {
+        eprintln!("Item selected: {}", x.get_items()[item]);
+    });
+
     engine.add_widget(Box::new(widget1), String::from("widget1"));
 
     engine.run(sdl_context, window);
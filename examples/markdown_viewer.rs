@@ -0,0 +1,63 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_BORDER, CONFIG_COLOR_SECONDARY};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::markdown_viewer_widget::*;
+use sdl2::pixels::Color;
+
+const HELP_TEXT: &str = "# Pushrod Help
+
+This is a **MarkdownViewerWidget**, showing a *help screen* written in Markdown.  Scroll with
+the mouse wheel to read the rest of it.
+
+## Features
+
+- Headings, **bold** and *italic* text
+- Inline `code` and fenced code blocks
+- Bulleted and numbered lists
+  - including nested ones
+- Links, like [the Pushrod repository](https://github.com/KenSuenobu/rust-pushrod)
+
+## Getting Started
+
+1. Create an `Engine`.
+2. Add your widgets to it.
+3. Call `run` with the SDL context and window.
+
+```
+let mut engine = Engine::new(400, 300, 60);
+
+engine.run(sdl_context, window);
+```
+
+---
+
+Clicking the link above prints its URL, and opens it in your browser.
+";
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render markdown viewer demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = MarkdownViewerWidget::new(make_points(0, 0), make_size(400, 300), HELP_TEXT);
+
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0, 102, 204));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(192, 192, 192));
+    widget1.set_open_in_browser(true);
+    widget1.on_link_clicked(|_, _widgets, _layouts, url| {
+        eprintln!("Link clicked: {}", url);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,85 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_HOVER, CONFIG_COLOR_SECONDARY,
+    CONFIG_COLOR_SELECTED, CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::menu_bar_widget::*;
+use sdl2::pixels::Color;
+
+const MENU_NEW: i32 = 1;
+const MENU_OPEN: i32 = 2;
+const MENU_RECENT_1: i32 = 3;
+const MENU_RECENT_2: i32 = 4;
+const MENU_SAVE: i32 = 5;
+const MENU_QUIT: i32 = 6;
+const MENU_UNDO: i32 = 7;
+const MENU_CUT: i32 = 8;
+const MENU_COPY: i32 = 9;
+const MENU_PASTE: i32 = 10;
+const MENU_ABOUT: i32 = 11;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render menu bar demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = MenuBarWidget::new(make_points(0, 0), make_size(400, 28));
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(0xF0, 0xF0, 0xF0));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0x80, 0x80, 0x80));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0xA0, 0xA0, 0xA0));
+    widget1.set_color(CONFIG_COLOR_HOVER, Color::RGB(0xE0, 0xE0, 0xFF));
+    widget1.set_color(CONFIG_COLOR_SELECTED, Color::RGB(0xC0, 0xC0, 0xFF));
+
+    widget1.add_menu(
+        "File",
+        vec![
+            MenuItem::item("New", MENU_NEW),
+            MenuItem::item("Open...", MENU_OPEN),
+            MenuItem::submenu(
+                "Open Recent",
+                vec![
+                    MenuItem::item("notes.txt", MENU_RECENT_1),
+                    MenuItem::item("todo.txt", MENU_RECENT_2),
+                ],
+            ),
+            MenuItem::disabled("Save", MENU_SAVE),
+            MenuItem::separator(),
+            MenuItem::item("Quit", MENU_QUIT),
+        ],
+    );
+    widget1.add_menu(
+        "Edit",
+        vec![
+            MenuItem::item("Undo", MENU_UNDO),
+            MenuItem::separator(),
+            MenuItem::item("Cut", MENU_CUT),
+            MenuItem::item("Copy", MENU_COPY),
+            MenuItem::item("Paste", MENU_PASTE),
+        ],
+    );
+    widget1.add_menu("Help", vec![MenuItem::item("About", MENU_ABOUT)]);
+
+    widget1.on_menu_item_selected(|x, _widgets, _layout, menu_id| {
+        eprintln!("Selected menu ID: {}", menu_id);
+
+        if menu_id == MENU_NEW {
+            x.set_item_enabled(MENU_SAVE, true);
+        }
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
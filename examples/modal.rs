@@ -0,0 +1,105 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::modal::close_dialog;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render modal demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut background_button = PushButtonWidget::new(
+        make_points(20, 20),
+        make_size(200, 40),
+        String::from("Blocked"),
+        20,
+    );
+
+    background_button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    background_button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    background_button.on_click(|_x, _widgets, _layouts| {
+        eprintln!("Background button clicked");
+    });
+
+    engine.add_widget(
+        Box::new(background_button),
+        String::from("background_button"),
+    );
+
+    let mut dialog = BaseWidget::new(make_points(60, 70), make_size(280, 160));
+
+    dialog.set_color(CONFIG_COLOR_BASE, Color::RGB(240, 240, 240));
+    dialog.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    dialog.set_numeric(CONFIG_BORDER_WIDTH, 2);
+
+    let dialog_handle = engine.add_widget(Box::new(dialog), String::from("dialog"));
+    let dialog_id = dialog_handle.id();
+
+    let mut message = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Continue?"),
+        make_points(80, 90),
+        make_size(240, 30),
+    );
+
+    message.set_color(CONFIG_COLOR_BASE, Color::RGB(240, 240, 240));
+    message.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut ok_button = PushButtonWidget::new(
+        make_points(80, 170),
+        make_size(110, 40),
+        String::from("OK"),
+        20,
+    );
+
+    ok_button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    ok_button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    ok_button.on_click(move |_x, widgets, _layouts| {
+        close_dialog(widgets, dialog_id, 1);
+    });
+
+    let mut cancel_button = PushButtonWidget::new(
+        make_points(210, 170),
+        make_size(110, 40),
+        String::from("Cancel"),
+        20,
+    );
+
+    cancel_button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    cancel_button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    cancel_button.on_click(move |_x, widgets, _layouts| {
+        close_dialog(widgets, dialog_id, 0);
+    });
+
+    let message_handle = engine.add_widget(Box::new(message), String::from("message"));
+    let ok_handle = engine.add_widget(Box::new(ok_button), String::from("ok_button"));
+    let cancel_handle = engine.add_widget(Box::new(cancel_button), String::from("cancel_button"));
+
+    engine.set_parent(message_handle, dialog_handle).unwrap();
+    engine.set_parent(ok_handle, dialog_handle).unwrap();
+    engine.set_parent(cancel_handle, dialog_handle).unwrap();
+
+    engine.on_dialog_closed(|_engine, _dialog, result| {
+        eprintln!("Dialog closed with result {}", result);
+    });
+
+    engine.show_modal(dialog_handle).unwrap();
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,93 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_INVALID, CONFIG_COLOR_SECONDARY,
+    CONFIG_COLOR_TEXT, CONFIG_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::numeric_input_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+/// Creates a `NumericInputWidget` that reports its committed value to the `TextWidget` named
+/// `status_name`.
+fn make_field(y: i32, status_name: &'static str) -> NumericInputWidget {
+    let mut widget = NumericInputWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        16,
+        make_points(20, y),
+        make_size(200, 30),
+    );
+
+    widget.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(128, 128, 128));
+    widget.set_color(CONFIG_COLOR_INVALID, Color::RGB(220, 0, 0));
+    widget.on_value_committed(move |_, _widgets, _layouts, value| {
+        let status_id = widget_id_for_name(_widgets, String::from(status_name));
+
+        _widgets[status_id]
+            .widget
+            .borrow_mut()
+            .set_text(CONFIG_TEXT, format!("Committed {}", value));
+    });
+
+    widget
+}
+
+/// Creates a `TextWidget` showing the committed value of a field.
+fn make_status(y: i32) -> TextWidget {
+    let mut widget = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Left,
+        String::new(),
+        make_points(230, y),
+        make_size(150, 30),
+    );
+
+    widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render numeric input demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = make_field(20, "widget2");
+    let mut widget3 = make_field(70, "widget4");
+    let mut widget5 = make_field(120, "widget6");
+
+    widget1.set_range(Some(0.0), Some(150.0));
+    widget1.set_decimal_places(1);
+    widget1.set_unit("kg");
+    widget1.set_value(72.5);
+
+    widget3.set_range(Some(-40.0), Some(50.0));
+    widget3.set_unit("°C");
+
+    widget5.set_range(Some(0.0), Some(100.0));
+    widget5.set_decimal_places(2);
+    widget5.set_unit("%");
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(make_status(20)), String::from("widget2"));
+    engine.add_widget(Box::new(widget3), String::from("widget3"));
+    engine.add_widget(Box::new(make_status(70)), String::from("widget4"));
+    engine.add_widget(Box::new(widget5), String::from("widget5"));
+    engine.add_widget(Box::new(make_status(120)), String::from("widget6"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,35 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::on_screen_keyboard_widget::*;
+use pushrod::widgets::text_area_widget::*;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render on-screen keyboard demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let widget1 = TextAreaWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        16,
+        make_points(10, 10),
+        make_size(380, 100),
+    );
+    let widget2 = OnScreenKeyboardWidget::new(
+        make_points(10, 120),
+        make_size(380, 170),
+        KeyboardLayout::Qwerty,
+    );
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
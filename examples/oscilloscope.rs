@@ -0,0 +1,61 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::oscilloscope_widget::*;
+use pushrod::widgets::timer_widget::*;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render oscilloscope demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = OscilloscopeWidget::new(make_points(20, 20), make_size(360, 120), 360);
+    let mut widget2 = OscilloscopeWidget::new(make_points(20, 160), make_size(360, 120), 2880);
+
+    widget1.set_amplitude_scale(0.8);
+    widget2.set_time_scale(8.0);
+    widget2.set_amplitude_scale(0.8);
+    widget2.set_persistence(0.85);
+
+    let mut phase = 0.0_f32;
+    let mut timer = TimerWidget::new(30, true);
+    timer.on_timeout(move |_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+        let samples: Vec<f32> = (0..64)
+            .map(|_| {
+                phase += 0.11;
+                phase.sin() * 0.7 + (phase * 3.7).sin() * 0.3
+            })
+            .collect();
+
+        cast!(_widgets, widget1_id, OscilloscopeWidget).push_samples(&samples);
+        cast!(_widgets, widget2_id, OscilloscopeWidget).push_samples(&samples);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,59 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT, CONFIG_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::password_field_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render password field demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = PasswordFieldWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        16,
+        make_points(20, 40),
+        make_size(360, 30),
+    );
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.on_value_changed(|x, _widgets, _layouts| {
+        let length = x.get_value().chars().count();
+
+        _widgets[2]
+            .widget
+            .borrow_mut()
+            .set_text(CONFIG_TEXT, format!("{} characters entered", length));
+    });
+
+    let mut text_widget = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("Click the field and type a password"),
+        make_points(20, 90),
+        make_size(360, 30),
+    );
+
+    text_widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(text_widget), String::from("text1"));
+
+    engine.run(sdl_context, window);
+}
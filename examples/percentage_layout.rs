@@ -0,0 +1,66 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::horizontal_layout::HorizontalLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::LayoutPosition;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+};
+use pushrod::render::{make_points_origin, make_size};
+use sdl2::pixels::Color;
+
+/// Creates a bordered `BaseWidget` to be sized by the layout.
+fn make_box(color: Color) -> BaseWidget {
+    let mut widget = BaseWidget::new(make_points_origin(), make_size(0, 0));
+
+    widget.get_config().set_color(CONFIG_COLOR_BASE, color);
+    widget
+        .get_config()
+        .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.get_config().set_numeric(CONFIG_BORDER_WIDTH, 1);
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render percentage layout demo", 400, 300)
+        .position_centered()
+        .resizable()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = HorizontalLayout::new(0, 0, 400, 300, PaddingConstraint::new(8, 8, 8, 8, 8));
+    let widget1_id = engine
+        .add_widget(
+            Box::new(make_box(Color::RGB(200, 255, 200))),
+            String::from("widget1"),
+        )
+        .id();
+    let widget2_id = engine
+        .add_widget(
+            Box::new(make_box(Color::RGB(255, 255, 255))),
+            String::from("widget2"),
+        )
+        .id();
+    let widget3_id = engine
+        .add_widget(
+            Box::new(make_box(Color::RGB(200, 200, 255))),
+            String::from("widget3"),
+        )
+        .id();
+
+    // The sidebars keep to 30% and 20% of the width as the window is resized, and the middle
+    // takes the rest.
+    layout.add_widget_with_percentage(widget1_id, LayoutPosition::new(0, 0), 30.0);
+    layout.add_widget_with_weight(widget2_id, LayoutPosition::new(0, 1), 1);
+    layout.add_widget_with_percentage(widget3_id, LayoutPosition::new(0, 2), 20.0);
+    layout.set_track_window(true);
+
+    engine.add_layout(Box::new(layout));
+    engine.run(sdl_context, window);
+}
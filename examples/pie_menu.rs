@@ -0,0 +1,93 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::CONFIG_COLOR_BASE;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::pie_menu_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render pie menu demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mouse_position = Rc::new(RefCell::new(vec![0, 0]));
+    let moved_position = mouse_position.clone();
+    let mut widget1 = BaseWidget::new(make_points(0, 0), make_size(400, 300));
+    let widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Center,
+        String::from("Press the mouse button anywhere to open the menu."),
+        make_points(20, 260),
+        make_size(360, 30),
+    );
+    let mut widget3 = PieMenuWidget::new(
+        80,
+        vec![
+            String::from("Move"),
+            String::from("Rotate"),
+            String::from("Scale"),
+            String::from("Copy"),
+            String::from("Delete"),
+            String::from("Hide"),
+        ],
+    );
+
+    widget1
+        .get_config()
+        .set_color(CONFIG_COLOR_BASE, Color::RGB(224, 232, 240));
+
+    widget1
+        .get_callbacks()
+        .on_mouse_moved(move |_widget, _widgets, _layouts, points| {
+            *moved_position.borrow_mut() = points;
+        });
+
+    widget1.get_callbacks().on_mouse_clicked(
+        move |_widget, _widgets, _layouts, button, _clicks, state| {
+            if button == 1 && state {
+                let widget3_id = widget_id_for_name(_widgets, String::from("widget3"));
+
+                cast!(_widgets, widget3_id, PieMenuWidget).open_at(mouse_position.borrow().clone());
+            }
+        },
+    );
+
+    widget3.on_option_selected(|menu, _widgets, _layouts, index| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+        let text = format!("Chose: {}", menu.get_options()[index]);
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(text);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(widget3), String::from("widget3"));
+
+    engine.run(sdl_context, window);
+}
@@ -12,12 +12,12 @@ pub fn main() {
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
     let window = video_subsystem
-        .window("pushrod-render progress demo", 400, 180)
+        .window("pushrod-render progress demo", 400, 230)
         .position_centered()
         .opengl()
         .build()
         .unwrap();
-    let mut engine = Engine::new(400, 180, 60);
+    let mut engine = Engine::new(400, 230, 60);
     let mut widget1 = ProgressWidget::new(make_points(20, 20), make_size(360, 40), 25);
 
     widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
@@ -30,9 +30,15 @@ pub fn main() {
 
     widget3.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
 
+    let mut widget4 = ProgressWidget::new(make_points(20, 170), make_size(360, 40), 0);
+
+    widget4.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
+    widget4.set_indeterminate(true);
+
     engine.add_widget(Box::new(widget1), String::from("widget1"));
     engine.add_widget(Box::new(widget2), String::from("widget2"));
     engine.add_widget(Box::new(widget3), String::from("widget3"));
+    engine.add_widget(Box::new(widget4), String::from("widget4"));
 
     engine.run(sdl_context, window);
 }
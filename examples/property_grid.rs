@@ -0,0 +1,88 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::property_grid_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render property grid demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = PropertyGridWidget::new(make_points(20, 20), make_size(360, 220));
+    let widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Left,
+        String::from("Click a value to change it."),
+        make_points(20, 250),
+        make_size(360, 30),
+    );
+
+    widget1.add_property("", "Name", PropertyValue::Text(String::from("button1")));
+    widget1.add_property("", "Type", PropertyValue::Text(String::from("PushButton")));
+    widget1.set_read_only("Type", true);
+    widget1.add_property(
+        "Appearance",
+        "Text",
+        PropertyValue::Text(String::from("OK")),
+    );
+    widget1.add_property(
+        "Appearance",
+        "Background",
+        PropertyValue::Color(Color::RGB(255, 255, 255)),
+    );
+    widget1.add_property(
+        "Appearance",
+        "Font Size",
+        PropertyValue::Choice(
+            vec![
+                String::from("Small"),
+                String::from("Medium"),
+                String::from("Large"),
+            ],
+            1,
+        ),
+    );
+    widget1.add_property("Behavior", "Enabled", PropertyValue::Bool(true));
+    widget1.add_property("Behavior", "Focusable", PropertyValue::Bool(false));
+
+    widget1.on_property_changed(|_grid, _widgets, _layouts, name, value| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+        let value = match value {
+            PropertyValue::Text(text) => text,
+            PropertyValue::Bool(flag) => flag.to_string(),
+            PropertyValue::Choice(options, selected) => options[selected].clone(),
+            PropertyValue::Color(color) => format!("{:?}", color),
+        };
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(format!("{} = {}", name, value));
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -3,7 +3,9 @@ extern crate sdl2;
 
 use pushrod::render::engine::Engine;
 use pushrod::render::widget::Widget;
-use pushrod::render::widget_config::{CONFIG_BORDER_WIDTH, CONFIG_COLOR_BORDER};
+use pushrod::render::widget_config::{
+    CONFIG_BORDER_WIDTH, CONFIG_COLOR_BORDER, CONFIG_TOOLTIP_TEXT,
+};
 use pushrod::render::{make_points, make_size};
 use pushrod::widgets::push_button_widget::PushButtonWidget;
 use sdl2::pixels::Color;
@@ -32,6 +34,10 @@ pub fn main() {
 
     button1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
     button1.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    button1.set_text(
+        CONFIG_TOOLTIP_TEXT,
+        String::from("Prints a message when clicked"),
+    );
     button1.on_click(|_x, _widgets, _layouts| {
         eprintln!("Click me clicked!");
     });
@@ -0,0 +1,56 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_BASE, CONFIG_THUMB_SHAPE};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::range_slider_widget::RangeSliderWidget;
+use pushrod::widgets::slider_widget::SliderOrientation::{SliderHorizontal, SliderVertical};
+use pushrod::widgets::slider_widget::ThumbShape;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render range slider demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = RangeSliderWidget::new(
+        make_points(20, 20),
+        make_size(300, 20),
+        0,
+        100,
+        25,
+        75,
+        SliderHorizontal,
+    );
+    let mut widget2 = RangeSliderWidget::new(
+        make_points(340, 20),
+        make_size(20, 260),
+        0,
+        1000,
+        200,
+        400,
+        SliderVertical,
+    );
+
+    widget1.on_range_changed(|_x, _widgets, _layout, low, high| {
+        eprintln!("Horizontal range: {} - {}", low, high);
+    });
+
+    widget2.set_color(CONFIG_COLOR_BASE, Color::RGB(0xE0, 0xE0, 0xE0));
+    widget2.set_numeric(CONFIG_THUMB_SHAPE, ThumbShape::Circle as i32);
+    widget2.on_range_changed(|_x, _widgets, _layout, low, high| {
+        eprintln!("Vertical range: {} - {}", low, high);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,58 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_TEXT, CONFIG_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::rating_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render rating demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = RatingWidget::new(make_points(75, 80), make_size(250, 50), 5, 3.0);
+
+    widget1.on_rating_changed(|_, _widgets, _layouts, rating| {
+        _widgets[3]
+            .widget
+            .borrow_mut()
+            .set_text(CONFIG_TEXT, format!("Rated {} of 5", rating));
+    });
+
+    let mut widget2 = RatingWidget::new(make_points(75, 150), make_size(250, 50), 5, 2.5);
+
+    widget2.set_half_stars(true);
+    widget2.on_rating_changed(|_, _widgets, _layouts, rating| {
+        _widgets[3]
+            .widget
+            .borrow_mut()
+            .set_text(CONFIG_TEXT, format!("Rated {} of 5", rating));
+    });
+
+    let mut text_widget = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Click a star to rate"),
+        make_points(20, 230),
+        make_size(360, 30),
+    );
+
+    text_widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(text_widget), String::from("text1"));
+
+    engine.run(sdl_context, window);
+}
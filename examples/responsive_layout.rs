@@ -0,0 +1,80 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::horizontal_layout::HorizontalLayout;
+use pushrod::layouts::responsive_layout::ResponsiveLayout;
+use pushrod::layouts::vertical_layout::VerticalLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::LayoutPosition;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+};
+use pushrod::render::{make_points_origin, make_size};
+use sdl2::pixels::Color;
+
+/// Creates a bordered `BaseWidget` to be placed by the layouts.
+fn make_box(color: Color) -> BaseWidget {
+    let mut widget = BaseWidget::new(make_points_origin(), make_size(0, 0));
+
+    widget.get_config().set_color(CONFIG_COLOR_BASE, color);
+    widget
+        .get_config()
+        .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.get_config().set_numeric(CONFIG_BORDER_WIDTH, 1);
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render responsive layout demo", 400, 300)
+        .position_centered()
+        .resizable()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let padding = PaddingConstraint::new(8, 8, 8, 8, 8);
+    let widget1_id = engine
+        .add_widget(
+            Box::new(make_box(Color::RGB(200, 255, 200))),
+            String::from("widget1"),
+        )
+        .id();
+    let widget2_id = engine
+        .add_widget(
+            Box::new(make_box(Color::RGB(255, 255, 255))),
+            String::from("widget2"),
+        )
+        .id();
+    let widget3_id = engine
+        .add_widget(
+            Box::new(make_box(Color::RGB(200, 200, 255))),
+            String::from("widget3"),
+        )
+        .id();
+
+    // Below 600 pixels wide, the navigation sits above the content, and the extra panel is hidden.
+    let mut narrow = VerticalLayout::new(0, 0, 0, 0, padding);
+
+    narrow.add_widget_with_fixed_size(widget1_id, LayoutPosition::new(0, 0), 40);
+    narrow.add_widget_with_weight(widget2_id, LayoutPosition::new(1, 0), 1);
+
+    // From 600 pixels wide, the navigation, content and extra panel sit side by side.
+    let mut wide = HorizontalLayout::new(0, 0, 0, 0, padding);
+
+    wide.add_widget_with_fixed_size(widget1_id, LayoutPosition::new(0, 0), 120);
+    wide.add_widget_with_weight(widget2_id, LayoutPosition::new(0, 1), 1);
+    wide.add_widget_with_percentage(widget3_id, LayoutPosition::new(0, 2), 25.0);
+
+    let mut layout = ResponsiveLayout::new(0, 0, 400, 300);
+
+    layout.add_breakpoint(0, Box::new(narrow));
+    layout.add_breakpoint(600, Box::new(wide));
+    layout.set_track_window(true);
+
+    engine.add_layout(Box::new(layout));
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,56 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BORDER, CONFIG_COLOR_SECONDARY, CONFIG_COLOR_THUMB_HOVER, CONFIG_COLOR_TRACK,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::scroll_bar_widget::ScrollBarOrientation::{
+    ScrollBarHorizontal, ScrollBarVertical,
+};
+use pushrod::widgets::scroll_bar_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render scroll bar demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 =
+        ScrollBarWidget::new(make_points(20, 20), make_size(340, 16), ScrollBarHorizontal);
+    let mut widget2 =
+        ScrollBarWidget::new(make_points(364, 20), make_size(16, 260), ScrollBarVertical);
+
+    widget1.set_color(CONFIG_COLOR_TRACK, Color::RGB(0xE0, 0xE0, 0xE0));
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0xA0, 0xA0, 0xA0));
+    widget1.set_color(CONFIG_COLOR_THUMB_HOVER, Color::RGB(0x80, 0x80, 0xFF));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_range(1000, 340);
+    widget1.set_step(20);
+
+    widget1.on_scroll_position_changed(|_x, _widgets, _layout, position| {
+        eprintln!("Horizontal position: {}", position);
+    });
+
+    widget2.set_color(CONFIG_COLOR_TRACK, Color::RGB(0xE0, 0xE0, 0xE0));
+    widget2.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0xA0, 0xA0, 0xA0));
+    widget2.set_color(CONFIG_COLOR_THUMB_HOVER, Color::RGB(0x80, 0x80, 0xFF));
+    widget2.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget2.set_range(100, 10);
+
+    widget2.on_scroll_position_changed(|_x, _widgets, _layout, position| {
+        eprintln!("Vertical position: {}", position);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,59 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_SECONDARY,
+    CONFIG_COLOR_THUMB_HOVER, CONFIG_COLOR_TRACK,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::scroll_container_widget::ScrollContainerWidget;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render scroll container demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut container = ScrollContainerWidget::new(make_points(20, 20), make_size(360, 260));
+
+    container.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    container.set_color(CONFIG_COLOR_TRACK, Color::RGB(0xE0, 0xE0, 0xE0));
+    container.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0xA0, 0xA0, 0xA0));
+    container.set_color(CONFIG_COLOR_THUMB_HOVER, Color::RGB(0x80, 0x80, 0xFF));
+    container.set_content_size(600, 800);
+
+    let container_handle = engine.add_widget(Box::new(container), String::from("container"));
+
+    for row in 0..8 {
+        for column in 0..3 {
+            let label = format!("Button {}", row * 3 + column + 1);
+            let mut button = PushButtonWidget::new(
+                make_points(20 + column * 200, 20 + row * 100),
+                make_size(160, 60),
+                label.clone(),
+                18,
+            );
+
+            button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+            button.on_click(move |_x, _widgets, _layouts| {
+                eprintln!("{} clicked", label);
+            });
+
+            let button_handle =
+                engine.add_widget(Box::new(button), format!("button{}", row * 3 + column));
+
+            engine.set_parent(button_handle, container_handle).unwrap();
+        }
+    }
+
+    engine.run(sdl_context, window);
+}
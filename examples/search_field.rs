@@ -0,0 +1,79 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::list_widget::*;
+use pushrod::widgets::search_field_widget::*;
+use sdl2::pixels::Color;
+
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+const FRUITS: [&str; 12] = [
+    "Apple",
+    "Apricot",
+    "Banana",
+    "Blueberry",
+    "Cherry",
+    "Grape",
+    "Lemon",
+    "Mango",
+    "Orange",
+    "Peach",
+    "Pear",
+    "Strawberry",
+];
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render search field demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = SearchFieldWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        16,
+        make_points(20, 20),
+        make_size(360, 30),
+    );
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.on_search_changed(|_, _widgets, _layouts, search| {
+        let search = search.to_lowercase();
+        let items = FRUITS
+            .iter()
+            .filter(|fruit| fruit.to_lowercase().contains(&search))
+            .map(|fruit| String::from(*fruit))
+            .collect();
+
+        cast!(_widgets, 2, ListWidget).set_items(items);
+    });
+
+    let mut widget2 = ListWidget::new(make_points(20, 60), make_size(360, 220));
+
+    widget2.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget2.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget2.set_items(FRUITS.iter().map(|fruit| String::from(*fruit)).collect());
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,64 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::segmented_control_widget::*;
+use pushrod::widgets::text_widget::*;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render segmented control demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = SegmentedControlWidget::new(
+        make_points(50, 60),
+        make_size(300, 30),
+        vec![
+            String::from("Day"),
+            String::from("Week"),
+            String::from("Month"),
+            String::from("Year"),
+        ],
+        1,
+    );
+    let widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Showing: Week"),
+        make_points(50, 120),
+        make_size(300, 30),
+    );
+
+    widget1.on_segment_selected(|control, _widgets, _layouts, index| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+        let text = format!("Showing: {}", control.get_segments()[index]);
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(text);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,60 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_SECONDARY, CONFIG_COLOR_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::seven_segment_widget::*;
+use pushrod::widgets::timer_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render seven segment demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = SevenSegmentWidget::new(make_points(20, 40), make_size(360, 100), 6);
+
+    widget1.set_value(-1234);
+
+    let mut widget2 = SevenSegmentWidget::new(make_points(20, 160), make_size(360, 100), 6);
+
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(64, 255, 64));
+    widget2.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(16, 48, 16));
+    widget2.set_text("c0ffee");
+
+    let mut timer = TimerWidget::new(100, true);
+    let mut count: i64 = -1234;
+
+    timer.on_timeout(move |_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+
+        count += 1;
+        cast!(_widgets, widget1_id, SevenSegmentWidget).set_value(count);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,60 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::horizontal_layout::HorizontalLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::{Layout, LayoutPosition};
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BORDER};
+use pushrod::render::{make_points_origin, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use sdl2::pixels::Color;
+
+/// Creates a toolbar button.
+fn make_button(text: &str) -> PushButtonWidget {
+    let mut widget = PushButtonWidget::new(
+        make_points_origin(),
+        make_size(0, 0),
+        String::from(text),
+        14,
+    );
+
+    widget.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.set_numeric(CONFIG_BORDER_WIDTH, 1);
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render spacer layout demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = HorizontalLayout::new(0, 0, 400, 40, PaddingConstraint::new(4, 4, 4, 4, 4));
+    let names = ["New", "Open", "Save", "Help"];
+    let ids: Vec<i32> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            engine
+                .add_widget(Box::new(make_button(name)), format!("widget{}", i + 1))
+                .id()
+        })
+        .collect();
+
+    // "New" and "Open" sit together, "Save" is pinned 20 pixels further on, and a flexible
+    // spacer pushes "Help" to the far right.
+    layout.add_widget_with_fixed_size(ids[0], LayoutPosition::new(0, 0), 60);
+    layout.add_widget_with_fixed_size(ids[1], LayoutPosition::new(0, 1), 60);
+    layout.add_strut(20);
+    layout.add_widget_with_fixed_size(ids[2], LayoutPosition::new(0, 3), 60);
+    layout.add_spacer(true);
+    layout.add_widget_with_fixed_size(ids[3], LayoutPosition::new(0, 5), 60);
+
+    engine.add_layout(Box::new(layout));
+    engine.run(sdl_context, window);
+}
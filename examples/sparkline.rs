@@ -0,0 +1,55 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::sparkline_widget::*;
+use pushrod::widgets::timer_widget::*;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render sparkline demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let widget1 = SparklineWidget::new(make_points(20, 40), make_size(360, 60), 120);
+    let mut widget2 = SparklineWidget::new(make_points(20, 160), make_size(360, 60), 60);
+
+    widget2.set_filled(true);
+    widget2.set_range(-1.0, 1.0);
+
+    let mut step = 0.0_f64;
+    let mut timer = TimerWidget::new(50, true);
+    timer.on_timeout(move |_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+        step += 1.0;
+        cast!(_widgets, widget1_id, SparklineWidget)
+            .push_value((step * 0.13).sin() + (step * 0.41).sin() * 0.3);
+        cast!(_widgets, widget2_id, SparklineWidget).push_value((step * 0.1).sin());
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
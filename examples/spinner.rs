@@ -0,0 +1,39 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_HOVER, CONFIG_COLOR_SECONDARY};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::spinner_widget::SpinnerWidget;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render spinner demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = SpinnerWidget::new(make_points(20, 20), make_size(120, 30), 0, 100, 50);
+    let mut widget2 = SpinnerWidget::new(make_points(20, 70), make_size(120, 30), -1000, 1000, 0);
+
+    widget1.on_value_changed(|_x, _widgets, _layout, value| {
+        eprintln!("Spinner 1 value: {}", value);
+    });
+
+    widget2.set_step(25);
+    widget2.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0xE0, 0xE0, 0xFF));
+    widget2.set_color(CONFIG_COLOR_HOVER, Color::RGB(0xC0, 0xC0, 0xFF));
+    widget2.on_value_changed(|_x, _widgets, _layout, value| {
+        eprintln!("Spinner 2 value: {}", value);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
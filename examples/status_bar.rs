@@ -0,0 +1,73 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::CONFIG_COLOR_TEXT;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::status_bar_widget::*;
+use pushrod::widgets::text_widget::*;
+use pushrod::widgets::timer_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render status bar demo", 400, 300)
+        .position_centered()
+        .resizable()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut text_widget = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("Resize the window: the status bar follows it."),
+        make_points(20, 20),
+        make_size(360, 30),
+    );
+
+    text_widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut widget1 = StatusBarWidget::new(400, 300, 24);
+
+    widget1.add_pane(PaneWidth::Stretch(1));
+    widget1.add_pane(PaneWidth::Fixed(100));
+    widget1.add_pane(PaneWidth::Fixed(60));
+    widget1.set_pane_text(0, "Ready");
+    widget1.set_pane_text(2, "INS");
+
+    let mut timer = TimerWidget::new(1000, true);
+    let mut seconds = 0;
+
+    timer.on_timeout(move |_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+
+        seconds += 1;
+        cast!(_widgets, widget1_id, StatusBarWidget)
+            .set_pane_text(1, &format!("{} seconds", seconds));
+    });
+
+    engine.add_widget(Box::new(text_widget), String::from("text1"));
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
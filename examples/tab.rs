@@ -0,0 +1,90 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_HOVER,
+    CONFIG_COLOR_SECONDARY, CONFIG_COLOR_SELECTED, CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::tab_widget::TabWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render tab demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut text_widget1 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("This is the general tab."),
+        make_points(40, 80),
+        make_size(320, 30),
+    );
+
+    text_widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut text_widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("This is the advanced tab."),
+        make_points(40, 80),
+        make_size(320, 30),
+    );
+
+    text_widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let mut button1 = PushButtonWidget::new(
+        make_points(40, 130),
+        make_size(150, 40),
+        String::from("Reset"),
+        20,
+    );
+
+    button1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    button1.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    button1.on_click(|_x, _widgets, _layouts| {
+        eprintln!("Reset clicked");
+    });
+
+    let text_handle1 = engine.add_widget(Box::new(text_widget1), String::from("text1"));
+    let text_handle2 = engine.add_widget(Box::new(text_widget2), String::from("text2"));
+    let button_handle1 = engine.add_widget(Box::new(button1), String::from("button1"));
+    let mut widget1 = TabWidget::new(make_points(20, 20), make_size(360, 260));
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0xD0, 0xD0, 0xD0));
+    widget1.set_color(CONFIG_COLOR_HOVER, Color::RGB(0xE0, 0xE0, 0xFF));
+    widget1.set_color(CONFIG_COLOR_SELECTED, Color::RGB(255, 255, 255));
+
+    widget1.add_tab("General", vec![text_handle1.id()]);
+    widget1.add_tab("Advanced", vec![text_handle2.id(), button_handle1.id()]);
+
+    widget1.on_tab_selected(|x, _widgets, _layout, tab| {
+        eprintln!("Selected tab: {}", x.get_tab_name(tab).unwrap());
+    });
+
+    let tab_handle1 = engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    // The contents of each tab are drawn above the tab widget.
+    for handle in &[text_handle1, text_handle2, button_handle1] {
+        engine.set_parent(*handle, tab_handle1).unwrap();
+    }
+
+    engine.run(sdl_context, window);
+}
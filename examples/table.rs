@@ -0,0 +1,65 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_HOVER, CONFIG_COLOR_SECONDARY,
+    CONFIG_COLOR_SELECTED, CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::table_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render table demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TableWidget::new(make_points(20, 20), make_size(360, 260));
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(0xD0, 0xD0, 0xD0));
+    widget1.set_color(CONFIG_COLOR_HOVER, Color::RGB(0xE0, 0xE0, 0xFF));
+    widget1.set_color(CONFIG_COLOR_SELECTED, Color::RGB(0x90, 0x90, 0xFF));
+
+    widget1.add_column("Name", 160);
+    widget1.add_column("Language", 120);
+    widget1.add_column("Year", 80);
+
+    for (name, language, year) in &[
+        ("Rust", "Systems", "2010"),
+        ("Go", "Systems", "2009"),
+        ("Python", "Scripting", "1991"),
+        ("Ruby", "Scripting", "1995"),
+        ("Haskell", "Functional", "1990"),
+        ("OCaml", "Functional", "1996"),
+        ("C", "Systems", "1972"),
+        ("Lua", "Scripting", "1993"),
+        ("Erlang", "Functional", "1986"),
+        ("Zig", "Systems", "2016"),
+        ("Elixir", "Functional", "2012"),
+        ("Perl", "Scripting", "1987"),
+    ] {
+        widget1.add_row(vec![
+            String::from(*name),
+            String::from(*language),
+            String::from(*year),
+        ]);
+    }
+
+    widget1.on_row_selected(|x, _widgets, _layout, row| {
+        eprintln!("Selected: {:?}", x.get_rows()[row]);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
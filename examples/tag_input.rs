@@ -0,0 +1,57 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_TEXT, CONFIG_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::tag_input_widget::*;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render tag input demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TagInputWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        16,
+        make_points(20, 20),
+        make_size(360, 96),
+    );
+
+    widget1.set_max_tags(Some(8));
+    widget1.set_tags(&[String::from("rust"), String::from("sdl2")]);
+    widget1.on_tags_changed(|_, _widgets, _layouts, tags| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+        _widgets[widget2_id].widget.borrow_mut().set_text(
+            CONFIG_TEXT,
+            format!("{} of 8 tags: {}", tags.len(), tags.join(", ")),
+        );
+    });
+
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Left,
+        String::from("Type a tag, then press Enter or a comma."),
+        make_points(20, 130),
+        make_size(360, 60),
+    );
+
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
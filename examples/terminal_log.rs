@@ -0,0 +1,81 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_SECONDARY, CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::terminal_log_widget::*;
+use pushrod::widgets::timer_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render terminal log demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TerminalLogWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        14,
+        make_points(10, 10),
+        make_size(380, 280),
+        200,
+    );
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(20, 20, 20));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(90, 90, 90));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(220, 220, 220));
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(110, 170, 110));
+    widget1.append_line("Scroll up with the mouse wheel to stop following new lines");
+
+    let mut count = 0;
+    let mut timer = TimerWidget::new(400, true);
+    timer.on_timeout(move |_, _widgets, _layouts| {
+        let widget1_id = widget_id_for_name(_widgets, String::from("widget1"));
+        count += 1;
+
+        let (line, color) = match count % 7 {
+            0 => (
+                format!("warning: request {} took longer than expected", count),
+                Some(Color::RGB(240, 200, 60)),
+            ),
+            5 => (
+                format!("error: request {} failed", count),
+                Some(Color::RGB(240, 90, 90)),
+            ),
+            _ => (format!("request {} completed", count), None),
+        };
+
+        match color {
+            Some(color) => {
+                cast!(_widgets, widget1_id, TerminalLogWidget).append_colored_line(&line, color)
+            }
+            None => cast!(_widgets, widget1_id, TerminalLogWidget).append_line(&line),
+        }
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(timer), String::from("timer1"));
+
+    engine.run(sdl_context, window);
+}
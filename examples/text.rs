@@ -7,17 +7,18 @@ use pushrod::render::widget_config::CONFIG_COLOR_TEXT;
 use pushrod::render::{make_points, make_size};
 use pushrod::widgets::text_widget::*;
 use sdl2::pixels::Color;
+use sdl2::ttf::FontStyle;
 
 pub fn main() {
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
     let window = video_subsystem
-        .window("pushrod text widget demo", 500, 200)
+        .window("pushrod text widget demo", 500, 300)
         .position_centered()
         .opengl()
         .build()
         .unwrap();
-    let mut engine = Engine::new(500, 200, 20);
+    let mut engine = Engine::new(500, 300, 20);
     let mut widget1 = TextWidget::new(
         String::from("assets/OpenSans-Regular.ttf"),
         sdl2::ttf::FontStyle::NORMAL,
@@ -60,9 +61,34 @@ pub fn main() {
         .get_config()
         .set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 255));
 
+    let mut widget4 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        18,
+        TextJustify::Center,
+        String::new(),
+        make_points(20, 200),
+        make_size(460, 80),
+    );
+
+    widget4.set_spans(vec![
+        TextSpan::new("Rich text mixes ", None, FontStyle::NORMAL),
+        TextSpan::new("bold", None, FontStyle::BOLD),
+        TextSpan::new(", ", None, FontStyle::NORMAL),
+        TextSpan::new("underlined", None, FontStyle::UNDERLINE),
+        TextSpan::new(" and ", None, FontStyle::NORMAL),
+        TextSpan::new("colored", Some(Color::RGB(200, 0, 200)), FontStyle::ITALIC),
+        TextSpan::new(
+            " runs within a single paragraph, wrapped to the width of the widget.",
+            None,
+            FontStyle::NORMAL,
+        ),
+    ]);
+
     engine.add_widget(Box::new(widget1), String::from("widget1"));
     engine.add_widget(Box::new(widget2), String::from("widget2"));
     engine.add_widget(Box::new(widget3), String::from("widget3"));
+    engine.add_widget(Box::new(widget4), String::from("widget4"));
 
     engine.run(sdl_context, window);
 }
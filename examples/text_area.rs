@@ -0,0 +1,48 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_area_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render text area demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TextAreaWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        16,
+        make_points(20, 20),
+        make_size(360, 260),
+    );
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_text(
+        "This is a multi-line text area.  Long lines are wrapped at word boundaries, and the \
+         text scrolls vertically when it does not fit.\n\nClick to place the cursor, and use the \
+         arrow keys, Home, End, Page Up and Page Down to move it.",
+    );
+
+    widget1.on_text_changed(|_x, _widgets, _layout, text| {
+        eprintln!("Text changed: {} characters", text.chars().count());
+    });
+
+    widget1.on_cursor_moved(|_x, _widgets, _layout, line, column| {
+        eprintln!("Cursor: line {} column {}", line + 1, column + 1);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
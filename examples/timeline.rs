@@ -0,0 +1,70 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_widget::*;
+use pushrod::widgets::timeline_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render timeline demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TimelineWidget::new(make_points(20, 60), make_size(360, 100), 180.0);
+    let widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Left,
+        String::from("Drag the playhead, click a region, or zoom with the wheel."),
+        make_points(20, 180),
+        make_size(360, 60),
+    );
+
+    widget1.add_region(0.0, 30.0, "Intro", None);
+    widget1.add_region(30.0, 120.0, "Verse", Some(Color::RGB(224, 144, 64)));
+    widget1.add_region(120.0, 165.0, "Chorus", Some(Color::RGB(160, 96, 200)));
+    widget1.add_marker(45.0, "Cue 1", None);
+    widget1.add_marker(140.0, "Cue 2", Some(Color::RGB(200, 0, 120)));
+
+    widget1.on_playhead_moved(|_timeline, _widgets, _layouts, time| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(format!("Playhead: {:.2}s", time));
+    });
+
+    widget1.on_region_selected(|timeline, _widgets, _layouts, index| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+        let region = timeline.get_regions()[index].clone();
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(format!(
+            "Region: {} ({:.0}s - {:.0}s)",
+            region.label, region.start, region.end
+        ));
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,37 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::make_size;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CompassPosition, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER};
+use pushrod::widgets::toast_notification_widget::ToastNotificationWidget;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render toast demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let toast1 =
+        ToastNotificationWidget::new("File saved.", make_size(220, 40), CompassPosition::NE, 3000);
+    let mut toast2 = ToastNotificationWidget::new(
+        "Click to dismiss this message.",
+        make_size(260, 40),
+        CompassPosition::SW,
+        10000,
+    );
+
+    toast2.set_color(CONFIG_COLOR_BASE, Color::RGB(0x20, 0x60, 0xA0));
+    toast2.set_color(CONFIG_COLOR_BORDER, Color::RGB(255, 255, 255));
+
+    engine.add_widget(Box::new(toast1), String::from("toast1"));
+    engine.add_widget(Box::new(toast2), String::from("toast2"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,37 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::CONFIG_COLOR_SELECTED;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::toggle_switch_widget::ToggleSwitchWidget;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render toggle switch demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = ToggleSwitchWidget::new(make_points(20, 20), make_size(60, 30), false);
+    let mut widget2 = ToggleSwitchWidget::new(make_points(20, 70), make_size(80, 40), true);
+
+    widget1.on_toggle(|_x, _widgets, _layout, selected| {
+        eprintln!("Switch 1 on: {}", selected);
+    });
+
+    widget2.set_color(CONFIG_COLOR_SELECTED, Color::RGB(0x40, 0x60, 0xC0));
+    widget2.on_toggle(|_x, _widgets, _layout, selected| {
+        eprintln!("Switch 2 on: {}", selected);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
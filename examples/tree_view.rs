@@ -0,0 +1,64 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{
+    CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_HOVER, CONFIG_COLOR_SELECTED,
+    CONFIG_COLOR_TEXT,
+};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::tree_view_widget::*;
+use sdl2::pixels::Color;
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render tree view demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = TreeViewWidget::new(make_points(20, 20), make_size(360, 260));
+
+    widget1.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    widget1.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+    widget1.set_color(CONFIG_COLOR_HOVER, Color::RGB(0xE0, 0xE0, 0xFF));
+    widget1.set_color(CONFIG_COLOR_SELECTED, Color::RGB(0x90, 0x90, 0xFF));
+
+    widget1.add_node(
+        TreeNode::new("src")
+            .with_expanded(true)
+            .with_child(
+                TreeNode::new("render")
+                    .with_child(TreeNode::new("engine.rs"))
+                    .with_child(TreeNode::new("widget.rs")),
+            )
+            .with_child(
+                TreeNode::new("widgets")
+                    .with_child(TreeNode::new("list_widget.rs"))
+                    .with_child(TreeNode::new("tree_view_widget.rs")),
+            )
+            .with_child(TreeNode::new("lib.rs")),
+    );
+    widget1.add_node(TreeNode::new("Cargo.toml"));
+
+    widget1.on_node_expanded(|_x, _widgets, _layout, path| {
+        eprintln!("Expanded: {:?}", path);
+    });
+
+    widget1.on_node_collapsed(|_x, _widgets, _layout, path| {
+        eprintln!("Collapsed: {:?}", path);
+    });
+
+    widget1.on_node_selected(|x, _widgets, _layout, path| {
+        eprintln!("Selected: {}", x.get_node(path).unwrap().label);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    engine.run(sdl_context, window);
+}
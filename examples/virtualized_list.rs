@@ -0,0 +1,69 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::engine::Engine;
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::text_widget::*;
+use pushrod::widgets::virtualized_list_widget::*;
+use sdl2::pixels::Color;
+
+#[macro_export]
+macro_rules! cast {
+    ($a:expr, $b:expr, $c:ident) => {
+        $a[$b]
+            .widget
+            .borrow_mut()
+            .as_any()
+            .downcast_mut::<$c>()
+            .unwrap()
+    };
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render virtualized list demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 =
+        VirtualizedListWidget::new(make_points(20, 20), make_size(360, 220), 100_000, |row| {
+            let mut data =
+                RowData::with_detail(&format!("Row {}", row + 1), &format!("0x{:05X}", row));
+
+            if row % 1000 == 0 {
+                data.color = Some(Color::RGB(192, 0, 0));
+            }
+
+            data
+        });
+    let widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        14,
+        TextJustify::Left,
+        String::from("100,000 rows; nothing selected."),
+        make_points(20, 254),
+        make_size(360, 30),
+    );
+
+    widget1.on_row_selected(|list, _widgets, _layouts, row| {
+        let widget2_id = widget_id_for_name(_widgets, String::from("widget2"));
+        let text = format!(
+            "Selected row {}, using {} row textures.",
+            row + 1,
+            list.get_texture_count()
+        );
+
+        cast!(_widgets, widget2_id, TextWidget).set_text(text);
+    });
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+    engine.add_widget(Box::new(widget2), String::from("widget2"));
+
+    engine.run(sdl_context, window);
+}
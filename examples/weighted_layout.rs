@@ -0,0 +1,70 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::layouts::horizontal_layout::HorizontalLayout;
+use pushrod::layouts::vertical_layout::VerticalLayout;
+use pushrod::render::engine::Engine;
+use pushrod::render::layout::LayoutPosition;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    PaddingConstraint, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+};
+use pushrod::render::{make_points_origin, make_size};
+use sdl2::pixels::Color;
+
+/// Creates a bordered `BaseWidget` to be sized by a layout.
+fn make_box(color: Color) -> BaseWidget {
+    let mut widget = BaseWidget::new(make_points_origin(), make_size(0, 0));
+
+    widget.get_config().set_color(CONFIG_COLOR_BASE, color);
+    widget
+        .get_config()
+        .set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    widget.get_config().set_numeric(CONFIG_BORDER_WIDTH, 1);
+    widget
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render weighted layout demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut layout = HorizontalLayout::new(20, 20, 360, 120, PaddingConstraint::new(4, 4, 4, 4, 4));
+    let mut layout2 = VerticalLayout::new(20, 160, 360, 120, PaddingConstraint::new(4, 4, 4, 4, 4));
+    let colors = [
+        Color::RGB(255, 200, 200),
+        Color::RGB(200, 255, 200),
+        Color::RGB(200, 200, 255),
+        Color::RGB(255, 255, 200),
+        Color::RGB(255, 200, 255),
+        Color::RGB(200, 255, 255),
+    ];
+    let ids: Vec<i32> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            engine
+                .add_widget(Box::new(make_box(*color)), format!("widget{}", i + 1))
+                .id()
+        })
+        .collect();
+
+    // A fixed 60 pixel column, then the rest of the width split one part to two.
+    layout.add_widget_with_fixed_size(ids[0], LayoutPosition::new(0, 0), 60);
+    layout.add_widget_with_weight(ids[1], LayoutPosition::new(0, 1), 1);
+    layout.add_widget_with_weight(ids[2], LayoutPosition::new(0, 2), 2);
+
+    // A fixed 24 pixel header, a content row that takes the rest, and a fixed 24 pixel footer.
+    layout2.add_widget_with_fixed_size(ids[3], LayoutPosition::new(0, 0), 24);
+    layout2.add_widget_with_weight(ids[4], LayoutPosition::new(1, 0), 1);
+    layout2.add_widget_with_fixed_size(ids[5], LayoutPosition::new(2, 0), 24);
+
+    engine.add_layout(Box::new(layout));
+    engine.add_layout(Box::new(layout2));
+    engine.run(sdl_context, window);
+}
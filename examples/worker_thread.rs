@@ -0,0 +1,89 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_SECONDARY, CONFIG_COLOR_TEXT};
+use pushrod::render::{make_points, make_size};
+use pushrod::widgets::progress_widget::ProgressWidget;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+use std::thread;
+use std::time::Duration;
+
+/// This is the message the worker thread posts to the `Engine` as it makes progress.
+enum WorkerMessage {
+    Progress(u8),
+    Finished,
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render worker thread demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new(400, 300, 60);
+    let mut widget1 = ProgressWidget::new(make_points(20, 100), make_size(360, 40), 0);
+    let mut widget2 = TextWidget::new(
+        String::from("assets/OpenSans-Regular.ttf"),
+        sdl2::ttf::FontStyle::NORMAL,
+        16,
+        TextJustify::Center,
+        String::from("Working in the background"),
+        make_points(20, 160),
+        make_size(360, 30),
+    );
+
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
+    widget2.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+
+    let widget1_id = engine
+        .add_widget(Box::new(widget1), String::from("widget1"))
+        .id();
+    let widget2_id = engine
+        .add_widget(Box::new(widget2), String::from("widget2"))
+        .id();
+
+    engine.on_event_message(
+        move |cache, message| match message.downcast_ref::<WorkerMessage>() {
+            Some(WorkerMessage::Progress(progress)) => cache
+                .get_container_by_id(widget1_id)
+                .widget
+                .borrow_mut()
+                .as_any()
+                .downcast_mut::<ProgressWidget>()
+                .unwrap()
+                .set_progress(*progress),
+            Some(WorkerMessage::Finished) => cache
+                .get_container_by_id(widget2_id)
+                .widget
+                .borrow_mut()
+                .as_any()
+                .downcast_mut::<TextWidget>()
+                .unwrap()
+                .set_text(String::from("Finished")),
+            None => {}
+        },
+    );
+
+    // The worker thread cannot touch the Widgets directly, so it posts its progress instead.
+    let sender = engine.get_event_sender();
+
+    thread::spawn(move || {
+        for progress in 1..=100 {
+            thread::sleep(Duration::from_millis(50));
+
+            if sender.send(WorkerMessage::Progress(progress)).is_err() {
+                return;
+            }
+        }
+
+        let _ = sender.send(WorkerMessage::Finished);
+    });
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,193 @@
+// Pushrod Rendering Library
+// Absolute Layout Manager
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
+
+/// This is the `AbsoluteLayout` storage structure for the `AbsoluteLayout` implementation.
+pub struct AbsoluteLayout {
+    widget_ids: Vec<i32>,
+    design_bounds: Vec<Option<Vec<i32>>>,
+    origin: Points,
+    size: Size,
+    reference_size: Size,
+    padding: PaddingConstraint,
+    track_window: bool,
+    invalidated: bool,
+}
+
+/// Creates a new `AbsoluteLayout` manager.  The size given is the reference resolution that the
+/// `Widget`s were designed for.
+impl AbsoluteLayout {
+    pub fn new(x: i32, y: i32, w: u32, h: u32, padding: PaddingConstraint) -> Self {
+        Self {
+            widget_ids: Vec::new(),
+            design_bounds: Vec::new(),
+            origin: vec![x, y],
+            size: vec![w, h],
+            reference_size: vec![w.max(1), h.max(1)],
+            padding,
+            track_window: false,
+            invalidated: false,
+        }
+    }
+
+    /// Adds a widget to the `AbsoluteLayout`, with its design-time bounds at `x`, `y` of `w` by `h`
+    /// pixels, relative to the origin of the `Layout` at the reference resolution.
+    pub fn insert_widget_at(&mut self, widget_id: i32, x: i32, y: i32, w: u32, h: u32) {
+        self.widget_ids.push(widget_id);
+        self.design_bounds
+            .push(Some(vec![x, y, w as i32, h as i32]));
+        self.invalidated = true;
+    }
+
+    /// Retrieves the reference resolution that the `Widget`s were designed for.
+    pub fn get_reference_size(&self) -> Size {
+        self.reference_size.clone()
+    }
+
+    /// Retrieves the bounds of the `AbsoluteLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `AbsoluteLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window, rescaling its `Widget`s to match.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+}
+
+/// This is the `Layout` implementation for the `AbsoluteLayout` manager.  Each `Widget` keeps the
+/// origin and size it was designed with at the reference resolution, scaled horizontally and
+/// vertically by how much the bounds of the `Layout` have grown or shrunk since.  This allows a
+/// screen designed in fixed pixels to fill a resizable window.  Padding and spacing are not used.
+impl Layout for AbsoluteLayout {
+    /// Adds a widget to the `AbsoluteLayout`.  Its design-time bounds are taken from its origin and
+    /// size the first time the `Layout` is performed.  The position is not used.
+    fn insert_widget(&mut self, widget_id: i32, _widget_position: LayoutPosition) {
+        self.widget_ids.push(widget_id);
+        self.design_bounds.push(None);
+        self.invalidated = true;
+    }
+
+    /// Appends a widget to the `AbsoluteLayout`.
+    fn append_widget(&mut self, widget_id: i32) {
+        self.insert_widget(widget_id, LayoutPosition::new(0, 0));
+    }
+
+    fn set_padding(&mut self, padding: PaddingConstraint) {
+        self.padding = padding;
+        self.invalidated = true;
+    }
+
+    fn get_padding(&self) -> PaddingConstraint {
+        self.padding
+    }
+
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager, scaling each from its
+    /// design-time bounds to the current bounds of the `Layout`.
+    fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
+        let scale_x = self.size[SIZE_WIDTH] as f64 / self.reference_size[SIZE_WIDTH] as f64;
+        let scale_y = self.size[SIZE_HEIGHT] as f64 / self.reference_size[SIZE_HEIGHT] as f64;
+
+        for (i, widget_id) in self.widget_ids.iter().enumerate() {
+            let mut widget = _widgets[*widget_id as usize].widget.borrow_mut();
+
+            if self.design_bounds[i].is_none() {
+                let origin = widget.get_config().get_point(CONFIG_ORIGIN);
+                let size = widget.get_config().get_size(CONFIG_SIZE);
+
+                self.design_bounds[i] = Some(vec![
+                    origin[POINT_X] - self.origin[0],
+                    origin[POINT_Y] - self.origin[1],
+                    size[SIZE_WIDTH] as i32,
+                    size[SIZE_HEIGHT] as i32,
+                ]);
+            }
+
+            // The edges are scaled, rather than the origin and size, so that adjoining Widgets
+            // stay adjoined after rounding.
+            let bounds = self.design_bounds[i].as_ref().unwrap();
+            let left = (bounds[0] as f64 * scale_x).round() as i32;
+            let top = (bounds[1] as f64 * scale_y).round() as i32;
+            let right = ((bounds[0] + bounds[2]) as f64 * scale_x).round() as i32;
+            let bottom = ((bounds[1] + bounds[3]) as f64 * scale_y).round() as i32;
+
+            widget.get_config().set_point(
+                CONFIG_ORIGIN,
+                self.origin[0] + left,
+                self.origin[1] + top,
+            );
+            widget.get_config().set_size(
+                CONFIG_SIZE,
+                (right - left).max(0) as u32,
+                (bottom - top).max(0) as u32,
+            );
+            widget.get_config().set_invalidated(true);
+        }
+
+        self.invalidated = false;
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+    }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `AbsoluteLayout`, rescaling its `Widget`s on the next layout.  The
+    /// reference resolution is not changed.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `AbsoluteLayout`, along with its design bounds.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+            self.design_bounds.remove(i);
+            self.invalidated = true;
+        }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids.clone()
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    /// Extends the `AbsoluteLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
+}
@@ -0,0 +1,307 @@
+// Pushrod Rendering Library
+// Anchor Layout Manager
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
+
+/// This enum describes how a `Widget` is anchored horizontally within an `AnchorLayout`.  Offsets
+/// are in pixels, measured inward from the padding of the `Layout`.
+#[derive(Clone, Debug, Copy)]
+pub enum HorizontalAnchor {
+    /// The left edge of the `Widget` is kept at an offset from the left edge.
+    Left(i32),
+
+    /// The right edge of the `Widget` is kept at an offset from the right edge.
+    Right(i32),
+
+    /// The `Widget` is centered, then moved right by the offset.
+    Center(i32),
+
+    /// The `Widget` is stretched between an offset from the left edge and an offset from the right
+    /// edge.
+    Stretch(i32, i32),
+}
+
+/// This enum describes how a `Widget` is anchored vertically within an `AnchorLayout`.  Offsets are
+/// in pixels, measured inward from the padding of the `Layout`.
+#[derive(Clone, Debug, Copy)]
+pub enum VerticalAnchor {
+    /// The top edge of the `Widget` is kept at an offset from the top edge.
+    Top(i32),
+
+    /// The bottom edge of the `Widget` is kept at an offset from the bottom edge.
+    Bottom(i32),
+
+    /// The `Widget` is centered, then moved down by the offset.
+    Center(i32),
+
+    /// The `Widget` is stretched between an offset from the top edge and an offset from the bottom
+    /// edge.
+    Stretch(i32, i32),
+}
+
+/// This is the `AnchorLayout` storage structure for the `AnchorLayout` implementation.
+pub struct AnchorLayout {
+    widget_ids: Vec<i32>,
+    widget_anchors: Vec<(HorizontalAnchor, VerticalAnchor)>,
+    origin: Points,
+    size: Size,
+    padding: PaddingConstraint,
+    track_window: bool,
+    invalidated: bool,
+}
+
+/// Creates a new `AnchorLayout` manager.
+impl AnchorLayout {
+    pub fn new(x: i32, y: i32, w: u32, h: u32, padding: PaddingConstraint) -> Self {
+        Self {
+            widget_ids: Vec::new(),
+            widget_anchors: Vec::new(),
+            origin: vec![x, y],
+            size: vec![w, h],
+            padding,
+            track_window: false,
+            invalidated: false,
+        }
+    }
+
+    /// Adds a widget to the `AnchorLayout`, anchored horizontally by `horizontal` and vertically by
+    /// `vertical`.
+    pub fn insert_widget_anchored(
+        &mut self,
+        widget_id: i32,
+        horizontal: HorizontalAnchor,
+        vertical: VerticalAnchor,
+    ) {
+        self.widget_ids.push(widget_id);
+        self.widget_anchors.push((horizontal, vertical));
+        self.invalidated = true;
+    }
+
+    /// Retrieves the bounds of the `AnchorLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `AnchorLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+}
+
+/// Returns the start and length of a `Widget` of `length` pixels anchored within a span of `extent`
+/// pixels starting at `start`, given its near and far offsets, its center offset, and which of
+/// them apply.
+fn anchor_span(
+    start: i32,
+    extent: i32,
+    length: i32,
+    near: Option<i32>,
+    far: Option<i32>,
+    center: Option<i32>,
+) -> (i32, i32) {
+    match (near, far, center) {
+        (Some(near), Some(far), _) => (start + near, (extent - near - far).max(0)),
+        (Some(near), None, _) => (start + near, length),
+        (None, Some(far), _) => (start + extent - far - length, length),
+        (None, None, center) => (start + (extent - length) / 2 + center.unwrap_or(0), length),
+    }
+}
+
+/// This is the `Layout` implementation for the `AnchorLayout` manager.  Each `Widget` is kept at
+/// the place given by its anchors within the bounds of the `Layout`, inside its padding.  Anchoring
+/// to the right or bottom keeps a `Widget` at that edge when the `Layout` grows, such as a status
+/// bar along the bottom of the window, or a button in its bottom right corner.  `Widget`s keep their
+/// size, unless they are stretched between two edges.  Spacing is not used.
+impl Layout for AnchorLayout {
+    /// Adds a widget to the `AnchorLayout`, with its top left corner anchored at the `x` and `y`
+    /// offsets of `widget_position`.
+    fn insert_widget(&mut self, widget_id: i32, widget_position: LayoutPosition) {
+        self.insert_widget_anchored(
+            widget_id,
+            HorizontalAnchor::Left(widget_position.x),
+            VerticalAnchor::Top(widget_position.y),
+        );
+    }
+
+    /// Appends a widget to the top left corner of the `AnchorLayout`.
+    fn append_widget(&mut self, widget_id: i32) {
+        self.insert_widget(widget_id, LayoutPosition::new(0, 0));
+    }
+
+    fn set_padding(&mut self, padding: PaddingConstraint) {
+        self.padding = padding;
+        self.invalidated = true;
+    }
+
+    fn get_padding(&self) -> PaddingConstraint {
+        self.padding
+    }
+
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager, placing and sizing each
+    /// `Widget` by its anchors.
+    fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
+        let left = self.origin[0] + self.padding.left;
+        let top = self.origin[1] + self.padding.top;
+        let width = self.size[SIZE_WIDTH] as i32 - self.padding.left - self.padding.right;
+        let height = self.size[SIZE_HEIGHT] as i32 - self.padding.top - self.padding.bottom;
+
+        for (i, widget_id) in self.widget_ids.iter().enumerate() {
+            let (horizontal, vertical) = self.widget_anchors[i];
+            let size = _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .get_size(CONFIG_SIZE);
+            let (x, w) = match horizontal {
+                HorizontalAnchor::Left(offset) => anchor_span(
+                    left,
+                    width,
+                    size[SIZE_WIDTH] as i32,
+                    Some(offset),
+                    None,
+                    None,
+                ),
+                HorizontalAnchor::Right(offset) => anchor_span(
+                    left,
+                    width,
+                    size[SIZE_WIDTH] as i32,
+                    None,
+                    Some(offset),
+                    None,
+                ),
+                HorizontalAnchor::Center(offset) => anchor_span(
+                    left,
+                    width,
+                    size[SIZE_WIDTH] as i32,
+                    None,
+                    None,
+                    Some(offset),
+                ),
+                HorizontalAnchor::Stretch(near, far) => anchor_span(
+                    left,
+                    width,
+                    size[SIZE_WIDTH] as i32,
+                    Some(near),
+                    Some(far),
+                    None,
+                ),
+            };
+            let (y, h) = match vertical {
+                VerticalAnchor::Top(offset) => anchor_span(
+                    top,
+                    height,
+                    size[SIZE_HEIGHT] as i32,
+                    Some(offset),
+                    None,
+                    None,
+                ),
+                VerticalAnchor::Bottom(offset) => anchor_span(
+                    top,
+                    height,
+                    size[SIZE_HEIGHT] as i32,
+                    None,
+                    Some(offset),
+                    None,
+                ),
+                VerticalAnchor::Center(offset) => anchor_span(
+                    top,
+                    height,
+                    size[SIZE_HEIGHT] as i32,
+                    None,
+                    None,
+                    Some(offset),
+                ),
+                VerticalAnchor::Stretch(near, far) => anchor_span(
+                    top,
+                    height,
+                    size[SIZE_HEIGHT] as i32,
+                    Some(near),
+                    Some(far),
+                    None,
+                ),
+            };
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_point(CONFIG_ORIGIN, x, y);
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_size(CONFIG_SIZE, w as u32, h as u32);
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_invalidated(true);
+        }
+
+        self.invalidated = false;
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+    }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `AnchorLayout`, re-anchoring its `Widget`s on the next layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `AnchorLayout`, along with its anchors.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+            self.widget_anchors.remove(i);
+            self.invalidated = true;
+        }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids.clone()
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    /// Extends the `AnchorLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// Pushrod Rendering Library
+// Card Layout Manager
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
+
+/// This is the `CardLayout` storage structure for the `CardLayout` implementation.
+pub struct CardLayout {
+    widget_ids: Vec<i32>,
+    origin: Points,
+    size: Size,
+    padding: PaddingConstraint,
+    current_card: usize,
+    track_window: bool,
+    invalidated: bool,
+}
+
+/// Creates a new `CardLayout` manager.  The first card is shown.
+impl CardLayout {
+    pub fn new(x: i32, y: i32, w: u32, h: u32, padding: PaddingConstraint) -> Self {
+        Self {
+            widget_ids: Vec::new(),
+            origin: vec![x, y],
+            size: vec![w, h],
+            padding,
+            current_card: 0,
+            track_window: false,
+            invalidated: false,
+        }
+    }
+
+    /// Shows the card at `index`, in the order the cards were added, and hides the rest.  Indexes
+    /// past the last card are ignored.
+    pub fn show_card(&mut self, index: usize) {
+        if index < self.widget_ids.len() {
+            self.current_card = index;
+            self.invalidated = true;
+        }
+    }
+
+    /// Retrieves the index of the card that is shown.
+    pub fn get_current_card(&self) -> usize {
+        self.current_card
+    }
+
+    /// Retrieves the number of cards.
+    pub fn card_count(&self) -> usize {
+        self.widget_ids.len()
+    }
+
+    /// Shows the card after the current card, if there is one.
+    pub fn next_card(&mut self) {
+        self.show_card(self.current_card + 1);
+    }
+
+    /// Shows the card before the current card, if there is one.
+    pub fn previous_card(&mut self) {
+        if self.current_card > 0 {
+            self.show_card(self.current_card - 1);
+        }
+    }
+
+    /// Retrieves the bounds of the `CardLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `CardLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+}
+
+/// This is the `Layout` implementation for the `CardLayout` manager.  Each `Widget` added is a
+/// card, and all cards are stacked at the same origin and size, within the padding of the
+/// `Layout`.  Only the current card is shown, and the others are hidden, along with any `Widget`s
+/// that have them as their parent.  Spacing is not used.
+impl Layout for CardLayout {
+    /// Adds a card to the `CardLayout`.  The position is not used.
+    fn insert_widget(&mut self, widget_id: i32, _widget_position: LayoutPosition) {
+        self.widget_ids.push(widget_id);
+        self.invalidated = true;
+    }
+
+    /// Appends a card to the `CardLayout`.
+    fn append_widget(&mut self, widget_id: i32) {
+        self.insert_widget(widget_id, LayoutPosition::new(0, 0));
+    }
+
+    fn set_padding(&mut self, padding: PaddingConstraint) {
+        self.padding = padding;
+        self.invalidated = true;
+    }
+
+    fn get_padding(&self) -> PaddingConstraint {
+        self.padding
+    }
+
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager, stacking every card
+    /// within the padding, and showing only the current card.
+    fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
+        let x = self.origin[0] + self.padding.left;
+        let y = self.origin[1] + self.padding.top;
+        let width = self.size[SIZE_WIDTH] as i32 - self.padding.left - self.padding.right;
+        let height = self.size[SIZE_HEIGHT] as i32 - self.padding.top - self.padding.bottom;
+
+        for (i, widget_id) in self.widget_ids.iter().enumerate() {
+            let mut widget = _widgets[*widget_id as usize].widget.borrow_mut();
+
+            widget.get_config().set_point(CONFIG_ORIGIN, x, y);
+            widget
+                .get_config()
+                .set_size(CONFIG_SIZE, width.max(0) as u32, height.max(0) as u32);
+
+            if i == self.current_card {
+                widget.get_config().show();
+            } else {
+                widget.get_config().hide();
+            }
+
+            widget.get_config().set_invalidated(true);
+        }
+
+        // The base widget is redrawn, so the hidden cards are cleared from the screen.
+        _widgets[0]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .set_invalidated(true);
+
+        self.invalidated = false;
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+    }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `CardLayout`, resizing its cards on the next layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a card from the `CardLayout`.  If the card that was removed was showing, the card
+    /// before it is shown.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+
+            if self.current_card > i || self.current_card >= self.widget_ids.len() {
+                self.current_card = self.current_card.saturating_sub(1);
+            }
+
+            self.invalidated = true;
+        }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids.clone()
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    /// Extends the `CardLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
+}
@@ -0,0 +1,853 @@
+// Pushrod Rendering Library
+// Constraint Layout Manager
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
+
+/// This is the ID used in a `Constraint` to refer to the bounds of the `ConstraintLayout` itself,
+/// inside its padding, rather than to a `Widget`.
+pub const CONSTRAINT_LAYOUT: i32 = -1;
+
+/// This is the weight of the stays that keep each `Widget` where it was, if nothing else moves it.
+/// It is weaker than `Strength::Weak`.
+const STAY_WEIGHT: f64 = 0.001;
+
+/// This is the tolerance used when comparing values in the solver.
+const EPSILON: f64 = 1e-7;
+
+/// This is the total error, in pixels, allowed in the `Required` constraints before they are
+/// considered to conflict.
+const FEASIBILITY_TOLERANCE: f64 = 1e-3;
+
+/// This is the maximum number of pivots performed by the solver in each phase.
+const MAX_PIVOTS: usize = 10_000;
+
+/// This enum is the edge or dimension of a `Widget`, or of the `ConstraintLayout`, that a
+/// `Constraint` refers to.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Attribute {
+    /// The X coordinate of the left edge.
+    Left,
+
+    /// The X coordinate of the right edge.
+    Right,
+
+    /// The Y coordinate of the top edge.
+    Top,
+
+    /// The Y coordinate of the bottom edge.
+    Bottom,
+
+    /// The width.
+    Width,
+
+    /// The height.
+    Height,
+
+    /// The X coordinate of the horizontal center.
+    CenterX,
+
+    /// The Y coordinate of the vertical center.
+    CenterY,
+}
+
+/// This enum is the relationship between the two sides of a `Constraint`.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Relation {
+    /// Both sides are equal.
+    Equal,
+
+    /// The left side is less than or equal to the right side.
+    LessOrEqual,
+
+    /// The left side is greater than or equal to the right side.
+    GreaterOrEqual,
+}
+
+/// This enum is how strongly a `Constraint` is enforced.  `Required` constraints are always met,
+/// and the rest are met as closely as possible, with each strength weighing a thousand times more
+/// than the one below it.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Strength {
+    /// The constraint must be met.
+    Required,
+
+    /// The constraint is met unless it conflicts with a `Required` constraint.
+    Strong,
+
+    /// The constraint is met unless it conflicts with a stronger constraint.
+    Medium,
+
+    /// The constraint is met unless it conflicts with any other constraint.
+    Weak,
+}
+
+impl Strength {
+    /// Returns the weight of an error in a constraint of this strength, or `None` if it is
+    /// `Required`.
+    fn weight(self) -> Option<f64> {
+        match self {
+            Strength::Required => None,
+            Strength::Strong => Some(1_000_000.0),
+            Strength::Medium => Some(1_000.0),
+            Strength::Weak => Some(1.0),
+        }
+    }
+}
+
+/// This is a linear relationship between an attribute of a `Widget` and an attribute of another
+/// `Widget`, in the form `widget.attribute (relation) multiplier * to.attribute + constant`.  For
+/// example, "`A.right + 8 == B.left`" is written as `B.left == 1.0 * A.right + 8`:
+///
+/// ```ignore
+/// Constraint::new(b, Attribute::Left, Relation::Equal)
+///     .to(a, Attribute::Right)
+///     .constant(8.0)
+/// ```
+///
+/// A `Constraint` without a `to` attribute compares against the constant alone, such as
+/// "`B.width >= 100`".  `CONSTRAINT_LAYOUT` can be used in place of a `Widget` ID to refer to the
+/// bounds of the `ConstraintLayout`.
+#[derive(Clone, Debug)]
+pub struct Constraint {
+    pub widget_id: i32,
+    pub attribute: Attribute,
+    pub relation: Relation,
+    pub to: Option<(i32, Attribute)>,
+    pub multiplier: f64,
+    pub constant: f64,
+    pub strength: Strength,
+}
+
+/// This is the implementation of the `Constraint`, which is built up from `new`.
+impl Constraint {
+    /// Creates a new `Required` `Constraint` on `attribute` of the `Widget` with `widget_id`,
+    /// comparing it against a constant of `0`.
+    pub fn new(widget_id: i32, attribute: Attribute, relation: Relation) -> Self {
+        Self {
+            widget_id,
+            attribute,
+            relation,
+            to: None,
+            multiplier: 1.0,
+            constant: 0.0,
+            strength: Strength::Required,
+        }
+    }
+
+    /// Compares against `attribute` of the `Widget` with `widget_id`.
+    pub fn to(mut self, widget_id: i32, attribute: Attribute) -> Self {
+        self.to = Some((widget_id, attribute));
+        self
+    }
+
+    /// Sets the multiplier applied to the `to` attribute.  A multiplier that is not finite, such
+    /// as `NaN`, is rejected, and the previous multiplier is kept.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        if multiplier.is_finite() {
+            self.multiplier = multiplier;
+        }
+
+        self
+    }
+
+    /// Sets the constant added to the right side.  A constant that is not finite, such as `NaN`,
+    /// is rejected, and the previous constant is kept.
+    pub fn constant(mut self, constant: f64) -> Self {
+        if constant.is_finite() {
+            self.constant = constant;
+        }
+
+        self
+    }
+
+    /// Indicates whether the multiplier and constant of this `Constraint` are both finite, so that
+    /// it can be solved.
+    fn is_finite(&self) -> bool {
+        self.multiplier.is_finite() && self.constant.is_finite()
+    }
+
+    /// Sets how strongly this `Constraint` is enforced.
+    pub fn strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+/// This is a linear equation or inequality over the solver's variables, in the form
+/// `sum(coefficient * variable) (relation) rhs`, with the weight of its error.
+struct Row {
+    coefficients: Vec<(usize, f64)>,
+    relation: Relation,
+    rhs: f64,
+    weight: Option<f64>,
+}
+
+/// This is the `ConstraintLayout` storage structure for the `ConstraintLayout` implementation.
+pub struct ConstraintLayout {
+    widget_ids: Vec<i32>,
+    constraints: Vec<Constraint>,
+    origin: Points,
+    size: Size,
+    padding: PaddingConstraint,
+    conflicting: bool,
+    track_window: bool,
+    invalidated: bool,
+}
+
+/// Creates a new `ConstraintLayout` manager.
+impl ConstraintLayout {
+    pub fn new(x: i32, y: i32, w: u32, h: u32, padding: PaddingConstraint) -> Self {
+        Self {
+            widget_ids: Vec::new(),
+            constraints: Vec::new(),
+            origin: vec![x, y],
+            size: vec![w, h],
+            padding,
+            conflicting: false,
+            track_window: false,
+            invalidated: false,
+        }
+    }
+
+    /// Adds a `Constraint`.  Any `Widget`s it refers to that have not been added to this `Layout`
+    /// are added.
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        let mut ids = vec![constraint.widget_id];
+
+        if let Some((widget_id, _)) = constraint.to {
+            ids.push(widget_id);
+        }
+
+        for widget_id in ids {
+            if widget_id != CONSTRAINT_LAYOUT && !self.widget_ids.contains(&widget_id) {
+                self.widget_ids.push(widget_id);
+            }
+        }
+
+        self.constraints.push(constraint);
+        self.invalidated = true;
+    }
+
+    /// Removes all of the `Constraint`s.  The `Widget`s stay where they are.
+    pub fn clear_constraints(&mut self) {
+        self.constraints.clear();
+        self.invalidated = true;
+    }
+
+    /// Retrieves the `Constraint`s, in the order they were added.
+    pub fn get_constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Indicates whether the `Required` constraints could not all be met during the last layout.
+    /// When this happens, the `Widget`s are not moved.
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicting
+    }
+
+    /// Returns the variable coefficients and the constant of `attribute` of the `Widget` with
+    /// `widget_id`, or of the bounds of the `Layout` for `CONSTRAINT_LAYOUT`.
+    fn expression(&self, widget_id: i32, attribute: Attribute) -> (Vec<(usize, f64)>, f64) {
+        if widget_id == CONSTRAINT_LAYOUT {
+            let left = (self.origin[POINT_X] + self.padding.left) as f64;
+            let top = (self.origin[POINT_Y] + self.padding.top) as f64;
+            let width =
+                (self.size[SIZE_WIDTH] as i32 - self.padding.left - self.padding.right) as f64;
+            let height =
+                (self.size[SIZE_HEIGHT] as i32 - self.padding.top - self.padding.bottom) as f64;
+            let value = match attribute {
+                Attribute::Left => left,
+                Attribute::Right => left + width,
+                Attribute::Top => top,
+                Attribute::Bottom => top + height,
+                Attribute::Width => width,
+                Attribute::Height => height,
+                Attribute::CenterX => left + width / 2.0,
+                Attribute::CenterY => top + height / 2.0,
+            };
+
+            return (vec![], value);
+        }
+
+        // Each Widget has four variables: left, top, width and height.
+        let base = self
+            .widget_ids
+            .iter()
+            .position(|id| *id == widget_id)
+            .unwrap()
+            * 4;
+        let coefficients = match attribute {
+            Attribute::Left => vec![(base, 1.0)],
+            Attribute::Right => vec![(base, 1.0), (base + 2, 1.0)],
+            Attribute::Top => vec![(base + 1, 1.0)],
+            Attribute::Bottom => vec![(base + 1, 1.0), (base + 3, 1.0)],
+            Attribute::Width => vec![(base + 2, 1.0)],
+            Attribute::Height => vec![(base + 3, 1.0)],
+            Attribute::CenterX => vec![(base, 1.0), (base + 2, 0.5)],
+            Attribute::CenterY => vec![(base + 1, 1.0), (base + 3, 0.5)],
+        };
+
+        (coefficients, 0.0)
+    }
+
+    /// Converts the `Constraint`s into rows for the solver, along with the stays that keep each
+    /// `Widget` at its current origin and size, and the requirement that sizes are not negative.
+    /// `Constraint`s with a multiplier or constant that is not finite are left out.
+    fn rows(&self, current: &[f64]) -> Vec<Row> {
+        let mut rows = Vec::new();
+
+        for constraint in self.constraints.iter().filter(|c| c.is_finite()) {
+            let (mut coefficients, lhs_constant) =
+                self.expression(constraint.widget_id, constraint.attribute);
+            let mut rhs = constraint.constant - lhs_constant;
+
+            if let Some((widget_id, attribute)) = constraint.to {
+                let (to_coefficients, to_constant) = self.expression(widget_id, attribute);
+
+                coefficients.extend(
+                    to_coefficients
+                        .iter()
+                        .map(|(variable, value)| (*variable, -value * constraint.multiplier)),
+                );
+                rhs += to_constant * constraint.multiplier;
+            }
+
+            rows.push(Row {
+                coefficients,
+                relation: constraint.relation,
+                rhs,
+                weight: constraint.strength.weight(),
+            });
+        }
+
+        for (variable, value) in current.iter().enumerate() {
+            if variable % 4 >= 2 {
+                rows.push(Row {
+                    coefficients: vec![(variable, 1.0)],
+                    relation: Relation::GreaterOrEqual,
+                    rhs: 0.0,
+                    weight: None,
+                });
+            }
+
+            rows.push(Row {
+                coefficients: vec![(variable, 1.0)],
+                relation: Relation::Equal,
+                rhs: *value,
+                weight: Some(STAY_WEIGHT),
+            });
+        }
+
+        rows
+    }
+
+    /// Retrieves the bounds of the `ConstraintLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `ConstraintLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+}
+
+/// Pivots the tableau on `row` and `column`, making `column` basic in `row`.
+fn pivot(tableau: &mut [Vec<f64>], basis: &mut [usize], row: usize, column: usize) {
+    let divisor = tableau[row][column];
+
+    for value in tableau[row].iter_mut() {
+        *value /= divisor;
+    }
+
+    for other in 0..tableau.len() {
+        let factor = tableau[other][column];
+
+        if other != row && factor.abs() > EPSILON {
+            for j in 0..tableau[other].len() {
+                tableau[other][j] -= factor * tableau[row][j];
+            }
+        }
+    }
+
+    basis[row] = column;
+}
+
+/// Minimizes `cost` over the tableau, whose last column is the right hand side, using Bland's rule
+/// so that it cannot cycle.  Columns that are `blocked` never enter the basis.  Returns `false` if
+/// the pivot limit is reached.
+fn minimize(tableau: &mut [Vec<f64>], basis: &mut [usize], cost: &[f64], blocked: &[bool]) -> bool {
+    let columns = cost.len();
+
+    for _ in 0..MAX_PIVOTS {
+        let entering = (0..columns).find(|j| {
+            !blocked[*j]
+                && cost[*j]
+                    - (0..tableau.len())
+                        .map(|i| cost[basis[i]] * tableau[i][*j])
+                        .sum::<f64>()
+                    < -EPSILON
+        });
+        let column = match entering {
+            Some(column) => column,
+            None => return true,
+        };
+        let leaving = (0..tableau.len())
+            .filter(|i| tableau[*i][column] > EPSILON)
+            .min_by(|a, b| {
+                let ratio_a = tableau[*a][columns] / tableau[*a][column];
+                let ratio_b = tableau[*b][columns] / tableau[*b][column];
+
+                ratio_a.total_cmp(&ratio_b).then(basis[*a].cmp(&basis[*b]))
+            });
+
+        match leaving {
+            Some(row) => pivot(tableau, basis, row, column),
+            None => return true,
+        }
+    }
+
+    false
+}
+
+/// Solves `rows` over `variables` free variables, minimizing the weighted errors of the rows that
+/// are not required.  This is done with a two-phase simplex: each free variable is split into a
+/// positive and a negative part, inequalities are given slack columns, and rows that are not
+/// required are given positive and negative error columns.  Returns `None` if the required rows
+/// cannot all be met.
+fn solve(variables: usize, rows: &[Row]) -> Option<Vec<f64>> {
+    let mut columns = variables * 2;
+    let mut layout = Vec::new();
+
+    // Each row records its slack and error columns, if it has them.
+    for row in rows {
+        let slack = if row.relation == Relation::Equal {
+            None
+        } else {
+            columns += 1;
+            Some(columns - 1)
+        };
+        let error = row.weight.map(|weight| {
+            columns += 2;
+            (columns - 2, weight)
+        });
+
+        layout.push((slack, error));
+    }
+
+    let artificial = columns;
+    let width = artificial + rows.len() + 1;
+    let mut tableau = vec![vec![0.0; width]; rows.len()];
+    let mut basis: Vec<usize> = (artificial..artificial + rows.len()).collect();
+    let mut cost = vec![0.0; width - 1];
+
+    for (i, row) in rows.iter().enumerate() {
+        for (variable, value) in &row.coefficients {
+            tableau[i][variable * 2] += value;
+            tableau[i][variable * 2 + 1] -= value;
+        }
+
+        match (row.relation, layout[i].0) {
+            (Relation::LessOrEqual, Some(slack)) => tableau[i][slack] = 1.0,
+            (Relation::GreaterOrEqual, Some(slack)) => tableau[i][slack] = -1.0,
+            _ => (),
+        }
+
+        if let Some((error, weight)) = layout[i].1 {
+            tableau[i][error] = 1.0;
+            tableau[i][error + 1] = -1.0;
+            cost[error] = weight;
+            cost[error + 1] = weight;
+        }
+
+        tableau[i][width - 1] = row.rhs;
+
+        if row.rhs < 0.0 {
+            for value in tableau[i].iter_mut() {
+                *value = -*value;
+            }
+        }
+
+        tableau[i][artificial + i] = 1.0;
+    }
+
+    // Phase one finds a solution that meets the rows, by driving the artificial columns to zero.
+    let phase_one: Vec<f64> = (0..width - 1)
+        .map(|j| if j >= artificial { 1.0 } else { 0.0 })
+        .collect();
+    let unblocked = vec![false; width - 1];
+
+    if !minimize(&mut tableau, &mut basis, &phase_one, &unblocked) {
+        return None;
+    }
+
+    let infeasibility: f64 = (0..rows.len())
+        .filter(|i| basis[*i] >= artificial)
+        .map(|i| tableau[i][width - 1])
+        .sum();
+
+    if infeasibility > FEASIBILITY_TOLERANCE {
+        return None;
+    }
+
+    // Artificial columns left in the basis at zero are pivoted out where possible.
+    for i in 0..rows.len() {
+        if basis[i] >= artificial {
+            if let Some(column) = (0..artificial).find(|j| tableau[i][*j].abs() > EPSILON) {
+                pivot(&mut tableau, &mut basis, i, column);
+            }
+        }
+    }
+
+    // Phase two minimizes the weighted errors, never letting an artificial column return.
+    let blocked: Vec<bool> = (0..width - 1).map(|j| j >= artificial).collect();
+
+    if !minimize(&mut tableau, &mut basis, &cost, &blocked) {
+        return None;
+    }
+
+    let mut values = vec![0.0; variables * 2];
+
+    for (i, column) in basis.iter().enumerate() {
+        if *column < variables * 2 {
+            values[*column] = tableau[i][width - 1];
+        }
+    }
+
+    Some(
+        (0..variables)
+            .map(|variable| values[variable * 2] - values[variable * 2 + 1])
+            .collect(),
+    )
+}
+
+/// This is the `Layout` implementation for the `ConstraintLayout` manager.  The origin and size of
+/// each `Widget` are solved from the `Constraint`s between their edges, in the style of the
+/// Cassowary constraint solver: `Required` constraints are always met, and the rest are met as
+/// closely as their strength allows.  Anything the `Constraint`s leave free stays where it was.
+impl Layout for ConstraintLayout {
+    /// Adds a widget to the `ConstraintLayout`.  The position is not used, as `Widget`s are placed
+    /// by their `Constraint`s.
+    fn insert_widget(&mut self, widget_id: i32, _widget_position: LayoutPosition) {
+        if !self.widget_ids.contains(&widget_id) {
+            self.widget_ids.push(widget_id);
+        }
+
+        self.invalidated = true;
+    }
+
+    /// Appends a widget to the `ConstraintLayout`.
+    fn append_widget(&mut self, widget_id: i32) {
+        self.insert_widget(widget_id, LayoutPosition::new(0, 0));
+    }
+
+    fn set_padding(&mut self, padding: PaddingConstraint) {
+        self.padding = padding;
+        self.invalidated = true;
+    }
+
+    fn get_padding(&self) -> PaddingConstraint {
+        self.padding
+    }
+
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager, solving their origins
+    /// and sizes from the `Constraint`s.  If the `Required` constraints conflict, or the `Layout`
+    /// refers to a `Widget` that is not in `_widgets`, the `Widget`s are not moved.
+    fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
+        self.invalidated = false;
+
+        if self
+            .widget_ids
+            .iter()
+            .any(|widget_id| *widget_id < 0 || *widget_id as usize >= _widgets.len())
+        {
+            eprintln!("A ConstraintLayout refers to a Widget that does not exist");
+            return;
+        }
+
+        let mut current = Vec::new();
+
+        for widget_id in &self.widget_ids {
+            let mut widget = _widgets[*widget_id as usize].widget.borrow_mut();
+            let origin = widget.get_config().get_point(CONFIG_ORIGIN);
+            let size = widget.get_config().get_size(CONFIG_SIZE);
+
+            current.extend(vec![
+                origin[POINT_X] as f64,
+                origin[POINT_Y] as f64,
+                size[SIZE_WIDTH] as f64,
+                size[SIZE_HEIGHT] as f64,
+            ]);
+        }
+
+        let values = match solve(current.len(), &self.rows(&current)) {
+            Some(values) => values,
+            None => {
+                eprintln!("Unable to meet the required constraints of a ConstraintLayout");
+                self.conflicting = true;
+                return;
+            }
+        };
+
+        self.conflicting = false;
+
+        for (i, widget_id) in self.widget_ids.iter().enumerate() {
+            let value = |offset: usize| values[i * 4 + offset].round() as i32;
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_point(CONFIG_ORIGIN, value(0), value(1));
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_size(CONFIG_SIZE, value(2).max(0) as u32, value(3).max(0) as u32);
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_invalidated(true);
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+    }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `ConstraintLayout`, solving its `Constraint`s again on the next layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `ConstraintLayout`, along with the `Constraint`s that refer to it.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+            self.constraints.retain(|constraint| {
+                constraint.widget_id != widget_id
+                    && constraint.to.map(|(to_id, _)| to_id) != Some(widget_id)
+            });
+            self.invalidated = true;
+        }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids.clone()
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    /// Extends the `ConstraintLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        coefficients: Vec<(usize, f64)>,
+        relation: Relation,
+        rhs: f64,
+        strength: Strength,
+    ) -> Row {
+        Row {
+            coefficients,
+            relation,
+            rhs,
+            weight: strength.weight(),
+        }
+    }
+
+    fn assert_values(values: Option<Vec<f64>>, expected: &[f64]) {
+        let values = values.expect("the rows should be solvable");
+
+        assert_eq!(values.len(), expected.len());
+
+        for (value, expected) in values.iter().zip(expected) {
+            assert!(
+                (value - expected).abs() < 1e-6,
+                "{:?} != {:?}",
+                values,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_equality() {
+        let rows = vec![
+            row(vec![(0, 1.0)], Relation::Equal, 10.0, Strength::Required),
+            row(
+                vec![(1, 1.0), (0, -1.0)],
+                Relation::Equal,
+                5.0,
+                Strength::Required,
+            ),
+        ];
+
+        assert_values(solve(2, &rows), &[10.0, 15.0]);
+    }
+
+    #[test]
+    fn test_negative_values() {
+        let rows = vec![
+            row(vec![(0, 1.0)], Relation::Equal, -25.0, Strength::Required),
+            row(
+                vec![(1, 2.0), (0, -1.0)],
+                Relation::Equal,
+                5.0,
+                Strength::Required,
+            ),
+        ];
+
+        assert_values(solve(2, &rows), &[-25.0, -10.0]);
+    }
+
+    #[test]
+    fn test_inequality() {
+        let at_least = vec![
+            row(
+                vec![(0, 1.0)],
+                Relation::GreaterOrEqual,
+                20.0,
+                Strength::Required,
+            ),
+            row(vec![(0, 1.0)], Relation::Equal, 5.0, Strength::Weak),
+        ];
+        let at_most = vec![
+            row(
+                vec![(0, 1.0)],
+                Relation::LessOrEqual,
+                30.0,
+                Strength::Required,
+            ),
+            row(vec![(0, 1.0)], Relation::Equal, 50.0, Strength::Weak),
+        ];
+        let satisfied = vec![
+            row(
+                vec![(0, 1.0)],
+                Relation::LessOrEqual,
+                30.0,
+                Strength::Required,
+            ),
+            row(vec![(0, 1.0)], Relation::Equal, 12.0, Strength::Weak),
+        ];
+
+        assert_values(solve(1, &at_least), &[20.0]);
+        assert_values(solve(1, &at_most), &[30.0]);
+        assert_values(solve(1, &satisfied), &[12.0]);
+    }
+
+    #[test]
+    fn test_strength_priority() {
+        let strong_over_weak = vec![
+            row(vec![(0, 1.0)], Relation::Equal, 20.0, Strength::Weak),
+            row(vec![(0, 1.0)], Relation::Equal, 10.0, Strength::Strong),
+        ];
+        let medium_over_weak = vec![
+            row(vec![(0, 1.0)], Relation::Equal, 20.0, Strength::Weak),
+            row(vec![(0, 1.0)], Relation::Equal, 10.0, Strength::Medium),
+        ];
+        let required_over_strong = vec![
+            row(vec![(0, 1.0)], Relation::Equal, 10.0, Strength::Strong),
+            row(
+                vec![(0, 1.0)],
+                Relation::GreaterOrEqual,
+                40.0,
+                Strength::Required,
+            ),
+        ];
+
+        assert_values(solve(1, &strong_over_weak), &[10.0]);
+        assert_values(solve(1, &medium_over_weak), &[10.0]);
+        assert_values(solve(1, &required_over_strong), &[40.0]);
+    }
+
+    #[test]
+    fn test_conflict() {
+        let equalities = vec![
+            row(vec![(0, 1.0)], Relation::Equal, 10.0, Strength::Required),
+            row(vec![(0, 1.0)], Relation::Equal, 20.0, Strength::Required),
+        ];
+        let inequalities = vec![
+            row(
+                vec![(0, 1.0)],
+                Relation::GreaterOrEqual,
+                30.0,
+                Strength::Required,
+            ),
+            row(
+                vec![(0, 1.0)],
+                Relation::LessOrEqual,
+                20.0,
+                Strength::Required,
+            ),
+        ];
+
+        assert!(solve(1, &equalities).is_none());
+        assert!(solve(1, &inequalities).is_none());
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        let constraint = Constraint::new(1, Attribute::Width, Relation::Equal)
+            .constant(50.0)
+            .constant(f64::NAN)
+            .multiplier(f64::INFINITY);
+
+        assert_eq!(constraint.constant, 50.0);
+        assert_eq!(constraint.multiplier, 1.0);
+
+        let mut layout = ConstraintLayout::new(0, 0, 100, 100, PaddingConstraint::default());
+        let mut constraint = Constraint::new(1, Attribute::Width, Relation::Equal);
+
+        constraint.constant = f64::NAN;
+        layout.add_constraint(constraint);
+
+        // Only the stays and the non-negative sizes of the Widget are left.
+        let rows = layout.rows(&[0.0, 0.0, 10.0, 10.0]);
+
+        assert_eq!(rows.len(), 6);
+        assert!(rows.iter().all(|row| row.rhs.is_finite()));
+    }
+}
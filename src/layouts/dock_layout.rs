@@ -0,0 +1,238 @@
+// Pushrod Rendering Library
+// Dock Layout Manager
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
+
+/// This enum is the region of a `DockLayout` that a `Widget` is docked to.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum DockRegion {
+    /// Docked along the top edge, keeping its height and spanning the full width.
+    North,
+
+    /// Docked along the bottom edge, keeping its height and spanning the full width.
+    South,
+
+    /// Docked along the right edge, keeping its width and spanning the height between the `North`
+    /// and `South` regions.
+    East,
+
+    /// Docked along the left edge, keeping its width and spanning the height between the `North`
+    /// and `South` regions.
+    West,
+
+    /// Filling the space left over by the edge regions.
+    Center,
+}
+
+/// This is the `DockLayout` storage structure for the `DockLayout` implementation.
+pub struct DockLayout {
+    widget_ids: Vec<i32>,
+    widget_regions: Vec<DockRegion>,
+    origin: Points,
+    size: Size,
+    padding: PaddingConstraint,
+    track_window: bool,
+    invalidated: bool,
+}
+
+/// Creates a new `DockLayout` manager.
+impl DockLayout {
+    pub fn new(x: i32, y: i32, w: u32, h: u32, padding: PaddingConstraint) -> Self {
+        Self {
+            widget_ids: Vec::new(),
+            widget_regions: Vec::new(),
+            origin: vec![x, y],
+            size: vec![w, h],
+            padding,
+            track_window: false,
+            invalidated: false,
+        }
+    }
+
+    /// Adds a widget to the `DockLayout`, docked to `region`.
+    pub fn insert_widget_docked(&mut self, widget_id: i32, region: DockRegion) {
+        self.widget_ids.push(widget_id);
+        self.widget_regions.push(region);
+        self.invalidated = true;
+    }
+
+    /// Retrieves the bounds of the `DockLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `DockLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+}
+
+/// This is the `Layout` implementation for the `DockLayout` manager, the classic border layout.
+/// `North` and `South` `Widget`s are placed first, spanning the full width at their own heights,
+/// then `West` and `East` `Widget`s between them at their own widths, and finally the `Center`
+/// `Widget`s fill the space that remains.  Several `Widget`s in the same edge region are stacked
+/// inward, in the order they were added, and several `Center` `Widget`s share the same space.
+/// `spacing` pixels are left between the regions.
+impl Layout for DockLayout {
+    /// Adds a widget to the `Center` of the `DockLayout`.  The position is not used.
+    fn insert_widget(&mut self, widget_id: i32, _widget_position: LayoutPosition) {
+        self.insert_widget_docked(widget_id, DockRegion::Center);
+    }
+
+    /// Appends a widget to the `Center` of the `DockLayout`.
+    fn append_widget(&mut self, widget_id: i32) {
+        self.insert_widget_docked(widget_id, DockRegion::Center);
+    }
+
+    fn set_padding(&mut self, padding: PaddingConstraint) {
+        self.padding = padding;
+        self.invalidated = true;
+    }
+
+    fn get_padding(&self) -> PaddingConstraint {
+        self.padding
+    }
+
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager, docking each `Widget`
+    /// to its region within the padding.
+    fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
+        let spacing = self.padding.spacing.max(0);
+        let mut left = self.origin[0] + self.padding.left;
+        let mut top = self.origin[1] + self.padding.top;
+        let mut right = self.origin[0] + self.size[SIZE_WIDTH] as i32 - self.padding.right;
+        let mut bottom = self.origin[1] + self.size[SIZE_HEIGHT] as i32 - self.padding.bottom;
+        let mut bounds = vec![Vec::new(); self.widget_ids.len()];
+
+        for pass in &[
+            [DockRegion::North, DockRegion::South],
+            [DockRegion::West, DockRegion::East],
+        ] {
+            for (i, widget_id) in self.widget_ids.iter().enumerate() {
+                let region = self.widget_regions[i];
+
+                if !pass.contains(&region) {
+                    continue;
+                }
+
+                let size = _widgets[*widget_id as usize]
+                    .widget
+                    .borrow_mut()
+                    .get_config()
+                    .get_size(CONFIG_SIZE);
+                let width = size[SIZE_WIDTH] as i32;
+                let height = size[SIZE_HEIGHT] as i32;
+
+                bounds[i] = match region {
+                    DockRegion::North => {
+                        top += height + spacing;
+                        vec![left, top - height - spacing, right - left, height]
+                    }
+                    DockRegion::South => {
+                        bottom -= height + spacing;
+                        vec![left, bottom + spacing, right - left, height]
+                    }
+                    DockRegion::West => {
+                        left += width + spacing;
+                        vec![left - width - spacing, top, width, bottom - top]
+                    }
+                    _ => {
+                        right -= width + spacing;
+                        vec![right + spacing, top, width, bottom - top]
+                    }
+                };
+            }
+        }
+
+        for (i, widget_id) in self.widget_ids.iter().enumerate() {
+            if self.widget_regions[i] == DockRegion::Center {
+                bounds[i] = vec![left, top, right - left, bottom - top];
+            }
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_point(CONFIG_ORIGIN, bounds[i][0], bounds[i][1]);
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_size(
+                    CONFIG_SIZE,
+                    bounds[i][2].max(0) as u32,
+                    bounds[i][3].max(0) as u32,
+                );
+
+            _widgets[*widget_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_invalidated(true);
+        }
+
+        self.invalidated = false;
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+    }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `DockLayout`, re-docking its `Widget`s on the next layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `DockLayout`, along with its region.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+            self.widget_regions.remove(i);
+            self.invalidated = true;
+        }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids.clone()
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    /// Extends the `DockLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
+}
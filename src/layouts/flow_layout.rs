@@ -0,0 +1,205 @@
+// Pushrod Rendering Library
+// Flow Layout Manager
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::layout::{
+    Layout, LayoutAlignment, LayoutDescription, LayoutItem, LayoutPosition, LayoutSize,
+};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
+
+/// This is the `FlowLayout` storage structure for the `FlowLayout` implementation.
+pub struct FlowLayout {
+    widget_ids: Vec<i32>,
+    widget_positions: Vec<LayoutPosition>,
+    origin: Points,
+    size: Size,
+    padding: PaddingConstraint,
+    track_window: bool,
+    invalidated: bool,
+}
+
+/// Creates a new `FlowLayout` manager.
+impl FlowLayout {
+    pub fn new(x: i32, y: i32, w: u32, h: u32, padding: PaddingConstraint) -> Self {
+        Self {
+            widget_ids: Vec::new(),
+            widget_positions: Vec::new(),
+            origin: vec![x, y],
+            size: vec![w, h],
+            padding,
+            track_window: false,
+            invalidated: false,
+        }
+    }
+
+    /// Retrieves the bounds of the `FlowLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `FlowLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+}
+
+/// This is the `Layout` implementation for the `FlowLayout` manager.  This `Layout` manager keeps
+/// the size of each `Widget`, and places them left to right, with `spacing` pixels between them.
+/// When a `Widget` does not fit in the remainder of a row, it is wrapped to the start of a new
+/// row, below the tallest `Widget` of the row before it.  `Widget`s are placed in the order of the
+/// `y` value of their `LayoutPosition`, which is their index in the flow.
+impl Layout for FlowLayout {
+    /// Adds a widget to the `FlowLayout` at the index `y` of `widget_position`.
+    fn insert_widget(&mut self, widget_id: i32, widget_position: LayoutPosition) {
+        self.widget_ids.push(widget_id);
+        self.widget_positions.push(widget_position);
+        self.invalidated = true;
+    }
+
+    /// Appends a widget to the end of the `FlowLayout`.
+    fn append_widget(&mut self, widget_id: i32) {
+        let positions = self.widget_positions.len();
+        let widget_position = if self.widget_positions.is_empty() {
+            LayoutPosition::new(0, 0)
+        } else {
+            LayoutPosition::new(0, self.widget_positions[positions - 1].y + 1)
+        };
+
+        self.insert_widget(widget_id, widget_position);
+    }
+
+    fn set_padding(&mut self, padding: PaddingConstraint) {
+        self.padding = padding;
+        self.invalidated = true;
+    }
+
+    fn get_padding(&self) -> PaddingConstraint {
+        self.padding
+    }
+
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager, moving each `Widget`
+    /// to its place in the flow within the padding.  `Widget`s are not resized.
+    fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
+        let left = self.origin[0] + self.padding.left;
+        let right = self.origin[0] + self.size[SIZE_WIDTH] as i32 - self.padding.right;
+        let spacing = self.padding.spacing.max(0);
+        let mut order: Vec<usize> = (0..self.widget_ids.len()).collect();
+        let mut x = left;
+        let mut y = self.origin[1] + self.padding.top;
+        let mut row_height = 0;
+
+        order.sort_by_key(|i| self.widget_positions[*i].y);
+
+        for i in order {
+            let widget_id = self.widget_ids[i] as usize;
+            let size = _widgets[widget_id]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .get_size(CONFIG_SIZE);
+
+            if x > left && x + size[SIZE_WIDTH] as i32 > right {
+                x = left;
+                y += row_height + spacing;
+                row_height = 0;
+            }
+
+            _widgets[widget_id]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_point(CONFIG_ORIGIN, x, y);
+
+            _widgets[widget_id]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_invalidated(true);
+
+            x += size[SIZE_WIDTH] as i32 + spacing;
+            row_height = row_height.max(size[SIZE_HEIGHT] as i32);
+        }
+
+        self.invalidated = false;
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+    }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `FlowLayout`, reflowing its `Widget`s on the next layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `FlowLayout`.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+            self.widget_positions.remove(i);
+            self.invalidated = true;
+        }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids.clone()
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    /// Describes this `Layout` as a `flow` layout.
+    fn describe(&self) -> Option<LayoutDescription> {
+        Some(LayoutDescription {
+            layout_type: String::from("flow"),
+            origin: self.origin.clone(),
+            size: self.size.clone(),
+            layout: vec![],
+            items: (0..self.widget_ids.len())
+                .map(|i| LayoutItem {
+                    widget_id: self.widget_ids[i],
+                    position: self.widget_positions[i],
+                    size: LayoutSize::Weight(1),
+                    span: vec![1, 1],
+                    alignment: (LayoutAlignment::Fill, LayoutAlignment::Fill),
+                })
+                .collect(),
+            track_window: self.track_window,
+        })
+    }
+
+    /// Extends the `FlowLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
+}
@@ -13,64 +13,201 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::layout::{
+    place_cells, Layout, LayoutAlignment, LayoutCell, LayoutDescription, LayoutItem,
+    LayoutPosition, LayoutSize, ParallelLayout,
+};
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::PaddingConstraint;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
 
 /// This is the `GridLayout` storage structure for the `GridLayout` implementation.
 pub struct GridLayout {
     widget_ids: Vec<i32>,
-    //    widget_positions: Vec<LayoutPosition>,
-    //    origin: Points,
-    //    size: Size,
+    widget_positions: Vec<LayoutPosition>,
+    widget_spans: Vec<Size>,
+    widget_alignments: Vec<(LayoutAlignment, LayoutAlignment)>,
+    origin: Points,
+    size: Size,
     padding: PaddingConstraint,
-    //    layout: Vec<i32>,
+    layout: Vec<i32>,
+    track_window: bool,
     invalidated: bool,
 }
 
-/// Creates a new `GridLayout` manager.
+/// Creates a new `GridLayout` manager.  `layout` is the number of columns and rows in the grid, as
+/// `vec![columns, rows]`; both are at least `1`.
 impl GridLayout {
     pub fn new(
-        _x: i32,
-        _y: i32,
-        _w: u32,
-        _h: u32,
-        _layout: Vec<i32>,
+        x: i32,
+        y: i32,
+        w: u32,
+        h: u32,
+        layout: Vec<i32>,
         padding: PaddingConstraint,
     ) -> Self {
+        let columns = layout.first().copied().unwrap_or(1).max(1);
+        let rows = layout.get(1).copied().unwrap_or(1).max(1);
+
         Self {
             widget_ids: Vec::new(),
-            //            widget_positions: Vec::new(),
-            //            origin: vec![x, y],
-            //            size: vec![w, h],
+            widget_positions: Vec::new(),
+            widget_spans: Vec::new(),
+            widget_alignments: Vec::new(),
+            origin: vec![x, y],
+            size: vec![w, h],
             padding,
-            //            layout,
+            layout: vec![columns, rows],
+            track_window: false,
             invalidated: false,
         }
     }
+
+    /// Adds a widget to the `GridLayout`, with its top left cell at `widget_position`, spanning
+    /// `columns` columns and `rows` rows.  Spans are at least `1`, and are cut off at the right
+    /// edge of the grid.
+    pub fn insert_widget_spanning(
+        &mut self,
+        widget_id: i32,
+        widget_position: LayoutPosition,
+        columns: u32,
+        rows: u32,
+    ) {
+        self.widget_ids.push(widget_id);
+        self.widget_positions.push(widget_position);
+        self.widget_spans.push(vec![columns.max(1), rows.max(1)]);
+        self.widget_alignments
+            .push((LayoutAlignment::Fill, LayoutAlignment::Fill));
+        self.invalidated = true;
+    }
+
+    /// Sets how the `Widget` with `widget_id` is aligned within the cells it covers, horizontally
+    /// and vertically.  `Widget`s fill their cells by default.
+    pub fn set_widget_alignment(
+        &mut self,
+        widget_id: i32,
+        horizontal: LayoutAlignment,
+        vertical: LayoutAlignment,
+    ) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_alignments[i] = (horizontal, vertical);
+            self.invalidated = true;
+        }
+    }
+
+    /// Retrieves the number of columns and rows in the grid, as `vec![columns, rows]`.
+    pub fn get_layout(&self) -> Vec<i32> {
+        self.layout.clone()
+    }
+
+    /// Indicates whether the cell at `column`, `row` is covered by a `Widget`, including the cells
+    /// covered by its span.
+    fn is_occupied(&self, column: i32, row: i32) -> bool {
+        self.widget_positions
+            .iter()
+            .zip(self.widget_spans.iter())
+            .any(|(position, span)| {
+                column >= position.x
+                    && column < position.x + span[SIZE_WIDTH] as i32
+                    && row >= position.y
+                    && row < position.y + span[SIZE_HEIGHT] as i32
+            })
+    }
+
+    /// Returns the number of rows to lay out: the configured number of rows, or more if `Widget`s
+    /// have been placed below them.
+    fn row_count(&self) -> i32 {
+        self.widget_positions
+            .iter()
+            .zip(self.widget_spans.iter())
+            .map(|(position, span)| position.y + span[SIZE_HEIGHT] as i32)
+            .fold(self.layout[1], i32::max)
+    }
+
+    /// Retrieves the bounds of the `GridLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `GridLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
 }
 
-/// This is the `Layout` implementation for the `GridLayout` manager.  This `Layout` manager will
-/// not reposition any objects within the bounds of the `Layout` until at least 2 objects have been
-/// added to the bounds of the `Layout`.
+/// Returns the offset of the edge before cell `index` of `count` cells, spread evenly over `length`
+/// pixels less `spacing` pixels between each cell.
+fn cell_edge(index: i32, count: i32, length: i32, spacing: i32) -> i32 {
+    let cells = (length - spacing * (count - 1)).max(0) as f64;
+
+    (cells * index as f64 / count as f64).round() as i32 + spacing * index
+}
+
+/// This is the `ParallelLayout` implementation for the `GridLayout` manager, which computes the
+/// cells covered by its `Widget`s without reading them.
+impl ParallelLayout for GridLayout {
+    fn compute_cells(&mut self) -> Vec<LayoutCell> {
+        let columns = self.layout[0];
+        let rows = self.row_count();
+        let spacing = self.padding.spacing.max(0);
+        let offset_x = self.origin[0] + self.padding.left;
+        let offset_y = self.origin[1] + self.padding.top;
+        let width = self.size[SIZE_WIDTH] as i32 - self.padding.left - self.padding.right;
+        let height = self.size[SIZE_HEIGHT] as i32 - self.padding.top - self.padding.bottom;
+
+        let mut cells = Vec::new();
+
+        for (i, widget_id) in self.widget_ids.iter().enumerate() {
+            let position = &self.widget_positions[i];
+            let column = position.x.max(0).min(columns - 1);
+            let row = position.y.max(0);
+            let end_column = (column + self.widget_spans[i][SIZE_WIDTH] as i32).min(columns);
+            let end_row = row + self.widget_spans[i][SIZE_HEIGHT] as i32;
+            let left = cell_edge(column, columns, width, spacing);
+            let top = cell_edge(row, rows, height, spacing);
+            let right = cell_edge(end_column, columns, width, spacing) - spacing;
+            let bottom = cell_edge(end_row, rows, height, spacing) - spacing;
+
+            cells.push(LayoutCell {
+                widget_id: *widget_id,
+                bounds: (offset_x + left, offset_y + top, right - left, bottom - top),
+                alignment: self.widget_alignments[i],
+            });
+        }
+
+        self.invalidated = false;
+        cells
+    }
+}
+
+/// This is the `Layout` implementation for the `GridLayout` manager.  Each `Widget` is placed in
+/// the cell at its `LayoutPosition`, where `x` is the column and `y` is the row, counting from `0`,
+/// and is sized to cover the cells it spans, unless it is aligned within them.  The cells share the
+/// area inside the padding evenly, with `spacing` pixels between them.  Appended `Widget`s fill the
+/// first free cell, left to right, then top to bottom; once the grid is full, further rows are
+/// added below it, and the rows are resized to fit.
 impl Layout for GridLayout {
-    /// Adds a widget to the `HorizontalLayout` managed stack.
-    fn insert_widget(&mut self, _widget_id: i32, _widget_position: LayoutPosition) {
-        //        self.widget_ids.push(widget_id);
-        //        self.widget_positions.push(widget_position);
-        //        self.invalidated = true;
+    /// Adds a widget to the `GridLayout` at the cell `widget_position`, spanning a single cell.
+    fn insert_widget(&mut self, widget_id: i32, widget_position: LayoutPosition) {
+        self.insert_widget_spanning(widget_id, widget_position, 1, 1);
     }
 
-    /// Appends a widget to the `HorizontalLayout` managed stack.
-    fn append_widget(&mut self, _widget_id: i32) {
-        //        let positions = self.widget_positions.len();
-        //        let widget_position = if self.widget_positions.is_empty() {
-        //            LayoutPosition::new(0, 0)
-        //        } else {
-        //            LayoutPosition::new(0, self.widget_positions[positions - 1].y + 1)
-        //        };
-        //
-        //        self.insert_widget(widget_id, widget_position);
+    /// Appends a widget to the first free cell of the `GridLayout`.
+    fn append_widget(&mut self, widget_id: i32) {
+        let columns = self.layout[0];
+        let mut cell = 0;
+
+        while self.is_occupied(cell % columns, cell / columns) {
+            cell += 1;
+        }
+
+        self.insert_widget(
+            widget_id,
+            LayoutPosition::new(cell % columns, cell / columns),
+        );
     }
 
     fn set_padding(&mut self, padding: PaddingConstraint) {
@@ -82,74 +219,81 @@ impl Layout for GridLayout {
         self.padding
     }
 
-    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager.  Currently only obeys
-    /// the spacing in the object.  The rest of the padding is not (yet) honored.
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager, placing each `Widget`
+    /// over the cells it covers, within the padding of the `Layout`.
     fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
-        if self.widget_ids.len() <= 1 {
-            return;
-        }
-
-        //        let offset_x: i32 = self.origin[0];
-        //        let offset_y: i32 = self.origin[1] + self.padding.top;
-        //        let num_widgets = self.widget_ids.len() as u32;
-        //        let widget_width = self.size[SIZE_WIDTH] / num_widgets as u32;
-        //        let widget_height = self.size[SIZE_HEIGHT] / num_widgets as u32;
-        //        let subtractor_right = ((self.padding.spacing as f64 / 2.0).ceil()) as u32;
-        //        let subtractor_left = ((self.padding.spacing as f64 / 2.0).floor()) as u32;
-        //        let subtractor_bottom = ((self.padding.spacing as f64 / 2.0).ceil()) as u32;
-        //        let subtractor_top = ((self.padding.spacing as f64 / 2.0).floor()) as u32;
-        //
-        //        for i in 0..num_widgets {
-        //            let set_x: i32;
-        //            let set_y: i32;
-        //            let mut set_height: u32 = widget_height;
-        //            let mut set_width: u32 = widget_width;
-        //            let widget_id = self.widget_ids[i as usize];
-        //
-        //            if i == 0 {
-        //                set_x = (i * set_width) as i32 + self.padding.left;
-        //                set_y = (i * set_height) as i32 + self.padding.top;
-        //                set_height = widget_height - subtractor_bottom - self.padding.top as u32;
-        //                set_width = widget_width - subtractor_right - self.padding.left as u32;
-        //            } else if i == num_widgets - 1 {
-        //                set_x = (i * set_width) as i32 + subtractor_left as i32;
-        //                set_y = (i * set_height) as i32 + subtractor_top as i32;
-        //                set_height = widget_height - subtractor_top - self.padding.bottom as u32;
-        //                set_width = widget_width - subtractor_left - self.padding.right as u32;
-        //            } else {
-        //                set_x = (i * set_width) as i32 + subtractor_left as i32;
-        //                set_y = (i * set_height) as i32 + subtractor_top as i32;
-        //                set_height = widget_height - subtractor_top - subtractor_bottom;
-        //                set_width = widget_width - subtractor_left - subtractor_right;
-        //            }
-        //
-        //            _widgets[widget_id as usize]
-        //                .widget
-        //                .borrow_mut()
-        //                .get_config()
-        //                .set_point(CONFIG_ORIGIN, offset_x + set_x, offset_y + set_y);
-        //
-        //            _widgets[widget_id as usize]
-        //                .widget
-        //                .borrow_mut()
-        //                .get_config()
-        //                .set_size(
-        //                    CONFIG_SIZE,
-        //                    self.size[SIZE_WIDTH] - self.padding.right as u32 - self.padding.left as u32,
-        //                    self.size[SIZE_HEIGHT] - self.padding.top as u32 - self.padding.bottom as u32,
-        //                );
-        //
-        //            _widgets[widget_id as usize]
-        //                .widget
-        //                .borrow_mut()
-        //                .get_config()
-        //                .set_invalidated(true);
-        //        }
+        let cells = self.compute_cells();
 
-        self.invalidated = false;
+        place_cells(_widgets, cells);
     }
 
     fn needs_layout(&self) -> bool {
         self.invalidated
     }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `GridLayout`, re-placing its `Widget`s on the next layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `GridLayout`, along with its span and alignment.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+            self.widget_positions.remove(i);
+            self.widget_spans.remove(i);
+            self.widget_alignments.remove(i);
+            self.invalidated = true;
+        }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids.clone()
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    fn as_parallel_layout(&mut self) -> Option<&mut dyn ParallelLayout> {
+        Some(self)
+    }
+
+    /// Describes this `Layout` as a `grid` layout, including the spans of its `Widget`s.
+    fn describe(&self) -> Option<LayoutDescription> {
+        Some(LayoutDescription {
+            layout_type: String::from("grid"),
+            origin: self.origin.clone(),
+            size: self.size.clone(),
+            layout: self.layout.clone(),
+            items: (0..self.widget_ids.len())
+                .map(|i| LayoutItem {
+                    widget_id: self.widget_ids[i],
+                    position: self.widget_positions[i],
+                    size: LayoutSize::Weight(1),
+                    span: self.widget_spans[i].clone(),
+                    alignment: self.widget_alignments[i],
+                })
+                .collect(),
+            track_window: self.track_window,
+        })
+    }
+
+    /// Extends the `GridLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
 }
@@ -25,3 +25,31 @@ pub mod vertical_layout;
 
 /// This is a `GridLayout` manager.  It handles the even spacing of `Widget`s in a grid area.
 pub mod grid_layout;
+
+/// This is a `FlowLayout` manager.  It places `Widget`s left to right at their own sizes, wrapping
+/// them to a new row when the width of the layout is exceeded.
+pub mod flow_layout;
+
+/// This is an `AnchorLayout` manager.  It keeps `Widget`s anchored to the edges or center of its
+/// bounds, and can follow the size of the window.
+pub mod anchor_layout;
+
+/// This is a `ConstraintLayout` manager.  It places `Widget`s by solving linear constraints between
+/// their edges.
+pub mod constraint_layout;
+
+/// This is a `DockLayout` manager.  It docks `Widget`s to the edges of its bounds, with a center
+/// `Widget` filling the space that remains.
+pub mod dock_layout;
+
+/// This is a `CardLayout` manager.  It stacks `Widget`s at the same origin and size, showing one
+/// at a time.
+pub mod card_layout;
+
+/// This is a `ResponsiveLayout` manager.  It switches between alternative `Layout`s for the same
+/// `Widget`s depending on its width.
+pub mod responsive_layout;
+
+/// This is an `AbsoluteLayout` manager.  It rescales `Widget`s placed at fixed pixel positions in
+/// proportion to its size.
+pub mod absolute_layout;
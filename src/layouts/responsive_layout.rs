@@ -0,0 +1,242 @@
+// Pushrod Rendering Library
+// Responsive Layout Manager
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::PaddingConstraint;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
+
+/// This is the `ResponsiveLayout` storage structure for the `ResponsiveLayout` implementation.
+pub struct ResponsiveLayout {
+    breakpoints: Vec<(u32, Box<dyn Layout>)>,
+    active_layout: Option<usize>,
+    origin: Points,
+    size: Size,
+    track_window: bool,
+    invalidated: bool,
+}
+
+/// Creates a new `ResponsiveLayout` manager, without any breakpoints.
+impl ResponsiveLayout {
+    pub fn new(x: i32, y: i32, w: u32, h: u32) -> Self {
+        Self {
+            breakpoints: Vec::new(),
+            active_layout: None,
+            origin: vec![x, y],
+            size: vec![w, h],
+            track_window: false,
+            invalidated: false,
+        }
+    }
+
+    /// Adds `layout` as the arrangement used while the `ResponsiveLayout` is at least `min_width`
+    /// pixels wide.  The `Layout` is given the bounds of the `ResponsiveLayout` whenever it is
+    /// used.  A breakpoint with a `min_width` of `0` is used at any width that no other breakpoint
+    /// covers.
+    pub fn add_breakpoint(&mut self, min_width: u32, layout: Box<dyn Layout>) {
+        self.breakpoints.push((min_width, layout));
+        self.breakpoints.sort_by_key(|(min_width, _)| *min_width);
+        self.active_layout = None;
+        self.invalidated = true;
+    }
+
+    /// Retrieves the index of the breakpoint in use, in order of increasing minimum width, or
+    /// `None` if no layout has been performed yet.
+    pub fn get_active_breakpoint(&self) -> Option<usize> {
+        self.active_layout
+    }
+
+    /// Retrieves the bounds of the `ResponsiveLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `ResponsiveLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window, switching breakpoints as needed.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+
+    /// Returns the index of the breakpoint for the current width: the one with the largest
+    /// minimum width that fits, or else the narrowest.
+    fn breakpoint_for_width(&self) -> Option<usize> {
+        if self.breakpoints.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.breakpoints
+                .iter()
+                .rposition(|(min_width, _)| *min_width <= self.size[SIZE_WIDTH])
+                .unwrap_or(0),
+        )
+    }
+}
+
+/// This is the `Layout` implementation for the `ResponsiveLayout` manager.  It holds alternative
+/// `Layout`s for the same `Widget`s, each keyed to a minimum width, and performs the one whose
+/// breakpoint matches its width - such as two columns on a wide screen, and one column on a
+/// narrow one.  The breakpoint is chosen again whenever the bounds change.  `Widget`s that are
+/// only in the `Layout`s not in use are hidden, and shown again when a `Layout` using them is
+/// chosen.  `Widget`s and padding are passed on to every breakpoint's `Layout`.
+impl Layout for ResponsiveLayout {
+    /// Adds a widget to the `Layout` of every breakpoint.
+    fn insert_widget(&mut self, widget_id: i32, widget_position: LayoutPosition) {
+        for (_, layout) in self.breakpoints.iter_mut() {
+            layout.insert_widget(widget_id, widget_position);
+        }
+
+        self.invalidated = true;
+    }
+
+    /// Appends a widget to the `Layout` of every breakpoint.
+    fn append_widget(&mut self, widget_id: i32) {
+        for (_, layout) in self.breakpoints.iter_mut() {
+            layout.append_widget(widget_id);
+        }
+
+        self.invalidated = true;
+    }
+
+    fn set_padding(&mut self, padding: PaddingConstraint) {
+        for (_, layout) in self.breakpoints.iter_mut() {
+            layout.set_padding(padding);
+        }
+
+        self.invalidated = true;
+    }
+
+    /// Retrieves the padding of the `Layout` in use, or of the narrowest breakpoint.
+    fn get_padding(&self) -> PaddingConstraint {
+        self.active_layout
+            .or_else(|| self.breakpoint_for_width())
+            .map(|i| self.breakpoints[i].1.get_padding())
+            .unwrap_or_default()
+    }
+
+    /// Chooses the breakpoint for the current width, and performs its `Layout` within the bounds of
+    /// the `ResponsiveLayout`.
+    fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
+        let active = match self.breakpoint_for_width() {
+            Some(active) => active,
+            None => {
+                self.invalidated = false;
+                return;
+            }
+        };
+
+        if self.active_layout != Some(active) {
+            let active_ids = self.breakpoints[active].1.get_widget_ids();
+
+            for (i, (_, layout)) in self.breakpoints.iter().enumerate() {
+                for widget_id in layout.get_widget_ids() {
+                    let mut widget = _widgets[widget_id as usize].widget.borrow_mut();
+
+                    if i == active || active_ids.contains(&widget_id) {
+                        widget.get_config().show();
+                    } else {
+                        widget.get_config().hide();
+                    }
+                }
+            }
+
+            // The base widget is redrawn, so that hidden widgets are cleared from the screen.
+            _widgets[0]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_invalidated(true);
+
+            self.active_layout = Some(active);
+        }
+
+        let layout = &mut self.breakpoints[active].1;
+
+        layout.set_bounds(
+            self.origin[0],
+            self.origin[1],
+            self.size[SIZE_WIDTH],
+            self.size[SIZE_HEIGHT],
+        );
+        layout.do_layout(_widgets);
+        self.invalidated = false;
+    }
+
+    /// Indicates whether the `ResponsiveLayout`, or the `Layout` in use, needs to be performed.
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+            || self
+                .active_layout
+                .is_some_and(|i| self.breakpoints[i].1.needs_layout())
+    }
+
+    fn invalidate(&mut self) {
+        for (_, layout) in self.breakpoints.iter_mut() {
+            layout.invalidate();
+        }
+
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `ResponsiveLayout`, choosing the breakpoint again on the next
+    /// layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `Layout`s of all of the breakpoints.
+    fn remove_widget(&mut self, widget_id: i32) {
+        for (_, layout) in &mut self.breakpoints {
+            layout.remove_widget(widget_id);
+        }
+
+        self.invalidated = true;
+    }
+
+    /// Retrieves the IDs of the `Widget`s in the `Layout`s of all of the breakpoints.
+    fn get_widget_ids(&self) -> Vec<i32> {
+        let mut widget_ids: Vec<i32> = Vec::new();
+
+        for (_, layout) in &self.breakpoints {
+            for widget_id in layout.get_widget_ids() {
+                if !widget_ids.contains(&widget_id) {
+                    widget_ids.push(widget_id);
+                }
+            }
+        }
+
+        widget_ids
+    }
+
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    /// Extends the `ResponsiveLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
+    }
+}
@@ -13,18 +13,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::render::layout::{Layout, LayoutPosition};
+use crate::render::layout::{
+    distribute_lengths, place_cells, Layout, LayoutAlignment, LayoutCell, LayoutDescription,
+    LayoutItem, LayoutPosition, LayoutSize, ParallelLayout, SPACER_ID,
+};
 use crate::render::widget_cache::WidgetContainer;
-use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::widget_config::PaddingConstraint;
 use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use std::any::Any;
 
 /// This is the `VerticalLayout` storage structure for the `VerticalLayout` implementation.
 pub struct VerticalLayout {
     widget_ids: Vec<i32>,
     widget_positions: Vec<LayoutPosition>,
+    widget_sizes: Vec<LayoutSize>,
+    widget_alignments: Vec<(LayoutAlignment, LayoutAlignment)>,
     origin: Points,
     size: Size,
     padding: PaddingConstraint,
+    track_window: bool,
     invalidated: bool,
 }
 
@@ -34,12 +41,120 @@ impl VerticalLayout {
         Self {
             widget_ids: Vec::new(),
             widget_positions: Vec::new(),
+            widget_sizes: Vec::new(),
+            widget_alignments: Vec::new(),
             origin: vec![x, y],
             size: vec![w, h],
             padding,
+            track_window: false,
             invalidated: false,
         }
     }
+
+    /// Adds a widget to the `VerticalLayout` managed stack, with its height given by `size`.
+    pub fn add_widget_with_size(
+        &mut self,
+        widget_id: i32,
+        widget_position: LayoutPosition,
+        size: LayoutSize,
+    ) {
+        self.widget_ids.push(widget_id);
+        self.widget_positions.push(widget_position);
+        self.widget_sizes.push(size);
+        self.widget_alignments
+            .push((LayoutAlignment::Fill, LayoutAlignment::Fill));
+        self.invalidated = true;
+    }
+
+    /// Sets how the `Widget` with `widget_id` is aligned within the space it is given, horizontally
+    /// and vertically.  `Widget`s fill their space by default.
+    pub fn set_widget_alignment(
+        &mut self,
+        widget_id: i32,
+        horizontal: LayoutAlignment,
+        vertical: LayoutAlignment,
+    ) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_alignments[i] = (horizontal, vertical);
+            self.invalidated = true;
+        }
+    }
+
+    /// Adds a widget to the `VerticalLayout` managed stack, with a `weight` that sets its share of the
+    /// space left over by `Widget`s of a fixed or percentage size.  A `Widget` with a weight of `2`
+    /// is given twice the height of a `Widget` with a weight of `1`, which is the weight given by
+    /// `insert_widget`.
+    pub fn add_widget_with_weight(
+        &mut self,
+        widget_id: i32,
+        widget_position: LayoutPosition,
+        weight: u32,
+    ) {
+        self.add_widget_with_size(widget_id, widget_position, LayoutSize::Weight(weight));
+    }
+
+    /// Adds a widget to the `VerticalLayout` managed stack, with a fixed height of `size` pixels.
+    pub fn add_widget_with_fixed_size(
+        &mut self,
+        widget_id: i32,
+        widget_position: LayoutPosition,
+        size: u32,
+    ) {
+        self.add_widget_with_size(widget_id, widget_position, LayoutSize::Fixed(size));
+    }
+
+    /// Adds a widget to the `VerticalLayout` managed stack, with a height of `percent` percent of the
+    /// `VerticalLayout`, so that it is resized along with the `Layout`.
+    pub fn add_widget_with_percentage(
+        &mut self,
+        widget_id: i32,
+        widget_position: LayoutPosition,
+        percent: f64,
+    ) {
+        self.add_widget_with_size(widget_id, widget_position, LayoutSize::Percent(percent));
+    }
+
+    /// Retrieves the bounds of the `VerticalLayout`, as its origin and size.
+    pub fn get_bounds(&self) -> (Points, Size) {
+        (self.origin.clone(), self.size.clone())
+    }
+
+    /// Sets whether the `VerticalLayout` follows the size of the window.  When set, each time the
+    /// window is resized, the layout keeps its origin, and extends to the right and bottom edges of
+    /// the window.
+    pub fn set_track_window(&mut self, track_window: bool) {
+        self.track_window = track_window;
+    }
+}
+
+/// This is the `ParallelLayout` implementation for the `VerticalLayout` manager, which divides its
+/// length between its items without reading its `Widget`s.
+impl ParallelLayout for VerticalLayout {
+    fn compute_cells(&mut self) -> Vec<LayoutCell> {
+        if self.widget_ids.len() <= 1 {
+            return Vec::new();
+        }
+
+        let offset_x: i32 = self.origin[0] + self.padding.left;
+        let offset_y: i32 = self.origin[1] + self.padding.top;
+        let width = self.size[SIZE_WIDTH] as i32 - self.padding.left - self.padding.right;
+        let height = self.size[SIZE_HEIGHT] as i32 - self.padding.top - self.padding.bottom;
+        let lengths = distribute_lengths(height, self.padding.spacing, &self.widget_sizes);
+
+        let cells = lengths
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| self.widget_ids[*i] != SPACER_ID)
+            .map(|(i, (set_y, set_height))| LayoutCell {
+                widget_id: self.widget_ids[i],
+                bounds: (offset_x, offset_y + set_y, width, set_height as i32),
+                alignment: self.widget_alignments[i],
+            })
+            .collect();
+
+        self.invalidated = false;
+        cells
+    }
 }
 
 /// This is the `Layout` implementation for the `VerticalLayout` manager.  This `Layout` manager will
@@ -48,9 +163,7 @@ impl VerticalLayout {
 impl Layout for VerticalLayout {
     /// Adds a widget to the `VerticalLayout` managed stack.
     fn insert_widget(&mut self, widget_id: i32, widget_position: LayoutPosition) {
-        self.widget_ids.push(widget_id);
-        self.widget_positions.push(widget_position);
-        self.invalidated = true;
+        self.add_widget_with_weight(widget_id, widget_position, 1);
     }
 
     /// Appends a widget to the `VerticalLayout` managed stack.
@@ -74,67 +187,107 @@ impl Layout for VerticalLayout {
         self.padding
     }
 
-    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager.  Currently only obeys
-    /// the spacing in the object.  The rest of the padding is not (yet) honored.
+    /// Adjusts the layout of the `Widget`s managed by this `Layout` manager.  The height inside the
+    /// padding is divided between the `Widget`s by their `LayoutSize`s, with `spacing`
+    /// pixels between them, and each `Widget` fills the width inside the padding, unless it is
+    /// aligned within its space.
     fn do_layout(&mut self, _widgets: &[WidgetContainer]) {
-        if self.widget_ids.len() <= 1 {
-            return;
+        let cells = self.compute_cells();
+
+        place_cells(_widgets, cells);
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.invalidated
+    }
+
+    fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Changes the bounds of the `VerticalLayout`, resizing its `Widget`s on the next layout.
+    fn set_bounds(&mut self, x: i32, y: i32, w: u32, h: u32) {
+        self.origin = vec![x, y];
+        self.size = vec![w, h];
+        self.invalidated = true;
+    }
+
+    /// Removes a widget from the `VerticalLayout` managed stack.
+    fn remove_widget(&mut self, widget_id: i32) {
+        if let Some(i) = self.widget_ids.iter().position(|id| *id == widget_id) {
+            self.widget_ids.remove(i);
+            self.widget_positions.remove(i);
+            self.widget_sizes.remove(i);
+            self.widget_alignments.remove(i);
+            self.invalidated = true;
         }
+    }
+
+    fn get_widget_ids(&self) -> Vec<i32> {
+        self.widget_ids
+            .iter()
+            .filter(|widget_id| **widget_id != SPACER_ID)
+            .copied()
+            .collect()
+    }
 
-        let offset_x: i32 = self.origin[0];
-        let offset_y: i32 = self.origin[1];
-        let num_widgets = self.widget_ids.len() as u32;
-        let widget_height = self.size[SIZE_HEIGHT] / num_widgets as u32;
-        let subtractor_bottom = ((self.padding.spacing as f64 / 2.0).ceil()) as u32;
-        let subtractor_top = ((self.padding.spacing as f64 / 2.0).floor()) as u32;
-
-        for i in 0..num_widgets {
-            let set_y: i32;
-            let mut set_height: u32 = widget_height;
-            let widget_id = self.widget_ids[i as usize];
-
-            if i == 0 {
-                set_y = (i * set_height) as i32 + self.padding.top;
-                set_height = widget_height - subtractor_bottom - self.padding.top as u32;
-            } else if i == num_widgets - 1 {
-                set_y = (i * set_height) as i32 + subtractor_top as i32;
-                set_height = widget_height - subtractor_top - self.padding.bottom as u32;
-            } else {
-                set_y = (i * set_height) as i32 + subtractor_top as i32;
-                set_height = widget_height - subtractor_top - subtractor_bottom;
-            }
-
-            _widgets[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .get_config()
-                .set_point(
-                    CONFIG_ORIGIN,
-                    offset_x + self.padding.left,
-                    offset_y + set_y,
-                );
-
-            _widgets[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .get_config()
-                .set_size(
-                    CONFIG_SIZE,
-                    self.size[SIZE_WIDTH] - self.padding.right as u32 - self.padding.left as u32,
-                    set_height,
-                );
-
-            _widgets[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .get_config()
-                .set_invalidated(true);
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    fn as_parallel_layout(&mut self) -> Option<&mut dyn ParallelLayout> {
+        Some(self)
+    }
+
+    /// Adds a spacer to the end of the managed stack.
+    fn add_spacer(&mut self, flexible: bool) {
+        self.append_widget(SPACER_ID);
+
+        if !flexible {
+            let last = self.widget_sizes.len() - 1;
+
+            self.widget_sizes[last] = LayoutSize::Weight(0);
         }
+    }
 
-        self.invalidated = false;
+    /// Describes this `Layout` as a `vertical` layout, including its spacers and struts.
+    fn describe(&self) -> Option<LayoutDescription> {
+        Some(LayoutDescription {
+            layout_type: String::from("vertical"),
+            origin: self.origin.clone(),
+            size: self.size.clone(),
+            layout: vec![],
+            items: (0..self.widget_ids.len())
+                .map(|i| LayoutItem {
+                    widget_id: self.widget_ids[i],
+                    position: self.widget_positions[i],
+                    size: self.widget_sizes[i],
+                    span: vec![1, 1],
+                    alignment: self.widget_alignments[i],
+                })
+                .collect(),
+            track_window: self.track_window,
+        })
     }
 
-    fn needs_layout(&self) -> bool {
-        self.invalidated
+    /// Adds a strut of `pixels` to the end of the managed stack.
+    fn add_strut(&mut self, pixels: u32) {
+        self.append_widget(SPACER_ID);
+
+        let last = self.widget_sizes.len() - 1;
+
+        self.widget_sizes[last] = LayoutSize::Fixed(pixels);
+    }
+
+    /// Extends the `VerticalLayout` to the right and bottom edges of the window, if it tracks the
+    /// window.
+    fn window_resized(&mut self, size: Size) {
+        if self.track_window {
+            self.size = vec![
+                (size[SIZE_WIDTH] as i32 - self.origin[0]).max(0) as u32,
+                (size[SIZE_HEIGHT] as i32 - self.origin[1]).max(0) as u32,
+            ];
+            self.invalidated = true;
+        }
     }
 }
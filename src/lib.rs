@@ -139,6 +139,80 @@ mod macros {
                     }
                 }
             }
+
+            /// This function is a macro-created key pressed callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn key_pressed_callback(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer], _keycode: sdl2::keyboard::Keycode, _keymod: sdl2::keyboard::Mod, _repeat: bool) {
+                if self.get_callbacks().has_on_key_pressed() {
+                    if let Some(mut cb) = self.get_callbacks().on_key_pressed.take() {
+                        cb(self, _widgets, _layouts, _keycode, _keymod, _repeat);
+                        self.get_callbacks().on_key_pressed = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created key released callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn key_released_callback(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer], _keycode: sdl2::keyboard::Keycode, _keymod: sdl2::keyboard::Mod, _repeat: bool) {
+                if self.get_callbacks().has_on_key_released() {
+                    if let Some(mut cb) = self.get_callbacks().on_key_released.take() {
+                        cb(self, _widgets, _layouts, _keycode, _keymod, _repeat);
+                        self.get_callbacks().on_key_released = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created focus gained callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn focus_gained_callback(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+                if self.get_callbacks().has_on_focus_gained() {
+                    if let Some(mut cb) = self.get_callbacks().on_focus_gained.take() {
+                        cb(self, _widgets, _layouts);
+                        self.get_callbacks().on_focus_gained = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created focus lost callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn focus_lost_callback(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+                if self.get_callbacks().has_on_focus_lost() {
+                    if let Some(mut cb) = self.get_callbacks().on_focus_lost.take() {
+                        cb(self, _widgets, _layouts);
+                        self.get_callbacks().on_focus_lost = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created file dropped callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn file_dropped_callback(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer], _path: String) -> bool {
+                if self.get_callbacks().has_on_file_dropped() {
+                    if let Some(mut cb) = self.get_callbacks().on_file_dropped.take() {
+                        cb(self, _widgets, _layouts, _path);
+                        self.get_callbacks().on_file_dropped = Some(cb);
+
+                        return true;
+                    }
+                }
+
+                false
+            }
+
+            /// This function is a macro-created text dropped callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn text_dropped_callback(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer], _text: String) -> bool {
+                if self.get_callbacks().has_on_text_dropped() {
+                    if let Some(mut cb) = self.get_callbacks().on_text_dropped.take() {
+                        cb(self, _widgets, _layouts, _text);
+                        self.get_callbacks().on_text_dropped = Some(cb);
+
+                        return true;
+                    }
+                }
+
+                false
+            }
         }
     }
 
@@ -165,3 +239,7 @@ pub mod render;
 
 /// `layouts` is the core layout managers included with `Pushrod`.
 pub mod layouts;
+
+/// `testing` contains utilities for testing `Pushrod` applications, such as offscreen rendering and
+/// golden-image snapshot comparisons.
+pub mod testing;
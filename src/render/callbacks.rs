@@ -16,6 +16,7 @@
 use crate::render::layout_cache::LayoutContainer;
 use crate::render::widget::Widget;
 use crate::render::widget_cache::WidgetContainer;
+use sdl2::keyboard::{Keycode, Mod};
 
 /// This is an `FnMut` type that takes no additional parameters, returning a mutable reference
 /// to the current `Widget`, and borrowing the `WidgetContainer` and `LayoutContainer` lists.
@@ -33,6 +34,19 @@ pub type FunctionPointParametersType =
 pub type FunctionClickParametersType =
     Option<Box<dyn FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer], u8, u8, bool)>>;
 
+/// This is an `FnMut` that takes a key code, the modifier keys that were held, and a repeat flag
+/// (`true` indicating the key is being held down and auto-repeating), returning a mutable reference
+/// to the current `Widget`, and borrowing the `WidgetContainer` and `LayoutContainer` lists.
+pub type FunctionKeyParametersType = Option<
+    Box<dyn FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer], Keycode, Mod, bool)>,
+>;
+
+/// This is an `FnMut` that takes a `String`, such as the path of a file or text that was dropped
+/// onto a `Widget`, returning a mutable reference to the current `Widget`, and borrowing the
+/// `WidgetContainer` and `LayoutContainer` lists.
+pub type FunctionStringParametersType =
+    Option<Box<dyn FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer], String)>>;
+
 /// This is a registry that contains a series of `FnMut` definitions for actions that can be applied
 /// to a `Widget`.  These can vary from a screen refresh (`tick`), to a mouse move event, etc.  Each
 /// callback gains access to the list of `WidgetContainer` objects stored by the cache.  This is
@@ -72,12 +86,42 @@ pub struct CallbackRegistry {
     /// the mouse button was pressed (`true`) or released (`false`).
     pub on_mouse_clicked: FunctionClickParametersType,
 
+    /// This function is called when a key is pressed while the `Widget` has keyboard focus.  It
+    /// contains the key code, the modifier keys that were held, and a boolean flag indicating
+    /// whether or not the key press is an auto-repeat (`true`) of a key being held down.
+    pub on_key_pressed: FunctionKeyParametersType,
+
+    /// This function is called when a key is released while the `Widget` has keyboard focus.  It
+    /// contains the key code, the modifier keys that were held, and the repeat flag, which is
+    /// always `false`.
+    pub on_key_released: FunctionKeyParametersType,
+
+    /// This function is called when a `Widget` gains keyboard focus.
+    pub on_focus_gained: FunctionNoParametersType,
+
+    /// This function is called when a `Widget` loses keyboard focus.
+    pub on_focus_lost: FunctionNoParametersType,
+
+    /// This function is called when a file is dropped onto a `Widget` from another application.
+    /// It contains the path of the file.
+    pub on_file_dropped: FunctionStringParametersType,
+
+    /// This function is called when text is dropped onto a `Widget` from another application.  It
+    /// contains the text.
+    pub on_text_dropped: FunctionStringParametersType,
+
     has_on_tick: bool,
     has_on_mouse_entered: bool,
     has_on_mouse_exited: bool,
     has_on_mouse_moved: bool,
     has_on_mouse_scrolled: bool,
     has_on_mouse_clicked: bool,
+    has_on_key_pressed: bool,
+    has_on_key_released: bool,
+    has_on_focus_gained: bool,
+    has_on_focus_lost: bool,
+    has_on_file_dropped: bool,
+    has_on_text_dropped: bool,
 }
 
 /// Implementation of the `CallbackRegistry`.
@@ -91,12 +135,24 @@ impl CallbackRegistry {
             on_mouse_moved: None,
             on_mouse_scrolled: None,
             on_mouse_clicked: None,
+            on_key_pressed: None,
+            on_key_released: None,
+            on_focus_gained: None,
+            on_focus_lost: None,
+            on_file_dropped: None,
+            on_text_dropped: None,
             has_on_tick: false,
             has_on_mouse_entered: false,
             has_on_mouse_exited: false,
             has_on_mouse_moved: false,
             has_on_mouse_scrolled: false,
             has_on_mouse_clicked: false,
+            has_on_key_pressed: false,
+            has_on_key_released: false,
+            has_on_focus_gained: false,
+            has_on_focus_lost: false,
+            has_on_file_dropped: false,
+            has_on_text_dropped: false,
         }
     }
 
@@ -160,6 +216,68 @@ impl CallbackRegistry {
         self.has_on_mouse_clicked = true;
     }
 
+    /// Assigns an `FnMut` that will be called when a key is pressed while a `Widget` has keyboard
+    /// focus.  If this is not set, this function will be bypassed.
+    pub fn on_key_pressed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer], Keycode, Mod, bool)
+            + 'static,
+    {
+        self.on_key_pressed = Some(Box::new(callback));
+        self.has_on_key_pressed = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a key is released while a `Widget` has keyboard
+    /// focus.  If this is not set, this function will be bypassed.
+    pub fn on_key_released<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer], Keycode, Mod, bool)
+            + 'static,
+    {
+        self.on_key_released = Some(Box::new(callback));
+        self.has_on_key_released = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a `Widget` gains keyboard focus.  If this is not
+    /// set, this function will be bypassed.
+    pub fn on_focus_gained<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer]) + 'static,
+    {
+        self.on_focus_gained = Some(Box::new(callback));
+        self.has_on_focus_gained = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a `Widget` loses keyboard focus.  If this is not
+    /// set, this function will be bypassed.
+    pub fn on_focus_lost<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer]) + 'static,
+    {
+        self.on_focus_lost = Some(Box::new(callback));
+        self.has_on_focus_lost = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a file is dropped onto a `Widget`.  If this is not
+    /// set, the file is passed to the `Engine`'s `on_file_dropped` callback instead.
+    pub fn on_file_dropped<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer], String) + 'static,
+    {
+        self.on_file_dropped = Some(Box::new(callback));
+        self.has_on_file_dropped = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when text is dropped onto a `Widget`.  If this is not
+    /// set, the text is passed to the `Engine`'s `on_text_dropped` callback instead.
+    pub fn on_text_dropped<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], &[LayoutContainer], String) + 'static,
+    {
+        self.on_text_dropped = Some(Box::new(callback));
+        self.has_on_text_dropped = true;
+    }
+
     /// Tells the `Widget` whether or not an `on_tick` callback has been set.
     pub fn has_on_tick(&mut self) -> bool {
         self.has_on_tick
@@ -189,10 +307,43 @@ impl CallbackRegistry {
     pub fn has_on_mouse_clicked(&mut self) -> bool {
         self.has_on_mouse_clicked
     }
+
+    /// Tells the `Widget` whether or not an `on_key_pressed` callback has been set.
+    pub fn has_on_key_pressed(&mut self) -> bool {
+        self.has_on_key_pressed
+    }
+
+    /// Tells the `Widget` whether or not an `on_key_released` callback has been set.
+    pub fn has_on_key_released(&mut self) -> bool {
+        self.has_on_key_released
+    }
+
+    /// Tells the `Widget` whether or not an `on_focus_gained` callback has been set.
+    pub fn has_on_focus_gained(&mut self) -> bool {
+        self.has_on_focus_gained
+    }
+
+    /// Tells the `Widget` whether or not an `on_focus_lost` callback has been set.
+    pub fn has_on_focus_lost(&mut self) -> bool {
+        self.has_on_focus_lost
+    }
+
+    /// Tells the `Widget` whether or not an `on_file_dropped` callback has been set.
+    pub fn has_on_file_dropped(&mut self) -> bool {
+        self.has_on_file_dropped
+    }
+
+    /// Tells the `Widget` whether or not an `on_text_dropped` callback has been set.
+    pub fn has_on_text_dropped(&mut self) -> bool {
+        self.has_on_text_dropped
+    }
 }
 
-pub fn widget_id_for_name(widgets: &[WidgetContainer], name: String) -> usize {
-    match widgets.iter().find(|x| x.get_widget_name() == name.clone()) {
+pub fn widget_id_for_name<N: AsRef<str>>(widgets: &[WidgetContainer], name: N) -> usize {
+    match widgets
+        .iter()
+        .find(|x| x.get_widget_name() == name.as_ref())
+    {
         Some(x) => x.get_widget_id() as usize,
         None => 0 as usize,
     }
@@ -17,7 +17,7 @@ use crate::render::widget::Widget;
 use crate::render::widget_config::{CONFIG_BORDER_WIDTH, CONFIG_SIZE};
 use crate::render::{SIZE_HEIGHT, SIZE_WIDTH};
 use sdl2::rect::{Point, Rect};
-use sdl2::render::Canvas;
+use sdl2::render::{Canvas, Texture};
 use sdl2::video::Window;
 
 /// This trait is used in conjunction with `Widget`s or anything else that draws to a `Canvas` object.
@@ -61,3 +61,274 @@ pub trait CanvasHelper: Widget {
         )
     }
 }
+
+/// Fills a rectangle with rounded corners of the given `radius` in the current draw color.  The
+/// radius is limited to half of the shorter side, so a square with a large radius is drawn as a
+/// circle.  Unlike the methods of `CanvasHelper`, coordinates are not translated.
+pub fn fill_rounded_rect(c: &mut Canvas<Window>, rect: Rect, radius: u32) {
+    let radius = radius.min(rect.width() / 2).min(rect.height() / 2) as i32;
+
+    for row in 0..rect.height() as i32 {
+        let dy = if row < radius {
+            radius - row
+        } else if row >= rect.height() as i32 - radius {
+            row - (rect.height() as i32 - radius - 1)
+        } else {
+            0
+        };
+        let inset = if dy == 0 {
+            0
+        } else {
+            let dx = ((radius * radius - (dy - 1) * (dy - 1)) as f64)
+                .sqrt()
+                .round() as i32;
+
+            radius - dx
+        };
+        let y = rect.y() + row;
+
+        c.draw_line(
+            Point::new(rect.x() + inset, y),
+            Point::new(rect.x() + rect.width() as i32 - 1 - inset, y),
+        )
+        .unwrap();
+    }
+}
+
+/// Returns the point at `radius` pixels from `center`, in the direction of `degrees`.  Angles are
+/// measured clockwise from the top of the circle, so `0.0` is straight up, and `90.0` is to the
+/// right.
+pub fn point_at_angle(center: Point, radius: f64, degrees: f64) -> Point {
+    let radians = degrees.to_radians();
+
+    Point::new(
+        center.x() + (radius * radians.sin()).round() as i32,
+        center.y() - (radius * radians.cos()).round() as i32,
+    )
+}
+
+/// Draws the outline of a circle of the given `radius` around `center` in the current draw color.
+/// Unlike the methods of `CanvasHelper`, coordinates are not translated.
+pub fn draw_circle(c: &mut Canvas<Window>, center: Point, radius: u32) {
+    let mut x = radius as i32;
+    let mut y = 0;
+    let mut error = 1 - x;
+
+    while x >= y {
+        for (dx, dy) in &[
+            (x, y),
+            (y, x),
+            (-y, x),
+            (-x, y),
+            (-x, -y),
+            (-y, -x),
+            (y, -x),
+            (x, -y),
+        ] {
+            c.draw_point(Point::new(center.x() + dx, center.y() + dy))
+                .unwrap();
+        }
+
+        y += 1;
+
+        if error < 0 {
+            error += 2 * y + 1;
+        } else {
+            x -= 1;
+            error += 2 * (y - x) + 1;
+        }
+    }
+}
+
+/// Fills a circle of the given `radius` around `center` in the current draw color.  Unlike the
+/// methods of `CanvasHelper`, coordinates are not translated.
+pub fn fill_circle(c: &mut Canvas<Window>, center: Point, radius: u32) {
+    let radius = radius as i32;
+
+    for dy in -radius..=radius {
+        let dx = ((radius * radius - dy * dy) as f64).sqrt().round() as i32;
+
+        c.draw_line(
+            Point::new(center.x() - dx, center.y() + dy),
+            Point::new(center.x() + dx, center.y() + dy),
+        )
+        .unwrap();
+    }
+}
+
+/// Draws an arc of the circle of the given `radius` around `center` in the current draw color,
+/// from `start_degrees` clockwise to `end_degrees`.  Angles are measured as in `point_at_angle`.
+/// The arc is `thickness` pixels wide, extending inwards from `radius`.  Unlike the methods of
+/// `CanvasHelper`, coordinates are not translated.
+pub fn draw_arc(
+    c: &mut Canvas<Window>,
+    center: Point,
+    radius: u32,
+    thickness: u32,
+    start_degrees: f64,
+    end_degrees: f64,
+) {
+    if end_degrees <= start_degrees {
+        return;
+    }
+
+    let sweep = end_degrees - start_degrees;
+    let steps = ((radius as f64 * sweep.to_radians()).ceil() as u32).max(1);
+
+    for ring in 0..thickness.min(radius + 1) {
+        let ring_radius = (radius - ring) as f64;
+        let mut previous = point_at_angle(center, ring_radius, start_degrees);
+
+        for step in 1..=steps {
+            let point = point_at_angle(
+                center,
+                ring_radius,
+                start_degrees + sweep * step as f64 / steps as f64,
+            );
+
+            c.draw_line(previous, point).unwrap();
+            previous = point;
+        }
+    }
+}
+
+/// Fills the part of the ring between `inner_radius` and `outer_radius` around `center` that lies
+/// from `start_degrees` clockwise to `end_degrees`, in the current draw color.  Angles are measured
+/// as in `point_at_angle`, and a sweep of `360.0` degrees or more fills the whole ring.  Unlike
+/// `draw_arc`, every pixel of the ring is filled, so thick arcs are drawn without gaps.  Unlike the
+/// methods of `CanvasHelper`, coordinates are not translated.
+pub fn fill_arc(
+    c: &mut Canvas<Window>,
+    center: Point,
+    outer_radius: u32,
+    inner_radius: u32,
+    start_degrees: f64,
+    end_degrees: f64,
+) {
+    let sweep = end_degrees - start_degrees;
+
+    if sweep <= 0.0 || inner_radius > outer_radius {
+        return;
+    }
+
+    let outer = outer_radius as i32;
+    let outer_squared = (outer_radius * outer_radius) as i32;
+    let inner_squared = (inner_radius * inner_radius) as i32;
+    let in_sweep = |dx: i32, dy: i32| {
+        sweep >= 360.0
+            || ((dx as f64).atan2(-dy as f64).to_degrees() - start_degrees).rem_euclid(360.0)
+                <= sweep
+    };
+
+    for dy in -outer..=outer {
+        let mut run_start: Option<i32> = None;
+
+        for dx in -outer..=outer + 1 {
+            let distance = dx * dx + dy * dy;
+            let inside = dx <= outer
+                && distance <= outer_squared
+                && distance >= inner_squared
+                && in_sweep(dx, dy);
+
+            match (inside, run_start) {
+                (true, None) => run_start = Some(dx),
+                (false, Some(start)) => {
+                    c.draw_line(
+                        Point::new(center.x() + start, center.y() + dy),
+                        Point::new(center.x() + dx - 1, center.y() + dy),
+                    )
+                    .unwrap();
+                    run_start = None;
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Fills the polygon with the given corner `points` in the current draw color, using the even-odd
+/// rule, so the polygon may be concave, like a star.  The last point is joined back to the first.
+/// Unlike the methods of `CanvasHelper`, coordinates are not translated.
+pub fn fill_polygon(c: &mut Canvas<Window>, points: &[Point]) {
+    if points.len() < 3 {
+        return;
+    }
+
+    let top = points.iter().map(|x| x.y()).min().unwrap();
+    let bottom = points.iter().map(|x| x.y()).max().unwrap();
+
+    for y in top..=bottom {
+        let scan_y = y as f64 + 0.5;
+        let mut crossings: Vec<f64> = Vec::new();
+
+        for (index, start) in points.iter().enumerate() {
+            let end = points[(index + 1) % points.len()];
+            let (y1, y2) = (start.y() as f64, end.y() as f64);
+
+            if (y1 <= scan_y) != (y2 <= scan_y) {
+                crossings.push(
+                    start.x() as f64 + (scan_y - y1) * (end.x() - start.x()) as f64 / (y2 - y1),
+                );
+            }
+        }
+
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for pair in crossings.chunks(2) {
+            if let [left, right] = pair {
+                let (left, right) = (left.round() as i32, right.round() as i32 - 1);
+
+                if right >= left {
+                    c.draw_line(Point::new(left, y), Point::new(right, y))
+                        .unwrap();
+                }
+            }
+        }
+    }
+}
+
+/// Draws a grid within `area` in the current draw color.  Vertical lines are drawn every `x_step`
+/// pixels and horizontal lines every `y_step` pixels, starting at the top left corner of `area`.
+/// If `connections` is `false`, only a dot is drawn where the lines would cross.  A step smaller
+/// than one pixel draws no lines in that direction.  Unlike the methods of `CanvasHelper`,
+/// coordinates are not translated.
+pub fn draw_grid(c: &mut Canvas<Window>, area: Rect, x_step: f64, y_step: f64, connections: bool) {
+    let steps = |start: i32, end: i32, step: f64| -> Vec<i32> {
+        if step < 1.0 {
+            return vec![];
+        }
+
+        (0..)
+            .map(|i| start + (i as f64 * step).round() as i32)
+            .take_while(|position| *position < end)
+            .collect()
+    };
+    let columns = steps(area.x(), area.right(), x_step);
+    let rows = steps(area.y(), area.bottom(), y_step);
+
+    if connections {
+        for x in &columns {
+            c.draw_line(Point::new(*x, area.y()), Point::new(*x, area.bottom()))
+                .unwrap();
+        }
+
+        for y in &rows {
+            c.draw_line(Point::new(area.x(), *y), Point::new(area.right(), *y))
+                .unwrap();
+        }
+    } else {
+        for x in &columns {
+            for y in &rows {
+                c.draw_point(Point::new(*x, *y)).unwrap();
+            }
+        }
+    }
+}
+
+/// Copies all of `texture` to `destination`, rotated clockwise by `degrees` around the center of
+/// `destination`.  Parts of the rotated texture that fall outside of the `Canvas` are clipped.
+/// Unlike the methods of `CanvasHelper`, coordinates are not translated.
+pub fn copy_rotated(c: &mut Canvas<Window>, texture: &Texture, destination: Rect, degrees: f64) {
+    c.copy_ex(texture, None, destination, degrees, None, false, false)
+        .unwrap();
+}
@@ -0,0 +1,90 @@
+// Pushrod Rendering Library
+// Clock Source
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+thread_local! {
+    static MOCK_TIME: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Returns the current time in milliseconds.  This is the wall clock time since the UNIX epoch,
+/// unless a `MockClock` is installed on the current thread, in which case the mock time is returned.
+/// `Widget`s that measure time (timers, animations, double-click detection, debouncing) should
+/// always use this function, so that they can be tested deterministically.
+pub fn now_millis() -> u64 {
+    MOCK_TIME.with(|mock_time| match mock_time.get() {
+        Some(ms) => ms,
+        None => {
+            let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+            (since_the_epoch.as_secs() * 1_000) + u64::from(since_the_epoch.subsec_millis())
+        }
+    })
+}
+
+/// Advances the `MockClock` installed on the current thread by `ms` milliseconds.  Has no effect
+/// when the wall clock is in use.
+pub fn advance_millis(ms: u64) {
+    MOCK_TIME.with(|mock_time| {
+        if let Some(now) = mock_time.get() {
+            mock_time.set(Some(now + ms));
+        }
+    });
+}
+
+/// Indicates whether or not a `MockClock` is installed on the current thread.
+pub fn is_mocked() -> bool {
+    MOCK_TIME.with(|mock_time| mock_time.get().is_some())
+}
+
+/// This is the storage object for the `MockClock`.  While it is in scope, `now_millis` on the current
+/// thread returns a controllable time that only moves when it is told to.  The wall clock is
+/// restored when the `MockClock` is dropped.
+pub struct MockClock {
+    _private: (),
+}
+
+/// This is the implementation of the `MockClock`.  Use it with `Engine::advance_frames` and
+/// `Engine::advance_time` to step animations and timers without sleeping.
+impl MockClock {
+    /// Installs a new `MockClock` on the current thread, starting at `start_ms` milliseconds.
+    pub fn new(start_ms: u64) -> Self {
+        MOCK_TIME.with(|mock_time| mock_time.set(Some(start_ms)));
+
+        Self { _private: () }
+    }
+
+    /// Sets the current time to `ms` milliseconds.
+    pub fn set(&self, ms: u64) {
+        MOCK_TIME.with(|mock_time| mock_time.set(Some(ms)));
+    }
+
+    /// Advances the current time by `ms` milliseconds.
+    pub fn advance(&self, ms: u64) {
+        advance_millis(ms);
+    }
+
+    /// Retrieves the current time in milliseconds.
+    pub fn now(&self) -> u64 {
+        now_millis()
+    }
+}
+
+impl Drop for MockClock {
+    fn drop(&mut self) {
+        MOCK_TIME.with(|mock_time| mock_time.set(None));
+    }
+}
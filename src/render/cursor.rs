@@ -0,0 +1,138 @@
+// Pushrod Rendering Library
+// Mouse Cursors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::CONFIG_CURSOR;
+use sdl2::mouse::{Cursor, SystemCursor};
+
+/// These are the mouse cursors that a `Widget` can request by setting `CONFIG_CURSOR`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum MouseCursor {
+    /// The standard arrow pointer.  This is the default.
+    Arrow,
+
+    /// A pointing hand, used for links and other clickable items.
+    Hand,
+
+    /// A text insertion bar, used for editable text.
+    IBeam,
+
+    /// A crosshair, used for precise selection.
+    Crosshair,
+
+    /// An hourglass or spinner, used while busy.
+    Wait,
+
+    /// A four-way arrow, used for items that can be moved.
+    SizeAll,
+
+    /// A slashed circle, used for items that cannot be interacted with.
+    No,
+}
+
+/// This is the implementation of the `MouseCursor`.
+impl MouseCursor {
+    /// Converts a numeric value stored in `CONFIG_CURSOR` to a `MouseCursor`.  Unknown values are
+    /// treated as `MouseCursor::Arrow`.
+    pub fn from_numeric(value: i32) -> Self {
+        match value {
+            1 => MouseCursor::Hand,
+            2 => MouseCursor::IBeam,
+            3 => MouseCursor::Crosshair,
+            4 => MouseCursor::Wait,
+            5 => MouseCursor::SizeAll,
+            6 => MouseCursor::No,
+            _ => MouseCursor::Arrow,
+        }
+    }
+
+    /// Converts the `MouseCursor` to the numeric value stored in `CONFIG_CURSOR`.
+    pub fn to_numeric(self) -> i32 {
+        self as i32
+    }
+
+    /// Internal function that returns the SDL2 system cursor for this `MouseCursor`.
+    fn system_cursor(self) -> SystemCursor {
+        match self {
+            MouseCursor::Arrow => SystemCursor::Arrow,
+            MouseCursor::Hand => SystemCursor::Hand,
+            MouseCursor::IBeam => SystemCursor::IBeam,
+            MouseCursor::Crosshair => SystemCursor::Crosshair,
+            MouseCursor::Wait => SystemCursor::Wait,
+            MouseCursor::SizeAll => SystemCursor::SizeAll,
+            MouseCursor::No => SystemCursor::No,
+        }
+    }
+}
+
+/// This is the storage object for the `CursorManager`.  The `Engine` owns a `CursorManager`, which
+/// shows the `MouseCursor` requested by the `Widget` under the mouse.  `Widget`s change the cursor
+/// by setting the `CONFIG_CURSOR` configuration key; no other code is required.
+pub struct CursorManager {
+    current: MouseCursor,
+    cursor: Option<Cursor>,
+}
+
+/// This is the implementation of the `CursorManager`.
+impl CursorManager {
+    /// Creates a new `CursorManager`, which shows the standard arrow pointer.
+    pub fn new() -> Self {
+        Self {
+            current: MouseCursor::Arrow,
+            cursor: None,
+        }
+    }
+
+    /// Retrieves the `MouseCursor` currently shown.
+    pub fn get_cursor(&self) -> MouseCursor {
+        self.current
+    }
+
+    /// Shows the `MouseCursor` requested by the `Widget` with the ID `widget_id`, or the arrow
+    /// pointer if it is hidden, or requests none.  The cursor is only changed when it differs from
+    /// the one shown.
+    pub fn update(&mut self, widgets: &[WidgetContainer], widget_id: i32) {
+        let requested = if widget_id > 0 && (widget_id as usize) < widgets.len() {
+            let mut widget = widgets[widget_id as usize].widget.borrow_mut();
+
+            if widget.get_config().is_hidden() {
+                MouseCursor::Arrow
+            } else {
+                MouseCursor::from_numeric(widget.get_config().get_numeric(CONFIG_CURSOR))
+            }
+        } else {
+            MouseCursor::Arrow
+        };
+
+        if requested == self.current {
+            return;
+        }
+
+        // The cursor must be kept alive for as long as it is shown, so it is stored; if the system
+        // cursor cannot be created, the current cursor is left as it is.
+        if let Ok(cursor) = Cursor::from_system(requested.system_cursor()) {
+            cursor.set();
+            self.cursor = Some(cursor);
+            self.current = requested;
+        }
+    }
+}
+
+/// This is the `Default` implementation of the `CursorManager`.
+impl Default for CursorManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,72 @@
+// Pushrod Rendering Library
+// Custom Events
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::engine::Engine;
+use sdl2::event::Event;
+use sdl2::Sdl;
+use std::any::Any;
+
+/// This is the callback type that is used when a custom event is received by the `Engine`.  It
+/// receives the currently running engine, and the event, which is downcast to the application's
+/// event type with `downcast_ref`.
+pub type CustomEventCallbackType = Option<Box<dyn FnMut(&mut Engine, &dyn Any)>>;
+
+/// This is the type of the functions that take a custom event of a registered type out of an SDL2
+/// `Event::User`.
+type CustomEventDecoder = Box<dyn Fn(&Event) -> Option<Box<dyn Any>>>;
+
+/// This is the list of custom event types registered with the `Engine`.  Custom events are SDL2
+/// user events that carry a value of an application-defined type, such as a variant of an `enum`
+/// of the application's own events.  They are pushed to the SDL2 event queue with
+/// `EventSubsystem::push_custom_event`, or from other threads with an `sdl2::event::EventSender`,
+/// and are received by the `Engine` along with mouse and keyboard events.
+#[derive(Default)]
+pub struct CustomEvents {
+    decoders: Vec<CustomEventDecoder>,
+}
+
+/// This is the implementation of the `CustomEvents` list.
+impl CustomEvents {
+    /// Creates a new, empty list of custom event types.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` as a custom event type with SDL2, so that values of `T` can be pushed to the
+    /// SDL2 event queue.  Returns an error if `T` has already been registered, or SDL2 has no more
+    /// user event types available.
+    pub fn register<T: Any>(&mut self, sdl: &Sdl) -> Result<(), String> {
+        sdl.event()?.register_custom_event::<T>()?;
+
+        self.decoders.push(Box::new(|event| {
+            event
+                .as_user_event_type::<T>()
+                .map(|value| Box::new(value) as Box<dyn Any>)
+        }));
+
+        Ok(())
+    }
+
+    /// Takes the custom event carried by `event` out of it, if it is an SDL2 user event of a
+    /// registered type.  The value is owned by the event until it is taken, so it can only be
+    /// taken once.
+    pub fn decode(&self, event: &Event) -> Option<Box<dyn Any>> {
+        if !event.is_user_event() {
+            return None;
+        }
+
+        self.decoders.iter().find_map(|decoder| decoder(event))
+    }
+}
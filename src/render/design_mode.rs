@@ -0,0 +1,487 @@
+// Pushrod Rendering Library
+// Design Mode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::widget_cache::{WidgetCache, WidgetContainer};
+use crate::render::widget_config::{CONFIG_ORIGIN, CONFIG_SIZE};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+/// Size in pixels of the square selection handles drawn around the selected `Widget`.
+const GRIP_SIZE: i32 = 8;
+
+/// Smallest width or height in pixels a `Widget` can be resized to in design mode.
+const MIN_WIDGET_SIZE: i32 = 8;
+
+/// This is a change made to a `Widget` in design mode, sent to the `on_design_change` callback
+/// registered with the `Engine`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DesignChange {
+    /// The `Widget` was moved to a new point of origin.
+    Moved { widget_id: i32, x: i32, y: i32 },
+
+    /// The `Widget` was resized.  Resizing from the top or left edge also moves its point of origin.
+    Resized {
+        widget_id: i32,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    },
+
+    /// A different `Widget` was selected.  A `widget_id` of `0` indicates that nothing is selected.
+    Selected { widget_id: i32 },
+}
+
+/// This is the callback type that is used when a `Widget` is changed in design mode.
+pub type DesignChangeCallbackType = Option<Box<dyn FnMut(&DesignChange)>>;
+
+/// The part of the selected `Widget` that is being dragged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Grip {
+    Move,
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+/// This is the implementation of the `Grip`.
+impl Grip {
+    /// Returns the grips that resize a `Widget`, which are drawn as selection handles.
+    fn resize_grips() -> [Grip; 8] {
+        [
+            Grip::North,
+            Grip::NorthEast,
+            Grip::East,
+            Grip::SouthEast,
+            Grip::South,
+            Grip::SouthWest,
+            Grip::West,
+            Grip::NorthWest,
+        ]
+    }
+
+    /// Returns the bounds of the handle for this grip, around a `Widget` with the given bounds.
+    fn handle_bounds(self, bounds: Rect) -> Rect {
+        let left = bounds.x();
+        let top = bounds.y();
+        let center_x = bounds.x() + bounds.width() as i32 / 2;
+        let center_y = bounds.y() + bounds.height() as i32 / 2;
+        let right = bounds.x() + bounds.width() as i32;
+        let bottom = bounds.y() + bounds.height() as i32;
+        let (x, y) = match self {
+            Grip::Move => (center_x, center_y),
+            Grip::North => (center_x, top),
+            Grip::NorthEast => (right, top),
+            Grip::East => (right, center_y),
+            Grip::SouthEast => (right, bottom),
+            Grip::South => (center_x, bottom),
+            Grip::SouthWest => (left, bottom),
+            Grip::West => (left, center_y),
+            Grip::NorthWest => (left, top),
+        };
+
+        Rect::new(
+            x - GRIP_SIZE / 2,
+            y - GRIP_SIZE / 2,
+            GRIP_SIZE as u32,
+            GRIP_SIZE as u32,
+        )
+    }
+
+    fn moves_left(self) -> bool {
+        matches!(self, Grip::West | Grip::NorthWest | Grip::SouthWest)
+    }
+
+    fn moves_right(self) -> bool {
+        matches!(self, Grip::East | Grip::NorthEast | Grip::SouthEast)
+    }
+
+    fn moves_top(self) -> bool {
+        matches!(self, Grip::North | Grip::NorthEast | Grip::NorthWest)
+    }
+
+    fn moves_bottom(self) -> bool {
+        matches!(self, Grip::South | Grip::SouthEast | Grip::SouthWest)
+    }
+}
+
+/// The state of a drag in progress: the grip being dragged, the point the drag started at, and the
+/// bounds of the `Widget` when the drag started.
+struct Drag {
+    grip: Grip,
+    start_x: i32,
+    start_y: i32,
+    start_bounds: Rect,
+}
+
+/// This is the storage object for `DesignMode`.  It stores the active state, the hotkey used to
+/// toggle it, the grid size used for snapping, the selected `Widget`, the drag in progress, and the
+/// change callback.
+pub struct DesignMode {
+    active: bool,
+    hotkey: Keycode,
+    grid_size: u32,
+    selected_widget_id: i32,
+    excluded_widget_id: i32,
+    drag: Option<Drag>,
+    on_change: DesignChangeCallbackType,
+}
+
+/// This is the implementation of `DesignMode`, which allows `Widget`s to be moved and resized with
+/// the mouse at runtime, as the foundation of a visual UI builder.  When active, mouse events are
+/// captured instead of being sent to `Widget`s: clicking a `Widget` selects it and shows selection
+/// handles around it, dragging the `Widget` moves it, and dragging a handle resizes it.  Positions
+/// and sizes are snapped to the grid, which is shown behind the `Widget`s by the `Engine`.
+impl DesignMode {
+    /// Creates a new, inactive `DesignMode`, toggled with the `F11` key, snapping to a 10 pixel grid.
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            hotkey: Keycode::F11,
+            grid_size: 10,
+            selected_widget_id: 0,
+            excluded_widget_id: 0,
+            drag: None,
+            on_change: None,
+        }
+    }
+
+    /// Changes the key used to toggle `DesignMode` on and off.
+    pub fn set_hotkey(&mut self, hotkey: Keycode) {
+        self.hotkey = hotkey;
+    }
+
+    /// Indicates whether or not `DesignMode` is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Turns `DesignMode` on or off.  The selection is cleared when it is turned off.
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+
+        if !active {
+            self.selected_widget_id = 0;
+            self.drag = None;
+        }
+    }
+
+    /// Sets the size of the grid that positions and sizes are snapped to.  A size of `0` or `1`
+    /// turns snapping off.
+    pub fn set_grid_size(&mut self, grid_size: u32) {
+        self.grid_size = grid_size;
+    }
+
+    /// Retrieves the size of the grid that positions and sizes are snapped to.
+    pub fn get_grid_size(&self) -> u32 {
+        self.grid_size
+    }
+
+    /// Retrieves the ID of the selected `Widget`, or `0` if nothing is selected.
+    pub fn get_selected_widget_id(&self) -> i32 {
+        self.selected_widget_id
+    }
+
+    /// Sets a `Widget` that cannot be selected, such as the grid shown behind the `Widget`s.
+    pub(crate) fn set_excluded_widget_id(&mut self, widget_id: i32) {
+        self.excluded_widget_id = widget_id;
+    }
+
+    /// Assigns the callback closure that is called when a `Widget` is selected, moved, or resized.
+    pub fn on_change<F>(&mut self, callback: F)
+    where
+        F: FnMut(&DesignChange) + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+    }
+
+    fn call_change_callback(&mut self, change: DesignChange) {
+        if let Some(mut cb) = self.on_change.take() {
+            cb(&change);
+            self.on_change = Some(cb);
+        }
+    }
+
+    /// Snaps a coordinate to the nearest grid line.
+    fn snap(&self, value: i32) -> i32 {
+        if self.grid_size <= 1 {
+            return value;
+        }
+
+        let grid_size = self.grid_size as i32;
+
+        ((value as f64 / grid_size as f64).round() as i32) * grid_size
+    }
+
+    /// Returns the bounds of a `Widget` by ID.
+    fn widget_bounds(cache: &mut WidgetCache, widget_id: i32) -> Rect {
+        cache
+            .get_container_by_id(widget_id)
+            .widget
+            .borrow_mut()
+            .get_drawing_area()
+    }
+
+    /// Determines which grip of the selected `Widget` is at a point, if any.
+    fn grip_at(&self, cache: &mut WidgetCache, x: i32, y: i32) -> Option<Grip> {
+        if self.selected_widget_id == 0 {
+            return None;
+        }
+
+        let bounds = Self::widget_bounds(cache, self.selected_widget_id);
+
+        Grip::resize_grips()
+            .iter()
+            .find(|grip| grip.handle_bounds(bounds).contains_point((x, y)))
+            .copied()
+            .or_else(|| {
+                if bounds.contains_point((x, y)) {
+                    Some(Grip::Move)
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Applies a drag to the selected `Widget`, snapping to the grid, and emitting a `DesignChange`
+    /// if its bounds changed.
+    fn apply_drag(&mut self, cache: &mut WidgetCache, x: i32, y: i32) {
+        let (grip, dx, dy, start) = match &self.drag {
+            Some(drag) => (
+                drag.grip,
+                x - drag.start_x,
+                y - drag.start_y,
+                drag.start_bounds,
+            ),
+            None => return,
+        };
+        let widget_id = self.selected_widget_id;
+        let current = Self::widget_bounds(cache, widget_id);
+        let mut left = start.x();
+        let mut top = start.y();
+        let mut right = start.x() + start.width() as i32;
+        let mut bottom = start.y() + start.height() as i32;
+
+        if grip == Grip::Move {
+            left = self.snap(start.x() + dx);
+            top = self.snap(start.y() + dy);
+            right = left + start.width() as i32;
+            bottom = top + start.height() as i32;
+        } else {
+            if grip.moves_left() {
+                left = self.snap(left + dx).min(right - MIN_WIDGET_SIZE);
+            }
+
+            if grip.moves_right() {
+                right = self.snap(right + dx).max(left + MIN_WIDGET_SIZE);
+            }
+
+            if grip.moves_top() {
+                top = self.snap(top + dy).min(bottom - MIN_WIDGET_SIZE);
+            }
+
+            if grip.moves_bottom() {
+                bottom = self.snap(bottom + dy).max(top + MIN_WIDGET_SIZE);
+            }
+        }
+
+        let updated = Rect::new(left, top, (right - left) as u32, (bottom - top) as u32);
+
+        if updated == current {
+            return;
+        }
+
+        {
+            let container = cache.get_container_by_id(widget_id);
+            let mut widget = container.widget.borrow_mut();
+
+            widget.get_config().set_point(CONFIG_ORIGIN, left, top);
+            widget
+                .get_config()
+                .set_size(CONFIG_SIZE, updated.width(), updated.height());
+            widget.get_config().set_invalidated(true);
+        }
+
+        cache
+            .get_container_by_id(0)
+            .widget
+            .borrow_mut()
+            .set_invalidated(true);
+
+        if grip == Grip::Move {
+            self.call_change_callback(DesignChange::Moved {
+                widget_id,
+                x: left,
+                y: top,
+            });
+        } else {
+            self.call_change_callback(DesignChange::Resized {
+                widget_id,
+                x: left,
+                y: top,
+                width: updated.width(),
+                height: updated.height(),
+            });
+        }
+    }
+
+    /// Changes the selected `Widget`, emitting a `DesignChange` if it changed.
+    fn select(&mut self, cache: &mut WidgetCache, widget_id: i32) {
+        let widget_id = if widget_id == self.excluded_widget_id {
+            0
+        } else {
+            widget_id
+        };
+
+        if widget_id != self.selected_widget_id {
+            self.selected_widget_id = widget_id;
+            cache
+                .get_container_by_id(0)
+                .widget
+                .borrow_mut()
+                .set_invalidated(true);
+            self.call_change_callback(DesignChange::Selected { widget_id });
+        }
+    }
+
+    /// Handles an `Event` before it is dispatched to the `Widget`s.  Returns `true` if `DesignMode`
+    /// consumed the `Event`, in which case it must not be dispatched any further.
+    pub fn handle_event(&mut self, event: &Event, cache: &mut WidgetCache) -> bool {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            if *keycode == self.hotkey {
+                self.set_active(!self.active);
+                cache
+                    .get_container_by_id(0)
+                    .widget
+                    .borrow_mut()
+                    .set_invalidated(true);
+
+                return true;
+            }
+
+            if self.active && *keycode == Keycode::Escape {
+                self.select(cache, 0);
+
+                return true;
+            }
+        }
+
+        if !self.active {
+            return false;
+        }
+
+        match event {
+            Event::MouseButtonDown {
+                mouse_btn: MouseButton::Left,
+                x,
+                y,
+                ..
+            } => {
+                let grip = match self.grip_at(cache, *x, *y) {
+                    Some(grip) => grip,
+                    None => {
+                        let widget_id = cache.find_widget(*x, *y);
+
+                        self.select(cache, widget_id);
+                        Grip::Move
+                    }
+                };
+
+                self.drag = if self.selected_widget_id == 0 {
+                    None
+                } else {
+                    Some(Drag {
+                        grip,
+                        start_x: *x,
+                        start_y: *y,
+                        start_bounds: Self::widget_bounds(cache, self.selected_widget_id),
+                    })
+                };
+
+                true
+            }
+
+            Event::MouseMotion { x, y, .. } => {
+                self.apply_drag(cache, *x, *y);
+
+                true
+            }
+
+            Event::MouseButtonUp {
+                mouse_btn: MouseButton::Left,
+                ..
+            } => {
+                self.drag = None;
+
+                true
+            }
+
+            Event::MouseButtonDown { .. }
+            | Event::MouseButtonUp { .. }
+            | Event::MouseWheel { .. }
+            | Event::KeyDown { .. }
+            | Event::KeyUp { .. }
+            | Event::TextInput { .. }
+            | Event::TextEditing { .. } => true,
+
+            _ => false,
+        }
+    }
+
+    /// Draws the selection outline and handles around the selected `Widget`, on top of the already
+    /// drawn `Widget`s.
+    pub fn draw(&mut self, c: &mut Canvas<Window>, widgets: &[WidgetContainer]) {
+        if !self.active || self.selected_widget_id == 0 {
+            return;
+        }
+
+        let bounds = match widgets.get(self.selected_widget_id as usize) {
+            Some(container) => container.widget.borrow_mut().get_drawing_area(),
+            None => return,
+        };
+
+        c.set_draw_color(Color::RGB(0, 120, 215));
+        c.draw_rect(bounds).unwrap();
+
+        for grip in Grip::resize_grips().iter() {
+            let handle = grip.handle_bounds(bounds);
+
+            c.set_draw_color(Color::RGB(255, 255, 255));
+            c.fill_rect(handle).unwrap();
+            c.set_draw_color(Color::RGB(0, 120, 215));
+            c.draw_rect(handle).unwrap();
+        }
+    }
+}
+
+impl Default for DesignMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
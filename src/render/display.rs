@@ -0,0 +1,173 @@
+// Pushrod Rendering Library
+// Display Enumeration
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sdl2::rect::Rect;
+use sdl2::video::{Window, WindowPos};
+use sdl2::VideoSubsystem;
+
+/// This is the DPI that corresponds to a scale factor of `1.0`.
+pub const DEFAULT_DPI: f32 = 96.0;
+
+/// This is the callback type that is used when the `Engine`'s window moves to a different display.
+pub type DisplayChangedCallbackType = Option<Box<dyn FnMut(&DisplayInfo)>>;
+
+/// This is the storage object for `DisplayInfo`, which describes a single connected display.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplayInfo {
+    /// The index of the display, as used by SDL2.
+    pub index: i32,
+
+    /// The name of the display, as reported by the platform.
+    pub name: String,
+
+    /// The bounds of the display in the desktop coordinate space.
+    pub bounds: Rect,
+
+    /// The bounds of the display, excluding areas reserved by the platform, such as task bars and
+    /// menu bars.
+    pub usable_bounds: Rect,
+
+    /// The diagonal, horizontal and vertical DPI of the display.  Platforms that do not report the
+    /// DPI are assumed to use `DEFAULT_DPI`.
+    pub dpi: (f32, f32, f32),
+
+    /// The refresh rate of the display in Hz, or `0` if it is not known.
+    pub refresh_rate: i32,
+}
+
+/// This is the implementation of `DisplayInfo`.
+impl DisplayInfo {
+    /// Retrieves the `DisplayInfo` for a display by index.
+    pub fn new(video: &VideoSubsystem, index: i32) -> Result<Self, String> {
+        let bounds = video.display_bounds(index)?;
+        let dpi = video
+            .display_dpi(index)
+            .unwrap_or((DEFAULT_DPI, DEFAULT_DPI, DEFAULT_DPI));
+        let refresh_rate = video
+            .current_display_mode(index)
+            .map(|mode| mode.refresh_rate)
+            .unwrap_or(0);
+
+        Ok(Self {
+            index,
+            name: video.display_name(index).unwrap_or_default(),
+            bounds,
+            usable_bounds: usable_bounds(index).unwrap_or(bounds),
+            dpi,
+            refresh_rate,
+        })
+    }
+
+    /// Returns the scale factor of the display, based on its horizontal DPI, where `1.0` is
+    /// `DEFAULT_DPI`.
+    pub fn scale(&self) -> f32 {
+        if self.dpi.1 > 0.0 {
+            self.dpi.1 / DEFAULT_DPI
+        } else {
+            1.0
+        }
+    }
+
+    /// Scales a logical size by the scale factor of the display.
+    pub fn scale_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let scale = self.scale();
+
+        (
+            (width as f32 * scale).round() as u32,
+            (height as f32 * scale).round() as u32,
+        )
+    }
+
+    /// Returns the point of origin that centers a window of the given size on the usable area of
+    /// the display.
+    pub fn centered_origin(&self, width: u32, height: u32) -> (i32, i32) {
+        let area = self.usable_bounds;
+
+        (
+            area.x() + (area.width() as i32 - width as i32) / 2,
+            area.y() + (area.height() as i32 - height as i32) / 2,
+        )
+    }
+}
+
+/// Returns the usable bounds of a display, which SDL2 does not expose in its safe API.
+fn usable_bounds(index: i32) -> Result<Rect, String> {
+    let mut rect = sdl2::sys::SDL_Rect {
+        x: 0,
+        y: 0,
+        w: 0,
+        h: 0,
+    };
+
+    // Safety: `rect` is a valid, writable `SDL_Rect`, and the video subsystem is initialized, as a
+    // `VideoSubsystem` is required to obtain a display index.
+    let result = unsafe { sdl2::sys::SDL_GetDisplayUsableBounds(index, &mut rect) };
+
+    if result == 0 {
+        Ok(Rect::new(rect.x, rect.y, rect.w as u32, rect.h as u32))
+    } else {
+        Err(sdl2::get_error())
+    }
+}
+
+/// Returns the `DisplayInfo` of every connected display.
+pub fn displays(video: &VideoSubsystem) -> Result<Vec<DisplayInfo>, String> {
+    (0..video.num_video_displays()?)
+        .map(|index| DisplayInfo::new(video, index))
+        .collect()
+}
+
+/// Returns the `DisplayInfo` of the display that contains the center of a `Window`.
+pub fn window_display(window: &Window) -> Result<DisplayInfo, String> {
+    DisplayInfo::new(window.subsystem(), window.display_index()?)
+}
+
+/// Creates a `Window` centered on a display, for use with `Engine::run`.  When `scale` is `true`,
+/// the `width` and `height` are treated as logical sizes, and are scaled by the scale factor of the
+/// display.
+pub fn create_window_on_display(
+    video: &VideoSubsystem,
+    title: &str,
+    width: u32,
+    height: u32,
+    display_index: i32,
+    scale: bool,
+) -> Result<Window, String> {
+    let display = DisplayInfo::new(video, display_index)?;
+    let (width, height) = if scale {
+        display.scale_size(width, height)
+    } else {
+        (width, height)
+    };
+    let (x, y) = display.centered_origin(width, height);
+
+    video
+        .window(title, width, height)
+        .position(x, y)
+        .opengl()
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Moves a `Window` so that it is centered on a display, keeping its size.
+pub fn move_window_to_display(window: &mut Window, display_index: i32) -> Result<(), String> {
+    let display = DisplayInfo::new(window.subsystem(), display_index)?;
+    let (width, height) = window.size();
+    let (x, y) = display.centered_origin(width, height);
+
+    window.set_position(WindowPos::Positioned(x), WindowPos::Positioned(y));
+
+    Ok(())
+}
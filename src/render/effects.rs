@@ -0,0 +1,309 @@
+// Pushrod Rendering Library
+// Post-Process Effects
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::render::{BlendMode, Canvas, Texture};
+use sdl2::video::Window;
+
+/// This is the number of box blur passes used to approximate a gaussian blur.
+const BLUR_PASSES: usize = 3;
+
+/// This is a post-process effect that can be applied to the cached texture of a `Widget` before it
+/// is composited onto its `Layer`.  Effects are computed in software, as the SDL2 renderer does not
+/// expose shaders, and are only recomputed when the `Widget` is redrawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Effect {
+    /// Applies an approximated gaussian blur of the given radius in pixels, such as for a
+    /// frosted-glass panel.
+    Blur(u32),
+
+    /// Draws a glow of the given `Color` and radius in pixels around the opaque parts of the
+    /// `Widget`.  The glow extends outside of the bounds of the `Widget`.
+    Glow(Color, u32),
+
+    /// Converts the `Widget` to grayscale.
+    Grayscale,
+}
+
+/// This indicates when a `PostEffect` is applied to a `Widget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EffectCondition {
+    /// The effect is always applied.
+    Always,
+
+    /// The effect is only applied while the `Widget` has keyboard focus.
+    Focused,
+
+    /// The effect is only applied while the `Widget` is disabled.
+    Disabled,
+}
+
+/// This is an `Effect`, along with the `EffectCondition` under which it is applied.  `PostEffect`s
+/// are assigned to `Widget`s with `Engine::set_effects`, and are applied in the order given.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PostEffect {
+    pub effect: Effect,
+    pub condition: EffectCondition,
+}
+
+/// This is the implementation of the `PostEffect`.
+impl PostEffect {
+    /// Creates a new `PostEffect`.
+    pub fn new(effect: Effect, condition: EffectCondition) -> Self {
+        Self { effect, condition }
+    }
+
+    /// Creates a blur that is always applied, for frosted-glass style panels.
+    pub fn frosted_glass(radius: u32) -> Self {
+        Self::new(Effect::Blur(radius), EffectCondition::Always)
+    }
+
+    /// Creates a glow that is applied while the `Widget` has focus.
+    pub fn glow_on_focus(color: Color, radius: u32) -> Self {
+        Self::new(Effect::Glow(color, radius), EffectCondition::Focused)
+    }
+
+    /// Creates a grayscale conversion that is applied while the `Widget` is disabled.
+    pub fn grayscale_when_disabled() -> Self {
+        Self::new(Effect::Grayscale, EffectCondition::Disabled)
+    }
+
+    /// Indicates whether or not the effect applies to a `Widget` in the given state.
+    pub fn is_active(&self, focused: bool, enabled: bool) -> bool {
+        match self.condition {
+            EffectCondition::Always => true,
+            EffectCondition::Focused => focused,
+            EffectCondition::Disabled => !enabled,
+        }
+    }
+}
+
+/// Returns the number of pixels that the given `Effect`s extend past each edge of a `Widget`.
+pub fn effect_padding(effects: &[Effect]) -> u32 {
+    effects
+        .iter()
+        .map(|effect| match effect {
+            Effect::Glow(_, radius) => *radius,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Applies a list of `Effect`s to a buffer of `RGBA32` pixels, returning the resulting pixels, and
+/// their width and height.  The result is larger than the source if any `Effect` extends past the
+/// edges of the image, as reported by `effect_padding`.
+pub fn apply_effects(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    effects: &[Effect],
+) -> (Vec<u8>, u32, u32) {
+    let mut pixels = pixels.to_vec();
+    let mut width = width;
+    let mut height = height;
+
+    for effect in effects {
+        match effect {
+            Effect::Blur(radius) => {
+                for _ in 0..BLUR_PASSES {
+                    box_blur(&mut pixels, width, height, *radius, 4);
+                }
+            }
+            Effect::Glow(color, radius) => {
+                let (glowed, glow_width, glow_height) =
+                    glow(&pixels, width, height, *color, *radius);
+
+                pixels = glowed;
+                width = glow_width;
+                height = glow_height;
+            }
+            Effect::Grayscale => grayscale(&mut pixels),
+        }
+    }
+
+    (pixels, width, height)
+}
+
+/// Reads back the contents of a `Widget`'s texture, applies the given `Effect`s in software, and
+/// uploads the result into a new `Texture`.  Returns the `Texture`, along with the number of pixels
+/// it extends past each edge of the source.
+pub(crate) fn render_effects(
+    c: &mut Canvas<Window>,
+    source: &Texture,
+    effects: &[Effect],
+) -> Result<(Texture, u32), String> {
+    let query = source.query();
+    let texture_creator = c.texture_creator();
+    let mut readback = texture_creator
+        .create_texture_target(None, query.width, query.height)
+        .map_err(|e| e.to_string())?;
+    let mut read_result = Err(String::new());
+
+    c.with_texture_canvas(&mut readback, |texture| {
+        texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+        texture.clear();
+
+        read_result = texture
+            .copy(source, None, None)
+            .and_then(|_| texture.read_pixels(None, PixelFormatEnum::RGBA32));
+    })
+    .map_err(|e| e.to_string())?;
+
+    // Safety: the readback texture is only used within this function.
+    unsafe {
+        readback.destroy();
+    }
+
+    let (pixels, width, height) = apply_effects(&read_result?, query.width, query.height, effects);
+    let mut texture = texture_creator
+        .create_texture_static(PixelFormatEnum::RGBA32, width, height)
+        .map_err(|e| e.to_string())?;
+
+    texture
+        .update(None, &pixels, width as usize * 4)
+        .map_err(|e| e.to_string())?;
+    texture.set_blend_mode(BlendMode::Blend);
+
+    Ok((texture, effect_padding(effects)))
+}
+
+/// Converts `RGBA32` pixels to grayscale in place, using the Rec. 601 luma weights.
+fn grayscale(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let luma = (f32::from(pixel[0]) * 0.299
+            + f32::from(pixel[1]) * 0.587
+            + f32::from(pixel[2]) * 0.114)
+            .round() as u8;
+
+        pixel[0] = luma;
+        pixel[1] = luma;
+        pixel[2] = luma;
+    }
+}
+
+/// Draws a glow around the opaque parts of `RGBA32` pixels, returning a new buffer that is padded
+/// by `radius` pixels on each side.
+fn glow(pixels: &[u8], width: u32, height: u32, color: Color, radius: u32) -> (Vec<u8>, u32, u32) {
+    let glow_width = width + radius * 2;
+    let glow_height = height + radius * 2;
+    let mut alpha = vec![0u8; (glow_width * glow_height) as usize];
+
+    for y in 0..height {
+        for x in 0..width {
+            alpha[((y + radius) * glow_width + x + radius) as usize] =
+                pixels[((y * width + x) * 4 + 3) as usize];
+        }
+    }
+
+    for _ in 0..BLUR_PASSES {
+        box_blur(&mut alpha, glow_width, glow_height, radius, 1);
+    }
+
+    let mut result = vec![0u8; (glow_width * glow_height * 4) as usize];
+
+    for (i, glow_alpha) in alpha.iter().enumerate() {
+        let x = i as u32 % glow_width;
+        let y = i as u32 / glow_width;
+        let glow_alpha = f32::from(*glow_alpha) * f32::from(color.a) / 255.0 / 255.0;
+        let mut out = [
+            f32::from(color.r),
+            f32::from(color.g),
+            f32::from(color.b),
+            glow_alpha,
+        ];
+
+        if x >= radius && x < radius + width && y >= radius && y < radius + height {
+            let offset = (((y - radius) * width + x - radius) * 4) as usize;
+            let src_alpha = f32::from(pixels[offset + 3]) / 255.0;
+            let out_alpha = src_alpha + glow_alpha * (1.0 - src_alpha);
+
+            if out_alpha > 0.0 {
+                for (channel, value) in out.iter_mut().take(3).enumerate() {
+                    *value = (f32::from(pixels[offset + channel]) * src_alpha
+                        + *value * glow_alpha * (1.0 - src_alpha))
+                        / out_alpha;
+                }
+            }
+
+            out[3] = out_alpha;
+        }
+
+        result[i * 4] = out[0].round() as u8;
+        result[i * 4 + 1] = out[1].round() as u8;
+        result[i * 4 + 2] = out[2].round() as u8;
+        result[i * 4 + 3] = (out[3] * 255.0).round() as u8;
+    }
+
+    (result, glow_width, glow_height)
+}
+
+/// Applies a single separable box blur pass of the given radius to a buffer of pixels with
+/// `channels` bytes per pixel.
+fn box_blur(pixels: &mut [u8], width: u32, height: u32, radius: u32, channels: usize) {
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+
+    let width = width as usize;
+    let height = height as usize;
+    let mut scratch = pixels.to_vec();
+
+    blur_lines(
+        pixels,
+        &mut scratch,
+        width,
+        height,
+        1,
+        width,
+        radius,
+        channels,
+    );
+    blur_lines(&scratch, pixels, height, width, width, 1, radius, channels);
+}
+
+/// Blurs `lines` lines of `length` pixels each from `source` into `dest`.  `step` is the distance in
+/// pixels between neighbouring pixels of a line, and `stride` is the distance between lines.
+#[allow(clippy::too_many_arguments)]
+fn blur_lines(
+    source: &[u8],
+    dest: &mut [u8],
+    length: usize,
+    lines: usize,
+    step: usize,
+    stride: usize,
+    radius: u32,
+    channels: usize,
+) {
+    let radius = radius as isize;
+    let window = (radius * 2 + 1) as u32;
+
+    for line in 0..lines {
+        let start = line * stride;
+
+        for channel in 0..channels {
+            let sample = |i: isize| -> u32 {
+                let i = i.clamp(0, length as isize - 1) as usize;
+
+                u32::from(source[(start + i * step) * channels + channel])
+            };
+            let mut sum: u32 = (-radius..=radius).map(sample).sum();
+
+            for i in 0..length as isize {
+                dest[(start + i as usize * step) * channels + channel] = (sum / window) as u8;
+                sum = sum + sample(i + radius + 1) - sample(i - radius);
+            }
+        }
+    }
+}
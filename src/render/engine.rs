@@ -13,18 +13,53 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sdl2::event::Event;
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::render::Canvas;
 use sdl2::video::Window;
 use sdl2::Sdl;
 
+use crate::render::clock;
+use crate::render::cursor::CursorManager;
+use crate::render::custom_event::{CustomEventCallbackType, CustomEvents};
+use crate::render::design_mode::{DesignChange, DesignMode};
+use crate::render::display::{
+    move_window_to_display, window_display, DisplayChangedCallbackType, DisplayInfo,
+};
+use crate::render::effects::PostEffect;
+use crate::render::event_filter::{EventFilterType, FilterResult};
+use crate::render::event_sender::{EventMessage, EventMessageCallbackType, EventSender};
+use crate::render::frame_stats::{texture_counts, FrameStats, FrameStatsCallbackType};
+use crate::render::input_settings::InputSettings;
+use crate::render::inspector::Inspector;
+use crate::render::layer::Layer;
 use crate::render::layout::Layout;
-use crate::render::layout_cache::LayoutCache;
+use crate::render::layout_cache::{LayoutCache, LayoutContainer};
+use crate::render::layout_file::{
+    export_layout, import_layout, load_layout, save_layout, LayoutFileError, LayoutFormat,
+    WidgetRegistry,
+};
+use crate::render::modal::DialogClosedCallbackType;
+use crate::render::texture_memory::TextureBudgetCallbackType;
+use crate::render::theme::Theme;
+use crate::render::timer::{TimerHandle, Timers};
+use crate::render::tooltip::Tooltip;
 use crate::render::widget::{BaseWidget, Widget};
-use crate::render::widget_cache::WidgetCache;
-use crate::render::{make_points_origin, make_size};
+use crate::render::widget_cache::{WidgetCache, WidgetContainer};
+use crate::render::widget_config::{CONFIG_LAYER, CONFIG_SIZE};
+use crate::render::widget_handle::{WidgetHandle, WidgetHandleError};
+use crate::render::widget_name::WidgetName;
+use crate::render::window_shape::{is_shaped_window, set_window_shape, WindowShape};
+use crate::render::{make_points_origin, make_size, Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use crate::widgets::grid_widget::GridWidget;
 use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use std::any::Any;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread::sleep;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// This function is called when when the application requests to quit.  It accepts the currently
 /// running engine, and the return value will indicate whether or not to quit.  Returning a `true`
@@ -32,14 +67,54 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 /// will quit when asked.
 pub type OnExitCallbackType = Option<Box<dyn FnMut(&mut Engine) -> bool>>;
 
+/// This function is called when a file or text is dropped onto the window from another
+/// application, and the `Widget` under the mouse did not handle it.  It accepts the currently
+/// running engine, and the path of the file, or the text, that was dropped.
+pub type OnDropCallbackType = Option<Box<dyn FnMut(&mut Engine, String)>>;
+
 /// This is a storage container for the Pushrod event engine.
 pub struct Engine {
     widget_cache: WidgetCache,
     layout_cache: LayoutCache,
+    timers: Timers,
+    message_sender: Sender<EventMessage>,
+    message_receiver: Receiver<EventMessage>,
+    on_event_message: EventMessageCallbackType,
+    custom_events: CustomEvents,
+    pending_custom_events: Vec<Box<dyn Any>>,
+    on_custom_event: CustomEventCallbackType,
+    event_filter: EventFilterType,
     current_widget_id: i32,
+    captured_widget_id: i32,
     frame_rate: u8,
     running: bool,
     on_exit: OnExitCallbackType,
+    on_file_dropped: OnDropCallbackType,
+    on_text_dropped: OnDropCallbackType,
+    inspector: Inspector,
+    design_mode: DesignMode,
+    design_grid: Option<WidgetHandle>,
+    widget_registry: WidgetRegistry,
+    tooltip: Tooltip,
+    cursors: CursorManager,
+    on_dialog_closed: DialogClosedCallbackType,
+    display_index: i32,
+    pending_display: Option<i32>,
+    display_scaling: bool,
+    display_scale: f32,
+    on_display_changed: DisplayChangedCallbackType,
+    window_shape: Option<WindowShape>,
+    window_shape_dirty: bool,
+    window_opacity: Option<f32>,
+    frame_stats: FrameStats,
+    on_frame_stats: FrameStatsCallbackType,
+    frame_start: Instant,
+    events_processed: u32,
+    texture_counts: (u64, u64),
+    texture_memory_budget: u64,
+    texture_budget_exceeded: bool,
+    on_texture_budget_exceeded: TextureBudgetCallbackType,
+    text_input_bounds: Option<Option<Rect>>,
 }
 
 /// This is the heart of the Pushrod event engine, and is what is used to drive the interaction
@@ -76,29 +151,459 @@ impl Engine {
         let base_widget = BaseWidget::new(make_points_origin(), make_size(w, h));
         let mut cache = WidgetCache::new();
 
-        cache.add_widget(Box::new(base_widget), "base".to_string());
+        cache.add_widget(Box::new(base_widget), "base");
+
+        let (message_sender, message_receiver) = channel();
 
         Self {
             widget_cache: cache,
             layout_cache: LayoutCache::new(),
+            timers: Timers::new(),
+            message_sender,
+            message_receiver,
+            on_event_message: None,
+            custom_events: CustomEvents::new(),
+            pending_custom_events: Vec::new(),
+            on_custom_event: None,
+            event_filter: None,
             current_widget_id: 0,
+            captured_widget_id: 0,
             frame_rate,
             running: true,
             on_exit: None,
+            on_file_dropped: None,
+            on_text_dropped: None,
+            inspector: Inspector::new(),
+            design_mode: DesignMode::new(),
+            design_grid: None,
+            widget_registry: WidgetRegistry::default(),
+            tooltip: Tooltip::new(),
+            cursors: CursorManager::new(),
+            on_dialog_closed: None,
+            display_index: -1,
+            pending_display: None,
+            display_scaling: false,
+            display_scale: 1.0,
+            on_display_changed: None,
+            window_shape: None,
+            window_shape_dirty: false,
+            window_opacity: None,
+            frame_stats: FrameStats::default(),
+            on_frame_stats: None,
+            frame_start: Instant::now(),
+            events_processed: 0,
+            texture_counts: texture_counts(),
+            texture_memory_budget: 0,
+            texture_budget_exceeded: false,
+            on_texture_budget_exceeded: None,
+            text_input_bounds: None,
         }
     }
 
     /// Adds a `Widget` to the display list.  `Widget`s are rendered in the order in which they were
-    /// created in the display list.
-    pub fn add_widget(&mut self, widget: Box<dyn Widget>, widget_name: String) -> i32 {
+    /// created in the display list.  Returns a `WidgetHandle` referring to the `Widget`; use
+    /// `handle.id()` where a raw `Widget` ID is required, such as when adding it to a `Layout`.
+    pub fn add_widget<N: Into<WidgetName>>(
+        &mut self,
+        widget: Box<dyn Widget>,
+        widget_name: N,
+    ) -> WidgetHandle {
         self.widget_cache.add_widget(widget, widget_name)
     }
 
+    /// Removes a `Widget` from the display list, along with all of its children, and removes them
+    /// from the `Layout`s they were added to.  Returns an error if the `WidgetHandle` is stale, or
+    /// refers to the base `Widget`.
+    pub fn remove_widget(&mut self, handle: WidgetHandle) -> Result<(), WidgetHandleError> {
+        self.widget_cache.remove_widget(handle)?;
+        self.remove_widgets_from_layouts();
+        self.close_removed_dialogs();
+
+        Ok(())
+    }
+
+    /// Internal function that removes the `Widget`s that have been removed from the cache from the
+    /// `Layout`s they were added to, and forgets the `Widget` that captured the mouse, or that the
+    /// mouse is over, if it was removed, so that a `Widget` reusing its slot does not receive its
+    /// events.
+    fn remove_widgets_from_layouts(&mut self) {
+        let removed = self.widget_cache.take_removed_widgets();
+
+        if removed.is_empty() {
+            return;
+        }
+
+        self.layout_cache.remove_widgets(&removed);
+
+        if removed.contains(&self.captured_widget_id) {
+            self.captured_widget_id = 0;
+        }
+
+        if removed.contains(&self.current_widget_id) {
+            self.hide_tooltip();
+            self.current_widget_id = 0;
+        }
+    }
+
+    /// Sets the `PostEffect`s applied to the `Widget` referred to by `handle`, such as a blur for a
+    /// frosted-glass panel, a glow while focused, or grayscale while disabled.  See
+    /// `WidgetCache::set_effects`.
+    pub fn set_effects(
+        &mut self,
+        handle: WidgetHandle,
+        effects: Vec<PostEffect>,
+    ) -> Result<(), WidgetHandleError> {
+        self.widget_cache.set_effects(handle, effects)
+    }
+
+    /// Makes the `Widget` referred to by `handle` a child of the `Widget` referred to by `parent`.
+    /// See `WidgetCache::set_parent`.
+    pub fn set_parent(
+        &mut self,
+        handle: WidgetHandle,
+        parent: WidgetHandle,
+    ) -> Result<(), WidgetHandleError> {
+        self.widget_cache.set_parent(handle, parent)
+    }
+
     /// Adds a `Layout` to the `Layout` list.
     pub fn add_layout(&mut self, layout: Box<dyn Layout>) -> i32 {
         self.layout_cache.add_layout(layout)
     }
 
+    /// Applies a `Theme` to all `Widget`s in the display list, including those added afterwards.
+    /// Use `Theme::high_contrast()` for an accessibility-friendly appearance.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.widget_cache.set_theme(theme);
+    }
+
+    /// Applies `InputSettings` (key repeat delay and interval, caret blink rate) to all `Widget`s in
+    /// the display list, including those added afterwards.
+    pub fn set_input_settings(&mut self, input_settings: InputSettings) {
+        self.widget_cache.set_input_settings(input_settings);
+    }
+
+    /// Sets keyboard focus to the `Widget` specified by `widget_id`.  A `widget_id` of `0` clears
+    /// the focus.
+    pub fn set_focus(&mut self, widget_id: i32) {
+        self.widget_cache.set_focus(widget_id);
+        self.widget_cache
+            .focus_callbacks(self.layout_cache.get_layout_cache());
+    }
+
+    /// Retrieves the ID of the `Widget` that currently has keyboard focus, `0` if none.
+    pub fn get_focus(&self) -> i32 {
+        self.widget_cache.get_focus()
+    }
+
+    /// Retrieves the `WidgetCache`, which can be used to look up `Widget`s in the display list by
+    /// ID or name.
+    pub fn get_widget_cache(&mut self) -> &mut WidgetCache {
+        &mut self.widget_cache
+    }
+
+    /// Sets the number of threads used to compute the thread-safe work of `Widget`s that implement
+    /// `ParallelTick`, and the cells of `Layout`s that implement `ParallelLayout`, each frame.  The
+    /// threads are started once, and kept in a pool until this is called again.  A value of `1` (the
+    /// default) performs all work on the main thread.  `Widget`s are always placed in their cells,
+    /// and other `Layout`s performed, on the main thread, as they modify the `Widget`s directly.
+    pub fn set_tick_threads(&mut self, threads: usize) {
+        self.widget_cache.set_tick_threads(threads);
+    }
+
+    /// Retrieves the `Inspector`, the runtime debugging overlay, which is toggled using the `F12`
+    /// key by default.
+    pub fn get_inspector(&mut self) -> &mut Inspector {
+        &mut self.inspector
+    }
+
+    /// Retrieves the `DesignMode`, which allows `Widget`s to be moved and resized with the mouse, and
+    /// is toggled using the `F11` key by default.
+    pub fn get_design_mode(&mut self) -> &mut DesignMode {
+        &mut self.design_mode
+    }
+
+    /// Retrieves the `Tooltip`, which shows the `CONFIG_TOOLTIP_TEXT` of the `Widget` the mouse is
+    /// resting over, so that its delay and colors can be changed.
+    pub fn get_tooltip(&mut self) -> &mut Tooltip {
+        &mut self.tooltip
+    }
+
+    /// Turns `DesignMode` on or off.  While it is on, a `GridWidget` showing the grid that `Widget`s
+    /// are snapped to is drawn behind the `Widget`s.
+    pub fn set_design_mode(&mut self, active: bool) {
+        self.design_mode.set_active(active);
+        self.sync_design_grid();
+        self.redraw();
+    }
+
+    /// Assigns the callback closure that will be used when a `Widget` is selected, moved or resized
+    /// in `DesignMode`.
+    pub fn on_design_change<F>(&mut self, callback: F)
+    where
+        F: FnMut(&DesignChange) + 'static,
+    {
+        self.design_mode.on_change(callback);
+    }
+
+    /// Registers the `Widget` type `W` as `type_name`, so that layout files can create `Widget`s of
+    /// this type by calling `constructor`.  See `WidgetRegistry::register`.
+    pub fn register_widget_type<W, F>(&mut self, type_name: &str, constructor: F)
+    where
+        W: Widget + 'static,
+        F: Fn(Points, Size) -> W + 'static,
+    {
+        self.widget_registry.register(type_name, constructor);
+    }
+
+    /// Exports the `Widget` tree and `Layout`s to a layout file in the given `format`, such as after
+    /// editing them in `DesignMode`.  See `layout_file::export_layout`.
+    pub fn export_layout(&mut self, format: LayoutFormat) -> Result<String, LayoutFileError> {
+        export_layout(
+            self.widget_cache.borrow_cache(),
+            self.layout_cache.get_layout_cache(),
+            &self.widget_registry,
+            format,
+        )
+    }
+
+    /// Imports a layout file in the given `format`, applying it to the `Widget`s and `Layout`s that
+    /// have already been added, and creating any new `Widget`s and `Layout`s it describes.  Returns
+    /// the number of `Widget`s created or updated.  See `layout_file::import_layout`.
+    pub fn import_layout(
+        &mut self,
+        text: &str,
+        format: LayoutFormat,
+    ) -> Result<usize, LayoutFileError> {
+        import_layout(
+            text,
+            format,
+            &self.widget_registry,
+            &mut self.widget_cache,
+            &mut self.layout_cache,
+        )
+    }
+
+    /// Exports the `Widget` tree and `Layout`s to a file, as JSON if its extension is `.json`, and as
+    /// RON otherwise.
+    pub fn save_layout<P: AsRef<Path>>(&mut self, path: P) -> Result<(), LayoutFileError> {
+        save_layout(
+            path,
+            self.widget_cache.borrow_cache(),
+            self.layout_cache.get_layout_cache(),
+            &self.widget_registry,
+        )
+    }
+
+    /// Imports a layout from a file, as JSON if its extension is `.json`, and as RON otherwise.
+    /// Returns the number of `Widget`s created or updated.
+    pub fn load_layout<P: AsRef<Path>>(&mut self, path: P) -> Result<usize, LayoutFileError> {
+        load_layout(
+            path,
+            &self.widget_registry,
+            &mut self.widget_cache,
+            &mut self.layout_cache,
+        )
+    }
+
+    /// Adds or removes the grid drawn behind the `Widget`s, to match the state of `DesignMode`.
+    fn sync_design_grid(&mut self) {
+        if self.design_mode.is_active() && self.design_grid.is_none() {
+            let size = self
+                .widget_cache
+                .get_container_by_id(0)
+                .widget
+                .borrow_mut()
+                .get_config()
+                .get_size(CONFIG_SIZE);
+            let mut grid = GridWidget::new(
+                make_points_origin(),
+                size,
+                self.design_mode.get_grid_size().max(2),
+                true,
+            );
+
+            grid.get_config()
+                .set_numeric(CONFIG_LAYER, Layer::Background as i32);
+
+            let handle = self.widget_cache.add_widget(Box::new(grid), "");
+
+            self.design_mode.set_excluded_widget_id(handle.id());
+            self.design_grid = Some(handle);
+        } else if !self.design_mode.is_active() {
+            if let Some(handle) = self.design_grid.take() {
+                self.widget_cache.remove_widget(handle).ok();
+                self.design_mode.set_excluded_widget_id(0);
+            }
+        }
+    }
+
+    /// Moves the `Engine`'s window so that it is centered on a display, by index.  The move takes place
+    /// at the start of the next frame.  Use `display::displays` to enumerate the connected displays.
+    pub fn move_to_display(&mut self, display_index: i32) {
+        self.pending_display = Some(display_index);
+    }
+
+    /// Retrieves the index of the display the `Engine`'s window is on, or `-1` if the `Engine` is not
+    /// running.
+    pub fn get_display_index(&self) -> i32 {
+        self.display_index
+    }
+
+    /// Turns per-display scaling on or off.  When on, drawing is scaled by the scale factor of the
+    /// display the window is on (see `DisplayInfo::scale`), and is updated whenever the window moves
+    /// to a different display.  The window is resized by the same factor (see
+    /// `DisplayInfo::scale_size`), so that the size of the base `Widget`, and the `Layout`s that
+    /// follow it, are unchanged.  This is off by default.
+    pub fn set_display_scaling(&mut self, display_scaling: bool) {
+        self.display_scaling = display_scaling;
+        self.display_index = -1;
+    }
+
+    /// Assigns the callback closure that will be used when the `Engine`'s window moves to a different
+    /// display, including when it is first shown.
+    pub fn on_display_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&DisplayInfo) + 'static,
+    {
+        self.on_display_changed = Some(Box::new(callback));
+    }
+
+    /// Applies a pending `move_to_display` request, and detects when the window has moved to a
+    /// different display, updating the scale and triggering the `on_display_changed` callback.
+    fn update_display(&mut self, canvas: &mut Canvas<Window>) {
+        if let Some(display_index) = self.pending_display.take() {
+            if let Err(e) = move_window_to_display(canvas.window_mut(), display_index) {
+                eprintln!("Unable to move window to display {}: {}", display_index, e);
+            }
+        }
+
+        let display_index = canvas.window().display_index().unwrap_or(-1);
+
+        if display_index == self.display_index || display_index < 0 {
+            return;
+        }
+
+        self.display_index = display_index;
+
+        let display = match window_display(canvas.window()) {
+            Ok(display) => display,
+            Err(_) => return,
+        };
+        let scale = if self.display_scaling {
+            display.scale()
+        } else {
+            1.0
+        };
+
+        if let Err(e) = canvas.set_scale(scale, scale) {
+            eprintln!("Unable to scale drawing by {}: {}", scale, e);
+            return;
+        }
+
+        let size = self
+            .widget_cache
+            .get_container_by_id(0)
+            .widget
+            .borrow_mut()
+            .get_config()
+            .get_size(CONFIG_SIZE);
+        let window_size = if self.display_scaling {
+            display.scale_size(size[SIZE_WIDTH], size[SIZE_HEIGHT])
+        } else {
+            (size[SIZE_WIDTH], size[SIZE_HEIGHT])
+        };
+
+        self.display_scale = scale;
+
+        if canvas.window().size() != window_size {
+            if let Err(e) = canvas.window_mut().set_size(window_size.0, window_size.1) {
+                eprintln!("Unable to resize window for display scaling: {}", e);
+            }
+        }
+
+        self.redraw();
+
+        if let Some(mut cb) = self.on_display_changed.take() {
+            cb(&display);
+            self.on_display_changed = Some(cb);
+        }
+    }
+
+    /// Sets the shape of the `Engine`'s window, which must have been created with
+    /// `window_shape::create_shaped_window`.  The shape is applied at the end of the next frame that is
+    /// drawn, and is updated on each redraw if it follows the `Widget`s drawn.  Setting `None` leaves
+    /// the current shape in place.
+    pub fn set_window_shape(&mut self, window_shape: Option<WindowShape>) {
+        self.window_shape = window_shape;
+        self.window_shape_dirty = true;
+        self.redraw();
+    }
+
+    /// Sets the opacity of the entire `Engine` window, from `0.0` (fully transparent) to `1.0` (fully
+    /// opaque.)  This is applied at the start of the next frame, and is ignored on platforms that do
+    /// not support window opacity.
+    pub fn set_window_opacity(&mut self, opacity: f32) {
+        self.window_opacity = Some(opacity.clamp(0.0, 1.0));
+    }
+
+    /// Applies a pending `set_window_opacity` request.
+    fn update_window_opacity(&mut self, canvas: &mut Canvas<Window>) {
+        if let Some(opacity) = self.window_opacity.take() {
+            if let Err(e) = canvas.window_mut().set_opacity(opacity) {
+                eprintln!("Unable to set window opacity: {}", e);
+            }
+        }
+    }
+
+    /// Enables SDL2 text input while the focused `Widget` accepts text input, placing the IME
+    /// candidate window at its bounds, and disables it otherwise.  SDL2 is only called when the
+    /// focused `Widget` or its bounds change.
+    fn update_text_input(&mut self, canvas: &mut Canvas<Window>) {
+        let bounds = self
+            .widget_cache
+            .get_text_input_bounds(self.widget_cache.get_focus());
+
+        if self.text_input_bounds == Some(bounds) {
+            return;
+        }
+
+        let text_input = canvas.window().subsystem().text_input();
+
+        match bounds {
+            Some(rect) => {
+                text_input.set_rect(rect);
+                text_input.start();
+            }
+            None => text_input.stop(),
+        }
+
+        self.text_input_bounds = Some(bounds);
+    }
+
+    /// Updates the shape of a shaped window after a frame has been drawn.
+    fn update_window_shape(&mut self, canvas: &mut Canvas<Window>, drawn: bool) {
+        let window_shape = match self.window_shape {
+            Some(window_shape) => window_shape,
+            None => return,
+        };
+
+        if !(self.window_shape_dirty || (drawn && window_shape.follow_widgets))
+            || !is_shaped_window(canvas.window())
+        {
+            return;
+        }
+
+        let (width, height) = canvas.window().size();
+        let mask = window_shape.build_mask(width, height, &self.widget_cache.get_visible_bounds());
+
+        if let Err(e) = set_window_shape(canvas.window(), &mask) {
+            eprintln!("Unable to set window shape: {}", e);
+        }
+
+        self.window_shape_dirty = false;
+    }
+
     /// Sets running flag: `false` shuts down the engine.
     pub fn set_running(&mut self, state: bool) {
         self.running = state;
@@ -112,6 +617,217 @@ impl Engine {
         self.on_exit = Some(Box::new(callback));
     }
 
+    /// Adds a timer that calls `callback` once `duration` has elapsed, and every `duration` after
+    /// that if `repeating` is set.  The callback is called from the main loop, after the `Widget`s
+    /// are ticked, with access to the `Widget`s and `Layout`s.  Returns a `TimerHandle` that can be
+    /// used to cancel the timer with `cancel_timer`.
+    pub fn add_timer<F>(&mut self, duration: Duration, repeating: bool, callback: F) -> TimerHandle
+    where
+        F: FnMut(&[WidgetContainer], &[LayoutContainer]) + 'static,
+    {
+        self.timers.add(duration, repeating, callback)
+    }
+
+    /// Cancels the timer referred to by `handle`.  Returns `false` if the timer has already fired,
+    /// and was not repeating, or has already been cancelled.
+    pub fn cancel_timer(&mut self, handle: TimerHandle) -> bool {
+        self.timers.cancel(handle)
+    }
+
+    /// Indicates whether or not the timer referred to by `handle` is still waiting to fire.
+    pub fn is_timer_active(&self, handle: TimerHandle) -> bool {
+        self.timers.is_active(handle)
+    }
+
+    /// Returns an `EventSender`, which other threads can use to post messages to the `Engine`.  The
+    /// messages are delivered to the handler set with `on_event_message` each frame, after the
+    /// `Widget`s are ticked.
+    pub fn get_event_sender(&self) -> EventSender {
+        EventSender::new(self.message_sender.clone())
+    }
+
+    /// Assigns the callback closure that receives the messages posted with an `EventSender`, along
+    /// with mutable access to the `WidgetCache`.  Messages received while this is not set are
+    /// discarded.
+    pub fn on_event_message<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut WidgetCache, EventMessage) + 'static,
+    {
+        self.on_event_message = Some(Box::new(callback));
+    }
+
+    /// Sets the event filter, which is called with every `Event` before the `Engine` handles it,
+    /// including the `Event`s used by the `Inspector` and `DesignMode`, and those synthesized by
+    /// `Widget`s.  The filter returns a `FilterResult`: `Pass` to handle the `Event` as usual,
+    /// `Replace` to handle another `Event` in its place, or `Swallow` to discard it.  This allows
+    /// applications to implement global keyboard shortcuts, record input, or lock down a kiosk.
+    /// Replaces any filter that was previously set.
+    pub fn set_event_filter<F>(&mut self, filter: F)
+    where
+        F: FnMut(&Event) -> FilterResult + 'static,
+    {
+        self.event_filter = Some(Box::new(filter));
+    }
+
+    /// Removes the event filter, so that all `Event`s are handled by the `Engine` as usual.
+    pub fn clear_event_filter(&mut self) {
+        self.event_filter = None;
+    }
+
+    /// Registers `T` as a custom event type, so that values of `T` pushed to the SDL2 event queue with
+    /// `EventSubsystem::push_custom_event` (or with an `sdl2::event::EventSender` from another
+    /// thread) are received by the `Engine`.  Each custom event is sent to the `on_custom_event`
+    /// callback, then to the `custom_event` method of every visible, enabled `Widget`.  Returns an
+    /// error if `T` has already been registered.
+    pub fn register_event_type<T: Any>(&mut self, sdl: &Sdl) -> Result<(), String> {
+        self.custom_events.register::<T>(sdl)
+    }
+
+    /// Queues `event` to be delivered as a custom event on the next frame, as though it had been
+    /// pushed to the SDL2 event queue.  The type of `event` does not need to be registered.
+    pub fn push_event<T: Any>(&mut self, event: T) {
+        self.pending_custom_events.push(Box::new(event));
+    }
+
+    /// Assigns the callback closure that receives custom events, before they are sent to the
+    /// `Widget`s.  Use `downcast_ref` to retrieve the application's event type.
+    pub fn on_custom_event<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut Engine, &dyn Any) + 'static,
+    {
+        self.on_custom_event = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when a file is dropped onto the window, and the
+    /// `Widget` under the mouse does not handle it with its own `on_file_dropped` callback.  Files
+    /// dropped while this is not set are ignored.
+    pub fn on_file_dropped<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut Engine, String) + 'static,
+    {
+        self.on_file_dropped = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when text is dropped onto the window, and the
+    /// `Widget` under the mouse does not handle it with its own `on_text_dropped` callback.  Text
+    /// dropped while this is not set is ignored.
+    pub fn on_text_dropped<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut Engine, String) + 'static,
+    {
+        self.on_text_dropped = Some(Box::new(callback));
+    }
+
+    /// Shows the `Widget` referred to by `dialog`, along with all of its descendants, as a modal
+    /// dialog.  The rest of the UI is dimmed, and all mouse and keyboard events are sent to the
+    /// `Widget`s of the dialog until it is closed, either with `close_modal`, or by setting
+    /// `CONFIG_DIALOG_RESULT` on the `Widget` (see `pushrod::render::modal::close_dialog`.)  Add the
+    /// `Widget`s of the dialog to the `Engine`, and make them children of the dialog's root `Widget`
+    /// with `set_parent`, before showing it.  Hide the root `Widget` of the dialog and its
+    /// descendants when adding them, so that the dialog is not drawn until it is shown.
+    pub fn show_modal(&mut self, dialog: WidgetHandle) -> Result<(), WidgetHandleError> {
+        let widget_id = self.widget_cache.validate_handle(dialog)?;
+
+        self.hide_tooltip();
+        self.captured_widget_id = 0;
+        self.widget_cache.show_modal(widget_id);
+
+        let current_widget_id = self.current_widget_id;
+
+        self.current_widget_id = widget_id;
+        self.widget_cache
+            .mouse_exited(current_widget_id, self.layout_cache.get_layout_cache());
+        self.widget_cache
+            .mouse_entered(widget_id, self.layout_cache.get_layout_cache());
+
+        Ok(())
+    }
+
+    /// Closes the modal dialog referred to by `dialog`, hiding it along with all of its
+    /// descendants, and triggers the `on_dialog_closed` callback with `result`.  Nothing happens if
+    /// the `Widget` is not a modal dialog that is currently shown.
+    pub fn close_modal(
+        &mut self,
+        dialog: WidgetHandle,
+        result: i32,
+    ) -> Result<(), WidgetHandleError> {
+        let widget_id = self.widget_cache.validate_handle(dialog)?;
+
+        if self.widget_cache.close_modal(widget_id) {
+            self.call_dialog_closed_callback(dialog, result);
+        }
+
+        Ok(())
+    }
+
+    /// Internal function that triggers the `on_dialog_closed` callback.
+    fn call_dialog_closed_callback(&mut self, dialog: WidgetHandle, result: i32) {
+        if let Some(mut cb) = self.on_dialog_closed.take() {
+            cb(self, dialog, result);
+            self.on_dialog_closed = Some(cb);
+        }
+    }
+
+    /// Assigns the callback closure that will be used when a modal dialog is closed.  It receives
+    /// the `WidgetHandle` of the dialog, and the result it was closed with.
+    pub fn on_dialog_closed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut Engine, WidgetHandle, i32) + 'static,
+    {
+        self.on_dialog_closed = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_dialog_closed` callback for the modal dialogs that
+    /// were removed while they were shown, either by `remove_widget` or by setting `CONFIG_REMOVE`.
+    /// The `WidgetHandle` each callback receives is stale, as the dialog no longer exists.
+    fn close_removed_dialogs(&mut self) {
+        for (dialog, result) in self.widget_cache.take_removed_modals() {
+            self.call_dialog_closed_callback(dialog, result);
+        }
+    }
+
+    /// Internal function that closes the modal dialogs that have requested to be closed by setting
+    /// `CONFIG_DIALOG_RESULT`.
+    fn close_requested_dialogs(&mut self) {
+        while let Some((widget_id, result)) = self.widget_cache.find_dialog_result() {
+            match self.widget_cache.get_handle(widget_id) {
+                Some(dialog) => self.close_modal(dialog, result).unwrap(),
+                None => break,
+            }
+        }
+    }
+
+    /// Retrieves the `FrameStats` gathered during the most recently completed frame.
+    pub fn get_frame_stats(&self) -> FrameStats {
+        self.frame_stats
+    }
+
+    /// Assigns the callback closure that receives the `FrameStats` at the end of every frame.  Use
+    /// this to log performance information without the need for a visual overlay.
+    pub fn on_frame_stats<F>(&mut self, callback: F)
+    where
+        F: FnMut(&FrameStats) + 'static,
+    {
+        self.on_frame_stats = Some(Box::new(callback));
+    }
+
+    /// Sets the texture memory budget in bytes.  When the estimated GPU memory used by `Texture`s
+    /// exceeds this budget, the `on_texture_budget_exceeded` callback is triggered.  A budget of `0`
+    /// (the default) turns off the check.
+    pub fn set_texture_memory_budget(&mut self, bytes: u64) {
+        self.texture_memory_budget = bytes;
+        self.texture_budget_exceeded = false;
+    }
+
+    /// Assigns the callback closure that is triggered when the texture memory budget is exceeded.
+    /// The callback receives the estimated number of bytes in use, followed by the budget.
+    pub fn on_texture_budget_exceeded<F>(&mut self, callback: F)
+    where
+        F: FnMut(u64, u64) + 'static,
+    {
+        self.on_texture_budget_exceeded = Some(Box::new(callback));
+    }
+
     /// Internal function that triggers the `on_exit` callback.
     fn call_exit_callback(&mut self) -> bool {
         if let Some(mut cb) = self.on_exit.take() {
@@ -125,6 +841,505 @@ impl Engine {
         }
     }
 
+    /// Internal function that delivers the messages posted with an `EventSender` to the
+    /// `on_event_message` callback.
+    fn receive_event_messages(&mut self) {
+        while let Ok(message) = self.message_receiver.try_recv() {
+            if let Some(cb) = self.on_event_message.as_mut() {
+                cb(&mut self.widget_cache, message);
+            }
+        }
+    }
+
+    /// Internal function that sends a custom event to the `on_custom_event` callback, then to the
+    /// `Widget`s.
+    fn dispatch_custom_event(&mut self, event: &dyn Any) {
+        if let Some(mut cb) = self.on_custom_event.take() {
+            cb(self, event);
+            self.on_custom_event = Some(cb);
+        }
+
+        self.widget_cache
+            .custom_event(event, self.layout_cache.get_layout_cache());
+    }
+
+    /// Internal function that triggers the `on_file_dropped` callback.
+    fn call_file_dropped_callback(&mut self, path: String) {
+        if let Some(mut cb) = self.on_file_dropped.take() {
+            cb(self, path);
+            self.on_file_dropped = Some(cb);
+        }
+    }
+
+    /// Internal function that triggers the `on_text_dropped` callback.
+    fn call_text_dropped_callback(&mut self, text: String) {
+        if let Some(mut cb) = self.on_text_dropped.take() {
+            cb(self, text);
+            self.on_text_dropped = Some(cb);
+        }
+    }
+
+    /// Forces the entire display list to be redrawn on the next frame.
+    pub fn redraw(&mut self) {
+        self.widget_cache
+            .get_container_by_id(0)
+            .widget
+            .borrow_mut()
+            .set_invalidated(true);
+    }
+
+    /// Performs a single frame of work without drawing: `Widget`s are sent a `tick`, `Layout`s are
+    /// performed, modal dialogs that have requested to be closed are closed, and events synthesized
+    /// by `Widget`s are handled.
+    pub fn step(&mut self) {
+        self.widget_cache.tick(self.layout_cache.get_layout_cache());
+        self.timers.fire(
+            self.widget_cache.borrow_cache(),
+            self.layout_cache.get_layout_cache(),
+        );
+        self.receive_event_messages();
+
+        for event in std::mem::take(&mut self.pending_custom_events) {
+            self.dispatch_custom_event(event.as_ref());
+        }
+        self.remove_widgets_from_layouts();
+
+        match self.widget_cache.get_tick_pool() {
+            Some(pool) => self
+                .layout_cache
+                .do_parallel_layout(self.widget_cache.borrow_cache(), &pool),
+            None => self
+                .layout_cache
+                .do_layout(self.widget_cache.borrow_cache()),
+        }
+
+        self.close_requested_dialogs();
+        self.close_removed_dialogs();
+        self.handle_synthesized_events();
+        self.widget_cache
+            .focus_callbacks(self.layout_cache.get_layout_cache());
+    }
+
+    /// Steps `frames` frames without drawing.  If a `MockClock` is installed, it is advanced by the
+    /// duration of one frame (based on the frame rate) before each frame is stepped, so animations
+    /// and timers can be tested deterministically without sleeping.
+    pub fn advance_frames(&mut self, frames: u32) {
+        let frame_ms = self.frame_duration_ms();
+
+        for _ in 0..frames {
+            clock::advance_millis(frame_ms);
+            self.step();
+        }
+    }
+
+    /// Advances time by `ms` milliseconds, stepping one frame for each frame-length interval that
+    /// elapses, and a final frame for any remainder.  Requires a `MockClock` to be installed to
+    /// advance time; otherwise, frames are stepped with the wall clock.
+    pub fn advance_time(&mut self, ms: u64) {
+        let frame_ms = self.frame_duration_ms();
+        let mut remaining = ms;
+
+        while remaining > 0 {
+            let elapsed = remaining.min(frame_ms);
+
+            clock::advance_millis(elapsed);
+            self.step();
+            remaining -= elapsed;
+        }
+    }
+
+    fn frame_duration_ms(&self) -> u64 {
+        (1000 / u64::from(self.frame_rate.max(1))).max(1)
+    }
+
+    /// Performs a single frame of work without handling any events: `Widget`s are sent a `tick`,
+    /// `Layout`s are performed, and the display list is drawn to the `canvas` if any `Widget` has been
+    /// invalidated.  Returns `true` if the `canvas` was drawn to.  The `canvas` is not presented; this
+    /// is left up to the caller.
+    pub fn draw_frame(&mut self, canvas: &mut Canvas<Window>) -> bool {
+        self.step();
+
+        if self.inspector.is_active() {
+            self.redraw();
+        }
+
+        if self.tooltip.update(self.widget_cache.borrow_cache()) {
+            self.redraw();
+        }
+
+        let draw_start = Instant::now();
+        let drawn = self.widget_cache.draw_loop(canvas);
+
+        if drawn {
+            self.tooltip
+                .draw(canvas, self.widget_cache.get_texture_cache());
+        }
+
+        if drawn && self.inspector.is_active() {
+            let (widgets, textures) = self.widget_cache.borrow_cache_and_textures();
+
+            self.inspector.draw(canvas, widgets, textures);
+        }
+
+        if drawn && self.design_mode.is_active() {
+            self.design_mode
+                .draw(canvas, self.widget_cache.borrow_cache());
+        }
+
+        let draw_time = draw_start.elapsed();
+
+        self.finish_frame_stats(drawn, draw_time);
+
+        drawn
+    }
+
+    /// Internal function that completes the `FrameStats` for the current frame, and triggers the
+    /// `on_frame_stats` callback.
+    fn finish_frame_stats(&mut self, drawn: bool, draw_time: Duration) {
+        let (created, destroyed) = texture_counts();
+
+        self.frame_stats = FrameStats {
+            frame_number: self.frame_stats.frame_number + 1,
+            frame_time: self.frame_start.elapsed(),
+            draw_time,
+            widgets_drawn: if drawn {
+                self.widget_cache.get_widgets_drawn()
+            } else {
+                0
+            },
+            textures_created: created - self.texture_counts.0,
+            textures_destroyed: destroyed - self.texture_counts.1,
+            events_processed: self.events_processed,
+            texture_memory: self.widget_cache.get_texture_memory(),
+        };
+
+        self.texture_counts = (created, destroyed);
+        self.events_processed = 0;
+        self.frame_start = Instant::now();
+
+        if let Some(mut cb) = self.on_frame_stats.take() {
+            cb(&self.frame_stats);
+            self.on_frame_stats = Some(cb);
+        }
+
+        self.check_texture_budget();
+    }
+
+    /// Internal function that triggers the `on_texture_budget_exceeded` callback when the texture
+    /// memory in use first exceeds the budget.  The callback is triggered again only after the memory
+    /// in use has dropped back within the budget.
+    fn check_texture_budget(&mut self) {
+        if self.texture_memory_budget == 0 {
+            return;
+        }
+
+        let texture_memory = self.frame_stats.texture_memory;
+        let exceeded = texture_memory > self.texture_memory_budget;
+
+        if exceeded && !self.texture_budget_exceeded {
+            if let Some(mut cb) = self.on_texture_budget_exceeded.take() {
+                cb(texture_memory, self.texture_memory_budget);
+                self.on_texture_budget_exceeded = Some(cb);
+            }
+        }
+
+        self.texture_budget_exceeded = exceeded;
+    }
+
+    /// Handles a single SDL2 `Event`, dispatching it to the `Inspector`, or the appropriate `Widget`
+    /// in the display list.  This is called by `run` for each event received, and can be called
+    /// directly to inject events into the `Engine` (see `pushrod::testing::simulate`.)  If a `Quit`
+    /// event is received and the application agrees to quit, the `Engine` stops running.
+    pub fn handle_event(&mut self, event: Event) {
+        self.events_processed += 1;
+
+        let event = match self.event_filter.as_mut().map(|filter| filter(&event)) {
+            None | Some(FilterResult::Pass) => event,
+            Some(FilterResult::Replace(replacement)) => replacement,
+            Some(FilterResult::Swallow) => return,
+        };
+
+        if let Some(custom_event) = self.custom_events.decode(&event) {
+            self.dispatch_custom_event(custom_event.as_ref());
+            return;
+        }
+
+        if self.inspector.handle_event(&event, &mut self.widget_cache) {
+            return;
+        }
+
+        let design_was_active = self.design_mode.is_active();
+
+        if self
+            .design_mode
+            .handle_event(&event, &mut self.widget_cache)
+        {
+            if design_was_active != self.design_mode.is_active() {
+                self.sync_design_grid();
+            }
+
+            return;
+        }
+
+        match event {
+            Event::MouseButtonDown {
+                mouse_btn, clicks, ..
+            } => {
+                self.hide_tooltip();
+
+                if self.widget_cache.captures_mouse(self.current_widget_id) {
+                    self.captured_widget_id = self.current_widget_id;
+                }
+
+                if self
+                    .widget_cache
+                    .takes_focus_on_click(self.current_widget_id)
+                {
+                    self.widget_cache.set_focus(self.current_widget_id);
+                }
+
+                self.widget_cache.button_clicked(
+                    self.current_widget_id,
+                    mouse_btn as u8,
+                    clicks,
+                    true,
+                    self.layout_cache.get_layout_cache(),
+                );
+            }
+
+            Event::MouseButtonUp {
+                mouse_btn, clicks, ..
+            } => {
+                self.captured_widget_id = 0;
+                self.widget_cache.button_clicked(
+                    -1,
+                    mouse_btn as u8,
+                    clicks,
+                    false,
+                    self.layout_cache.get_layout_cache(),
+                );
+            }
+
+            // While a `Widget` has captured the mouse, it receives all mouse movement, and no other
+            // `Widget` is entered or exited.
+            Event::MouseMotion { x, y, .. } if self.captured_widget_id != 0 => {
+                self.widget_cache.mouse_moved(
+                    self.captured_widget_id,
+                    vec![x, y],
+                    self.layout_cache.get_layout_cache(),
+                );
+            }
+
+            Event::MouseMotion { x, y, .. } => {
+                let cur_widget_id = self.current_widget_id;
+
+                self.current_widget_id = self.widget_cache.find_widget(x, y);
+
+                if cur_widget_id != self.current_widget_id {
+                    self.widget_cache
+                        .mouse_exited(cur_widget_id, self.layout_cache.get_layout_cache());
+                    self.widget_cache.mouse_entered(
+                        self.current_widget_id,
+                        self.layout_cache.get_layout_cache(),
+                    );
+                }
+
+                self.widget_cache.mouse_moved(
+                    self.current_widget_id,
+                    vec![x, y],
+                    self.layout_cache.get_layout_cache(),
+                );
+
+                if self.tooltip.mouse_moved(self.current_widget_id, x, y) {
+                    self.redraw();
+                }
+
+                self.cursors
+                    .update(self.widget_cache.borrow_cache(), self.current_widget_id);
+            }
+
+            Event::MouseWheel { x, y, .. } => {
+                self.hide_tooltip();
+                self.widget_cache.mouse_scrolled(
+                    self.current_widget_id,
+                    vec![x, y],
+                    self.layout_cache.get_layout_cache(),
+                );
+            }
+
+            Event::Window {
+                win_event: WindowEvent::SizeChanged(w, h),
+                ..
+            } => {
+                // The window is sized in pixels, and the `Widget`s are sized before display scaling.
+                let size = make_size(
+                    ((w as f32 / self.display_scale).round() as u32).max(1),
+                    ((h as f32 / self.display_scale).round() as u32).max(1),
+                );
+
+                self.layout_cache.window_resized(size.clone());
+                self.widget_cache
+                    .window_resized(size, self.layout_cache.get_layout_cache());
+                self.redraw();
+            }
+
+            // Dropped files and text go to the `Widget` under the mouse, or to the `Engine`'s
+            // callbacks if it does not handle them.
+            Event::DropFile { filename, .. } => {
+                if !self.widget_cache.file_dropped(
+                    self.current_widget_id,
+                    filename.clone(),
+                    self.layout_cache.get_layout_cache(),
+                ) {
+                    self.call_file_dropped_callback(filename);
+                }
+            }
+
+            Event::DropText { filename, .. } => {
+                if !self.widget_cache.text_dropped(
+                    self.current_widget_id,
+                    filename.clone(),
+                    self.layout_cache.get_layout_cache(),
+                ) {
+                    self.call_text_dropped_callback(filename);
+                }
+            }
+
+            Event::Quit { .. } => {
+                if self.call_exit_callback() {
+                    self.running = false;
+                }
+            }
+
+            remaining_event => {
+                if let Event::KeyDown { .. }
+                | Event::Window {
+                    win_event: WindowEvent::Leave,
+                    ..
+                } = remaining_event
+                {
+                    self.hide_tooltip();
+                }
+
+                // Tab and Shift-Tab move the focus, unless the focused `Widget` uses Tab itself, in
+                // which case Ctrl-Tab and Ctrl-Shift-Tab do.
+                if let Event::KeyDown {
+                    keycode: Some(Keycode::Tab),
+                    keymod,
+                    ..
+                } = remaining_event
+                {
+                    let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+
+                    if ctrl || !self.widget_cache.focus_captures_tab_key() {
+                        self.widget_cache
+                            .focus_next(keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD));
+                        self.widget_cache
+                            .focus_callbacks(self.layout_cache.get_layout_cache());
+                        return;
+                    }
+                }
+
+                // Keyboard events go to the focused `Widget`, if there is one.
+                let focused_id = self.widget_cache.get_focus();
+                let target_id = match remaining_event {
+                    Event::KeyDown { .. }
+                    | Event::KeyUp { .. }
+                    | Event::TextInput { .. }
+                    | Event::TextEditing { .. }
+                        if focused_id != 0 =>
+                    {
+                        focused_id
+                    }
+                    _ => self.current_widget_id,
+                };
+
+                // Text is also sent to the focused `Widget`'s `text_input` and `text_editing`
+                // methods, before the `Event` itself.
+                if focused_id != 0 {
+                    match &remaining_event {
+                        Event::TextInput { text, .. } => self.widget_cache.text_input(
+                            focused_id,
+                            text.clone(),
+                            self.layout_cache.get_layout_cache(),
+                        ),
+                        Event::TextEditing {
+                            text,
+                            start,
+                            length,
+                            ..
+                        } => self.widget_cache.text_editing(
+                            focused_id,
+                            text.clone(),
+                            *start,
+                            *length,
+                            self.layout_cache.get_layout_cache(),
+                        ),
+                        _ => {}
+                    }
+                }
+
+                // Key presses and releases are also sent to the focused `Widget`'s `key_pressed`
+                // and `key_released` methods, before the `Event` itself.
+                if focused_id != 0 {
+                    match remaining_event {
+                        Event::KeyDown {
+                            keycode: Some(keycode),
+                            keymod,
+                            repeat,
+                            ..
+                        } => self.widget_cache.key_pressed(
+                            focused_id,
+                            keycode,
+                            keymod,
+                            repeat,
+                            self.layout_cache.get_layout_cache(),
+                        ),
+                        Event::KeyUp {
+                            keycode: Some(keycode),
+                            keymod,
+                            ..
+                        } => self.widget_cache.key_released(
+                            focused_id,
+                            keycode,
+                            keymod,
+                            self.layout_cache.get_layout_cache(),
+                        ),
+                        _ => {}
+                    }
+                }
+
+                self.widget_cache.other_event(
+                    target_id,
+                    remaining_event,
+                    self.layout_cache.get_layout_cache(),
+                );
+            }
+        }
+
+        self.handle_synthesized_events();
+    }
+
+    /// Internal function that handles the events synthesized by `Widget`s, such as the key presses
+    /// of an on-screen keyboard, as though they had been received from SDL2.
+    fn handle_synthesized_events(&mut self) {
+        for event in self.widget_cache.take_synthesized_events() {
+            self.handle_event(event);
+        }
+    }
+
+    /// Internal function that hides the tooltip, redrawing the screen if one was shown.
+    fn hide_tooltip(&mut self) {
+        if self.tooltip.hide() {
+            self.redraw();
+        }
+    }
+
+    /// Indicates whether or not the `Engine` is still running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
     /// Main application run loop, controls interaction between the user and the application.
     pub fn run(&mut self, sdl: Sdl, window: Window) {
         let mut canvas = window
@@ -147,81 +1362,23 @@ impl Engine {
                 .unwrap()
                 .as_millis();
 
-            for event in event_pump.poll_iter() {
-                match event {
-                    Event::MouseButtonDown {
-                        mouse_btn, clicks, ..
-                    } => {
-                        self.widget_cache.button_clicked(
-                            self.current_widget_id,
-                            mouse_btn as u8,
-                            clicks,
-                            true,
-                            self.layout_cache.get_layout_cache(),
-                        );
-                    }
-
-                    Event::MouseButtonUp {
-                        mouse_btn, clicks, ..
-                    } => {
-                        self.widget_cache.button_clicked(
-                            -1,
-                            mouse_btn as u8,
-                            clicks,
-                            false,
-                            self.layout_cache.get_layout_cache(),
-                        );
-                    }
-
-                    Event::MouseMotion { x, y, .. } => {
-                        let cur_widget_id = self.current_widget_id;
-
-                        self.current_widget_id = self.widget_cache.find_widget(x, y);
-
-                        if cur_widget_id != self.current_widget_id {
-                            self.widget_cache
-                                .mouse_exited(cur_widget_id, self.layout_cache.get_layout_cache());
-                            self.widget_cache.mouse_entered(
-                                self.current_widget_id,
-                                self.layout_cache.get_layout_cache(),
-                            );
-                        }
-
-                        self.widget_cache.mouse_moved(
-                            self.current_widget_id,
-                            vec![x, y],
-                            self.layout_cache.get_layout_cache(),
-                        );
-                    }
-
-                    Event::MouseWheel { x, y, .. } => {
-                        self.widget_cache.mouse_scrolled(
-                            self.current_widget_id,
-                            vec![x, y],
-                            self.layout_cache.get_layout_cache(),
-                        );
-                    }
+            self.frame_start = Instant::now();
 
-                    Event::Quit { .. } => {
-                        if self.call_exit_callback() {
-                            break 'running;
-                        }
-                    }
+            for event in event_pump.poll_iter() {
+                self.handle_event(event);
 
-                    remaining_event => {
-                        self.widget_cache.other_event(
-                            self.current_widget_id,
-                            remaining_event,
-                            self.layout_cache.get_layout_cache(),
-                        );
-                    }
+                if !self.running {
+                    break 'running;
                 }
             }
 
-            self.widget_cache.tick(self.layout_cache.get_layout_cache());
-            self.layout_cache
-                .do_layout(self.widget_cache.borrow_cache());
-            self.widget_cache.draw_loop(&mut canvas);
+            self.update_display(&mut canvas);
+            self.update_window_opacity(&mut canvas);
+            self.update_text_input(&mut canvas);
+
+            let drawn = self.draw_frame(&mut canvas);
+
+            self.update_window_shape(&mut canvas, drawn);
 
             canvas.present();
 
@@ -243,3 +1400,24 @@ impl Engine {
         }
     }
 }
+
+/// Opens `url` in the default web browser of the system, or any other URL with the application the
+/// system associates with it.  This returns as soon as the application has been started.  Returns
+/// an error if the application could not be started.
+///
+/// On Windows, the URL is handed to the shell's protocol handler directly, rather than through
+/// `cmd`, which would interpret characters such as `&` and `%` in the URL.
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("rundll32");
+
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+
+    command.arg(url).spawn().map(|_| ())
+}
@@ -0,0 +1,35 @@
+// Pushrod Rendering Library
+// Event Filter
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sdl2::event::Event;
+
+/// This is the result returned by an event filter, which decides what happens to each `Event`
+/// before the `Engine` handles it.
+#[derive(Clone, Debug)]
+pub enum FilterResult {
+    /// The `Event` is handled by the `Engine` as usual.
+    Pass,
+
+    /// The `Event` is replaced by another `Event`, which is handled by the `Engine` instead.
+    Replace(Event),
+
+    /// The `Event` is discarded, and is not handled by the `Engine`.
+    Swallow,
+}
+
+/// This is the callback type that is used to filter `Event`s, set with `Engine::set_event_filter`.
+/// It is called with every `Event` before it is handled by the `Engine`, and returns a
+/// `FilterResult` deciding what happens to it.
+pub type EventFilterType = Option<Box<dyn FnMut(&Event) -> FilterResult>>;
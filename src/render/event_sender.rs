@@ -0,0 +1,70 @@
+// Pushrod Rendering Library
+// Cross-Thread Event Sender
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::widget_cache::WidgetCache;
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc::Sender;
+
+/// This is the type of the messages posted with an `EventSender`.  Any value that can be sent to
+/// another thread can be posted; the handler downcasts it back to its original type.
+pub type EventMessage = Box<dyn Any + Send>;
+
+/// This is the callback type that is used when a message posted with an `EventSender` is received
+/// by the `Engine`.  It receives the `WidgetCache`, so that `Widget`s can be updated, and the
+/// message.
+pub type EventMessageCallbackType = Option<Box<dyn FnMut(&mut WidgetCache, EventMessage)>>;
+
+/// This is a handle used to post messages to the `Engine` from other threads, such as the result of
+/// a background download or computation.  It is returned by `Engine::get_event_sender`, can be
+/// cloned, and sent to other threads.  Messages are delivered, in the order they were posted, to the
+/// handler set with `Engine::on_event_message` at the start of the next frame.
+#[derive(Clone)]
+pub struct EventSender {
+    sender: Sender<EventMessage>,
+}
+
+/// This is the implementation of the `EventSender`.
+impl EventSender {
+    /// Creates a new `EventSender` that posts messages to `sender`.
+    pub(crate) fn new(sender: Sender<EventMessage>) -> Self {
+        Self { sender }
+    }
+
+    /// Posts `message` to the `Engine`.  Returns an error if the `Engine` no longer exists.
+    pub fn send<T: Any + Send>(&self, message: T) -> Result<(), EventSenderError> {
+        self.sender
+            .send(Box::new(message))
+            .map_err(|_| EventSenderError::Disconnected)
+    }
+}
+
+/// This is the error returned when a message cannot be posted with an `EventSender`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventSenderError {
+    /// The `Engine` the `EventSender` posts to no longer exists.
+    Disconnected,
+}
+
+impl fmt::Display for EventSenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventSenderError::Disconnected => write!(f, "The Engine no longer exists"),
+        }
+    }
+}
+
+impl Error for EventSenderError {}
@@ -0,0 +1,74 @@
+// Pushrod Rendering Library
+// Frame Statistics
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+static TEXTURES_CREATED: AtomicU64 = AtomicU64::new(0);
+static TEXTURES_DESTROYED: AtomicU64 = AtomicU64::new(0);
+
+/// This is the callback type that is used when an `on_frame_stats` callback is triggered from the
+/// `Engine` at the end of each frame.
+pub type FrameStatsCallbackType = Option<Box<dyn FnMut(&FrameStats)>>;
+
+/// This is the storage object for `FrameStats`.  A new set of statistics is gathered by the `Engine`
+/// for each frame, and can be retrieved with `Engine::get_frame_stats`, or streamed by registering
+/// an `Engine::on_frame_stats` callback.
+#[derive(Clone, Debug, Default, Copy)]
+pub struct FrameStats {
+    /// The sequence number of the frame, starting at 1.
+    pub frame_number: u64,
+
+    /// Total time spent on the frame, including event handling, `tick`, layout and drawing.  This
+    /// does not include time spent sleeping to maintain the frame rate.
+    pub frame_time: Duration,
+
+    /// Time spent drawing the display list.
+    pub draw_time: Duration,
+
+    /// Number of `Widget`s that were drawn.  This is `0` if nothing was invalidated.
+    pub widgets_drawn: u32,
+
+    /// Number of `Texture`s created during the frame, either as `Widget` backing textures, or as
+    /// images loaded into the `TextureCache`.
+    pub textures_created: u64,
+
+    /// Number of `Widget` backing textures, or `TextureCache` images, destroyed during the frame.
+    pub textures_destroyed: u64,
+
+    /// Number of events processed during the frame.
+    pub events_processed: u32,
+
+    /// Estimated number of bytes of GPU memory used by `Texture`s at the end of the frame.
+    pub texture_memory: u64,
+}
+
+/// Records the creation of a `Texture`.  Called by the `TextureStore` and `TextureCache`.
+pub(crate) fn record_texture_created() {
+    TEXTURES_CREATED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records the destruction of a `Texture`.  Called by the `TextureStore` and `TextureCache`.
+pub(crate) fn record_texture_destroyed() {
+    TEXTURES_DESTROYED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the total number of `Texture`s created and destroyed since the application started.
+pub fn texture_counts() -> (u64, u64) {
+    (
+        TEXTURES_CREATED.load(Ordering::Relaxed),
+        TEXTURES_DESTROYED.load(Ordering::Relaxed),
+    )
+}
@@ -0,0 +1,454 @@
+// Pushrod Rendering Library
+// GIF Decoder
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// This is the largest number of codes in a GIF LZW code table.
+const MAX_CODES: usize = 4096;
+
+/// This is the largest canvas, or frame, that is decoded, in pixels.  Larger sizes are rejected
+/// rather than allocated.
+const MAX_CANVAS_PIXELS: usize = 4096 * 4096;
+
+/// This is the largest total size of the decoded frames of an animation, in bytes.  Each frame
+/// covers the whole canvas, so long animations with large canvases are rejected.
+const MAX_ANIMATION_BYTES: usize = 512 * 1024 * 1024;
+
+/// This is the delay used for frames that specify a delay too short to be honored, matching the
+/// behavior of web browsers.
+const DEFAULT_FRAME_DELAY: u32 = 100;
+
+/// This is a single fully composited frame of a GIF animation.
+pub struct GifFrame {
+    /// The pixels of the whole animation canvas, in RGBA byte order, row by row.
+    pub pixels: Vec<u8>,
+
+    /// The number of milliseconds this frame is shown for.
+    pub delay: u32,
+}
+
+/// This is a decoded GIF animation.  Every frame covers the whole canvas of the animation, with
+/// the disposal of earlier frames already applied.
+pub struct GifAnimation {
+    pub width: u32,
+    pub height: u32,
+    pub frames: Vec<GifFrame>,
+}
+
+/// This is a cursor over the bytes of a GIF file.
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, String> {
+        let byte = *self
+            .data
+            .get(self.position)
+            .ok_or_else(|| String::from("Unexpected end of GIF data"))?;
+
+        self.position += 1;
+
+        Ok(byte)
+    }
+
+    fn word(&mut self) -> Result<u16, String> {
+        Ok(self.byte()? as u16 | (self.byte()? as u16) << 8)
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let end = self.position + length;
+        let bytes = self
+            .data
+            .get(self.position..end)
+            .ok_or_else(|| String::from("Unexpected end of GIF data"))?;
+
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    /// Reads a color table of `2 ^ (size_bits + 1)` RGB entries.
+    fn color_table(&mut self, size_bits: u8) -> Result<Vec<[u8; 3]>, String> {
+        Ok(self
+            .bytes(3 << (size_bits as usize + 1))?
+            .chunks(3)
+            .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+            .collect())
+    }
+
+    /// Reads a sequence of data sub-blocks, up to and including the empty terminating block.
+    fn sub_blocks(&mut self) -> Result<Vec<u8>, String> {
+        let mut data = Vec::new();
+
+        loop {
+            let length = self.byte()? as usize;
+
+            if length == 0 {
+                return Ok(data);
+            }
+
+            data.extend_from_slice(self.bytes(length)?);
+        }
+    }
+}
+
+/// Decodes a GIF87a or GIF89a file, returning every frame of the animation composited onto the
+/// full canvas.  Transparent pixels, and the disposal methods "leave in place", "restore to
+/// background" and "restore to previous" are honored.  The background is restored to transparent,
+/// as browsers do, rather than to the background color.  Canvases and frames of more than
+/// `MAX_CANVAS_PIXELS`, and animations of more than `MAX_ANIMATION_BYTES` once decoded, are
+/// rejected with an error.
+pub fn decode_gif(data: &[u8]) -> Result<GifAnimation, String> {
+    let mut reader = Reader { data, position: 0 };
+    let signature = reader.bytes(6)?;
+
+    if signature != b"GIF87a" && signature != b"GIF89a" {
+        return Err(String::from("Not a GIF file"));
+    }
+
+    let width = reader.word()? as usize;
+    let height = reader.word()? as usize;
+    let flags = reader.byte()?;
+
+    if width * height > MAX_CANVAS_PIXELS {
+        return Err(format!("GIF canvas of {}x{} is too large", width, height));
+    }
+
+    reader.bytes(2)?;
+
+    let global_colors = if flags & 0x80 != 0 {
+        reader.color_table(flags & 0x07)?
+    } else {
+        vec![]
+    };
+    let mut canvas = vec![0u8; width * height * 4];
+    let mut frames = Vec::new();
+    let mut delay = 0;
+    let mut disposal = 0;
+    let mut transparent: Option<u8> = None;
+
+    loop {
+        match reader.byte()? {
+            0x21 => {
+                let label = reader.byte()?;
+                let block = reader.sub_blocks()?;
+
+                if label == 0xF9 && block.len() >= 4 {
+                    disposal = (block[0] >> 2) & 0x07;
+                    delay = (block[1] as u32 | (block[2] as u32) << 8) * 10;
+                    transparent = if block[0] & 0x01 != 0 {
+                        Some(block[3])
+                    } else {
+                        None
+                    };
+                }
+            }
+            0x2C => {
+                let left = reader.word()? as usize;
+                let top = reader.word()? as usize;
+                let frame_width = reader.word()? as usize;
+                let frame_height = reader.word()? as usize;
+                let flags = reader.byte()?;
+
+                if frame_width * frame_height > MAX_CANVAS_PIXELS {
+                    return Err(format!(
+                        "GIF frame of {}x{} is too large",
+                        frame_width, frame_height
+                    ));
+                }
+
+                if (frames.len() + 1) * canvas.len() > MAX_ANIMATION_BYTES {
+                    return Err(format!(
+                        "GIF animation is larger than {} bytes when decoded",
+                        MAX_ANIMATION_BYTES
+                    ));
+                }
+
+                let local_colors = if flags & 0x80 != 0 {
+                    Some(reader.color_table(flags & 0x07)?)
+                } else {
+                    None
+                };
+                let colors = local_colors.as_ref().unwrap_or(&global_colors);
+                let min_code_size = reader.byte()?;
+                let indices = lzw_decode(
+                    &reader.sub_blocks()?,
+                    min_code_size,
+                    frame_width * frame_height,
+                )?;
+                let rows = frame_rows(frame_height, flags & 0x40 != 0);
+                let previous = if disposal == 3 {
+                    Some(canvas.clone())
+                } else {
+                    None
+                };
+
+                for (source_row, y) in rows.iter().enumerate() {
+                    for x in 0..frame_width {
+                        let (canvas_x, canvas_y) = (left + x, top + y);
+                        let index = match indices.get(source_row * frame_width + x) {
+                            Some(index) => *index,
+                            None => continue,
+                        };
+
+                        if canvas_x >= width || canvas_y >= height || Some(index) == transparent {
+                            continue;
+                        }
+
+                        if let Some(rgb) = colors.get(index as usize) {
+                            let offset = (canvas_y * width + canvas_x) * 4;
+
+                            canvas[offset..offset + 3].copy_from_slice(rgb);
+                            canvas[offset + 3] = 255;
+                        }
+                    }
+                }
+
+                frames.push(GifFrame {
+                    pixels: canvas.clone(),
+                    delay: if delay < 20 {
+                        DEFAULT_FRAME_DELAY
+                    } else {
+                        delay
+                    },
+                });
+
+                match (disposal, previous) {
+                    (2, _) => {
+                        for y in top..(top + frame_height).min(height) {
+                            for x in left..(left + frame_width).min(width) {
+                                let offset = (y * width + x) * 4;
+
+                                canvas[offset..offset + 4].copy_from_slice(&[0, 0, 0, 0]);
+                            }
+                        }
+                    }
+                    (3, Some(previous)) => canvas = previous,
+                    _ => (),
+                }
+
+                delay = 0;
+                disposal = 0;
+                transparent = None;
+            }
+            0x3B => break,
+            _ => return Err(String::from("Unknown block in GIF data")),
+        }
+    }
+
+    if frames.is_empty() {
+        return Err(String::from("GIF contains no frames"));
+    }
+
+    Ok(GifAnimation {
+        width: width as u32,
+        height: height as u32,
+        frames,
+    })
+}
+
+/// Returns the canvas row of each row of frame data, in the order the rows are stored.
+fn frame_rows(height: usize, interlaced: bool) -> Vec<usize> {
+    if !interlaced {
+        return (0..height).collect();
+    }
+
+    [(0, 8), (4, 8), (2, 4), (1, 2)]
+        .iter()
+        .flat_map(|(start, step)| (*start..height).step_by(*step))
+        .collect()
+}
+
+/// Decompresses the LZW coded image data of a frame, returning at most `pixel_count` color indexes.
+fn lzw_decode(data: &[u8], min_code_size: u8, pixel_count: usize) -> Result<Vec<u8>, String> {
+    if !(1..=11).contains(&min_code_size) {
+        return Err(String::from("Invalid LZW code size in GIF data"));
+    }
+
+    let clear_code = 1usize << min_code_size;
+    let end_code = clear_code + 1;
+    let mut prefixes = [0u16; MAX_CODES];
+    let mut suffixes = [0u8; MAX_CODES];
+    let mut output = Vec::with_capacity(pixel_count);
+    let mut code_size = min_code_size as u32 + 1;
+    let mut next_code = end_code + 1;
+    let mut previous: Option<usize> = None;
+    let mut bits = 0u32;
+    let mut bit_count = 0u32;
+    let mut position = 0;
+
+    for (code, suffix) in suffixes.iter_mut().enumerate().take(clear_code) {
+        *suffix = code as u8;
+    }
+
+    // Writes the string for `code` to the output, returning its first byte.
+    let emit = |code: usize, prefixes: &[u16], suffixes: &[u8], output: &mut Vec<u8>| -> u8 {
+        let start = output.len();
+        let mut code = code;
+
+        loop {
+            output.push(suffixes[code]);
+
+            if code < clear_code {
+                break;
+            }
+
+            code = prefixes[code] as usize;
+        }
+
+        output[start..].reverse();
+        output[start]
+    };
+
+    while output.len() < pixel_count {
+        while bit_count < code_size {
+            match data.get(position) {
+                Some(byte) => {
+                    bits |= (*byte as u32) << bit_count;
+                    bit_count += 8;
+                    position += 1;
+                }
+                None => return Ok(output),
+            }
+        }
+
+        let code = (bits & ((1 << code_size) - 1)) as usize;
+
+        bits >>= code_size;
+        bit_count -= code_size;
+
+        if code == clear_code {
+            code_size = min_code_size as u32 + 1;
+            next_code = end_code + 1;
+            previous = None;
+            continue;
+        }
+
+        if code == end_code {
+            break;
+        }
+
+        let new_entry = match previous {
+            None if code < clear_code => {
+                emit(code, &prefixes, &suffixes, &mut output);
+                None
+            }
+            Some(previous) if code < next_code => {
+                let first = emit(code, &prefixes, &suffixes, &mut output);
+
+                Some((previous, first))
+            }
+            Some(previous) if code == next_code => {
+                let first = emit(previous, &prefixes, &suffixes, &mut output);
+
+                output.push(first);
+                Some((previous, first))
+            }
+            _ => return Err(String::from("Invalid LZW code in GIF data")),
+        };
+
+        if let Some((prefix, suffix)) = new_entry {
+            if next_code < MAX_CODES {
+                prefixes[next_code] = prefix as u16;
+                suffixes[next_code] = suffix;
+                next_code += 1;
+
+                if next_code == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+        }
+
+        previous = Some(code);
+    }
+
+    output.truncate(pixel_count);
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a GIF of `width` by `height` pixels with a two color global color table of white and
+    /// black, containing the `blocks` that follow the header.
+    fn gif(width: u16, height: u16, blocks: &[u8]) -> Vec<u8> {
+        let mut data = b"GIF89a".to_vec();
+
+        data.extend_from_slice(&width.to_le_bytes());
+        data.extend_from_slice(&height.to_le_bytes());
+        data.extend_from_slice(&[0x80, 0, 0, 255, 255, 255, 0, 0, 0]);
+        data.extend_from_slice(blocks);
+        data.push(0x3B);
+        data
+    }
+
+    /// This is a 1x1 image block whose only pixel has the color index `0`.
+    const SINGLE_PIXEL: [u8; 15] = [
+        0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0x02, 0x02, 0x44, 0x01, 0x00,
+    ];
+
+    #[test]
+    fn test_lzw_decode() {
+        assert_eq!(lzw_decode(&[0x44, 0x01], 2, 1).unwrap(), vec![0]);
+
+        // Clear, 1, the code being defined, 1, end: the second code repeats the first.
+        assert_eq!(lzw_decode(&[0x8C, 0x53], 2, 4).unwrap(), vec![1, 1, 1, 1]);
+        assert!(lzw_decode(&[0x44, 0x01], 0, 1).is_err());
+        assert!(lzw_decode(&[0xFC, 0x01], 2, 1).is_err());
+    }
+
+    #[test]
+    fn test_decode_gif() {
+        let animation = decode_gif(&gif(1, 1, &SINGLE_PIXEL)).unwrap();
+
+        assert_eq!((animation.width, animation.height), (1, 1));
+        assert_eq!(animation.frames.len(), 1);
+        assert_eq!(animation.frames[0].pixels, vec![255, 255, 255, 255]);
+        assert_eq!(animation.frames[0].delay, DEFAULT_FRAME_DELAY);
+    }
+
+    #[test]
+    fn test_decode_gif_transparency() {
+        let mut blocks = vec![0x21, 0xF9, 0x04, 0x01, 0x05, 0x00, 0x00, 0x00];
+
+        blocks.extend_from_slice(&SINGLE_PIXEL);
+
+        let animation = decode_gif(&gif(1, 1, &blocks)).unwrap();
+
+        assert_eq!(animation.frames[0].pixels, vec![0, 0, 0, 0]);
+        assert_eq!(animation.frames[0].delay, 50);
+    }
+
+    #[test]
+    fn test_decode_gif_errors() {
+        assert!(decode_gif(b"PNG89a").is_err());
+        assert!(decode_gif(&gif(1, 1, &[])).is_err());
+        assert!(decode_gif(&gif(1, 1, &SINGLE_PIXEL[..12])).is_err());
+        assert!(decode_gif(&gif(1, 1, &[0x99])).is_err());
+    }
+
+    #[test]
+    fn test_decode_gif_too_large() {
+        assert!(decode_gif(&gif(65535, 65535, &SINGLE_PIXEL)).is_err());
+
+        let mut frame = SINGLE_PIXEL;
+
+        frame[5..9].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+
+        assert!(decode_gif(&gif(1, 1, &frame)).is_err());
+    }
+}
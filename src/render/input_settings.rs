@@ -0,0 +1,210 @@
+// Pushrod Rendering Library
+// Input Settings
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::widget_config::{
+    WidgetConfig, CONFIG_CARET_BLINK_RATE, CONFIG_KEY_REPEAT_DELAY, CONFIG_KEY_REPEAT_INTERVAL,
+};
+
+use sdl2::keyboard::Keycode;
+
+/// This is the storage object for `InputSettings`.  These settings are applied by the `Engine` to
+/// each `Widget`'s `WidgetConfig`, so that `Widget`s that implement their own auto-repeat (such as
+/// spinners and sliders) or draw a text caret behave consistently throughout the application.
+#[derive(Clone, Debug, Copy)]
+pub struct InputSettings {
+    /// Delay in milliseconds before a held key or button starts repeating.
+    pub key_repeat_delay: u32,
+
+    /// Interval in milliseconds between repeats once repeating has started.
+    pub key_repeat_interval: u32,
+
+    /// Interval in milliseconds between caret blinks.  A value of `0` turns blinking off, leaving the
+    /// caret permanently visible.
+    pub caret_blink_rate: u32,
+}
+
+/// This is the implementation of the `InputSettings`.  SDL2 does not expose the operating system's
+/// keyboard repeat or caret blink settings, so `InputSettings::default()` uses the values most
+/// desktop environments ship with.
+impl InputSettings {
+    /// Creates a new `InputSettings` object, given the key repeat delay, key repeat interval, and
+    /// caret blink rate, all in milliseconds.
+    pub fn new(key_repeat_delay: u32, key_repeat_interval: u32, caret_blink_rate: u32) -> Self {
+        Self {
+            key_repeat_delay,
+            key_repeat_interval,
+            caret_blink_rate,
+        }
+    }
+
+    /// Returns a copy of these `InputSettings` with caret blinking turned off.
+    pub fn without_caret_blink(self) -> Self {
+        Self {
+            caret_blink_rate: 0,
+            ..self
+        }
+    }
+
+    /// Applies these settings to a `WidgetConfig`.
+    pub fn apply(&self, config: &mut WidgetConfig) {
+        config.set_numeric(CONFIG_KEY_REPEAT_DELAY, self.key_repeat_delay as i32);
+        config.set_numeric(CONFIG_KEY_REPEAT_INTERVAL, self.key_repeat_interval as i32);
+        config.set_numeric(CONFIG_CARET_BLINK_RATE, self.caret_blink_rate as i32);
+    }
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        Self::new(500, 33, 530)
+    }
+}
+
+/// Determines whether or not a text caret should be drawn, given the blink rate stored in
+/// `CONFIG_CARET_BLINK_RATE`, and the number of milliseconds since the caret last moved.  The caret
+/// is always visible when blinking is turned off.
+pub fn caret_visible(caret_blink_rate: i32, elapsed: u64) -> bool {
+    if caret_blink_rate <= 0 {
+        return true;
+    }
+
+    elapsed % (caret_blink_rate as u64 * 2) < caret_blink_rate as u64
+}
+
+/// This is a helper used by `Widget`s that implement their own auto-repeat.  Call `press` when the
+/// key or button goes down, `release` when it goes up, and `should_repeat` from the `tick` callback
+/// to find out whether another repeat is due.
+#[derive(Default)]
+pub struct KeyRepeat {
+    pressed_at: u64,
+    repeats: u64,
+    active: bool,
+}
+
+/// This is the implementation of the `KeyRepeat` helper.
+impl KeyRepeat {
+    /// Creates a new, inactive `KeyRepeat` object.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking a held key or button, given the current time in milliseconds.
+    pub fn press(&mut self, now: u64) {
+        self.pressed_at = now;
+        self.repeats = 0;
+        self.active = true;
+    }
+
+    /// Stops tracking the held key or button.
+    pub fn release(&mut self) {
+        self.active = false;
+    }
+
+    /// Indicates whether or not a key or button is currently being held.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Returns `true` if a repeat is due at time `now`, given the `delay` and `interval` in
+    /// milliseconds (usually read from `CONFIG_KEY_REPEAT_DELAY` and `CONFIG_KEY_REPEAT_INTERVAL`).
+    /// Each call that returns `true` consumes one repeat.
+    pub fn should_repeat(&mut self, now: u64, delay: i32, interval: i32) -> bool {
+        if !self.active || now < self.pressed_at + delay.max(0) as u64 {
+            return false;
+        }
+
+        let elapsed = now - self.pressed_at - delay.max(0) as u64;
+        let due = elapsed / interval.max(1) as u64 + 1;
+
+        if due > self.repeats {
+            self.repeats += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// This is a helper used by `Widget`s that draw a blinking text caret.  Call `restart` when the
+/// caret moves, so that it is shown straight away, `focus_changed` when the `Widget` gains or loses
+/// focus, and `tick` from the `tick` callback while the caret is active, to find out whether the
+/// `Widget` needs to be redrawn.
+#[derive(Default)]
+pub struct CaretBlink {
+    moved_at: u64,
+    shown: bool,
+}
+
+/// This is the implementation of the `CaretBlink` helper.
+impl CaretBlink {
+    /// Creates a new, hidden `CaretBlink` object.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the caret, and restarts its blinking from the current time `now`, in milliseconds.
+    pub fn restart(&mut self, now: u64) {
+        self.moved_at = now;
+        self.shown = true;
+    }
+
+    /// Restarts the caret when the `Widget` gains focus, and hides it when the `Widget` loses focus.
+    pub fn focus_changed(&mut self, focused: bool, now: u64) {
+        self.moved_at = now;
+        self.shown = focused;
+    }
+
+    /// Indicates whether or not the caret should currently be drawn.
+    pub fn is_shown(&self) -> bool {
+        self.shown
+    }
+
+    /// Updates whether or not the caret is shown at time `now`, given the `caret_blink_rate` (usually
+    /// read from `CONFIG_CARET_BLINK_RATE`).  Returns `true` if it changed, so that the `Widget` can
+    /// be redrawn.
+    pub fn tick(&mut self, now: u64, caret_blink_rate: i32) -> bool {
+        let shown = caret_visible(caret_blink_rate, now.saturating_sub(self.moved_at));
+        let changed = shown != self.shown;
+
+        self.shown = shown;
+        changed
+    }
+}
+
+/// Applies an editing key to a single line of `text` with the caret at `cursor`.  The arrow keys,
+/// `Home` and `End` move the cursor, and `Backspace` and `Delete` remove the character before or
+/// after it.  Returns whether the text changed, and whether the text or the cursor changed.
+pub fn handle_edit_key(keycode: Keycode, text: &mut Vec<char>, cursor: &mut usize) -> (bool, bool) {
+    let original_cursor = *cursor;
+    let mut text_changed = false;
+
+    match keycode {
+        Keycode::Left => *cursor = cursor.saturating_sub(1),
+        Keycode::Right => *cursor = (*cursor + 1).min(text.len()),
+        Keycode::Home => *cursor = 0,
+        Keycode::End => *cursor = text.len(),
+        Keycode::Backspace if *cursor > 0 => {
+            *cursor -= 1;
+            text.remove(*cursor);
+            text_changed = true;
+        }
+        Keycode::Delete if *cursor < text.len() => {
+            text.remove(*cursor);
+            text_changed = true;
+        }
+        _ => (),
+    }
+
+    (text_changed, text_changed || *cursor != original_cursor)
+}
@@ -0,0 +1,498 @@
+// Pushrod Rendering Library
+// Runtime Widget Inspector
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::widget::Widget;
+use crate::render::widget_cache::{WidgetCache, WidgetContainer};
+use crate::render::widget_config::{config_key_name, Config};
+use sdl2::event::Event;
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, Canvas, Texture};
+use sdl2::video::Window;
+use std::path::Path;
+
+/// Width of the `Inspector` side panel in pixels.
+const PANEL_WIDTH: u32 = 320;
+
+/// Height of a single line of text in the `Inspector` side panel.
+const LINE_HEIGHT: i32 = 16;
+
+/// This is a line of text in the `Inspector` side panel, along with its rendered `Texture`, which
+/// is kept until the text of the line changes.
+struct LineTexture {
+    text: String,
+    highlighted: bool,
+    texture: Option<Texture>,
+}
+
+impl LineTexture {
+    fn destroy(self) {
+        if let Some(texture) = self.texture {
+            // Safety: line textures are only referenced by the `Inspector` that rendered them.
+            unsafe {
+                texture.destroy();
+            }
+        }
+    }
+}
+
+/// This is the storage object for the `Inspector`.  It stores the active state, the hotkey used to
+/// toggle it, the hovered and selected `Widget` IDs, and the currently selected configuration row.
+pub struct Inspector {
+    active: bool,
+    hotkey: Keycode,
+    hovered_widget_id: i32,
+    selected_widget_id: i32,
+    selected_row: usize,
+    panel_bounds: Rect,
+    line_textures: Vec<LineTexture>,
+}
+
+/// This is the implementation of the `Inspector`, a runtime debugging overlay similar to a browser's
+/// developer tools.  When active, mouse events are captured by the `Inspector` instead of being sent
+/// to `Widget`s: hovering highlights a `Widget`, and clicking selects it.  The side panel shows the
+/// selected `Widget`'s ID, name, parent, z-order, configuration values, and the callbacks registered
+/// in its `CallbackRegistry`.
+///
+/// Configuration values can be edited live: use `Up` and `Down` to choose a value, `Left` and `Right`
+/// to decrease or increase it (hold `Shift` to adjust the Y or height component of points and sizes),
+/// and `Space` to flip a toggle.
+impl Inspector {
+    /// Creates a new, inactive `Inspector`, toggled with the `F12` key.
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            hotkey: Keycode::F12,
+            hovered_widget_id: 0,
+            selected_widget_id: 0,
+            selected_row: 0,
+            panel_bounds: Rect::new(0, 0, 1, 1),
+            line_textures: Vec::new(),
+        }
+    }
+
+    /// Changes the key used to toggle the `Inspector` on and off.
+    pub fn set_hotkey(&mut self, hotkey: Keycode) {
+        self.hotkey = hotkey;
+    }
+
+    /// Indicates whether or not the `Inspector` overlay is currently shown.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Shows or hides the `Inspector` overlay.  The text of the side panel is released when it is
+    /// hidden.
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+
+        if !active {
+            self.release_line_textures();
+        }
+    }
+
+    /// Retrieves the ID of the `Widget` currently selected in the `Inspector`.
+    pub fn get_selected_widget_id(&self) -> i32 {
+        self.selected_widget_id
+    }
+
+    /// Handles an `Event` before it is dispatched to the `Widget`s.  Returns `true` if the `Inspector`
+    /// consumed the `Event`, in which case it must not be dispatched any further.
+    pub fn handle_event(&mut self, event: &Event, cache: &mut WidgetCache) -> bool {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            keymod,
+            ..
+        } = event
+        {
+            if *keycode == self.hotkey {
+                self.set_active(!self.active);
+                cache
+                    .get_container_by_id(0)
+                    .widget
+                    .borrow_mut()
+                    .set_invalidated(true);
+
+                return true;
+            }
+
+            if !self.active {
+                return false;
+            }
+
+            let shifted = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+
+            match keycode {
+                Keycode::Up if self.selected_row > 0 => self.selected_row -= 1,
+                Keycode::Down => self.selected_row += 1,
+                Keycode::Left => self.edit_selected(cache, -1, shifted),
+                Keycode::Right => self.edit_selected(cache, 1, shifted),
+                Keycode::Space => self.edit_selected(cache, 0, shifted),
+                _ => (),
+            }
+
+            return true;
+        }
+
+        if !self.active {
+            return false;
+        }
+
+        match event {
+            Event::MouseMotion { x, y, .. } => {
+                if !self.panel_bounds.contains_point((*x, *y)) {
+                    self.hovered_widget_id = cache.find_widget(*x, *y);
+                }
+
+                true
+            }
+
+            Event::MouseButtonDown { x, y, .. } => {
+                if !self.panel_bounds.contains_point((*x, *y)) {
+                    self.selected_widget_id = self.hovered_widget_id;
+                    self.selected_row = 0;
+                }
+
+                true
+            }
+
+            Event::MouseButtonUp { .. }
+            | Event::MouseWheel { .. }
+            | Event::KeyUp { .. }
+            | Event::TextInput { .. }
+            | Event::TextEditing { .. } => true,
+
+            _ => false,
+        }
+    }
+
+    /// Draws the `Inspector` overlay on top of the already drawn `Widget`s: the hovered and selected
+    /// `Widget` highlights, and the side panel describing the selected `Widget`.
+    pub fn draw(
+        &mut self,
+        c: &mut Canvas<Window>,
+        widgets: &[WidgetContainer],
+        t: &mut TextureCache,
+    ) {
+        if !self.active {
+            return;
+        }
+
+        let (window_w, window_h) = c.output_size().unwrap();
+        let panel_width = PANEL_WIDTH.min(window_w);
+
+        self.panel_bounds = Rect::new(
+            window_w as i32 - panel_width as i32,
+            0,
+            panel_width,
+            window_h,
+        );
+
+        c.set_blend_mode(BlendMode::Blend);
+
+        if let Some(container) = widgets.get(self.hovered_widget_id as usize) {
+            let bounds = container.widget.borrow_mut().get_drawing_area();
+
+            c.set_draw_color(Color::RGBA(255, 0, 255, 64));
+            c.fill_rect(bounds).unwrap();
+        }
+
+        if let Some(container) = widgets.get(self.selected_widget_id as usize) {
+            let bounds = container.widget.borrow_mut().get_drawing_area();
+
+            c.set_draw_color(Color::RGBA(0, 255, 0, 255));
+            c.draw_rect(bounds).unwrap();
+        }
+
+        c.set_draw_color(Color::RGBA(0, 0, 0, 216));
+        c.fill_rect(self.panel_bounds).unwrap();
+        c.set_blend_mode(BlendMode::None);
+
+        let lines = self.describe(widgets);
+
+        self.update_line_textures(c, t, &lines);
+
+        for (i, line) in self.line_textures.iter().enumerate() {
+            if let Some(texture) = &line.texture {
+                let query = texture.query();
+
+                c.copy(
+                    texture,
+                    None,
+                    Rect::new(
+                        self.panel_bounds.x() + 8,
+                        4 + i as i32 * LINE_HEIGHT,
+                        query.width,
+                        query.height,
+                    ),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    /// Renders the lines of text of the side panel that have changed since they were last drawn,
+    /// destroying the `Texture`s of the lines they replace.  The font is only loaded if a line has
+    /// changed.
+    fn update_line_textures(
+        &mut self,
+        c: &mut Canvas<Window>,
+        t: &mut TextureCache,
+        lines: &[(String, bool)],
+    ) {
+        let unchanged = |line: &LineTexture, (text, highlighted): &(String, bool)| {
+            line.text == *text && line.highlighted == *highlighted
+        };
+
+        if lines.len() == self.line_textures.len()
+            && self
+                .line_textures
+                .iter()
+                .zip(lines)
+                .all(|(line, text)| unchanged(line, text))
+        {
+            return;
+        }
+
+        let texture_creator = c.texture_creator();
+        let mut font = t
+            .get_ttf_context()
+            .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 12)
+            .unwrap();
+
+        font.set_style(sdl2::ttf::FontStyle::NORMAL);
+
+        while self.line_textures.len() > lines.len() {
+            self.line_textures.pop().unwrap().destroy();
+        }
+
+        for (i, (text, highlighted)) in lines.iter().enumerate() {
+            if i < self.line_textures.len() && unchanged(&self.line_textures[i], &lines[i]) {
+                continue;
+            }
+
+            let texture = if text.is_empty() {
+                None
+            } else {
+                let color = if *highlighted {
+                    Color::RGB(255, 255, 0)
+                } else {
+                    Color::RGB(255, 255, 255)
+                };
+                let surface = font
+                    .render(text)
+                    .blended(color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+
+                Some(
+                    texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap(),
+                )
+            };
+            let line = LineTexture {
+                text: text.clone(),
+                highlighted: *highlighted,
+                texture,
+            };
+
+            if i < self.line_textures.len() {
+                std::mem::replace(&mut self.line_textures[i], line).destroy();
+            } else {
+                self.line_textures.push(line);
+            }
+        }
+    }
+
+    /// Destroys the `Texture`s of the lines of text of the side panel.
+    fn release_line_textures(&mut self) {
+        for line in self.line_textures.drain(..) {
+            line.destroy();
+        }
+    }
+
+    /// Builds the lines of text displayed in the side panel.  Each line is paired with a flag
+    /// indicating whether or not it is the currently selected configuration row.
+    fn describe(&mut self, widgets: &[WidgetContainer]) -> Vec<(String, bool)> {
+        let mut lines = vec![(String::from("Widget Inspector"), false)];
+        let container = match widgets.get(self.selected_widget_id as usize) {
+            Some(container) => container,
+            None => return lines,
+        };
+        let z_order = draw_order(widgets)
+            .iter()
+            .position(|x| *x == self.selected_widget_id)
+            .unwrap_or(0);
+        let mut widget = container.widget.borrow_mut();
+        let keys = config_keys(&mut **widget);
+
+        if self.selected_row >= keys.len() && !keys.is_empty() {
+            self.selected_row = keys.len() - 1;
+        }
+
+        lines.push((String::new(), false));
+        lines.push((format!("ID: {}", container.get_widget_id()), false));
+        lines.push((format!("Name: {}", container.get_widget_name()), false));
+        lines.push((format!("Parent: {}", container.get_parent_id()), false));
+        lines.push((format!("Z-Order: {}", z_order), false));
+        lines.push((
+            format!(
+                "Hidden: {}  Enabled: {}",
+                widget.get_config().is_hidden(),
+                widget.get_config().is_enabled()
+            ),
+            false,
+        ));
+        lines.push((String::new(), false));
+        lines.push((String::from("Config:"), false));
+
+        for (row, key) in keys.iter().enumerate() {
+            let value = match widget.get_config().config.get(key) {
+                Some(value) => format_config(value),
+                None => String::new(),
+            };
+
+            lines.push((
+                format!("  {}: {}", config_key_name(*key), value),
+                row == self.selected_row,
+            ));
+        }
+
+        lines.push((String::new(), false));
+        lines.push((String::from("Callbacks:"), false));
+
+        let callbacks = widget.get_callbacks();
+        let registered = [
+            ("on_tick", callbacks.has_on_tick()),
+            ("on_mouse_entered", callbacks.has_on_mouse_entered()),
+            ("on_mouse_exited", callbacks.has_on_mouse_exited()),
+            ("on_mouse_moved", callbacks.has_on_mouse_moved()),
+            ("on_mouse_scrolled", callbacks.has_on_mouse_scrolled()),
+            ("on_mouse_clicked", callbacks.has_on_mouse_clicked()),
+            ("on_key_pressed", callbacks.has_on_key_pressed()),
+            ("on_key_released", callbacks.has_on_key_released()),
+            ("on_focus_gained", callbacks.has_on_focus_gained()),
+            ("on_focus_lost", callbacks.has_on_focus_lost()),
+            ("on_file_dropped", callbacks.has_on_file_dropped()),
+            ("on_text_dropped", callbacks.has_on_text_dropped()),
+        ];
+
+        for (name, _) in registered.iter().filter(|(_, is_set)| *is_set) {
+            lines.push((format!("  {}", name), false));
+        }
+
+        if !registered.iter().any(|(_, is_set)| *is_set) {
+            lines.push((String::from("  (none)"), false));
+        }
+
+        lines
+    }
+
+    /// Adjusts the selected configuration value of the selected `Widget` by `delta`.
+    fn edit_selected(&mut self, cache: &mut WidgetCache, delta: i32, shifted: bool) {
+        let container = cache.get_container_by_id(self.selected_widget_id);
+        let mut widget = container.widget.borrow_mut();
+        let keys = config_keys(&mut **widget);
+
+        if self.selected_row >= keys.len() {
+            return;
+        }
+
+        let key = keys[self.selected_row];
+
+        match widget.get_config().config.get(&key).cloned() {
+            Some(Config::Numeric(value)) => widget.set_numeric(key, value + delta),
+            Some(Config::Toggle(value)) => widget.set_toggle(key, !value),
+            Some(Config::Points(points)) => {
+                if shifted {
+                    widget.set_point(key, points[0], points[1] + delta);
+                } else {
+                    widget.set_point(key, points[0] + delta, points[1]);
+                }
+            }
+            Some(Config::Size(size)) => {
+                let adjust = |value: u32| (value as i32 + delta).max(0) as u32;
+
+                if shifted {
+                    widget.get_config().set_size(key, size[0], adjust(size[1]));
+                } else {
+                    widget.get_config().set_size(key, adjust(size[0]), size[1]);
+                }
+            }
+            Some(Config::Color(color)) => {
+                let adjust = |value: u8| (value as i32 + delta * 16).clamp(0, 255) as u8;
+
+                widget.set_color(
+                    key,
+                    Color::RGBA(adjust(color.r), adjust(color.g), adjust(color.b), color.a),
+                );
+            }
+            _ => (),
+        }
+
+        widget.set_invalidated(true);
+    }
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the configuration keys that are set for a `Widget`, in sorted order.
+fn config_keys(widget: &mut dyn Widget) -> Vec<u8> {
+    let mut keys: Vec<u8> = widget.get_config().config.keys().cloned().collect();
+
+    keys.sort();
+    keys
+}
+
+/// Formats a `Config` value for display.
+fn format_config(value: &Config) -> String {
+    match value {
+        Config::Points(points) => format!("{:?}", points),
+        Config::Size(size) => format!("{:?}", size),
+        Config::Color(color) => format!("RGBA({}, {}, {}, {})", color.r, color.g, color.b, color.a),
+        Config::Numeric(numeric) => format!("{}", numeric),
+        Config::Text(text) => format!("{:?}", text),
+        Config::Toggle(toggle) => format!("{}", toggle),
+        Config::CompassPosition(position) => format!("{:?}", position),
+        Config::PaddingConstraint(padding) => format!("{:?}", padding),
+    }
+}
+
+/// Returns the list of `Widget` IDs in the order in which they are drawn by the `WidgetCache`.
+fn draw_order(widgets: &[WidgetContainer]) -> Vec<i32> {
+    let mut order = Vec::new();
+
+    collect_draw_order(widgets, 0, &mut order);
+
+    order
+}
+
+fn collect_draw_order(widgets: &[WidgetContainer], parent_id: i32, order: &mut Vec<i32>) {
+    for x in widgets.iter().filter(|x| x.get_parent_id() == parent_id) {
+        order.push(x.get_widget_id());
+
+        if x.get_widget_id() != parent_id {
+            collect_draw_order(widgets, x.get_widget_id(), order);
+        }
+    }
+}
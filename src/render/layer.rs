@@ -0,0 +1,66 @@
+// Pushrod Rendering Library
+// Rendering Layers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// This is the number of rendering `Layer`s.
+pub const LAYER_COUNT: usize = 4;
+
+/// This enum identifies the rendering layer a `Widget` is drawn on.  Each layer is drawn to its own
+/// texture, and only redrawn when a `Widget` on that layer is invalidated.  The layer textures are
+/// composited in order, from `Background` to `Overlay`, so a `Widget` on a higher layer always
+/// appears above the `Widget`s on lower layers, regardless of the order in which they were added.
+/// Set a `Widget`'s layer with `CONFIG_LAYER`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+    /// The background, such as the top-level `Widget` that fills the window.
+    Background = 0,
+
+    /// Regular application content.  This is the default for all `Widget`s.
+    Content = 1,
+
+    /// Floating content drawn above the application, such as popups, tooltips, and drag ghosts.
+    Floating = 2,
+
+    /// Overlays drawn above everything else, such as toasts and debugging displays.
+    Overlay = 3,
+}
+
+/// This is the implementation of the `Layer`.
+impl Layer {
+    /// Returns all `Layer`s, in the order in which they are composited.
+    pub fn all() -> [Layer; LAYER_COUNT] {
+        [
+            Layer::Background,
+            Layer::Content,
+            Layer::Floating,
+            Layer::Overlay,
+        ]
+    }
+
+    /// Converts a `CONFIG_LAYER` value to a `Layer`.  Values out of range are clamped to the nearest
+    /// `Layer`.
+    pub fn from_numeric(value: i32) -> Layer {
+        match value {
+            i32::MIN..=0 => Layer::Background,
+            1 => Layer::Content,
+            2 => Layer::Floating,
+            _ => Layer::Overlay,
+        }
+    }
+
+    /// Returns the index of this `Layer`, which is also its `CONFIG_LAYER` value.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
@@ -14,11 +14,15 @@
 // limitations under the License.
 
 use crate::render::widget_cache::WidgetContainer;
-use crate::render::widget_config::PaddingConstraint;
+use crate::render::widget_config::{PaddingConstraint, CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
 
 /// This is a structure that describes the position of a `Widget` within its `Layout`.  `X` and
 /// `Y` coordinates are not given as physical positions on the screen, rather, their position in the
 /// `Layout` matrix.
+#[derive(Clone, Debug, Copy, PartialEq)]
 pub struct LayoutPosition {
     pub x: i32,
     pub y: i32,
@@ -57,4 +61,262 @@ pub trait Layout {
     /// Indicates whether or not the `Layout` needs to have `do_layout` re-run.  This is generally
     /// needed when the `LayoutPosition` changes, or when `PaddingConstraint`s change.
     fn needs_layout(&self) -> bool;
+
+    /// Changes the bounds of the `Layout`, so that its `Widget`s are placed within them on the next
+    /// `do_layout`.  This function implementation is **optional**.
+    fn set_bounds(&mut self, _x: i32, _y: i32, _w: u32, _h: u32) {}
+
+    /// Marks the `Layout` as needing `do_layout` to be re-run, such as after the window has been
+    /// resized.  This function implementation is **optional**.
+    fn invalidate(&mut self) {}
+
+    /// Retrieves the IDs of the `Widget`s managed by this `Layout`, in the order they were added.
+    /// This is used when exporting layouts.  This function implementation is **optional**.
+    fn get_widget_ids(&self) -> Vec<i32> {
+        Vec::new()
+    }
+
+    /// Removes the `Widget` by ID from the `Layout` manager, so that it is no longer placed.  This is
+    /// called when the `Widget` is removed from the `Engine`, before its ID is reused.  This
+    /// function implementation is **optional**.
+    fn remove_widget(&mut self, _widget_id: i32) {}
+
+    /// Called when the window has been resized to `_size`, so that a `Layout` that tracks the
+    /// window can resize itself and request a new layout.  This function implementation is
+    /// **optional**.
+    fn window_resized(&mut self, _size: Size) {}
+
+    /// Retrieves this `Layout` as an `Any` object so that it can be downcast using `downcast_mut`
+    /// to the `struct` that implements the `Layout` trait, in order to call its own functions
+    /// after it has been added to the `Engine`.  This function implementation is **optional**.
+    fn as_any(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
+
+    /// Retrieves this `Layout` as a `ParallelLayout`, so that the cells of its `Widget`s can be
+    /// computed on a worker thread.  This function implementation is **optional**.
+    fn as_parallel_layout(&mut self) -> Option<&mut dyn ParallelLayout> {
+        None
+    }
+
+    /// Adds an empty space to the `Layout` in the next available position, pushing the `Widget`s
+    /// on either side of it apart.  A `flexible` spacer takes a share of the space left over, like
+    /// a `Widget` with a weight of `1`; otherwise it has no length, and only adds another gap of
+    /// `spacing`.  This function implementation is **optional**.
+    fn add_spacer(&mut self, _flexible: bool) {}
+
+    /// Adds an empty space of a fixed number of `_pixels` to the `Layout` in the next available
+    /// position.  This function implementation is **optional**.
+    fn add_strut(&mut self, _pixels: u32) {}
+
+    /// Describes the type, bounds and items of this `Layout`, so that it can be exported to a
+    /// layout file and created again when the file is imported.  `Layout`s that return `None` are
+    /// exported with only their padding and `Widget`s.  This function implementation is
+    /// **optional**.
+    fn describe(&self) -> Option<LayoutDescription> {
+        None
+    }
+}
+
+/// This trait is implemented by `Layout`s that compute the cells of their `Widget`s from their own
+/// state alone, without reading or changing the `Widget`s, so that independent `Layout`s can be
+/// computed on worker threads.  When parallel ticking is enabled with `Engine::set_tick_threads`,
+/// `compute_cells` is called on worker threads, and the `Widget`s are placed in their cells on the
+/// main thread afterwards, in the order in which the `Layout`s were added.
+pub trait ParallelLayout: Send {
+    /// Computes the cells of the `Widget`s in this `Layout`, and marks the `Layout` as no longer
+    /// needing layout.
+    fn compute_cells(&mut self) -> Vec<LayoutCell>;
+}
+
+/// This is the cell that a `Layout` gives one of its `Widget`s.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct LayoutCell {
+    /// The ID of the `Widget`.
+    pub widget_id: i32,
+
+    /// The `x, y, w, h` bounds of the cell.
+    pub bounds: (i32, i32, i32, i32),
+
+    /// How the `Widget` is aligned within the cell, horizontally and vertically.
+    pub alignment: (LayoutAlignment, LayoutAlignment),
+}
+
+/// This is a description of a `Layout`, used by layout files to export and create `Layout`s.
+#[derive(Clone, Debug)]
+pub struct LayoutDescription {
+    /// The type of `Layout`: `horizontal`, `vertical`, `grid` or `flow`.
+    pub layout_type: String,
+
+    /// The origin of the bounds of the `Layout`.
+    pub origin: Points,
+
+    /// The size of the bounds of the `Layout`.
+    pub size: Size,
+
+    /// The number of columns and rows of a `grid` `Layout`, or empty for other types.
+    pub layout: Vec<i32>,
+
+    /// The `Widget`s, spacers and struts in the `Layout`, in the order they were added.
+    pub items: Vec<LayoutItem>,
+
+    /// Whether or not the `Layout` extends to the right and bottom edges of the window when it is
+    /// resized.
+    pub track_window: bool,
+}
+
+/// This is a `Widget`, spacer or strut in a `LayoutDescription`.
+#[derive(Clone, Debug)]
+pub struct LayoutItem {
+    /// The ID of the `Widget`, or `-1` for a spacer or strut.
+    pub widget_id: i32,
+
+    /// The position of the item in the `Layout`.
+    pub position: LayoutPosition,
+
+    /// The length given to the item, for `Layout`s that size their items.
+    pub size: LayoutSize,
+
+    /// The number of columns and rows covered by the item, for `Layout`s with cells.
+    pub span: Size,
+
+    /// How the `Widget` is aligned within its space, horizontally and vertically.
+    pub alignment: (LayoutAlignment, LayoutAlignment),
+}
+
+/// This is the ID stored by a `Layout` in place of a `Widget` ID for a spacer or strut.
+pub(crate) const SPACER_ID: i32 = -1;
+
+/// This enum is how much of the length of a `Layout` a `Widget` is given, along the direction in
+/// which the `Layout` places its `Widget`s.
+#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LayoutSize {
+    /// A share of the length left over by the `Fixed` and `Percent` sizes, in proportion to the
+    /// weights of the other `Weight` sizes.
+    Weight(u32),
+
+    /// A fixed number of pixels.
+    Fixed(u32),
+
+    /// A percentage of the length of the `Layout` inside its padding, less the spacing between its
+    /// `Widget`s, so that the `Widget` grows and shrinks with the `Layout`.
+    Percent(f64),
+}
+
+/// Divides `length` pixels between items separated by `spacing` pixels, returning the offset and
+/// length of each.  `Fixed` and `Percent` sizes are given their pixels first, and the rest is
+/// shared between the `Weight` sizes in proportion to their weights.
+pub(crate) fn distribute_lengths(
+    length: i32,
+    spacing: i32,
+    sizes: &[LayoutSize],
+) -> Vec<(i32, u32)> {
+    let count = sizes.len() as i32;
+    let available = (length - spacing * (count - 1).max(0)).max(0) as f64;
+    let claimed: f64 = sizes
+        .iter()
+        .map(|size| match size {
+            LayoutSize::Fixed(pixels) => *pixels as f64,
+            LayoutSize::Percent(percent) => (available * percent / 100.0).round().max(0.0),
+            LayoutSize::Weight(_) => 0.0,
+        })
+        .sum();
+    let weight_total: u32 = sizes
+        .iter()
+        .map(|size| match size {
+            LayoutSize::Weight(weight) => *weight,
+            _ => 0,
+        })
+        .sum();
+    let shared = (available - claimed).max(0.0);
+    let mut weight_so_far = 0;
+    let mut offset = 0;
+
+    sizes
+        .iter()
+        .map(|size| {
+            // Shared lengths are rounded at their running totals, so that they add up exactly.
+            let pixels = match size {
+                LayoutSize::Fixed(pixels) => *pixels,
+                LayoutSize::Percent(percent) => {
+                    (available * percent / 100.0).round().max(0.0) as u32
+                }
+                LayoutSize::Weight(weight) if weight_total > 0 => {
+                    let start = (shared * weight_so_far as f64 / weight_total as f64).round();
+
+                    weight_so_far += weight;
+
+                    let end = (shared * weight_so_far as f64 / weight_total as f64).round();
+
+                    (end - start) as u32
+                }
+                LayoutSize::Weight(_) => 0,
+            };
+            let item = (offset, pixels);
+
+            offset += pixels as i32 + spacing;
+            item
+        })
+        .collect()
+}
+
+/// This enum is how a `Widget` is aligned within the cell a `Layout` gives it, along one axis.
+#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LayoutAlignment {
+    /// The `Widget` keeps its size, at the left or top of the cell.
+    Start,
+
+    /// The `Widget` keeps its size, centered in the cell.
+    Center,
+
+    /// The `Widget` keeps its size, at the right or bottom of the cell.
+    End,
+
+    /// The `Widget` is stretched to fill the cell.  This is the default.
+    Fill,
+}
+
+/// Returns the start and length of a `Widget` of `size` pixels aligned within a cell of `length`
+/// pixels beginning at `start`.
+fn align_span(start: i32, length: i32, size: u32, alignment: LayoutAlignment) -> (i32, u32) {
+    let size = size as i32;
+
+    match alignment {
+        LayoutAlignment::Start => (start, size as u32),
+        LayoutAlignment::Center => (start + (length - size) / 2, size as u32),
+        LayoutAlignment::End => (start + length - size, size as u32),
+        LayoutAlignment::Fill => (start, length.max(0) as u32),
+    }
+}
+
+/// Places a `Widget` in the cell at `x`, `y` of `w` by `h` pixels, aligned horizontally and
+/// vertically by `alignment`, and invalidates it.  A `Widget` that is not stretched keeps its size
+/// along that axis.
+pub(crate) fn place_widget(
+    widget: &WidgetContainer,
+    cell: (i32, i32, i32, i32),
+    alignment: (LayoutAlignment, LayoutAlignment),
+) {
+    let (x, y, w, h) = cell;
+    let mut widget = widget.widget.borrow_mut();
+    let size = widget.get_config().get_size(CONFIG_SIZE);
+    let (set_x, set_width) = align_span(x, w, size[SIZE_WIDTH], alignment.0);
+    let (set_y, set_height) = align_span(y, h, size[SIZE_HEIGHT], alignment.1);
+
+    widget.get_config().set_point(CONFIG_ORIGIN, set_x, set_y);
+    widget
+        .get_config()
+        .set_size(CONFIG_SIZE, set_width, set_height);
+    widget.get_config().set_invalidated(true);
+}
+
+/// Places each `Widget` in the cell computed for it by a `ParallelLayout`.
+pub(crate) fn place_cells(widgets: &[WidgetContainer], cells: Vec<LayoutCell>) {
+    for cell in cells {
+        place_widget(
+            &widgets[cell.widget_id as usize],
+            cell.bounds,
+            cell.alignment,
+        );
+    }
 }
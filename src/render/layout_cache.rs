@@ -13,9 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::render::layout::Layout;
+use crate::render::layout::{place_cells, Layout, LayoutCell, ParallelLayout};
 use crate::render::widget_cache::WidgetContainer;
-use std::cell::RefCell;
+use crate::render::Size;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
 
 /// This is a container object that stores a `Layout` object, and its ID.
 pub struct LayoutContainer {
@@ -80,6 +84,77 @@ impl LayoutCache {
             }
         }
     }
+
+    /// Performs the `do_layout` call on `Layout` objects that need layout, like `do_layout`, but
+    /// first computes the cells of those that implement `ParallelLayout` on the threads of `pool`.
+    /// The `Widget`s are placed on the calling thread, in the order in which the `Layout`s were
+    /// added, so a `Widget` in more than one `Layout` is placed by the last one, as before.
+    pub fn do_parallel_layout(&self, widgets: &[WidgetContainer], pool: &ThreadPool) {
+        let mut layouts: Vec<(usize, RefMut<Box<dyn Layout>>)> = self
+            .cache
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| {
+                let layout = x.layout.borrow_mut();
+
+                if layout.needs_layout() {
+                    Some((i, layout))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut tasks: Vec<(usize, &mut dyn ParallelLayout)> = layouts
+            .iter_mut()
+            .filter_map(|(i, layout)| layout.as_parallel_layout().map(|task| (*i, task)))
+            .collect();
+        let mut cells: HashMap<usize, Vec<LayoutCell>> = pool.install(|| {
+            tasks
+                .par_iter_mut()
+                .map(|(i, task)| (*i, task.compute_cells()))
+                .collect()
+        });
+
+        drop(tasks);
+
+        for (i, layout) in layouts.iter_mut() {
+            match cells.remove(i) {
+                Some(layout_cells) => place_cells(widgets, layout_cells),
+                None => layout.do_layout(widgets),
+            }
+        }
+    }
+
+    /// Sends the new window `size` to all of the `Layout` objects, so that they can adjust their
+    /// bounds, and marks them all as needing layout, so that their `Widget`s are reflowed on the
+    /// next `do_layout`.
+    pub fn window_resized(&self, size: Size) {
+        for x in &self.cache {
+            let mut layout = x.layout.borrow_mut();
+
+            layout.window_resized(size.clone());
+            layout.invalidate();
+        }
+    }
+
+    /// Removes the `Widget`s specified by `widget_ids` from all of the `Layout` objects, so that
+    /// their IDs are not placed after they have been reused by other `Widget`s.
+    pub fn remove_widgets(&self, widget_ids: &[i32]) {
+        for x in &self.cache {
+            let mut layout = x.layout.borrow_mut();
+
+            for widget_id in widget_ids {
+                layout.remove_widget(*widget_id);
+            }
+        }
+    }
+
+    /// Marks all of the `Layout` objects as needing layout.
+    pub fn invalidate_all(&self) {
+        for x in &self.cache {
+            x.layout.borrow_mut().invalidate();
+        }
+    }
 }
 
 impl Default for LayoutCache {
@@ -0,0 +1,1040 @@
+// Pushrod Rendering Library
+// Layout Export and Import
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::layouts::flow_layout::FlowLayout;
+use crate::layouts::grid_layout::GridLayout;
+use crate::layouts::horizontal_layout::HorizontalLayout;
+use crate::layouts::vertical_layout::VerticalLayout;
+use crate::render::layout::{Layout, LayoutAlignment, LayoutPosition, LayoutSize, SPACER_ID};
+use crate::render::layout_cache::{LayoutCache, LayoutContainer};
+use crate::render::widget::{BaseWidget, Widget};
+use crate::render::widget_cache::{WidgetCache, WidgetContainer};
+use crate::render::widget_config::{
+    config_key_from_name, config_key_name, CompassPosition, Config, PaddingConstraint,
+    CONFIG_ORIGIN, CONFIG_SIZE,
+};
+use crate::render::{make_points_origin, make_size, Points, Size};
+use crate::widgets::accordion_widget::AccordionWidget;
+use crate::widgets::calendar_widget::CalendarWidget;
+use crate::widgets::canvas_widget::CanvasWidget;
+use crate::widgets::circular_progress_widget::CircularProgressWidget;
+use crate::widgets::compass_widget::CompassWidget;
+use crate::widgets::drawing_pad_widget::DrawingPadWidget;
+use crate::widgets::dropdown_widget::DropdownWidget;
+use crate::widgets::keypad_widget::KeypadWidget;
+use crate::widgets::list_widget::ListWidget;
+use crate::widgets::progress_widget::ProgressWidget;
+use ron::extensions::Extensions;
+use sdl2::pixels::Color;
+use serde::{Deserialize, Serialize};
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// This is the format of a layout file.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LayoutFormat {
+    /// Rusty Object Notation.  This is the default.
+    #[default]
+    Ron,
+
+    /// JSON.
+    Json,
+}
+
+/// This is the implementation of the `LayoutFormat`.
+impl LayoutFormat {
+    /// Chooses the format of the layout file at `path` by its extension: `.json` files are JSON,
+    /// and all other files are RON.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|x| x.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => LayoutFormat::Json,
+            _ => LayoutFormat::Ron,
+        }
+    }
+
+    /// RON files are written and read with the `implicit_some` extension, so that optional values
+    /// can be given without wrapping them in `Some`.
+    fn ron_options() -> ron::Options {
+        ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+    }
+
+    fn write(self, document: &LayoutDocument) -> Result<String, LayoutFileError> {
+        match self {
+            LayoutFormat::Ron => Self::ron_options()
+                .to_string_pretty(document, ron::ser::PrettyConfig::default())
+                .map_err(|e| LayoutFileError::Parse(e.to_string())),
+            LayoutFormat::Json => serde_json::to_string_pretty(document)
+                .map_err(|e| LayoutFileError::Parse(e.to_string())),
+        }
+    }
+
+    fn read(self, text: &str) -> Result<LayoutDocument, LayoutFileError> {
+        match self {
+            LayoutFormat::Ron => Self::ron_options()
+                .from_str(text)
+                .map_err(|e| LayoutFileError::Parse(e.to_string())),
+            LayoutFormat::Json => {
+                serde_json::from_str(text).map_err(|e| LayoutFileError::Parse(e.to_string()))
+            }
+        }
+    }
+}
+
+/// This is a function that creates a `Widget` of a registered type, given the origin and size read
+/// from a layout file.
+pub type WidgetConstructorType = Box<dyn Fn(Points, Size) -> Box<dyn Widget>>;
+
+/// This is a registry of the `Widget` types that layout files can create, keyed by the type names
+/// written to the files.  The default registry contains the `Widget`s that need only an origin and
+/// a size: `base`, `accordion`, `calendar`, `canvas`, `circular_progress`, `compass`,
+/// `drawing_pad`, `dropdown`, `keypad`, `list` and `progress`.  Other `Widget`s, such as those that
+/// need a font or some text when they are created, can be added with `register`.
+pub struct WidgetRegistry {
+    constructors: HashMap<String, WidgetConstructorType>,
+    type_names: HashMap<TypeId, String>,
+}
+
+/// This is the implementation of the `WidgetRegistry`.
+impl WidgetRegistry {
+    /// Creates a new registry, without any `Widget` types.
+    pub fn empty() -> Self {
+        Self {
+            constructors: HashMap::new(),
+            type_names: HashMap::new(),
+        }
+    }
+
+    /// Registers the `Widget` type `W` as `type_name`, so that layout files can create it by calling
+    /// `constructor`, and so that exported `Widget`s of this type are written with `type_name`.
+    /// Configuration values from the layout file are applied after the `Widget` is created.
+    /// Registering a `type_name` again replaces its constructor.
+    pub fn register<W, F>(&mut self, type_name: &str, constructor: F)
+    where
+        W: Widget + 'static,
+        F: Fn(Points, Size) -> W + 'static,
+    {
+        self.constructors.insert(
+            String::from(type_name),
+            Box::new(move |points, size| Box::new(constructor(points, size))),
+        );
+        self.type_names
+            .insert(TypeId::of::<W>(), String::from(type_name));
+    }
+
+    /// Retrieves the name that the type of `widget` was registered as, if it has been registered.
+    pub fn type_name_of(&self, widget: &mut dyn Widget) -> Option<&str> {
+        let widget: &dyn Any = widget.as_any();
+
+        self.type_names
+            .get(&(*widget).type_id())
+            .map(String::as_str)
+    }
+
+    /// Creates a new `Widget` of the type registered as `type_name`, at `points` and of `size`.
+    pub fn create(&self, type_name: &str, points: Points, size: Size) -> Option<Box<dyn Widget>> {
+        self.constructors
+            .get(type_name)
+            .map(|constructor| constructor(points, size))
+    }
+}
+
+impl Default for WidgetRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+
+        registry.register("base", BaseWidget::new);
+        registry.register("accordion", AccordionWidget::new);
+        registry.register("calendar", CalendarWidget::new);
+        registry.register("canvas", CanvasWidget::new);
+        registry.register("circular_progress", |points, size| {
+            CircularProgressWidget::new(points, size, 0)
+        });
+        registry.register("compass", CompassWidget::new);
+        registry.register("drawing_pad", DrawingPadWidget::new);
+        registry.register("dropdown", DropdownWidget::new);
+        registry.register("keypad", KeypadWidget::new);
+        registry.register("list", ListWidget::new);
+        registry.register("progress", |points, size| {
+            ProgressWidget::new(points, size, 0)
+        });
+        registry
+    }
+}
+
+/// This is the error returned when a layout file cannot be exported or imported.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutFileError {
+    /// The layout file could not be parsed or written.
+    Parse(String),
+
+    /// The layout file refers to a `Widget` name that is not in the `WidgetCache`, and does not
+    /// give a type with which to create it.
+    UnknownWidget(String),
+
+    /// The layout file refers to a `Layout` ID that is not in the `LayoutCache`.
+    UnknownLayout(i32),
+
+    /// The layout file would make the named `Widget` an ancestor of itself, or re-parent the base
+    /// `Widget`.
+    CyclicParent(String),
+
+    /// The layout file describes something that cannot be applied, such as an unknown
+    /// configuration key or `Layout` type.
+    Invalid(String),
+
+    /// The layout file could not be read or written.
+    Io(String),
+}
+
+impl fmt::Display for LayoutFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutFileError::Parse(message) => write!(f, "Parse error: {}", message),
+            LayoutFileError::UnknownWidget(name) => write!(f, "Unknown Widget: {}", name),
+            LayoutFileError::UnknownLayout(id) => write!(f, "Unknown Layout: {}", id),
+            LayoutFileError::CyclicParent(name) => {
+                write!(f, "Widget {} cannot be made a child of itself", name)
+            }
+            LayoutFileError::Invalid(message) => write!(f, "Invalid layout: {}", message),
+            LayoutFileError::Io(message) => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+impl Error for LayoutFileError {}
+
+/// Exports the `Widget` tree to a layout file in the given `format`: the type, parent, visibility,
+/// enabled state, and configuration values (including position and size) of each named `Widget`,
+/// followed by the padding and `Widget`s of each `Layout`.  `Layout`s that can `describe`
+/// themselves are also given their type, bounds and window tracking, and each `Widget`, spacer and
+/// strut is written as an item with its position, size, span and alignment, so that they can be
+/// created again when the file is imported.  `Widget`s without a name are not exported, as they
+/// cannot be matched when importing.  The type of a `Widget` is the name it was registered as in
+/// `registry`, and is left out for `Widget` types that have not been registered.
+///
+/// Configuration values are keyed by the names returned by `config_key_name`.
+pub fn export_layout(
+    widgets: &[WidgetContainer],
+    layouts: &[LayoutContainer],
+    registry: &WidgetRegistry,
+    format: LayoutFormat,
+) -> Result<String, LayoutFileError> {
+    let name_of = |widget_id: i32| {
+        widgets
+            .get(widget_id as usize)
+            .map(|widget| widget.get_widget_name().to_string())
+            .filter(|name| !name.is_empty())
+    };
+    let mut document = LayoutDocument::default();
+
+    for container in widgets {
+        let name = container.get_widget_name();
+
+        if name.is_empty() {
+            continue;
+        }
+
+        let mut widget = container.widget.borrow_mut();
+        let widget_type = registry.type_name_of(widget.as_mut()).map(String::from);
+        let config = widget.get_config();
+
+        document.widgets.push(WidgetEntry {
+            name: name.to_string(),
+            widget_type,
+            parent: if container.get_widget_id() == 0 {
+                None
+            } else {
+                name_of(container.get_parent_id())
+            },
+            hidden: Some(config.is_hidden()),
+            enabled: Some(config.is_enabled()),
+            config: config
+                .config
+                .iter()
+                .map(|(key, value)| (config_key_name(*key), ConfigValue::from(value)))
+                .collect(),
+        });
+    }
+
+    for container in layouts {
+        let layout = container.layout.borrow();
+        let mut entry = LayoutEntry {
+            id: container.get_layout_id(),
+            layout_type: None,
+            bounds: None,
+            grid: None,
+            track_window: false,
+            padding: Some(layout.get_padding()),
+            widgets: Vec::new(),
+            items: Vec::new(),
+        };
+
+        match layout.describe() {
+            Some(description) => {
+                entry.layout_type = Some(description.layout_type);
+                entry.bounds = Some((
+                    description.origin[0],
+                    description.origin[1],
+                    description.size[0],
+                    description.size[1],
+                ));
+                entry.track_window = description.track_window;
+
+                if description.layout.len() == 2 {
+                    entry.grid = Some((description.layout[0], description.layout[1]));
+                }
+
+                for item in description.items {
+                    let widget = if item.widget_id == SPACER_ID {
+                        None
+                    } else {
+                        match name_of(item.widget_id) {
+                            Some(name) => Some(name),
+                            None => continue,
+                        }
+                    };
+
+                    entry.items.push(ItemEntry {
+                        widget,
+                        position: (item.position.x, item.position.y),
+                        size: item.size,
+                        span: (item.span[0], item.span[1]),
+                        alignment: item.alignment,
+                    });
+                }
+            }
+            None => {
+                entry.widgets = layout
+                    .get_widget_ids()
+                    .into_iter()
+                    .filter_map(name_of)
+                    .collect();
+            }
+        }
+
+        document.layouts.push(entry);
+    }
+
+    format.write(&document)
+}
+
+/// Imports a layout file in the given `format`, applying it to the `Widget`s in the `WidgetCache`
+/// with matching names, and to the `Layout`s in the `LayoutCache` with matching IDs.  A named
+/// `Widget` that is not in the `WidgetCache` is created from its `widget_type`, using the
+/// constructor registered in `registry`, at the origin and size given in its configuration; it is
+/// then configured and placed like the other `Widget`s.  Returns the number of `Widget`s created or
+/// updated.
+///
+/// A `Layout` with a `layout_type` - `horizontal`, `vertical`, `grid` or `flow` - is created from
+/// its bounds and items: if its ID is the next one in the `LayoutCache`, it is added, and
+/// otherwise, it replaces the `Layout` with that ID, so that a file exported by `export_layout`
+/// restores its `Layout`s exactly.  A `Layout` without a type keeps its own bounds and items;
+/// only its padding is set, and the `Widget`s it is missing are appended to it.
+///
+/// The whole file is parsed, and all names, types, IDs, parents and values are checked, before any
+/// changes are made, so a file that fails to import leaves the `Widget`s and `Layout`s untouched.
+pub fn import_layout(
+    text: &str,
+    format: LayoutFormat,
+    registry: &WidgetRegistry,
+    widgets: &mut WidgetCache,
+    layouts: &mut LayoutCache,
+) -> Result<usize, LayoutFileError> {
+    let document = format.read(text)?;
+    let current_parents: Vec<i32> = widgets
+        .borrow_cache()
+        .iter()
+        .map(|container| container.get_parent_id())
+        .collect();
+
+    // Widgets that are not in the cache are given the IDs they will have once they are added, so
+    // that everything referring to them can be checked before they are created.
+    let mut new_names: Vec<&str> = Vec::new();
+
+    for entry in &document.widgets {
+        if widgets.get_widget_id_by_name(&entry.name).is_none()
+            && !new_names.contains(&entry.name.as_str())
+        {
+            new_names.push(&entry.name);
+        }
+    }
+
+    let new_ids: HashMap<&str, i32> = new_names
+        .iter()
+        .cloned()
+        .zip(widgets.next_widget_ids(new_names.len()))
+        .collect();
+    let widget_id_for = |name: &str| {
+        widgets
+            .get_widget_id_by_name(name)
+            .or_else(|| new_ids.get(name).cloned())
+            .ok_or_else(|| LayoutFileError::UnknownWidget(String::from(name)))
+    };
+    let mut new_widgets = Vec::new();
+    let mut widget_changes = Vec::new();
+    let mut parents = HashMap::new();
+    let mut names = HashMap::new();
+
+    for entry in &document.widgets {
+        let widget_id = widget_id_for(&entry.name)?;
+        let mut config = Vec::new();
+
+        if let Some(parent_name) = &entry.parent {
+            if widget_id == 0 {
+                return Err(LayoutFileError::CyclicParent(entry.name.clone()));
+            }
+
+            parents.insert(widget_id, widget_id_for(parent_name)?);
+        }
+
+        for (name, value) in &entry.config {
+            let key = config_key_from_name(name).ok_or_else(|| {
+                LayoutFileError::Invalid(format!("Unknown configuration key {}", name))
+            })?;
+
+            config.push((key, value.to_config()));
+        }
+
+        if new_ids.contains_key(entry.name.as_str())
+            && !new_widgets.iter().any(|(name, _)| *name == entry.name)
+        {
+            new_widgets.push((
+                entry.name.as_str(),
+                create_widget(entry, &config, registry)?,
+            ));
+        }
+
+        names.insert(widget_id, entry.name.as_str());
+        widget_changes.push((widget_id, entry, config));
+    }
+
+    check_parents(&current_parents, &parents, &names)?;
+
+    let layout_count = layouts.get_layout_cache().len() as i32;
+    let mut next_layout_id = layout_count;
+    let mut layout_changes = Vec::new();
+
+    for entry in &document.layouts {
+        let layout = match entry.layout_type {
+            Some(_) => Some(create_layout(entry, widget_id_for)?),
+            None if !entry.items.is_empty() => {
+                return Err(LayoutFileError::Invalid(format!(
+                    "Layout {} has items, but no layout_type",
+                    entry.id
+                )));
+            }
+            None => None,
+        };
+
+        if entry.id == next_layout_id && layout.is_some() {
+            next_layout_id += 1;
+        } else if entry.id < 0 || entry.id >= layout_count {
+            return Err(LayoutFileError::UnknownLayout(entry.id));
+        }
+
+        let mut widget_ids = Vec::new();
+
+        for name in &entry.widgets {
+            widget_ids.push(widget_id_for(name)?);
+        }
+
+        layout_changes.push((entry, layout, widget_ids));
+    }
+
+    // Everything has been checked, so nothing below can fail.  New Widgets are added first, so
+    // that they can be re-parented like the others.
+    for (name, widget) in new_widgets {
+        let handle = widgets.add_widget(widget, name);
+
+        debug_assert_eq!(Some(&handle.id()), new_ids.get(name));
+    }
+
+    // Re-parented Widgets are first moved to the base Widget, so that no intermediate tree
+    // contains a cycle.
+    let base = widgets.get_handle(0).unwrap();
+    let mut reparented: Vec<(i32, i32)> = parents
+        .into_iter()
+        .filter(|(widget_id, parent_id)| {
+            widgets.get_container_by_id(*widget_id).get_parent_id() != *parent_id
+        })
+        .collect();
+
+    reparented.sort();
+
+    for (widget_id, _) in &reparented {
+        let handle = widgets.get_handle(*widget_id).unwrap();
+
+        widgets.set_parent(handle, base).unwrap();
+    }
+
+    for (widget_id, parent_id) in &reparented {
+        let handle = widgets.get_handle(*widget_id).unwrap();
+        let parent = widgets.get_handle(*parent_id).unwrap();
+
+        widgets.set_parent(handle, parent).unwrap();
+    }
+
+    for (widget_id, entry, config) in &widget_changes {
+        let container = widgets.get_container_by_id(*widget_id);
+        let mut widget = container.widget.borrow_mut();
+
+        // Widgets that keep config values in their own fields are told about each value, in the
+        // same way as the typed setters.
+        for (key, value) in config {
+            widget.get_config().config.insert(*key, value.clone());
+            widget.on_config_changed(*key, value.clone());
+        }
+
+        let widget_config = widget.get_config();
+
+        match entry.hidden {
+            Some(true) => widget_config.hide(),
+            Some(false) => widget_config.show(),
+            None => (),
+        }
+
+        match entry.enabled {
+            Some(true) => widget_config.enable(),
+            Some(false) => widget_config.disable(),
+            None => (),
+        }
+
+        widget_config.set_invalidated(true);
+    }
+
+    for (entry, layout, widget_ids) in layout_changes {
+        match layout {
+            Some(layout) if entry.id == layouts.get_layout_cache().len() as i32 => {
+                layouts.add_layout(layout);
+            }
+            Some(layout) => {
+                *layouts.get_layout_by_id(entry.id).layout.borrow_mut() = layout;
+            }
+            None => {
+                let container = layouts.get_layout_by_id(entry.id);
+                let mut layout = container.layout.borrow_mut();
+
+                if let Some(padding) = entry.padding {
+                    layout.set_padding(padding);
+                }
+
+                let existing = layout.get_widget_ids();
+
+                for widget_id in widget_ids {
+                    if !existing.contains(&widget_id) {
+                        layout.append_widget(widget_id);
+                    }
+                }
+            }
+        }
+    }
+
+    layouts.invalidate_all();
+    widgets
+        .get_container_by_id(0)
+        .widget
+        .borrow_mut()
+        .set_invalidated(true);
+
+    Ok(widget_changes.len())
+}
+
+/// Exports the `Widget` tree to a file, in the format chosen by its extension.  See
+/// `export_layout` and `LayoutFormat::from_path`.
+pub fn save_layout<P: AsRef<Path>>(
+    path: P,
+    widgets: &[WidgetContainer],
+    layouts: &[LayoutContainer],
+    registry: &WidgetRegistry,
+) -> Result<(), LayoutFileError> {
+    let text = export_layout(widgets, layouts, registry, LayoutFormat::from_path(&path))?;
+
+    fs::write(path, text).map_err(|e| LayoutFileError::Io(e.to_string()))
+}
+
+/// Imports a layout from a file, in the format chosen by its extension.  See `import_layout` and
+/// `LayoutFormat::from_path`.
+pub fn load_layout<P: AsRef<Path>>(
+    path: P,
+    registry: &WidgetRegistry,
+    widgets: &mut WidgetCache,
+    layouts: &mut LayoutCache,
+) -> Result<usize, LayoutFileError> {
+    let text = fs::read_to_string(&path).map_err(|e| LayoutFileError::Io(e.to_string()))?;
+
+    import_layout(
+        &text,
+        LayoutFormat::from_path(&path),
+        registry,
+        widgets,
+        layouts,
+    )
+}
+
+/// The contents of a layout file.
+#[derive(Default, Serialize, Deserialize)]
+struct LayoutDocument {
+    #[serde(default)]
+    widgets: Vec<WidgetEntry>,
+
+    #[serde(default)]
+    layouts: Vec<LayoutEntry>,
+}
+
+/// A `Widget` in a layout file.
+#[derive(Serialize, Deserialize)]
+struct WidgetEntry {
+    name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    widget_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hidden: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    config: BTreeMap<String, ConfigValue>,
+}
+
+/// A `Layout` in a layout file.
+#[derive(Serialize, Deserialize)]
+struct LayoutEntry {
+    id: i32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    layout_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bounds: Option<(i32, i32, u32, u32)>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    grid: Option<(i32, i32)>,
+
+    #[serde(default, skip_serializing_if = "is_false")]
+    track_window: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    padding: Option<PaddingConstraint>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    widgets: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    items: Vec<ItemEntry>,
+}
+
+/// A `Widget`, spacer or strut in a `Layout` in a layout file.  Items without a `widget` are
+/// spacers or struts.
+#[derive(Serialize, Deserialize)]
+struct ItemEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    widget: Option<String>,
+
+    position: (i32, i32),
+
+    #[serde(default = "default_size", skip_serializing_if = "is_default_size")]
+    size: LayoutSize,
+
+    #[serde(default = "default_span", skip_serializing_if = "is_default_span")]
+    span: (u32, u32),
+
+    #[serde(
+        default = "default_alignment",
+        skip_serializing_if = "is_default_alignment"
+    )]
+    alignment: (LayoutAlignment, LayoutAlignment),
+}
+
+fn default_size() -> LayoutSize {
+    LayoutSize::Weight(1)
+}
+
+fn is_default_size(size: &LayoutSize) -> bool {
+    *size == default_size()
+}
+
+fn default_span() -> (u32, u32) {
+    (1, 1)
+}
+
+fn is_default_span(span: &(u32, u32)) -> bool {
+    *span == default_span()
+}
+
+fn default_alignment() -> (LayoutAlignment, LayoutAlignment) {
+    (LayoutAlignment::Fill, LayoutAlignment::Fill)
+}
+
+fn is_default_alignment(alignment: &(LayoutAlignment, LayoutAlignment)) -> bool {
+    *alignment == default_alignment()
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// A `Config` value in a layout file.
+#[derive(Serialize, Deserialize)]
+enum ConfigValue {
+    Points(i32, i32),
+    Size(u32, u32),
+    Color(u8, u8, u8, u8),
+    Numeric(i32),
+    Text(String),
+    Toggle(bool),
+    Compass(CompassPosition),
+    Padding(PaddingConstraint),
+}
+
+impl From<&Config> for ConfigValue {
+    fn from(config: &Config) -> Self {
+        match config {
+            Config::Points(points) => ConfigValue::Points(points[0], points[1]),
+            Config::Size(size) => ConfigValue::Size(size[0], size[1]),
+            Config::Color(color) => ConfigValue::Color(color.r, color.g, color.b, color.a),
+            Config::Numeric(value) => ConfigValue::Numeric(*value),
+            Config::Text(text) => ConfigValue::Text(text.clone()),
+            Config::Toggle(flag) => ConfigValue::Toggle(*flag),
+            Config::CompassPosition(position) => ConfigValue::Compass(*position),
+            Config::PaddingConstraint(padding) => ConfigValue::Padding(*padding),
+        }
+    }
+}
+
+impl ConfigValue {
+    fn to_config(&self) -> Config {
+        match self {
+            ConfigValue::Points(x, y) => Config::Points(vec![*x, *y]),
+            ConfigValue::Size(w, h) => Config::Size(vec![*w, *h]),
+            ConfigValue::Color(r, g, b, a) => Config::Color(Color::RGBA(*r, *g, *b, *a)),
+            ConfigValue::Numeric(value) => Config::Numeric(*value),
+            ConfigValue::Text(text) => Config::Text(text.clone()),
+            ConfigValue::Toggle(flag) => Config::Toggle(*flag),
+            ConfigValue::Compass(position) => Config::CompassPosition(*position),
+            ConfigValue::Padding(padding) => Config::PaddingConstraint(*padding),
+        }
+    }
+}
+
+/// Checks that giving the `Widget`s in `parents` their new parents would not make any `Widget` an
+/// ancestor of itself.  `current_parents` are the parent IDs of the `Widget`s in the cache; a
+/// `Widget` that is not in the cache yet is added to the base `Widget`.
+fn check_parents(
+    current_parents: &[i32],
+    parents: &HashMap<i32, i32>,
+    names: &HashMap<i32, &str>,
+) -> Result<(), LayoutFileError> {
+    let widget_count = current_parents.len() + parents.len();
+
+    for widget_id in parents.keys() {
+        let mut ancestor_id = *widget_id;
+
+        // A chain longer than the number of Widgets must contain a cycle, even if the Widget being
+        // checked is not part of it.
+        for _ in 0..=widget_count {
+            ancestor_id = match parents.get(&ancestor_id) {
+                Some(parent_id) => *parent_id,
+                None => current_parents
+                    .get(ancestor_id as usize)
+                    .cloned()
+                    .unwrap_or(0),
+            };
+
+            if ancestor_id == 0 || ancestor_id == *widget_id {
+                break;
+            }
+        }
+
+        if ancestor_id != 0 {
+            return Err(LayoutFileError::CyclicParent(String::from(
+                names[widget_id],
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a new `Widget` for a `Widget` in a layout file that is not in the `WidgetCache`, using
+/// the constructor registered for its `widget_type`.
+fn create_widget(
+    entry: &WidgetEntry,
+    config: &[(u8, Config)],
+    registry: &WidgetRegistry,
+) -> Result<Box<dyn Widget>, LayoutFileError> {
+    let type_name = entry
+        .widget_type
+        .as_deref()
+        .ok_or_else(|| LayoutFileError::UnknownWidget(entry.name.clone()))?;
+    let mut points = make_points_origin();
+    let mut size = make_size(0, 0);
+
+    for (key, value) in config {
+        match (*key, value) {
+            (CONFIG_ORIGIN, Config::Points(value)) => points = value.clone(),
+            (CONFIG_SIZE, Config::Size(value)) => size = value.clone(),
+            _ => (),
+        }
+    }
+
+    registry.create(type_name, points, size).ok_or_else(|| {
+        LayoutFileError::Invalid(format!(
+            "Widget {}: Unknown widget type {}",
+            entry.name, type_name
+        ))
+    })
+}
+
+/// Creates a new `Layout` from a `Layout` in a layout file with a `layout_type`, adding its
+/// `Widget`s, spacers and struts.
+fn create_layout<F>(
+    entry: &LayoutEntry,
+    widget_id_for: F,
+) -> Result<Box<dyn Layout>, LayoutFileError>
+where
+    F: Fn(&str) -> Result<i32, LayoutFileError>,
+{
+    let error =
+        |message: &str| LayoutFileError::Invalid(format!("Layout {}: {}", entry.id, message));
+    let (x, y, w, h) = entry.bounds.ok_or_else(|| error("Missing bounds"))?;
+    let padding = entry.padding.unwrap_or_default();
+    let mut items = Vec::new();
+
+    for item in &entry.items {
+        let widget_id = match &item.widget {
+            Some(name) => widget_id_for(name)?,
+            None => SPACER_ID,
+        };
+
+        items.push((widget_id, item));
+    }
+
+    let position = |item: &ItemEntry| LayoutPosition::new(item.position.0, item.position.1);
+    let mut layout: Box<dyn Layout> = match entry.layout_type.as_deref() {
+        Some("horizontal") => {
+            let mut layout = HorizontalLayout::new(x, y, w, h, padding);
+
+            for (widget_id, item) in &items {
+                layout.add_widget_with_size(*widget_id, position(item), item.size);
+
+                if *widget_id != SPACER_ID {
+                    layout.set_widget_alignment(*widget_id, item.alignment.0, item.alignment.1);
+                }
+            }
+
+            layout.set_track_window(entry.track_window);
+            Box::new(layout)
+        }
+        Some("vertical") => {
+            let mut layout = VerticalLayout::new(x, y, w, h, padding);
+
+            for (widget_id, item) in &items {
+                layout.add_widget_with_size(*widget_id, position(item), item.size);
+
+                if *widget_id != SPACER_ID {
+                    layout.set_widget_alignment(*widget_id, item.alignment.0, item.alignment.1);
+                }
+            }
+
+            layout.set_track_window(entry.track_window);
+            Box::new(layout)
+        }
+        Some("grid") => {
+            let (columns, rows) = entry.grid.ok_or_else(|| error("Missing grid"))?;
+            let mut layout = GridLayout::new(x, y, w, h, vec![columns, rows], padding);
+
+            for (widget_id, item) in items.iter().filter(|(id, _)| *id != SPACER_ID) {
+                layout.insert_widget_spanning(*widget_id, position(item), item.span.0, item.span.1);
+                layout.set_widget_alignment(*widget_id, item.alignment.0, item.alignment.1);
+            }
+
+            layout.set_track_window(entry.track_window);
+            Box::new(layout)
+        }
+        Some("flow") => {
+            let mut layout = FlowLayout::new(x, y, w, h, padding);
+
+            for (widget_id, item) in items.iter().filter(|(id, _)| *id != SPACER_ID) {
+                layout.insert_widget(*widget_id, position(item));
+            }
+
+            layout.set_track_window(entry.track_window);
+            Box::new(layout)
+        }
+        Some(other) => return Err(error(&format!("Unknown layout type {}", other))),
+        None => return Err(error("Missing layout type")),
+    };
+
+    layout.invalidate();
+
+    Ok(layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// This is a layout file with a configured `Widget`, and a `Layout` of a `Widget` and a strut.
+    const DOCUMENT: &str = r#"(
+        widgets: [
+            (
+                name: "panel",
+                widget_type: "base",
+                parent: "base",
+                hidden: false,
+                config: {
+                    "CONFIG_COLOR_BASE": Color(255, 200, 200, 255),
+                    "CONFIG_BORDER_WIDTH": Numeric(1),
+                },
+            ),
+        ],
+        layouts: [
+            (
+                id: 0,
+                layout_type: "horizontal",
+                bounds: (0, 0, 400, 50),
+                padding: (top: 5, bottom: 5, left: 5, right: 5, spacing: 5),
+                items: [
+                    (widget: "panel", position: (0, 0), size: Fixed(80)),
+                    (position: (0, 1), size: Fixed(20), alignment: (Start, Center)),
+                ],
+            ),
+        ],
+    )"#;
+
+    /// Creates a `WidgetCache` with the base `Widget`, and two children of it named `first` and
+    /// `second`.
+    fn widget_cache() -> WidgetCache {
+        let mut widgets = WidgetCache::new();
+
+        for name in &["base", "first", "second"] {
+            widgets.add_widget(
+                Box::new(BaseWidget::new(make_points_origin(), make_size(10, 10))),
+                *name,
+            );
+        }
+
+        widgets
+    }
+
+    /// Imports `text` as RON into `widgets`, with an empty `LayoutCache`.
+    fn import(text: &str, widgets: &mut WidgetCache) -> Result<usize, LayoutFileError> {
+        import_layout(
+            text,
+            LayoutFormat::Ron,
+            &WidgetRegistry::default(),
+            widgets,
+            &mut LayoutCache::new(),
+        )
+    }
+
+    /// Indicates whether the `Widget` with the ID `widget_id` is hidden.
+    fn is_hidden(widgets: &mut WidgetCache, widget_id: i32) -> bool {
+        widgets
+            .get_container_by_id(widget_id)
+            .widget
+            .borrow_mut()
+            .get_config()
+            .is_hidden()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let document = LayoutFormat::Ron.read(DOCUMENT).unwrap();
+        let ron = LayoutFormat::Ron.write(&document).unwrap();
+        let json = LayoutFormat::Json.write(&document).unwrap();
+        let from_ron = LayoutFormat::Ron.read(&ron).unwrap();
+        let from_json = LayoutFormat::Json.read(&json).unwrap();
+
+        assert_eq!(LayoutFormat::Ron.write(&from_ron).unwrap(), ron);
+        assert_eq!(LayoutFormat::Ron.write(&from_json).unwrap(), ron);
+        assert_eq!(LayoutFormat::Json.write(&from_ron).unwrap(), json);
+
+        let widget = &from_json.widgets[0];
+        let items = &from_json.layouts[0].items;
+
+        assert_eq!(widget.widget_type.as_deref(), Some("base"));
+        assert_eq!(widget.parent.as_deref(), Some("base"));
+        assert_eq!(widget.config.len(), 2);
+        assert_eq!(from_json.layouts[0].bounds, Some((0, 0, 400, 50)));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].widget.as_deref(), Some("panel"));
+        assert!(items[1].widget.is_none());
+        assert_eq!(items[1].size, LayoutSize::Fixed(20));
+        assert_eq!(
+            items[1].alignment,
+            (LayoutAlignment::Start, LayoutAlignment::Center)
+        );
+    }
+
+    #[test]
+    fn test_cyclic_parent() {
+        let mut widgets = widget_cache();
+        let result = import(
+            r#"(widgets: [
+                (name: "first", parent: "second", hidden: true),
+                (name: "second", parent: "first"),
+            ])"#,
+            &mut widgets,
+        );
+
+        assert!(matches!(result, Err(LayoutFileError::CyclicParent(_))));
+        assert_eq!(widgets.get_container_by_id(1).get_parent_id(), 0);
+        assert_eq!(widgets.get_container_by_id(2).get_parent_id(), 0);
+        assert!(!is_hidden(&mut widgets, 1));
+    }
+
+    #[test]
+    fn test_unknown_config_key() {
+        let mut widgets = widget_cache();
+        let result = import(
+            r#"(widgets: [
+                (name: "first", hidden: true),
+                (name: "second", config: { "CONFIG_UNKNOWN": Numeric(1) }),
+            ])"#,
+            &mut widgets,
+        );
+
+        assert!(matches!(result, Err(LayoutFileError::Invalid(_))));
+        assert!(!is_hidden(&mut widgets, 1));
+    }
+
+    #[test]
+    fn test_unknown_layout() {
+        let mut widgets = widget_cache();
+        let result = import(
+            r#"(
+                widgets: [(name: "first", hidden: true), (name: "third", widget_type: "base")],
+                layouts: [(id: 3)],
+            )"#,
+            &mut widgets,
+        );
+
+        assert_eq!(result, Err(LayoutFileError::UnknownLayout(3)));
+        assert!(!is_hidden(&mut widgets, 1));
+        assert_eq!(widgets.get_widget_id_by_name("third"), None);
+        assert_eq!(widgets.borrow_cache().len(), 3);
+    }
+}
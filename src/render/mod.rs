@@ -53,6 +53,56 @@ pub fn inverse_color(color: Color) -> Color {
     Color::RGBA(255 - color.r, 255 - color.g, 255 - color.b, color.a)
 }
 
+/// Converts a color in the HSV color space to an opaque `Color`.  The `hue` is specified in degrees,
+/// and wraps around at `360.0`; the `saturation` and `value` range from `0.0` to `1.0`, and are
+/// clamped to that range.
+pub fn hsv_to_color(hue: f64, saturation: f64, value: f64) -> Color {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let saturation = saturation.clamp(0.0, 1.0);
+    let value = value.clamp(0.0, 1.0);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+
+    Color::RGB(
+        ((r + m) * 255.0).round() as u8,
+        ((g + m) * 255.0).round() as u8,
+        ((b + m) * 255.0).round() as u8,
+    )
+}
+
+/// Converts a `Color` to the HSV color space, returning its hue in degrees from `0.0` up to
+/// `360.0`, and its saturation and value from `0.0` to `1.0`.  The alpha channel is ignored.  Grays
+/// have a hue and saturation of `0.0`.
+pub fn color_to_hsv(color: Color) -> (f64, f64, f64) {
+    let r = color.r as f64 / 255.0;
+    let g = color.g as f64 / 255.0;
+    let b = color.b as f64 / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    (hue, saturation, max)
+}
+
 /// This is a store used by `Widget`s for drawing against.  Once the drawing is complete, the
 /// `Texture` stored within is used for blitting to the screen.
 pub mod texture_store;
@@ -88,3 +138,94 @@ pub mod layout_cache;
 
 /// This is a caching object that stores Textures for fonts and images.
 pub mod texture_cache;
+
+/// This is a `Theme` definition, which stores the colors applied to `Widget`s, and the appearance
+/// of the focus indicator.
+pub mod theme;
+
+/// This is the `InputSettings` definition, which stores keyboard repeat and caret blink rates
+/// shared by `Widget`s.
+pub mod input_settings;
+
+/// This is the `Inspector`, a runtime debugging overlay that displays and edits information about
+/// the `Widget`s managed by the `Engine`.
+pub mod inspector;
+
+/// This is the clock source used by the `Engine` and `Widget`s, which can be replaced by a
+/// `MockClock` for deterministic testing.
+pub mod clock;
+
+/// This is the `FrameStats` definition, which contains performance information gathered by the
+/// `Engine` for each frame.
+pub mod frame_stats;
+
+/// This is the texture memory accounting library, which estimates the GPU memory used by `Texture`s
+/// held by `Widget`s and the `TextureCache`.
+pub mod texture_memory;
+
+/// This is the `WidgetName` definition, an interned name used to look up `Widget`s without
+/// allocating.
+pub mod widget_name;
+
+/// This is the `WidgetHandle` definition, a generational reference to a `Widget` that detects when
+/// the `Widget` it refers to has been removed.
+pub mod widget_handle;
+
+/// This is the `Layer` definition, which identifies the rendering layer a `Widget` is drawn on.
+pub mod layer;
+
+/// This is the post-process effects library, which applies effects such as blurring, glowing and
+/// grayscale conversion to the cached textures of `Widget`s.
+pub mod effects;
+
+/// This is the skinning library, which defines the `NinePatch` images that a `Theme` can supply to
+/// draw `Widget`s with a custom appearance.
+pub mod skin;
+
+/// This is the `DesignMode` definition, which allows `Widget`s to be moved and resized with the mouse
+/// at runtime.
+pub mod design_mode;
+
+/// This is the layout file library, which exports the `Widget` tree and `Layout`s to RON or JSON,
+/// and imports them back.
+pub mod layout_file;
+
+/// This is the display library, which enumerates the connected displays, and places windows on
+/// them.
+pub mod display;
+
+/// This is the window shape library, which creates borderless, shaped windows whose visible area
+/// follows the `Widget`s drawn.
+pub mod window_shape;
+
+/// This is the tooltip library, which shows the tooltip text of the `Widget` the mouse is resting
+/// over.
+pub mod tooltip;
+
+/// This is the modal dialog library, which blocks interaction with the rest of the UI while a
+/// dialog is shown.
+pub mod modal;
+
+/// This is the mouse cursor library, which shows the mouse cursor requested by the `Widget` under
+/// the mouse.
+pub mod cursor;
+
+/// This is the GIF decoder, which decodes every frame of an animated GIF, so that it can be played
+/// by an `AnimatedImageWidget`.
+pub mod gif;
+
+/// This is the timer library, which fires callbacks after a delay, or repeatedly at an interval,
+/// from the `Engine`'s main loop.
+pub mod timer;
+
+/// This is the event sender library, which allows other threads to post messages to the `Engine`
+/// to update the `Widget`s.
+pub mod event_sender;
+
+/// This is the custom event library, which delivers application-defined events pushed to the SDL2
+/// event queue to the `Engine` and its `Widget`s.
+pub mod custom_event;
+
+/// This is the event filter library, which allows applications to observe, replace, or discard
+/// `Event`s before the `Engine` handles them.
+pub mod event_filter;
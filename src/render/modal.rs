@@ -0,0 +1,49 @@
+// Pushrod Rendering Library
+// Modal Dialogs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::engine::Engine;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::CONFIG_DIALOG_RESULT;
+use crate::render::widget_handle::WidgetHandle;
+use sdl2::pixels::Color;
+
+/// This is the default `Color` drawn over the rest of the UI while a modal dialog is shown.
+pub const DEFAULT_MODAL_DIM_COLOR: Color = Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 128,
+};
+
+/// This is the callback type that is used when a modal dialog is closed.  It receives the `Engine`,
+/// the `WidgetHandle` of the root `Widget` of the dialog, and the result the dialog was closed with.
+pub type DialogClosedCallbackType = Option<Box<dyn FnMut(&mut Engine, WidgetHandle, i32)>>;
+
+/// Requests that the modal dialog whose root `Widget` is `dialog_id` be closed with `result`.  This
+/// is intended to be called from the callbacks of `Widget`s inside of the dialog, such as the
+/// `on_click` callback of an "OK" button, which have no access to the `Engine`.  The `Engine` closes
+/// the dialog, and triggers its `on_dialog_closed` callback, at the start of the next frame.
+///
+/// If the callback belongs to the root `Widget` of the dialog itself, set `CONFIG_DIALOG_RESULT`
+/// on that `Widget` directly instead, as it is already borrowed while its callback runs.
+pub fn close_dialog(widgets: &[WidgetContainer], dialog_id: i32, result: i32) {
+    if let Some(container) = widgets.get(dialog_id as usize) {
+        if let Ok(mut widget) = container.widget.try_borrow_mut() {
+            widget
+                .get_config()
+                .set_numeric(CONFIG_DIALOG_RESULT, result);
+        }
+    }
+}
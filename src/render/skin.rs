@@ -0,0 +1,219 @@
+// Pushrod Rendering Library
+// Nine-Patch Skinning
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+use std::collections::HashMap;
+
+/// This identifies the part of a `Widget` that a `NinePatch` image is drawn for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SkinPart {
+    /// The background of a `PushButtonWidget`.
+    Button,
+
+    /// The background of a `BaseWidget`, used as a panel.
+    Panel,
+
+    /// The track of a `SliderWidget`, drawn across its entire bounds.
+    SliderTrack,
+
+    /// The draggable thumb of a `SliderWidget`.
+    SliderThumb,
+
+    /// The background of a `ProgressWidget`.
+    ProgressTrack,
+
+    /// The filled portion of a `ProgressWidget`.
+    ProgressFill,
+
+    /// The track of a `ScrollBarWidget`, drawn across its entire bounds.
+    ScrollBarTrack,
+
+    /// The draggable thumb of a `ScrollBarWidget`.
+    ScrollBarThumb,
+}
+
+/// This identifies the interaction state of a `Widget` that a `NinePatch` image is drawn for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SkinState {
+    /// The `Widget` is enabled, and not being interacted with.
+    Normal,
+
+    /// The mouse is over the `Widget`.
+    Hover,
+
+    /// The `Widget` is being pressed.
+    Pressed,
+
+    /// The `Widget` is disabled.
+    Disabled,
+}
+
+/// This is the implementation of the `SkinState`.
+impl SkinState {
+    /// Determines the `SkinState` of a `Widget`, given whether it is enabled, hovered and pressed.
+    pub fn from_flags(enabled: bool, hovered: bool, pressed: bool) -> Self {
+        if !enabled {
+            SkinState::Disabled
+        } else if pressed {
+            SkinState::Pressed
+        } else if hovered {
+            SkinState::Hover
+        } else {
+            SkinState::Normal
+        }
+    }
+}
+
+/// This is a nine-patch image: an image that is split into a 3x3 grid by four insets.  When drawn,
+/// the corners are copied unscaled, the edges are stretched along one axis, and the center is
+/// stretched along both axes, so that the image can be drawn at any size without distorting its
+/// borders.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NinePatch {
+    /// The path to the image, loaded through the `TextureCache`.
+    pub image: String,
+
+    /// The width of the left column, in pixels.
+    pub left: u32,
+
+    /// The height of the top row, in pixels.
+    pub top: u32,
+
+    /// The width of the right column, in pixels.
+    pub right: u32,
+
+    /// The height of the bottom row, in pixels.
+    pub bottom: u32,
+}
+
+/// This is the implementation of the `NinePatch`.
+impl NinePatch {
+    /// Creates a new `NinePatch` from an image path and its `left`, `top`, `right` and `bottom`
+    /// insets.
+    pub fn new(image: &str, left: u32, top: u32, right: u32, bottom: u32) -> Self {
+        Self {
+            image: String::from(image),
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Creates a new `NinePatch` with the same inset on all four sides.
+    pub fn uniform(image: &str, inset: u32) -> Self {
+        Self::new(image, inset, inset, inset, inset)
+    }
+
+    /// Draws the `NinePatch` to a `Canvas`, stretched to fill `dest`.  `texture` is the loaded
+    /// image, as retrieved from the `TextureCache`.  If `dest` is smaller than the insets, the
+    /// corners are scaled down to fit.
+    pub fn draw(&self, c: &mut Canvas<Window>, texture: &Texture, dest: Rect) {
+        let query = texture.query();
+        let src_columns = Self::spans(query.width, self.left, self.right);
+        let src_rows = Self::spans(query.height, self.top, self.bottom);
+        let dest_columns = Self::spans(dest.width(), self.left, self.right);
+        let dest_rows = Self::spans(dest.height(), self.top, self.bottom);
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let (src_x, src_w) = src_columns[column];
+                let (src_y, src_h) = src_rows[row];
+                let (dest_x, dest_w) = dest_columns[column];
+                let (dest_y, dest_h) = dest_rows[row];
+
+                if src_w == 0 || src_h == 0 || dest_w == 0 || dest_h == 0 {
+                    continue;
+                }
+
+                c.copy(
+                    texture,
+                    Rect::new(src_x as i32, src_y as i32, src_w, src_h),
+                    Rect::new(
+                        dest.x() + dest_x as i32,
+                        dest.y() + dest_y as i32,
+                        dest_w,
+                        dest_h,
+                    ),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    /// Splits a length into the start and length of the three spans of a `NinePatch`, scaling the
+    /// insets down if they do not fit.
+    fn spans(length: u32, start_inset: u32, end_inset: u32) -> [(u32, u32); 3] {
+        let (start_inset, end_inset) = if start_inset + end_inset > length {
+            let total = (start_inset + end_inset).max(1);
+            let start = start_inset * length / total;
+
+            (start, length - start)
+        } else {
+            (start_inset, end_inset)
+        };
+        let middle = length - start_inset - end_inset;
+
+        [
+            (0, start_inset),
+            (start_inset, middle),
+            (start_inset + middle, end_inset),
+        ]
+    }
+}
+
+/// This is the storage object for a `Skin`, a set of `NinePatch` images for each `SkinPart` and
+/// `SkinState`.  A `Skin` is supplied as part of a `Theme`, and is applied to each `Widget` that
+/// supports skinning through `Widget::apply_skin`.  `Widget`s draw their standard appearance for
+/// any part that has no image.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Skin {
+    patches: HashMap<(SkinPart, SkinState), NinePatch>,
+}
+
+/// This is the implementation of the `Skin`.
+impl Skin {
+    /// Creates a new, empty `Skin`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `NinePatch` image drawn for a `SkinPart` in a `SkinState`.
+    pub fn set_patch(&mut self, part: SkinPart, state: SkinState, patch: NinePatch) {
+        self.patches.insert((part, state), patch);
+    }
+
+    /// Sets the `NinePatch` image drawn for a `SkinPart` in a `SkinState`, returning the `Skin`, so
+    /// that calls can be chained.
+    pub fn with_patch(mut self, part: SkinPart, state: SkinState, patch: NinePatch) -> Self {
+        self.set_patch(part, state, patch);
+        self
+    }
+
+    /// Retrieves the `NinePatch` image for a `SkinPart` in a `SkinState`.  If the state has no
+    /// image, the image for `SkinState::Normal` is returned instead.
+    pub fn get_patch(&self, part: SkinPart, state: SkinState) -> Option<&NinePatch> {
+        self.patches
+            .get(&(part, state))
+            .or_else(|| self.patches.get(&(part, SkinState::Normal)))
+    }
+
+    /// Indicates whether or not the `Skin` contains any images for a `SkinPart`.
+    pub fn has_part(&self, part: SkinPart) -> bool {
+        self.patches.keys().any(|(x, _)| *x == part)
+    }
+}
@@ -13,28 +13,72 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::render::frame_stats::{record_texture_created, record_texture_destroyed};
+use crate::render::gif::decode_gif;
+use crate::render::texture_memory::texture_bytes;
+use crate::render::{Size, SIZE_HEIGHT, SIZE_WIDTH};
 use sdl2::image::LoadTexture;
-use sdl2::pixels::Color;
-use sdl2::render::{Canvas, Texture, TextureQuery};
-use sdl2::ttf::{FontStyle, Sdl2TtfContext};
-use sdl2::video::Window;
-use std::collections::HashMap;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, Canvas, Texture, TextureCreator, TextureQuery};
+use sdl2::surface::Surface;
+use sdl2::ttf::{Font, FontStyle, Sdl2TtfContext};
+use sdl2::video::{Window, WindowContext};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+/// This is the widest a sheet of decoded GIF frames is allowed to grow before the frames are
+/// wrapped onto another row.
+const MAX_FRAME_SHEET_WIDTH: u32 = 4096;
+
+/// This is the suffix added to the name of a GIF to form the name that its sheet of frames is
+/// cached under.
+pub const FRAME_SHEET_SUFFIX: &str = "#frames";
+
+/// This is a single frame of an animated image: the area of the cached texture that holds the
+/// frame, and the number of milliseconds the frame is shown for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageFrame {
+    pub source: Rect,
+    pub delay: u32,
+}
+
+/// This is a cached image, along with the information used to decide when it is evicted.
+struct CachedImage {
+    texture: Texture,
+    memory_size: u64,
+    last_used: u64,
+    last_frame: u64,
+    frames: Vec<ImageFrame>,
+}
+
 /// This is the structure for the `TextureCache`.
 pub struct TextureCache {
-    images: HashMap<String, Texture>,
+    images: HashMap<String, CachedImage>,
+    pinned: HashSet<String>,
+    memory_budget: u64,
+    use_counter: u64,
+    frame: u64,
     ttf_context: Sdl2TtfContext,
 }
 
 /// This is a `Texture` cache object that is used by the `WidgetCache`.  This is responsible for loading
 /// in images into a cache in memory so that it can be copied multiple times as required by the
 /// application.
+///
+/// If a memory budget is set with `set_memory_budget`, the least recently used images are evicted
+/// whenever loading an image takes the cache over budget.  Images that are pinned, or that have been
+/// used during the current frame, are never evicted.  Evicted images are reloaded automatically the
+/// next time they are requested.
 impl TextureCache {
     /// Creates a new `TextureCache`.
     pub fn new() -> Self {
         Self {
             images: HashMap::new(),
+            pinned: HashSet::new(),
+            memory_budget: 0,
+            use_counter: 0,
+            frame: 0,
             ttf_context: sdl2::ttf::init().map_err(|e| e.to_string()).unwrap(),
         }
     }
@@ -47,11 +91,253 @@ impl TextureCache {
     /// Loads an image based on the `image_name`, which is the filename for the image to load.
     /// Returns a reference to the `Texture` that was loaded.
     pub fn get_image(&mut self, c: &mut Canvas<Window>, image_name: String) -> &Texture {
-        self.images.entry(image_name.clone()).or_insert({
-            c.texture_creator()
+        self.use_counter += 1;
+
+        if !self.images.contains_key(&image_name) {
+            let texture = c
+                .texture_creator()
                 .load_texture(Path::new(&image_name))
-                .unwrap()
-        })
+                .unwrap();
+
+            self.insert_image(image_name.clone(), texture, vec![]);
+        }
+
+        &self.touch_image(&image_name).texture
+    }
+
+    /// Loads a sprite sheet based on the `image_name`, and splits it into `frame_count` frames of
+    /// `frame_size`, read from left to right, then top to bottom.  Each frame is shown for
+    /// `1000 / fps` milliseconds.  Frames that do not fit within the image are left out.  Returns a
+    /// reference to the `Texture` of the whole sheet, along with its frames.
+    pub fn get_sprite_frames(
+        &mut self,
+        c: &mut Canvas<Window>,
+        image_name: String,
+        frame_size: Size,
+        frame_count: u32,
+        fps: u32,
+    ) -> (&Texture, Vec<ImageFrame>) {
+        let texture = self.get_image(c, image_name);
+        let TextureQuery { width, height, .. } = texture.query();
+        let frame_width = frame_size[SIZE_WIDTH].max(1);
+        let frame_height = frame_size[SIZE_HEIGHT].max(1);
+        let columns = width / frame_width;
+        let rows = height / frame_height;
+        let delay = 1000 / fps.max(1);
+        let frames = (0..frame_count.min(columns * rows))
+            .map(|index| ImageFrame {
+                source: Rect::new(
+                    ((index % columns) * frame_width) as i32,
+                    ((index / columns) * frame_height) as i32,
+                    frame_width,
+                    frame_height,
+                ),
+                delay,
+            })
+            .collect();
+
+        (texture, frames)
+    }
+
+    /// Loads an animated GIF based on the `image_name`, decoding all of its frames onto a single
+    /// sheet, which is cached under the `image_name` followed by `FRAME_SHEET_SUFFIX`.  Returns a
+    /// reference to the `Texture` of the sheet, along with the area and delay of each frame.
+    /// Unlike `get_image`, which only shows the first frame of a GIF, every frame is decoded.
+    /// Returns an error if the file cannot be read, is not a valid GIF, or has frames with no
+    /// width or height.
+    pub fn get_gif_frames(
+        &mut self,
+        c: &mut Canvas<Window>,
+        image_name: String,
+    ) -> Result<(&Texture, Vec<ImageFrame>), String> {
+        let sheet_name = format!("{}{}", image_name, FRAME_SHEET_SUFFIX);
+
+        self.use_counter += 1;
+
+        if !self.images.contains_key(&sheet_name) {
+            let data = std::fs::read(&image_name).map_err(|e| e.to_string())?;
+            let animation = decode_gif(&data)?;
+
+            if animation.width == 0 || animation.height == 0 {
+                return Err(String::from("GIF frames have no width or height"));
+            }
+
+            let frame_width = animation.width;
+            let frame_height = animation.height;
+            let row_bytes = (frame_width * 4) as usize;
+
+            if animation
+                .frames
+                .iter()
+                .any(|frame| frame.pixels.len() != row_bytes * frame_height as usize)
+            {
+                return Err(String::from("GIF frame does not match the size of the GIF"));
+            }
+
+            let frame_count = animation.frames.len() as u32;
+            let columns = (MAX_FRAME_SHEET_WIDTH / frame_width)
+                .max(1)
+                .min(frame_count);
+            let rows = frame_count.div_ceil(columns);
+            let sheet_width = columns * frame_width;
+            let sheet_height = rows
+                .checked_mul(frame_height)
+                .ok_or_else(|| String::from("GIF has too many frames"))?;
+            let mut pixels = vec![0u8; sheet_width as usize * sheet_height as usize * 4];
+            let mut frames = Vec::new();
+
+            for (index, frame) in animation.frames.iter().enumerate() {
+                let x = (index as u32 % columns) * frame_width;
+                let y = (index as u32 / columns) * frame_height;
+
+                for (row, source) in frame.pixels.chunks(row_bytes).enumerate() {
+                    let offset = ((y as usize + row) * sheet_width as usize + x as usize) * 4;
+
+                    pixels[offset..offset + source.len()].copy_from_slice(source);
+                }
+
+                frames.push(ImageFrame {
+                    source: Rect::new(x as i32, y as i32, frame_width, frame_height),
+                    delay: frame.delay,
+                });
+            }
+
+            let surface = Surface::from_data(
+                &mut pixels,
+                sheet_width,
+                sheet_height,
+                sheet_width * 4,
+                PixelFormatEnum::RGBA32,
+            )?;
+            let mut texture = c
+                .texture_creator()
+                .create_texture_from_surface(&surface)
+                .map_err(|e| e.to_string())?;
+
+            texture.set_blend_mode(BlendMode::Blend);
+            self.insert_image(sheet_name.clone(), texture, frames);
+        }
+
+        let image = self.touch_image(&sheet_name);
+
+        Ok((&image.texture, image.frames.clone()))
+    }
+
+    /// Adds a newly loaded image to the cache, evicting other images if the cache goes over budget.
+    fn insert_image(&mut self, image_name: String, texture: Texture, frames: Vec<ImageFrame>) {
+        let TextureQuery {
+            width,
+            height,
+            format,
+            ..
+        } = texture.query();
+
+        record_texture_created();
+        self.images.insert(
+            image_name.clone(),
+            CachedImage {
+                texture,
+                memory_size: texture_bytes(width, height, format),
+                last_used: self.use_counter,
+                last_frame: self.frame,
+                frames,
+            },
+        );
+        self.evict(&image_name);
+    }
+
+    /// Marks a cached image as used, so that it is not evicted during the current frame.
+    fn touch_image(&mut self, image_name: &str) -> &CachedImage {
+        let image = self.images.get_mut(image_name).unwrap();
+
+        image.last_used = self.use_counter;
+        image.last_frame = self.frame;
+
+        image
+    }
+
+    /// Sets the memory budget in bytes for images stored in this cache.  Least recently used images
+    /// are evicted immediately if the cache is over the new budget.  A budget of `0` (the default)
+    /// allows the cache to grow without bound.
+    pub fn set_memory_budget(&mut self, bytes: u64) {
+        self.memory_budget = bytes;
+        self.evict("");
+    }
+
+    /// Retrieves the memory budget in bytes for images stored in this cache.
+    pub fn get_memory_budget(&self) -> u64 {
+        self.memory_budget
+    }
+
+    /// Pins an image, so that it is never evicted from the cache.
+    pub fn pin_image(&mut self, image_name: String) {
+        self.pinned.insert(image_name);
+    }
+
+    /// Unpins an image, allowing it to be evicted from the cache.
+    pub fn unpin_image(&mut self, image_name: &str) {
+        self.pinned.remove(image_name);
+    }
+
+    /// Indicates whether or not an image is currently loaded in the cache.
+    pub fn contains_image(&self, image_name: &str) -> bool {
+        self.images.contains_key(image_name)
+    }
+
+    /// Marks the start of a new frame.  Images used during the current frame are considered in use,
+    /// and are not evicted.  This is called by the `WidgetCache` before each draw.
+    pub fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Retrieves the estimated number of bytes of GPU memory used by all images stored in this
+    /// cache.
+    pub fn get_memory_size(&self) -> u64 {
+        self.images.values().map(|image| image.memory_size).sum()
+    }
+
+    /// Retrieves the estimated number of bytes of GPU memory used by each image stored in this cache,
+    /// keyed by image name.
+    pub fn get_image_memory_sizes(&self) -> HashMap<String, u64> {
+        self.images
+            .iter()
+            .map(|(name, image)| (name.clone(), image.memory_size))
+            .collect()
+    }
+
+    fn evict(&mut self, keep: &str) {
+        if self.memory_budget == 0 {
+            return;
+        }
+
+        while self.get_memory_size() > self.memory_budget {
+            let frame = self.frame;
+            let candidate = self
+                .images
+                .iter()
+                .filter(|(name, image)| {
+                    name.as_str() != keep
+                        && !self.pinned.contains(name.as_str())
+                        && image.last_frame != frame
+                })
+                .min_by_key(|(_, image)| image.last_used)
+                .map(|(name, _)| name.clone());
+
+            match candidate {
+                Some(name) => {
+                    if let Some(image) = self.images.remove(&name) {
+                        // Safety: cached textures are only lent out by reference, so nothing
+                        // else refers to an image once it has been removed from the cache.
+                        unsafe {
+                            image.texture.destroy();
+                        }
+                    }
+
+                    record_texture_destroyed();
+                }
+                None => break,
+            }
+        }
     }
 
     /// Renders text, given the font name, size, style, color, string, and max width.  Transfers
@@ -92,6 +378,33 @@ impl TextureCache {
     }
 }
 
+/// Renders a single line of text with `font` to a `Texture`, returning `None` if the text is empty.
+/// Unlike `TextureCache::render_text`, the font is loaded by the caller, so that it can also be used
+/// to measure the text.
+pub fn render_text_line(
+    font: &Font,
+    texture_creator: &TextureCreator<WindowContext>,
+    text: &str,
+    color: Color,
+) -> Option<Texture> {
+    if text.is_empty() {
+        return None;
+    }
+
+    let surface = font
+        .render(text)
+        .blended(color)
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+    Some(
+        texture_creator
+            .create_texture_from_surface(&surface)
+            .map_err(|e| e.to_string())
+            .unwrap(),
+    )
+}
+
 impl Default for TextureCache {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,55 @@
+// Pushrod Rendering Library
+// Texture Memory Accounting
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sdl2::pixels::PixelFormatEnum;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static WIDGET_TEXTURE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// This is the callback type that is used when the texture memory budget set with
+/// `Engine::set_texture_memory_budget` is exceeded.  It receives the estimated number of bytes in
+/// use, followed by the budget in bytes.
+pub type TextureBudgetCallbackType = Option<Box<dyn FnMut(u64, u64)>>;
+
+/// Returns the estimated number of bytes of GPU memory used by a `Texture` of the given size and
+/// `PixelFormatEnum`.  Formats whose size cannot be determined are estimated at 4 bytes per pixel.
+pub fn texture_bytes(width: u32, height: u32, format: PixelFormatEnum) -> u64 {
+    let pixels = width as usize * height as usize;
+
+    match format {
+        PixelFormatEnum::Unknown
+        | PixelFormatEnum::Index1LSB
+        | PixelFormatEnum::Index1MSB
+        | PixelFormatEnum::Index4LSB
+        | PixelFormatEnum::Index4MSB => (pixels * 4) as u64,
+        _ => format.byte_size_of_pixels(pixels) as u64,
+    }
+}
+
+/// Returns the estimated number of bytes of GPU memory currently held by all `Widget` backing
+/// textures stored in `TextureStore`s.
+pub fn widget_texture_memory() -> u64 {
+    WIDGET_TEXTURE_BYTES.load(Ordering::Relaxed)
+}
+
+/// Records the allocation of a `Widget` backing texture.  Called by the `TextureStore`.
+pub(crate) fn record_widget_texture_allocated(bytes: u64) {
+    WIDGET_TEXTURE_BYTES.fetch_add(bytes, Ordering::Relaxed);
+}
+
+/// Records the release of a `Widget` backing texture.  Called by the `TextureStore`.
+pub(crate) fn record_widget_texture_released(bytes: u64) {
+    WIDGET_TEXTURE_BYTES.fetch_sub(bytes, Ordering::Relaxed);
+}
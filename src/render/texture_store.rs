@@ -13,7 +13,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sdl2::render::{Canvas, Texture};
+use crate::render::frame_stats::{record_texture_created, record_texture_destroyed};
+use crate::render::texture_memory::{
+    record_widget_texture_allocated, record_widget_texture_released, texture_bytes,
+};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::render::{BlendMode, Canvas, Texture};
 use sdl2::video::Window;
 
 /// This is a store used by the `TextureStore`.
@@ -22,6 +27,8 @@ pub struct TextureStore {
     store: Option<Texture>,
     width: u32,
     height: u32,
+    memory_size: u64,
+    blended: bool,
 }
 
 /// This is a `TextureStore` that is used by `Widget`s to draw against.  It serves as a GPU-based
@@ -49,7 +56,7 @@ impl TextureStore {
     /// Retrieves a `Option<&Texture>` object for the `Texture` object store.  Use this as a shortcut
     /// to the `Widget`'s return values (see `BaseWidget` for reference.)
     pub fn get_optional_ref(&mut self) -> Option<&Texture> {
-        Some(self.store.as_ref().unwrap())
+        self.store.as_ref()
     }
 
     /// This is used to create a new `Texture` object that can be drawn against.  If the `Widget` is
@@ -58,11 +65,79 @@ impl TextureStore {
     /// function (ie. the width changes, height changes, or the store is lost), it is regenerated.
     pub fn create_or_resize_texture(&mut self, c: &mut Canvas<Window>, width: u32, height: u32) {
         if self.store.is_none() || self.width != width || self.height != height {
+            self.release();
+
+            let mut texture = c.create_texture_target(None, width, height).unwrap();
+
+            if self.blended {
+                texture.set_blend_mode(BlendMode::Blend);
+            }
+
             self.width = width;
             self.height = height;
-            self.store = Some(c.create_texture_target(None, width, height).unwrap());
+            self.memory_size = texture_bytes(width, height, texture.query().format);
+            self.store = Some(texture);
+            record_texture_created();
+            record_widget_texture_allocated(self.memory_size);
 
             eprintln!("Created texture: size={}x{}", width, height);
         }
     }
+
+    /// Sets whether or not the stored `Texture` is alpha blended when it is copied, allowing
+    /// translucent and transparent pixels to show what is drawn behind it.  This applies to the
+    /// current `Texture`, and any `Texture` created afterwards.  Textures are not blended by default.
+    pub fn set_blended(&mut self, blended: bool) {
+        self.blended = blended;
+
+        if let Some(texture) = self.store.as_mut() {
+            texture.set_blend_mode(if blended {
+                BlendMode::Blend
+            } else {
+                BlendMode::None
+            });
+        }
+    }
+
+    /// Retrieves the width of the stored `Texture`.
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Retrieves the height of the stored `Texture`.
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Retrieves the `PixelFormatEnum` of the stored `Texture`, if one has been created.
+    pub fn get_format(&self) -> Option<PixelFormatEnum> {
+        self.store.as_ref().map(|texture| texture.query().format)
+    }
+
+    /// Retrieves the estimated number of bytes of GPU memory used by the stored `Texture`.
+    pub fn get_memory_size(&self) -> u64 {
+        self.memory_size
+    }
+
+    /// Releases the stored `Texture`, freeing its GPU memory.  The `Texture` is recreated the next
+    /// time `create_or_resize_texture` is called.
+    pub fn release(&mut self) {
+        if let Some(texture) = self.store.take() {
+            // Safety: the stored `Texture` is only ever lent out by reference, so nothing else
+            // refers to it once it has been taken out of the store.
+            unsafe {
+                texture.destroy();
+            }
+
+            record_texture_destroyed();
+            record_widget_texture_released(self.memory_size);
+            self.memory_size = 0;
+        }
+    }
+}
+
+impl Drop for TextureStore {
+    fn drop(&mut self) {
+        self.release();
+    }
 }
@@ -0,0 +1,156 @@
+// Pushrod Rendering Library
+// Theme Definitions
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::skin::Skin;
+use crate::render::widget::Widget;
+use crate::render::widget_config::{
+    WidgetConfig, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_HOVER, CONFIG_COLOR_INVALID,
+    CONFIG_COLOR_SECONDARY, CONFIG_COLOR_SELECTED, CONFIG_COLOR_TEXT, CONFIG_COLOR_THUMB_HOVER,
+    CONFIG_COLOR_THUMB_PRESSED, CONFIG_COLOR_TRACK,
+};
+use sdl2::pixels::Color;
+
+/// This enum controls how the focus indicator is drawn around the currently focused `Widget`.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum FocusStyle {
+    /// Draws a solid outline around the bounds of the `Widget`, `focus_width` pixels thick.
+    Outline,
+
+    /// Draws a soft glow around the bounds of the `Widget`, fading out over `focus_width` pixels.
+    Glow,
+}
+
+/// This is the storage object for a `Theme`.  A `Theme` contains the set of colors that are applied
+/// to each `Widget`'s `WidgetConfig`, along with the settings used by the `Engine` to draw the focus
+/// indicator around the currently focused `Widget`.
+#[derive(Clone, Debug)]
+pub struct Theme {
+    /// Color applied to `CONFIG_COLOR_BASE`.
+    pub base_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_HOVER`.
+    pub hover_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_BORDER`.
+    pub border_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_TEXT`.
+    pub text_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_SELECTED`.
+    pub selected_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_SECONDARY`.
+    pub secondary_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_TRACK`, the track of sliders and scrollbars.
+    pub track_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_THUMB_HOVER`, the thumb of a hovered slider or scrollbar.
+    pub thumb_hover_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_THUMB_PRESSED`, the thumb of a slider or scrollbar that is
+    /// being dragged.
+    pub thumb_pressed_color: Color,
+
+    /// Color applied to `CONFIG_COLOR_INVALID`, the border of an input whose value is invalid.
+    pub invalid_color: Color,
+
+    /// Color of the focus indicator drawn around the focused `Widget`.
+    pub focus_color: Color,
+
+    /// Thickness of the focus indicator in pixels.
+    pub focus_width: u32,
+
+    /// The `FocusStyle` used to draw the focus indicator.
+    pub focus_style: FocusStyle,
+
+    /// The optional `Skin` of `NinePatch` images drawn by `Widget`s that support skinning, in place
+    /// of their standard appearance.
+    pub skin: Option<Skin>,
+}
+
+/// This is the implementation of the `Theme`.  Use `Theme::default()` for the standard black-on-white
+/// appearance, or `Theme::high_contrast()` for an accessibility-friendly appearance.
+impl Theme {
+    /// Creates the high-contrast `Theme`: white and yellow on black, with a thick yellow focus
+    /// outline that is visible regardless of the `Widget` type being focused.
+    pub fn high_contrast() -> Self {
+        Self {
+            base_color: Color::RGB(0, 0, 0),
+            hover_color: Color::RGB(0, 0, 128),
+            border_color: Color::RGB(255, 255, 255),
+            text_color: Color::RGB(255, 255, 255),
+            selected_color: Color::RGB(0, 255, 255),
+            secondary_color: Color::RGB(255, 255, 0),
+            track_color: Color::RGB(255, 255, 255),
+            thumb_hover_color: Color::RGB(0, 0, 128),
+            thumb_pressed_color: Color::RGB(0, 255, 255),
+            invalid_color: Color::RGB(255, 64, 64),
+            focus_color: Color::RGB(255, 255, 0),
+            focus_width: 4,
+            focus_style: FocusStyle::Outline,
+            skin: None,
+        }
+    }
+
+    /// Applies the colors of this `Theme` to a `WidgetConfig`, invalidating it so that it is redrawn.
+    pub fn apply(&self, config: &mut WidgetConfig) {
+        config.set_color(CONFIG_COLOR_BASE, self.base_color);
+        config.set_color(CONFIG_COLOR_HOVER, self.hover_color);
+        config.set_color(CONFIG_COLOR_BORDER, self.border_color);
+        config.set_color(CONFIG_COLOR_TEXT, self.text_color);
+        config.set_color(CONFIG_COLOR_SELECTED, self.selected_color);
+        config.set_color(CONFIG_COLOR_SECONDARY, self.secondary_color);
+        config.set_color(CONFIG_COLOR_TRACK, self.track_color);
+        config.set_color(CONFIG_COLOR_THUMB_HOVER, self.thumb_hover_color);
+        config.set_color(CONFIG_COLOR_THUMB_PRESSED, self.thumb_pressed_color);
+        config.set_color(CONFIG_COLOR_INVALID, self.invalid_color);
+        config.set_invalidated(true);
+    }
+
+    /// Applies the colors of this `Theme` to a `Widget`'s `WidgetConfig`, along with its `Skin`.
+    pub fn apply_to_widget(&self, widget: &mut dyn Widget) {
+        self.apply(widget.get_config());
+        widget.apply_skin(self.skin.as_ref());
+    }
+
+    /// Sets the `Skin` of this `Theme`, returning the `Theme`, so that calls can be chained.
+    pub fn with_skin(mut self, skin: Skin) -> Self {
+        self.skin = Some(skin);
+        self
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            base_color: Color::RGB(255, 255, 255),
+            hover_color: Color::RGB(192, 192, 192),
+            border_color: Color::RGB(0, 0, 0),
+            text_color: Color::RGB(0, 0, 0),
+            selected_color: Color::RGB(0, 0, 0),
+            secondary_color: Color::RGB(0, 0, 255),
+            track_color: Color::RGB(192, 192, 192),
+            thumb_hover_color: Color::RGB(224, 224, 224),
+            thumb_pressed_color: Color::RGB(160, 160, 160),
+            invalid_color: Color::RGB(220, 0, 0),
+            focus_color: Color::RGB(0, 120, 215),
+            focus_width: 2,
+            focus_style: FocusStyle::Outline,
+            skin: None,
+        }
+    }
+}
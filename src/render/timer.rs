@@ -0,0 +1,112 @@
+// Pushrod Rendering Library
+// Timers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::clock;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget_cache::WidgetContainer;
+use std::time::Duration;
+
+/// This is the callback type that is used when a timer fires.  It borrows the `WidgetContainer` and
+/// `LayoutContainer` lists, so that `Widget`s can be modified as a result.
+pub type TimerCallbackType = Box<dyn FnMut(&[WidgetContainer], &[LayoutContainer])>;
+
+/// This is a handle to a timer added with `Engine::add_timer`, which is used to cancel it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimerHandle(u64);
+
+/// This is the storage object for a single timer.
+struct Timer {
+    handle: TimerHandle,
+    interval: u64,
+    repeating: bool,
+    fire_at: u64,
+    callback: TimerCallbackType,
+}
+
+/// This is the list of timers managed by the `Engine`.  Timers are checked once per frame, so they
+/// fire on the first frame after they are due, and are no more precise than the frame rate.  Time
+/// is measured with `clock::now_millis`, so timers can be tested with a `MockClock`.
+#[derive(Default)]
+pub struct Timers {
+    timers: Vec<Timer>,
+    next_id: u64,
+}
+
+/// This is the implementation of the `Timers` list.
+impl Timers {
+    /// Creates a new, empty list of timers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a timer that fires `duration` from now, and every `duration` after that if `repeating`
+    /// is set.  Returns the `TimerHandle` used to cancel it.  A repeating timer that falls behind,
+    /// such as when a frame takes longer than its interval, fires once and is rescheduled from the
+    /// current time, rather than firing repeatedly to catch up.
+    pub fn add<F>(&mut self, duration: Duration, repeating: bool, callback: F) -> TimerHandle
+    where
+        F: FnMut(&[WidgetContainer], &[LayoutContainer]) + 'static,
+    {
+        let handle = TimerHandle(self.next_id);
+        let interval = (duration.as_millis() as u64).max(1);
+
+        self.next_id += 1;
+        self.timers.push(Timer {
+            handle,
+            interval,
+            repeating,
+            fire_at: clock::now_millis() + interval,
+            callback: Box::new(callback),
+        });
+
+        handle
+    }
+
+    /// Cancels the timer referred to by `handle`.  Returns `false` if the timer has already fired,
+    /// and was not repeating, or has already been cancelled.
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        let count = self.timers.len();
+
+        self.timers.retain(|timer| timer.handle != handle);
+        self.timers.len() != count
+    }
+
+    /// Indicates whether or not the timer referred to by `handle` is still waiting to fire.
+    pub fn is_active(&self, handle: TimerHandle) -> bool {
+        self.timers.iter().any(|timer| timer.handle == handle)
+    }
+
+    /// Fires all of the timers that are due, in the order in which they were added.  Timers that are
+    /// not repeating are removed after they fire.
+    pub fn fire(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        let now = clock::now_millis();
+
+        self.timers.retain_mut(|timer| {
+            if timer.fire_at > now {
+                return true;
+            }
+
+            (timer.callback)(widgets, layouts);
+
+            timer.fire_at = if now - timer.fire_at < timer.interval {
+                timer.fire_at + timer.interval
+            } else {
+                now + timer.interval
+            };
+
+            timer.repeating
+        });
+    }
+}
@@ -0,0 +1,203 @@
+// Pushrod Rendering Library
+// Tooltips
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::clock;
+use crate::render::texture_cache::TextureCache;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::CONFIG_TOOLTIP_TEXT;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, TextureQuery};
+use sdl2::video::Window;
+use std::path::Path;
+
+/// This is the default number of milliseconds the mouse must rest over a `Widget` before its tooltip
+/// is shown.
+pub const DEFAULT_TOOLTIP_DELAY: u64 = 750;
+
+/// This is the offset from the mouse cursor to the top left-hand corner of a tooltip.
+const CURSOR_OFFSET: (i32, i32) = (12, 20);
+
+/// This is the number of pixels between the border of a tooltip and its text.
+const TOOLTIP_PADDING: u32 = 4;
+
+/// This is the storage object for the `Tooltip`.  It tracks the `Widget` the mouse is resting over,
+/// and how long it has been resting there.  The `Engine` owns a `Tooltip`, which it updates as the
+/// mouse moves, and draws above all `Widget`s.  `Widget`s show a tooltip by setting the
+/// `CONFIG_TOOLTIP_TEXT` configuration key; no other code is required.
+pub struct Tooltip {
+    /// Color of the background of the tooltip.
+    pub background_color: Color,
+
+    /// Color of the border of the tooltip.
+    pub border_color: Color,
+
+    /// Color of the text of the tooltip.
+    pub text_color: Color,
+
+    delay: u64,
+    widget_id: i32,
+    mouse_position: (i32, i32),
+    rest_start: u64,
+    text: Option<String>,
+}
+
+/// This is the implementation of the `Tooltip`.
+impl Tooltip {
+    /// Creates a new `Tooltip` with the default delay, and black text on a pale yellow background.
+    pub fn new() -> Self {
+        Self {
+            background_color: Color::RGB(255, 255, 225),
+            border_color: Color::RGB(0, 0, 0),
+            text_color: Color::RGB(0, 0, 0),
+            delay: DEFAULT_TOOLTIP_DELAY,
+            widget_id: 0,
+            mouse_position: (0, 0),
+            rest_start: 0,
+            text: None,
+        }
+    }
+
+    /// Sets the number of milliseconds the mouse must rest over a `Widget` before its tooltip is
+    /// shown.
+    pub fn set_delay(&mut self, delay: u64) {
+        self.delay = delay;
+    }
+
+    /// Retrieves the number of milliseconds the mouse must rest over a `Widget` before its tooltip
+    /// is shown.
+    pub fn get_delay(&self) -> u64 {
+        self.delay
+    }
+
+    /// Indicates whether or not a tooltip is currently shown.
+    pub fn is_visible(&self) -> bool {
+        self.text.is_some()
+    }
+
+    /// Records that the mouse moved to `x, y`, over the `Widget` with the ID `widget_id`.  The
+    /// tooltip is hidden, and the delay starts over.  Returns `true` if a tooltip was hidden, so the
+    /// screen needs to be redrawn.
+    pub fn mouse_moved(&mut self, widget_id: i32, x: i32, y: i32) -> bool {
+        self.widget_id = widget_id;
+        self.mouse_position = (x, y);
+        self.rest_start = clock::now_millis();
+
+        self.hide()
+    }
+
+    /// Hides the tooltip, until the mouse moves again.  This is called when a mouse button is
+    /// pressed, a key is pressed, or the mouse leaves the window.  Returns `true` if a tooltip was
+    /// hidden, so the screen needs to be redrawn.
+    pub fn hide(&mut self) -> bool {
+        self.rest_start = u64::MAX;
+        self.text.take().is_some()
+    }
+
+    /// Shows the tooltip of the `Widget` under the mouse once the mouse has rested over it for the
+    /// delay, if the `Widget` has tooltip text, and is not hidden.  Returns `true` if a tooltip was
+    /// shown, so the screen needs to be redrawn.
+    pub fn update(&mut self, widgets: &[WidgetContainer]) -> bool {
+        if self.text.is_some()
+            || self.widget_id <= 0
+            || self.widget_id as usize >= widgets.len()
+            || clock::now_millis() < self.rest_start.saturating_add(self.delay)
+        {
+            return false;
+        }
+
+        let mut widget = widgets[self.widget_id as usize].widget.borrow_mut();
+        let text = widget.get_config().get_text(CONFIG_TOOLTIP_TEXT);
+
+        // The delay has elapsed, so the tooltip is not checked again until the mouse moves.
+        self.rest_start = u64::MAX;
+
+        if text.is_empty() || widget.get_config().is_hidden() {
+            return false;
+        }
+
+        self.text = Some(text);
+
+        true
+    }
+
+    /// Draws the tooltip near the mouse cursor, if it is shown.  The tooltip is kept within the
+    /// bounds of the `Canvas`.
+    pub fn draw(&self, c: &mut Canvas<Window>, t: &TextureCache) {
+        let text = match &self.text {
+            Some(text) => text,
+            None => return,
+        };
+
+        let texture_creator = c.texture_creator();
+        let font = t
+            .get_ttf_context()
+            .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+            .unwrap();
+        let surface = font
+            .render(text)
+            .blended(self.text_color)
+            .map_err(|e| e.to_string())
+            .unwrap();
+        let font_texture = texture_creator
+            .create_texture_from_surface(&surface)
+            .map_err(|e| e.to_string())
+            .unwrap();
+        let TextureQuery { width, height, .. } = font_texture.query();
+        let (canvas_width, canvas_height) = c.output_size().unwrap_or((0, 0));
+        let tooltip_width = width + TOOLTIP_PADDING * 2;
+        let tooltip_height = height + TOOLTIP_PADDING * 2;
+        let mut x = self.mouse_position.0 + CURSOR_OFFSET.0;
+        let mut y = self.mouse_position.1 + CURSOR_OFFSET.1;
+
+        if x + tooltip_width as i32 > canvas_width as i32 {
+            x = (canvas_width as i32 - tooltip_width as i32).max(0);
+        }
+
+        // Below the cursor if there is room, otherwise above it, so the cursor does not cover it.
+        if y + tooltip_height as i32 > canvas_height as i32 {
+            y = (self.mouse_position.1 - tooltip_height as i32 - 2).max(0);
+        }
+
+        c.set_draw_color(self.background_color);
+        c.fill_rect(Rect::new(x, y, tooltip_width, tooltip_height))
+            .unwrap();
+        c.set_draw_color(self.border_color);
+        c.draw_rect(Rect::new(x, y, tooltip_width, tooltip_height))
+            .unwrap();
+        c.copy(
+            &font_texture,
+            None,
+            Rect::new(
+                x + TOOLTIP_PADDING as i32,
+                y + TOOLTIP_PADDING as i32,
+                width,
+                height,
+            ),
+        )
+        .unwrap();
+
+        // Safety: the text texture is only used within this function.
+        unsafe {
+            font_texture.destroy();
+        }
+    }
+}
+
+impl Default for Tooltip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -19,12 +19,14 @@ use sdl2::video::Window;
 
 use crate::render::callbacks::*;
 use crate::render::layout_cache::LayoutContainer;
+use crate::render::skin::{NinePatch, Skin, SkinPart, SkinState};
 use crate::render::texture_cache::TextureCache;
 use crate::render::texture_store::TextureStore;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
 use crate::render::{Points, Size};
 use sdl2::event::Event;
+use sdl2::keyboard::{Keycode, Mod};
 use sdl2::pixels::Color;
 use std::any::Any;
 use std::collections::HashMap;
@@ -129,12 +131,74 @@ pub trait Widget {
         self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
     }
 
+    /// When a key is pressed while this `Widget` has keyboard focus, this function is called.  It
+    /// contains the key code, the modifier keys that were held, and whether or not the key press is
+    /// an auto-repeat of a key being held down.  The `Event` is also sent to `other_event`
+    /// afterwards, so `Widget`s should handle a key press in one or the other.  This function
+    /// implementation is **optional**.
+    fn key_pressed(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _keycode: Keycode,
+        _keymod: Mod,
+        _repeat: bool,
+    ) {
+        self.key_pressed_callback(_widgets, _layouts, _keycode, _keymod, _repeat);
+    }
+
+    /// When a key is released while this `Widget` has keyboard focus, this function is called.  It
+    /// contains the key code, the modifier keys that were held, and a repeat flag, which is always
+    /// `false`.  The `Event` is also sent to `other_event` afterwards.  This function
+    /// implementation is **optional**.
+    fn key_released(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _keycode: Keycode,
+        _keymod: Mod,
+        _repeat: bool,
+    ) {
+        self.key_released_callback(_widgets, _layouts, _keycode, _keymod, _repeat);
+    }
+
+    /// When a file is dropped onto this `Widget` from another application, this function is called
+    /// with the path of the file.  Returns `true` if the file was handled; otherwise, it is passed
+    /// to the `Engine`'s `on_file_dropped` callback.  This function implementation is **optional**.
+    fn file_dropped(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _path: String,
+    ) -> bool {
+        self.file_dropped_callback(_widgets, _layouts, _path)
+    }
+
+    /// When text is dropped onto this `Widget` from another application, this function is called
+    /// with the text.  Returns `true` if the text was handled; otherwise, it is passed to the
+    /// `Engine`'s `on_text_dropped` callback.  This function implementation is **optional**.
+    fn text_dropped(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _text: String,
+    ) -> bool {
+        self.text_dropped_callback(_widgets, _layouts, _text)
+    }
+
     /// When a timer tick goes by (ie. a frame is displayed on the screen), this function is
     /// called.  This function implementation is **optional**.
     fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
         self.tick_callback(_widgets, _layouts);
     }
 
+    /// Returns this `Widget` as a `ParallelTick` object if its per-frame work is thread-safe, so that
+    /// it can be computed on a worker thread when the `Engine` is configured with more than one tick
+    /// thread.  Returns `None` by default, in which case all work is done in `tick`.
+    fn as_parallel_tick(&mut self) -> Option<&mut dyn ParallelTick> {
+        None
+    }
+
     /// When an `Event` is sent to the application that is not handled by the `Engine::run` loop, this
     /// method is called, sending the unhandled `Event` to the currently active `Widget`.  **This behavior
     /// is subject to change** as the `Engine::run` loop is modified to handle more `Event`s.
@@ -147,6 +211,29 @@ pub trait Widget {
         eprintln!("Other event: {:?}", _event);
     }
 
+    /// This is called on every visible, enabled `Widget` when a custom event is received by the
+    /// `Engine`, after the `Engine`'s `on_custom_event` callback.  Use `downcast_ref` to check
+    /// whether the event is of a type this `Widget` handles.  The default implementation does
+    /// nothing.
+    fn custom_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _event: &dyn Any,
+    ) {
+    }
+
+    /// This is called on every `Widget` when the window is resized, with the new `size` of the
+    /// window.  `Widget`s that follow the edges of the window, such as a status bar anchored to its
+    /// bottom, move or resize themselves here.  The default implementation does nothing.
+    fn window_resized(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _size: Size,
+    ) {
+    }
+
     /// This calls the `on_tick` callback.  This is implemented by the `default_widget_callbacks!` macro,
     /// so you do not need to implement it.  However, you need to call this function if you wish
     /// to honor an `on_tick` callback.
@@ -207,6 +294,71 @@ pub trait Widget {
     ) {
     }
 
+    /// This calls the `on_key_pressed` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_key_pressed` callback.
+    fn key_pressed_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _keycode: Keycode,
+        _keymod: Mod,
+        _repeat: bool,
+    ) {
+    }
+
+    /// This calls the `on_key_released` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_key_released` callback.
+    fn key_released_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _keycode: Keycode,
+        _keymod: Mod,
+        _repeat: bool,
+    ) {
+    }
+
+    /// This calls the `on_focus_gained` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  The `WidgetCache` calls this function after the
+    /// `Widget` gains keyboard focus.
+    fn focus_gained_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+    ) {
+    }
+
+    /// This calls the `on_focus_lost` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  The `WidgetCache` calls this function after the
+    /// `Widget` loses keyboard focus.
+    fn focus_lost_callback(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {}
+
+    /// This calls the `on_file_dropped` callback, returning `true` if it was set.  This is
+    /// implemented by the `default_widget_callbacks!` macro, so you do not need to implement it.
+    /// However, you need to call this function if you wish to honor an `on_file_dropped` callback.
+    fn file_dropped_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _path: String,
+    ) -> bool {
+        false
+    }
+
+    /// This calls the `on_text_dropped` callback, returning `true` if it was set.  This is
+    /// implemented by the `default_widget_callbacks!` macro, so you do not need to implement it.
+    /// However, you need to call this function if you wish to honor an `on_text_dropped` callback.
+    fn text_dropped_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _text: String,
+    ) -> bool {
+        false
+    }
+
     /// This callback is called when a setter is used to configure a value.  It is _not_ called when a
     /// call to `get_config()` using the setter is called, so it is best to use the top-level setters
     /// and getters for the configuration values - at least, until the `get_config()` call can be made
@@ -264,11 +416,21 @@ pub trait Widget {
         self.get_config().get_color(k)
     }
 
+    /// Retrieves a `Color` for a configuration key.  Returns `default` if not set.
+    fn get_color_or(&mut self, k: u8, default: Color) -> Color {
+        self.get_config().get_color_or(k, default)
+    }
+
     /// Retrieves a numeric value for a configuration key.  Returns 0 if not set.
     fn get_numeric(&mut self, k: u8) -> i32 {
         self.get_config().get_numeric(k)
     }
 
+    /// Retrieves a numeric value for a configuration key.  Returns `default` if not set.
+    fn get_numeric_or(&mut self, k: u8, default: i32) -> i32 {
+        self.get_config().get_numeric_or(k, default)
+    }
+
     /// Retrieves text for a configuration key.  Returns a blank string if not set.
     fn get_text(&mut self, k: u8) -> String {
         self.get_config().get_text(k)
@@ -328,6 +490,159 @@ pub trait Widget {
     fn set_invalidated(&mut self, flag: bool) {
         self.get_config().set_invalidated(flag);
     }
+
+    /// Releases any `Texture`s this `Widget` holds, freeing GPU memory.  This is called by the
+    /// `WidgetCache` when a `Widget` has been hidden for a number of frames.  Implementations must
+    /// invalidate the `Widget`, so that its `Texture`s are recreated the next time it is drawn.
+    fn release_textures(&mut self) {}
+
+    /// Applies the `Skin` of the current `Theme` to this `Widget`, or removes it if `None`.  This is
+    /// called by the `WidgetCache` when a `Theme` is applied.  `Widget`s that support skinning store
+    /// the `Skin`, and draw its `NinePatch` images in place of their standard appearance.
+    fn apply_skin(&mut self, _skin: Option<&Skin>) {}
+
+    /// When this `Widget` gains or loses keyboard focus, this function is triggered.  `Widget`s that
+    /// draw a focus-dependent state, such as a text caret, use this to track whether they have focus.
+    /// This function implementation is **optional**.
+    fn focus_changed(&mut self, _focused: bool) {}
+
+    /// Returns the bounds of the popup this `Widget` currently has open, in window coordinates, or
+    /// `None` if no popup is open.  Popups (such as drop-down lists and menus) may extend outside of
+    /// the bounds of the `Widget`.  Mouse events within the popup are sent to this `Widget`.  This
+    /// function implementation is **optional**.
+    fn get_popup_bounds(&mut self) -> Option<Rect> {
+        None
+    }
+
+    /// Draws the popup this `Widget` currently has open, returning a `Texture` the size of
+    /// `get_popup_bounds`.  Popups are drawn above all `Layer`s, whenever the display list is drawn.
+    /// Invalidate the `Widget` when the popup opens, closes, or changes.  This function
+    /// implementation is **optional**.
+    fn draw_popup(&mut self, _c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        None
+    }
+
+    /// Closes the popup this `Widget` has open.  This is called by the `WidgetCache` when a mouse
+    /// button is pressed outside of this `Widget` and its popup.  This function implementation is
+    /// **optional**.
+    fn close_popup(&mut self) {}
+
+    /// Indicates whether or not this `Widget` captures the mouse while a mouse button that was
+    /// pressed over it is held.  While the mouse is captured, all mouse movement is sent to this
+    /// `Widget`, even when the mouse leaves its bounds, so that drags (such as dragging the thumb of
+    /// a scroll bar) can be tracked.  Returns `false` by default.  This function implementation is
+    /// **optional**.
+    fn captures_mouse(&mut self) -> bool {
+        false
+    }
+
+    /// Indicates whether or not pressing a mouse button over this `Widget` moves the keyboard focus
+    /// to it.  `Widget`s that return `false` leave the focus where it is, so they can act on the
+    /// focused `Widget`, as an on-screen keyboard does.  Returns `true` by default.  This function
+    /// implementation is **optional**.
+    fn takes_focus_on_click(&mut self) -> bool {
+        true
+    }
+
+    /// Indicates whether or not this `Widget` accepts text input while it has keyboard focus.  While
+    /// the focused `Widget` accepts text input, the `Engine` enables SDL2 text input, so that typed
+    /// and composed text (including dead keys and IME composition) is delivered as `TextInput` and
+    /// `TextEditing` events, and places the IME candidate window at the bounds of the `Widget`.
+    /// Editable `Widget`s return `true`.  Returns `false` by default.  This function implementation
+    /// is **optional**.
+    fn accepts_text_input(&mut self) -> bool {
+        false
+    }
+
+    /// When text is typed or committed by an input method while this `Widget` has keyboard focus,
+    /// this function is called with the text, which may contain more than one character.  The
+    /// `TextInput` `Event` is also sent to `other_event` afterwards, so `Widget`s should handle
+    /// text in one or the other.  This function implementation is **optional**.
+    fn text_input(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _text: String,
+    ) {
+    }
+
+    /// When an input method is composing text while this `Widget` has keyboard focus, this function
+    /// is called with the text being composed, and the `start` and `length` of the selection within
+    /// it, in characters.  The composition ends when the text is committed with `text_input`, or
+    /// when this function is called with empty text.  The `TextEditing` `Event` is also sent to
+    /// `other_event` afterwards.  This function implementation is **optional**.
+    fn text_editing(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _text: String,
+        _start: i32,
+        _length: i32,
+    ) {
+    }
+
+    /// Indicates whether or not this `Widget` uses the `Tab` key itself while it has keyboard focus,
+    /// as a code editor does to indent.  When it does, `Tab` is sent to this `Widget` instead of
+    /// moving the focus, and `Ctrl-Tab` moves the focus instead.  Returns `false` by default.  This
+    /// function implementation is **optional**.
+    fn captures_tab_key(&mut self) -> bool {
+        false
+    }
+
+    /// Returns the events this `Widget` has synthesized since it was last asked, such as the key
+    /// presses of an on-screen keyboard.  The `Engine` handles them as though they had been
+    /// received from SDL2, so keyboard events are delivered to the focused `Widget`.  Returns no
+    /// events by default.  This function implementation is **optional**.
+    fn take_synthesized_events(&mut self) -> Vec<Event> {
+        Vec::new()
+    }
+
+    /// Returns the scrolling `Viewport` of this `Widget`, or `None` if this `Widget` is not a
+    /// viewport.  The children of a viewport are positioned relative to the top-left corner of its
+    /// content, are drawn into a `Texture` the size of its content, and are clipped to the visible
+    /// area of the viewport.  Invalidate the `Widget` when the viewport scrolls.  This function
+    /// implementation is **optional**.
+    fn get_viewport(&mut self) -> Option<Viewport> {
+        None
+    }
+}
+
+/// This describes the scrolling area of a viewport `Widget`: the area of the `Widget` that its
+/// children are shown in, the size of the content its children are positioned in, and the offset
+/// by which that content is scrolled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    /// The area of the `Widget` that shows its children, relative to the top-left corner of the
+    /// `Widget`.
+    pub bounds: Rect,
+
+    /// The width of the content.
+    pub content_width: u32,
+
+    /// The height of the content.
+    pub content_height: u32,
+
+    /// The horizontal offset of the content that is shown at the left edge of `bounds`.
+    pub scroll_x: i32,
+
+    /// The vertical offset of the content that is shown at the top edge of `bounds`.
+    pub scroll_y: i32,
+}
+
+/// This trait is implemented by `Widget`s whose per-frame work (such as advancing an animation or
+/// recomputing derived data) does not touch other `Widget`s, callbacks, or SDL2 resources, and can
+/// therefore be run on a worker thread.  When parallel ticking is enabled with
+/// `Engine::set_tick_threads`, `parallel_tick` is called on worker threads before the regular `tick`
+/// is called on the main thread, where callbacks are still triggered.  See the `OscilloscopeWidget`,
+/// which computes its trace this way.
+///
+/// A `MockClock` is installed on a single thread, so `clock::now_millis` called from
+/// `parallel_tick` on a worker thread returns the wall clock, even while a `MockClock` is installed.
+/// Work that depends on time should be done in `tick` instead.
+pub trait ParallelTick: Send {
+    /// Performs the thread-safe portion of this `Widget`'s per-frame work.  Returns `true` if the
+    /// `Widget` needs to be redrawn; the `Widget` is invalidated on the main thread afterwards.
+    fn parallel_tick(&mut self) -> bool;
 }
 
 /// This is an example top-level `Widget` object that is used to draw a background and a border
@@ -339,6 +654,7 @@ pub struct BaseWidget {
     system_properties: HashMap<i32, String>,
     callback_registry: CallbackRegistry,
     texture_store: TextureStore,
+    skin: Option<Skin>,
 }
 
 /// Base top-level implementation of the `BaseWidget`, which other classes can extend.
@@ -350,6 +666,7 @@ impl BaseWidget {
             system_properties: HashMap::new(),
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
+            skin: None,
         }
     }
 }
@@ -360,7 +677,7 @@ impl BaseWidget {
 /// display contents, and so on.  Look through the code in the `pushrod::widgets` module to get
 /// more of an idea of what is possible.
 impl Widget for BaseWidget {
-    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
         // You _can_ remove this `if` statement here, and just let the code run each time.  It will
         // eventually make your application less efficient if this is constantly called.
         if self.get_config().invalidated() {
@@ -371,22 +688,57 @@ impl Widget for BaseWidget {
 
             let base_color = self.get_config().get_color(CONFIG_COLOR_BASE);
             let border_color = self.get_config().get_color(CONFIG_COLOR_BORDER);
-
-            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
-                texture.set_draw_color(base_color);
-                texture.clear();
-
-                texture.set_draw_color(border_color);
-                texture
-                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+            let state = SkinState::from_flags(self.get_config().is_enabled(), false, false);
+            let patch: Option<NinePatch> = self
+                .skin
+                .as_ref()
+                .and_then(|skin| skin.get_patch(SkinPart::Panel, state))
+                .cloned();
+
+            match patch {
+                Some(patch) => {
+                    let image = t.get_image(c, patch.image.clone());
+
+                    c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                        texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                        texture.clear();
+
+                        patch.draw(texture, image, Rect::new(0, 0, bounds[0], bounds[1]));
+                    })
+                    .unwrap();
+                }
+                None => {
+                    c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                        texture.set_draw_color(base_color);
+                        texture.clear();
+
+                        texture.set_draw_color(border_color);
+                        texture
+                            .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                            .unwrap();
+                    })
                     .unwrap();
-            })
-            .unwrap();
+                }
+            }
         }
 
         self.texture_store.get_optional_ref()
     }
 
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    /// Stores the `Skin`, drawing its `SkinPart::Panel` image in place of the base color and border.
+    fn apply_skin(&mut self, skin: Option<&Skin>) {
+        self.skin = skin.filter(|x| x.has_part(SkinPart::Panel)).cloned();
+        self.texture_store.set_blended(self.skin.is_some());
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
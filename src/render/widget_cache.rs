@@ -13,51 +13,154 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cell::RefCell;
+use std::cell::{RefCell, RefMut};
 
+use crate::render::canvas_helper::copy_rotated;
+use crate::render::effects::{render_effects, Effect, PostEffect};
+use crate::render::input_settings::InputSettings;
+use crate::render::layer::{Layer, LAYER_COUNT};
 use crate::render::layout_cache::LayoutContainer;
+use crate::render::modal::DEFAULT_MODAL_DIM_COLOR;
 use crate::render::texture_cache::TextureCache;
-use crate::render::widget::Widget;
-use crate::render::widget_config::{CONFIG_ORIGIN, CONFIG_SIZE};
+use crate::render::texture_memory::widget_texture_memory;
+use crate::render::theme::{FocusStyle, Theme};
+use crate::render::widget::{BaseWidget, ParallelTick, Viewport, Widget};
+use crate::render::widget_config::{
+    CONFIG_COMPOSITED, CONFIG_DIALOG_RESULT, CONFIG_FOCUSABLE, CONFIG_KEY_REPEAT_DELAY,
+    CONFIG_LAYER, CONFIG_ORIGIN, CONFIG_REMOVE, CONFIG_ROTATION, CONFIG_SIZE, CONFIG_TAB_ORDER,
+};
+use crate::render::widget_handle::{WidgetHandle, WidgetHandleError};
+use crate::render::widget_name::WidgetName;
+use crate::render::{make_points_origin, make_size, Size, SIZE_HEIGHT, SIZE_WIDTH};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use sdl2::event::Event;
+use sdl2::keyboard::{Keycode, Mod};
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
-use sdl2::render::Canvas;
+use sdl2::render::{BlendMode, Canvas, Texture};
 use sdl2::video::Window;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// This is the cached result of applying `PostEffect`s to a `Widget`'s texture, along with the
+/// effects that were active when it was computed.
+struct EffectTexture {
+    texture: Texture,
+    padding: u32,
+    active: Vec<Effect>,
+}
+
+/// Destroys the cached result of applying `PostEffect`s to a `Widget`, if one exists.
+fn release_effect_texture(effect_textures: &mut HashMap<i32, EffectTexture>, widget_id: i32) {
+    if let Some(effect_texture) = effect_textures.remove(&widget_id) {
+        // Safety: effect textures are only referenced by the `effect_textures` map.
+        unsafe {
+            effect_texture.texture.destroy();
+        }
+    }
+}
+
+/// This is the cached texture of a composited container `Widget` and its subtree.
+struct Composite {
+    texture: Texture,
+    size: (u32, u32),
+}
+
+/// Destroys the cached composite texture of a container `Widget`, if one exists.
+fn release_composite(composites: &mut HashMap<i32, Composite>, widget_id: i32) {
+    if let Some(composite) = composites.remove(&widget_id) {
+        // Safety: composite textures are only referenced by the `composites` map.
+        unsafe {
+            composite.texture.destroy();
+        }
+    }
+}
+
+/// This identifies the texture that `Widget`s are drawn to during the draw loop: either the texture
+/// of a `Layer`, the composite texture of a container `Widget`, along with the point of origin of
+/// that container, or the texture of the modal dialog being drawn.
+#[derive(Clone, Copy)]
+enum DrawTarget {
+    Layer(Layer),
+    Composite(i32, i32, i32),
+    Modal,
+}
+
+impl DrawTarget {
+    fn offset(self) -> (i32, i32) {
+        match self {
+            DrawTarget::Layer(_) | DrawTarget::Modal => (0, 0),
+            DrawTarget::Composite(_, x, y) => (x, y),
+        }
+    }
+
+    fn texture<'a>(
+        self,
+        layer_textures: &'a mut [Option<Texture>],
+        composites: &'a mut HashMap<i32, Composite>,
+        modal_texture: &'a mut Option<Texture>,
+    ) -> &'a mut Texture {
+        match self {
+            DrawTarget::Layer(layer) => layer_textures[layer.index()].as_mut().unwrap(),
+            DrawTarget::Modal => modal_texture.as_mut().unwrap(),
+            DrawTarget::Composite(widget_id, _, _) => {
+                &mut composites.get_mut(&widget_id).unwrap().texture
+            }
+        }
+    }
+}
+
+/// This is the default number of consecutive frames a `Widget` must be hidden before its `Texture`s
+/// are released.
+pub const DEFAULT_TEXTURE_RELEASE_FRAMES: u32 = 300;
 
 /// This is a container that stores information about a `Widget` that will be drawn on the screen.
 /// It stores the `Widget` object, the actual point of origin inside the `Window` (as a `Vec<i32>`
 /// of X and Y points), the parent ID of this `Widget`, if it is being added as a child.
 pub struct WidgetContainer {
     pub widget: RefCell<Box<dyn Widget>>,
-    widget_name: String,
+    widget_name: WidgetName,
     pub origin: Vec<i32>,
     widget_id: i32,
     parent_id: i32,
+    generation: u32,
+    texture_memory: u64,
+    hidden_frames: u32,
+    on_screen: bool,
+    drawn_layer: Layer,
+    free: bool,
 }
 
 /// This is the `WidgetContainer` object that stores a `Widget` and its accompanying information:
 /// its name, point of origin, and parent ID.
 impl WidgetContainer {
     /// Creates a new `WidgetContainer` storage object.
-    pub fn new(
+    pub fn new<N: Into<WidgetName>>(
         widget: Box<dyn Widget>,
-        widget_name: String,
+        widget_name: N,
         origin: Vec<i32>,
         widget_id: i32,
         parent_id: i32,
     ) -> Self {
         Self {
             widget: RefCell::new(widget),
-            widget_name,
+            widget_name: widget_name.into(),
             origin,
             widget_id,
             parent_id,
+            generation: 0,
+            texture_memory: 0,
+            hidden_frames: 0,
+            on_screen: false,
+            drawn_layer: Layer::Content,
+            free: false,
         }
     }
 
-    /// Retrieves the name of this `Widget`.
-    pub fn get_widget_name(&self) -> String {
+    /// Retrieves the name of this `Widget`.  The name is interned, so this does not allocate.
+    pub fn get_widget_name(&self) -> WidgetName {
         self.widget_name.clone()
     }
 
@@ -71,6 +174,17 @@ impl WidgetContainer {
     pub fn get_parent_id(&self) -> i32 {
         self.parent_id
     }
+
+    /// Retrieves the `WidgetHandle` that refers to this `Widget`.
+    pub fn get_handle(&self) -> WidgetHandle {
+        WidgetHandle::new(self.widget_id, self.generation)
+    }
+
+    /// Retrieves the estimated number of bytes of GPU memory used by the backing texture of this
+    /// `Widget`, as measured the last time it was drawn.
+    pub fn get_texture_memory(&self) -> u64 {
+        self.texture_memory
+    }
 }
 
 /// This is the `WidgetCache` struct, which contains a list of `Widget`s that are managed by the Pushrod
@@ -80,7 +194,28 @@ impl WidgetContainer {
 /// always start at 1.
 pub struct WidgetCache {
     cache: Vec<WidgetContainer>,
+    name_index: HashMap<WidgetName, i32>,
+    free_slots: Vec<i32>,
+    removed_widgets: Vec<i32>,
     texture_cache: TextureCache,
+    focused_widget_id: i32,
+    focus_changes: Vec<(i32, bool)>,
+    theme: Theme,
+    themed: bool,
+    input_settings: InputSettings,
+    widgets_drawn: u32,
+    texture_release_frames: u32,
+    tick_pool: Option<Arc<ThreadPool>>,
+    layer_textures: Vec<Option<Texture>>,
+    modal_texture: Option<Texture>,
+    layer_size: (u32, u32),
+    composites: HashMap<i32, Composite>,
+    viewport_origins: HashMap<i32, (i32, i32)>,
+    modal_stack: Vec<i32>,
+    removed_modals: Vec<(WidgetHandle, i32)>,
+    modal_dim_color: Color,
+    effects: HashMap<i32, Vec<PostEffect>>,
+    effect_textures: HashMap<i32, EffectTexture>,
 }
 
 /// This is the `WidgetCache` implementation.  This cache object manages the `Widget` list for use by the
@@ -89,38 +224,269 @@ impl WidgetCache {
     pub fn new() -> Self {
         Self {
             cache: Vec::new(),
+            name_index: HashMap::new(),
+            free_slots: Vec::new(),
+            removed_widgets: Vec::new(),
             texture_cache: TextureCache::new(),
+            focused_widget_id: 0,
+            focus_changes: Vec::new(),
+            theme: Theme::default(),
+            themed: false,
+            input_settings: InputSettings::default(),
+            widgets_drawn: 0,
+            texture_release_frames: DEFAULT_TEXTURE_RELEASE_FRAMES,
+            tick_pool: None,
+            layer_textures: Vec::new(),
+            modal_texture: None,
+            layer_size: (0, 0),
+            composites: HashMap::new(),
+            viewport_origins: HashMap::new(),
+            modal_stack: Vec::new(),
+            removed_modals: Vec::new(),
+            modal_dim_color: DEFAULT_MODAL_DIM_COLOR,
+            effects: HashMap::new(),
+            effect_textures: HashMap::new(),
         }
     }
 
     /// This adds a `Widget` to the render list.  It requires that the `Widget` being added is in a `Box`,
-    /// along with a `widget_name`.  Returns the ID of the `Widget` that was added.  Use this ID if
-    /// you plan on adding further `Widget`s, with this `Widget` as the parent.  The point of
-    /// `origin` (extracted from the `Widget`'s position at creation time) is its physical location
-    /// inside the `Window`.
-    pub fn add_widget(&mut self, mut widget: Box<dyn Widget>, widget_name: String) -> i32 {
+    /// along with a `widget_name`.  Returns the `WidgetHandle` of the `Widget` that was added; use
+    /// `handle.id()` if you plan on adding further `Widget`s, with this `Widget` as the parent.  The
+    /// point of `origin` (extracted from the `Widget`'s position at creation time) is its physical
+    /// location inside the `Window`.  Slots freed by `remove_widget` are reused.
+    pub fn add_widget<N: Into<WidgetName>>(
+        &mut self,
+        mut widget: Box<dyn Widget>,
+        widget_name: N,
+    ) -> WidgetHandle {
         let origin = widget.get_config().get_point(CONFIG_ORIGIN);
-        let widget_id = self.cache.len();
+        let widget_id = match self.free_slots.pop() {
+            Some(slot) => slot as usize,
+            None => self.cache.len(),
+        };
+        let widget_name = widget_name.into();
 
-        self.cache.push(WidgetContainer::new(
-            widget,
-            widget_name,
-            origin,
-            widget_id as i32,
-            0,
-        ));
+        self.name_index
+            .entry(widget_name.clone())
+            .or_insert(widget_id as i32);
+
+        if self.themed {
+            self.theme.apply_to_widget(widget.as_mut());
+        }
+
+        if !widget
+            .get_config()
+            .config
+            .contains_key(&CONFIG_KEY_REPEAT_DELAY)
+        {
+            self.input_settings.apply(widget.get_config());
+        }
+
+        let mut container = WidgetContainer::new(widget, widget_name, origin, widget_id as i32, 0);
+
+        if widget_id < self.cache.len() {
+            container.generation = self.cache[widget_id].generation;
+            self.cache[widget_id] = container;
+        } else {
+            self.cache.push(container);
+        }
+
+        self.cache[widget_id].get_handle()
+    }
+
+    /// Retrieves the IDs that the next `count` `Widget`s added to the cache will be given, in the
+    /// order they are added, so that references to them can be checked before they are created.
+    pub fn next_widget_ids(&self, count: usize) -> Vec<i32> {
+        let mut widget_ids: Vec<i32> = self.free_slots.iter().rev().take(count).cloned().collect();
+        let next_id = self.cache.len() as i32;
+
+        widget_ids.extend((next_id..).take(count - widget_ids.len()));
+        widget_ids
+    }
+
+    /// Removes the `Widget` referred to by `handle` from the render list, along with all of its
+    /// descendants.  Their slots are freed for reuse, and all existing `WidgetHandle`s to them become
+    /// stale.  Raw `Widget` IDs referring to the slots are not protected, and will refer to the next
+    /// `Widget`s added; use `take_removed_widgets` to remove them from any `Layout`s.
+    pub fn remove_widget(&mut self, handle: WidgetHandle) -> Result<(), WidgetHandleError> {
+        let widget_id = self.validate_handle(handle)?;
+
+        if widget_id == 0 {
+            return Err(WidgetHandleError::BaseWidget);
+        }
+
+        let subtree: Vec<i32> = (1..self.cache.len() as i32)
+            .filter(|id| !self.cache[*id as usize].free && self.is_in_subtree(*id, widget_id))
+            .collect();
+
+        if self.is_in_subtree(self.focused_widget_id, widget_id) {
+            self.set_focus(0);
+        }
+
+        for removed_id in subtree {
+            self.free_slot(removed_id);
+        }
+
+        self.cache[0].widget.borrow_mut().set_invalidated(true);
+
+        Ok(())
+    }
+
+    /// Retrieves the IDs of the `Widget`s that have been removed since this function was last
+    /// called, so that they can be removed from the `Layout`s that refer to them.
+    pub fn take_removed_widgets(&mut self) -> Vec<i32> {
+        std::mem::take(&mut self.removed_widgets)
+    }
+
+    /// Retrieves the `WidgetHandle`s of the modal dialogs that have been removed while they were
+    /// shown since this function was last called, along with the result each of them requested, or
+    /// `0` if none was requested, so that their `on_dialog_closed` callbacks can be triggered.
+    pub fn take_removed_modals(&mut self) -> Vec<(WidgetHandle, i32)> {
+        std::mem::take(&mut self.removed_modals)
+    }
+
+    /// Sets the `PostEffect`s applied to the texture of the `Widget` referred to by `handle` before
+    /// it is composited.  Effects are applied in order, and are recomputed only when the `Widget` is
+    /// redrawn, or when the set of active effects changes.  An empty list removes all effects.
+    pub fn set_effects(
+        &mut self,
+        handle: WidgetHandle,
+        effects: Vec<PostEffect>,
+    ) -> Result<(), WidgetHandleError> {
+        let widget_id = self.validate_handle(handle)?;
+
+        release_effect_texture(&mut self.effect_textures, widget_id);
+
+        if effects.is_empty() {
+            self.effects.remove(&widget_id);
+        } else {
+            self.effects.insert(widget_id, effects);
+        }
+
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .set_invalidated(true);
+
+        Ok(())
+    }
+
+    /// Retrieves the `PostEffect`s applied to a `Widget` by ID.
+    pub fn get_effects(&self, widget_id: i32) -> &[PostEffect] {
+        self.effects
+            .get(&widget_id)
+            .map(|effects| effects.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Makes the `Widget` referred to by `handle` a child of the `Widget` referred to by `parent`.
+    /// Children are drawn after their parent, are clipped to their parent's bounds when culling, and
+    /// are drawn into their parent's cached texture when the parent has `CONFIG_COMPOSITED` set.  If
+    /// the parent is a viewport (see `Widget::get_viewport`), the origin of the child is relative to
+    /// the top-left corner of the parent's content, and moves with it as it scrolls.  Returns an error if either handle is invalid, or if the change would make a `Widget` its own
+    /// ancestor.
+    pub fn set_parent(
+        &mut self,
+        handle: WidgetHandle,
+        parent: WidgetHandle,
+    ) -> Result<(), WidgetHandleError> {
+        let widget_id = self.validate_handle(handle)?;
+        let parent_id = self.validate_handle(parent)?;
+
+        if widget_id == 0 {
+            return Err(WidgetHandleError::BaseWidget);
+        }
+
+        let mut ancestor_id = parent_id;
+
+        while ancestor_id != 0 {
+            if ancestor_id == widget_id {
+                return Err(WidgetHandleError::CyclicParent(handle));
+            }
+
+            ancestor_id = self.cache[ancestor_id as usize].parent_id;
+        }
+
+        let old_parent_id = self.cache[widget_id as usize].parent_id;
+        let (old_x, old_y) = self.get_viewport_origin(old_parent_id);
+        let (new_x, new_y) = self.get_viewport_origin(parent_id);
+
+        self.cache[widget_id as usize].parent_id = parent_id;
+        self.move_widget(widget_id, new_x - old_x, new_y - old_y);
+        self.cache[old_parent_id as usize]
+            .widget
+            .borrow_mut()
+            .set_invalidated(true);
+        self.cache[parent_id as usize]
+            .widget
+            .borrow_mut()
+            .set_invalidated(true);
+
+        Ok(())
+    }
+
+    /// Validates a `WidgetHandle`, returning the ID of the `Widget` it refers to, or an error if the
+    /// handle does not refer to a `Widget`, or the `Widget` has been removed.
+    pub fn validate_handle(&self, handle: WidgetHandle) -> Result<i32, WidgetHandleError> {
+        let widget_id = handle.id();
+
+        if widget_id < 0 || widget_id as usize >= self.cache.len() {
+            return Err(WidgetHandleError::InvalidIndex(handle));
+        }
+
+        if self.cache[widget_id as usize].generation != handle.generation()
+            || self.cache[widget_id as usize].free
+        {
+            return Err(WidgetHandleError::Stale(handle));
+        }
+
+        Ok(widget_id)
+    }
+
+    /// Indicates whether or not a `WidgetHandle` still refers to a `Widget` in the render list.
+    pub fn is_valid_handle(&self, handle: WidgetHandle) -> bool {
+        self.validate_handle(handle).is_ok()
+    }
+
+    /// Returns the `WidgetContainer` referred to by a `WidgetHandle`, after validating it.
+    pub fn get_container(
+        &mut self,
+        handle: WidgetHandle,
+    ) -> Result<&mut WidgetContainer, WidgetHandleError> {
+        let widget_id = self.validate_handle(handle)?;
+
+        Ok(&mut self.cache[widget_id as usize])
+    }
+
+    /// Returns the current `WidgetHandle` for a raw `Widget` ID, or `None` if the ID does not refer
+    /// to a `Widget`.
+    pub fn get_handle(&self, widget_id: i32) -> Option<WidgetHandle> {
+        if widget_id < 0 {
+            return None;
+        }
 
-        (self.cache.len() - 1) as i32
+        self.cache
+            .get(widget_id as usize)
+            .filter(|container| !container.free)
+            .map(|container| container.get_handle())
     }
 
     /// This locates the ID of a `Widget` at a given `x` and `y` coordinate.  If a `Widget` could not
     /// be found, the top-level `Widget` (id 0) is returned.  This function returns the top-most
-    /// visible `Widget` id.
+    /// visible `Widget` id, taking the `Layer` each `Widget` is drawn on into account.  Open popups
+    /// are above all `Layer`s, so a point within a popup returns the `Widget` that owns it.  While a
+    /// modal dialog is shown, only its `Widget`s are found, and the root `Widget` of the dialog is
+    /// returned for points outside of it.
     pub fn find_widget(&mut self, x: i32, y: i32) -> i32 {
+        if let Some(popup_owner_id) = self.find_popup_owner(x, y) {
+            return popup_owner_id;
+        }
+
         let mut found_widget_id: i32 = 0;
+        let mut found_layer = Layer::Background;
 
         for i in 0..self.cache.len() {
-            if !self.is_hidden(i as i32) {
+            if !self.is_hidden(i as i32) && !self.is_culled(i as i32) {
                 let start_x: i32 = self.cache[i]
                     .widget
                     .borrow_mut()
@@ -144,13 +510,27 @@ impl WidgetCache {
                         .get_config()
                         .get_size(CONFIG_SIZE)[1] as i32);
 
-                if x >= start_x && x <= end_x && y >= start_y && y <= end_y {
+                let layer = self.get_layer(i as i32);
+
+                if x >= start_x
+                    && x <= end_x
+                    && y >= start_y
+                    && y <= end_y
+                    && layer.index() >= found_layer.index()
+                    && !self.is_clipped(i as i32, x, y)
+                    && self.is_in_modal(i as i32)
+                {
                     found_widget_id = i as i32;
+                    found_layer = layer;
                 }
             }
         }
 
-        found_widget_id
+        if self.is_in_modal(found_widget_id) {
+            found_widget_id
+        } else {
+            self.get_modal()
+        }
     }
 
     /// Returns a `WidgetContainer` object by its ID.  This is the same `Widget` ID that is returned
@@ -164,16 +544,164 @@ impl WidgetCache {
     /// Returns a `WidgetContainer` object by the name of the `Widget`.  If the `WidgetContainer`
     /// cannot find the `Widget` by the `name` specified, the top-level `Widget` is returned for
     /// safety.
-    pub fn get_container_by_name(&mut self, name: String) -> &mut WidgetContainer {
-        let cache_size = self.cache.len();
+    pub fn get_container_by_name<N: AsRef<str>>(&mut self, name: N) -> &mut WidgetContainer {
+        let widget_id = self.get_widget_id_by_name(name).unwrap_or(0);
 
-        for i in 0..cache_size {
-            if self.cache[i].get_widget_name() == name {
-                return self.get_container_by_id(i as i32);
+        self.get_container_by_id(widget_id)
+    }
+
+    /// Returns the ID of the `Widget` with the specified `name`, or `None` if no `Widget` has that
+    /// name.  If more than one `Widget` shares the same name, the first one added is returned.
+    pub fn get_widget_id_by_name<N: AsRef<str>>(&self, name: N) -> Option<i32> {
+        self.name_index.get(name.as_ref()).cloned()
+    }
+
+    /// Applies a `Theme` to all of the `Widget`s currently stored in the cache.  `Widget`s that are
+    /// added after the `Theme` has been set automatically have the `Theme` applied as they are
+    /// added.  The `Theme` also controls the appearance of the focus indicator.
+    pub fn set_theme(&mut self, theme: Theme) {
+        for x in &self.cache {
+            theme.apply_to_widget(x.widget.borrow_mut().as_mut());
+        }
+
+        self.theme = theme;
+        self.themed = true;
+    }
+
+    /// Retrieves the currently active `Theme`.
+    pub fn get_theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Applies `InputSettings` to all of the `Widget`s currently stored in the cache.  `Widget`s that
+    /// are added afterwards receive these settings unless they have been configured individually.
+    pub fn set_input_settings(&mut self, input_settings: InputSettings) {
+        for x in &self.cache {
+            input_settings.apply(x.widget.borrow_mut().get_config());
+        }
+
+        self.input_settings = input_settings;
+    }
+
+    /// Retrieves the currently active `InputSettings`.
+    pub fn get_input_settings(&self) -> InputSettings {
+        self.input_settings
+    }
+
+    /// Sets keyboard focus to the `Widget` specified by `widget_id`.  If the `Widget` is not
+    /// focusable (see `CONFIG_FOCUSABLE`), hidden, or disabled, focus is cleared instead.  A
+    /// `widget_id` of `0` clears the focus.
+    pub fn set_focus(&mut self, widget_id: i32) {
+        let new_focus_id = if widget_id > 0
+            && (widget_id as usize) < self.cache.len()
+            && self.is_focusable(widget_id)
+            && self.is_in_modal(widget_id)
+        {
+            widget_id
+        } else {
+            0
+        };
+
+        if new_focus_id == self.focused_widget_id {
+            return;
+        }
+
+        let previous_focus_id = self.focused_widget_id;
+
+        self.focused_widget_id = new_focus_id;
+
+        {
+            let mut previous = self.cache[previous_focus_id as usize].widget.borrow_mut();
+
+            previous.set_invalidated(true);
+
+            if previous_focus_id != 0 {
+                previous.focus_changed(false);
+                self.focus_changes.push((previous_focus_id, false));
+            }
+        }
+
+        let mut focused = self.cache[new_focus_id as usize].widget.borrow_mut();
+
+        focused.set_invalidated(true);
+
+        if new_focus_id != 0 {
+            focused.focus_changed(true);
+            self.focus_changes.push((new_focus_id, true));
+        }
+    }
+
+    /// Moves keyboard focus to the next focusable `Widget` in the tab order (see `CONFIG_TAB_ORDER`),
+    /// or the previous one if `reverse` is set, wrapping around at either end.  Hidden and disabled
+    /// `Widget`s, and `Widget`s outside of the topmost modal dialog, are skipped.  If no `Widget`
+    /// has focus, the first (or last) `Widget` in the tab order is focused.
+    pub fn focus_next(&mut self, reverse: bool) {
+        let mut tab_order: Vec<(bool, i32, i32)> = (1..self.cache.len() as i32)
+            .filter(|id| self.is_focusable(*id) && self.is_in_modal(*id))
+            .map(|id| {
+                let order = self.cache[id as usize]
+                    .widget
+                    .borrow_mut()
+                    .get_config()
+                    .get_numeric(CONFIG_TAB_ORDER);
+
+                (order <= 0, order, id)
+            })
+            .filter(|(_, order, _)| *order >= 0)
+            .collect();
+
+        if tab_order.is_empty() {
+            return;
+        }
+
+        tab_order.sort();
+
+        let count = tab_order.len();
+        let next = match tab_order
+            .iter()
+            .position(|(_, _, id)| *id == self.focused_widget_id)
+        {
+            Some(i) if reverse => (i + count - 1) % count,
+            Some(i) => (i + 1) % count,
+            None if reverse => count - 1,
+            None => 0,
+        };
+
+        self.set_focus(tab_order[next].2);
+    }
+
+    /// Indicates whether or not the focused `Widget` uses the `Tab` key itself.  Returns `false` if
+    /// no `Widget` has focus.
+    pub fn focus_captures_tab_key(&self) -> bool {
+        self.focused_widget_id != 0
+            && self.cache[self.focused_widget_id as usize]
+                .widget
+                .borrow_mut()
+                .captures_tab_key()
+    }
+
+    /// Calls the `on_focus_lost` and `on_focus_gained` callbacks of the `Widget`s whose keyboard
+    /// focus has changed since this function was last called, in the order the changes were made.
+    pub fn focus_callbacks(&mut self, cache: &[LayoutContainer]) {
+        for (widget_id, focused) in std::mem::take(&mut self.focus_changes) {
+            if widget_id as usize >= self.cache.len() {
+                continue;
+            }
+
+            let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
+
+            if focused {
+                widget.focus_gained_callback(&self.cache, cache);
+            } else {
+                widget.focus_lost_callback(&self.cache, cache);
             }
         }
+    }
 
-        self.get_container_by_id(0 as i32)
+    /// Retrieves the ID of the `Widget` that currently has keyboard focus.  Returns `0` if no
+    /// `Widget` has focus.
+    pub fn get_focus(&self) -> i32 {
+        self.focused_widget_id
     }
 
     /// This function calls the `button_clicked` callback for the `Widget` specified by `widget_id`.
@@ -201,169 +729,1390 @@ impl WidgetCache {
                     );
                 }
             }
-        } else if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
-            self.cache[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .button_clicked(&self.cache, cache, button, clicks, state);
-        }
-    }
+        } else {
+            if state {
+                self.close_popups_except(widget_id);
+            }
 
-    /// This function calls the `mouse_moved` callback for the `Widget` specified by `widget_id`.
-    pub fn mouse_moved(&mut self, widget_id: i32, points: Vec<i32>, cache: &[LayoutContainer]) {
-        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
-            self.cache[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .mouse_moved(&self.cache, cache, points);
+            if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+                self.cache[widget_id as usize]
+                    .widget
+                    .borrow_mut()
+                    .button_clicked(&self.cache, cache, button, clicks, state);
+            }
         }
     }
 
-    /// This function calls the `mouse_scrolled` callback for the `Widget` specified by `widget_id`.
-    pub fn mouse_scrolled(&mut self, widget_id: i32, points: Vec<i32>, cache: &[LayoutContainer]) {
-        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
-            self.cache[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .mouse_scrolled(&self.cache, cache, points);
-        }
+    /// Returns the ID of the `Widget` whose open popup contains the point `x, y`.  If popups
+    /// overlap, the most recently added `Widget` wins.
+    fn find_popup_owner(&self, x: i32, y: i32) -> Option<i32> {
+        (0..self.cache.len() as i32).rev().find(|id| {
+            !self.is_hidden(*id)
+                && self.is_in_modal(*id)
+                && self.cache[*id as usize]
+                    .widget
+                    .borrow_mut()
+                    .get_popup_bounds()
+                    .is_some_and(|bounds| bounds.contains_point((x, y)))
+        })
     }
 
-    /// This function calls the `mouse_exited` callback for the `Widget` specified by `widget_id`.
-    pub fn mouse_exited(&mut self, widget_id: i32, cache: &[LayoutContainer]) {
-        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
-            self.cache[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .mouse_exited(&self.cache, cache);
+    /// Closes the open popups of all `Widget`s other than `widget_id`.
+    fn close_popups_except(&mut self, widget_id: i32) {
+        for (id, container) in self.cache.iter().enumerate() {
+            let mut widget = container.widget.borrow_mut();
+
+            if id as i32 != widget_id && widget.get_popup_bounds().is_some() {
+                widget.close_popup();
+                widget.set_invalidated(true);
+            }
         }
     }
 
-    /// This function calls the `mouse_entered` callback for the `Widget` specified by `widget_id`.
-    pub fn mouse_entered(&mut self, widget_id: i32, cache: &[LayoutContainer]) {
-        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
-            self.cache[widget_id as usize]
+    /// Removes the `Widget`s that have requested to be removed by setting `CONFIG_REMOVE`.
+    fn remove_requested_widgets(&mut self) {
+        for widget_id in 1..self.cache.len() as i32 {
+            if self.cache[widget_id as usize].free {
+                continue;
+            }
+
+            let remove = self.cache[widget_id as usize]
                 .widget
                 .borrow_mut()
-                .mouse_entered(&self.cache, cache);
+                .get_config()
+                .get_toggle(CONFIG_REMOVE);
+
+            if remove {
+                let handle = self.cache[widget_id as usize].get_handle();
+
+                self.remove_widget(handle).unwrap();
+            }
         }
     }
 
-    /// This function calls the `tick` method on all registered `Widget`s in the cache.  The purpose
-    /// for the `tick` is to indicate that a drawing loop is about to occur, and the `Widget` can
-    /// update itself as necessary beforehand.
-    pub fn tick(&mut self, _cache: &[LayoutContainer]) {
-        let cache_size = self.cache.len();
+    /// Draws each modal dialog onto the `Canvas`, above all `Layer`s, dimming everything beneath it.
+    fn draw_modals(&mut self, c: &mut Canvas<Window>) {
+        for index in 0..self.modal_stack.len() {
+            let modal_id = self.modal_stack[index];
+            let parent_id = self.cache[modal_id as usize].parent_id;
 
-        for i in 0..cache_size {
-            if !self.is_hidden(i as i32) {
-                self.cache[i].widget.borrow_mut().tick(&self.cache, _cache);
+            if self.modal_texture.is_none() {
+                let mut texture = c
+                    .create_texture_target(None, self.layer_size.0, self.layer_size.1)
+                    .unwrap();
+
+                texture.set_blend_mode(BlendMode::Blend);
+                self.modal_texture = Some(texture);
             }
+
+            c.with_texture_canvas(self.modal_texture.as_mut().unwrap(), |texture| {
+                texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                texture.clear();
+            })
+            .unwrap();
+
+            for layer in Layer::all().iter() {
+                self.draw_widget(parent_id, modal_id, *layer, DrawTarget::Modal, c);
+            }
+
+            c.set_blend_mode(BlendMode::Blend);
+            c.set_draw_color(self.modal_dim_color);
+            c.fill_rect(None).unwrap();
+            c.set_blend_mode(BlendMode::None);
+            c.copy(self.modal_texture.as_ref().unwrap(), None, None)
+                .unwrap();
         }
     }
 
-    /// This function sends all other un-handled events from SDL2 to the currently highlighted
-    /// `Widget`.
-    pub fn other_event(&mut self, widget_id: i32, event: Event, cache: &[LayoutContainer]) {
-        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
-            self.cache[widget_id as usize]
-                .widget
-                .borrow_mut()
-                .other_event(&self.cache, cache, event);
+    /// Indicates whether or not a `Widget` can receive events: `true` if no modal dialog is shown, or
+    /// if the `Widget` is part of the modal dialog that was shown last.
+    fn is_in_modal(&self, widget_id: i32) -> bool {
+        match self.modal_stack.last() {
+            Some(modal_id) => self.is_in_subtree(widget_id, *modal_id),
+            None => true,
         }
     }
 
-    /// This function performs the draw loop for all of the `Widget`s stored in the `cache`.  Each
-    /// `Widget` receives a mutable reference to the `Canvas` so that the `Widget` can be drawn on
-    /// the screen during the draw loop of the `Engine`.  This `draw_loop` function automatically
-    /// clips the screen area so that the `Widget` cannot draw outside of its bounds.  Returns `true`
-    /// if the display loop needs to refresh the top-level canvas, `false` otherwise.
-    pub fn draw_loop(&mut self, c: &mut Canvas<Window>) -> bool {
-        let cache_size = self.cache.len();
-
-        for i in 0..cache_size {
-            if self.cache[i].widget.borrow_mut().is_invalidated() {
-                self.draw(0, c);
+    /// Indicates whether or not a `Widget` is `root_id`, or one of its descendants.
+    fn is_in_subtree(&self, widget_id: i32, root_id: i32) -> bool {
+        let mut ancestor_id = widget_id;
 
-                return true;
+        while ancestor_id != root_id {
+            if ancestor_id == 0 {
+                return false;
             }
+
+            ancestor_id = self.cache[ancestor_id as usize].parent_id;
         }
 
-        false
+        true
     }
 
-    /// Returns a borrowed slice of the `WidgetContainer` `Vec` object, which can be passed on to
-    /// `Layout` objects so that the layout can be computed and performed.
-    pub fn borrow_cache(&mut self) -> &[WidgetContainer] {
-        &self.cache
-    }
+    /// Hides or shows a `Widget`, along with all of its descendants.
+    fn set_subtree_hidden(&mut self, widget_id: i32, hidden: bool) {
+        {
+            let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
 
-    // Private functions
+            if hidden {
+                widget.get_config().hide();
+            } else {
+                widget.get_config().show();
+            }
 
-    fn get_children_of(&mut self, widget_id: i32) -> Vec<i32> {
-        self.cache
-            .iter()
-            .filter(|x| x.parent_id == widget_id)
-            .map(|x| x.widget_id)
-            .collect()
-    }
+            widget.set_invalidated(true);
+        }
 
-    fn draw(&mut self, widget_id: i32, c: &mut Canvas<Window>) {
-        let parents_of_widget = self.get_children_of(widget_id);
+        for child_id in self.get_children_of(widget_id) {
+            if child_id != widget_id {
+                self.set_subtree_hidden(child_id, hidden);
+            }
+        }
+    }
+
+    /// Draws the open popups of all visible `Widget`s onto the `Canvas`, above all `Layer`s.
+    fn draw_popups(&mut self, c: &mut Canvas<Window>) {
+        for i in 0..self.cache.len() {
+            if self.is_hidden(i as i32) {
+                continue;
+            }
 
-        if parents_of_widget.is_empty() {
+            let mut widget = self.cache[i].widget.borrow_mut();
+
+            if let Some(bounds) = widget.get_popup_bounds() {
+                if let Some(texture) = widget.draw_popup(c, &mut self.texture_cache) {
+                    c.copy(texture, None, bounds).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Shows the `Widget` specified by `widget_id`, along with all of its descendants, as a modal
+    /// dialog.  While a modal dialog is shown, the rest of the UI is dimmed, and all mouse and
+    /// keyboard events are sent to the `Widget`s of the dialog.  Open popups are closed, and keyboard
+    /// focus is cleared if it lies outside of the dialog.  Modal dialogs can be stacked, in which
+    /// case the dialog shown last receives all events.
+    pub fn show_modal(&mut self, widget_id: i32) {
+        if widget_id <= 0 || self.modal_stack.contains(&widget_id) {
             return;
         }
 
-        for paint_id in &parents_of_widget {
-            let paint_widget = &mut self.cache[*paint_id as usize];
-            let is_hidden = paint_widget.widget.borrow_mut().get_config().is_hidden();
-            let is_enabled = paint_widget.widget.borrow_mut().get_config().is_enabled();
-            let widget_x = paint_widget.widget.borrow_mut().get_config().to_x(0);
-            let widget_y = paint_widget.widget.borrow_mut().get_config().to_y(0);
-            let widget_w = paint_widget
+        self.set_subtree_hidden(widget_id, false);
+        self.modal_stack.push(widget_id);
+        self.close_popups_except(widget_id);
+
+        if !self.is_in_modal(self.focused_widget_id) {
+            self.set_focus(0);
+        }
+
+        self.cache[0].widget.borrow_mut().set_invalidated(true);
+    }
+
+    /// Closes the modal dialog whose root `Widget` is specified by `widget_id`, hiding it along with
+    /// all of its descendants.  Returns `false` if the `Widget` is not a modal dialog.
+    pub fn close_modal(&mut self, widget_id: i32) -> bool {
+        let position = match self.modal_stack.iter().position(|id| *id == widget_id) {
+            Some(position) => position,
+            None => return false,
+        };
+
+        self.modal_stack.remove(position);
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .config
+            .remove(&CONFIG_DIALOG_RESULT);
+
+        if self.is_in_subtree(self.focused_widget_id, widget_id) {
+            self.set_focus(0);
+        }
+
+        self.set_subtree_hidden(widget_id, true);
+        self.cache[0].widget.borrow_mut().set_invalidated(true);
+
+        true
+    }
+
+    /// Retrieves the ID of the root `Widget` of the modal dialog that receives all events.  Returns
+    /// `0` if no modal dialog is shown.
+    pub fn get_modal(&self) -> i32 {
+        self.modal_stack.last().cloned().unwrap_or(0)
+    }
+
+    /// Sets the `Color` that is drawn over the UI beneath a modal dialog.  The alpha channel of the
+    /// `Color` controls how much the UI is dimmed.
+    pub fn set_modal_dim_color(&mut self, color: Color) {
+        self.modal_dim_color = color;
+        self.cache[0].widget.borrow_mut().set_invalidated(true);
+    }
+
+    /// Returns the ID of the root `Widget` of a modal dialog that has requested to be closed, by
+    /// setting `CONFIG_DIALOG_RESULT`, along with the result it requested, if any.
+    pub fn find_dialog_result(&self) -> Option<(i32, i32)> {
+        self.modal_stack.iter().rev().find_map(|id| {
+            let mut widget = self.cache[*id as usize].widget.borrow_mut();
+
+            if widget
+                .get_config()
+                .config
+                .contains_key(&CONFIG_DIALOG_RESULT)
+            {
+                Some((*id, widget.get_config().get_numeric(CONFIG_DIALOG_RESULT)))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// This function calls the `mouse_moved` callback for the `Widget` specified by `widget_id`.
+    pub fn mouse_moved(&mut self, widget_id: i32, points: Vec<i32>, cache: &[LayoutContainer]) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .mouse_moved(&self.cache, cache, points);
+        }
+    }
+
+    /// Indicates whether or not the `Widget` specified by `widget_id` captures the mouse while a
+    /// mouse button that was pressed over it is held.  See `Widget::captures_mouse`.
+    pub fn captures_mouse(&self, widget_id: i32) -> bool {
+        widget_id > 0
+            && !self.is_hidden(widget_id)
+            && self.cache[widget_id as usize]
                 .widget
                 .borrow_mut()
-                .get_config()
-                .get_size(CONFIG_SIZE)[0];
-            let widget_h = paint_widget
+                .captures_mouse()
+    }
+
+    /// Indicates whether or not pressing a mouse button over the `Widget` specified by `widget_id`
+    /// moves the keyboard focus to it.  See `Widget::takes_focus_on_click`.
+    pub fn takes_focus_on_click(&self, widget_id: i32) -> bool {
+        widget_id <= 0
+            || self.is_hidden(widget_id)
+            || self.cache[widget_id as usize]
                 .widget
                 .borrow_mut()
-                .get_config()
-                .get_size(CONFIG_SIZE)[1];
+                .takes_focus_on_click()
+    }
+
+    /// Collects the events synthesized by all `Widget`s since they were last collected, in the
+    /// order of the display list.  See `Widget::take_synthesized_events`.
+    pub fn take_synthesized_events(&mut self) -> Vec<Event> {
+        self.cache
+            .iter()
+            .flat_map(|container| container.widget.borrow_mut().take_synthesized_events())
+            .collect()
+    }
+
+    /// This function calls the `mouse_scrolled` callback for the `Widget` specified by `widget_id`.
+    pub fn mouse_scrolled(&mut self, widget_id: i32, points: Vec<i32>, cache: &[LayoutContainer]) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .mouse_scrolled(&self.cache, cache, points);
+        }
+    }
+
+    /// This function calls the `mouse_exited` callback for the `Widget` specified by `widget_id`.
+    pub fn mouse_exited(&mut self, widget_id: i32, cache: &[LayoutContainer]) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .mouse_exited(&self.cache, cache);
+        }
+    }
+
+    /// This function calls the `mouse_entered` callback for the `Widget` specified by `widget_id`.
+    pub fn mouse_entered(&mut self, widget_id: i32, cache: &[LayoutContainer]) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .mouse_entered(&self.cache, cache);
+        }
+    }
+
+    /// This function calls the `tick` method on all registered `Widget`s in the cache.  The purpose
+    /// for the `tick` is to indicate that a drawing loop is about to occur, and the `Widget` can
+    /// update itself as necessary beforehand.  If more than one tick thread has been configured,
+    /// `Widget`s that implement `ParallelTick` have their thread-safe work computed in parallel
+    /// first.  Afterwards, `Widget`s that have set `CONFIG_REMOVE` are removed.
+    pub fn tick(&mut self, _cache: &[LayoutContainer]) {
+        let cache_size = self.cache.len();
+
+        if let Some(pool) = self.tick_pool.clone() {
+            self.parallel_tick(&pool);
+        }
+
+        for i in 0..cache_size {
+            if !self.is_hidden(i as i32) {
+                self.cache[i].widget.borrow_mut().tick(&self.cache, _cache);
+            }
+        }
+
+        self.remove_requested_widgets();
+    }
+
+    /// Sets the number of threads used to compute `ParallelTick` and `ParallelLayout` work each
+    /// frame.  The threads are kept in a pool that lasts until the number of threads is changed.  A
+    /// value of `1` (the default) performs all work on the main thread, as does a pool that cannot
+    /// be started.
+    pub fn set_tick_threads(&mut self, threads: usize) {
+        self.tick_pool = if threads > 1 {
+            ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .ok()
+                .map(Arc::new)
+        } else {
+            None
+        };
+    }
+
+    /// Retrieves the pool of threads set by `set_tick_threads`, if more than one thread was set.
+    pub fn get_tick_pool(&self) -> Option<Arc<ThreadPool>> {
+        self.tick_pool.clone()
+    }
+
+    /// This function sends all other un-handled events from SDL2 to the currently highlighted
+    /// `Widget`.
+    pub fn other_event(&mut self, widget_id: i32, event: Event, cache: &[LayoutContainer]) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .other_event(&self.cache, cache, event);
+        }
+    }
+
+    /// This function calls the `file_dropped` method of the `Widget` specified by `widget_id`, if
+    /// it is visible and enabled.  Returns `true` if the `Widget` handled the file.
+    pub fn file_dropped(
+        &mut self,
+        widget_id: i32,
+        path: String,
+        cache: &[LayoutContainer],
+    ) -> bool {
+        !self.is_hidden(widget_id)
+            && self.is_enabled(widget_id)
+            && self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .file_dropped(&self.cache, cache, path)
+    }
+
+    /// This function calls the `text_dropped` method of the `Widget` specified by `widget_id`, if
+    /// it is visible and enabled.  Returns `true` if the `Widget` handled the text.
+    pub fn text_dropped(
+        &mut self,
+        widget_id: i32,
+        text: String,
+        cache: &[LayoutContainer],
+    ) -> bool {
+        !self.is_hidden(widget_id)
+            && self.is_enabled(widget_id)
+            && self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .text_dropped(&self.cache, cache, text)
+    }
+
+    /// This function calls the `key_pressed` method of the `Widget` specified by `widget_id`, if
+    /// it is visible and enabled.
+    pub fn key_pressed(
+        &mut self,
+        widget_id: i32,
+        keycode: Keycode,
+        keymod: Mod,
+        repeat: bool,
+        cache: &[LayoutContainer],
+    ) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .key_pressed(&self.cache, cache, keycode, keymod, repeat);
+        }
+    }
+
+    /// This function calls the `key_released` method of the `Widget` specified by `widget_id`, if
+    /// it is visible and enabled.
+    pub fn key_released(
+        &mut self,
+        widget_id: i32,
+        keycode: Keycode,
+        keymod: Mod,
+        cache: &[LayoutContainer],
+    ) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .key_released(&self.cache, cache, keycode, keymod, false);
+        }
+    }
+
+    /// This function calls the `text_input` method of the `Widget` specified by `widget_id`, if it
+    /// is visible and enabled.
+    pub fn text_input(&mut self, widget_id: i32, text: String, cache: &[LayoutContainer]) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .text_input(&self.cache, cache, text);
+        }
+    }
+
+    /// This function calls the `text_editing` method of the `Widget` specified by `widget_id`, if
+    /// it is visible and enabled.
+    pub fn text_editing(
+        &mut self,
+        widget_id: i32,
+        text: String,
+        start: i32,
+        length: i32,
+        cache: &[LayoutContainer],
+    ) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .text_editing(&self.cache, cache, text, start, length);
+        }
+    }
 
-            if !is_hidden {
-                match paint_widget
+    /// Returns the bounds of the `Widget` specified by `widget_id` if it accepts text input, or
+    /// `None` if it does not, or is hidden or disabled.
+    pub fn get_text_input_bounds(&self, widget_id: i32) -> Option<Rect> {
+        if widget_id <= 0 || self.is_hidden(widget_id) || !self.is_enabled(widget_id) {
+            return None;
+        }
+
+        let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
+
+        if widget.accepts_text_input() {
+            Some(widget.get_drawing_area())
+        } else {
+            None
+        }
+    }
+
+    /// This function calls the `custom_event` method on all visible, enabled `Widget`s, in the
+    /// order in which they were added.
+    pub fn custom_event(&mut self, event: &dyn Any, cache: &[LayoutContainer]) {
+        for i in 0..self.cache.len() as i32 {
+            if !self.is_hidden(i) && self.is_enabled(i) {
+                self.cache[i as usize]
                     .widget
                     .borrow_mut()
-                    .draw(c, &mut self.texture_cache)
-                {
-                    Some(texture) => {
-                        c.copy(
+                    .custom_event(&self.cache, cache, event);
+            }
+        }
+    }
+
+    /// This function resizes the top-level `Widget` to the new `size` of the window, then calls the
+    /// `window_resized` method on all registered `Widget`s, including hidden ones, so that they can
+    /// follow the edges of the window.
+    pub fn window_resized(&mut self, size: Size, cache: &[LayoutContainer]) {
+        {
+            let mut base_widget = self.cache[0].widget.borrow_mut();
+
+            base_widget
+                .get_config()
+                .set_size(CONFIG_SIZE, size[SIZE_WIDTH], size[SIZE_HEIGHT]);
+            base_widget.get_config().set_invalidated(true);
+        }
+
+        for i in 0..self.cache.len() {
+            self.cache[i]
+                .widget
+                .borrow_mut()
+                .window_resized(&self.cache, cache, size.clone());
+        }
+    }
+
+    /// This function performs the draw loop for all of the `Widget`s stored in the `cache`.  Each
+    /// `Widget` receives a mutable reference to the `Canvas` so that the `Widget` can be drawn on
+    /// the screen during the draw loop of the `Engine`.  This `draw_loop` function automatically
+    /// clips the screen area so that the `Widget` cannot draw outside of its bounds.  `Widget`s are
+    /// drawn to the texture of their `Layer`, and only `Layer`s containing an invalidated `Widget`
+    /// are redrawn, before all `Layer`s are composited onto the `Canvas`.  Returns `true` if the
+    /// display loop needs to refresh the top-level canvas, `false` otherwise.
+    pub fn draw_loop(&mut self, c: &mut Canvas<Window>) -> bool {
+        let cache_size = self.cache.len();
+        let mut dirty_layers = [false; LAYER_COUNT];
+
+        self.widgets_drawn = 0;
+        self.release_hidden_textures();
+        self.update_viewports();
+
+        if self.ensure_layer_textures(c) {
+            dirty_layers = [true; LAYER_COUNT];
+        }
+
+        for i in 0..cache_size {
+            if self.needs_redraw(i as i32) {
+                let layer = self.get_layer(i as i32);
+
+                dirty_layers[layer.index()] = true;
+
+                if self.cache[i].on_screen {
+                    dirty_layers[self.cache[i].drawn_layer.index()] = true;
+                }
+            }
+        }
+
+        if !dirty_layers.iter().any(|dirty| *dirty) {
+            return false;
+        }
+
+        self.texture_cache.begin_frame();
+
+        for layer in Layer::all().iter() {
+            if dirty_layers[layer.index()] {
+                c.with_texture_canvas(
+                    self.layer_textures[layer.index()].as_mut().unwrap(),
+                    |texture| {
+                        texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                        texture.clear();
+                    },
+                )
+                .unwrap();
+
+                self.draw(0, *layer, DrawTarget::Layer(*layer), c);
+            }
+        }
+
+        c.set_draw_color(Color::RGB(0, 0, 0));
+        c.clear();
+
+        for layer_texture in self.layer_textures.iter().flatten() {
+            c.copy(layer_texture, None, None).unwrap();
+        }
+
+        self.draw_modals(c);
+        self.draw_popups(c);
+        self.draw_focus_indicator(c);
+
+        true
+    }
+
+    /// Retrieves the `Layer` that the `Widget` specified by `widget_id` is drawn on.
+    pub fn get_layer(&self, widget_id: i32) -> Layer {
+        let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
+
+        if widget.get_config().config.contains_key(&CONFIG_LAYER) {
+            Layer::from_numeric(widget.get_config().get_numeric(CONFIG_LAYER))
+        } else if widget_id == 0 {
+            Layer::Background
+        } else {
+            Layer::Content
+        }
+    }
+
+    /// Returns the bounds of all visible `Widget`s, other than the base `Widget`.  Hidden `Widget`s,
+    /// and `Widget`s that lie entirely outside of their parents, are excluded.
+    pub fn get_visible_bounds(&self) -> Vec<Rect> {
+        (1..self.cache.len() as i32)
+            .filter(|id| !self.is_hidden(*id) && !self.is_culled(*id))
+            .map(|id| self.widget_bounds(id))
+            .collect()
+    }
+
+    /// Returns the estimated number of bytes of GPU memory used by all `Widget` backing textures and
+    /// images stored in the `TextureCache`.
+    pub fn get_texture_memory(&self) -> u64 {
+        widget_texture_memory() + self.texture_cache.get_memory_size()
+    }
+
+    /// Returns the estimated number of bytes of GPU memory used by the backing texture of the
+    /// `Widget` specified by `widget_id`.
+    pub fn get_widget_texture_memory(&self, widget_id: i32) -> u64 {
+        self.cache[widget_id as usize].get_texture_memory()
+    }
+
+    /// Sets the number of consecutive frames a `Widget` must be hidden before its `Texture`s are
+    /// released.  They are recreated when the `Widget` is shown again.  A value of `0` keeps the
+    /// `Texture`s of hidden `Widget`s allocated.
+    pub fn set_texture_release_frames(&mut self, frames: u32) {
+        self.texture_release_frames = frames;
+    }
+
+    /// Retrieves the `TextureCache` used to draw `Widget`s, which can be used to set its memory budget
+    /// and pin images.
+    pub fn get_texture_cache(&mut self) -> &mut TextureCache {
+        &mut self.texture_cache
+    }
+
+    /// Returns the number of `Widget`s drawn by the last call to `draw_loop`.
+    pub fn get_widgets_drawn(&self) -> u32 {
+        self.widgets_drawn
+    }
+
+    /// Returns a borrowed slice of the `WidgetContainer` `Vec` object, which can be passed on to
+    /// `Layout` objects so that the layout can be computed and performed.
+    pub fn borrow_cache(&mut self) -> &[WidgetContainer] {
+        &self.cache
+    }
+
+    /// Returns a borrowed slice of the `WidgetContainer` `Vec` object along with the `TextureCache`,
+    /// so that overlays (such as the `Inspector`) can be drawn on top of the `Widget`s.
+    pub fn borrow_cache_and_textures(&mut self) -> (&[WidgetContainer], &mut TextureCache) {
+        (&self.cache, &mut self.texture_cache)
+    }
+
+    // Private functions
+
+    /// Replaces the `Widget` in the slot of `widget_id` with a hidden placeholder, and frees the
+    /// slot for reuse, releasing everything that is stored for it.
+    fn free_slot(&mut self, widget_id: i32) {
+        let widget_name = self.cache[widget_id as usize].get_widget_name();
+
+        if self.modal_stack.contains(&widget_id) {
+            let container = &self.cache[widget_id as usize];
+            let result = container
+                .widget
+                .borrow_mut()
+                .get_config()
+                .get_numeric(CONFIG_DIALOG_RESULT);
+
+            self.modal_stack.retain(|id| *id != widget_id);
+            self.removed_modals.push((container.get_handle(), result));
+        }
+
+        if self.name_index.get(&widget_name) == Some(&widget_id) {
+            self.name_index.remove(&widget_name);
+        }
+
+        let mut placeholder = BaseWidget::new(make_points_origin(), make_size(0, 0));
+
+        placeholder.get_config().hide();
+        placeholder.get_config().disable();
+        placeholder.set_invalidated(false);
+
+        let generation = self.cache[widget_id as usize].generation.wrapping_add(1);
+        let mut container = WidgetContainer::new(
+            Box::new(placeholder),
+            "",
+            make_points_origin(),
+            widget_id,
+            0,
+        );
+
+        container.generation = generation;
+        container.free = true;
+        self.cache[widget_id as usize] = container;
+        self.free_slots.push(widget_id);
+        self.removed_widgets.push(widget_id);
+        release_composite(&mut self.composites, widget_id);
+        self.viewport_origins.remove(&widget_id);
+        self.effects.remove(&widget_id);
+        release_effect_texture(&mut self.effect_textures, widget_id);
+    }
+
+    fn get_children_of(&mut self, widget_id: i32) -> Vec<i32> {
+        self.cache
+            .iter()
+            .filter(|x| x.parent_id == widget_id)
+            .map(|x| x.widget_id)
+            .collect()
+    }
+
+    /// Creates the layer textures, or recreates them if the size of the top-level `Widget` has
+    /// changed.  Returns `true` if the layer textures were (re)created, and must all be redrawn.
+    fn ensure_layer_textures(&mut self, c: &mut Canvas<Window>) -> bool {
+        let size = self.cache[0]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .get_size(CONFIG_SIZE);
+        let layer_size = (size[0].max(1), size[1].max(1));
+
+        if self.layer_textures.iter().all(|texture| texture.is_some())
+            && self.layer_size == layer_size
+        {
+            return false;
+        }
+
+        for texture in self
+            .layer_textures
+            .drain(..)
+            .flatten()
+            .chain(self.modal_texture.take())
+        {
+            // Safety: layer textures and the modal texture are only referenced by the `WidgetCache`.
+            unsafe {
+                texture.destroy();
+            }
+        }
+
+        self.layer_size = layer_size;
+        self.layer_textures = Layer::all()
+            .iter()
+            .map(|_| {
+                let mut texture = c
+                    .create_texture_target(None, layer_size.0, layer_size.1)
+                    .unwrap();
+
+                texture.set_blend_mode(BlendMode::Blend);
+                Some(texture)
+            })
+            .collect();
+
+        true
+    }
+
+    fn draw(&mut self, widget_id: i32, layer: Layer, target: DrawTarget, c: &mut Canvas<Window>) {
+        for paint_id in self.get_children_of(widget_id) {
+            if paint_id != widget_id && self.modal_stack.contains(&paint_id) {
+                continue;
+            }
+
+            self.draw_widget(widget_id, paint_id, layer, target, c);
+        }
+    }
+
+    /// Draws the `Widget` specified by `paint_id`, a child of `widget_id`, to the `target`, if it is
+    /// on the given `layer`, followed by its children.
+    fn draw_widget(
+        &mut self,
+        widget_id: i32,
+        paint_id: i32,
+        layer: Layer,
+        target: DrawTarget,
+        c: &mut Canvas<Window>,
+    ) {
+        let (offset_x, offset_y) = target.offset();
+        let is_culled = self.is_culled(paint_id);
+        let on_layer = self.get_layer(paint_id) == layer;
+        let viewport = if paint_id != widget_id {
+            self.get_viewport(paint_id)
+        } else {
+            None
+        };
+        let is_composited =
+            paint_id != widget_id && viewport.is_none() && self.is_composited(paint_id);
+        let is_focused = self.focused_widget_id == paint_id;
+        let paint_widget = &mut self.cache[paint_id as usize];
+        let rotation = paint_widget
+            .widget
+            .borrow_mut()
+            .get_config()
+            .get_numeric(CONFIG_ROTATION) as f64;
+        let is_hidden = paint_widget.widget.borrow_mut().get_config().is_hidden();
+        let is_enabled = paint_widget.widget.borrow_mut().get_config().is_enabled();
+        let is_invalidated = paint_widget.widget.borrow_mut().is_invalidated();
+        let active_effects: Vec<Effect> = self
+            .effects
+            .get(&paint_id)
+            .map(|effects| {
+                effects
+                    .iter()
+                    .filter(|x| x.is_active(is_focused, is_enabled))
+                    .map(|x| x.effect)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let widget_x = paint_widget.widget.borrow_mut().get_config().to_x(0) - offset_x;
+        let widget_y = paint_widget.widget.borrow_mut().get_config().to_y(0) - offset_y;
+        let widget_w = paint_widget
+            .widget
+            .borrow_mut()
+            .get_config()
+            .get_size(CONFIG_SIZE)[0];
+        let widget_h = paint_widget
+            .widget
+            .borrow_mut()
+            .get_config()
+            .get_size(CONFIG_SIZE)[1];
+
+        if on_layer {
+            paint_widget.on_screen = !is_hidden && !is_culled;
+            paint_widget.drawn_layer = layer;
+        }
+
+        if on_layer && is_composited && paint_widget.on_screen {
+            self.draw_composited(paint_id, layer, c);
+
+            let composite = self.composites.remove(&paint_id).unwrap();
+            let target_texture = target.texture(
+                &mut self.layer_textures,
+                &mut self.composites,
+                &mut self.modal_texture,
+            );
+
+            c.with_texture_canvas(target_texture, |target_canvas| {
+                copy_rotated(
+                    target_canvas,
+                    &composite.texture,
+                    Rect::new(widget_x, widget_y, widget_w, widget_h),
+                    rotation,
+                );
+            })
+            .unwrap();
+
+            self.composites.insert(paint_id, composite);
+            return;
+        }
+
+        if on_layer && paint_widget.on_screen {
+            let memory_before = widget_texture_memory();
+            let target_texture = target.texture(
+                &mut self.layer_textures,
+                &mut self.composites,
+                &mut self.modal_texture,
+            );
+
+            match paint_widget
+                .widget
+                .borrow_mut()
+                .draw(c, &mut self.texture_cache)
+            {
+                Some(texture) if !active_effects.is_empty() => {
+                    let stale = match self.effect_textures.get(&paint_id) {
+                        Some(effect_texture) => {
+                            is_invalidated || effect_texture.active != active_effects
+                        }
+                        None => true,
+                    };
+
+                    if stale {
+                        match render_effects(c, texture, &active_effects) {
+                            Ok((effect_texture, padding)) => {
+                                release_effect_texture(&mut self.effect_textures, paint_id);
+                                self.effect_textures.insert(
+                                    paint_id,
+                                    EffectTexture {
+                                        texture: effect_texture,
+                                        padding,
+                                        active: active_effects.clone(),
+                                    },
+                                );
+                            }
+                            Err(e) => {
+                                eprintln!("Unable to apply effects: ID={}: {}", paint_id, e);
+                                release_effect_texture(&mut self.effect_textures, paint_id);
+                            }
+                        }
+                    }
+
+                    let (effect_texture, padding) = match self.effect_textures.get(&paint_id) {
+                        Some(effect_texture) => (&effect_texture.texture, effect_texture.padding),
+                        None => (texture, 0),
+                    };
+
+                    c.with_texture_canvas(target_texture, |target_canvas| {
+                        copy_rotated(
+                            target_canvas,
+                            effect_texture,
+                            Rect::new(
+                                widget_x - padding as i32,
+                                widget_y - padding as i32,
+                                widget_w + padding * 2,
+                                widget_h + padding * 2,
+                            ),
+                            rotation,
+                        );
+                    })
+                    .unwrap();
+                }
+                Some(texture) => {
+                    release_effect_texture(&mut self.effect_textures, paint_id);
+
+                    c.with_texture_canvas(target_texture, |target_canvas| {
+                        copy_rotated(
+                            target_canvas,
                             texture,
-                            None,
                             Rect::new(widget_x, widget_y, widget_w, widget_h),
-                        )
-                        .unwrap();
+                            rotation,
+                        );
+                    })
+                    .unwrap();
+                }
+                None => eprintln!("No texture presented: ID={}", paint_id),
+            };
+
+            paint_widget.widget.borrow_mut().set_invalidated(false);
+            paint_widget.texture_memory = (paint_widget.texture_memory + widget_texture_memory())
+                .saturating_sub(memory_before);
+            self.widgets_drawn += 1;
+        }
+
+        if let Some(viewport) = viewport {
+            if on_layer && self.cache[paint_id as usize].on_screen {
+                self.draw_viewport(paint_id, viewport, layer, target, c);
+            }
+        } else if paint_id != widget_id {
+            self.draw(paint_id, layer, target, c);
+        }
+
+        if on_layer && !is_enabled {
+            let target_texture = target.texture(
+                &mut self.layer_textures,
+                &mut self.composites,
+                &mut self.modal_texture,
+            );
+
+            c.with_texture_canvas(target_texture, |target_canvas| {
+                target_canvas.set_draw_color(Color::RGBA(0, 0, 0, 128));
+                target_canvas
+                    .draw_rect(Rect::new(widget_x, widget_y, widget_w, widget_h))
+                    .unwrap();
+            })
+            .unwrap();
+        }
+    }
+
+    /// Indicates whether or not a `Widget` caches the composed texture of its subtree, as set by
+    /// `CONFIG_COMPOSITED`.
+    fn is_composited(&self, widget_id: i32) -> bool {
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .get_toggle(CONFIG_COMPOSITED)
+    }
+
+    /// Indicates whether or not a `Widget`, or any of its descendants, need to be redrawn.
+    fn is_subtree_dirty(&self, widget_id: i32) -> bool {
+        if self.needs_redraw(widget_id) {
+            return true;
+        }
+
+        self.cache
+            .iter()
+            .filter(|x| x.parent_id == widget_id && x.widget_id != widget_id)
+            .any(|x| self.is_subtree_dirty(x.widget_id))
+    }
+
+    /// Draws a composited container and its subtree into the container's cached composite texture.
+    /// If neither the container nor any of its descendants have changed, and the container has not
+    /// been resized, the cached texture is reused without drawing anything.
+    fn draw_composited(&mut self, widget_id: i32, layer: Layer, c: &mut Canvas<Window>) {
+        let bounds = self.widget_bounds(widget_id);
+        let size_changed = match self.composites.get(&widget_id) {
+            Some(composite) => composite.size != (bounds.width(), bounds.height()),
+            None => true,
+        };
+
+        if !size_changed && !self.is_subtree_dirty(widget_id) {
+            return;
+        }
+
+        if size_changed {
+            release_composite(&mut self.composites, widget_id);
+
+            let mut texture = c
+                .create_texture_target(None, bounds.width(), bounds.height())
+                .unwrap();
+
+            texture.set_blend_mode(BlendMode::Blend);
+            self.composites.insert(
+                widget_id,
+                Composite {
+                    texture,
+                    size: (bounds.width(), bounds.height()),
+                },
+            );
+        }
+
+        c.with_texture_canvas(
+            &mut self.composites.get_mut(&widget_id).unwrap().texture,
+            |texture| {
+                texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                texture.clear();
+            },
+        )
+        .unwrap();
+
+        self.draw(
+            widget_id,
+            layer,
+            DrawTarget::Composite(widget_id, bounds.x(), bounds.y()),
+            c,
+        );
+    }
+
+    /// Draws the children of a viewport into the texture of its content, and copies the visible area
+    /// of the content to the `target`.  The content is only redrawn if one of the viewport's
+    /// descendants has changed, or the content has been resized, so scrolling only copies a different
+    /// area of the cached content.
+    fn draw_viewport(
+        &mut self,
+        widget_id: i32,
+        viewport: Viewport,
+        layer: Layer,
+        target: DrawTarget,
+        c: &mut Canvas<Window>,
+    ) {
+        let content_size = (
+            viewport.content_width.max(1),
+            viewport.content_height.max(1),
+        );
+        let size_changed = match self.composites.get(&widget_id) {
+            Some(composite) => composite.size != content_size,
+            None => true,
+        };
+        let content_dirty = self
+            .get_children_of(widget_id)
+            .iter()
+            .any(|child_id| self.is_subtree_dirty(*child_id));
+
+        if size_changed {
+            release_composite(&mut self.composites, widget_id);
+
+            let mut texture = c
+                .create_texture_target(None, content_size.0, content_size.1)
+                .unwrap();
+
+            texture.set_blend_mode(BlendMode::Blend);
+            self.composites.insert(
+                widget_id,
+                Composite {
+                    texture,
+                    size: content_size,
+                },
+            );
+        }
+
+        if size_changed || content_dirty {
+            let (content_x, content_y) = self.content_origin(widget_id, &viewport);
+
+            c.with_texture_canvas(
+                &mut self.composites.get_mut(&widget_id).unwrap().texture,
+                |texture| {
+                    texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                    texture.clear();
+                },
+            )
+            .unwrap();
+
+            self.draw(
+                widget_id,
+                layer,
+                DrawTarget::Composite(widget_id, content_x, content_y),
+                c,
+            );
+        }
+
+        let visible = Rect::new(
+            viewport.scroll_x,
+            viewport.scroll_y,
+            viewport.bounds.width(),
+            viewport.bounds.height(),
+        );
+        let source = match visible.intersection(Rect::new(0, 0, content_size.0, content_size.1)) {
+            Some(source) => source,
+            None => return,
+        };
+        let bounds = self.widget_bounds(widget_id);
+        let (offset_x, offset_y) = target.offset();
+        let destination = Rect::new(
+            bounds.x() + viewport.bounds.x() + source.x() - viewport.scroll_x - offset_x,
+            bounds.y() + viewport.bounds.y() + source.y() - viewport.scroll_y - offset_y,
+            source.width(),
+            source.height(),
+        );
+        let composite = self.composites.remove(&widget_id).unwrap();
+        let target_texture = target.texture(
+            &mut self.layer_textures,
+            &mut self.composites,
+            &mut self.modal_texture,
+        );
+
+        c.with_texture_canvas(target_texture, |target_canvas| {
+            target_canvas
+                .copy(&composite.texture, source, destination)
+                .unwrap();
+        })
+        .unwrap();
+
+        self.composites.insert(widget_id, composite);
+    }
+
+    /// Returns the `Viewport` of a `Widget`, or `None` if the `Widget` is not a viewport.
+    fn get_viewport(&self, widget_id: i32) -> Option<Viewport> {
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .get_viewport()
+    }
+
+    /// Returns the window coordinates of the top-left corner of the content of a viewport.
+    fn content_origin(&self, widget_id: i32, viewport: &Viewport) -> (i32, i32) {
+        let bounds = self.widget_bounds(widget_id);
+
+        (
+            bounds.x() + viewport.bounds.x() - viewport.scroll_x,
+            bounds.y() + viewport.bounds.y() - viewport.scroll_y,
+        )
+    }
+
+    /// Returns the window coordinates that the children of a `Widget` are currently positioned
+    /// relative to: the top-left corner of its content if it is a viewport, or `(0, 0)` otherwise.
+    fn get_viewport_origin(&mut self, widget_id: i32) -> (i32, i32) {
+        if self.get_viewport(widget_id).is_none() {
+            return (0, 0);
+        }
+
+        *self.viewport_origins.entry(widget_id).or_insert((0, 0))
+    }
+
+    /// Moves the children of each viewport whose content has moved since its children were last
+    /// positioned, either because it scrolled, or because the viewport itself moved.
+    fn update_viewports(&mut self) {
+        for widget_id in 1..self.cache.len() as i32 {
+            if let Some(viewport) = self.get_viewport(widget_id) {
+                let (x, y) = self.content_origin(widget_id, &viewport);
+                let (applied_x, applied_y) = self.get_viewport_origin(widget_id);
+
+                if (x, y) != (applied_x, applied_y) {
+                    self.viewport_origins.insert(widget_id, (x, y));
+
+                    for child_id in self.get_children_of(widget_id) {
+                        self.move_widget(child_id, x - applied_x, y - applied_y);
                     }
-                    None => eprintln!("No texture presented: ID={}", paint_id),
-                };
+                }
+            }
+        }
+    }
+
+    /// Moves a `Widget` and all of its descendants by `dx` and `dy`, along with the content of any
+    /// viewports among them.
+    fn move_widget(&mut self, widget_id: i32, dx: i32, dy: i32) {
+        if dx == 0 && dy == 0 {
+            return;
+        }
+
+        {
+            let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
+            let origin = widget.get_config().get_point(CONFIG_ORIGIN);
+
+            widget
+                .get_config()
+                .set_point(CONFIG_ORIGIN, origin[0] + dx, origin[1] + dy);
+        }
 
-                paint_widget.widget.borrow_mut().set_invalidated(false);
+        if let Some(origin) = self.viewport_origins.get_mut(&widget_id) {
+            origin.0 += dx;
+            origin.1 += dy;
+        }
+
+        for child_id in self.get_children_of(widget_id) {
+            if child_id != widget_id {
+                self.move_widget(child_id, dx, dy);
             }
+        }
+    }
 
-            if *paint_id != widget_id {
-                self.draw(*paint_id, c);
+    /// Indicates whether or not a point lies outside of the visible area of any viewport that
+    /// contains a `Widget`.
+    fn is_clipped(&self, widget_id: i32, x: i32, y: i32) -> bool {
+        let mut parent_id = self.cache[widget_id as usize].parent_id;
+
+        while parent_id != 0 {
+            if let Some(viewport) = self.get_viewport(parent_id) {
+                let bounds = self.widget_bounds(parent_id);
+                let visible = Rect::new(
+                    bounds.x() + viewport.bounds.x(),
+                    bounds.y() + viewport.bounds.y(),
+                    viewport.bounds.width(),
+                    viewport.bounds.height(),
+                );
+
+                if !visible.contains_point((x, y)) {
+                    return true;
+                }
             }
 
-            if !is_enabled {
-                c.set_draw_color(Color::RGBA(0, 0, 0, 128));
-                c.draw_rect(Rect::new(widget_x, widget_y, widget_w, widget_h))
-                    .unwrap();
+            parent_id = self.cache[parent_id as usize].parent_id;
+        }
+
+        false
+    }
+
+    fn parallel_tick(&mut self, pool: &ThreadPool) {
+        let mut widgets: Vec<(usize, RefMut<Box<dyn Widget>>)> = self
+            .cache
+            .iter()
+            .enumerate()
+            .filter_map(|(i, container)| {
+                let mut widget = container.widget.borrow_mut();
+
+                if widget.get_config().is_hidden() {
+                    None
+                } else {
+                    Some((i, widget))
+                }
+            })
+            .collect();
+        let mut tasks: Vec<(usize, &mut dyn ParallelTick)> = widgets
+            .iter_mut()
+            .filter_map(|(i, widget)| widget.as_parallel_tick().map(|task| (*i, task)))
+            .collect();
+
+        if tasks.is_empty() {
+            return;
+        }
+
+        let invalidated: Vec<usize> = pool.install(|| {
+            tasks
+                .par_iter_mut()
+                .filter_map(|(i, task)| if task.parallel_tick() { Some(*i) } else { None })
+                .collect()
+        });
+
+        drop(tasks);
+
+        for (i, widget) in widgets.iter_mut() {
+            if invalidated.contains(i) {
+                widget.set_invalidated(true);
+            }
+        }
+    }
+
+    fn release_hidden_textures(&mut self) {
+        if self.texture_release_frames == 0 {
+            return;
+        }
+
+        for container in self.cache.iter_mut() {
+            if !container.widget.borrow_mut().get_config().is_hidden() {
+                container.hidden_frames = 0;
+                continue;
+            }
+
+            container.hidden_frames = container.hidden_frames.saturating_add(1);
+
+            if container.hidden_frames == self.texture_release_frames
+                && container.texture_memory > 0
+            {
+                let memory_before = widget_texture_memory();
+
+                container.widget.borrow_mut().release_textures();
+                container.texture_memory = (container.texture_memory + widget_texture_memory())
+                    .saturating_sub(memory_before);
             }
         }
     }
 
+    fn draw_focus_indicator(&mut self, c: &mut Canvas<Window>) {
+        let focused_id = self.focused_widget_id;
+
+        if focused_id == 0 || self.is_hidden(focused_id) {
+            return;
+        }
+
+        let bounds = self.cache[focused_id as usize]
+            .widget
+            .borrow_mut()
+            .get_drawing_area();
+        let focus_color = self.theme.focus_color;
+        let focus_width = self.theme.focus_width as i32;
+
+        if self.theme.focus_style == FocusStyle::Glow {
+            c.set_blend_mode(BlendMode::Blend);
+        }
+
+        for i in 1..=focus_width {
+            let alpha = match self.theme.focus_style {
+                FocusStyle::Outline => 255,
+                FocusStyle::Glow => 255 - (255 * (i - 1) / focus_width),
+            };
+
+            c.set_draw_color(Color::RGBA(
+                focus_color.r,
+                focus_color.g,
+                focus_color.b,
+                alpha as u8,
+            ));
+            c.draw_rect(Rect::new(
+                bounds.x() - i,
+                bounds.y() - i,
+                bounds.width() + (i * 2) as u32,
+                bounds.height() + (i * 2) as u32,
+            ))
+            .unwrap();
+        }
+
+        c.set_blend_mode(BlendMode::None);
+    }
+
+    fn widget_bounds(&self, widget_id: i32) -> Rect {
+        let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
+        let origin = widget.get_config().get_point(CONFIG_ORIGIN);
+        let size = widget.get_config().get_size(CONFIG_SIZE);
+
+        Rect::new(origin[0], origin[1], size[0], size[1])
+    }
+
+    /// Indicates whether or not a `Widget` lies entirely outside of its viewport: the intersection
+    /// of the bounds of all of its parents, up to and including the top-level `Widget`, which covers
+    /// the window.  A parent that is a viewport `Widget` contributes the bounds of its content
+    /// instead, and ends the search, as its content is drawn whether or not it is scrolled into view.
+    fn is_culled(&self, widget_id: i32) -> bool {
+        if widget_id == 0 {
+            return false;
+        }
+
+        let bounds = self.widget_bounds(widget_id);
+        let mut parent_id = self.cache[widget_id as usize].parent_id;
+        let mut viewport = self.clip_bounds(parent_id);
+
+        while parent_id != 0 && self.get_viewport(parent_id).is_none() {
+            parent_id = self.cache[parent_id as usize].parent_id;
+
+            match viewport.intersection(self.clip_bounds(parent_id)) {
+                Some(intersection) => viewport = intersection,
+                None => return true,
+            }
+        }
+
+        !viewport.has_intersection(bounds)
+    }
+
+    /// Returns the bounds that the children of a `Widget` are culled against: the content of a
+    /// viewport, or the bounds of any other `Widget`.
+    fn clip_bounds(&self, widget_id: i32) -> Rect {
+        match self.get_viewport(widget_id) {
+            Some(viewport) => {
+                let (x, y) = self.content_origin(widget_id, &viewport);
+
+                Rect::new(
+                    x,
+                    y,
+                    viewport.content_width.max(1),
+                    viewport.content_height.max(1),
+                )
+            }
+            None => self.widget_bounds(widget_id),
+        }
+    }
+
+    /// Indicates whether or not a `Widget`'s invalidation requires the display list to be redrawn.
+    /// Invalidated `Widget`s that are hidden or culled are only redrawn if they were visible the
+    /// last time the display list was drawn, so that their area is cleared.
+    fn needs_redraw(&self, widget_id: i32) -> bool {
+        let container = &self.cache[widget_id as usize];
+
+        if !container.widget.borrow_mut().is_invalidated() {
+            return false;
+        }
+
+        container.on_screen || !(self.is_hidden(widget_id) || self.is_culled(widget_id))
+    }
+
+    fn is_focusable(&self, widget_id: i32) -> bool {
+        let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
+
+        widget.get_config().get_toggle(CONFIG_FOCUSABLE)
+            && !widget.get_config().is_hidden()
+            && widget.get_config().is_enabled()
+    }
+
     fn is_hidden(&self, widget_id: i32) -> bool {
         self.cache[widget_id as usize]
             .widget
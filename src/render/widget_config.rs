@@ -15,6 +15,7 @@
 
 use crate::render::{Points, Size};
 use sdl2::pixels::Color;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// `Widget` Base `Color` key for `colors` `HashMap`.  This is the base fill color of a `Widget`
@@ -70,9 +71,161 @@ pub const CONFIG_FONT_SIZE: u8 = 12;
 /// `PushButtonWidget` selected state.  This is stored as a `Config::Toggle` value.
 pub const CONFIG_SELECTED_STATE: u8 = 13;
 
+/// `Widget` focusable flag.  When set, the `Widget` receives keyboard focus when clicked, and the
+/// `Engine` draws a focus indicator around it.  This is stored as a `Config::Toggle` value.
+pub const CONFIG_FOCUSABLE: u8 = 14;
+
+/// Delay in milliseconds before a held key or button starts auto-repeating.  This is applied from the
+/// `Engine`'s `InputSettings`, and is stored as a `Config::Numeric` value.
+pub const CONFIG_KEY_REPEAT_DELAY: u8 = 15;
+
+/// Interval in milliseconds between auto-repeats of a held key or button.  This is applied from the
+/// `Engine`'s `InputSettings`, and is stored as a `Config::Numeric` value.
+pub const CONFIG_KEY_REPEAT_INTERVAL: u8 = 16;
+
+/// Interval in milliseconds between text caret blinks, `0` to turn blinking off.  This is applied
+/// from the `Engine`'s `InputSettings`, and is stored as a `Config::Numeric` value.
+pub const CONFIG_CARET_BLINK_RATE: u8 = 17;
+
+/// The rendering `Layer` a `Widget` is drawn on (see `pushrod::render::layer::Layer`).  If not set,
+/// the top-level `Widget` is drawn on the `Background` layer, and all others on the `Content` layer.
+/// This is stored as a `Config::Numeric` value.
+pub const CONFIG_LAYER: u8 = 18;
+
+/// Container compositing flag.  When set, the `Widget` and its children are drawn into a cached
+/// texture, which is reused when the container is redrawn (such as when it is moved), until the
+/// container or one of its children is invalidated.  This is stored as a `Config::Toggle` value.
+pub const CONFIG_COMPOSITED: u8 = 19;
+
+/// Length in pixels of the thumb of a slider or scrollbar, along its direction of travel.  This is
+/// stored as a `Config::Numeric` value.
+pub const CONFIG_THUMB_SIZE: u8 = 20;
+
+/// Shape of the thumb of a slider or scrollbar (see `pushrod::widgets::slider_widget::ThumbShape`).
+/// This is stored as a `Config::Numeric` value.
+pub const CONFIG_THUMB_SHAPE: u8 = 21;
+
+/// Thickness in pixels of the track of a slider or scrollbar.  This is stored as a
+/// `Config::Numeric` value.
+pub const CONFIG_TRACK_THICKNESS: u8 = 22;
+
+/// Path to the image drawn for the thumb of a slider or scrollbar when its shape is
+/// `ThumbShape::Image`.  This is stored as a `Config::Text` value.
+pub const CONFIG_THUMB_IMAGE: u8 = 23;
+
+/// `Color` of the thumb of a slider or scrollbar while the mouse is over the `Widget`.  The thumb is
+/// otherwise drawn in `CONFIG_COLOR_BASE`.  This is stored as a `Config::Color`.
+pub const CONFIG_COLOR_THUMB_HOVER: u8 = 24;
+
+/// `Color` of the thumb of a slider or scrollbar while it is being dragged.  This is stored as a
+/// `Config::Color`.
+pub const CONFIG_COLOR_THUMB_PRESSED: u8 = 25;
+
+/// `Color` of the track of a slider, scrollbar, or toggle switch.  This is stored as a
+/// `Config::Color`.
+pub const CONFIG_COLOR_TRACK: u8 = 26;
+
+/// Text of the tooltip shown by the `Engine` when the mouse rests over the `Widget`.  No tooltip is
+/// shown when this is blank.  This is stored as a `Config::Text` value.
+pub const CONFIG_TOOLTIP_TEXT: u8 = 27;
+
+/// Result of a modal dialog.  Setting this on the root `Widget` of a modal dialog requests that the
+/// `Engine` close the dialog, triggering the `on_dialog_closed` callback with this value.  This is
+/// stored as a `Config::Numeric` value.
+pub const CONFIG_DIALOG_RESULT: u8 = 28;
+
+/// Requests that the `WidgetCache` remove the `Widget` from the display list at the end of the
+/// current `tick`.  `Widget`s can set this on themselves, such as when a notification expires, or
+/// on other `Widget`s from within a callback.  This is stored as a `Config::Toggle` value.
+pub const CONFIG_REMOVE: u8 = 29;
+
+/// Mouse cursor shown by the `Engine` while the mouse is over the `Widget` (see
+/// `pushrod::render::cursor::MouseCursor`).  The arrow pointer is shown when this is not set.  This
+/// is stored as a `Config::Numeric` value.
+pub const CONFIG_CURSOR: u8 = 30;
+
+/// Alignment of text within the bounds of a `Widget`: the horizontal alignment of each line, and
+/// the vertical alignment of the block of text.  For example, `CompassPosition::NW` aligns text to
+/// the top left-hand corner, and `CompassPosition::Center` centers it both ways.  This is stored as
+/// a `Config::CompassPosition` value.
+pub const CONFIG_TEXT_ALIGN: u8 = 31;
+
+/// How text that is wider than a `Widget` is laid out (see
+/// `pushrod::widgets::text_widget::TextWrap`).  Text is wrapped at spaces when this is not set.
+/// This is stored as a `Config::Numeric` value.
+pub const CONFIG_TEXT_WRAP: u8 = 32;
+
+/// Angle in degrees, clockwise, by which the `Engine` rotates the texture of a `Widget` around its
+/// center when copying it to the screen.  Mouse events are still delivered within the unrotated
+/// bounds of the `Widget`, and the `Widget` must be invalidated after this is changed, so that it is
+/// drawn at its new angle.  This is stored as a `Config::Numeric` value.
+pub const CONFIG_ROTATION: u8 = 33;
+
+/// `Color` of the border of an input `Widget` whose value is invalid, such as a number that is out
+/// of range.  This is stored as a `Config::Color`.
+pub const CONFIG_COLOR_INVALID: u8 = 34;
+
+/// Position of a focusable `Widget` in the order that `Tab` moves the keyboard focus through.
+/// `Widget`s with a positive tab order are visited first, from the lowest to the highest, followed
+/// by `Widget`s with no tab order (`0`) in the order they were added.  `Widget`s with a negative tab
+/// order are skipped by `Tab`, but can still be focused by clicking.  This is stored as a
+/// `Config::Numeric` value.
+pub const CONFIG_TAB_ORDER: u8 = 35;
+
+/// Returns a human-readable name for a configuration key, used by debugging tools such as the
+/// `Inspector`.  Keys that are not defined by `Pushrod` are returned as `CONFIG_<number>`.
+pub fn config_key_name(key: u8) -> String {
+    let name = match key {
+        CONFIG_COLOR_BASE => "CONFIG_COLOR_BASE",
+        CONFIG_COLOR_HOVER => "CONFIG_COLOR_HOVER",
+        CONFIG_COLOR_BORDER => "CONFIG_COLOR_BORDER",
+        CONFIG_COLOR_TEXT => "CONFIG_COLOR_TEXT",
+        CONFIG_COLOR_SELECTED => "CONFIG_COLOR_SELECTED",
+        CONFIG_COLOR_SECONDARY => "CONFIG_COLOR_SECONDARY",
+        CONFIG_ORIGIN => "CONFIG_ORIGIN",
+        CONFIG_SIZE => "CONFIG_SIZE",
+        CONFIG_BORDER_WIDTH => "CONFIG_BORDER_WIDTH",
+        CONFIG_TEXT => "CONFIG_TEXT",
+        CONFIG_IMAGE_POSITION => "CONFIG_IMAGE_POSITION",
+        CONFIG_FONT_SIZE => "CONFIG_FONT_SIZE",
+        CONFIG_SELECTED_STATE => "CONFIG_SELECTED_STATE",
+        CONFIG_FOCUSABLE => "CONFIG_FOCUSABLE",
+        CONFIG_KEY_REPEAT_DELAY => "CONFIG_KEY_REPEAT_DELAY",
+        CONFIG_KEY_REPEAT_INTERVAL => "CONFIG_KEY_REPEAT_INTERVAL",
+        CONFIG_CARET_BLINK_RATE => "CONFIG_CARET_BLINK_RATE",
+        CONFIG_LAYER => "CONFIG_LAYER",
+        CONFIG_COMPOSITED => "CONFIG_COMPOSITED",
+        CONFIG_THUMB_SIZE => "CONFIG_THUMB_SIZE",
+        CONFIG_THUMB_SHAPE => "CONFIG_THUMB_SHAPE",
+        CONFIG_TRACK_THICKNESS => "CONFIG_TRACK_THICKNESS",
+        CONFIG_THUMB_IMAGE => "CONFIG_THUMB_IMAGE",
+        CONFIG_COLOR_THUMB_HOVER => "CONFIG_COLOR_THUMB_HOVER",
+        CONFIG_COLOR_THUMB_PRESSED => "CONFIG_COLOR_THUMB_PRESSED",
+        CONFIG_COLOR_TRACK => "CONFIG_COLOR_TRACK",
+        CONFIG_TOOLTIP_TEXT => "CONFIG_TOOLTIP_TEXT",
+        CONFIG_DIALOG_RESULT => "CONFIG_DIALOG_RESULT",
+        CONFIG_REMOVE => "CONFIG_REMOVE",
+        CONFIG_CURSOR => "CONFIG_CURSOR",
+        CONFIG_TEXT_ALIGN => "CONFIG_TEXT_ALIGN",
+        CONFIG_TEXT_WRAP => "CONFIG_TEXT_WRAP",
+        CONFIG_ROTATION => "CONFIG_ROTATION",
+        CONFIG_COLOR_INVALID => "CONFIG_COLOR_INVALID",
+        CONFIG_TAB_ORDER => "CONFIG_TAB_ORDER",
+        _ => return format!("CONFIG_{}", key),
+    };
+
+    String::from(name)
+}
+
+/// Returns the configuration key for a name returned by `config_key_name`, including names of the
+/// form `CONFIG_<number>`.  Returns `None` if the name is not recognized.
+pub fn config_key_from_name(name: &str) -> Option<u8> {
+    (0..=u8::MAX).find(|key| config_key_name(*key) == name)
+}
+
 /// This enum is used by the `ImageWidget`, which controls the positioning of the image being
 /// rendered within the bounds of the `Widget`.
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug, Copy, Serialize, Deserialize)]
 pub enum CompassPosition {
     /// Upper left-hand corner of the bounds.
     NW,
@@ -103,7 +256,7 @@ pub enum CompassPosition {
 }
 
 /// This struct stores padding constraints.
-#[derive(Clone, Default, Debug, Copy)]
+#[derive(Clone, Default, Debug, Copy, Serialize, Deserialize)]
 pub struct PaddingConstraint {
     pub top: i32,
     pub bottom: i32,
@@ -322,6 +475,14 @@ impl WidgetConfig {
         }
     }
 
+    /// Retrieves a `Color` for a configuration key.  Returns `default` if not set.
+    pub fn get_color_or(&self, k: u8, default: Color) -> Color {
+        match self.config.get(&k) {
+            Some(Config::Color(color)) => *color,
+            _ => default,
+        }
+    }
+
     /// Retrieves a numeric value for a configuration key.  Returns 0 if not set.
     pub fn get_numeric(&self, k: u8) -> i32 {
         match self.config.get(&k) {
@@ -330,6 +491,14 @@ impl WidgetConfig {
         }
     }
 
+    /// Retrieves a numeric value for a configuration key.  Returns `default` if not set.
+    pub fn get_numeric_or(&self, k: u8, default: i32) -> i32 {
+        match self.config.get(&k) {
+            Some(Config::Numeric(numeric)) => *numeric,
+            _ => default,
+        }
+    }
+
     /// Retrieves text for a configuration key.  Returns a blank string if not set.
     pub fn get_text(&self, k: u8) -> String {
         match self.config.get(&k) {
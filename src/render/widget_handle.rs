@@ -0,0 +1,100 @@
+// Pushrod Rendering Library
+// Generational Widget Handles
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+/// This is a handle to a `Widget` stored in the `WidgetCache`, returned by `add_widget`.  It
+/// combines the `Widget`'s slot index (its ID) with a generation counter, which is incremented each
+/// time the slot is freed by `remove_widget`.  A handle to a `Widget` that has since been removed is
+/// stale, and is rejected by the `WidgetCache`, rather than silently referring to whichever `Widget`
+/// reused the slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WidgetHandle {
+    index: i32,
+    generation: u32,
+}
+
+/// This is the implementation of the `WidgetHandle`.
+impl WidgetHandle {
+    /// Creates a new `WidgetHandle` for the given slot index and generation.
+    pub fn new(index: i32, generation: u32) -> Self {
+        Self { index, generation }
+    }
+
+    /// Retrieves the numeric ID of the `Widget`, for use with functions that take a raw `Widget` ID,
+    /// such as `Layout::append_widget`.
+    pub fn id(&self) -> i32 {
+        self.index
+    }
+
+    /// Retrieves the generation of the slot at the time this handle was created.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl From<WidgetHandle> for i32 {
+    fn from(handle: WidgetHandle) -> Self {
+        handle.index
+    }
+}
+
+impl fmt::Display for WidgetHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+/// This is the error returned when a `WidgetHandle` cannot be used to access a `Widget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WidgetHandleError {
+    /// The handle refers to a slot that does not exist in the `WidgetCache`.
+    InvalidIndex(WidgetHandle),
+
+    /// The `Widget` the handle referred to has been removed.
+    Stale(WidgetHandle),
+
+    /// The handle refers to the top-level base `Widget`, which cannot be removed or re-parented.
+    BaseWidget,
+
+    /// Re-parenting the `Widget` the handle refers to would make it an ancestor of itself.
+    CyclicParent(WidgetHandle),
+}
+
+impl fmt::Display for WidgetHandleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WidgetHandleError::InvalidIndex(handle) => {
+                write!(f, "Widget handle {} does not refer to a Widget", handle)
+            }
+            WidgetHandleError::Stale(handle) => {
+                write!(f, "Widget handle {} refers to a removed Widget", handle)
+            }
+            WidgetHandleError::BaseWidget => {
+                write!(f, "The base Widget cannot be removed or re-parented")
+            }
+            WidgetHandleError::CyclicParent(handle) => {
+                write!(
+                    f,
+                    "Widget handle {} cannot be made a child of itself",
+                    handle
+                )
+            }
+        }
+    }
+}
+
+impl Error for WidgetHandleError {}
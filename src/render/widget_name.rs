@@ -0,0 +1,146 @@
+// Pushrod Rendering Library
+// Interned Widget Names
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+thread_local! {
+    static INTERNED_NAMES: RefCell<HashSet<Rc<str>>> = RefCell::new(HashSet::new());
+}
+
+/// This is an interned `Widget` name.  Names are stored once per thread while they are in use, so
+/// cloning a `WidgetName` only increments a reference count, rather than allocating a new `String`.  A `WidgetName` can be
+/// compared directly against `&str` and `String` values.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WidgetName(Rc<str>);
+
+/// This is the implementation of the `WidgetName`.
+impl WidgetName {
+    /// Creates a `WidgetName` from a `&str`, reusing the existing interned copy if the name has been
+    /// seen before.
+    pub fn new(name: &str) -> Self {
+        INTERNED_NAMES.with(|names| {
+            let mut names = names.borrow_mut();
+
+            match names.get(name) {
+                Some(interned) => WidgetName(interned.clone()),
+                None => {
+                    let interned: Rc<str> = Rc::from(name);
+
+                    names.insert(interned.clone());
+                    WidgetName(interned)
+                }
+            }
+        })
+    }
+
+    /// Returns the name as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Removes the name from the interned names when the last `WidgetName` referring to it is dropped,
+/// so that the names of removed `Widget`s are not kept for the life of the thread.
+impl Drop for WidgetName {
+    fn drop(&mut self) {
+        // The only other reference is the interned copy.
+        if Rc::strong_count(&self.0) == 2 {
+            let _ = INTERNED_NAMES.try_with(|names| {
+                if let Ok(mut names) = names.try_borrow_mut() {
+                    names.remove(&*self.0);
+                }
+            });
+        }
+    }
+}
+
+impl Deref for WidgetName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for WidgetName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for WidgetName {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for WidgetName {
+    fn from(name: &str) -> Self {
+        WidgetName::new(name)
+    }
+}
+
+impl From<String> for WidgetName {
+    fn from(name: String) -> Self {
+        WidgetName::new(&name)
+    }
+}
+
+impl From<&String> for WidgetName {
+    fn from(name: &String) -> Self {
+        WidgetName::new(name)
+    }
+}
+
+impl From<WidgetName> for String {
+    fn from(name: WidgetName) -> Self {
+        name.0.to_string()
+    }
+}
+
+impl PartialEq<str> for WidgetName {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for WidgetName {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for WidgetName {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl fmt::Display for WidgetName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Debug for WidgetName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
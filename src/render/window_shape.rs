@@ -0,0 +1,196 @@
+// Pushrod Rendering Library
+// Shaped and Transparent Windows
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rect::Rect;
+use sdl2::surface::Surface;
+use sdl2::sys;
+use sdl2::video::Window;
+use sdl2::VideoSubsystem;
+use std::ffi::CString;
+
+/// This describes the shape of a borderless window.  Pixels outside of the shape are not drawn,
+/// and mouse clicks on them pass through to whatever is underneath the window.
+///
+/// SDL2 does not support per-pixel alpha blending of a window with the desktop, so the shape is
+/// binary: each pixel is either part of the window, or it is not.  Use `Engine::set_window_opacity`
+/// to make the entire window translucent, where the platform allows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowShape {
+    /// When `true`, the shape is the union of the bounds of all visible `Widget`s, other than the
+    /// base `Widget`, and is updated whenever they are redrawn.  When `false`, the shape is the
+    /// entire window.
+    pub follow_widgets: bool,
+
+    /// The radius of the rounded corners of the shape, in pixels.  When following `Widget`s, each
+    /// `Widget`'s bounds are rounded; otherwise, the corners of the window are rounded.
+    pub corner_radius: u32,
+}
+
+/// This is the implementation of the `WindowShape`.
+impl WindowShape {
+    /// Creates a `WindowShape` that covers the entire window, with rounded corners, for
+    /// custom-chrome applications.
+    pub fn rounded(corner_radius: u32) -> Self {
+        Self {
+            follow_widgets: false,
+            corner_radius,
+        }
+    }
+
+    /// Creates a `WindowShape` that follows the `Widget`s drawn, for splash screens and
+    /// widgets-on-desktop applications.
+    pub fn widgets(corner_radius: u32) -> Self {
+        Self {
+            follow_widgets: true,
+            corner_radius,
+        }
+    }
+
+    /// Builds the alpha mask for this shape, given the size of the window, and the bounds of the
+    /// visible `Widget`s.  The mask contains one byte per pixel: `255` inside the shape, and `0`
+    /// outside of it.
+    pub fn build_mask(&self, width: u32, height: u32, widget_bounds: &[Rect]) -> Vec<u8> {
+        let mut mask = vec![0u8; (width * height) as usize];
+
+        if self.follow_widgets {
+            for bounds in widget_bounds {
+                fill_mask(&mut mask, width, height, *bounds, self.corner_radius);
+            }
+        } else {
+            fill_mask(
+                &mut mask,
+                width,
+                height,
+                Rect::new(0, 0, width, height),
+                self.corner_radius,
+            );
+        }
+
+        mask
+    }
+}
+
+/// Fills a rectangle with rounded corners into a mask.
+fn fill_mask(mask: &mut [u8], width: u32, height: u32, bounds: Rect, radius: u32) {
+    let radius = radius.min(bounds.width() / 2).min(bounds.height() / 2) as i32;
+    let left = bounds.x().max(0);
+    let top = bounds.y().max(0);
+    let right = bounds.right().min(width as i32);
+    let bottom = bounds.bottom().min(height as i32);
+
+    for y in top..bottom {
+        for x in left..right {
+            // Distance into the corner circle, if this pixel lies in a corner square.
+            let dx = (bounds.x() + radius - x - 1)
+                .max(x - (bounds.right() - radius))
+                .max(-1)
+                + 1;
+            let dy = (bounds.y() + radius - y - 1)
+                .max(y - (bounds.bottom() - radius))
+                .max(-1)
+                + 1;
+
+            if dx > 0 && dy > 0 && dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+
+            mask[(y as u32 * width + x as u32) as usize] = 255;
+        }
+    }
+}
+
+/// Creates a borderless, shaped `Window` centered on the screen, for use with `Engine::run` and
+/// `Engine::set_window_shape`.  Shaped windows are not supported on all platforms; an error is
+/// returned if the window could not be created.
+pub fn create_shaped_window(
+    video: &VideoSubsystem,
+    title: &str,
+    width: u32,
+    height: u32,
+) -> Result<Window, String> {
+    let title = CString::new(title).map_err(|e| e.to_string())?;
+    let centered = sys::SDL_WINDOWPOS_CENTERED_MASK;
+
+    // Safety: the title is a valid, NUL-terminated string, and the video subsystem is initialized,
+    // as a `VideoSubsystem` is required to call this function.
+    let raw = unsafe {
+        sys::SDL_CreateShapedWindow(
+            title.as_ptr(),
+            centered,
+            centered,
+            width,
+            height,
+            sys::SDL_WindowFlags::SDL_WINDOW_OPENGL as u32,
+        )
+    };
+
+    if raw.is_null() {
+        Err(sdl2::get_error())
+    } else {
+        // Safety: `raw` is a valid window that is owned by the returned `Window`.
+        Ok(unsafe { Window::from_ll(video.clone(), raw) })
+    }
+}
+
+/// Indicates whether or not a `Window` was created with `create_shaped_window`.
+pub fn is_shaped_window(window: &Window) -> bool {
+    // Safety: `window.raw()` is a valid window for the lifetime of `window`.
+    unsafe { sys::SDL_IsShapedWindow(window.raw()) == sys::SDL_bool::SDL_TRUE }
+}
+
+/// Sets the shape of a shaped `Window` from an alpha mask, as built by `WindowShape::build_mask`.
+/// The mask must contain one byte per pixel of the `Window`.
+pub fn set_window_shape(window: &Window, mask: &[u8]) -> Result<(), String> {
+    let (width, height) = window.size();
+
+    if mask.len() != (width * height) as usize {
+        return Err(format!(
+            "Mask is {} bytes, expected {} for a {}x{} window",
+            mask.len(),
+            width * height,
+            width,
+            height
+        ));
+    }
+
+    let mut surface = Surface::new(width, height, PixelFormatEnum::RGBA32)?;
+    let pitch = surface.pitch() as usize;
+
+    surface.with_lock_mut(|pixels| {
+        for (i, alpha) in mask.iter().enumerate() {
+            let offset = (i / width as usize) * pitch + (i % width as usize) * 4;
+
+            pixels[offset..offset + 4].copy_from_slice(&[255, 255, 255, *alpha]);
+        }
+    });
+
+    let mut shape_mode = sys::SDL_WindowShapeMode {
+        mode: sys::WindowShapeMode::ShapeModeBinarizeAlpha,
+        parameters: sys::SDL_WindowShapeParams {
+            binarizationCutoff: 1,
+        },
+    };
+
+    // Safety: the window and surface are valid for the duration of the call, and SDL2 copies the
+    // shape rather than keeping a reference to the surface.
+    let result = unsafe { sys::SDL_SetWindowShape(window.raw(), surface.raw(), &mut shape_mode) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(sdl2::get_error())
+    }
+}
@@ -0,0 +1,22 @@
+// Pushrod Testing Library
+// Testing Utilities
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// This is the golden-image snapshot library, which renders `Widget`s offscreen into images, and
+/// compares them against stored reference images.
+pub mod snapshot;
+
+/// This is the input simulation library, which injects synthetic mouse and keyboard events into the
+/// `Engine`, so that interaction flows can be tested without a user.
+pub mod simulate;
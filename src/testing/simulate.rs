@@ -0,0 +1,255 @@
+// Pushrod Testing Library
+// Input Simulation
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::engine::Engine;
+use sdl2::event::Event;
+use sdl2::keyboard::{Keycode, Mod, Scancode};
+use sdl2::mouse::{MouseButton, MouseState, MouseWheelDirection};
+
+/// This is the storage object for the `Simulator`.  It tracks the simulated mouse position, pressed
+/// mouse buttons, and held modifier keys, so that each generated event carries consistent state.
+pub struct Simulator<'a> {
+    engine: &'a mut Engine,
+    mouse_x: i32,
+    mouse_y: i32,
+    mouse_buttons: u32,
+    keymod: Mod,
+    timestamp: u32,
+}
+
+/// This is the implementation of the `Simulator`, which injects synthetic input into an `Engine`.
+/// Events are dispatched immediately and in the order in which the methods are called, so an
+/// interaction flow can be asserted on directly after the calls return.  Each method returns the
+/// `Simulator`, so calls can be chained:
+///
+/// ```no_run
+/// # use pushrod::render::engine::Engine;
+/// # use pushrod::testing::simulate::Simulator;
+/// # use sdl2::keyboard::Keycode;
+/// # let mut engine = Engine::new(400, 300, 30);
+/// Simulator::new(&mut engine)
+///     .click(20, 20)
+///     .type_text("Hello")
+///     .key_chord(&[Keycode::LCtrl, Keycode::A]);
+/// ```
+impl<'a> Simulator<'a> {
+    /// Creates a new `Simulator` that sends events to the specified `Engine`.  The simulated mouse
+    /// starts at the origin.
+    pub fn new(engine: &'a mut Engine) -> Self {
+        Self {
+            engine,
+            mouse_x: 0,
+            mouse_y: 0,
+            mouse_buttons: 0,
+            keymod: Mod::NOMOD,
+            timestamp: 0,
+        }
+    }
+
+    /// Retrieves the `Engine` that events are sent to.
+    pub fn get_engine(&mut self) -> &mut Engine {
+        self.engine
+    }
+
+    /// Sends an arbitrary `Event` to the `Engine`.
+    pub fn send(&mut self, event: Event) -> &mut Self {
+        self.timestamp += 1;
+        self.engine.handle_event(event);
+        self
+    }
+
+    /// Moves the mouse to `x, y`.
+    pub fn mouse_move(&mut self, x: i32, y: i32) -> &mut Self {
+        let event = Event::MouseMotion {
+            timestamp: self.timestamp,
+            window_id: 0,
+            which: 0,
+            mousestate: MouseState::from_sdl_state(self.mouse_buttons),
+            x,
+            y,
+            xrel: x - self.mouse_x,
+            yrel: y - self.mouse_y,
+        };
+
+        self.mouse_x = x;
+        self.mouse_y = y;
+        self.send(event)
+    }
+
+    /// Presses the specified mouse button at the current mouse position.
+    pub fn mouse_down(&mut self, button: MouseButton) -> &mut Self {
+        self.mouse_buttons |= button_mask(button);
+
+        let event = Event::MouseButtonDown {
+            timestamp: self.timestamp,
+            window_id: 0,
+            which: 0,
+            mouse_btn: button,
+            clicks: 1,
+            x: self.mouse_x,
+            y: self.mouse_y,
+        };
+
+        self.send(event)
+    }
+
+    /// Releases the specified mouse button at the current mouse position.
+    pub fn mouse_up(&mut self, button: MouseButton) -> &mut Self {
+        self.mouse_buttons &= !button_mask(button);
+
+        let event = Event::MouseButtonUp {
+            timestamp: self.timestamp,
+            window_id: 0,
+            which: 0,
+            mouse_btn: button,
+            clicks: 1,
+            x: self.mouse_x,
+            y: self.mouse_y,
+        };
+
+        self.send(event)
+    }
+
+    /// Moves the mouse to `x, y`, and clicks the left mouse button.
+    pub fn click(&mut self, x: i32, y: i32) -> &mut Self {
+        self.mouse_move(x, y)
+            .mouse_down(MouseButton::Left)
+            .mouse_up(MouseButton::Left)
+    }
+
+    /// Drags the mouse with the left button held from `from` to `to`, generating `steps` intermediate
+    /// mouse motion events along a straight line.
+    pub fn drag(&mut self, from: (i32, i32), to: (i32, i32), steps: u32) -> &mut Self {
+        let steps = steps.max(1) as i32;
+
+        self.mouse_move(from.0, from.1)
+            .mouse_down(MouseButton::Left);
+
+        for step in 1..=steps {
+            let x = from.0 + (to.0 - from.0) * step / steps;
+            let y = from.1 + (to.1 - from.1) * step / steps;
+
+            self.mouse_move(x, y);
+        }
+
+        self.mouse_up(MouseButton::Left)
+    }
+
+    /// Scrolls the mouse wheel by `x, y` at the current mouse position.
+    pub fn scroll(&mut self, x: i32, y: i32) -> &mut Self {
+        let event = Event::MouseWheel {
+            timestamp: self.timestamp,
+            window_id: 0,
+            which: 0,
+            x,
+            y,
+            direction: MouseWheelDirection::Normal,
+        };
+
+        self.send(event)
+    }
+
+    /// Presses a key.  Modifier keys stay held, and are applied to subsequent key events, until they
+    /// are released with `key_up`.
+    pub fn key_down(&mut self, keycode: Keycode) -> &mut Self {
+        self.keymod |= modifier_mask(keycode);
+
+        let event = Event::KeyDown {
+            timestamp: self.timestamp,
+            window_id: 0,
+            keycode: Some(keycode),
+            scancode: Scancode::from_keycode(keycode),
+            keymod: self.keymod,
+            repeat: false,
+        };
+
+        self.send(event)
+    }
+
+    /// Releases a key.
+    pub fn key_up(&mut self, keycode: Keycode) -> &mut Self {
+        self.keymod &= !modifier_mask(keycode);
+
+        let event = Event::KeyUp {
+            timestamp: self.timestamp,
+            window_id: 0,
+            keycode: Some(keycode),
+            scancode: Scancode::from_keycode(keycode),
+            keymod: self.keymod,
+            repeat: false,
+        };
+
+        self.send(event)
+    }
+
+    /// Presses and releases a single key.
+    pub fn key_press(&mut self, keycode: Keycode) -> &mut Self {
+        self.key_down(keycode).key_up(keycode)
+    }
+
+    /// Presses a chord of keys, such as `[Keycode::LCtrl, Keycode::C]`: each key is pressed in the
+    /// order given, then released in reverse order.
+    pub fn key_chord(&mut self, keycodes: &[Keycode]) -> &mut Self {
+        for keycode in keycodes {
+            self.key_down(*keycode);
+        }
+
+        for keycode in keycodes.iter().rev() {
+            self.key_up(*keycode);
+        }
+
+        self
+    }
+
+    /// Types text, sending a `TextInput` event for each character.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        for ch in text.chars() {
+            let event = Event::TextInput {
+                timestamp: self.timestamp,
+                window_id: 0,
+                text: ch.to_string(),
+            };
+
+            self.send(event);
+        }
+
+        self
+    }
+}
+
+fn button_mask(button: MouseButton) -> u32 {
+    match button {
+        MouseButton::Left => 1,
+        MouseButton::Middle => 1 << 1,
+        MouseButton::Right => 1 << 2,
+        MouseButton::X1 => 1 << 3,
+        MouseButton::X2 => 1 << 4,
+        MouseButton::Unknown => 0,
+    }
+}
+
+fn modifier_mask(keycode: Keycode) -> Mod {
+    match keycode {
+        Keycode::LShift => Mod::LSHIFTMOD,
+        Keycode::RShift => Mod::RSHIFTMOD,
+        Keycode::LCtrl => Mod::LCTRLMOD,
+        Keycode::RCtrl => Mod::RCTRLMOD,
+        Keycode::LAlt => Mod::LALTMOD,
+        Keycode::RAlt => Mod::RALTMOD,
+        Keycode::LGui => Mod::LGUIMOD,
+        Keycode::RGui => Mod::RGUIMOD,
+        _ => Mod::NOMOD,
+    }
+}
@@ -0,0 +1,412 @@
+// Pushrod Testing Library
+// Golden Image Snapshots
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::engine::Engine;
+use crate::render::texture_cache::TextureCache;
+use crate::render::widget::Widget;
+use crate::render::widget_config::CONFIG_SIZE;
+use crate::render::{SIZE_HEIGHT, SIZE_WIDTH};
+use sdl2::image::{LoadSurface, SaveSurface};
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::surface::Surface;
+use sdl2::video::Window;
+use sdl2::Sdl;
+use std::path::Path;
+
+/// Set this environment variable to any value to overwrite stored reference images with the
+/// currently rendered images, rather than comparing against them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "PUSHROD_UPDATE_SNAPSHOTS";
+
+/// This is an image stored in memory as tightly packed 8-bit `R, G, B, A` values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// This is the implementation of the `RgbaImage`, which can be loaded from and saved to PNG files.
+impl RgbaImage {
+    /// Creates a new `RgbaImage` of the given size, filled with transparent black.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    /// Retrieves the `Color` of the pixel at `x, y`.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Color {
+        let offset = ((y * self.width + x) * 4) as usize;
+
+        Color::RGBA(
+            self.pixels[offset],
+            self.pixels[offset + 1],
+            self.pixels[offset + 2],
+            self.pixels[offset + 3],
+        )
+    }
+
+    /// Sets the `Color` of the pixel at `x, y`.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        let offset = ((y * self.width + x) * 4) as usize;
+
+        self.pixels[offset] = color.r;
+        self.pixels[offset + 1] = color.g;
+        self.pixels[offset + 2] = color.b;
+        self.pixels[offset + 3] = color.a;
+    }
+
+    /// Loads an `RgbaImage` from an image file (PNG, or any other format supported by `SDL2_image`.)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let surface = Surface::from_file(path)?.convert_format(PixelFormatEnum::RGBA32)?;
+        let width = surface.width();
+        let height = surface.height();
+        let pitch = surface.pitch() as usize;
+        let row_length = (width * 4) as usize;
+        let mut image = Self::new(width, height);
+
+        surface.with_lock(|data| {
+            for y in 0..height as usize {
+                image.pixels[y * row_length..(y + 1) * row_length]
+                    .copy_from_slice(&data[y * pitch..y * pitch + row_length]);
+            }
+        });
+
+        Ok(image)
+    }
+
+    /// Saves this `RgbaImage` as a PNG file.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut pixels = self.pixels.clone();
+        let surface = Surface::from_data(
+            &mut pixels,
+            self.width,
+            self.height,
+            self.width * 4,
+            PixelFormatEnum::RGBA32,
+        )?;
+
+        surface.save(path)
+    }
+}
+
+/// This is the result of comparing two `RgbaImage`s with `compare_images`.
+pub struct ImageComparison {
+    /// Number of pixels whose channels differ by more than the tolerance.  When the images differ in
+    /// size, pixels that are only inside one of the images are counted as mismatched.
+    pub mismatched_pixels: u32,
+
+    /// The largest per-channel difference found between the two images.
+    pub max_difference: u8,
+
+    /// Whether or not the images differ in size.
+    pub size_mismatch: bool,
+
+    /// An image highlighting mismatched pixels in red over a faded copy of the expected image.
+    /// When the images differ in size, it covers both of them, so the pixels that are only inside
+    /// one of the images are also shown in red.  Only generated when there are mismatches.
+    pub diff: Option<RgbaImage>,
+}
+
+/// This is the implementation of the `ImageComparison` result.
+impl ImageComparison {
+    /// Indicates whether or not the images matched within the tolerance.
+    pub fn matches(&self) -> bool {
+        self.mismatched_pixels == 0 && !self.size_mismatch
+    }
+}
+
+/// Compares two `RgbaImage`s pixel by pixel.  A pixel is considered mismatched if any of its color
+/// channels differ by more than `tolerance`.  Images of different sizes never match.
+pub fn compare_images(actual: &RgbaImage, expected: &RgbaImage, tolerance: u8) -> ImageComparison {
+    let width = actual.width.max(expected.width);
+    let height = actual.height.max(expected.height);
+    let size_mismatch = actual.width != expected.width || actual.height != expected.height;
+    let mut mismatched_pixels = 0;
+    let mut max_difference = 0;
+    let mut diff = RgbaImage::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            if x >= actual.width
+                || y >= actual.height
+                || x >= expected.width
+                || y >= expected.height
+            {
+                mismatched_pixels += 1;
+                max_difference = 255;
+                diff.set_pixel(x, y, Color::RGBA(255, 0, 0, 255));
+                continue;
+            }
+
+            let a = actual.get_pixel(x, y);
+            let e = expected.get_pixel(x, y);
+            let difference = [
+                (a.r as i32 - e.r as i32).abs(),
+                (a.g as i32 - e.g as i32).abs(),
+                (a.b as i32 - e.b as i32).abs(),
+                (a.a as i32 - e.a as i32).abs(),
+            ]
+            .iter()
+            .cloned()
+            .max()
+            .unwrap_or(0) as u8;
+
+            max_difference = max_difference.max(difference);
+
+            if difference > tolerance {
+                mismatched_pixels += 1;
+                diff.set_pixel(x, y, Color::RGBA(255, 0, 0, 255));
+            } else {
+                let luma = ((e.r as u32 + e.g as u32 + e.b as u32) / 3) as u8;
+
+                diff.set_pixel(x, y, Color::RGBA(luma, luma, luma, 64));
+            }
+        }
+    }
+
+    ImageComparison {
+        mismatched_pixels,
+        max_difference,
+        size_mismatch,
+        diff: if mismatched_pixels > 0 {
+            Some(diff)
+        } else {
+            None
+        },
+    }
+}
+
+/// Compares an `RgbaImage` against the reference image stored at `reference`.  If the reference does
+/// not exist (or `PUSHROD_UPDATE_SNAPSHOTS` is set), the image is stored as the new reference.  On a
+/// mismatch, including a mismatch in size, the rendered image and a diff image are written next to
+/// the reference as `<name>.actual.png` and `<name>.diff.png`, and an `Err` describing the mismatch
+/// is returned.
+pub fn compare_snapshot(actual: &RgbaImage, reference: &Path, tolerance: u8) -> Result<(), String> {
+    if !reference.exists() || std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok() {
+        return actual.save(reference);
+    }
+
+    let expected = RgbaImage::load(reference)?;
+    let comparison = compare_images(actual, &expected, tolerance);
+
+    if comparison.matches() {
+        return Ok(());
+    }
+
+    actual.save(&reference.with_extension("actual.png"))?;
+
+    if let Some(diff) = comparison.diff {
+        diff.save(&reference.with_extension("diff.png"))?;
+    }
+
+    if comparison.size_mismatch {
+        return Err(format!(
+            "Snapshot {} mismatched: rendered {}x{}, expected {}x{}",
+            reference.display(),
+            actual.width,
+            actual.height,
+            expected.width,
+            expected.height
+        ));
+    }
+
+    Err(format!(
+        "Snapshot {} mismatched: {} pixels differ (max channel difference {}, tolerance {})",
+        reference.display(),
+        comparison.mismatched_pixels,
+        comparison.max_difference,
+        tolerance
+    ))
+}
+
+/// Panics if an `RgbaImage` does not match the reference image stored at `reference`.  See
+/// `compare_snapshot` for details.
+pub fn assert_snapshot(actual: &RgbaImage, reference: &Path, tolerance: u8) {
+    if let Err(message) = compare_snapshot(actual, reference, tolerance) {
+        panic!("{}", message);
+    }
+}
+
+/// This is the storage object for the `OffscreenRenderer`.  It owns the SDL2 context, a hidden window,
+/// and a software-rendered `Canvas`, so that rendering results are deterministic across machines.
+pub struct OffscreenRenderer {
+    _sdl: Sdl,
+    canvas: Canvas<Window>,
+    texture_cache: TextureCache,
+}
+
+/// This is the implementation of the `OffscreenRenderer`, which renders `Widget`s without showing a
+/// window.  Only one `OffscreenRenderer` can exist at a time, as SDL2 can only be initialized once;
+/// run snapshot tests with `--test-threads=1`.
+///
+/// Although nothing is shown, the `Canvas` belongs to a hidden window, so SDL2 needs a working video
+/// driver, and `new` returns an error without one.  On machines without a display, such as CI
+/// servers, set the `SDL_VIDEODRIVER` environment variable to `dummy` or `offscreen`.
+impl OffscreenRenderer {
+    /// Creates a new `OffscreenRenderer` with a drawing area of `width` x `height` pixels.
+    pub fn new(width: u32, height: u32) -> Result<Self, String> {
+        let sdl = sdl2::init()?;
+        let window = sdl
+            .video()?
+            .window("pushrod-offscreen", width, height)
+            .hidden()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let canvas = window
+            .into_canvas()
+            .software()
+            .target_texture()
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            _sdl: sdl,
+            canvas,
+            texture_cache: TextureCache::new(),
+        })
+    }
+
+    /// Renders a single `Widget` into an `RgbaImage` the size of the `Widget`.  The `Widget` is drawn
+    /// at the origin, regardless of its configured position.
+    pub fn render_widget(&mut self, widget: &mut dyn Widget) -> Result<RgbaImage, String> {
+        let size = widget.get_config().get_size(CONFIG_SIZE);
+        let bounds = Rect::new(0, 0, size[SIZE_WIDTH], size[SIZE_HEIGHT]);
+
+        widget.set_invalidated(true);
+        self.canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+        self.canvas.clear();
+
+        if let Some(texture) = widget.draw(&mut self.canvas, &mut self.texture_cache) {
+            self.canvas.copy(texture, None, bounds)?;
+        }
+
+        widget.set_invalidated(false);
+
+        self.read_image(bounds)
+    }
+
+    /// Renders the full display list of an `Engine` into an `RgbaImage`, performing a single frame of
+    /// `tick`, layout, and drawing.
+    pub fn render_engine(&mut self, engine: &mut Engine) -> Result<RgbaImage, String> {
+        let (width, height) = self.canvas.output_size()?;
+
+        self.canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+        self.canvas.clear();
+
+        engine.redraw();
+        engine.draw_frame(&mut self.canvas);
+
+        self.read_image(Rect::new(0, 0, width, height))
+    }
+
+    /// Retrieves the `Canvas` used for rendering.
+    pub fn get_canvas(&mut self) -> &mut Canvas<Window> {
+        &mut self.canvas
+    }
+
+    fn read_image(&mut self, bounds: Rect) -> Result<RgbaImage, String> {
+        let pixels = self.canvas.read_pixels(bounds, PixelFormatEnum::RGBA32)?;
+
+        Ok(RgbaImage {
+            width: bounds.width(),
+            height: bounds.height(),
+            pixels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an `RgbaImage` of `width` by `height` pixels filled with `color`.
+    fn filled(width: u32, height: u32, color: Color) -> RgbaImage {
+        let mut image = RgbaImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                image.set_pixel(x, y, color);
+            }
+        }
+
+        image
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let image = filled(2, 2, Color::RGB(30, 60, 90));
+        let comparison = compare_images(&image, &image.clone(), 0);
+
+        assert!(comparison.matches());
+        assert_eq!(comparison.mismatched_pixels, 0);
+        assert_eq!(comparison.max_difference, 0);
+        assert!(comparison.diff.is_none());
+    }
+
+    #[test]
+    fn test_within_tolerance() {
+        let expected = filled(2, 2, Color::RGB(30, 60, 90));
+        let mut actual = expected.clone();
+
+        actual.set_pixel(1, 0, Color::RGB(33, 60, 90));
+
+        let comparison = compare_images(&actual, &expected, 3);
+
+        assert!(comparison.matches());
+        assert_eq!(comparison.max_difference, 3);
+        assert!(comparison.diff.is_none());
+    }
+
+    #[test]
+    fn test_over_tolerance() {
+        let expected = filled(2, 2, Color::RGB(30, 60, 90));
+        let mut actual = expected.clone();
+
+        actual.set_pixel(1, 0, Color::RGB(33, 60, 90));
+
+        let comparison = compare_images(&actual, &expected, 2);
+        let diff = comparison.diff.as_ref().unwrap();
+
+        assert!(!comparison.matches());
+        assert!(!comparison.size_mismatch);
+        assert_eq!(comparison.mismatched_pixels, 1);
+        assert_eq!(comparison.max_difference, 3);
+        assert_eq!(diff.get_pixel(1, 0), Color::RGBA(255, 0, 0, 255));
+        assert_eq!(diff.get_pixel(0, 0), Color::RGBA(60, 60, 60, 64));
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let expected = filled(2, 2, Color::RGB(30, 60, 90));
+        let actual = filled(3, 1, Color::RGB(30, 60, 90));
+        let comparison = compare_images(&actual, &expected, 0);
+        let diff = comparison.diff.as_ref().unwrap();
+
+        assert!(!comparison.matches());
+        assert!(comparison.size_mismatch);
+        assert_eq!(comparison.mismatched_pixels, 4);
+        assert_eq!(comparison.max_difference, 255);
+        assert_eq!((diff.width, diff.height), (3, 2));
+        assert_eq!(diff.get_pixel(0, 0), Color::RGBA(60, 60, 60, 64));
+        assert_eq!(diff.get_pixel(2, 0), Color::RGBA(255, 0, 0, 255));
+        assert_eq!(diff.get_pixel(0, 1), Color::RGBA(255, 0, 0, 255));
+        assert_eq!(diff.get_pixel(2, 1), Color::RGBA(255, 0, 0, 255));
+    }
+}
@@ -0,0 +1,503 @@
+// Pushrod Widget Library
+// Accordion Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_polygon;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_WIDTH};
+use crate::widgets::tab_widget::set_subtree_hidden;
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of the header of each section.
+const HEADER_HEIGHT: u32 = 28;
+
+/// This is the number of pixels between the left edge of a header and its arrow, and between the
+/// arrow and the title.
+const HEADER_PADDING: u32 = 10;
+
+/// This is the width and height of the arrow that shows whether a section is open.
+const ARROW_SIZE: u32 = 8;
+
+/// This is the callback type that is used when an `on_section_toggled` callback is triggered from
+/// this `Widget`.  It receives the index of the section, starting from `0`, and whether it is now
+/// open.
+pub type OnSectionToggledCallbackType = Option<
+    Box<dyn FnMut(&mut AccordionWidget, &[WidgetContainer], &[LayoutContainer], usize, bool)>,
+>;
+
+/// This is a single section of an `AccordionWidget`: its title, the height of its content area, the
+/// IDs of the `Widget`s shown when it is open, and how far each of those `Widget`s has been moved.
+struct Section {
+    title: String,
+    content_height: u32,
+    widget_ids: Vec<i32>,
+    applied_offsets: Vec<i32>,
+    open: bool,
+}
+
+/// This is the storage object for the `AccordionWidget`.  It stores the config, properties,
+/// callback registry, the sections and the `Widget`s they own, and the hovered section.
+pub struct AccordionWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    sections: Vec<Section>,
+    hovered_section: i32,
+    exclusive: bool,
+    layout_changed: bool,
+    mouse_position: Points,
+    on_section_toggled: OnSectionToggledCallbackType,
+}
+
+/// This is the implementation of the `AccordionWidget`, which stacks titled sections vertically.
+/// Clicking the header of a section opens or closes it.  An open section shows its content area
+/// below its header, and the sections below it move down to make room.
+///
+/// Each section refers to a list of `Widget` IDs.  The `Widget`s of every section are created as if
+/// their section's content area started directly below the first header; the `AccordionWidget`
+/// moves them, along with all of their children, into place below their own header, and hides them
+/// while their section is closed.  The `AccordionWidget` does not resize itself, so it should be
+/// tall enough for its headers and the content of its open sections.
+impl AccordionWidget {
+    /// Creates a new `AccordionWidget` given the `x, y, w, h` coordinates.
+    pub fn new(points: Points, size: Size) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            sections: vec![],
+            hovered_section: -1,
+            exclusive: false,
+            layout_changed: false,
+            mouse_position: vec![0, 0],
+            on_section_toggled: None,
+        }
+    }
+
+    /// Adds a closed section, given its title, the height of its content area, and the IDs of the
+    /// `Widget`s that are shown when it is open.  Returns the index of the new section.
+    pub fn add_section(&mut self, title: &str, content_height: u32, widget_ids: Vec<i32>) -> usize {
+        self.sections.push(Section {
+            title: String::from(title),
+            content_height,
+            applied_offsets: vec![0; widget_ids.len()],
+            widget_ids,
+            open: false,
+        });
+
+        self.layout_changed = true;
+        self.get_config().set_invalidated(true);
+
+        self.sections.len() - 1
+    }
+
+    /// Adds a `Widget` ID to an existing section.  The `Widget` is created as if the content area
+    /// of the section started directly below the first header.
+    pub fn add_widget_to_section(&mut self, section: usize, widget_id: i32) {
+        if let Some(section) = self.sections.get_mut(section) {
+            section.widget_ids.push(widget_id);
+            section.applied_offsets.push(0);
+            self.layout_changed = true;
+        }
+    }
+
+    /// Retrieves the number of sections.
+    pub fn get_section_count(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Retrieves the title of a section.
+    pub fn get_section_title(&self, section: usize) -> Option<&str> {
+        self.sections.get(section).map(|x| x.title.as_str())
+    }
+
+    /// Retrieves the IDs of the `Widget`s owned by a section.
+    pub fn get_section_widgets(&self, section: usize) -> Option<&[i32]> {
+        self.sections.get(section).map(|x| x.widget_ids.as_slice())
+    }
+
+    /// Indicates whether or not a section is open.  Returns `false` if the section does not exist.
+    pub fn is_section_open(&self, section: usize) -> bool {
+        self.sections.get(section).is_some_and(|x| x.open)
+    }
+
+    /// Opens or closes a section.  The sections are laid out again on the next frame.  This does
+    /// not trigger the `on_section_toggled` callback, and does not close other sections, even if
+    /// only one section may be open at a time.
+    pub fn set_section_open(&mut self, section: usize, open: bool) {
+        if let Some(section) = self.sections.get_mut(section) {
+            if section.open != open {
+                section.open = open;
+                self.layout_changed = true;
+                self.get_config().set_invalidated(true);
+            }
+        }
+    }
+
+    /// Sets whether or not only one section may be open at a time.  When set, opening a section by
+    /// clicking its header closes the section that was open.  This is off by default.
+    pub fn set_exclusive(&mut self, exclusive: bool) {
+        self.exclusive = exclusive;
+    }
+
+    /// Indicates whether or not only one section may be open at a time.
+    pub fn get_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Assigns the callback closure that will be used when the user opens or closes a section.
+    pub fn on_section_toggled<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut AccordionWidget, &[WidgetContainer], &[LayoutContainer], usize, bool)
+            + 'static,
+    {
+        self.on_section_toggled = Some(Box::new(callback));
+    }
+
+    /// Returns the top of the header of a section, relative to the `Widget`.
+    fn section_top(&self, section: usize) -> i32 {
+        self.sections[..section]
+            .iter()
+            .map(|x| {
+                if x.open {
+                    (HEADER_HEIGHT + x.content_height) as i32
+                } else {
+                    HEADER_HEIGHT as i32
+                }
+            })
+            .sum()
+    }
+
+    /// Returns the section whose header is under the last known mouse position, or `-1` if the
+    /// mouse is not over a header.
+    fn section_at_mouse(&mut self) -> i32 {
+        let y = self.mouse_position[POINT_Y] - self.get_config().to_y(0);
+
+        (0..self.sections.len())
+            .find(|section| {
+                let top = self.section_top(*section);
+
+                y >= top && y < top + HEADER_HEIGHT as i32
+            })
+            .map_or(-1, |section| section as i32)
+    }
+
+    /// Opens or closes a section, closing any other open section if only one may be open, then lays
+    /// out the sections again, and triggers the `on_section_toggled` callback for each section that
+    /// changed.
+    fn toggle_section(
+        &mut self,
+        section: usize,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let open = !self.sections[section].open;
+        let mut changed = vec![(section, open)];
+
+        if open && self.exclusive {
+            for (index, other) in self.sections.iter().enumerate() {
+                if index != section && other.open {
+                    changed.push((index, false));
+                }
+            }
+        }
+
+        for (index, open) in &changed {
+            self.sections[*index].open = *open;
+        }
+
+        self.layout_changed = true;
+        self.get_config().set_invalidated(true);
+        self.update_layout(widgets);
+
+        if let Some(mut cb) = self.on_section_toggled.take() {
+            for (index, open) in changed {
+                cb(self, widgets, layouts, index, open);
+            }
+
+            self.on_section_toggled = Some(cb);
+        }
+    }
+
+    /// Moves the `Widget`s of each section below its header, and shows the `Widget`s of open
+    /// sections, hiding the rest.
+    fn update_layout(&mut self, widgets: &[WidgetContainer]) {
+        for index in 0..self.sections.len() {
+            let offset = self.section_top(index);
+            let section = &mut self.sections[index];
+
+            for (widget_id, applied_offset) in section
+                .widget_ids
+                .iter()
+                .zip(section.applied_offsets.iter_mut())
+            {
+                move_subtree(widgets, *widget_id, offset - *applied_offset);
+                set_subtree_hidden(widgets, *widget_id, !section.open);
+                *applied_offset = offset;
+            }
+        }
+
+        self.layout_changed = false;
+    }
+}
+
+/// Moves a `Widget` down by `dy`, along with all of its children.  The `Widget` that is currently
+/// handling an event cannot be borrowed, and is skipped.
+fn move_subtree(widgets: &[WidgetContainer], widget_id: i32, dy: i32) {
+    if dy == 0 || widget_id <= 0 || widget_id as usize >= widgets.len() {
+        return;
+    }
+
+    if let Ok(mut widget) = widgets[widget_id as usize].widget.try_borrow_mut() {
+        let origin = widget.get_config().get_point(CONFIG_ORIGIN);
+
+        widget
+            .get_config()
+            .set_point(CONFIG_ORIGIN, origin[POINT_X], origin[POINT_Y] + dy);
+        widget.get_config().set_invalidated(true);
+    }
+
+    for child in widgets
+        .iter()
+        .filter(|x| x.get_parent_id() == widget_id && x.get_widget_id() != widget_id)
+    {
+        move_subtree(widgets, child.get_widget_id(), dy);
+    }
+}
+
+/// This is the `Widget` implementation of the `AccordionWidget`.
+impl Widget for AccordionWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let header_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(224, 224, 224));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(200, 200, 240));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let hovered_section = self.hovered_section;
+            let width = bounds[SIZE_WIDTH];
+            let headers: Vec<(i32, bool, u32)> = self
+                .sections
+                .iter()
+                .enumerate()
+                .map(|(index, section)| {
+                    (
+                        self.section_top(index),
+                        section.open,
+                        section.content_height,
+                    )
+                })
+                .collect();
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+            let title_textures: Vec<Option<Texture>> = self
+                .sections
+                .iter()
+                .map(|section| {
+                    if section.title.is_empty() {
+                        return None;
+                    }
+
+                    let surface = font
+                        .render(&section.title)
+                        .blended(text_color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+
+                    Some(
+                        texture_creator
+                            .create_texture_from_surface(&surface)
+                            .map_err(|e| e.to_string())
+                            .unwrap(),
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (index, ((top, open, content_height), title)) in
+                    headers.iter().zip(title_textures.iter()).enumerate()
+                {
+                    let header = Rect::new(0, *top, width, HEADER_HEIGHT);
+
+                    texture.set_draw_color(if index as i32 == hovered_section {
+                        hover_color
+                    } else {
+                        header_color
+                    });
+                    texture.fill_rect(header).unwrap();
+                    texture.set_draw_color(border_color);
+                    texture.draw_rect(header).unwrap();
+
+                    // The arrow points right while the section is closed, and down while it is open.
+                    let arrow_x = HEADER_PADDING as i32;
+                    let arrow_y = top + (HEADER_HEIGHT - ARROW_SIZE) as i32 / 2;
+                    let size = ARROW_SIZE as i32;
+                    let arrow = if *open {
+                        [
+                            Point::new(arrow_x, arrow_y),
+                            Point::new(arrow_x + size, arrow_y),
+                            Point::new(arrow_x + size / 2, arrow_y + size),
+                        ]
+                    } else {
+                        [
+                            Point::new(arrow_x, arrow_y),
+                            Point::new(arrow_x + size, arrow_y + size / 2),
+                            Point::new(arrow_x, arrow_y + size),
+                        ]
+                    };
+
+                    texture.set_draw_color(text_color);
+                    fill_polygon(texture, &arrow);
+
+                    if let Some(title) = title {
+                        let TextureQuery { width, height, .. } = title.query();
+
+                        texture
+                            .copy(
+                                title,
+                                None,
+                                Rect::new(
+                                    (HEADER_PADDING * 2 + ARROW_SIZE) as i32,
+                                    top + (HEADER_HEIGHT as i32 - height as i32) / 2,
+                                    width,
+                                    height,
+                                ),
+                            )
+                            .unwrap();
+                    }
+
+                    if *open && *content_height > 0 {
+                        texture.set_draw_color(border_color);
+                        texture
+                            .draw_rect(Rect::new(
+                                0,
+                                top + HEADER_HEIGHT as i32 - 1,
+                                width,
+                                content_height + 1,
+                            ))
+                            .unwrap();
+                    }
+                }
+            })
+            .unwrap();
+
+            for title in title_textures.into_iter().flatten() {
+                // Safety: the title textures are only used within this function.
+                unsafe {
+                    title.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Applies any pending changes to the positions and visibility of the `Widget`s of each
+    /// section.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.layout_changed {
+            self.update_layout(_widgets);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Clears the highlighted header when the mouse leaves the `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.hovered_section != -1 {
+            self.hovered_section = -1;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the header under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        let hovered_section = self.section_at_mouse();
+
+        if hovered_section != self.hovered_section {
+            self.hovered_section = hovered_section;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Opens or closes the section whose header was clicked.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let section = self.section_at_mouse();
+
+            if section >= 0 {
+                self.toggle_section(section as usize, _widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,311 @@
+// Pushrod Widget Library
+// Animated Image Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::{ImageFrame, TextureCache};
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is where an `AnimatedImageWidget` takes its frames from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnimationSource {
+    /// The frames are laid out on a sprite sheet, in frames of `frame_size`, read from left to
+    /// right, then top to bottom, and played at `fps` frames per second.
+    SpriteSheet {
+        frame_size: Size,
+        frame_count: u32,
+        fps: u32,
+    },
+
+    /// The frames, and the time each frame is shown for, are read from an animated GIF.  If the GIF
+    /// cannot be loaded, the error is logged, and only the background is drawn.
+    Gif,
+}
+
+/// This is the storage object for the `AnimatedImageWidget`.  It stores the config, properties,
+/// callback registry, the image name and where its frames come from, and the playback state.
+pub struct AnimatedImageWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    image_name: String,
+    source: AnimationSource,
+    scaled: bool,
+    frames: Vec<ImageFrame>,
+    current_frame: usize,
+    frame_started: u64,
+    playing: bool,
+    looping: bool,
+}
+
+/// This is the implementation of the `AnimatedImageWidget`, which plays the frames of a sprite
+/// sheet or an animated GIF.  The frames are extracted by the `TextureCache` the first time this
+/// `Widget` is drawn, and frames are advanced in `tick`, so this `Widget` is only redrawn when the
+/// frame shown changes.  Animations start playing, and loop, by default.
+impl AnimatedImageWidget {
+    /// Creates a new `AnimatedImageWidget` that plays `frame_count` frames of `frame_size` from the
+    /// sprite sheet `image_name`, at `fps` frames per second, within the `x, y, w, h` coordinates.
+    /// If `scaled` is set, frames are stretched to the bounds of this `Widget`, otherwise they are
+    /// centered.
+    pub fn from_sprite_sheet(
+        image_name: String,
+        points: Points,
+        size: Size,
+        scaled: bool,
+        frame_size: Size,
+        frame_count: u32,
+        fps: u32,
+    ) -> Self {
+        Self::new(
+            image_name,
+            points,
+            size,
+            scaled,
+            AnimationSource::SpriteSheet {
+                frame_size,
+                frame_count,
+                fps,
+            },
+        )
+    }
+
+    /// Creates a new `AnimatedImageWidget` that plays the animated GIF `image_name` within the
+    /// `x, y, w, h` coordinates.  If `scaled` is set, frames are stretched to the bounds of this
+    /// `Widget`, otherwise they are centered.
+    pub fn from_gif(image_name: String, points: Points, size: Size, scaled: bool) -> Self {
+        Self::new(image_name, points, size, scaled, AnimationSource::Gif)
+    }
+
+    /// Creates a new `AnimatedImageWidget` that plays the frames of `image_name` described by
+    /// `source`.
+    pub fn new(
+        image_name: String,
+        points: Points,
+        size: Size,
+        scaled: bool,
+        source: AnimationSource,
+    ) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            image_name,
+            source,
+            scaled,
+            frames: vec![],
+            current_frame: 0,
+            frame_started: clock::now_millis(),
+            playing: true,
+            looping: true,
+        }
+    }
+
+    /// Starts or resumes playing the animation from the current frame.
+    pub fn play(&mut self) {
+        self.playing = true;
+        self.frame_started = clock::now_millis();
+    }
+
+    /// Pauses the animation on the current frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Indicates whether the animation is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Sets whether the animation starts over after its last frame.  If not, playback stops on the
+    /// last frame.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Indicates whether the animation starts over after its last frame.
+    pub fn get_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Shows the frame at `index`, restarting its delay.  Indexes past the last frame show the last
+    /// frame, once the frames have been loaded.
+    pub fn set_frame(&mut self, index: usize) {
+        self.current_frame = if self.frames.is_empty() {
+            index
+        } else {
+            index.min(self.frames.len() - 1)
+        };
+        self.frame_started = clock::now_millis();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the index of the frame shown.
+    pub fn get_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    /// Retrieves the number of frames in the animation.  This is `0` until this `Widget` has been
+    /// drawn for the first time.
+    pub fn get_frame_count(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+/// This is the `Widget` implementation of the `AnimatedImageWidget`.
+impl Widget for AnimatedImageWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = if self.get_config().config.contains_key(&CONFIG_COLOR_BASE) {
+                self.get_color(CONFIG_COLOR_BASE)
+            } else {
+                Color::RGBA(0, 0, 0, 0)
+            };
+            let (sheet, frames) = match self.source.clone() {
+                AnimationSource::SpriteSheet {
+                    frame_size,
+                    frame_count,
+                    fps,
+                } => {
+                    let (sheet, frames) = t.get_sprite_frames(
+                        c,
+                        self.image_name.clone(),
+                        frame_size,
+                        frame_count,
+                        fps,
+                    );
+
+                    (Some(sheet), frames)
+                }
+                AnimationSource::Gif => match t.get_gif_frames(c, self.image_name.clone()) {
+                    Ok((sheet, frames)) => (Some(sheet), frames),
+                    Err(e) => {
+                        eprintln!("Unable to load animated GIF {}: {}", self.image_name, e);
+                        (None, Vec::new())
+                    }
+                },
+            };
+
+            self.current_frame = self.current_frame.min(frames.len().saturating_sub(1));
+            self.frames = frames;
+
+            let source = self
+                .frames
+                .get(self.current_frame)
+                .map(|frame| frame.source);
+            let destination = match source {
+                Some(source) if !self.scaled => Rect::new(
+                    (bounds[SIZE_WIDTH] as i32 - source.width() as i32) / 2,
+                    (bounds[SIZE_HEIGHT] as i32 - source.height() as i32) / 2,
+                    source.width(),
+                    source.height(),
+                ),
+                _ => Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]),
+            };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let (Some(sheet), Some(source)) = (sheet, source) {
+                    texture.copy(sheet, source, destination).unwrap();
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Advances the animation by as many frames as the elapsed time calls for, redrawing this
+    /// `Widget` only if the frame shown changes.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let now = clock::now_millis();
+
+        if !self.playing || self.frames.is_empty() {
+            self.frame_started = now;
+        } else {
+            let mut frame = self.current_frame;
+
+            for _ in 0..self.frames.len() {
+                let delay = self.frames[frame].delay.max(1) as u64;
+
+                if now.saturating_sub(self.frame_started) < delay {
+                    break;
+                }
+
+                self.frame_started += delay;
+
+                if frame + 1 < self.frames.len() {
+                    frame += 1;
+                } else if self.looping {
+                    frame = 0;
+                } else {
+                    self.playing = false;
+                    break;
+                }
+            }
+
+            // Skips ahead, rather than playing catch-up, after a stall longer than the animation.
+            if now.saturating_sub(self.frame_started) >= self.frames[frame].delay.max(1) as u64 {
+                self.frame_started = now;
+            }
+
+            if frame != self.current_frame {
+                self.current_frame = frame;
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Redraws this `Widget` when its base color changes.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        if _k == CONFIG_COLOR_BASE {
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
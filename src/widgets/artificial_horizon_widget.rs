@@ -0,0 +1,400 @@
+// Pushrod Widget Library
+// Artificial Horizon Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{copy_rotated, draw_arc, fill_polygon, point_at_angle};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// These are the bank angles, in degrees, marked on the roll scale.
+const ROLL_MARKS: [f64; 11] = [
+    -60.0, -45.0, -30.0, -20.0, -10.0, 0.0, 10.0, 20.0, 30.0, 45.0, 60.0,
+];
+
+/// This is the storage object for the `ArtificialHorizonWidget`.  It stores the config, properties,
+/// callback registry, the attitude, and the horizon, which is only drawn again when the pitch, size
+/// or colors change.
+pub struct ArtificialHorizonWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    horizon_store: TextureStore,
+    horizon_invalidated: bool,
+    pitch: f64,
+    roll: f64,
+    visible_pitch: f64,
+}
+
+/// This is the implementation of the `ArtificialHorizonWidget`, an attitude indicator that shows
+/// the pitch and roll of an aircraft.  The horizon divides the sky from the ground, with a pitch
+/// ladder marked every 5 degrees, and labelled every 10 degrees.  A positive pitch raises the nose,
+/// moving the horizon down, and a positive roll banks to the right, turning the horizon
+/// counterclockwise.  A fixed aircraft symbol is drawn in the middle, and a fixed roll scale at
+/// the top is read against a pointer that turns with the horizon.
+///
+/// The horizon is drawn to its own texture, large enough to cover this `Widget` at any angle, and
+/// copied to this `Widget`'s texture rotated by the roll, so changing only the roll does not draw
+/// the pitch ladder again.  The sky is drawn in `CONFIG_COLOR_SECONDARY`, the ground in
+/// `CONFIG_COLOR_BASE`, the horizon, ladder and roll scale in `CONFIG_COLOR_TEXT`, the aircraft
+/// symbol in `CONFIG_COLOR_SELECTED`, and the edge of this `Widget` in `CONFIG_COLOR_BORDER`.
+impl ArtificialHorizonWidget {
+    /// Creates a new `ArtificialHorizonWidget`, given the `x, y, w, h` coordinates, in level
+    /// flight.
+    pub fn new(points: Points, size: Size) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            horizon_store: TextureStore::default(),
+            horizon_invalidated: true,
+            pitch: 0.0,
+            roll: 0.0,
+            visible_pitch: 20.0,
+        }
+    }
+
+    /// Sets the pitch in degrees, positive with the nose up.  The pitch is limited to `-90.0` to
+    /// `90.0`.
+    pub fn set_pitch(&mut self, pitch: f64) {
+        let pitch = pitch.clamp(-90.0, 90.0);
+
+        if (pitch - self.pitch).abs() > f64::EPSILON {
+            self.pitch = pitch;
+            self.horizon_invalidated = true;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the pitch in degrees.
+    pub fn get_pitch(&self) -> f64 {
+        self.pitch
+    }
+
+    /// Sets the roll in degrees, positive when banking to the right.  Angles outside of `-180.0` to
+    /// `180.0` are wrapped around.
+    pub fn set_roll(&mut self, roll: f64) {
+        let roll = 180.0 - (180.0 - roll).rem_euclid(360.0);
+
+        if (roll - self.roll).abs() > f64::EPSILON {
+            self.roll = roll;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the roll in degrees.
+    pub fn get_roll(&self) -> f64 {
+        self.roll
+    }
+
+    /// Sets both the pitch and the roll, in degrees.
+    pub fn set_attitude(&mut self, pitch: f64, roll: f64) {
+        self.set_pitch(pitch);
+        self.set_roll(roll);
+    }
+
+    /// Sets the number of degrees of pitch between the middle and the top edge of this `Widget`,
+    /// which sets the spacing of the pitch ladder.  This is `20.0` by default.
+    pub fn set_visible_pitch(&mut self, degrees: f64) {
+        if degrees > 0.0 {
+            self.visible_pitch = degrees;
+            self.horizon_invalidated = true;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the number of degrees of pitch between the middle and the top edge of this
+    /// `Widget`.
+    pub fn get_visible_pitch(&self) -> f64 {
+        self.visible_pitch
+    }
+
+    /// Internal function that draws the sky, ground, pitch ladder and roll pointer, for the current
+    /// pitch and no roll, to a square horizon texture of `side` pixels.
+    fn draw_horizon(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache, side: u32) {
+        self.horizon_store.create_or_resize_texture(c, side, side);
+
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let sky_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(70, 140, 220));
+        let ground_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(140, 90, 40));
+        let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(255, 255, 255));
+        let font_size =
+            self.get_numeric_or(CONFIG_FONT_SIZE, (bounds[SIZE_HEIGHT] as i32 / 20).max(8));
+        let middle = side as i32 / 2;
+        let pixels_per_degree = bounds[SIZE_HEIGHT] as f64 / 2.0 / self.visible_pitch;
+        let horizon_y = middle + (self.pitch * pixels_per_degree).round() as i32;
+        let roll_radius = bounds[SIZE_WIDTH].min(bounds[SIZE_HEIGHT]) as i32 / 2 - 4;
+        let pointer_size = (roll_radius / 10).max(4);
+        let pointer = [
+            Point::new(middle, middle - roll_radius + 2),
+            Point::new(
+                middle - pointer_size / 2,
+                middle - roll_radius + 2 + pointer_size,
+            ),
+            Point::new(
+                middle + pointer_size / 2,
+                middle - roll_radius + 2 + pointer_size,
+            ),
+        ];
+
+        // Only the rungs that can be seen at any roll are drawn, as the rest fall outside of the
+        // bounds of this `Widget`.
+        let rungs: Vec<(i32, i32, i32)> = (-18..=18)
+            .map(|step| step * 5)
+            .filter(|degrees| *degrees != 0)
+            .map(|degrees| {
+                let half_width = if degrees % 10 == 0 {
+                    bounds[SIZE_WIDTH] as i32 / 8
+                } else {
+                    bounds[SIZE_WIDTH] as i32 / 16
+                };
+
+                (
+                    degrees,
+                    horizon_y - (degrees as f64 * pixels_per_degree).round() as i32,
+                    half_width,
+                )
+            })
+            .filter(|(_, y, _)| *y > 0 && *y < side as i32)
+            .collect();
+
+        let ttf_context = t.get_ttf_context();
+        let texture_creator = c.texture_creator();
+        let font = ttf_context
+            .load_font(
+                Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                font_size as u16,
+            )
+            .unwrap();
+        let labels: Vec<(Texture, Rect, Rect)> = rungs
+            .iter()
+            .filter(|(degrees, _, _)| degrees % 10 == 0)
+            .map(|(degrees, y, half_width)| {
+                let surface = font
+                    .render(&degrees.abs().to_string())
+                    .blended(text_color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+                let label_texture = texture_creator
+                    .create_texture_from_surface(&surface)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+                let TextureQuery { width, height, .. } = label_texture.query();
+                let top = y - height as i32 / 2;
+
+                (
+                    label_texture,
+                    Rect::new(middle - half_width - 4 - width as i32, top, width, height),
+                    Rect::new(middle + half_width + 4, top, width, height),
+                )
+            })
+            .collect();
+
+        c.with_texture_canvas(self.horizon_store.get_mut_ref(), |texture| {
+            texture.set_draw_color(sky_color);
+            texture.clear();
+
+            if horizon_y < side as i32 {
+                let top = horizon_y.max(0);
+
+                texture.set_draw_color(ground_color);
+                texture
+                    .fill_rect(Rect::new(0, top, side, (side as i32 - top) as u32))
+                    .unwrap();
+            }
+
+            texture.set_draw_color(text_color);
+            texture
+                .draw_line(Point::new(0, horizon_y), Point::new(side as i32, horizon_y))
+                .unwrap();
+
+            // Rungs below the horizon are dashed, so that a nose down attitude is easy to tell.
+            for (degrees, y, half_width) in &rungs {
+                if *degrees > 0 {
+                    texture
+                        .draw_line(
+                            Point::new(middle - half_width, *y),
+                            Point::new(middle + half_width, *y),
+                        )
+                        .unwrap();
+                } else {
+                    for dash in (-half_width..*half_width).step_by(6) {
+                        texture
+                            .draw_line(
+                                Point::new(middle + dash, *y),
+                                Point::new(middle + (dash + 3).min(*half_width), *y),
+                            )
+                            .unwrap();
+                    }
+                }
+            }
+
+            for (label_texture, left, right) in &labels {
+                texture.copy(label_texture, None, *left).unwrap();
+                texture.copy(label_texture, None, *right).unwrap();
+            }
+
+            fill_polygon(texture, &pointer);
+        })
+        .unwrap();
+
+        // Safety: the label textures are only used while drawing the horizon.
+        for (label_texture, _, _) in labels {
+            unsafe {
+                label_texture.destroy();
+            }
+        }
+
+        self.horizon_invalidated = false;
+    }
+}
+
+/// This is the `Widget` implementation of the `ArtificialHorizonWidget`.
+impl Widget for ArtificialHorizonWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+            let width = bounds[SIZE_WIDTH] as i32;
+            let height = bounds[SIZE_HEIGHT] as i32;
+
+            // The horizon is as wide as the diagonal of this `Widget`, so that no corner is left
+            // uncovered when it is rotated.
+            let side = ((width as f64).hypot(height as f64).ceil() as u32 + 2).max(2);
+
+            if self.horizon_invalidated
+                || self.horizon_store.get_optional_ref().is_none()
+                || self.horizon_store.get_width() != side
+            {
+                self.draw_horizon(c, t, side);
+            }
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(255, 255, 255));
+            let aircraft_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(255, 200, 0));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let roll = self.roll;
+            let center = Point::new(width / 2, height / 2);
+            let roll_radius = width.min(height) / 2 - 4;
+            let horizon_rect = Rect::new(
+                center.x() - side as i32 / 2,
+                center.y() - side as i32 / 2,
+                side,
+                side,
+            );
+            let wing = (width / 4).max(6);
+            let gap = (width / 14).max(3);
+            let index_size = (roll_radius / 10).max(4);
+            let index = [
+                Point::new(center.x(), center.y() - roll_radius + 2),
+                Point::new(
+                    center.x() - index_size / 2,
+                    center.y() - roll_radius + 2 - index_size,
+                ),
+                Point::new(
+                    center.x() + index_size / 2,
+                    center.y() - roll_radius + 2 - index_size,
+                ),
+            ];
+            let horizon = self.horizon_store.get_optional_ref().unwrap();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                // The horizon turns the opposite way to the roll, as the aircraft banks around it.
+                copy_rotated(texture, horizon, horizon_rect, -roll);
+
+                texture.set_draw_color(text_color);
+                draw_arc(texture, center, roll_radius as u32, 2, -60.0, 60.0);
+
+                for mark in ROLL_MARKS.iter() {
+                    let length = if (*mark as i32) % 30 == 0 { 8.0 } else { 4.0 };
+
+                    texture
+                        .draw_line(
+                            point_at_angle(center, roll_radius as f64, *mark),
+                            point_at_angle(center, roll_radius as f64 + length, *mark),
+                        )
+                        .unwrap();
+                }
+
+                fill_polygon(texture, &index);
+
+                texture.set_draw_color(aircraft_color);
+                texture
+                    .fill_rect(Rect::new(
+                        center.x() - gap - wing,
+                        center.y() - 1,
+                        wing as u32,
+                        3,
+                    ))
+                    .unwrap();
+                texture
+                    .fill_rect(Rect::new(center.x() + gap, center.y() - 1, wing as u32, 3))
+                    .unwrap();
+                texture
+                    .fill_rect(Rect::new(center.x() - 2, center.y() - 2, 5, 5))
+                    .unwrap();
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]))
+                    .unwrap();
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Draws the horizon again when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE | CONFIG_COLOR_SECONDARY | CONFIG_COLOR_TEXT | CONFIG_FONT_SIZE => {
+                self.horizon_invalidated = true;
+                self.get_config().set_invalidated(true);
+            }
+            CONFIG_COLOR_SELECTED | CONFIG_COLOR_BORDER => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing textures of this `Widget`, including the horizon.  They are recreated
+    /// the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.horizon_store.release();
+        self.horizon_invalidated = true;
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
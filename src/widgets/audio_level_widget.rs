@@ -0,0 +1,292 @@
+// Pushrod Widget Library
+// Audio Level Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// This is the space between segments of the meter, in pixels.
+const SEGMENT_GAP: u32 = 2;
+
+/// This is the brightness of unlit segments, as a fraction of the color of their zone.
+const UNLIT_BRIGHTNESS: f64 = 0.25;
+
+/// This is a handle to the level of an `AudioLevelWidget`, which can be sent to another thread,
+/// such as an audio callback, and used to set the level without access to the `Widget`.  The level
+/// is picked up by the `Widget` on its next `tick`.  Handles can be cloned, and all clones set the
+/// same level.
+#[derive(Clone, Debug)]
+pub struct AudioLevelHandle {
+    level: Arc<AtomicU32>,
+}
+
+/// This is the implementation of the `AudioLevelHandle`.
+impl AudioLevelHandle {
+    /// Sets the level of the meter, from `0.0` to `1.0`.
+    pub fn set_level(&self, level: f32) {
+        self.level
+            .store(level.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Retrieves the level most recently set.
+    pub fn get_level(&self) -> f32 {
+        f32::from_bits(self.level.load(Ordering::Relaxed))
+    }
+}
+
+/// Darkens `color` to `brightness`, which ranges from `0.0` to `1.0`.
+fn dim_color(color: Color, brightness: f64) -> Color {
+    let dim = |channel: u8| (channel as f64 * brightness).round() as u8;
+
+    Color::RGBA(dim(color.r), dim(color.g), dim(color.b), color.a)
+}
+
+/// This is the storage object for the `AudioLevelWidget`.  It stores the config, properties,
+/// callback registry, the shared level, the level and peak shown, the zones, and the peak hold
+/// settings.
+pub struct AudioLevelWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    handle: AudioLevelHandle,
+    level: f32,
+    peak: f32,
+    hold_remaining: u32,
+    hold_ticks: u32,
+    peak_decay: f32,
+    segments: u32,
+    yellow_from: f32,
+    red_from: f32,
+    zone_colors: [Color; 3],
+}
+
+/// This is the implementation of the `AudioLevelWidget`, a segmented level meter, such as a VU
+/// meter for audio.  The meter fills from the bottom when it is taller than it is wide, and from the
+/// left otherwise.  Segments are green up to the yellow zone, yellow up to the red zone, and red
+/// beyond; unlit segments are drawn darkened.  The highest recent level is marked by a peak-hold
+/// segment, which stays for a number of ticks, then falls a little every tick.
+///
+/// The level is set with `set_level`, or from another thread, such as an audio callback, with an
+/// `AudioLevelHandle` from `get_handle`.  Either way, the new level is shown on the next `tick`, so
+/// it can be set as often as the audio is processed.  The space between segments is filled with
+/// `CONFIG_COLOR_BASE`.
+impl AudioLevelWidget {
+    /// Creates a new `AudioLevelWidget` given the `x, y, w, h` coordinates, and the number of
+    /// `segments`.  The yellow zone starts at `0.7`, and the red zone at `0.9`.  Peaks are held for
+    /// `30` ticks, then fall by `0.01` every tick.
+    pub fn new(points: Points, size: Size, segments: u32) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            handle: AudioLevelHandle {
+                level: Arc::new(AtomicU32::new(0.0f32.to_bits())),
+            },
+            level: 0.0,
+            peak: 0.0,
+            hold_remaining: 0,
+            hold_ticks: 30,
+            peak_decay: 0.01,
+            segments: segments.max(1),
+            yellow_from: 0.7,
+            red_from: 0.9,
+            zone_colors: [
+                Color::RGB(0, 200, 0),
+                Color::RGB(240, 200, 0),
+                Color::RGB(230, 0, 0),
+            ],
+        }
+    }
+
+    /// Sets the level of the meter, from `0.0` to `1.0`.  The level is shown on the next `tick`.
+    pub fn set_level(&mut self, level: f32) {
+        self.handle.set_level(level);
+    }
+
+    /// Retrieves the level shown by the meter.
+    pub fn get_level(&self) -> f32 {
+        self.level
+    }
+
+    /// Retrieves the level marked by the peak-hold segment.
+    pub fn get_peak(&self) -> f32 {
+        self.peak
+    }
+
+    /// Returns a handle that sets the level of the meter, and can be sent to another thread.
+    pub fn get_handle(&self) -> AudioLevelHandle {
+        self.handle.clone()
+    }
+
+    /// Sets the number of segments of the meter.
+    pub fn set_segments(&mut self, segments: u32) {
+        self.segments = segments.max(1);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the number of segments of the meter.
+    pub fn get_segments(&self) -> u32 {
+        self.segments
+    }
+
+    /// Sets the levels at which the yellow and red zones start, from `0.0` to `1.0`.
+    pub fn set_zones(&mut self, yellow_from: f32, red_from: f32) {
+        self.yellow_from = yellow_from.clamp(0.0, 1.0);
+        self.red_from = red_from.max(self.yellow_from).min(1.0);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the colors of the green, yellow, and red zones.
+    pub fn set_zone_colors(&mut self, green: Color, yellow: Color, red: Color) {
+        self.zone_colors = [green, yellow, red];
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the number of ticks a peak is held for, and how far it then falls every tick.  A hold
+    /// of `0` ticks starts the fall immediately.
+    pub fn set_peak_hold(&mut self, hold_ticks: u32, decay_per_tick: f32) {
+        self.hold_ticks = hold_ticks;
+        self.peak_decay = decay_per_tick.max(0.0);
+    }
+
+    /// Internal function that returns the color of the zone the level `position` falls in.
+    fn zone_color(&self, position: f32) -> Color {
+        if position >= self.red_from {
+            self.zone_colors[2]
+        } else if position >= self.yellow_from {
+            self.zone_colors[1]
+        } else {
+            self.zone_colors[0]
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `AudioLevelWidget`.
+impl Widget for AudioLevelWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(32, 32, 32));
+            let vertical = bounds[SIZE_HEIGHT] >= bounds[SIZE_WIDTH];
+            let length = if vertical {
+                bounds[SIZE_HEIGHT]
+            } else {
+                bounds[SIZE_WIDTH]
+            };
+            let segments = self.segments;
+            let lit = (self.level * segments as f32).round() as u32;
+            let peak = ((self.peak * segments as f32).ceil() as u32).min(segments);
+            let cells: Vec<(Rect, Color)> = (0..segments)
+                .map(|segment| {
+                    let start = length * segment / segments;
+                    let end = length * (segment + 1) / segments;
+                    let size = (end - start).saturating_sub(SEGMENT_GAP).max(1);
+                    let rect = if vertical {
+                        Rect::new(
+                            0,
+                            (bounds[SIZE_HEIGHT] - start - size) as i32,
+                            bounds[SIZE_WIDTH],
+                            size,
+                        )
+                    } else {
+                        Rect::new(start as i32, 0, size, bounds[SIZE_HEIGHT])
+                    };
+                    let color = self.zone_color((segment as f32 + 0.5) / segments as f32);
+
+                    if segment < lit || (peak > 0 && segment == peak - 1) {
+                        (rect, color)
+                    } else {
+                        (rect, dim_color(color, UNLIT_BRIGHTNESS))
+                    }
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (rect, color) in &cells {
+                    texture.set_draw_color(*color);
+                    texture.fill_rect(*rect).unwrap();
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Shows the level most recently set, and holds or lowers the peak.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let level = self.handle.get_level();
+        let previous = (self.level, self.peak);
+
+        self.level = level;
+
+        if level >= self.peak {
+            self.peak = level;
+            self.hold_remaining = self.hold_ticks;
+        } else if self.hold_remaining > 0 {
+            self.hold_remaining -= 1;
+        } else {
+            self.peak = (self.peak - self.peak_decay).max(level);
+        }
+
+        if previous != (self.level, self.peak) {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Redraws the meter when its base color changes.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        if _k == CONFIG_COLOR_BASE {
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,659 @@
+// Pushrod Widget Library
+// Auto Complete Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock;
+use crate::render::input_settings::{handle_edit_key, CaretBlink};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::{render_text_line, TextureCache};
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of each suggestion in the popup list.
+const ITEM_HEIGHT: u32 = 24;
+
+/// This is the maximum number of suggestions shown in the popup list at once.  Longer lists scroll.
+const MAX_VISIBLE_ITEMS: usize = 8;
+
+/// This is the number of pixels between the left edge of the `AutoCompleteWidget` and its text.
+const TEXT_PADDING: i32 = 6;
+
+/// This is the type of the function that provides the suggestions for the text being typed.
+pub type SuggestionProviderType = Option<Box<dyn Fn(&str) -> Vec<String>>>;
+
+/// This is the callback type that is used when an `on_text_committed` callback is triggered from
+/// this `Widget`.  It receives the committed text.
+pub type OnTextCommittedCallbackType =
+    Option<Box<dyn FnMut(&mut AutoCompleteWidget, &[WidgetContainer], &[LayoutContainer], String)>>;
+
+/// This is the storage object for the `AutoCompleteWidget`.  It stores the config, properties,
+/// callback registry, the font name and size, the text being edited, the cursor and scroll
+/// positions, the suggestion provider, and the state of the popup list of suggestions.
+pub struct AutoCompleteWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    popup_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    text: Vec<char>,
+    cursor: usize,
+    scroll_x: i32,
+    offsets: Vec<u32>,
+    provider: SuggestionProviderType,
+    suggestions: Vec<String>,
+    highlighted_item: i32,
+    popup_bounds: Option<Rect>,
+    popup_scroll: usize,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_text_committed: OnTextCommittedCallbackType,
+}
+
+/// This is the implementation of the `AutoCompleteWidget`, a single-line text field that opens a
+/// popup list of suggestions below it as the user types.  The suggestions are returned by a
+/// provider function, which is given the text typed so far, so they can be filtered from a fixed
+/// list, or looked up elsewhere.  The popup list is closed when the provider returns no
+/// suggestions, or the text is empty.
+///
+/// The `Up` and `Down` keys move through the suggestions, and `Return` or a click replaces the
+/// text with the highlighted suggestion.  Pressing `Return` with no suggestion highlighted keeps the
+/// typed text.  Either way, the popup list is closed, and the `on_text_committed` callback is
+/// triggered with the text.  `Escape` closes the popup list without changing the text.
+impl AutoCompleteWidget {
+    /// Creates a new `AutoCompleteWidget`, given the path to the font file, the size of the font in
+    /// pixels, and the `x, y, w, h` coordinates.  No suggestions are shown until a provider is set.
+    pub fn new(font_name: String, font_size: i32, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            popup_store: TextureStore::default(),
+            font_name,
+            font_size,
+            text: vec![],
+            cursor: 0,
+            scroll_x: 0,
+            offsets: vec![0],
+            provider: None,
+            suggestions: vec![],
+            highlighted_item: -1,
+            popup_bounds: None,
+            popup_scroll: 0,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_text_committed: None,
+        }
+    }
+
+    /// Sets the function that returns the suggestions for the text typed so far, in the order they
+    /// are listed.
+    pub fn set_provider<F>(&mut self, provider: F)
+    where
+        F: Fn(&str) -> Vec<String> + 'static,
+    {
+        self.provider = Some(Box::new(provider));
+    }
+
+    /// Replaces the text, moving the cursor to the end, and closing the popup list.  This does not
+    /// trigger the `on_text_committed` callback.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().collect();
+        self.cursor = self.text.len();
+        self.close_popup();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the text.
+    pub fn get_text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Retrieves the suggestions for the current text, as last returned by the provider.
+    pub fn get_suggestions(&self) -> &[String] {
+        &self.suggestions
+    }
+
+    /// Indicates whether or not the popup list of suggestions is open.
+    pub fn is_open(&self) -> bool {
+        self.popup_bounds.is_some()
+    }
+
+    /// Assigns the callback closure that will be used when the text is committed with `Return`, or
+    /// by choosing a suggestion.
+    pub fn on_text_committed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut AutoCompleteWidget, &[WidgetContainer], &[LayoutContainer], String) + 'static,
+    {
+        self.on_text_committed = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_text_committed` callback.
+    fn call_text_committed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_text_committed.take() {
+            cb(self, widgets, layouts, self.text.iter().collect());
+            self.on_text_committed = Some(cb);
+        }
+    }
+
+    /// Asks the provider for the suggestions for the current text, and opens the popup list below
+    /// this `Widget` if there are any, or above it if there is not enough room below it within the
+    /// top-level `Widget`.  Otherwise, the popup list is closed.
+    fn update_suggestions(&mut self, widgets: &[WidgetContainer]) {
+        let text: String = self.text.iter().collect();
+
+        self.suggestions = match &self.provider {
+            Some(provider) if !text.is_empty() => provider(&text),
+            _ => vec![],
+        };
+        self.highlighted_item = -1;
+        self.popup_scroll = 0;
+
+        if self.suggestions.is_empty() {
+            self.close_popup();
+            return;
+        }
+
+        let origin = self.get_config().get_point(CONFIG_ORIGIN);
+        let size = self.get_config().get_size(CONFIG_SIZE);
+        let height = ITEM_HEIGHT * self.suggestions.len().min(MAX_VISIBLE_ITEMS) as u32 + 2;
+        let window_height = widgets
+            .first()
+            .map(|base| base.widget.borrow_mut().get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT])
+            .unwrap_or(u32::MAX);
+        let below = origin[POINT_Y] + size[SIZE_HEIGHT] as i32;
+        let y = if below as i64 + height as i64 > window_height as i64
+            && origin[POINT_Y] >= height as i32
+        {
+            origin[POINT_Y] - height as i32
+        } else {
+            below
+        };
+
+        self.popup_bounds = Some(Rect::new(origin[0], y, size[SIZE_WIDTH], height));
+    }
+
+    /// Replaces the text with the suggestion at `index`, closing the popup list, and triggering the
+    /// `on_text_committed` callback.
+    fn accept(&mut self, index: i32, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if let Some(suggestion) = self.suggestions.get(index as usize) {
+            self.text = suggestion.chars().collect();
+            self.cursor = self.text.len();
+            self.close_popup();
+            self.get_config().set_invalidated(true);
+            self.call_text_committed_callback(widgets, layouts);
+        }
+    }
+
+    /// Scrolls the popup list so that the suggestion at `index` is visible.
+    fn reveal_item(&mut self, index: i32) {
+        if index < 0 {
+            return;
+        }
+
+        let index = index as usize;
+
+        if index < self.popup_scroll {
+            self.popup_scroll = index;
+        } else if index >= self.popup_scroll + MAX_VISIBLE_ITEMS {
+            self.popup_scroll = index + 1 - MAX_VISIBLE_ITEMS;
+        }
+    }
+
+    /// Returns the index of the suggestion at the given window Y coordinate, or `-1` if there is
+    /// none.
+    fn item_at(&self, y: i32) -> i32 {
+        match self.popup_bounds {
+            Some(bounds) if y > bounds.y() && y < bounds.bottom() - 1 => {
+                let index =
+                    self.popup_scroll + ((y - bounds.y() - 1) as u32 / ITEM_HEIGHT) as usize;
+
+                if index < self.suggestions.len() {
+                    index as i32
+                } else {
+                    -1
+                }
+            }
+            _ => -1,
+        }
+    }
+
+    /// Moves the cursor to the character closest to the last known mouse position.
+    fn move_cursor_to_mouse(&mut self) {
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(TEXT_PADDING) + self.scroll_x;
+
+        self.cursor = self
+            .offsets
+            .windows(2)
+            .position(|pair| x < ((pair[0] + pair[1]) / 2) as i32)
+            .unwrap_or(self.offsets.len() - 1)
+            .min(self.text.len());
+    }
+}
+
+/// This is the `Widget` implementation of the `AutoCompleteWidget`.
+impl Widget for AutoCompleteWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let text_width = (bounds[SIZE_WIDTH] as i32 - TEXT_PADDING * 2).max(1);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+            let line_height = font.recommended_line_spacing().max(1);
+
+            self.offsets = (0..=self.text.len())
+                .map(|end| {
+                    let prefix: String = self.text[..end].iter().collect();
+
+                    font.size_of(&prefix).map(|(w, _)| w).unwrap_or(0)
+                })
+                .collect();
+
+            // The text is scrolled horizontally so that the cursor is always within the field.
+            let caret_x = self.offsets[self.cursor.min(self.text.len())] as i32;
+
+            if caret_x - self.scroll_x >= text_width {
+                self.scroll_x = caret_x - text_width + 1;
+            } else if caret_x < self.scroll_x {
+                self.scroll_x = caret_x;
+            }
+
+            let scroll_x = self.scroll_x;
+            let text_y = (bounds[SIZE_HEIGHT] as i32 - line_height) / 2;
+            let caret = if self.focused && self.caret.is_shown() {
+                Some(TEXT_PADDING + caret_x - scroll_x)
+            } else {
+                None
+            };
+            let text: String = self.text.iter().collect();
+            let font_texture = render_text_line(&font, &texture_creator, &text, text_color);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let Some(font_texture) = &font_texture {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+                    let visible_width = (width as i32 - scroll_x).min(text_width);
+
+                    if visible_width > 0 {
+                        texture
+                            .copy(
+                                font_texture,
+                                Rect::new(scroll_x, 0, visible_width as u32, height),
+                                Rect::new(TEXT_PADDING, text_y, visible_width as u32, height),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                texture.set_draw_color(text_color);
+
+                if let Some(x) = caret {
+                    texture
+                        .fill_rect(Rect::new(x, text_y, 1, line_height as u32))
+                        .unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            if let Some(font_texture) = font_texture {
+                // Safety: the font texture is only used within this function.
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Draws the popup list of suggestions, highlighting the suggestion under the mouse, or chosen
+    /// with the arrow keys.
+    fn draw_popup(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        let bounds = self.popup_bounds?;
+
+        self.popup_store
+            .create_or_resize_texture(c, bounds.width(), bounds.height());
+
+        let base_color = self.get_color(CONFIG_COLOR_BASE);
+        let border_color = self.get_color(CONFIG_COLOR_BORDER);
+        let hover_color = self.get_color(CONFIG_COLOR_HOVER);
+        let text_color = self.get_color(CONFIG_COLOR_TEXT);
+        let first_item = self.popup_scroll;
+        let last_item = (first_item + MAX_VISIBLE_ITEMS).min(self.suggestions.len());
+        let highlighted_item = self.highlighted_item;
+
+        let ttf_context = t.get_ttf_context();
+        let texture_creator = c.texture_creator();
+        let font = ttf_context
+            .load_font(Path::new(&self.font_name), self.font_size as u16)
+            .unwrap();
+        let item_textures: Vec<Option<Texture>> = self.suggestions[first_item..last_item]
+            .iter()
+            .map(|item| render_text_line(&font, &texture_creator, item, text_color))
+            .collect();
+
+        c.with_texture_canvas(self.popup_store.get_mut_ref(), |texture| {
+            texture.set_draw_color(base_color);
+            texture.clear();
+
+            for (row, font_texture) in item_textures.iter().enumerate() {
+                let y = 1 + (row as u32 * ITEM_HEIGHT) as i32;
+
+                if (first_item + row) as i32 == highlighted_item {
+                    texture.set_draw_color(hover_color);
+                    texture
+                        .fill_rect(Rect::new(1, y, bounds.width() - 2, ITEM_HEIGHT))
+                        .unwrap();
+                }
+
+                if let Some(font_texture) = font_texture {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+
+                    texture
+                        .copy(
+                            font_texture,
+                            None,
+                            Rect::new(
+                                TEXT_PADDING,
+                                y + (ITEM_HEIGHT as i32 - height as i32) / 2,
+                                width,
+                                height,
+                            ),
+                        )
+                        .unwrap();
+                }
+            }
+
+            texture.set_draw_color(border_color);
+            texture
+                .draw_rect(Rect::new(0, 0, bounds.width(), bounds.height()))
+                .unwrap();
+        })
+        .unwrap();
+
+        // Safety: the item textures are only used within this function.
+        for font_texture in item_textures.into_iter().flatten() {
+            unsafe {
+                font_texture.destroy();
+            }
+        }
+
+        self.popup_store.get_optional_ref()
+    }
+
+    fn get_popup_bounds(&mut self) -> Option<Rect> {
+        self.popup_bounds
+    }
+
+    fn close_popup(&mut self) {
+        self.popup_bounds = None;
+        self.highlighted_item = -1;
+    }
+
+    /// Blinks the caret while this `Widget` has focus.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the suggestion under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        if self.popup_bounds.is_some() {
+            let highlighted_item = self.item_at(self.mouse_position[POINT_Y]);
+
+            if highlighted_item >= 0 && highlighted_item != self.highlighted_item {
+                self.highlighted_item = highlighted_item;
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the popup list of suggestions.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        if self.popup_bounds.is_some() {
+            let max_scroll = self.suggestions.len().saturating_sub(MAX_VISIBLE_ITEMS) as i32;
+            let popup_scroll = (self.popup_scroll as i32 - points[POINT_Y]).clamp(0, max_scroll);
+
+            if popup_scroll as usize != self.popup_scroll {
+                self.popup_scroll = popup_scroll as usize;
+                self.highlighted_item = self.item_at(self.mouse_position[POINT_Y]);
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Accepts the clicked suggestion, or moves the cursor to the clicked position in the text.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let index = self.item_at(self.mouse_position[POINT_Y]);
+            let in_popup = self.popup_bounds.is_some_and(|bounds| {
+                bounds.contains_point((self.mouse_position[POINT_X], self.mouse_position[POINT_Y]))
+            });
+
+            if in_popup {
+                self.accept(index, _widgets, _layouts);
+            } else {
+                self.move_cursor_to_mouse();
+                self.caret.restart(clock::now_millis());
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles text input, cursor navigation, and choosing suggestions while this `Widget` has
+    /// focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let is_open = self.popup_bounds.is_some();
+        let last_item = self.suggestions.len() as i32 - 1;
+
+        let (text_changed, cursor_moved) = match event {
+            Event::TextInput { text, .. } => {
+                for c in text.chars() {
+                    self.text.insert(self.cursor, c);
+                    self.cursor += 1;
+                }
+
+                (true, true)
+            }
+            Event::KeyDown {
+                keycode: Some(keycode @ Keycode::Up),
+                ..
+            }
+            | Event::KeyDown {
+                keycode: Some(keycode @ Keycode::Down),
+                ..
+            } if is_open => {
+                let delta = if keycode == Keycode::Up { -1 } else { 1 };
+
+                self.highlighted_item = (self.highlighted_item + delta).clamp(0, last_item);
+                self.reveal_item(self.highlighted_item);
+                self.get_config().set_invalidated(true);
+                (false, false)
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Down),
+                ..
+            } => {
+                self.update_suggestions(_widgets);
+                self.get_config().set_invalidated(true);
+                (false, false)
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Return),
+                ..
+            }
+            | Event::KeyDown {
+                keycode: Some(Keycode::KpEnter),
+                ..
+            } => {
+                if is_open && self.highlighted_item >= 0 {
+                    self.accept(self.highlighted_item, _widgets, _layouts);
+                } else {
+                    self.close_popup();
+                    self.get_config().set_invalidated(true);
+                    self.call_text_committed_callback(_widgets, _layouts);
+                }
+
+                (false, true)
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Escape),
+                ..
+            } if is_open => {
+                self.close_popup();
+                self.get_config().set_invalidated(true);
+                (false, false)
+            }
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } => handle_edit_key(keycode, &mut self.text, &mut self.cursor),
+            _ => (false, false),
+        };
+
+        if cursor_moved {
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        if text_changed {
+            self.update_suggestions(_widgets);
+        }
+    }
+
+    /// Monitors for changes in the text, colors, or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _)
+            | (CONFIG_COLOR_BASE, _)
+            | (CONFIG_COLOR_BORDER, _)
+            | (CONFIG_COLOR_HOVER, _) => self.get_config().set_invalidated(true),
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            (CONFIG_TEXT, Config::Text(text)) => self.set_text(&text),
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus, and closes the popup list when it loses
+    /// focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+
+        if !focused {
+            self.close_popup();
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus.
+    fn accepts_text_input(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing textures of this `Widget`.  The textures are recreated the next time
+    /// this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.popup_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
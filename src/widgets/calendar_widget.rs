@@ -0,0 +1,619 @@
+// Pushrod Widget Library
+// Calendar Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::Font;
+use sdl2::video::Window;
+
+use crate::render::clock;
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of the header, which shows the month and the navigation buttons, in pixels.
+const HEADER_HEIGHT: u32 = 30;
+
+/// This is the height of the row of weekday names, in pixels.
+const WEEKDAY_HEIGHT: u32 = 20;
+
+/// This is the width of the previous and next month buttons, in pixels.
+const NAV_BUTTON_WIDTH: u32 = 30;
+
+/// This is the number of rows in the month grid, which is enough to show any month.
+const GRID_ROWS: u32 = 6;
+
+/// These are the names of the months, starting with January.
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// These are the abbreviated names of the days of the week, starting with Sunday.
+const WEEKDAY_NAMES: [&str; 7] = ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"];
+
+/// This is the callback type that is used when an `on_date_selected` callback is triggered from
+/// this `Widget`.  It receives the newly selected `CalendarDate`.
+pub type OnDateSelectedCallbackType = Option<
+    Box<dyn FnMut(&mut CalendarWidget, &[WidgetContainer], &[LayoutContainer], CalendarDate)>,
+>;
+
+/// This is a date in the proleptic Gregorian calendar.  Months and days are numbered starting
+/// at `1`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct CalendarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// This is the implementation of the `CalendarDate`, which provides the date arithmetic used by
+/// the `CalendarWidget`.
+impl CalendarDate {
+    /// Creates a new `CalendarDate`.  The `month` is clamped from `1` to `12`, and the `day` is
+    /// clamped to the number of days in that month.
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        let month = month.clamp(1, 12);
+
+        Self {
+            year,
+            month,
+            day: day.clamp(1, days_in_month(year, month)),
+        }
+    }
+
+    /// Returns today's date in UTC, as reported by the `clock` module.
+    pub fn today() -> Self {
+        Self::from_days((clock::now_millis() / 86_400_000) as i64)
+    }
+
+    /// Creates a `CalendarDate` from the number of days since January 1, 1970.
+    pub fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = (year_of_era + era * 400) as i32 + if month <= 2 { 1 } else { 0 };
+
+        Self { year, month, day }
+    }
+
+    /// Returns the number of days between January 1, 1970 and this date.
+    pub fn to_days(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Returns the day of the week of this date, where `0` is Sunday and `6` is Saturday.
+    pub fn weekday(&self) -> u32 {
+        (self.to_days() + 4).rem_euclid(7) as u32
+    }
+
+    /// Returns the date that is `days` days after this date, or before it if `days` is negative.
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_days(self.to_days() + days)
+    }
+
+    /// Returns the date that is `months` months after this date, or before it if `months` is
+    /// negative.  The day is clamped to the number of days in the resulting month.
+    pub fn add_months(&self, months: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+
+        Self::new(
+            index.div_euclid(12),
+            index.rem_euclid(12) as u32 + 1,
+            self.day,
+        )
+    }
+}
+
+/// Indicates whether or not `year` is a leap year.
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days in the `month` of `year`.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// These are the parts of a `CalendarWidget` that can be clicked.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum CalendarHit {
+    /// Nothing that can be clicked.
+    Nothing,
+
+    /// The previous month button.
+    PreviousMonth,
+
+    /// The next month button.
+    NextMonth,
+
+    /// A day of the displayed month.
+    Day(u32),
+}
+
+/// This is the storage object for the `CalendarWidget`.  It stores the config, properties,
+/// callback registry, the displayed month, the selected date, and the part of the `Widget` under
+/// the mouse.
+pub struct CalendarWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    year: i32,
+    month: u32,
+    selected: Option<CalendarDate>,
+    hovered: CalendarHit,
+    on_date_selected: OnDateSelectedCallbackType,
+}
+
+/// This is the implementation of the `CalendarWidget`, which draws a grid of the days of a month,
+/// with buttons in its header that move to the previous and next months.  Today's date is outlined,
+/// and the selected date is highlighted.  Clicking on a day selects it.  While the `Widget` has
+/// focus, the arrow keys move the selection by a day or a week, and the `PageUp` and `PageDown`
+/// keys move it by a month.  The mouse wheel changes the displayed month.
+impl CalendarWidget {
+    /// Creates a new `CalendarWidget` given the `x, y, w, h` coordinates.  It initially displays
+    /// the current month, with no date selected.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+        let today = CalendarDate::today();
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            year: today.year,
+            month: today.month,
+            selected: None,
+            hovered: CalendarHit::Nothing,
+            on_date_selected: None,
+        }
+    }
+
+    /// Selects a `date`, and displays the month that contains it.  This does not trigger the
+    /// `on_date_selected` callback.
+    pub fn set_selected_date(&mut self, date: Option<CalendarDate>) {
+        if let Some(date) = date {
+            self.year = date.year;
+            self.month = date.month;
+        }
+
+        self.selected = date;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the selected date, if any.
+    pub fn get_selected_date(&self) -> Option<CalendarDate> {
+        self.selected
+    }
+
+    /// Displays the `month` of `year`.  The `month` is clamped from `1` to `12`.
+    pub fn set_displayed_month(&mut self, year: i32, month: u32) {
+        self.year = year;
+        self.month = month.clamp(1, 12);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the displayed year and month.
+    pub fn get_displayed_month(&self) -> (i32, u32) {
+        (self.year, self.month)
+    }
+
+    /// Displays the month after the one currently displayed.
+    pub fn next_month(&mut self) {
+        self.change_month(1);
+    }
+
+    /// Displays the month before the one currently displayed.
+    pub fn previous_month(&mut self) {
+        self.change_month(-1);
+    }
+
+    /// Assigns the callback closure that will be used when a date is selected by clicking on it,
+    /// or by moving the selection with the keyboard.
+    pub fn on_date_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut CalendarWidget, &[WidgetContainer], &[LayoutContainer], CalendarDate)
+            + 'static,
+    {
+        self.on_date_selected = Some(Box::new(callback));
+    }
+
+    /// Internal function that changes the displayed month by `months` months.
+    fn change_month(&mut self, months: i32) {
+        let date = CalendarDate::new(self.year, self.month, 1).add_months(months);
+
+        self.set_displayed_month(date.year, date.month);
+    }
+
+    /// Internal function that selects `date`, displays its month, and triggers the
+    /// `on_date_selected` callback.
+    fn select_date(
+        &mut self,
+        date: CalendarDate,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        self.set_selected_date(Some(date));
+
+        if let Some(mut cb) = self.on_date_selected.take() {
+            cb(self, widgets, layouts, date);
+            self.on_date_selected = Some(cb);
+        }
+    }
+
+    /// Internal function that returns the size of a single cell of the month grid.
+    fn cell_size(&mut self) -> (u32, u32) {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+
+        (
+            (size[SIZE_WIDTH] / 7).max(1),
+            (size[SIZE_HEIGHT].saturating_sub(HEADER_HEIGHT + WEEKDAY_HEIGHT) / GRID_ROWS).max(1),
+        )
+    }
+
+    /// Internal function that returns the bounds of the cell that shows `day` of the displayed
+    /// month, relative to the `Widget`.
+    fn day_bounds(&mut self, day: u32) -> Rect {
+        let (cell_width, cell_height) = self.cell_size();
+        let index = CalendarDate::new(self.year, self.month, 1).weekday() + day - 1;
+
+        Rect::new(
+            ((index % 7) * cell_width) as i32,
+            (HEADER_HEIGHT + WEEKDAY_HEIGHT + (index / 7) * cell_height) as i32,
+            cell_width,
+            cell_height,
+        )
+    }
+
+    /// Internal function that returns the part of the `Widget` at the window coordinates in
+    /// `points`.
+    fn hit_at(&mut self, points: &[i32]) -> CalendarHit {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+        let x = points[POINT_X] - self.get_config().to_x(0);
+        let y = points[POINT_Y] - self.get_config().to_y(0);
+
+        if x < 0 || y < 0 || x >= size[SIZE_WIDTH] as i32 || y >= size[SIZE_HEIGHT] as i32 {
+            return CalendarHit::Nothing;
+        }
+
+        if y < HEADER_HEIGHT as i32 {
+            return if x < NAV_BUTTON_WIDTH as i32 {
+                CalendarHit::PreviousMonth
+            } else if x >= (size[SIZE_WIDTH] - NAV_BUTTON_WIDTH.min(size[SIZE_WIDTH])) as i32 {
+                CalendarHit::NextMonth
+            } else {
+                CalendarHit::Nothing
+            };
+        }
+
+        for day in 1..=days_in_month(self.year, self.month) {
+            if self.day_bounds(day).contains_point(Point::new(x, y)) {
+                return CalendarHit::Day(day);
+            }
+        }
+
+        CalendarHit::Nothing
+    }
+}
+
+/// Draws `text` centered within `bounds`.
+fn draw_centered_text(c: &mut Canvas<Window>, font: &Font, text: &str, color: Color, bounds: Rect) {
+    let texture_creator = c.texture_creator();
+    let surface = font
+        .render(text)
+        .blended(color)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    let font_texture = texture_creator
+        .create_texture_from_surface(&surface)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    let TextureQuery { width, height, .. } = font_texture.query();
+
+    c.copy(
+        &font_texture,
+        None,
+        Rect::new(
+            bounds.x() + (bounds.width() as i32 - width as i32) / 2,
+            bounds.y() + (bounds.height() as i32 - height as i32) / 2,
+            width,
+            height,
+        ),
+    )
+    .unwrap();
+
+    // Safety: the font texture is only used within this function.
+    unsafe {
+        font_texture.destroy();
+    }
+}
+
+/// This is the `Widget` implementation of the `CalendarWidget`.
+impl Widget for CalendarWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let header_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(224, 224, 224));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(200, 200, 240));
+            let selected_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(64, 96, 192));
+            let today = CalendarDate::today();
+            let title = format!("{} {}", MONTH_NAMES[self.month as usize - 1], self.year);
+            let (cell_width, _) = self.cell_size();
+            let day_cells: Vec<(u32, Rect)> = (1..=days_in_month(self.year, self.month))
+                .map(|day| (day, self.day_bounds(day)))
+                .collect();
+            let (year, month, selected, hovered) =
+                (self.year, self.month, self.selected, self.hovered);
+
+            let ttf_context = t.get_ttf_context();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(header_color);
+                texture
+                    .fill_rect(Rect::new(0, 0, bounds[SIZE_WIDTH], HEADER_HEIGHT))
+                    .unwrap();
+
+                for (hit, x, label) in &[
+                    (CalendarHit::PreviousMonth, 0, "<"),
+                    (
+                        CalendarHit::NextMonth,
+                        bounds[SIZE_WIDTH].saturating_sub(NAV_BUTTON_WIDTH) as i32,
+                        ">",
+                    ),
+                ] {
+                    let button_bounds = Rect::new(*x, 0, NAV_BUTTON_WIDTH, HEADER_HEIGHT);
+
+                    if hovered == *hit {
+                        texture.set_draw_color(hover_color);
+                        texture.fill_rect(button_bounds).unwrap();
+                    }
+
+                    draw_centered_text(texture, &font, label, text_color, button_bounds);
+                }
+
+                draw_centered_text(
+                    texture,
+                    &font,
+                    &title,
+                    text_color,
+                    Rect::new(0, 0, bounds[SIZE_WIDTH], HEADER_HEIGHT),
+                );
+
+                for (index, name) in WEEKDAY_NAMES.iter().enumerate() {
+                    draw_centered_text(
+                        texture,
+                        &font,
+                        name,
+                        text_color,
+                        Rect::new(
+                            index as i32 * cell_width as i32,
+                            HEADER_HEIGHT as i32,
+                            cell_width,
+                            WEEKDAY_HEIGHT,
+                        ),
+                    );
+                }
+
+                for (day, cell) in &day_cells {
+                    let date = CalendarDate::new(year, month, *day);
+                    let mut day_color = text_color;
+
+                    if selected == Some(date) {
+                        texture.set_draw_color(selected_color);
+                        texture.fill_rect(*cell).unwrap();
+                        day_color = base_color;
+                    } else if hovered == CalendarHit::Day(*day) {
+                        texture.set_draw_color(hover_color);
+                        texture.fill_rect(*cell).unwrap();
+                    }
+
+                    if today == date {
+                        texture.set_draw_color(selected_color);
+                        texture.draw_rect(*cell).unwrap();
+                    }
+
+                    draw_centered_text(texture, &font, &day.to_string(), day_color, *cell);
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]))
+                    .unwrap();
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Removes the highlight from the part of the `Widget` that was under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.hovered != CalendarHit::Nothing {
+            self.hovered = CalendarHit::Nothing;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the navigation button or day under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let hovered = self.hit_at(&points);
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Changes the displayed month by one month for each step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        if points[POINT_Y] != 0 {
+            self.change_month(-points[POINT_Y]);
+            self.hovered = CalendarHit::Nothing;
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Changes the displayed month when a navigation button is clicked, or selects the day that was
+    /// clicked.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 && _state {
+            match self.hovered {
+                CalendarHit::PreviousMonth => self.previous_month(),
+                CalendarHit::NextMonth => self.next_month(),
+                CalendarHit::Day(day) => {
+                    let date = CalendarDate::new(self.year, self.month, day);
+
+                    self.select_date(date, _widgets, _layouts);
+                }
+                CalendarHit::Nothing => (),
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Moves the selection by a day with the `Left` and `Right` keys, by a week with the `Up` and
+    /// `Down` keys, and by a month with the `PageUp` and `PageDown` keys.  If no date is selected,
+    /// the first day of the displayed month is selected.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            let current = self
+                .selected
+                .unwrap_or_else(|| CalendarDate::new(self.year, self.month, 1));
+            let date = match keycode {
+                Keycode::Left => Some(current.add_days(-1)),
+                Keycode::Right => Some(current.add_days(1)),
+                Keycode::Up => Some(current.add_days(-7)),
+                Keycode::Down => Some(current.add_days(7)),
+                Keycode::PageUp => Some(current.add_months(-1)),
+                Keycode::PageDown => Some(current.add_months(1)),
+                _ => None,
+            };
+
+            if let Some(date) = date {
+                let date = if self.selected.is_some() {
+                    date
+                } else {
+                    current
+                };
+
+                self.select_date(date, _widgets, _layouts);
+            }
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,130 @@
+// Pushrod Widget Library
+// Canvas Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the closure type that is called to draw the contents of a `CanvasWidget`.  It receives
+/// the canvas of the `Widget`'s texture, and the bounds of the texture.
+pub type CanvasDrawFunction = Box<dyn FnMut(&mut Canvas<Window>, &Rect)>;
+
+/// This is the optional draw closure stored by a `CanvasWidget`.
+pub type CanvasDrawFunctionType = Option<CanvasDrawFunction>;
+
+/// This is the storage object for the `CanvasWidget`.  It stores the config, properties, callback
+/// registry, and the draw closure.
+pub struct CanvasWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    draw_fn: CanvasDrawFunctionType,
+}
+
+/// This is the implementation of the `CanvasWidget`, which hands its texture to a user supplied
+/// closure for drawing, so that custom drawing can be done without implementing the `Widget`
+/// trait.  The texture is cleared to `CONFIG_COLOR_BASE` before the closure is called, and
+/// coordinates are relative to the top left corner of this `Widget`.  Like any other `Widget`, the
+/// closure is only called again once this `Widget` is invalidated, so animated drawing should call
+/// `set_invalidated(true)`, for instance from a `TimerWidget`.
+impl CanvasWidget {
+    /// Creates a new `CanvasWidget` given the `x, y, w, h` coordinates.  Nothing is drawn until a
+    /// draw closure is set.
+    pub fn new(points: Points, size: Size) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            draw_fn: None,
+        }
+    }
+
+    /// Sets the closure that draws the contents of this `Widget`, and redraws it.
+    pub fn set_draw_fn(&mut self, draw_fn: CanvasDrawFunction) {
+        self.draw_fn = Some(draw_fn);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Removes the draw closure, leaving this `Widget` filled with `CONFIG_COLOR_BASE`.
+    pub fn clear_draw_fn(&mut self) {
+        self.draw_fn = None;
+        self.get_config().set_invalidated(true);
+    }
+}
+
+/// This is the `Widget` implementation of the `CanvasWidget`.
+impl Widget for CanvasWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = if self.get_config().config.contains_key(&CONFIG_COLOR_BASE) {
+                self.get_color(CONFIG_COLOR_BASE)
+            } else {
+                Color::RGB(255, 255, 255)
+            };
+            let draw_fn = &mut self.draw_fn;
+            let rect = Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let Some(draw_fn) = draw_fn {
+                    draw_fn(texture, &rect);
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Redraws this `Widget` when its base color changes.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        if _k == CONFIG_COLOR_BASE {
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,536 @@
+// Pushrod Widget Library
+// Chart Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::draw_grid;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::Font;
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the width in pixels reserved to the left of the plot area for the value labels.
+const VALUE_LABEL_WIDTH: u32 = 48;
+
+/// This is the height in pixels reserved below the plot area for the category labels, when any
+/// are set.
+const CATEGORY_LABEL_HEIGHT: u32 = 20;
+
+/// This is the number of pixels left between the plot area and the edges of the `Widget`.
+const CHART_PADDING: u32 = 8;
+
+/// This is the approximate number of gridlines drawn across the value axis.
+const GRIDLINE_COUNT: f64 = 5.0;
+
+/// This is the fraction of each category slot that is covered by bars in a bar chart.
+const BAR_GROUP_FRACTION: f64 = 0.8;
+
+/// This is the style in which a `ChartWidget` draws its series.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChartKind {
+    /// Each series is drawn as a line joining its values.
+    Line,
+
+    /// Each series is drawn as a bar for each of its values, grouped side by side by category.
+    Bar,
+}
+
+/// This is a series of values drawn by a `ChartWidget`, along with the color it is drawn in.
+#[derive(Clone, Debug)]
+pub struct ChartSeries {
+    pub data: Vec<f64>,
+    pub color: Color,
+}
+
+/// This is the storage object for the `ChartWidget`.  It stores the config, properties, callback
+/// registry, the chart kind, the series and category labels, and the value range.
+pub struct ChartWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    kind: ChartKind,
+    series: Vec<ChartSeries>,
+    labels: Vec<String>,
+    autoscale: bool,
+    min: f64,
+    max: f64,
+    gridlines: bool,
+}
+
+/// This is the implementation of the `ChartWidget`, which draws one or more series of values as a
+/// line or bar chart, with a value axis, category labels and gridlines.  By default, the value
+/// axis is scaled automatically to fit all of the series, rounded out to the nearest gridline.
+/// The plot area is drawn in `CONFIG_COLOR_BASE`, the gridlines in `CONFIG_COLOR_SECONDARY`, the
+/// axes in `CONFIG_COLOR_BORDER`, and the labels in `CONFIG_COLOR_TEXT`.
+impl ChartWidget {
+    /// Creates a new `ChartWidget` of the given `kind`, given the `x, y, w, h` coordinates.  The
+    /// chart has no series until `add_series` is called.
+    pub fn new(points: Points, size: Size, kind: ChartKind) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            kind,
+            series: vec![],
+            labels: vec![],
+            autoscale: true,
+            min: 0.0,
+            max: 1.0,
+            gridlines: true,
+        }
+    }
+
+    /// Sets the style in which the series are drawn.
+    pub fn set_kind(&mut self, kind: ChartKind) {
+        self.kind = kind;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the style in which the series are drawn.
+    pub fn get_kind(&self) -> ChartKind {
+        self.kind
+    }
+
+    /// Adds a series of values drawn in `color`, returning its index.
+    pub fn add_series(&mut self, data: Vec<f64>, color: Color) -> usize {
+        self.series.push(ChartSeries { data, color });
+        self.get_config().set_invalidated(true);
+        self.series.len() - 1
+    }
+
+    /// Replaces the values of the series at `index`.  Only this `Widget` is redrawn.  Indexes out
+    /// of range are ignored.
+    pub fn set_data(&mut self, index: usize, data: Vec<f64>) {
+        if let Some(series) = self.series.get_mut(index) {
+            series.data = data;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the values of the series at `index`, or an empty slice if it does not exist.
+    pub fn get_data(&self, index: usize) -> &[f64] {
+        self.series
+            .get(index)
+            .map(|series| series.data.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Sets the color that the series at `index` is drawn in.  Indexes out of range are ignored.
+    pub fn set_series_color(&mut self, index: usize, color: Color) {
+        if let Some(series) = self.series.get_mut(index) {
+            series.color = color;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the color that the series at `index` is drawn in.
+    pub fn get_series_color(&self, index: usize) -> Option<Color> {
+        self.series.get(index).map(|series| series.color)
+    }
+
+    /// Retrieves all of the series drawn by this `Widget`.
+    pub fn get_series(&self) -> &[ChartSeries] {
+        &self.series
+    }
+
+    /// Removes all of the series.
+    pub fn clear_series(&mut self) {
+        self.series.clear();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the labels drawn below each category.  When no labels are set, the plot area extends
+    /// to the bottom of the `Widget`.
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        self.labels = labels;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the labels drawn below each category.
+    pub fn get_labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Sets a fixed range for the value axis, turning off automatic scaling.  Values outside of
+    /// the range are clipped to the plot area.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.autoscale = false;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Turns automatic scaling of the value axis on or off.  When turned off, the range last set
+    /// by `set_range` is used.
+    pub fn set_autoscale(&mut self, autoscale: bool) {
+        self.autoscale = autoscale;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the value axis is scaled automatically.
+    pub fn get_autoscale(&self) -> bool {
+        self.autoscale
+    }
+
+    /// Turns the gridlines on or off.  When off, a dot is drawn where the gridlines would cross.
+    pub fn set_gridlines(&mut self, gridlines: bool) {
+        self.gridlines = gridlines;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the gridlines are drawn.
+    pub fn get_gridlines(&self) -> bool {
+        self.gridlines
+    }
+
+    /// Retrieves the range of the value axis currently shown, and the distance between its
+    /// gridlines.
+    pub fn get_value_range(&self) -> (f64, f64, f64) {
+        if !self.autoscale {
+            let step = nice_step(self.max - self.min);
+
+            return (self.min, self.max, step);
+        }
+
+        let values = self.series.iter().flat_map(|series| series.data.iter());
+        let (mut min, mut max) = values
+            .filter(|value| value.is_finite())
+            .fold((f64::MAX, f64::MIN), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+
+        if min > max {
+            min = 0.0;
+            max = 1.0;
+        }
+
+        if self.kind == ChartKind::Bar {
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+
+        if min == max {
+            min -= 1.0;
+            max += 1.0;
+        }
+
+        let step = nice_step(max - min);
+
+        (
+            (min / step).floor() * step,
+            (max / step).ceil() * step,
+            step,
+        )
+    }
+
+    /// Returns the number of categories, which is the length of the longest series.
+    fn category_count(&self) -> usize {
+        self.series
+            .iter()
+            .map(|series| series.data.len())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Returns a round distance between gridlines, that splits `range` into roughly
+/// `GRIDLINE_COUNT` parts.
+fn nice_step(range: f64) -> f64 {
+    if range <= 0.0 || !range.is_finite() {
+        return 1.0;
+    }
+
+    let raw = range / GRIDLINE_COUNT;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let fraction = raw / magnitude;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+
+    nice * magnitude
+}
+
+/// Formats `value` with as many decimal places as are needed to tell gridlines `step` apart.
+fn format_value(value: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+
+    format!("{:.*}", decimals, value)
+}
+
+/// Draws `text` in `color` within `bounds`, vertically centered.  The text is right aligned if
+/// `right_aligned` is set, otherwise it is centered horizontally.
+fn draw_label(
+    c: &mut Canvas<Window>,
+    font: &Font,
+    text: &str,
+    color: Color,
+    bounds: Rect,
+    right_aligned: bool,
+) {
+    if text.is_empty() {
+        return;
+    }
+
+    let texture_creator = c.texture_creator();
+    let surface = font
+        .render(text)
+        .blended(color)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    let font_texture = texture_creator
+        .create_texture_from_surface(&surface)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    let TextureQuery { width, height, .. } = font_texture.query();
+    let x = if right_aligned {
+        bounds.right() - width as i32
+    } else {
+        bounds.center().x() - width as i32 / 2
+    };
+
+    c.copy(
+        &font_texture,
+        None,
+        Rect::new(x, bounds.center().y() - height as i32 / 2, width, height),
+    )
+    .unwrap();
+
+    // Safety: the font texture is only used within this function.
+    unsafe {
+        font_texture.destroy();
+    }
+}
+
+/// This is the `Widget` implementation of the `ChartWidget`.
+impl Widget for ChartWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let grid_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(220, 220, 220));
+            let axis_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let label_height = if self.labels.is_empty() {
+                0
+            } else {
+                CATEGORY_LABEL_HEIGHT
+            };
+            let plot = Rect::new(
+                (VALUE_LABEL_WIDTH + CHART_PADDING) as i32,
+                CHART_PADDING as i32,
+                bounds[SIZE_WIDTH]
+                    .saturating_sub(VALUE_LABEL_WIDTH + CHART_PADDING * 2)
+                    .max(1),
+                bounds[SIZE_HEIGHT]
+                    .saturating_sub(label_height + CHART_PADDING * 2)
+                    .max(1),
+            );
+            let (min, max, step) = self.get_value_range();
+            let range = (max - min).max(f64::EPSILON);
+            let y_of = |value: f64| -> i32 {
+                let value = value.max(min).min(max);
+
+                plot.bottom() - ((value - min) / range * plot.height() as f64).round() as i32
+            };
+
+            // Gridlines fall on multiples of the step, so the first one lies at or below the top of
+            // the value axis.
+            let top_gridline = (max / step).floor() * step;
+            let gridline_values: Vec<f64> = (0..)
+                .map(|i| top_gridline - i as f64 * step)
+                .take_while(|value| *value >= min - step * 1e-6)
+                .collect();
+            let grid_area = Rect::new(
+                plot.x(),
+                y_of(top_gridline),
+                plot.width(),
+                (plot.bottom() - y_of(top_gridline)).max(1) as u32,
+            );
+            let categories = self.category_count().max(self.labels.len());
+            let slot_width = plot.width() as f64 / categories.max(1) as f64;
+            let slot_center = |index: usize| -> i32 {
+                plot.x() + (slot_width * (index as f64 + 0.5)).round() as i32
+            };
+            let kind = self.kind;
+            let series = self.series.clone();
+            let labels = self.labels.clone();
+            let gridlines = self.gridlines;
+
+            let ttf_context = t.get_ttf_context();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 12)
+                .unwrap();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(grid_color);
+                draw_grid(
+                    texture,
+                    grid_area,
+                    slot_width,
+                    step / range * plot.height() as f64,
+                    gridlines,
+                );
+
+                match kind {
+                    ChartKind::Line => {
+                        for series in &series {
+                            let points: Vec<Point> = series
+                                .data
+                                .iter()
+                                .enumerate()
+                                .map(|(index, value)| Point::new(slot_center(index), y_of(*value)))
+                                .collect();
+
+                            texture.set_draw_color(series.color);
+
+                            for offset in &[0, 1] {
+                                let points: Vec<Point> = points
+                                    .iter()
+                                    .map(|point| point.offset(0, *offset))
+                                    .collect();
+
+                                texture.draw_lines(points.as_slice()).unwrap();
+                            }
+                        }
+                    }
+                    ChartKind::Bar => {
+                        let baseline = y_of(0.0);
+                        let bar_width =
+                            (slot_width * BAR_GROUP_FRACTION / series.len().max(1) as f64).max(1.0);
+
+                        for (series_index, series) in series.iter().enumerate() {
+                            texture.set_draw_color(series.color);
+
+                            for (index, value) in series.data.iter().enumerate() {
+                                let left = plot.x() as f64
+                                    + slot_width * index as f64
+                                    + slot_width * (1.0 - BAR_GROUP_FRACTION) / 2.0
+                                    + bar_width * series_index as f64;
+                                let top = y_of(*value);
+
+                                texture
+                                    .fill_rect(Rect::new(
+                                        left.round() as i32,
+                                        top.min(baseline),
+                                        bar_width.round() as u32,
+                                        (top - baseline).unsigned_abs().max(1),
+                                    ))
+                                    .unwrap();
+                            }
+                        }
+                    }
+                }
+
+                texture.set_draw_color(axis_color);
+                texture
+                    .draw_line(
+                        Point::new(plot.x(), plot.y()),
+                        Point::new(plot.x(), plot.bottom()),
+                    )
+                    .unwrap();
+                texture
+                    .draw_line(
+                        Point::new(plot.x(), plot.bottom()),
+                        Point::new(plot.right(), plot.bottom()),
+                    )
+                    .unwrap();
+
+                for value in &gridline_values {
+                    draw_label(
+                        texture,
+                        &font,
+                        &format_value(*value, step),
+                        text_color,
+                        Rect::new(
+                            0,
+                            y_of(*value) - CATEGORY_LABEL_HEIGHT as i32 / 2,
+                            VALUE_LABEL_WIDTH,
+                            CATEGORY_LABEL_HEIGHT,
+                        ),
+                        true,
+                    );
+                }
+
+                for (index, label) in labels.iter().enumerate() {
+                    draw_label(
+                        texture,
+                        &font,
+                        label,
+                        text_color,
+                        Rect::new(
+                            slot_center(index) - slot_width as i32 / 2,
+                            plot.bottom(),
+                            slot_width.max(1.0) as u32,
+                            label_height,
+                        ),
+                        false,
+                    );
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Monitors for changes in the colors.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_SECONDARY
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_TEXT => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -99,6 +99,7 @@ impl CheckboxWidget {
         checked_widget.set_compass(CONFIG_IMAGE_POSITION, Center);
 
         config.set_toggle(CONFIG_SELECTED_STATE, selected);
+        config.set_toggle(CONFIG_FOCUSABLE, true);
 
         Self {
             config,
@@ -250,6 +251,16 @@ impl Widget for CheckboxWidget {
         self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
     }
 
+    /// Releases the backing texture of this `Widget`, along with those of the `Widget`s it is
+    /// composed of.  The textures are recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.text_widget.release_textures();
+        self.unchecked_widget.release_textures();
+        self.checked_widget.release_textures();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
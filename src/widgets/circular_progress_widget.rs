@@ -0,0 +1,161 @@
+// Pushrod Widget Library
+// Circular Progress Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_arc;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Point;
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use sdl2::render::{Canvas, Texture};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the default thickness of the ring, in pixels.
+const DEFAULT_THICKNESS: u32 = 8;
+
+/// This is the storage object for the `CircularProgressWidget`.  It stores the config, properties,
+/// callback registry, progress from 0 to 100, the thickness of the ring, and the angle at which
+/// the progress arc starts.
+pub struct CircularProgressWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    progress: u8,
+    thickness: u32,
+    start_angle: f64,
+}
+
+/// This is the implementation of the `CircularProgressWidget`, which draws progress as an arc that
+/// sweeps clockwise around a ring.  The ring is drawn in `CONFIG_COLOR_TRACK`, and the progress arc
+/// over it in `CONFIG_COLOR_SECONDARY`.
+impl CircularProgressWidget {
+    /// Creates a new instance of the `CircularProgressWidget` object.  It draws a progress ring
+    /// given the `xywh` coordinates, and the `progress` from 0-100.  The ring is centered within
+    /// the bounds of the `Widget`, and is as large as the smaller of its width and height.  The
+    /// progress arc starts at the top of the ring.
+    pub fn new(points: Points, size: Size, progress: u8) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            progress: progress.min(100),
+            thickness: DEFAULT_THICKNESS,
+            start_angle: 0.0,
+        }
+    }
+
+    /// Sets the progress for the widget.  Progress value is between 0 and 100.  Anything over
+    /// 100 will just set the progress to 100.
+    pub fn set_progress(&mut self, progress: u8) {
+        self.progress = progress.min(100);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the current progress value as a `u8` value.
+    pub fn get_progress(&mut self) -> u8 {
+        self.progress
+    }
+
+    /// Sets the thickness of the ring in pixels.  A thickness of at least the radius of the ring
+    /// draws the progress as a filled pie.
+    pub fn set_thickness(&mut self, thickness: u32) {
+        self.thickness = thickness;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the thickness of the ring in pixels.
+    pub fn get_thickness(&self) -> u32 {
+        self.thickness
+    }
+
+    /// Sets the angle in degrees at which the progress arc starts.  `0.0` is the top of the ring,
+    /// and angles increase clockwise, so `90.0` starts the arc at the right side of the ring.
+    pub fn set_start_angle(&mut self, start_angle: f64) {
+        self.start_angle = start_angle;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the angle in degrees at which the progress arc starts.
+    pub fn get_start_angle(&self) -> f64 {
+        self.start_angle
+    }
+}
+
+/// This is the `Widget` implementation of the `CircularProgressWidget`.
+impl Widget for CircularProgressWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let track_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(220, 220, 220));
+            let fill_color = self.get_color(CONFIG_COLOR_SECONDARY);
+            let outer_radius = (bounds[SIZE_WIDTH].min(bounds[SIZE_HEIGHT]) / 2).saturating_sub(1);
+            let inner_radius = outer_radius.saturating_sub(self.thickness);
+            let center = Point::new(
+                (bounds[SIZE_WIDTH] / 2) as i32,
+                (bounds[SIZE_HEIGHT] / 2) as i32,
+            );
+            let start_angle = self.start_angle;
+            let end_angle = start_angle + 360.0 * f64::from(self.progress) / 100.0;
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(track_color);
+                fill_arc(texture, center, outer_radius, inner_radius, 0.0, 360.0);
+
+                texture.set_draw_color(fill_color);
+                fill_arc(
+                    texture,
+                    center,
+                    outer_radius,
+                    inner_radius,
+                    start_angle,
+                    end_angle,
+                );
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,1011 @@
+// Pushrod Widget Library
+// Code Editor Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock;
+use crate::render::input_settings::CaretBlink;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between the border of the `CodeEditorWidget` and its text, and
+/// between the line numbers and the text.
+const TEXT_PADDING: u32 = 4;
+
+/// This is the default number of columns between tab stops.
+const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// These are the keywords highlighted by the `RustTokenizer`.
+const RUST_KEYWORDS: [&str; 39] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "yield",
+];
+
+/// This is the kind of a `Token`, which decides the color it is drawn in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    Type,
+    String,
+    Number,
+    Comment,
+    Punctuation,
+}
+
+/// This is a run of characters within a line that are drawn in the same color.  `start` and `end`
+/// are character indexes into the line, with `end` being exclusive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub kind: TokenKind,
+}
+
+/// This is the interface used by the `CodeEditorWidget` to color its text.  Lines are tokenized in
+/// order, from the first line, and each line receives the state returned for the line before it
+/// (`0` for the first line), so that tokens such as block comments can span lines.  Characters
+/// that are not covered by a `Token` are drawn as `TokenKind::Plain`.
+pub trait Tokenizer {
+    /// Splits a line into tokens, returning them along with the state to pass to the next line.
+    fn tokenize_line(&self, line: &[char], state: u32) -> (Vec<Token>, u32);
+}
+
+/// This is a `Tokenizer` that leaves all text plain.
+pub struct PlainTokenizer;
+
+impl Tokenizer for PlainTokenizer {
+    fn tokenize_line(&self, _line: &[char], state: u32) -> (Vec<Token>, u32) {
+        (vec![], state)
+    }
+}
+
+/// This is a `Tokenizer` for Rust source code, which highlights keywords, type names, string and
+/// character literals, numbers, and line and block comments.  Nested block comments are treated
+/// as a single comment.
+pub struct RustTokenizer;
+
+/// This is the state of the `RustTokenizer` at the end of a line that is within a block comment.
+const RUST_IN_COMMENT: u32 = 1;
+
+/// This is the state of the `RustTokenizer` at the end of a line that is within a string literal.
+const RUST_IN_STRING: u32 = 2;
+
+/// Returns the index just past the end of the string literal that continues from `start`, or
+/// `None` if the string continues onto the next line.
+fn string_end(line: &[char], start: usize) -> Option<usize> {
+    let mut index = start;
+
+    while index < line.len() {
+        match line[index] {
+            '\\' => index += 2,
+            '"' => return Some(index + 1),
+            _ => index += 1,
+        }
+    }
+
+    None
+}
+
+impl Tokenizer for RustTokenizer {
+    fn tokenize_line(&self, line: &[char], state: u32) -> (Vec<Token>, u32) {
+        let mut tokens = vec![];
+        let mut index = 0;
+        let mut push = |start: usize, end: usize, kind: TokenKind| {
+            tokens.push(Token { start, end, kind });
+        };
+
+        match state {
+            RUST_IN_COMMENT => match line.windows(2).position(|pair| pair == ['*', '/']) {
+                Some(position) => {
+                    index = position + 2;
+                    push(0, index, TokenKind::Comment);
+                }
+                None => {
+                    push(0, line.len(), TokenKind::Comment);
+                    return (tokens, RUST_IN_COMMENT);
+                }
+            },
+            RUST_IN_STRING => match string_end(line, 0) {
+                Some(end) => {
+                    index = end;
+                    push(0, index, TokenKind::String);
+                }
+                None => {
+                    push(0, line.len(), TokenKind::String);
+                    return (tokens, RUST_IN_STRING);
+                }
+            },
+            _ => (),
+        }
+
+        while index < line.len() {
+            let c = line[index];
+            let next = line.get(index + 1).copied();
+            let start = index;
+
+            if c == '/' && next == Some('/') {
+                push(start, line.len(), TokenKind::Comment);
+                break;
+            } else if c == '/' && next == Some('*') {
+                match line[start + 2..]
+                    .windows(2)
+                    .position(|pair| pair == ['*', '/'])
+                {
+                    Some(position) => {
+                        index = start + 2 + position + 2;
+                        push(start, index, TokenKind::Comment);
+                    }
+                    None => {
+                        push(start, line.len(), TokenKind::Comment);
+                        return (tokens, RUST_IN_COMMENT);
+                    }
+                }
+            } else if c == '"' {
+                match string_end(line, start + 1) {
+                    Some(end) => {
+                        index = end;
+                        push(start, index, TokenKind::String);
+                    }
+                    None => {
+                        push(start, line.len(), TokenKind::String);
+                        return (tokens, RUST_IN_STRING);
+                    }
+                }
+            } else if c == '\'' && next == Some('\\') {
+                index = line[start + 2..]
+                    .iter()
+                    .position(|c| *c == '\'')
+                    .map(|position| start + 2 + position + 1)
+                    .unwrap_or(line.len());
+                push(start, index, TokenKind::String);
+            } else if c == '\'' && line.get(index + 2) == Some(&'\'') {
+                index += 3;
+                push(start, index, TokenKind::String);
+            } else if c.is_ascii_digit() {
+                while index < line.len() && (line[index].is_alphanumeric() || line[index] == '_') {
+                    index += 1;
+                }
+
+                if line.get(index) == Some(&'.')
+                    && line.get(index + 1).is_some_and(|c| c.is_ascii_digit())
+                {
+                    index += 1;
+
+                    while index < line.len()
+                        && (line[index].is_alphanumeric() || line[index] == '_')
+                    {
+                        index += 1;
+                    }
+                }
+
+                push(start, index, TokenKind::Number);
+            } else if c.is_alphabetic() || c == '_' {
+                while index < line.len() && (line[index].is_alphanumeric() || line[index] == '_') {
+                    index += 1;
+                }
+
+                let word: String = line[start..index].iter().collect();
+
+                if RUST_KEYWORDS.contains(&word.as_str()) {
+                    push(start, index, TokenKind::Keyword);
+                } else if c.is_uppercase() {
+                    push(start, index, TokenKind::Type);
+                }
+            } else if c.is_whitespace() {
+                index += 1;
+            } else {
+                index += 1;
+                push(start, index, TokenKind::Punctuation);
+            }
+        }
+
+        (tokens, 0)
+    }
+}
+
+/// This is the callback type that is used when an `on_text_changed` callback is triggered from this
+/// `Widget`.  It receives the new text.
+pub type OnTextChangedCallbackType =
+    Option<Box<dyn FnMut(&mut CodeEditorWidget, &[WidgetContainer], &[LayoutContainer], &str)>>;
+
+/// This is the callback type that is used when an `on_cursor_moved` callback is triggered from this
+/// `Widget`.  It receives the line and column of the cursor, both starting at `0`.
+pub type OnCursorMovedCallbackType = Option<
+    Box<dyn FnMut(&mut CodeEditorWidget, &[WidgetContainer], &[LayoutContainer], usize, usize)>,
+>;
+
+/// This is the storage object for the `CodeEditorWidget`.  It stores the config, properties,
+/// callback registry, the font name and size, the lines being edited, the cursor and scroll
+/// positions, the tokenizer and its colors, and the cache of rendered characters.
+pub struct CodeEditorWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    lines: Vec<Vec<char>>,
+    cursor_line: usize,
+    cursor_column: usize,
+    preferred_column: usize,
+    scroll_line: usize,
+    scroll_column: usize,
+    visible_lines: usize,
+    visible_columns: usize,
+    char_width: u32,
+    line_height: u32,
+    gutter_width: u32,
+    tokenizer: Box<dyn Tokenizer>,
+    token_colors: HashMap<TokenKind, Color>,
+    glyphs: HashMap<(char, Color), Texture>,
+    read_only: bool,
+    show_line_numbers: bool,
+    tab_width: usize,
+    reveal_cursor: bool,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_text_changed: OnTextChangedCallbackType,
+    on_cursor_moved: OnCursorMovedCallbackType,
+}
+
+/// This is the implementation of the `CodeEditorWidget`, a multi-line editor for source code.  Text
+/// is laid out on a grid of fixed width cells, so it lines up in columns even if the font is not
+/// monospaced, and scrolls both vertically and horizontally, without wrapping.  Line numbers are
+/// drawn in a gutter in `CONFIG_COLOR_SECONDARY`, and text is colored by a pluggable `Tokenizer`.
+/// Text that the `Tokenizer` leaves plain is drawn in `CONFIG_COLOR_TEXT`.  Tabs are expanded to
+/// spaces.  The editor can be made read-only to show highlighted code without allowing changes.
+impl CodeEditorWidget {
+    /// Creates a new `CodeEditorWidget`, given the path to the font file, the size of the font in
+    /// pixels, and the `x, y, w, h` coordinates.  Text is not highlighted until a `Tokenizer` is
+    /// set.
+    pub fn new(font_name: String, font_size: i32, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+        let mut token_colors = HashMap::new();
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+        token_colors.insert(TokenKind::Keyword, Color::RGB(170, 50, 160));
+        token_colors.insert(TokenKind::Type, Color::RGB(30, 110, 160));
+        token_colors.insert(TokenKind::String, Color::RGB(40, 140, 60));
+        token_colors.insert(TokenKind::Number, Color::RGB(180, 90, 20));
+        token_colors.insert(TokenKind::Comment, Color::RGB(130, 130, 130));
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            lines: vec![vec![]],
+            cursor_line: 0,
+            cursor_column: 0,
+            preferred_column: 0,
+            scroll_line: 0,
+            scroll_column: 0,
+            visible_lines: 1,
+            visible_columns: 1,
+            char_width: (font_size.max(2) / 2) as u32,
+            line_height: font_size.max(1) as u32,
+            gutter_width: 0,
+            tokenizer: Box::new(PlainTokenizer),
+            token_colors,
+            glyphs: HashMap::new(),
+            read_only: false,
+            show_line_numbers: true,
+            tab_width: DEFAULT_TAB_WIDTH,
+            reveal_cursor: false,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_text_changed: None,
+            on_cursor_moved: None,
+        }
+    }
+
+    /// Replaces the text being edited, moving the cursor to the start of the text.  Tabs are
+    /// expanded to spaces.  This does not trigger the `on_text_changed` callback.
+    pub fn set_text(&mut self, text: &str) {
+        let tab_width = self.tab_width;
+
+        self.lines = text
+            .split('\n')
+            .map(|line| {
+                let mut expanded = vec![];
+
+                for c in line.trim_end_matches('\r').chars() {
+                    if c == '\t' {
+                        let spaces = tab_width - expanded.len() % tab_width;
+
+                        expanded.extend(std::iter::repeat_n(' ', spaces));
+                    } else {
+                        expanded.push(c);
+                    }
+                }
+
+                expanded
+            })
+            .collect();
+        self.cursor_line = 0;
+        self.cursor_column = 0;
+        self.preferred_column = 0;
+        self.scroll_line = 0;
+        self.scroll_column = 0;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the text being edited, with lines separated by newlines.
+    pub fn get_text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Retrieves the number of lines in the text.
+    pub fn get_line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Retrieves the line and column of the cursor, both starting at `0`.
+    pub fn get_cursor(&self) -> (usize, usize) {
+        (self.cursor_line, self.cursor_column)
+    }
+
+    /// Moves the cursor to a line and column, clamped to the text, and scrolls it into view.  This
+    /// does not trigger the `on_cursor_moved` callback.
+    pub fn set_cursor(&mut self, line: usize, column: usize) {
+        self.cursor_line = line.min(self.lines.len() - 1);
+        self.cursor_column = column.min(self.lines[self.cursor_line].len());
+        self.preferred_column = self.cursor_column;
+        self.reveal_cursor = true;
+        self.caret.restart(clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the `Tokenizer` used to color the text.
+    pub fn set_tokenizer(&mut self, tokenizer: Box<dyn Tokenizer>) {
+        self.tokenizer = tokenizer;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the color that tokens of `kind` are drawn in.
+    pub fn set_token_color(&mut self, kind: TokenKind, color: Color) {
+        self.token_colors.insert(kind, color);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the color that tokens of `kind` are drawn in, or `None` if they are drawn in
+    /// `CONFIG_COLOR_TEXT`.
+    pub fn get_token_color(&self, kind: TokenKind) -> Option<Color> {
+        self.token_colors.get(&kind).copied()
+    }
+
+    /// Sets whether the text can be changed by the user.  The cursor can still be moved while the
+    /// text is read-only.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Indicates whether the text can be changed by the user.
+    pub fn get_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Shows or hides the line numbers.
+    pub fn set_show_line_numbers(&mut self, show_line_numbers: bool) {
+        self.show_line_numbers = show_line_numbers;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the line numbers are shown.
+    pub fn get_show_line_numbers(&self) -> bool {
+        self.show_line_numbers
+    }
+
+    /// Sets the number of columns between tab stops, used when the `Tab` key is pressed, and when
+    /// expanding tabs in text passed to `set_text`.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width.max(1);
+    }
+
+    /// Retrieves the number of columns between tab stops.
+    pub fn get_tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Assigns the callback closure that will be used when the text is changed by the user.
+    pub fn on_text_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut CodeEditorWidget, &[WidgetContainer], &[LayoutContainer], &str) + 'static,
+    {
+        self.on_text_changed = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when the cursor is moved by the user, either
+    /// by navigating, clicking, or editing the text.
+    pub fn on_cursor_moved<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut CodeEditorWidget, &[WidgetContainer], &[LayoutContainer], usize, usize)
+            + 'static,
+    {
+        self.on_cursor_moved = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_text_changed` callback.
+    fn call_text_changed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_text_changed.take() {
+            let text = CodeEditorWidget::get_text(self);
+
+            cb(self, widgets, layouts, &text);
+            self.on_text_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that triggers the `on_cursor_moved` callback.
+    fn call_cursor_moved_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_cursor_moved.take() {
+            let (line, column) = self.get_cursor();
+
+            cb(self, widgets, layouts, line, column);
+            self.on_cursor_moved = Some(cb);
+        }
+    }
+
+    /// Inserts text at the cursor, moving the cursor past it.  Newlines split the line.
+    fn insert(&mut self, text: &str) {
+        for c in text.chars() {
+            if c == '\n' {
+                let rest = self.lines[self.cursor_line].split_off(self.cursor_column);
+
+                self.cursor_line += 1;
+                self.cursor_column = 0;
+                self.lines.insert(self.cursor_line, rest);
+            } else if c != '\r' {
+                self.lines[self.cursor_line].insert(self.cursor_column, c);
+                self.cursor_column += 1;
+            }
+        }
+    }
+
+    /// Moves the cursor `delta` lines up or down, keeping to the preferred column where the line is
+    /// long enough.
+    fn move_lines(&mut self, delta: isize) {
+        let target = (self.cursor_line as isize + delta).clamp(0, self.lines.len() as isize - 1);
+
+        self.cursor_line = target as usize;
+        self.cursor_column = self
+            .preferred_column
+            .min(self.lines[self.cursor_line].len());
+    }
+
+    /// Handles a key press, returning whether the text and the cursor changed.
+    fn handle_edit_key(&mut self, keycode: Keycode) -> (bool, bool) {
+        let cursor = self.get_cursor();
+        let editable = !self.read_only;
+        let mut text_changed = false;
+        let mut keep_preferred_column = false;
+
+        match keycode {
+            Keycode::Left if self.cursor_column > 0 => self.cursor_column -= 1,
+            Keycode::Left if self.cursor_line > 0 => {
+                self.cursor_line -= 1;
+                self.cursor_column = self.lines[self.cursor_line].len();
+            }
+            Keycode::Right if self.cursor_column < self.lines[self.cursor_line].len() => {
+                self.cursor_column += 1
+            }
+            Keycode::Right if self.cursor_line + 1 < self.lines.len() => {
+                self.cursor_line += 1;
+                self.cursor_column = 0;
+            }
+            Keycode::Up => {
+                self.move_lines(-1);
+                keep_preferred_column = true;
+            }
+            Keycode::Down => {
+                self.move_lines(1);
+                keep_preferred_column = true;
+            }
+            Keycode::PageUp => {
+                self.move_lines(-(self.visible_lines as isize));
+                keep_preferred_column = true;
+            }
+            Keycode::PageDown => {
+                self.move_lines(self.visible_lines as isize);
+                keep_preferred_column = true;
+            }
+            Keycode::Home => {
+                // Moves to the first non-blank character, or to the start of the line if already
+                // there.
+                let indent = self.lines[self.cursor_line]
+                    .iter()
+                    .take_while(|c| **c == ' ')
+                    .count();
+
+                self.cursor_column = if self.cursor_column == indent {
+                    0
+                } else {
+                    indent
+                };
+            }
+            Keycode::End => self.cursor_column = self.lines[self.cursor_line].len(),
+            Keycode::Backspace if editable && self.cursor_column > 0 => {
+                self.cursor_column -= 1;
+                self.lines[self.cursor_line].remove(self.cursor_column);
+                text_changed = true;
+            }
+            Keycode::Backspace if editable && self.cursor_line > 0 => {
+                let line = self.lines.remove(self.cursor_line);
+
+                self.cursor_line -= 1;
+                self.cursor_column = self.lines[self.cursor_line].len();
+                self.lines[self.cursor_line].extend(line);
+                text_changed = true;
+            }
+            Keycode::Delete
+                if editable && self.cursor_column < self.lines[self.cursor_line].len() =>
+            {
+                self.lines[self.cursor_line].remove(self.cursor_column);
+                text_changed = true;
+            }
+            Keycode::Delete if editable && self.cursor_line + 1 < self.lines.len() => {
+                let line = self.lines.remove(self.cursor_line + 1);
+
+                self.lines[self.cursor_line].extend(line);
+                text_changed = true;
+            }
+            Keycode::Return | Keycode::KpEnter if editable => {
+                // Keeps the indentation of the current line.
+                let indent = self.lines[self.cursor_line]
+                    .iter()
+                    .take_while(|c| **c == ' ')
+                    .count()
+                    .min(self.cursor_column);
+
+                self.insert("\n");
+                self.insert(&" ".repeat(indent));
+                text_changed = true;
+            }
+            Keycode::Tab if editable => {
+                let spaces = self.tab_width - self.cursor_column % self.tab_width;
+
+                self.insert(&" ".repeat(spaces));
+                text_changed = true;
+            }
+            _ => (),
+        }
+
+        if !keep_preferred_column {
+            self.preferred_column = self.cursor_column;
+        }
+
+        (text_changed, text_changed || cursor != self.get_cursor())
+    }
+
+    /// Moves the cursor to the character cell closest to the last known mouse position.
+    fn move_cursor_to_mouse(&mut self) {
+        let left = (TEXT_PADDING + self.gutter_width) as i32;
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(left);
+        let y = self.mouse_position[POINT_Y] - self.get_config().to_y(TEXT_PADDING as i32);
+        let line = self.scroll_line + (y.max(0) as u32 / self.line_height.max(1)) as usize;
+        let column = self.scroll_column
+            + ((x.max(0) as u32 + self.char_width / 2) / self.char_width.max(1)) as usize;
+
+        self.cursor_line = line.min(self.lines.len() - 1);
+        self.cursor_column = column.min(self.lines[self.cursor_line].len());
+        self.preferred_column = self.cursor_column;
+    }
+
+    /// Clamps the scroll position to the text, and scrolls the cursor into view if it has moved.
+    fn update_scroll(&mut self) {
+        if self.reveal_cursor {
+            if self.cursor_line < self.scroll_line {
+                self.scroll_line = self.cursor_line;
+            } else if self.cursor_line >= self.scroll_line + self.visible_lines {
+                self.scroll_line = self.cursor_line + 1 - self.visible_lines;
+            }
+
+            if self.cursor_column < self.scroll_column {
+                self.scroll_column = self.cursor_column;
+            } else if self.cursor_column >= self.scroll_column + self.visible_columns {
+                self.scroll_column = self.cursor_column + 1 - self.visible_columns;
+            }
+
+            self.reveal_cursor = false;
+        }
+
+        let longest_line = self.lines.iter().map(|line| line.len()).max().unwrap_or(0);
+
+        self.scroll_line = self
+            .scroll_line
+            .min(self.lines.len().saturating_sub(self.visible_lines));
+        self.scroll_column = self
+            .scroll_column
+            .min((longest_line + 1).saturating_sub(self.visible_columns));
+    }
+
+    /// Destroys the rendered characters, which are recreated as they are needed.
+    fn clear_glyphs(&mut self) {
+        for (_, glyph) in self.glyphs.drain() {
+            // Safety: the rendered characters are only used by this `Widget`.
+            unsafe {
+                glyph.destroy();
+            }
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `CodeEditorWidget`.
+impl Widget for CodeEditorWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let line_number_color = self.get_color(CONFIG_COLOR_SECONDARY);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+
+            // Every character is drawn in a cell as wide as the widest digit or capital letter, so
+            // that columns line up.
+            self.char_width = "0123456789MW"
+                .chars()
+                .filter_map(|c| font.size_of_char(c).ok())
+                .map(|(w, _)| w)
+                .max()
+                .unwrap_or(1)
+                .max(1);
+            self.line_height = font.recommended_line_spacing().max(1) as u32;
+
+            let number_digits = self.lines.len().to_string().len() as u32;
+
+            self.gutter_width = if self.show_line_numbers {
+                number_digits * self.char_width + TEXT_PADDING
+            } else {
+                0
+            };
+
+            let text_left = TEXT_PADDING + self.gutter_width;
+
+            self.visible_lines = ((bounds[SIZE_HEIGHT].saturating_sub(TEXT_PADDING * 2))
+                / self.line_height)
+                .max(1) as usize;
+            self.visible_columns = ((bounds[SIZE_WIDTH].saturating_sub(text_left + TEXT_PADDING))
+                / self.char_width)
+                .max(1) as usize;
+            self.update_scroll();
+
+            // Lines are tokenized from the start of the text, so that tokens spanning lines are
+            // colored correctly.
+            let last_line = (self.scroll_line + self.visible_lines + 1).min(self.lines.len());
+            let mut state = 0;
+            let mut cells: Vec<(i32, i32, char, Color)> = vec![];
+
+            for (index, line) in self.lines[..last_line].iter().enumerate() {
+                let (tokens, next_state) = self.tokenizer.tokenize_line(line, state);
+
+                state = next_state;
+
+                if index < self.scroll_line {
+                    continue;
+                }
+
+                let row = (index - self.scroll_line) as i32;
+                let y = TEXT_PADDING as i32 + row * self.line_height as i32;
+                let mut colors = vec![text_color; line.len()];
+
+                for token in tokens {
+                    if let Some(color) = self.token_colors.get(&token.kind) {
+                        for cell in colors
+                            .iter_mut()
+                            .take(token.end.min(line.len()))
+                            .skip(token.start)
+                        {
+                            *cell = *color;
+                        }
+                    }
+                }
+
+                for (column, ch) in line
+                    .iter()
+                    .enumerate()
+                    .skip(self.scroll_column)
+                    .take(self.visible_columns + 1)
+                {
+                    if !ch.is_whitespace() {
+                        let x = text_left as i32
+                            + (column - self.scroll_column) as i32 * self.char_width as i32;
+
+                        cells.push((x, y, *ch, colors[column]));
+                    }
+                }
+
+                if self.show_line_numbers {
+                    let number = (index + 1).to_string();
+                    let digits = number.len() as i32;
+
+                    for (position, ch) in number.chars().enumerate() {
+                        let x = TEXT_PADDING as i32
+                            + (number_digits as i32 - digits + position as i32)
+                                * self.char_width as i32;
+
+                        cells.push((x, y, ch, line_number_color));
+                    }
+                }
+            }
+
+            for (_, _, ch, color) in &cells {
+                if let Entry::Vacant(entry) = self.glyphs.entry((*ch, *color)) {
+                    let glyph = font
+                        .render_char(*ch)
+                        .blended(*color)
+                        .ok()
+                        .and_then(|surface| {
+                            texture_creator.create_texture_from_surface(&surface).ok()
+                        });
+
+                    if let Some(glyph) = glyph {
+                        entry.insert(glyph);
+                    }
+                }
+            }
+
+            let glyphs = &self.glyphs;
+            let char_width = self.char_width;
+            let gutter_width = self.gutter_width;
+            let caret =
+                if self.focused && self.caret.is_shown() && self.cursor_line >= self.scroll_line {
+                    let column = self.cursor_column as i32 - self.scroll_column as i32;
+                    let row = (self.cursor_line - self.scroll_line) as i32;
+
+                    if column >= 0 {
+                        Some(Rect::new(
+                            text_left as i32 + column * char_width as i32,
+                            TEXT_PADDING as i32 + row * self.line_height as i32,
+                            2,
+                            self.line_height,
+                        ))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (x, y, ch, color) in &cells {
+                    if let Some(glyph) = glyphs.get(&(*ch, *color)) {
+                        let TextureQuery { width, height, .. } = glyph.query();
+                        let x = x + (char_width as i32 - width as i32) / 2;
+
+                        texture
+                            .copy(glyph, None, Rect::new(x, *y, width, height))
+                            .unwrap();
+                    }
+                }
+
+                if gutter_width > 0 {
+                    let x = (TEXT_PADDING + gutter_width) as i32 - TEXT_PADDING as i32 / 2;
+
+                    texture.set_draw_color(border_color);
+                    texture
+                        .draw_line(Point::new(x, 0), Point::new(x, bounds[SIZE_HEIGHT] as i32))
+                        .unwrap();
+                }
+
+                if let Some(caret) = caret {
+                    texture.set_draw_color(text_color);
+                    texture.fill_rect(caret).unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while this `Widget` has focus.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Tracks the mouse position, so that clicks can move the cursor.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the text by one line per vertical step of the mouse wheel, and by one column per
+    /// horizontal step.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let scroll_line = (self.scroll_line as i32 - points[POINT_Y]).max(0) as usize;
+        let scroll_column = (self.scroll_column as i32 + points[POINT_X]).max(0) as usize;
+
+        if scroll_line != self.scroll_line || scroll_column != self.scroll_column {
+            self.scroll_line = scroll_line;
+            self.scroll_column = scroll_column;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Moves the cursor to the clicked position.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let cursor = self.get_cursor();
+
+            self.move_cursor_to_mouse();
+
+            if cursor != self.get_cursor() {
+                self.caret.restart(clock::now_millis());
+                self.get_config().set_invalidated(true);
+                self.call_cursor_moved_callback(_widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles text input and cursor navigation while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let (text_changed, cursor_moved) = match event {
+            Event::TextInput { text, .. } if !self.read_only => {
+                self.insert(&text);
+                self.preferred_column = self.cursor_column;
+                (true, true)
+            }
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } => self.handle_edit_key(keycode),
+            _ => (false, false),
+        };
+
+        if cursor_moved {
+            self.reveal_cursor = true;
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        if text_changed {
+            self.call_text_changed_callback(_widgets, _layouts);
+        }
+
+        if cursor_moved {
+            self.call_cursor_moved_callback(_widgets, _layouts);
+        }
+    }
+
+    /// Monitors for changes in the text, colors, or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _)
+            | (CONFIG_COLOR_BASE, _)
+            | (CONFIG_COLOR_BORDER, _)
+            | (CONFIG_COLOR_SECONDARY, _) => self.get_config().set_invalidated(true),
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.clear_glyphs();
+                self.get_config().set_invalidated(true);
+            }
+            (CONFIG_TEXT, Config::Text(text)) => self.set_text(&text),
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus, unless it is read-only.
+    fn accepts_text_input(&mut self) -> bool {
+        !self.read_only
+    }
+
+    /// Uses `Tab` to indent, unless this `Widget` is read-only.
+    fn captures_tab_key(&mut self) -> bool {
+        !self.read_only
+    }
+
+    /// Releases the backing texture of this `Widget`, and the rendered characters.  They are
+    /// recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.clear_glyphs();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,431 @@
+// Pushrod Widget Library
+// Color Picker Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{
+    color_to_hsv, hsv_to_color, inverse_color, Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT,
+    SIZE_WIDTH,
+};
+
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the width of the hue strip, in pixels.
+const HUE_STRIP_WIDTH: u32 = 20;
+
+/// This is the height of the preview swatch, in pixels.
+const SWATCH_HEIGHT: u32 = 24;
+
+/// This is the space between the saturation/value square, the hue strip, and the preview swatch,
+/// in pixels.
+const PICKER_SPACING: u32 = 8;
+
+/// This is the callback type that is used when an `on_color_selected` callback is triggered from
+/// this `Widget`.  It receives the newly selected `Color`.
+pub type OnColorSelectedCallbackType =
+    Option<Box<dyn FnMut(&mut ColorPickerWidget, &[WidgetContainer], &[LayoutContainer], Color)>>;
+
+/// These are the areas of a `ColorPickerWidget` that can be dragged.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum ColorPickerArea {
+    /// The saturation/value square.
+    SaturationValue,
+
+    /// The hue strip.
+    Hue,
+}
+
+/// This is the storage object for the `ColorPickerWidget`.  It stores the config, properties,
+/// callback registry, the selected color in HSV components, the area being dragged, and the cached
+/// texture of the saturation/value square.
+pub struct ColorPickerWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    hue: f64,
+    saturation: f64,
+    value: f64,
+    dragging: Option<ColorPickerArea>,
+    mouse_position: Points,
+    square_texture: Option<Texture>,
+    square_texture_size: (u32, u32),
+    square_invalidated: bool,
+    on_color_selected: OnColorSelectedCallbackType,
+}
+
+/// This is the implementation of the `ColorPickerWidget`, which selects a color using a square that
+/// chooses its saturation (horizontally) and value (vertically), and a strip that chooses its hue.
+/// Both can be clicked or dragged, and the selected color is shown in a preview swatch below them.
+impl ColorPickerWidget {
+    /// Creates a new `ColorPickerWidget` given the `x, y, w, h` coordinates, and the initially
+    /// selected `color`.
+    pub fn new(points: Points, size: Size, color: Color) -> Self {
+        let (hue, saturation, value) = color_to_hsv(color);
+
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            hue,
+            saturation,
+            value,
+            dragging: None,
+            mouse_position: vec![0, 0],
+            square_texture: None,
+            square_texture_size: (0, 0),
+            square_invalidated: true,
+            on_color_selected: None,
+        }
+    }
+
+    /// Selects a `color`.  This does not trigger the `on_color_selected` callback.
+    pub fn set_selected_color(&mut self, color: Color) {
+        let (hue, saturation, value) = color_to_hsv(color);
+
+        // Grays have no hue, so the hue strip keeps its position when one is selected.
+        if saturation > 0.0 && value > 0.0 {
+            self.hue = hue;
+            self.square_invalidated = true;
+        }
+
+        self.saturation = saturation;
+        self.value = value;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the selected color.
+    pub fn get_selected_color(&self) -> Color {
+        hsv_to_color(self.hue, self.saturation, self.value)
+    }
+
+    /// Retrieves the selected color as its hue in degrees, and its saturation and value from `0.0`
+    /// to `1.0`.
+    pub fn get_selected_hsv(&self) -> (f64, f64, f64) {
+        (self.hue, self.saturation, self.value)
+    }
+
+    /// Assigns the callback closure that will be used when a color is selected by clicking on, or
+    /// dragging within, the `Widget`.
+    pub fn on_color_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut ColorPickerWidget, &[WidgetContainer], &[LayoutContainer], Color) + 'static,
+    {
+        self.on_color_selected = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_color_selected` callback.
+    fn call_color_selected_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_color_selected.take() {
+            let color = self.get_selected_color();
+
+            cb(self, widgets, layouts, color);
+            self.on_color_selected = Some(cb);
+        }
+    }
+
+    /// Internal function that returns the bounds of the saturation/value square, the hue strip, and
+    /// the preview swatch, relative to the `Widget`.
+    fn areas(&mut self) -> (Rect, Rect, Rect) {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+        let square_width = size[SIZE_WIDTH]
+            .saturating_sub(HUE_STRIP_WIDTH + PICKER_SPACING)
+            .max(1);
+        let square_height = size[SIZE_HEIGHT]
+            .saturating_sub(SWATCH_HEIGHT + PICKER_SPACING)
+            .max(1);
+
+        (
+            Rect::new(0, 0, square_width, square_height),
+            Rect::new(
+                (square_width + PICKER_SPACING) as i32,
+                0,
+                HUE_STRIP_WIDTH,
+                square_height,
+            ),
+            Rect::new(
+                0,
+                (square_height + PICKER_SPACING) as i32,
+                size[SIZE_WIDTH].max(1),
+                SWATCH_HEIGHT,
+            ),
+        )
+    }
+
+    /// Internal function that returns the area of the `Widget` under the mouse, if any.
+    fn area_under_mouse(&mut self) -> Option<ColorPickerArea> {
+        let (square, strip, _) = self.areas();
+        let point = self.mouse_in_widget();
+
+        if square.contains_point(point) {
+            Some(ColorPickerArea::SaturationValue)
+        } else if strip.contains_point(point) {
+            Some(ColorPickerArea::Hue)
+        } else {
+            None
+        }
+    }
+
+    /// Internal function that returns the position of the mouse relative to the `Widget`.
+    fn mouse_in_widget(&mut self) -> Point {
+        Point::new(
+            self.mouse_position[POINT_X] - self.get_config().to_x(0),
+            self.mouse_position[POINT_Y] - self.get_config().to_y(0),
+        )
+    }
+
+    /// Internal function that selects the color under the mouse in the `area` being dragged, and
+    /// triggers the `on_color_selected` callback if the selected color changed.
+    fn select_at_mouse(
+        &mut self,
+        area: ColorPickerArea,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let (square, strip, _) = self.areas();
+        let point = self.mouse_in_widget();
+        let previous = self.get_selected_hsv();
+
+        match area {
+            ColorPickerArea::SaturationValue => {
+                self.saturation = fraction(point.x() - square.x(), square.width());
+                self.value = 1.0 - fraction(point.y() - square.y(), square.height());
+            }
+            ColorPickerArea::Hue => {
+                self.hue = fraction(point.y() - strip.y(), strip.height()) * 360.0;
+                self.square_invalidated = true;
+            }
+        }
+
+        if self.get_selected_hsv() != previous {
+            self.get_config().set_invalidated(true);
+            self.call_color_selected_callback(widgets, layouts);
+        }
+    }
+
+    /// Internal function that regenerates the texture of the saturation/value square when the hue
+    /// or the size of the square has changed.
+    fn update_square_texture(&mut self, c: &mut Canvas<Window>, width: u32, height: u32) {
+        if !self.square_invalidated
+            && self.square_texture.is_some()
+            && self.square_texture_size == (width, height)
+        {
+            return;
+        }
+
+        if let Some(texture) = self.square_texture.take() {
+            // Safety: the square texture is only referenced by this `Widget`.
+            unsafe {
+                texture.destroy();
+            }
+        }
+
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+
+        for y in 0..height {
+            let value = 1.0 - fraction(y as i32, height);
+
+            for x in 0..width {
+                let color = hsv_to_color(self.hue, fraction(x as i32, width), value);
+
+                pixels.extend_from_slice(&[color.r, color.g, color.b]);
+            }
+        }
+
+        let mut texture = c
+            .texture_creator()
+            .create_texture_streaming(PixelFormatEnum::RGB24, width, height)
+            .unwrap();
+
+        texture.update(None, &pixels, (width * 3) as usize).unwrap();
+
+        self.square_texture = Some(texture);
+        self.square_texture_size = (width, height);
+        self.square_invalidated = false;
+    }
+}
+
+/// Returns the fraction of `length` that `offset` represents, clamped from `0.0` to `1.0`.
+fn fraction(offset: i32, length: u32) -> f64 {
+    if length <= 1 {
+        0.0
+    } else {
+        (offset as f64 / (length - 1) as f64).clamp(0.0, 1.0)
+    }
+}
+
+/// This is the `Widget` implementation of the `ColorPickerWidget`.
+impl Widget for ColorPickerWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let (square, strip, swatch) = self.areas();
+
+            self.update_square_texture(c, square.width(), square.height());
+
+            let base_color = if self.get_config().config.contains_key(&CONFIG_COLOR_BASE) {
+                self.get_color(CONFIG_COLOR_BASE)
+            } else {
+                Color::RGB(255, 255, 255)
+            };
+            let border_color = if self.get_config().config.contains_key(&CONFIG_COLOR_BORDER) {
+                self.get_color(CONFIG_COLOR_BORDER)
+            } else {
+                Color::RGB(0, 0, 0)
+            };
+            let selected_color = self.get_selected_color();
+            let marker_color = if self.value > 0.5 {
+                Color::RGB(0, 0, 0)
+            } else {
+                Color::RGB(255, 255, 255)
+            };
+            let hue_marker_color = inverse_color(hsv_to_color(self.hue, 1.0, 1.0));
+            let marker_x = square.x()
+                + (self.saturation * square.width().saturating_sub(1) as f64).round() as i32;
+            let marker_y = square.y()
+                + ((1.0 - self.value) * square.height().saturating_sub(1) as f64).round() as i32;
+            let hue_y = strip.y()
+                + (self.hue / 360.0 * strip.height().saturating_sub(1) as f64).round() as i32;
+            let square_texture = self.square_texture.as_ref().unwrap();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.copy(square_texture, None, square).unwrap();
+
+                for y in 0..strip.height() {
+                    texture.set_draw_color(hsv_to_color(
+                        fraction(y as i32, strip.height()) * 360.0,
+                        1.0,
+                        1.0,
+                    ));
+                    texture
+                        .draw_line(
+                            Point::new(strip.x(), strip.y() + y as i32),
+                            Point::new(strip.right() - 1, strip.y() + y as i32),
+                        )
+                        .unwrap();
+                }
+
+                texture.set_draw_color(selected_color);
+                texture.fill_rect(swatch).unwrap();
+
+                texture.set_draw_color(marker_color);
+                texture
+                    .draw_rect(Rect::new(marker_x - 3, marker_y - 3, 7, 7))
+                    .unwrap();
+
+                texture.set_draw_color(hue_marker_color);
+                texture
+                    .draw_rect(Rect::new(strip.x() - 2, hue_y - 2, strip.width() + 4, 5))
+                    .unwrap();
+
+                texture.set_draw_color(border_color);
+                texture.draw_rect(square).unwrap();
+                texture.draw_rect(strip).unwrap();
+                texture.draw_rect(swatch).unwrap();
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Updates the selected color while the mouse is dragged within the saturation/value square or
+    /// the hue strip.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        if let Some(area) = self.dragging {
+            self.select_at_mouse(area, _widgets, _layouts);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Selects the color under the mouse when the saturation/value square or the hue strip is
+    /// pressed, and starts dragging within it.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                self.dragging = self.area_under_mouse();
+
+                if let Some(area) = self.dragging {
+                    self.select_at_mouse(area, _widgets, _layouts);
+                }
+            } else {
+                self.dragging = None;
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Captures the mouse, so that the selection can be dragged beyond the bounds of the `Widget`.
+    fn captures_mouse(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing textures of this `Widget`.  The textures are recreated the next time
+    /// this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        if let Some(texture) = self.square_texture.take() {
+            // Safety: the square texture is only referenced by this `Widget`.
+            unsafe {
+                texture.destroy();
+            }
+        }
+
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,321 @@
+// Pushrod Widget Library
+// Compass Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{
+    copy_rotated, draw_circle, fill_circle, fill_polygon, point_at_angle,
+};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// These are the labels drawn on the compass card every 30 degrees, starting at north.
+const CARD_LABELS: [&str; 12] = [
+    "N", "3", "6", "E", "12", "15", "S", "21", "24", "W", "30", "33",
+];
+
+/// This is the storage object for the `CompassWidget`.  It stores the config, properties, callback
+/// registry, the heading, and the compass card, which is only drawn again when its size or colors
+/// change.
+pub struct CompassWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    card_store: TextureStore,
+    card_invalidated: bool,
+    heading: f64,
+}
+
+/// This is the implementation of the `CompassWidget`, a heading indicator drawn as a round compass
+/// card that turns beneath a fixed lubber mark at the top, with the heading shown in degrees in the
+/// middle.  The card has a tick every 5 degrees, and a label every 30 degrees, which is turned to
+/// face outwards like on a real compass card.
+///
+/// The card is drawn once to its own texture, and copied to this `Widget`'s texture rotated by the
+/// heading, so changing the heading does not draw the ticks and labels again.  The card is drawn
+/// in `CONFIG_COLOR_BASE`, with an edge in `CONFIG_COLOR_BORDER`, and ticks and labels in
+/// `CONFIG_COLOR_TEXT`.  The lubber mark is drawn in `CONFIG_COLOR_SECONDARY`.  The card is the
+/// largest circle that fits in the bounds of this `Widget`.
+impl CompassWidget {
+    /// Creates a new `CompassWidget`, given the `x, y, w, h` coordinates, pointing north.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut texture_store = TextureStore::default();
+        let mut card_store = TextureStore::default();
+
+        texture_store.set_blended(true);
+        card_store.set_blended(true);
+
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store,
+            card_store,
+            card_invalidated: true,
+            heading: 0.0,
+        }
+    }
+
+    /// Sets the heading in degrees, clockwise from north.  Headings outside of `0.0` to `360.0` are
+    /// wrapped around.
+    pub fn set_heading(&mut self, heading: f64) {
+        let heading = heading.rem_euclid(360.0);
+
+        if (heading - self.heading).abs() > f64::EPSILON {
+            self.heading = heading;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the heading in degrees, clockwise from north.
+    pub fn get_heading(&self) -> f64 {
+        self.heading
+    }
+
+    /// Internal function that draws the compass card, pointing north, to the card texture.
+    fn draw_card(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache, diameter: u32) {
+        self.card_store
+            .create_or_resize_texture(c, diameter, diameter);
+
+        let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(32, 32, 32));
+        let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(128, 128, 128));
+        let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(255, 255, 255));
+        let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, (diameter as i32 / 14).max(8));
+        let radius = diameter / 2;
+        let center = Point::new(radius as i32, radius as i32);
+        let long_tick = (diameter as f64 * 0.08).max(4.0);
+
+        let ttf_context = t.get_ttf_context();
+        let texture_creator = c.texture_creator();
+        let font = ttf_context
+            .load_font(
+                Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                font_size as u16,
+            )
+            .unwrap();
+        let labels: Vec<(Texture, Rect, f64)> = CARD_LABELS
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                let surface = font
+                    .render(label)
+                    .blended(text_color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+                let label_texture = texture_creator
+                    .create_texture_from_surface(&surface)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+                let TextureQuery { width, height, .. } = label_texture.query();
+                let bearing = index as f64 * 30.0;
+                let at = point_at_angle(
+                    center,
+                    radius as f64 - long_tick - 2.0 - height as f64 / 2.0,
+                    bearing,
+                );
+
+                (
+                    label_texture,
+                    Rect::new(
+                        at.x() - width as i32 / 2,
+                        at.y() - height as i32 / 2,
+                        width,
+                        height,
+                    ),
+                    bearing,
+                )
+            })
+            .collect();
+
+        c.with_texture_canvas(self.card_store.get_mut_ref(), |texture| {
+            texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+            texture.clear();
+
+            texture.set_draw_color(base_color);
+            fill_circle(texture, center, radius - 1);
+
+            texture.set_draw_color(text_color);
+
+            for bearing in (0..360).step_by(5) {
+                let length = if bearing % 30 == 0 {
+                    long_tick
+                } else if bearing % 10 == 0 {
+                    long_tick * 0.6
+                } else {
+                    long_tick * 0.35
+                };
+                let outer = (radius - 2) as f64;
+
+                texture
+                    .draw_line(
+                        point_at_angle(center, outer, bearing as f64),
+                        point_at_angle(center, outer - length, bearing as f64),
+                    )
+                    .unwrap();
+            }
+
+            // Each label is turned to its bearing, so that it reads outwards from the middle.
+            for (label_texture, rect, bearing) in &labels {
+                copy_rotated(texture, label_texture, *rect, *bearing);
+            }
+
+            texture.set_draw_color(border_color);
+            draw_circle(texture, center, radius - 1);
+        })
+        .unwrap();
+
+        // Safety: the label textures are only used while drawing the card.
+        for (label_texture, _, _) in labels {
+            unsafe {
+                label_texture.destroy();
+            }
+        }
+
+        self.card_invalidated = false;
+    }
+}
+
+/// This is the `Widget` implementation of the `CompassWidget`.
+impl Widget for CompassWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+            let diameter = bounds[SIZE_WIDTH].min(bounds[SIZE_HEIGHT]).max(2);
+
+            if self.card_invalidated
+                || self.card_store.get_optional_ref().is_none()
+                || self.card_store.get_width() != diameter
+            {
+                self.draw_card(c, t, diameter);
+            }
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(32, 32, 32));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(128, 128, 128));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(255, 255, 255));
+            let lubber_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(255, 140, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, (diameter as i32 / 14).max(8));
+            let heading = self.heading;
+            let radius = (diameter / 2) as i32;
+            let center = Point::new(
+                bounds[SIZE_WIDTH] as i32 / 2,
+                bounds[SIZE_HEIGHT] as i32 / 2,
+            );
+            let card_rect = Rect::new(center.x() - radius, center.y() - radius, diameter, diameter);
+            let lubber_size = (radius / 6).max(4);
+            let lubber = [
+                Point::new(center.x() - lubber_size / 2, center.y() - radius),
+                Point::new(center.x() + lubber_size / 2, center.y() - radius),
+                Point::new(center.x(), center.y() - radius + lubber_size),
+            ];
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+            let surface = font
+                .render(&format!("{:03}", heading.round() as i32 % 360))
+                .blended(text_color)
+                .map_err(|e| e.to_string())
+                .unwrap();
+            let readout_texture = texture_creator
+                .create_texture_from_surface(&surface)
+                .map_err(|e| e.to_string())
+                .unwrap();
+            let TextureQuery { width, height, .. } = readout_texture.query();
+            let readout_rect = Rect::new(
+                center.x() - width as i32 / 2,
+                center.y() - height as i32 / 2,
+                width,
+                height,
+            );
+            let readout_box = Rect::new(readout_rect.x() - 3, readout_rect.y(), width + 6, height);
+            let card = self.card_store.get_optional_ref().unwrap();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                texture.clear();
+
+                // The card turns the opposite way to the heading, so the heading stays at the top.
+                copy_rotated(texture, card, card_rect, -heading);
+
+                texture.set_draw_color(lubber_color);
+                fill_polygon(texture, &lubber);
+
+                texture.set_draw_color(base_color);
+                texture.fill_rect(readout_box).unwrap();
+                texture.set_draw_color(border_color);
+                texture.draw_rect(readout_box).unwrap();
+                texture.copy(&readout_texture, None, readout_rect).unwrap();
+            })
+            .unwrap();
+
+            // Safety: the readout texture is only used while drawing this `Widget`.
+            unsafe {
+                readout_texture.destroy();
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Draws the compass card again when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_TEXT
+            | CONFIG_COLOR_SECONDARY
+            | CONFIG_FONT_SIZE => {
+                self.card_invalidated = true;
+                self.get_config().set_invalidated(true);
+            }
+            _ => (),
+        };
+    }
+
+    /// Releases the backing textures of this `Widget`, including the compass card.  They are
+    /// recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.card_store.release();
+        self.card_invalidated = true;
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
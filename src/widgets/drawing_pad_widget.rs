@@ -0,0 +1,324 @@
+// Pushrod Widget Library
+// Drawing Pad Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_circle;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is a stroke drawn on a `DrawingPadWidget`: the points the pen passed through, in local
+/// coordinates, and the pen it was drawn with.
+#[derive(Clone, Debug)]
+struct Stroke {
+    points: Vec<Point>,
+    width: u32,
+    color: Color,
+}
+
+/// Draws a line from `from` to `to` that is `width` pixels wide, with rounded ends, in the current
+/// draw color.
+fn draw_pen_line(c: &mut Canvas<Window>, from: Point, to: Point, width: u32) {
+    if width <= 1 {
+        c.draw_line(from, to).unwrap();
+        return;
+    }
+
+    let steps = (to.x() - from.x())
+        .abs()
+        .max((to.y() - from.y()).abs())
+        .max(1);
+
+    for step in 0..=steps {
+        let point = Point::new(
+            from.x() + (to.x() - from.x()) * step / steps,
+            from.y() + (to.y() - from.y()) * step / steps,
+        );
+
+        fill_circle(c, point, width / 2);
+    }
+}
+
+/// This is the storage object for the `DrawingPadWidget`.  It stores the config, properties,
+/// callback registry, the strokes drawn, the pen, the position of the mouse, and the parts of the
+/// current stroke that have not been drawn yet.
+pub struct DrawingPadWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    strokes: Vec<Stroke>,
+    pen_width: u32,
+    pen_color: Color,
+    drawing: bool,
+    mouse_position: Points,
+    undrawn_from: usize,
+    full_redraw: bool,
+}
+
+/// This is the implementation of the `DrawingPadWidget`, a surface that captures freehand strokes
+/// of the mouse, or of a finger on a touchscreen, for signatures and quick annotations.  Each stroke
+/// runs from pressing the mouse button to releasing it, and is drawn with the pen width and color
+/// in effect when it started.  While a stroke is drawn, only its new segments are drawn onto the
+/// texture, so long strokes stay fast.
+///
+/// The pad is filled with `CONFIG_COLOR_BASE`, and outlined in `CONFIG_COLOR_BORDER`.
+impl DrawingPadWidget {
+    /// Creates a new, empty `DrawingPadWidget` given the `x, y, w, h` coordinates.  The pen is
+    /// black, and `2` pixels wide.
+    pub fn new(points: Points, size: Size) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            strokes: Vec::new(),
+            pen_width: 2,
+            pen_color: Color::RGB(0, 0, 0),
+            drawing: false,
+            mouse_position: vec![0, 0],
+            undrawn_from: 0,
+            full_redraw: true,
+        }
+    }
+
+    /// Sets the width of the pen, in pixels, for the strokes drawn from now on.
+    pub fn set_pen_width(&mut self, width: u32) {
+        self.pen_width = width.max(1);
+    }
+
+    /// Retrieves the width of the pen, in pixels.
+    pub fn get_pen_width(&self) -> u32 {
+        self.pen_width
+    }
+
+    /// Sets the color of the pen, for the strokes drawn from now on.
+    pub fn set_pen_color(&mut self, color: Color) {
+        self.pen_color = color;
+    }
+
+    /// Retrieves the color of the pen.
+    pub fn get_pen_color(&self) -> Color {
+        self.pen_color
+    }
+
+    /// Removes all of the strokes from the pad.
+    pub fn clear(&mut self) {
+        self.strokes.clear();
+        self.drawing = false;
+        self.redraw_strokes();
+    }
+
+    /// Removes the most recent stroke from the pad.  Returns `false` if there were no strokes.
+    pub fn undo_stroke(&mut self) -> bool {
+        if self.strokes.pop().is_some() {
+            self.drawing = false;
+            self.redraw_strokes();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Retrieves the number of strokes on the pad.
+    pub fn get_stroke_count(&self) -> usize {
+        self.strokes.len()
+    }
+
+    /// Returns the points of each stroke, in the order in which they were drawn.  The points are
+    /// relative to the top left corner of the pad.
+    pub fn export_strokes(&self) -> Vec<Vec<Point>> {
+        self.strokes
+            .iter()
+            .map(|stroke| stroke.points.clone())
+            .collect()
+    }
+
+    /// Internal function that redraws every stroke the next time the pad is drawn.
+    fn redraw_strokes(&mut self) {
+        self.full_redraw = true;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Internal function that adds the absolute position `points` to the current stroke.
+    fn add_point(&mut self, points: &[i32]) {
+        let point = Point::new(
+            points[POINT_X] - self.get_config().to_x(0),
+            points[POINT_Y] - self.get_config().to_y(0),
+        );
+
+        if let Some(stroke) = self.strokes.last_mut() {
+            if stroke.points.last() != Some(&point) {
+                stroke.points.push(point);
+                self.get_config().set_invalidated(true);
+            }
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `DrawingPadWidget`.
+impl Widget for DrawingPadWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            // The strokes are lost whenever the texture is recreated.
+            if self.texture_store.get_optional_ref().is_none()
+                || self.texture_store.get_width() != bounds[SIZE_WIDTH]
+                || self.texture_store.get_height() != bounds[SIZE_HEIGHT]
+            {
+                self.full_redraw = true;
+            }
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let full_redraw = self.full_redraw;
+            let undrawn_from = self.undrawn_from;
+            let drawing = self.drawing;
+            let strokes = &self.strokes;
+            let last_stroke = strokes.len().saturating_sub(1);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                if full_redraw {
+                    texture.set_draw_color(base_color);
+                    texture.clear();
+                }
+
+                for (index, stroke) in strokes.iter().enumerate() {
+                    // Only the undrawn points of the current stroke are drawn, unless everything is
+                    // being redrawn, starting from the last point that was drawn so the segments
+                    // join up.
+                    let first = if full_redraw {
+                        0
+                    } else if drawing && index == last_stroke {
+                        undrawn_from.saturating_sub(1)
+                    } else {
+                        continue;
+                    };
+
+                    texture.set_draw_color(stroke.color);
+
+                    if stroke.points.len() == 1 && first == 0 {
+                        draw_pen_line(texture, stroke.points[0], stroke.points[0], stroke.width);
+                    }
+
+                    for segment in stroke.points[first.min(stroke.points.len())..].windows(2) {
+                        draw_pen_line(texture, segment[0], segment[1], stroke.width);
+                    }
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            self.full_redraw = false;
+            self.undrawn_from = self
+                .strokes
+                .last()
+                .map(|stroke| stroke.points.len())
+                .unwrap_or(0);
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Adds the mouse position to the current stroke while the mouse button is held.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        if self.drawing {
+            self.add_point(&points);
+        }
+
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Starts a stroke when the mouse button is pressed, and finishes it when it is released.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                self.strokes.push(Stroke {
+                    points: Vec::new(),
+                    width: self.pen_width,
+                    color: self.pen_color,
+                });
+                self.drawing = true;
+                self.undrawn_from = 0;
+
+                let position = self.mouse_position.clone();
+
+                self.add_point(&position);
+            } else {
+                self.drawing = false;
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Captures the mouse while a stroke is drawn, so it continues if the mouse leaves the pad.
+    fn captures_mouse(&mut self) -> bool {
+        true
+    }
+
+    /// Redraws the pad when its colors change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE | CONFIG_COLOR_BORDER => self.redraw_strokes(),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated, and every stroke
+    /// redrawn, the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.full_redraw = true;
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
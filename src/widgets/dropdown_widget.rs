@@ -0,0 +1,540 @@
+// Pushrod Widget Library
+// Dropdown Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::{render_text_line, TextureCache};
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of each item in the popup list.
+const ITEM_HEIGHT: u32 = 24;
+
+/// This is the maximum number of items shown in the popup list at once.  Longer lists scroll.
+const MAX_VISIBLE_ITEMS: usize = 8;
+
+/// This is the width of the area on the right-hand side of the `DropdownWidget` that contains the
+/// drop-down arrow.
+const ARROW_AREA_WIDTH: u32 = 20;
+
+/// This is the callback type that is used when an `on_selection_changed` callback is triggered from
+/// this `Widget`.  It receives the index of the selected item.
+pub type OnSelectionChangedCallbackType =
+    Option<Box<dyn FnMut(&mut DropdownWidget, &[WidgetContainer], &[LayoutContainer], i32)>>;
+
+/// This is the storage object for the `DropdownWidget`.  It stores the config, properties, callback
+/// registry, the list of items, and the state of the popup list.
+pub struct DropdownWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    popup_store: TextureStore,
+    items: Vec<String>,
+    selected_item: i32,
+    highlighted_item: i32,
+    popup_bounds: Option<Rect>,
+    popup_scroll: usize,
+    mouse_y: i32,
+    on_selection_changed: OnSelectionChangedCallbackType,
+}
+
+/// This is the implementation of the `DropdownWidget`, a combo box that displays the selected item,
+/// and opens a popup list of choices when clicked.
+impl DropdownWidget {
+    /// Creates a new `DropdownWidget` given the `x, y, w, h` coordinates.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            popup_store: TextureStore::default(),
+            items: vec![],
+            selected_item: -1,
+            highlighted_item: -1,
+            popup_bounds: None,
+            popup_scroll: 0,
+            mouse_y: 0,
+            on_selection_changed: None,
+        }
+    }
+
+    /// Replaces the items that can be selected.  The selection is cleared.
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.selected_item = -1;
+        self.highlighted_item = -1;
+        self.popup_bounds = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Adds an item to the end of the list of items.  Returns the number of items.
+    pub fn add_item(&mut self, item: String) -> usize {
+        self.items.push(item);
+        self.get_config().set_invalidated(true);
+
+        self.items.len()
+    }
+
+    /// Retrieves the items that can be selected.
+    pub fn get_items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Retrieves the index of the selected item, or `-1` if no item is selected.
+    pub fn get_selected_index(&self) -> i32 {
+        self.selected_item
+    }
+
+    /// Selects an item by index.  An index of `-1`, or an index out of range, clears the selection.
+    /// This does not trigger the `on_selection_changed` callback.
+    pub fn set_selected_index(&mut self, index: i32) {
+        self.selected_item = if index >= 0 && (index as usize) < self.items.len() {
+            index
+        } else {
+            -1
+        };
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the text of the selected item, if an item is selected.
+    pub fn get_selected_item(&self) -> Option<&str> {
+        if self.selected_item < 0 {
+            None
+        } else {
+            self.items
+                .get(self.selected_item as usize)
+                .map(|item| item.as_str())
+        }
+    }
+
+    /// Indicates whether or not the popup list is open.
+    pub fn is_open(&self) -> bool {
+        self.popup_bounds.is_some()
+    }
+
+    /// Assigns the callback closure that will be used when the user selects a different item.
+    pub fn on_selection_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut DropdownWidget, &[WidgetContainer], &[LayoutContainer], i32) + 'static,
+    {
+        self.on_selection_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_selection_changed` callback.
+    fn call_selection_changed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_selection_changed.take() {
+            cb(self, widgets, layouts, self.selected_item);
+            self.on_selection_changed = Some(cb);
+        }
+    }
+
+    /// Opens the popup list below the `Widget`, or above it if there is not enough room below it
+    /// within the top-level `Widget`.
+    fn open_popup(&mut self, widgets: &[WidgetContainer]) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let origin = self.get_config().get_point(CONFIG_ORIGIN);
+        let size = self.get_config().get_size(CONFIG_SIZE);
+        let height = ITEM_HEIGHT * self.items.len().min(MAX_VISIBLE_ITEMS) as u32 + 2;
+        let window_height = widgets
+            .first()
+            .map(|base| base.widget.borrow_mut().get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT])
+            .unwrap_or(u32::MAX);
+        let below = origin[POINT_Y] + size[SIZE_HEIGHT] as i32;
+        let y = if below as i64 + height as i64 > window_height as i64
+            && origin[POINT_Y] >= height as i32
+        {
+            origin[POINT_Y] - height as i32
+        } else {
+            below
+        };
+
+        self.highlighted_item = self.selected_item;
+        self.popup_scroll = 0;
+        self.reveal_item(self.highlighted_item);
+        self.popup_bounds = Some(Rect::new(origin[0], y, size[SIZE_WIDTH], height));
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Scrolls the popup list so that the item at `index` is visible.
+    fn reveal_item(&mut self, index: i32) {
+        if index < 0 {
+            return;
+        }
+
+        let index = index as usize;
+
+        if index < self.popup_scroll {
+            self.popup_scroll = index;
+        } else if index >= self.popup_scroll + MAX_VISIBLE_ITEMS {
+            self.popup_scroll = index + 1 - MAX_VISIBLE_ITEMS;
+        }
+    }
+
+    /// Returns the index of the popup list item at the given window Y coordinate, or `-1` if there
+    /// is none.
+    fn item_at(&self, y: i32) -> i32 {
+        match self.popup_bounds {
+            Some(bounds) if y > bounds.y() && y < bounds.bottom() - 1 => {
+                let index =
+                    self.popup_scroll + ((y - bounds.y() - 1) as u32 / ITEM_HEIGHT) as usize;
+
+                if index < self.items.len() {
+                    index as i32
+                } else {
+                    -1
+                }
+            }
+            _ => -1,
+        }
+    }
+
+    /// Selects an item, closing the popup list, and triggering the `on_selection_changed` callback
+    /// if the selection changed.
+    fn select(&mut self, index: i32, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        let changed = index >= 0 && index != self.selected_item;
+
+        if index >= 0 {
+            self.selected_item = index;
+        }
+
+        self.close_popup();
+        self.get_config().set_invalidated(true);
+
+        if changed {
+            self.call_selection_changed_callback(widgets, layouts);
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `DropdownWidget`.
+impl Widget for DropdownWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let label = self.get_selected_item().unwrap_or("").to_string();
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 16)
+                .unwrap();
+            let font_texture = render_text_line(&font, &texture_creator, &label, text_color);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let Some(font_texture) = &font_texture {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+                    let max_width = bounds[SIZE_WIDTH].saturating_sub(ARROW_AREA_WIDTH + 6);
+
+                    texture
+                        .copy(
+                            font_texture,
+                            Rect::new(0, 0, width.min(max_width).max(1), height),
+                            Rect::new(
+                                6,
+                                (bounds[SIZE_HEIGHT] as i32 - height as i32) / 2,
+                                width.min(max_width).max(1),
+                                height,
+                            ),
+                        )
+                        .unwrap();
+                }
+
+                // The drop-down arrow is a small downward-pointing triangle.
+                let center_x = (bounds[SIZE_WIDTH] - ARROW_AREA_WIDTH / 2) as i32;
+                let center_y = bounds[SIZE_HEIGHT] as i32 / 2;
+
+                texture.set_draw_color(text_color);
+
+                for row in 0..4 {
+                    texture
+                        .draw_line(
+                            Point::new(center_x - 4 + row, center_y - 2 + row),
+                            Point::new(center_x + 4 - row, center_y - 2 + row),
+                        )
+                        .unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            if let Some(font_texture) = font_texture {
+                // Safety: the text texture is only used within this function.
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Draws the popup list of items, highlighting the item under the mouse, and the selected item.
+    fn draw_popup(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        let bounds = self.popup_bounds?;
+
+        self.popup_store
+            .create_or_resize_texture(c, bounds.width(), bounds.height());
+
+        let base_color = self.get_color(CONFIG_COLOR_BASE);
+        let border_color = self.get_color(CONFIG_COLOR_BORDER);
+        let hover_color = self.get_color(CONFIG_COLOR_HOVER);
+        let text_color = self.get_color(CONFIG_COLOR_TEXT);
+        let selected_color = self.get_color(CONFIG_COLOR_SELECTED);
+        let first_item = self.popup_scroll;
+        let last_item = (first_item + MAX_VISIBLE_ITEMS).min(self.items.len());
+        let highlighted_item = self.highlighted_item;
+        let selected_item = self.selected_item;
+
+        let ttf_context = t.get_ttf_context();
+        let texture_creator = c.texture_creator();
+        let font = ttf_context
+            .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 16)
+            .unwrap();
+        let item_textures: Vec<Option<Texture>> = self.items[first_item..last_item]
+            .iter()
+            .map(|item| render_text_line(&font, &texture_creator, item, text_color))
+            .collect();
+
+        c.with_texture_canvas(self.popup_store.get_mut_ref(), |texture| {
+            texture.set_draw_color(base_color);
+            texture.clear();
+
+            for (row, font_texture) in item_textures.iter().enumerate() {
+                let index = (first_item + row) as i32;
+                let y = 1 + (row as u32 * ITEM_HEIGHT) as i32;
+
+                if index == highlighted_item || index == selected_item {
+                    texture.set_draw_color(if index == highlighted_item {
+                        hover_color
+                    } else {
+                        selected_color
+                    });
+                    texture
+                        .fill_rect(Rect::new(1, y, bounds.width() - 2, ITEM_HEIGHT))
+                        .unwrap();
+                }
+
+                if let Some(font_texture) = font_texture {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+
+                    texture
+                        .copy(
+                            font_texture,
+                            None,
+                            Rect::new(
+                                6,
+                                y + (ITEM_HEIGHT as i32 - height as i32) / 2,
+                                width,
+                                height,
+                            ),
+                        )
+                        .unwrap();
+                }
+            }
+
+            texture.set_draw_color(border_color);
+            texture
+                .draw_rect(Rect::new(0, 0, bounds.width(), bounds.height()))
+                .unwrap();
+        })
+        .unwrap();
+
+        // Safety: the item textures are only used within this function.
+        for font_texture in item_textures.into_iter().flatten() {
+            unsafe {
+                font_texture.destroy();
+            }
+        }
+
+        self.popup_store.get_optional_ref()
+    }
+
+    fn get_popup_bounds(&mut self) -> Option<Rect> {
+        self.popup_bounds
+    }
+
+    fn close_popup(&mut self) {
+        self.popup_bounds = None;
+        self.highlighted_item = -1;
+    }
+
+    /// Highlights the popup list item under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_y = points[POINT_Y];
+
+        if self.popup_bounds.is_some() {
+            let highlighted_item = self.item_at(self.mouse_y);
+
+            if highlighted_item >= 0 && highlighted_item != self.highlighted_item {
+                self.highlighted_item = highlighted_item;
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the popup list.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        if self.popup_bounds.is_some() {
+            let max_scroll = self.items.len().saturating_sub(MAX_VISIBLE_ITEMS) as i32;
+            let popup_scroll = (self.popup_scroll as i32 - points[POINT_Y]).clamp(0, max_scroll);
+
+            if popup_scroll as usize != self.popup_scroll {
+                self.popup_scroll = popup_scroll as usize;
+                self.highlighted_item = self.item_at(self.mouse_y);
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Opens the popup list when the `Widget` is clicked, or selects the clicked item when it is
+    /// open.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            if self.popup_bounds.is_some() {
+                let index = self.item_at(self.mouse_y);
+
+                self.select(index, _widgets, _layouts);
+            } else {
+                self.open_popup(_widgets);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Changes the selection with the arrow keys, and opens and closes the popup list with `Space`,
+    /// `Return` and `Escape`, while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            let is_open = self.popup_bounds.is_some();
+            let last_item = self.items.len() as i32 - 1;
+
+            match keycode {
+                Keycode::Up | Keycode::Down if is_open => {
+                    let delta = if keycode == Keycode::Up { -1 } else { 1 };
+
+                    self.highlighted_item = (self.highlighted_item + delta).clamp(0, last_item);
+                    self.reveal_item(self.highlighted_item);
+                    self.get_config().set_invalidated(true);
+                }
+                Keycode::Up | Keycode::Down if last_item >= 0 => {
+                    let delta = if keycode == Keycode::Up { -1 } else { 1 };
+                    let index = (self.selected_item + delta).clamp(0, last_item);
+
+                    self.select(index, _widgets, _layouts);
+                }
+                Keycode::Return | Keycode::KpEnter | Keycode::Space if is_open => {
+                    self.select(self.highlighted_item, _widgets, _layouts);
+                }
+                Keycode::Return | Keycode::KpEnter | Keycode::Space => self.open_popup(_widgets),
+                Keycode::Escape if is_open => {
+                    self.close_popup();
+                    self.get_config().set_invalidated(true);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Closes the popup list when this `Widget` loses focus.
+    fn focus_changed(&mut self, focused: bool) {
+        if !focused && self.popup_bounds.is_some() {
+            self.close_popup();
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Releases the backing textures of this `Widget`.  The textures are recreated the next time
+    /// this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.popup_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,586 @@
+// Pushrod Widget Library
+// File Chooser Dialog
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::engine::Engine;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::modal::close_dialog;
+use crate::render::widget::{BaseWidget, Widget};
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::widget_handle::WidgetHandle;
+use crate::render::{
+    make_points, make_size, Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH,
+};
+use crate::widgets::dropdown_widget::DropdownWidget;
+use crate::widgets::list_widget::ListWidget;
+use crate::widgets::push_button_widget::PushButtonWidget;
+use crate::widgets::text_area_widget::TextAreaWidget;
+use crate::widgets::text_widget::{TextJustify, TextWidget};
+
+use sdl2::pixels::Color;
+use sdl2::ttf::FontStyle;
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// This is the font used by the `Widget`s of the dialog.
+const DIALOG_FONT: &str = "assets/OpenSans-Regular.ttf";
+
+/// This is the space between the edges of the dialog and the `Widget`s inside of it, in pixels.
+const DIALOG_PADDING: i32 = 10;
+
+/// This is the height of a row of controls in the dialog, in pixels.
+const ROW_HEIGHT: u32 = 30;
+
+/// This is the width of the filter dropdown, in pixels.
+const FILTER_WIDTH: u32 = 160;
+
+/// This is the width of the OK and Cancel buttons, in pixels.
+const BUTTON_WIDTH: u32 = 80;
+
+/// This is the callback type that is used when a file is chosen in a `FileChooserDialog`.  It
+/// receives the path to the file that was chosen.
+pub type OnFileChosenCallbackType =
+    Option<Box<dyn FnMut(&[WidgetContainer], &[LayoutContainer], PathBuf)>>;
+
+/// These are the modes that a `FileChooserDialog` can be shown in.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum FileChooserMode {
+    /// Chooses an existing file to open.
+    Open,
+
+    /// Chooses the name of a file to save, which does not need to exist.
+    Save,
+}
+
+/// This is a filter that limits the files shown by a `FileChooserDialog` to those with one of a
+/// set of extensions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileFilter {
+    /// The name of the filter, shown in the dialog.
+    pub name: String,
+
+    /// The extensions of the files shown, without the leading `.`.  If empty, all files are shown.
+    pub extensions: Vec<String>,
+}
+
+/// This is the implementation of the `FileFilter`.
+impl FileFilter {
+    /// Creates a new `FileFilter` called `name`, which shows files with any of the `extensions`.
+    pub fn new(name: &str, extensions: &[&str]) -> Self {
+        Self {
+            name: String::from(name),
+            extensions: extensions
+                .iter()
+                .map(|extension| extension.trim_start_matches('.').to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Indicates whether or not the file at `path` is shown by this filter.  Extensions are
+    /// compared without regard to case.
+    pub fn matches(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+
+        match path.extension() {
+            Some(extension) => {
+                let extension = extension.to_string_lossy().to_lowercase();
+
+                self.extensions.contains(&extension)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the label of this filter shown in the dialog, such as `Images (*.png, *.jpg)`.
+    fn label(&self) -> String {
+        if self.extensions.is_empty() {
+            self.name.clone()
+        } else {
+            let patterns: Vec<String> = self
+                .extensions
+                .iter()
+                .map(|extension| format!("*.{}", extension))
+                .collect();
+
+            format!("{} ({})", self.name, patterns.join(", "))
+        }
+    }
+}
+
+/// This is an entry shown in the list of a `FileChooserDialog`.
+#[derive(Clone, Debug)]
+struct FileChooserEntry {
+    path: PathBuf,
+    label: String,
+    is_directory: bool,
+}
+
+/// This is the state shared by the callbacks of the `Widget`s that make up a `FileChooserDialog`.
+struct FileChooserState {
+    mode: FileChooserMode,
+    directory: PathBuf,
+    filters: Vec<FileFilter>,
+    filter_index: usize,
+    entries: Vec<FileChooserEntry>,
+    dialog_id: i32,
+    path_id: i32,
+    list_id: i32,
+    file_name_id: i32,
+    on_file_chosen: OnFileChosenCallbackType,
+}
+
+/// This is the implementation of the shared `FileChooserState`.
+impl FileChooserState {
+    /// Reads the contents of the current directory: its parent directory first, then its
+    /// subdirectories, then the files shown by the selected filter, each sorted by name.  Hidden
+    /// files are not shown.  If the directory cannot be read, only its parent is listed.
+    fn read_directory(&mut self) {
+        let mut directories = vec![];
+        let mut files = vec![];
+
+        if let Ok(read_dir) = fs::read_dir(&self.directory) {
+            for entry in read_dir.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let path = entry.path();
+
+                if name.starts_with('.') {
+                    continue;
+                }
+
+                if path.is_dir() {
+                    directories.push(FileChooserEntry {
+                        path,
+                        label: format!("{}/", name),
+                        is_directory: true,
+                    });
+                } else if self
+                    .filters
+                    .get(self.filter_index)
+                    .is_none_or(|filter| filter.matches(&path))
+                {
+                    files.push(FileChooserEntry {
+                        path,
+                        label: name,
+                        is_directory: false,
+                    });
+                }
+            }
+        }
+
+        directories.sort_by_key(|entry| entry.label.to_lowercase());
+        files.sort_by_key(|entry| entry.label.to_lowercase());
+
+        self.entries.clear();
+
+        if let Some(parent) = self.directory.parent() {
+            self.entries.push(FileChooserEntry {
+                path: parent.to_path_buf(),
+                label: String::from("../"),
+                is_directory: true,
+            });
+        }
+
+        self.entries.extend(directories);
+        self.entries.extend(files);
+    }
+
+    /// Returns the labels of the entries shown in the list.
+    fn labels(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| entry.label.clone())
+            .collect()
+    }
+
+    /// Changes to `directory`, and updates the path and list `Widget`s to show its contents.  The
+    /// list is updated directly through `list` if it is the `Widget` whose callback is running,
+    /// as it is already borrowed.
+    fn change_directory(
+        &mut self,
+        directory: PathBuf,
+        widgets: &[WidgetContainer],
+        list: Option<&mut ListWidget>,
+    ) {
+        self.directory = directory;
+        self.read_directory();
+        self.refresh(widgets, list);
+    }
+
+    /// Updates the path and list `Widget`s to show the current directory and its entries.
+    fn refresh(&self, widgets: &[WidgetContainer], list: Option<&mut ListWidget>) {
+        if let Ok(mut path_widget) = widgets[self.path_id as usize].widget.try_borrow_mut() {
+            path_widget.set_text(CONFIG_TEXT, self.directory.to_string_lossy().to_string());
+        }
+
+        match list {
+            Some(list) => list.set_items(self.labels()),
+            None => with_widget(widgets, self.list_id, |list: &mut ListWidget| {
+                list.set_items(self.labels())
+            }),
+        }
+    }
+
+    /// Returns the path named by the file name field, relative to the current directory.  In
+    /// `Save` mode, the first extension of the selected filter is added to names without one.
+    /// Returns `None` if the field is empty.
+    fn chosen_path(&self, widgets: &[WidgetContainer]) -> Option<PathBuf> {
+        let mut file_name = String::new();
+
+        with_widget(widgets, self.file_name_id, |field: &mut TextAreaWidget| {
+            file_name = TextAreaWidget::get_text(field)
+                .replace('\n', "")
+                .trim()
+                .to_string();
+        });
+
+        if file_name.is_empty() {
+            return None;
+        }
+
+        let mut path = self.directory.join(file_name);
+
+        if self.mode == FileChooserMode::Save && path.extension().is_none() && !path.is_dir() {
+            if let Some(extension) = self
+                .filters
+                .get(self.filter_index)
+                .and_then(|filter| filter.extensions.first())
+            {
+                path.set_extension(extension);
+            }
+        }
+
+        Some(path)
+    }
+}
+
+/// Calls `f` with the `Widget` whose ID is `widget_id`, if it is a `W`, and is not already
+/// borrowed.
+fn with_widget<W: 'static, F: FnOnce(&mut W)>(widgets: &[WidgetContainer], widget_id: i32, f: F) {
+    if let Some(container) = widgets.get(widget_id as usize) {
+        if let Ok(mut widget) = container.widget.try_borrow_mut() {
+            if let Some(widget) = widget.as_any().downcast_mut::<W>() {
+                f(widget);
+            }
+        }
+    }
+}
+
+/// This is the `FileChooserDialog`, a modal dialog that chooses a file to open or save.  It is
+/// composed of the crate's own `Widget`s: a `ListWidget` of the contents of the current directory,
+/// a `TextAreaWidget` to enter the name of a file, a `DropdownWidget` that selects a filter (if any
+/// filters were added), and `PushButtonWidget`s to accept or cancel the choice.
+///
+/// Clicking on a directory in the list changes to it, and clicking on a file copies its name into
+/// the file name field.  Entering the name of a directory and pressing the OK button also changes
+/// to it.  When the OK button is pressed with the name of a file, the `on_file_chosen` callback is
+/// triggered with its path, and the dialog is closed with a result of `1`.  Pressing the Cancel
+/// button closes the dialog with a result of `0`.  In `Open` mode, the file must exist.
+///
+/// The dialog is built and shown with `show`, which returns the `WidgetHandle` of its root
+/// `Widget`.  This is the handle passed to the `Engine`'s `on_dialog_closed` callback, and can be
+/// passed to `Engine::show_modal` to show the dialog again.
+pub struct FileChooserDialog {
+    mode: FileChooserMode,
+    directory: PathBuf,
+    title: String,
+    file_name: String,
+    filters: Vec<FileFilter>,
+    on_file_chosen: OnFileChosenCallbackType,
+}
+
+/// This is the implementation of the `FileChooserDialog`.
+impl FileChooserDialog {
+    /// Creates a new `FileChooserDialog` in the given `mode`, which initially shows the contents of
+    /// `directory`.  Relative directories are resolved against the current working directory.
+    pub fn new<P: AsRef<Path>>(mode: FileChooserMode, directory: P) -> Self {
+        let directory = directory.as_ref();
+
+        Self {
+            mode,
+            directory: fs::canonicalize(directory).unwrap_or_else(|_| directory.to_path_buf()),
+            title: String::from(match mode {
+                FileChooserMode::Open => "Open File",
+                FileChooserMode::Save => "Save File",
+            }),
+            file_name: String::new(),
+            filters: vec![],
+            on_file_chosen: None,
+        }
+    }
+
+    /// Sets the title shown at the top of the dialog.
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+    }
+
+    /// Sets the file name initially entered in the file name field.
+    pub fn set_file_name(&mut self, file_name: &str) {
+        self.file_name = String::from(file_name);
+    }
+
+    /// Adds a `FileFilter` to the dialog.  The first filter added is selected when the dialog is
+    /// shown.  If no filters are added, all files are shown.
+    pub fn add_filter(&mut self, filter: FileFilter) {
+        self.filters.push(filter);
+    }
+
+    /// Assigns the callback closure that will be used when a file is chosen.
+    pub fn on_file_chosen<F>(&mut self, callback: F)
+    where
+        F: FnMut(&[WidgetContainer], &[LayoutContainer], PathBuf) + 'static,
+    {
+        self.on_file_chosen = Some(Box::new(callback));
+    }
+
+    /// Builds the `Widget`s of the dialog within the bounds given by `points` and `size`, adds
+    /// them to the `engine`, and shows them as a modal dialog.  Returns the `WidgetHandle` of the
+    /// root `Widget` of the dialog.  The dialog should be at least 300 by 200 pixels in size.
+    pub fn show(self, engine: &mut Engine, points: Points, size: Size) -> WidgetHandle {
+        let x = points[POINT_X];
+        let y = points[POINT_Y];
+        let width = size[SIZE_WIDTH];
+        let height = size[SIZE_HEIGHT];
+        let inner_width = width.saturating_sub(DIALOG_PADDING as u32 * 2);
+        let button_y = y + height as i32 - DIALOG_PADDING - ROW_HEIGHT as i32;
+        let file_name_y = button_y - DIALOG_PADDING - ROW_HEIGHT as i32;
+        let list_y = y + DIALOG_PADDING + 56;
+        let list_height = (file_name_y - DIALOG_PADDING - list_y).max(ROW_HEIGHT as i32) as u32;
+        let file_name_width = if self.filters.is_empty() {
+            inner_width
+        } else {
+            inner_width.saturating_sub(FILTER_WIDTH + DIALOG_PADDING as u32)
+        };
+        let panel_color = Color::RGB(240, 240, 240);
+        let border_color = Color::RGB(0, 0, 0);
+
+        let state = Rc::new(RefCell::new(FileChooserState {
+            mode: self.mode,
+            directory: self.directory,
+            filters: self.filters,
+            filter_index: 0,
+            entries: vec![],
+            dialog_id: 0,
+            path_id: 0,
+            list_id: 0,
+            file_name_id: 0,
+            on_file_chosen: self.on_file_chosen,
+        }));
+
+        state.borrow_mut().read_directory();
+
+        let mut dialog = BaseWidget::new(points.clone(), size.clone());
+
+        dialog.set_color(CONFIG_COLOR_BASE, panel_color);
+        dialog.set_color(CONFIG_COLOR_BORDER, border_color);
+        dialog.set_numeric(CONFIG_BORDER_WIDTH, 2);
+
+        let dialog_handle = engine.add_widget(Box::new(dialog), String::from("file_chooser"));
+
+        let mut title = TextWidget::new(
+            String::from(DIALOG_FONT),
+            FontStyle::BOLD,
+            16,
+            TextJustify::Left,
+            self.title,
+            make_points(x + DIALOG_PADDING, y + DIALOG_PADDING),
+            make_size(inner_width, 24),
+        );
+
+        title.set_color(CONFIG_COLOR_BASE, panel_color);
+        title.set_color(CONFIG_COLOR_TEXT, border_color);
+
+        let mut path = TextWidget::new(
+            String::from(DIALOG_FONT),
+            FontStyle::NORMAL,
+            12,
+            TextJustify::Left,
+            state.borrow().directory.to_string_lossy().to_string(),
+            make_points(x + DIALOG_PADDING, y + DIALOG_PADDING + 28),
+            make_size(inner_width, 20),
+        );
+
+        path.set_color(CONFIG_COLOR_BASE, panel_color);
+        path.set_color(CONFIG_COLOR_TEXT, border_color);
+
+        let mut list = ListWidget::new(
+            make_points(x + DIALOG_PADDING, list_y),
+            make_size(inner_width, list_height),
+        );
+        let list_state = state.clone();
+
+        list.set_items(state.borrow().labels());
+        list.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+        list.set_color(CONFIG_COLOR_BORDER, border_color);
+        list.set_color(CONFIG_COLOR_HOVER, Color::RGB(0xE0, 0xE0, 0xFF));
+        list.set_color(CONFIG_COLOR_SELECTED, Color::RGB(0xC0, 0xC0, 0xFF));
+        list.set_numeric(CONFIG_BORDER_WIDTH, 1);
+        list.on_item_selected(move |list, widgets, _layouts, index| {
+            let mut state = list_state.borrow_mut();
+
+            if let Some(entry) = state.entries.get(index).cloned() {
+                if entry.is_directory {
+                    state.change_directory(entry.path, widgets, Some(list));
+                } else {
+                    with_widget(widgets, state.file_name_id, |field: &mut TextAreaWidget| {
+                        field.set_text(&entry.label)
+                    });
+                }
+            }
+        });
+
+        let mut file_name = TextAreaWidget::new(
+            String::from(DIALOG_FONT),
+            14,
+            make_points(x + DIALOG_PADDING, file_name_y),
+            make_size(file_name_width, ROW_HEIGHT),
+        );
+
+        file_name.set_text(&self.file_name);
+        file_name.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+        file_name.set_color(CONFIG_COLOR_BORDER, border_color);
+
+        let filter = if state.borrow().filters.is_empty() {
+            None
+        } else {
+            let mut filter = DropdownWidget::new(
+                make_points(
+                    x + DIALOG_PADDING + (file_name_width + DIALOG_PADDING as u32) as i32,
+                    file_name_y,
+                ),
+                make_size(FILTER_WIDTH, ROW_HEIGHT),
+            );
+            let filter_state = state.clone();
+
+            filter.set_items(
+                state
+                    .borrow()
+                    .filters
+                    .iter()
+                    .map(|filter| filter.label())
+                    .collect(),
+            );
+            filter.set_selected_index(0);
+            filter.on_selection_changed(move |_filter, widgets, _layouts, index| {
+                let mut state = filter_state.borrow_mut();
+
+                if index >= 0 {
+                    state.filter_index = index as usize;
+                    state.read_directory();
+                    state.refresh(widgets, None);
+                }
+            });
+
+            Some(filter)
+        };
+
+        let mut ok_button = PushButtonWidget::new(
+            make_points(
+                x + width as i32 - DIALOG_PADDING * 2 - BUTTON_WIDTH as i32 * 2,
+                button_y,
+            ),
+            make_size(BUTTON_WIDTH, ROW_HEIGHT),
+            String::from(match self.mode {
+                FileChooserMode::Open => "Open",
+                FileChooserMode::Save => "Save",
+            }),
+            16,
+        );
+        let ok_state = state.clone();
+
+        ok_button.set_color(CONFIG_COLOR_BORDER, border_color);
+        ok_button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+        ok_button.on_click(move |_button, widgets, layouts| {
+            let mut state = ok_state.borrow_mut();
+            let path = match state.chosen_path(widgets) {
+                Some(path) => path,
+                None => return,
+            };
+
+            if path.is_dir() {
+                with_widget(widgets, state.file_name_id, |field: &mut TextAreaWidget| {
+                    field.set_text("")
+                });
+                state.change_directory(path, widgets, None);
+                return;
+            }
+
+            if state.mode == FileChooserMode::Open && !path.is_file() {
+                return;
+            }
+
+            if let Some(mut cb) = state.on_file_chosen.take() {
+                cb(widgets, layouts, path);
+                state.on_file_chosen = Some(cb);
+            }
+
+            close_dialog(widgets, state.dialog_id, 1);
+        });
+
+        let mut cancel_button = PushButtonWidget::new(
+            make_points(
+                x + width as i32 - DIALOG_PADDING - BUTTON_WIDTH as i32,
+                button_y,
+            ),
+            make_size(BUTTON_WIDTH, ROW_HEIGHT),
+            String::from("Cancel"),
+            16,
+        );
+        let cancel_state = state.clone();
+
+        cancel_button.set_color(CONFIG_COLOR_BORDER, border_color);
+        cancel_button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+        cancel_button.on_click(move |_button, widgets, _layouts| {
+            close_dialog(widgets, cancel_state.borrow().dialog_id, 0);
+        });
+
+        let title_handle = engine.add_widget(Box::new(title), String::from("file_chooser_title"));
+        let path_handle = engine.add_widget(Box::new(path), String::from("file_chooser_path"));
+        let list_handle = engine.add_widget(Box::new(list), String::from("file_chooser_list"));
+        let file_name_handle =
+            engine.add_widget(Box::new(file_name), String::from("file_chooser_file_name"));
+        let mut handles = vec![title_handle, path_handle, list_handle, file_name_handle];
+
+        if let Some(filter) = filter {
+            handles.push(engine.add_widget(Box::new(filter), String::from("file_chooser_filter")));
+        }
+
+        handles.push(engine.add_widget(Box::new(ok_button), String::from("file_chooser_ok")));
+        handles
+            .push(engine.add_widget(Box::new(cancel_button), String::from("file_chooser_cancel")));
+
+        {
+            let mut state = state.borrow_mut();
+
+            state.dialog_id = dialog_handle.id();
+            state.path_id = path_handle.id();
+            state.list_id = list_handle.id();
+            state.file_name_id = file_name_handle.id();
+        }
+
+        for handle in handles {
+            engine.set_parent(handle, dialog_handle).unwrap();
+        }
+
+        engine.show_modal(dialog_handle).unwrap();
+        engine.set_focus(file_name_handle.id());
+
+        dialog_handle
+    }
+}
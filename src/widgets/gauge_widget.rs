@@ -0,0 +1,341 @@
+// Pushrod Widget Library
+// Gauge Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{fill_arc, fill_circle, point_at_angle};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::Font;
+use sdl2::video::Window;
+
+use crate::render::clock;
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the angle of the minimum value, in degrees clockwise from the top of the gauge.
+const GAUGE_START_ANGLE: f64 = -90.0;
+
+/// This is the angle of the maximum value, in degrees clockwise from the top of the gauge.
+const GAUGE_END_ANGLE: f64 = 90.0;
+
+/// This is the thickness of the arc that shows the scale and the zones, in pixels.
+const ARC_THICKNESS: u32 = 12;
+
+/// This is the height of the area below the gauge that shows the minimum and maximum labels, in
+/// pixels.
+const LABEL_HEIGHT: u32 = 20;
+
+/// This is the radius of the hub at the base of the needle, in pixels.
+const HUB_RADIUS: u32 = 6;
+
+/// This is the number of milliseconds it takes the needle to move to a new value.
+const NEEDLE_DURATION: u64 = 400;
+
+/// This is a colored range of values on the scale of a `GaugeWidget`, such as the green, yellow
+/// and red zones of a dashboard dial.
+#[derive(Clone, Debug)]
+pub struct GaugeZone {
+    /// This is the lowest value covered by the zone.
+    pub low: f64,
+
+    /// This is the highest value covered by the zone.
+    pub high: f64,
+
+    /// This is the color in which the zone is drawn.
+    pub color: Color,
+}
+
+/// This is the storage object for the `GaugeWidget`.  It stores the config, properties, callback
+/// registry, the range and value of the gauge, its zones, and the position of the needle.
+pub struct GaugeWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    min: f64,
+    max: f64,
+    value: f64,
+    zones: Vec<GaugeZone>,
+    needle_value: f64,
+    needle_start: Option<(u64, f64)>,
+}
+
+/// This is the implementation of the `GaugeWidget`, a dashboard-style dial that shows a value on a
+/// semicircular scale with a needle.  The scale is drawn in `CONFIG_COLOR_TRACK`, with any zones
+/// drawn over it in their own colors.  The needle is drawn in `CONFIG_COLOR_SECONDARY`, and the
+/// minimum and maximum labels below the scale in `CONFIG_COLOR_TEXT`.  When the value changes, the
+/// needle moves smoothly to the new value over the next few ticks.
+impl GaugeWidget {
+    /// Creates a new `GaugeWidget` given the `x, y, w, h` coordinates, the `min` and `max` values
+    /// of its scale, and its initial `value`.  The gauge is drawn best when it is about twice as
+    /// wide as it is high.
+    pub fn new(points: Points, size: Size, min: f64, max: f64, value: f64) -> Self {
+        let value = value.max(min).min(max);
+
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            min,
+            max,
+            value,
+            zones: Vec::new(),
+            needle_value: value,
+            needle_start: None,
+        }
+    }
+
+    /// Sets the `min` and `max` values of the scale.  The current value is clamped to the new
+    /// range, and the needle jumps to it.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.min = min;
+        self.max = max;
+        self.value = self.value.max(min).min(max);
+        self.needle_value = self.value;
+        self.needle_start = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the minimum value of the scale.
+    pub fn get_min(&self) -> f64 {
+        self.min
+    }
+
+    /// Retrieves the maximum value of the scale.
+    pub fn get_max(&self) -> f64 {
+        self.max
+    }
+
+    /// Sets the value shown by the gauge, clamped to its range.  The needle moves smoothly from its
+    /// current position to the new value.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value.max(self.min).min(self.max);
+        self.needle_start = Some((clock::now_millis(), self.needle_value));
+    }
+
+    /// Retrieves the value shown by the gauge.  This is the value the needle is moving towards,
+    /// even if it has not reached it yet.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Adds a zone to the scale, covering the values from `low` to `high`, drawn in `color`.  Zones
+    /// are drawn in the order in which they were added, so later zones are drawn over earlier ones.
+    pub fn add_zone(&mut self, low: f64, high: f64, color: Color) {
+        self.zones.push(GaugeZone { low, high, color });
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the zones on the scale.
+    pub fn get_zones(&self) -> &[GaugeZone] {
+        &self.zones
+    }
+
+    /// Removes all of the zones from the scale.
+    pub fn clear_zones(&mut self) {
+        self.zones.clear();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Internal function that returns the angle of `value` on the scale.
+    fn angle_of(&self, value: f64) -> f64 {
+        let range = self.max - self.min;
+
+        if range <= 0.0 {
+            return GAUGE_START_ANGLE;
+        }
+
+        let amount = ((value - self.min) / range).clamp(0.0, 1.0);
+
+        GAUGE_START_ANGLE + (GAUGE_END_ANGLE - GAUGE_START_ANGLE) * amount
+    }
+}
+
+/// Draws `text` in `color`, horizontally centered on `x`, and vertically centered within the
+/// `LABEL_HEIGHT` pixels starting at `y`.
+fn draw_label(c: &mut Canvas<Window>, font: &Font, text: &str, color: Color, x: i32, y: i32) {
+    let texture_creator = c.texture_creator();
+    let surface = font
+        .render(text)
+        .blended(color)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    let font_texture = texture_creator
+        .create_texture_from_surface(&surface)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    let TextureQuery { width, height, .. } = font_texture.query();
+
+    c.copy(
+        &font_texture,
+        None,
+        Rect::new(
+            x - width as i32 / 2,
+            y + (LABEL_HEIGHT as i32 - height as i32) / 2,
+            width,
+            height,
+        ),
+    )
+    .unwrap();
+
+    // Safety: the font texture is only used within this function.
+    unsafe {
+        font_texture.destroy();
+    }
+}
+
+/// This is the `Widget` implementation of the `GaugeWidget`.
+impl Widget for GaugeWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let track_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(220, 220, 220));
+            let needle_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(64, 64, 64));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let arc_height = bounds[SIZE_HEIGHT].saturating_sub(LABEL_HEIGHT);
+            let radius = (bounds[SIZE_WIDTH] / 2).min(arc_height).saturating_sub(2);
+            let inner_radius = radius.saturating_sub(ARC_THICKNESS);
+            let center = Point::new((bounds[SIZE_WIDTH] / 2) as i32, arc_height as i32);
+            let zones: Vec<(f64, f64, Color)> = self
+                .zones
+                .iter()
+                .map(|zone| {
+                    (
+                        self.angle_of(zone.low),
+                        self.angle_of(zone.high),
+                        zone.color,
+                    )
+                })
+                .collect();
+            let needle_angle = self.angle_of(self.needle_value);
+            let needle_length = inner_radius.saturating_sub(4) as f64;
+            let label_offset = (radius - ARC_THICKNESS / 2) as i32;
+            let min_label = format!("{}", self.min);
+            let max_label = format!("{}", self.max);
+
+            let ttf_context = t.get_ttf_context();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(track_color);
+                fill_arc(
+                    texture,
+                    center,
+                    radius,
+                    inner_radius,
+                    GAUGE_START_ANGLE,
+                    GAUGE_END_ANGLE,
+                );
+
+                for (start_angle, end_angle, color) in &zones {
+                    texture.set_draw_color(*color);
+                    fill_arc(
+                        texture,
+                        center,
+                        radius,
+                        inner_radius,
+                        *start_angle,
+                        *end_angle,
+                    );
+                }
+
+                texture.set_draw_color(needle_color);
+
+                for offset in &[-1, 0, 1] {
+                    let start = Point::new(center.x() + offset, center.y());
+
+                    texture
+                        .draw_line(start, point_at_angle(start, needle_length, needle_angle))
+                        .unwrap();
+                }
+
+                fill_circle(texture, center, HUB_RADIUS);
+
+                draw_label(
+                    texture,
+                    &font,
+                    &min_label,
+                    text_color,
+                    center.x() - label_offset,
+                    center.y(),
+                );
+                draw_label(
+                    texture,
+                    &font,
+                    &max_label,
+                    text_color,
+                    center.x() + label_offset,
+                    center.y(),
+                );
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Moves the needle towards the current value, slowing down as it gets closer.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if let Some((started_at, start_value)) = self.needle_start {
+            let progress =
+                clock::now_millis().saturating_sub(started_at) as f64 / NEEDLE_DURATION as f64;
+
+            if progress >= 1.0 {
+                self.needle_value = self.value;
+                self.needle_start = None;
+            } else {
+                let eased = 1.0 - (1.0 - progress) * (1.0 - progress);
+
+                self.needle_value = start_value + (self.value - start_value) * eased;
+            }
+
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -22,12 +22,12 @@ use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
 use sdl2::render::{Canvas, Texture};
 use sdl2::video::Window;
 
-use crate::render::canvas_helper::CanvasHelper;
+use crate::render::canvas_helper::{draw_grid, CanvasHelper};
 use crate::render::layout_cache::LayoutContainer;
 use crate::render::texture_cache::TextureCache;
 use crate::render::texture_store::TextureStore;
 use sdl2::pixels::Color;
-use sdl2::rect::{Point, Rect};
+use sdl2::rect::Rect;
 use std::any::Any;
 use std::collections::HashMap;
 
@@ -84,7 +84,7 @@ impl Widget for GridWidget {
             let border_color = self.get_config().get_color(CONFIG_COLOR_BORDER);
             let size = self.get_config().get_size(CONFIG_SIZE);
             let grid_connections = self.grid_connections;
-            let grid_size = self.grid_size as usize;
+            let grid_size = self.grid_size as f64;
 
             c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
                 texture.set_draw_color(base_color);
@@ -92,34 +92,18 @@ impl Widget for GridWidget {
 
                 if grid_connections {
                     texture.set_draw_color(Color::RGB(192, 192, 192));
-
-                    for i in (0..size[SIZE_WIDTH]).step_by(grid_size) {
-                        texture
-                            .draw_line(
-                                Point::new(i as i32, 0),
-                                Point::new(i as i32, size[SIZE_HEIGHT] as i32),
-                            )
-                            .unwrap();
-                    }
-
-                    for i in (0..size[SIZE_HEIGHT]).step_by(grid_size) {
-                        texture
-                            .draw_line(
-                                Point::new(0, i as i32),
-                                Point::new(size[SIZE_WIDTH] as i32, i as i32),
-                            )
-                            .unwrap();
-                    }
                 } else {
                     texture.set_draw_color(Color::RGB(0, 0, 0));
-
-                    for x in (0..size[SIZE_WIDTH]).step_by(grid_size) {
-                        for y in (0..size[SIZE_HEIGHT]).step_by(grid_size) {
-                            texture.draw_point(Point::new(x as i32, y as i32)).unwrap();
-                        }
-                    }
                 }
 
+                draw_grid(
+                    texture,
+                    Rect::new(0, 0, size[SIZE_WIDTH], size[SIZE_HEIGHT]),
+                    grid_size,
+                    grid_size,
+                    grid_connections,
+                );
+
                 texture.set_draw_color(border_color);
                 texture
                     .draw_rect(Rect::new(0, 0, size[0], size[1]))
@@ -131,6 +115,13 @@ impl Widget for GridWidget {
         self.texture_store.get_optional_ref()
     }
 
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
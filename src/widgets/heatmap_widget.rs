@@ -0,0 +1,435 @@
+// Pushrod Widget Library
+// Heatmap Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Blends from color `from` to color `to` by `amount`, which ranges from `0.0` to `1.0`.
+fn blend_color(from: Color, to: Color, amount: f64) -> Color {
+    let blend = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * amount).round() as u8;
+
+    Color::RGBA(
+        blend(from.r, to.r),
+        blend(from.g, to.g),
+        blend(from.b, to.b),
+        blend(from.a, to.a),
+    )
+}
+
+/// This is a color gradient, which maps a position from `0.0` to `1.0` to a color, by blending
+/// between colors placed at positions along it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorGradient {
+    stops: Vec<(f64, Color)>,
+}
+
+/// This is the implementation of the `ColorGradient`.
+impl ColorGradient {
+    /// Creates a new `ColorGradient` from `stops`, each a position from `0.0` to `1.0` and the
+    /// color at that position.  Positions before the first stop take its color, and positions after
+    /// the last stop take its color.  A gradient without stops is black.
+    pub fn new(mut stops: Vec<(f64, Color)>) -> Self {
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        Self { stops }
+    }
+
+    /// Retrieves the stops of the gradient, ordered by position.
+    pub fn get_stops(&self) -> &[(f64, Color)] {
+        &self.stops
+    }
+
+    /// Returns the color of the gradient at `position`, from `0.0` to `1.0`.
+    pub fn color_at(&self, position: f64) -> Color {
+        let first = match self.stops.first() {
+            Some(first) => first,
+            None => return Color::RGB(0, 0, 0),
+        };
+
+        if position <= first.0 {
+            return first.1;
+        }
+
+        for pair in self.stops.windows(2) {
+            let ((from_position, from), (to_position, to)) = (pair[0], pair[1]);
+
+            if position <= to_position {
+                let span = to_position - from_position;
+
+                return if span > 0.0 {
+                    blend_color(from, to, (position - from_position) / span)
+                } else {
+                    to
+                };
+            }
+        }
+
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+/// This is the default `ColorGradient`, which runs from blue for low values, through cyan, green,
+/// and yellow, to red for high values.
+impl Default for ColorGradient {
+    fn default() -> Self {
+        Self::new(vec![
+            (0.0, Color::RGB(0, 0, 255)),
+            (0.25, Color::RGB(0, 255, 255)),
+            (0.5, Color::RGB(0, 255, 0)),
+            (0.75, Color::RGB(255, 255, 0)),
+            (1.0, Color::RGB(255, 0, 0)),
+        ])
+    }
+}
+
+/// This is the callback type that is used when an `on_cell_clicked` callback is triggered from this
+/// `Widget`.  It receives the row and column of the cell.
+pub type OnCellClickedCallbackType = Option<
+    Box<dyn FnMut(&mut HeatmapWidget, &[WidgetContainer], &[LayoutContainer], usize, usize)>,
+>;
+
+/// This is the storage object for the `HeatmapWidget`.  It stores the config, properties, callback
+/// registry, the matrix of values, the gradient and range they are mapped through, and the cell
+/// under the mouse.
+pub struct HeatmapWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    values: Vec<Vec<f64>>,
+    gradient: ColorGradient,
+    range: Option<(f64, f64)>,
+    grid_lines: bool,
+    hovered: Option<(usize, usize)>,
+    on_cell_clicked: OnCellClickedCallbackType,
+}
+
+/// This is the implementation of the `HeatmapWidget`, which draws a matrix of values as a grid of
+/// colored cells.  Each value is mapped through a `ColorGradient`, from the lowest value of the
+/// range at the start of the gradient, to the highest at the end.  By default, the range is that of
+/// the values themselves.  The matrix is given as rows, from top to bottom, and rows may have
+/// different lengths; missing and `NaN` values are drawn in `CONFIG_COLOR_BASE`.
+///
+/// The cell under the mouse is outlined in `CONFIG_COLOR_HOVER`, and clicking a cell triggers the
+/// `on_cell_clicked` callback.  Grid lines, when shown, are drawn in `CONFIG_COLOR_BORDER`.
+impl HeatmapWidget {
+    /// Creates a new `HeatmapWidget` given the `x, y, w, h` coordinates, and the matrix of
+    /// `values`, as rows from top to bottom.  The cells are stretched to fill the `Widget`.
+    pub fn new(points: Points, size: Size, values: Vec<Vec<f64>>) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            values,
+            gradient: ColorGradient::default(),
+            range: None,
+            grid_lines: false,
+            hovered: None,
+            on_cell_clicked: None,
+        }
+    }
+
+    /// Replaces the matrix of values, as rows from top to bottom.
+    pub fn set_values(&mut self, values: Vec<Vec<f64>>) {
+        self.values = values;
+        self.hovered = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the matrix of values.
+    pub fn get_values(&self) -> &[Vec<f64>] {
+        &self.values
+    }
+
+    /// Changes the value of the cell at `row` and `col`, if it exists.
+    pub fn set_value(&mut self, row: usize, col: usize, value: f64) {
+        if let Some(cell) = self.values.get_mut(row).and_then(|row| row.get_mut(col)) {
+            *cell = value;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the value of the cell at `row` and `col`, if it exists.
+    pub fn get_value(&self, row: usize, col: usize) -> Option<f64> {
+        self.values.get(row).and_then(|row| row.get(col)).cloned()
+    }
+
+    /// Sets the gradient the values are mapped through.
+    pub fn set_gradient(&mut self, gradient: ColorGradient) {
+        self.gradient = gradient;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the gradient the values are mapped through.
+    pub fn get_gradient(&self) -> &ColorGradient {
+        &self.gradient
+    }
+
+    /// Sets the range of values mapped onto the gradient, from `min` to `max`.  Values outside the
+    /// range take the color at the nearest end of the gradient.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.range = Some((min.min(max), min.max(max)));
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Maps the values onto the gradient from the lowest to the highest value.  This is the
+    /// default.
+    pub fn clear_range(&mut self) {
+        self.range = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the range of values mapped onto the gradient, as `(min, max)`.
+    pub fn get_range(&self) -> (f64, f64) {
+        self.range.unwrap_or_else(|| {
+            self.values
+                .iter()
+                .flatten()
+                .filter(|value| !value.is_nan())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                    (min.min(*value), max.max(*value))
+                })
+        })
+    }
+
+    /// Sets whether lines are drawn between the cells.  This is off by default.
+    pub fn set_grid_lines(&mut self, grid_lines: bool) {
+        self.grid_lines = grid_lines;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether lines are drawn between the cells.
+    pub fn get_grid_lines(&self) -> bool {
+        self.grid_lines
+    }
+
+    /// Assigns the callback closure that will be used when a cell is clicked.
+    pub fn on_cell_clicked<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut HeatmapWidget, &[WidgetContainer], &[LayoutContainer], usize, usize)
+            + 'static,
+    {
+        self.on_cell_clicked = Some(Box::new(callback));
+    }
+
+    /// Internal function that returns the number of rows and columns of the grid.
+    fn dimensions(&self) -> (usize, usize) {
+        (
+            self.values.len(),
+            self.values.iter().map(|row| row.len()).max().unwrap_or(0),
+        )
+    }
+
+    /// Internal function that returns the bounds of the cell at `row` and `col`, in local
+    /// coordinates.  Cell edges are rounded so that the cells fill the `Widget` without gaps.
+    fn cell_rect(&mut self, row: usize, col: usize) -> Rect {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let (rows, cols) = self.dimensions();
+        let edge = |index: usize, count: usize, length: u32| {
+            (index as f64 * length as f64 / count.max(1) as f64).round() as i32
+        };
+        let left = edge(col, cols, bounds[SIZE_WIDTH]);
+        let top = edge(row, rows, bounds[SIZE_HEIGHT]);
+
+        Rect::new(
+            left,
+            top,
+            (edge(col + 1, cols, bounds[SIZE_WIDTH]) - left).max(1) as u32,
+            (edge(row + 1, rows, bounds[SIZE_HEIGHT]) - top).max(1) as u32,
+        )
+    }
+
+    /// Internal function that returns the row and column of the cell at the absolute position
+    /// `points`.
+    fn cell_at(&mut self, points: &[i32]) -> Option<(usize, usize)> {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let (rows, cols) = self.dimensions();
+        let x = points[POINT_X] - self.get_config().to_x(0);
+        let y = points[POINT_Y] - self.get_config().to_y(0);
+
+        if rows == 0 || cols == 0 || x < 0 || y < 0 {
+            return None;
+        }
+
+        let row = (y as usize * rows) / bounds[SIZE_HEIGHT].max(1) as usize;
+        let col = (x as usize * cols) / bounds[SIZE_WIDTH].max(1) as usize;
+
+        Some((row, col)).filter(|(row, col)| *row < rows && *col < cols)
+    }
+}
+
+/// This is the `Widget` implementation of the `HeatmapWidget`.
+impl Widget for HeatmapWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(64, 64, 64));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(0, 0, 0));
+            let (min, max) = self.get_range();
+            let span = max - min;
+            let (rows, cols) = self.dimensions();
+            let mut cells: Vec<(Rect, Color)> = Vec::with_capacity(rows * cols);
+
+            for row in 0..rows {
+                for col in 0..self.values[row].len() {
+                    let value = self.values[row][col];
+
+                    if value.is_nan() {
+                        continue;
+                    }
+
+                    let position = if span > 0.0 {
+                        (value - min) / span
+                    } else {
+                        0.5
+                    };
+
+                    cells.push((self.cell_rect(row, col), self.gradient.color_at(position)));
+                }
+            }
+
+            let mut grid_lines: Vec<(Point, Point)> = vec![];
+
+            if self.grid_lines {
+                let width = bounds[SIZE_WIDTH] as i32;
+                let height = bounds[SIZE_HEIGHT] as i32;
+
+                for col in 1..cols {
+                    let x = self.cell_rect(0, col).x();
+
+                    grid_lines.push((Point::new(x, 0), Point::new(x, height - 1)));
+                }
+
+                for row in 1..rows {
+                    let y = self.cell_rect(row, 0).y();
+
+                    grid_lines.push((Point::new(0, y), Point::new(width - 1, y)));
+                }
+            }
+
+            let hovered = self.hovered.map(|(row, col)| self.cell_rect(row, col));
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (rect, color) in &cells {
+                    texture.set_draw_color(*color);
+                    texture.fill_rect(*rect).unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+
+                for (from, to) in &grid_lines {
+                    texture.draw_line(*from, *to).unwrap();
+                }
+
+                if let Some(rect) = hovered {
+                    texture.set_draw_color(hover_color);
+                    texture.draw_rect(rect).unwrap();
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Stops outlining the cell under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hovered = None;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Outlines the cell under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let hovered = self.cell_at(&points);
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Triggers the `on_cell_clicked` callback for the cell under the mouse.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 && _state {
+            if let Some((row, col)) = self.hovered {
+                if let Some(mut cb) = self.on_cell_clicked.take() {
+                    cb(self, _widgets, _layouts, row, col);
+                    self.on_cell_clicked = Some(cb);
+                }
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Redraws the heatmap when its colors change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE | CONFIG_COLOR_BORDER | CONFIG_COLOR_HOVER => {
+                self.get_config().set_invalidated(true)
+            }
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
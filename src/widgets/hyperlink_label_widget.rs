@@ -0,0 +1,270 @@
+// Pushrod Widget Library
+// Hyperlink Label Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::cursor::MouseCursor;
+use crate::render::engine::open_url;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::FontStyle;
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the callback type that is used when an `on_link_clicked` callback is triggered from this
+/// `Widget`.  It receives the URL of the link.
+pub type OnLinkClickedCallbackType = Option<
+    Box<dyn FnMut(&mut HyperlinkLabelWidget, &[WidgetContainer], &[LayoutContainer], String)>,
+>;
+
+/// This is the storage object for the `HyperlinkLabelWidget`.  It stores the config, properties,
+/// callback registry, the URL of the link, and the state of the mouse.
+pub struct HyperlinkLabelWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    url: String,
+    open_in_browser: bool,
+    in_bounds: bool,
+    originated: bool,
+    on_link_clicked: OnLinkClickedCallbackType,
+}
+
+/// This is the implementation of the `HyperlinkLabelWidget`, a text label styled as a hyperlink.
+/// The text, stored in `CONFIG_TEXT`, is drawn in `CONFIG_COLOR_TEXT`.  While the mouse is over the
+/// link, the text is drawn underlined in `CONFIG_COLOR_HOVER`, and the mouse cursor changes to a
+/// pointing hand.  Clicking the link, or pressing `Return` while it has focus, triggers the
+/// `on_link_clicked` callback, and optionally opens the URL in the system browser.
+impl HyperlinkLabelWidget {
+    /// Creates a new `HyperlinkLabelWidget` given the `x, y, w, h` coordinates, the `text` to show,
+    /// and the `url` of the link.  The text is vertically centered, and drawn with the size in
+    /// `CONFIG_FONT_SIZE`, or `14` if it is not set.
+    pub fn new(points: Points, size: Size, text: String, url: String) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_text(CONFIG_TEXT, text);
+        config.set_numeric(CONFIG_CURSOR, MouseCursor::Hand.to_numeric());
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            url,
+            open_in_browser: false,
+            in_bounds: false,
+            originated: false,
+            on_link_clicked: None,
+        }
+    }
+
+    /// Sets the URL of the link.
+    pub fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    /// Retrieves the URL of the link.
+    pub fn get_url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Sets whether or not clicking the link opens its URL in the system browser, using
+    /// `pushrod::render::engine::open_url`.  This is off by default, so the link only triggers the
+    /// `on_link_clicked` callback.
+    pub fn set_open_in_browser(&mut self, open_in_browser: bool) {
+        self.open_in_browser = open_in_browser;
+    }
+
+    /// Indicates whether or not clicking the link opens its URL in the system browser.
+    pub fn get_open_in_browser(&self) -> bool {
+        self.open_in_browser
+    }
+
+    /// Assigns the callback closure that will be used when the link is clicked.
+    pub fn on_link_clicked<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut HyperlinkLabelWidget, &[WidgetContainer], &[LayoutContainer], String)
+            + 'static,
+    {
+        self.on_link_clicked = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_link_clicked` callback, then opens the URL if
+    /// requested.
+    fn activate(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if let Some(mut cb) = self.on_link_clicked.take() {
+            cb(self, widgets, layouts, self.url.clone());
+            self.on_link_clicked = Some(cb);
+        }
+
+        if self.open_in_browser {
+            // There is nothing useful the link can do if no browser could be started.
+            let _ = open_url(&self.url);
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `HyperlinkLabelWidget`.
+impl Widget for HyperlinkLabelWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let text_color = if self.in_bounds {
+                self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(0, 0, 160))
+            } else {
+                self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 238))
+            };
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 14);
+            let text = self.get_config().get_text(CONFIG_TEXT);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let mut font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+
+            if self.in_bounds {
+                font.set_style(FontStyle::UNDERLINE);
+            }
+
+            // Fonts cannot render an empty string, so a blank link is drawn as a space.
+            let surface = font
+                .render(if text.is_empty() { " " } else { &text })
+                .blended(text_color)
+                .map_err(|e| e.to_string())
+                .unwrap();
+            let font_texture = texture_creator
+                .create_texture_from_surface(&surface)
+                .map_err(|e| e.to_string())
+                .unwrap();
+            let TextureQuery { width, height, .. } = font_texture.query();
+            let text_y = (bounds[SIZE_HEIGHT] as i32 - height as i32) / 2;
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture
+                    .copy(&font_texture, None, Rect::new(0, text_y, width, height))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the font texture is only used while drawing this `Widget`.
+            unsafe {
+                font_texture.destroy();
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Underlines the link while the mouse is over it.
+    fn mouse_entered(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = true;
+        self.get_config().set_invalidated(true);
+        self.mouse_entered_callback(_widgets, _layouts);
+    }
+
+    /// Removes the underline when the mouse leaves the link.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = false;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Follows the link when the mouse button is pressed and released over it.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                self.originated = true;
+            } else {
+                if self.in_bounds && self.originated {
+                    self.activate(_widgets, _layouts);
+                }
+
+                self.originated = false;
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Follows the link when `Return` is pressed.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(Keycode::Return),
+            ..
+        } = event
+        {
+            self.activate(_widgets, _layouts);
+        }
+    }
+
+    /// Redraws the link when its text, colors, or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_TEXT | CONFIG_FONT_SIZE | CONFIG_COLOR_BASE | CONFIG_COLOR_TEXT
+            | CONFIG_COLOR_HOVER => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use crate::render::callbacks::CallbackRegistry;
+use crate::render::skin::SkinState;
 use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
@@ -40,7 +41,8 @@ use std::collections::HashMap;
 pub type OnClickCallbackType =
     Option<Box<dyn FnMut(&mut ImageButtonWidget, &[WidgetContainer], &[LayoutContainer])>>;
 
-/// This is the storage object for the `ImageButtonWidget`.  It stores the config, properties, callback registry.
+/// This is the storage object for the `ImageButtonWidget`.  It stores the config, properties, callback
+/// registry, the images shown for each state of the button, and the state of the mouse.
 pub struct ImageButtonWidget {
     config: WidgetConfig,
     system_properties: HashMap<i32, String>,
@@ -49,6 +51,8 @@ pub struct ImageButtonWidget {
     base_widget: BaseWidget,
     text_widget: TextWidget,
     image_widget: ImageWidget,
+    image_name: String,
+    state_images: HashMap<SkinState, String>,
     active: bool,
     in_bounds: bool,
     originated: bool,
@@ -56,6 +60,9 @@ pub struct ImageButtonWidget {
 }
 
 /// This is the implementation of the `ImageButtonWidget`, which displays an image next to some text.
+/// A different image can be shown while the mouse is over the button, while it is pressed, and
+/// while it is disabled, by setting an image for each `SkinState` with `set_state_image`.  The text
+/// of a disabled button is drawn in gray.
 impl ImageButtonWidget {
     /// Creates a new `ImageButtonWidget`, given the `x, y, w, h` coordinates, a block of `text`, the
     /// `font_size` to use, and the `image_name` to load and display.
@@ -83,7 +90,7 @@ impl ImageButtonWidget {
             ),
         );
         let mut image_widget = ImageWidget::new(
-            image_name,
+            image_name.clone(),
             make_points(points[POINT_X] + 2, points[POINT_Y] + 2),
             make_size(size[SIZE_HEIGHT] - 4, size[SIZE_HEIGHT] - 4),
             false,
@@ -93,14 +100,20 @@ impl ImageButtonWidget {
         text_widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
         image_widget.set_compass(CONFIG_IMAGE_POSITION, Center);
 
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
         Self {
-            config: WidgetConfig::new(points, size),
+            config,
             system_properties: HashMap::new(),
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
             base_widget,
             text_widget,
             image_widget,
+            image_name,
+            state_images: HashMap::new(),
             active: false,
             in_bounds: false,
             originated: false,
@@ -108,6 +121,43 @@ impl ImageButtonWidget {
         }
     }
 
+    /// Sets the image shown while the button is in `state`.  The image given to `new` is shown in
+    /// any state that has no image of its own.  Setting the image for `SkinState::Normal` replaces
+    /// that image.
+    pub fn set_state_image(&mut self, state: SkinState, image_name: String) {
+        if state == SkinState::Normal {
+            self.image_name = image_name;
+        } else {
+            self.state_images.insert(state, image_name);
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the image shown while the button is in `state`.
+    pub fn get_state_image(&self, state: SkinState) -> String {
+        self.state_images
+            .get(&state)
+            .unwrap_or(&self.image_name)
+            .clone()
+    }
+
+    /// Removes the image shown while the button is in `state`, so that the normal image is shown
+    /// instead.
+    pub fn clear_state_image(&mut self, state: SkinState) {
+        self.state_images.remove(&state);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the current `SkinState` of the button.
+    pub fn get_state(&self) -> SkinState {
+        SkinState::from_flags(
+            self.config.is_enabled(),
+            self.in_bounds,
+            self.active && self.in_bounds,
+        )
+    }
+
     fn draw_hovered(&mut self) {
         self.base_widget
             .set_color(CONFIG_COLOR_BASE, Color::RGB(0, 0, 0));
@@ -151,6 +201,17 @@ impl Widget for ImageButtonWidget {
         if self.get_config().invalidated() {
             let bounds = self.get_config().get_size(CONFIG_SIZE);
             let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let state = self.get_state();
+            let text_color = if state == SkinState::Disabled {
+                Color::RGB(160, 160, 160)
+            } else if state == SkinState::Pressed {
+                Color::RGB(255, 255, 255)
+            } else {
+                Color::RGB(0, 0, 0)
+            };
+
+            self.image_widget.set_image(self.get_state_image(state));
+            self.text_widget.set_color(CONFIG_COLOR_TEXT, text_color);
 
             self.texture_store
                 .create_or_resize_texture(c, bounds[0] as u32, bounds[1] as u32);
@@ -208,6 +269,7 @@ impl Widget for ImageButtonWidget {
         }
 
         self.in_bounds = true;
+        self.get_config().set_invalidated(true);
         self.mouse_entered_callback(_widgets, _layouts);
     }
 
@@ -219,6 +281,7 @@ impl Widget for ImageButtonWidget {
         }
 
         self.in_bounds = false;
+        self.get_config().set_invalidated(true);
         self.mouse_exited_callback(_widgets, _layouts);
     }
 
@@ -261,6 +324,16 @@ impl Widget for ImageButtonWidget {
         self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
     }
 
+    /// Releases the backing texture of this `Widget`, along with those of the `Widget`s it is
+    /// composed of.  The textures are recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.base_widget.release_textures();
+        self.text_widget.release_textures();
+        self.image_widget.release_textures();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
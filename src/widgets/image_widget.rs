@@ -66,6 +66,20 @@ impl ImageWidget {
         }
     }
 
+    /// Changes the image drawn by this `Widget` to `image_name`.  Images are loaded through the
+    /// `TextureCache`, so switching back and forth between images does not load them again.
+    pub fn set_image(&mut self, image_name: String) {
+        if image_name != self.image_name {
+            self.image_name = image_name;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the name of the image drawn by this `Widget`.
+    pub fn get_image(&self) -> String {
+        self.image_name.clone()
+    }
+
     /// Returns the size of the texture.
     pub fn get_texture_size(&self) -> Size {
         self.texture_sizes.clone()
@@ -150,6 +164,13 @@ impl Widget for ImageWidget {
         }
     }
 
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
@@ -0,0 +1,462 @@
+// Pushrod Widget Library
+// Keypad Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::{widget_id_for_name, CallbackRegistry};
+use crate::render::canvas_helper::fill_rounded_rect;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the space between keys, and around the edges of the keypad, in pixels.
+const KEY_GAP: u32 = 4;
+
+/// This is the radius of the corners of each key, in pixels.
+const KEY_RADIUS: u32 = 4;
+
+/// This is the number of columns of keys.
+const COLUMNS: u32 = 4;
+
+/// This is the number of rows of keys.
+const ROWS: u32 = 4;
+
+/// This is the default number of characters that can be entered.
+const DEFAULT_MAX_LENGTH: usize = 12;
+
+/// This is what pressing a key of the keypad does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum KeypadKey {
+    Digit(char),
+    Decimal,
+    Backspace,
+    Enter,
+}
+
+/// These are the keys of the keypad, with the column and row of their top left corner, and the
+/// number of columns and rows they cover.  `Backspace` and `Enter` are double-height, and `0` is
+/// double-width, as on a desktop keypad.
+const KEYS: [(KeypadKey, u32, u32, u32, u32); 13] = [
+    (KeypadKey::Digit('7'), 0, 0, 1, 1),
+    (KeypadKey::Digit('8'), 1, 0, 1, 1),
+    (KeypadKey::Digit('9'), 2, 0, 1, 1),
+    (KeypadKey::Backspace, 3, 0, 1, 2),
+    (KeypadKey::Digit('4'), 0, 1, 1, 1),
+    (KeypadKey::Digit('5'), 1, 1, 1, 1),
+    (KeypadKey::Digit('6'), 2, 1, 1, 1),
+    (KeypadKey::Digit('1'), 0, 2, 1, 1),
+    (KeypadKey::Digit('2'), 1, 2, 1, 1),
+    (KeypadKey::Digit('3'), 2, 2, 1, 1),
+    (KeypadKey::Enter, 3, 2, 1, 2),
+    (KeypadKey::Digit('0'), 0, 3, 2, 1),
+    (KeypadKey::Decimal, 2, 3, 1, 1),
+];
+
+/// This is the callback type that is used when an `on_value_committed` callback is triggered from
+/// this `Widget`.  It receives the value entered.
+pub type OnValueCommittedCallbackType =
+    Option<Box<dyn FnMut(&mut KeypadWidget, &[WidgetContainer], &[LayoutContainer], String)>>;
+
+/// This is the storage object for the `KeypadWidget`.  It stores the config, properties, callback
+/// registry, the value entered, the name of the bound `Widget`, and the key under the mouse and the
+/// key being pressed.
+pub struct KeypadWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    value: String,
+    max_length: usize,
+    target: Option<String>,
+    hovered: Option<usize>,
+    pressed: Option<usize>,
+    mouse_position: Points,
+    on_value_committed: OnValueCommittedCallbackType,
+}
+
+/// This is the implementation of the `KeypadWidget`, a compact numeric keypad with the digits `0`
+/// to `9`, a decimal point, `Backspace`, and `Enter`, for point-of-sale style applications.  The
+/// keypad keeps the value being entered, which is limited to one decimal point, and to a maximum
+/// number of characters.  When bound to another `Widget` by name, every change to the value is
+/// copied to that `Widget`'s `CONFIG_TEXT`, so a text or numeric field shows what is being entered.
+/// Pressing `Enter` triggers `on_value_committed`.
+///
+/// Keys are filled with `CONFIG_COLOR_SECONDARY`, `CONFIG_COLOR_HOVER` under the mouse, and
+/// `CONFIG_COLOR_SELECTED` while pressed.  The keypad is filled with `CONFIG_COLOR_BASE`, and the
+/// keys are outlined in `CONFIG_COLOR_BORDER`, and labelled in `CONFIG_COLOR_TEXT`, with the size
+/// in `CONFIG_FONT_SIZE`, or `18` if it is not set.
+impl KeypadWidget {
+    /// Creates a new `KeypadWidget` given the `x, y, w, h` coordinates.  The keys are stretched to
+    /// fill the `Widget`.
+    pub fn new(points: Points, size: Size) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            value: String::new(),
+            max_length: DEFAULT_MAX_LENGTH,
+            target: None,
+            hovered: None,
+            pressed: None,
+            mouse_position: vec![0, 0],
+            on_value_committed: None,
+        }
+    }
+
+    /// Binds the keypad to the `Widget` called `name`, whose `CONFIG_TEXT` is set to the value
+    /// whenever it changes.
+    pub fn set_target(&mut self, name: &str) {
+        self.target = Some(String::from(name));
+    }
+
+    /// Unbinds the keypad from its `Widget`.
+    pub fn clear_target(&mut self) {
+        self.target = None;
+    }
+
+    /// Retrieves the name of the `Widget` the keypad is bound to, if any.
+    pub fn get_target(&self) -> Option<String> {
+        self.target.clone()
+    }
+
+    /// Sets the value being entered, without updating the bound `Widget`.  Characters other than
+    /// digits and the first decimal point are dropped, and the value is limited to the maximum
+    /// length.
+    pub fn set_value(&mut self, value: &str) {
+        let mut decimal = false;
+
+        self.value = value
+            .chars()
+            .filter(|c| {
+                if *c == '.' && !decimal {
+                    decimal = true;
+                    true
+                } else {
+                    c.is_ascii_digit()
+                }
+            })
+            .take(self.max_length)
+            .collect();
+    }
+
+    /// Retrieves the value being entered.
+    pub fn get_value(&self) -> String {
+        self.value.clone()
+    }
+
+    /// Clears the value being entered, without updating the bound `Widget`.
+    pub fn clear(&mut self) {
+        self.value.clear();
+    }
+
+    /// Sets the maximum number of characters that can be entered.  The value is shortened if it is
+    /// longer.
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = max_length.max(1);
+        self.value.truncate(self.max_length);
+    }
+
+    /// Retrieves the maximum number of characters that can be entered.
+    pub fn get_max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Assigns the callback closure that will be used when `Enter` is pressed.
+    pub fn on_value_committed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut KeypadWidget, &[WidgetContainer], &[LayoutContainer], String) + 'static,
+    {
+        self.on_value_committed = Some(Box::new(callback));
+    }
+
+    /// Internal function that returns the bounds of each key of `KEYS`, in local coordinates.
+    fn key_rects(&mut self) -> Vec<Rect> {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let cell_width = bounds[SIZE_WIDTH].saturating_sub(KEY_GAP) / COLUMNS;
+        let cell_height = bounds[SIZE_HEIGHT].saturating_sub(KEY_GAP) / ROWS;
+
+        KEYS.iter()
+            .map(|(_, column, row, columns, rows)| {
+                Rect::new(
+                    (KEY_GAP + column * cell_width) as i32,
+                    (KEY_GAP + row * cell_height) as i32,
+                    (columns * cell_width).saturating_sub(KEY_GAP).max(1),
+                    (rows * cell_height).saturating_sub(KEY_GAP).max(1),
+                )
+            })
+            .collect()
+    }
+
+    /// Internal function that returns the index of the key at the absolute position `points`.
+    fn key_at(&mut self, points: &[i32]) -> Option<usize> {
+        let point = Point::new(
+            points[POINT_X] - self.get_config().to_x(0),
+            points[POINT_Y] - self.get_config().to_y(0),
+        );
+
+        self.key_rects()
+            .iter()
+            .position(|rect| rect.contains_point(point))
+    }
+
+    /// Internal function that applies `key` to the value, copying it to the bound `Widget` if it
+    /// changed, and triggering `on_value_committed` for `Enter`.
+    fn press_key(
+        &mut self,
+        key: KeypadKey,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let previous = self.value.clone();
+
+        match key {
+            KeypadKey::Digit(digit) if self.value.len() < self.max_length => {
+                self.value.push(digit);
+            }
+            KeypadKey::Decimal if !self.value.contains('.') => {
+                if self.value.is_empty() && self.max_length > 1 {
+                    self.value.push('0');
+                }
+
+                if self.value.len() < self.max_length {
+                    self.value.push('.');
+                }
+            }
+            KeypadKey::Backspace => {
+                self.value.pop();
+            }
+            KeypadKey::Enter => {
+                if let Some(mut cb) = self.on_value_committed.take() {
+                    cb(self, widgets, layouts, self.value.clone());
+                    self.on_value_committed = Some(cb);
+                }
+            }
+            _ => (),
+        }
+
+        if self.value != previous {
+            self.update_target(widgets);
+        }
+    }
+
+    /// Internal function that copies the value to the `CONFIG_TEXT` of the bound `Widget`, if it
+    /// exists.
+    fn update_target(&mut self, widgets: &[WidgetContainer]) {
+        if let Some(target) = &self.target {
+            let target_id = widget_id_for_name(widgets, target);
+
+            if target_id != 0 {
+                // The keypad is already borrowed if it is bound to itself, which is ignored.
+                if let Ok(mut widget) = widgets[target_id].widget.try_borrow_mut() {
+                    widget.set_text(CONFIG_TEXT, self.value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `KeypadWidget`.
+impl Widget for KeypadWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(208, 212, 218));
+            let key_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(255, 255, 255));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(232, 238, 250));
+            let pressed_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(150, 170, 210));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(150, 150, 150));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 18);
+            let rects = self.key_rects();
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+            let keys: Vec<(Rect, Color, Texture, Rect)> = KEYS
+                .iter()
+                .enumerate()
+                .map(|(index, (key, _, _, _, _))| {
+                    let rect = rects[index];
+                    let color = if self.pressed == Some(index) {
+                        pressed_color
+                    } else if self.hovered == Some(index) {
+                        hover_color
+                    } else {
+                        key_color
+                    };
+                    let label = match key {
+                        KeypadKey::Digit(digit) => digit.to_string(),
+                        KeypadKey::Decimal => String::from("."),
+                        KeypadKey::Backspace => String::from("Bksp"),
+                        KeypadKey::Enter => String::from("Enter"),
+                    };
+                    let surface = font
+                        .render(&label)
+                        .blended(text_color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let label_texture = texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let TextureQuery { width, height, .. } = label_texture.query();
+                    let center = rect.center();
+
+                    (
+                        rect,
+                        color,
+                        label_texture,
+                        Rect::new(
+                            center.x() - width as i32 / 2,
+                            center.y() - height as i32 / 2,
+                            width,
+                            height,
+                        ),
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (rect, color, label_texture, label_rect) in &keys {
+                    let outline = Rect::new(
+                        rect.x() - 1,
+                        rect.y() - 1,
+                        rect.width() + 2,
+                        rect.height() + 2,
+                    );
+
+                    texture.set_draw_color(border_color);
+                    fill_rounded_rect(texture, outline, KEY_RADIUS + 1);
+                    texture.set_draw_color(*color);
+                    fill_rounded_rect(texture, *rect, KEY_RADIUS);
+                    texture.set_clip_rect(*rect);
+                    texture.copy(label_texture, None, *label_rect).unwrap();
+                    texture.set_clip_rect(None);
+                }
+            })
+            .unwrap();
+
+            // Safety: the label textures are only used while drawing this `Widget`.
+            for (_, _, label_texture, _) in keys {
+                unsafe {
+                    label_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Stops highlighting the key under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hovered = None;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the key under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let hovered = self.key_at(&points);
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Presses the key under the mouse when the button is pressed, and highlights it until the
+    /// button is released.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                let position = self.mouse_position.clone();
+
+                if let Some(index) = self.key_at(&position) {
+                    self.pressed = Some(index);
+                    self.get_config().set_invalidated(true);
+                    self.press_key(KEYS[index].0, _widgets, _layouts);
+                }
+            } else if self.pressed.take().is_some() {
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Redraws the keypad when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_SECONDARY
+            | CONFIG_COLOR_HOVER
+            | CONFIG_COLOR_SELECTED
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_TEXT
+            | CONFIG_FONT_SIZE => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
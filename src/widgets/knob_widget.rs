@@ -0,0 +1,383 @@
+// Pushrod Widget Library
+// Knob Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{draw_arc, draw_circle, fill_circle, point_at_angle};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Point;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the angle of the minimum value, in degrees clockwise from the top of the knob.
+const KNOB_START_ANGLE: f64 = -135.0;
+
+/// This is the angle of the maximum value, in degrees clockwise from the top of the knob.
+const KNOB_END_ANGLE: f64 = 135.0;
+
+/// This is the width of the arc drawn around the knob to show its value, in pixels.
+const ARC_WIDTH: u32 = 4;
+
+/// This is the space between the arc and the body of the knob, in pixels.
+const ARC_SPACING: u32 = 3;
+
+/// This is the number of pixels the mouse must be dragged vertically to turn the knob from its
+/// minimum to its maximum value, when using `KnobDragMode::Vertical`.
+const DRAG_DISTANCE: i32 = 150;
+
+/// This is the callback type that is used when an `on_value_changed` callback is triggered from
+/// this `Widget`.  It receives the new value.
+pub type OnKnobValueChangedCallbackType =
+    Option<Box<dyn FnMut(&mut KnobWidget, &[WidgetContainer], &[LayoutContainer], i32)>>;
+
+/// These are the ways in which dragging the mouse turns a `KnobWidget`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum KnobDragMode {
+    /// Dragging the mouse up turns the knob clockwise, and dragging it down turns it
+    /// counter-clockwise.  This is the default.
+    Vertical,
+
+    /// The knob turns to point at the mouse as it is dragged around the center of the knob.
+    Circular,
+}
+
+/// This is the storage object for the `KnobWidget`.  It stores the config, properties, callback
+/// registry, the value range, and the state of the drag in progress.
+pub struct KnobWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    min: i32,
+    max: i32,
+    value: i32,
+    step: i32,
+    drag_mode: KnobDragMode,
+    drag_start: Option<(i32, i32)>,
+    mouse_position: Points,
+    on_value_changed: OnKnobValueChangedCallbackType,
+}
+
+/// This is the implementation of the `KnobWidget`, a rotary dial that selects a value between a
+/// minimum and maximum, as found in audio applications.  The knob turns through 270 degrees, with
+/// an indicator pointing at the current value, and an arc around it showing how far it has been
+/// turned.  Dragging the mouse turns the knob as set by its `KnobDragMode`, and the mouse wheel
+/// turns it by the step size.
+impl KnobWidget {
+    /// Creates a new `KnobWidget` given the `x, y, w, h` coordinates, the `min` and `max` values,
+    /// and the initial `value`, which is clamped to the range.  The knob is drawn as a circle that
+    /// fills the shorter side of the `Widget`.
+    pub fn new(points: Points, size: Size, min: i32, max: i32, value: i32) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            min: min.min(max),
+            max: max.max(min),
+            value: value.clamp(min.min(max), max.max(min)),
+            step: 1,
+            drag_mode: KnobDragMode::Vertical,
+            drag_start: None,
+            mouse_position: vec![0, 0],
+            on_value_changed: None,
+        }
+    }
+
+    /// Sets the `min` and `max` values.  The value is clamped to the new range.  This does not
+    /// trigger the `on_value_changed` callback.
+    pub fn set_range(&mut self, min: i32, max: i32) {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.set_value(self.value);
+    }
+
+    /// Retrieves the minimum value.
+    pub fn get_min(&self) -> i32 {
+        self.min
+    }
+
+    /// Retrieves the maximum value.
+    pub fn get_max(&self) -> i32 {
+        self.max
+    }
+
+    /// Sets the value, clamped to the range from `min` to `max`.  This does not trigger the
+    /// `on_value_changed` callback.
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the value.
+    pub fn get_value(&self) -> i32 {
+        self.value
+    }
+
+    /// Sets the amount that each step of the mouse wheel changes the value by.
+    pub fn set_step(&mut self, step: i32) {
+        self.step = step.max(1);
+    }
+
+    /// Retrieves the amount that each step of the mouse wheel changes the value by.
+    pub fn get_step(&self) -> i32 {
+        self.step
+    }
+
+    /// Sets the way in which dragging the mouse turns the knob.
+    pub fn set_drag_mode(&mut self, drag_mode: KnobDragMode) {
+        self.drag_mode = drag_mode;
+    }
+
+    /// Retrieves the way in which dragging the mouse turns the knob.
+    pub fn get_drag_mode(&self) -> KnobDragMode {
+        self.drag_mode
+    }
+
+    /// Assigns the callback closure that will be used when the user changes the value.
+    pub fn on_value_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut KnobWidget, &[WidgetContainer], &[LayoutContainer], i32) + 'static,
+    {
+        self.on_value_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that changes the value, triggering the `on_value_changed` callback if it
+    /// changed.
+    fn change_value(
+        &mut self,
+        value: i64,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let value = value.clamp(self.min as i64, self.max as i64) as i32;
+
+        if value == self.value {
+            return;
+        }
+
+        self.value = value;
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_value_changed.take() {
+            cb(self, widgets, layouts, value);
+            self.on_value_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that returns the angle of the indicator for the current value.
+    fn value_angle(&self) -> f64 {
+        let range = (self.max - self.min) as f64;
+        let fraction = if range > 0.0 {
+            (self.value - self.min) as f64 / range
+        } else {
+            0.0
+        };
+
+        KNOB_START_ANGLE + fraction * (KNOB_END_ANGLE - KNOB_START_ANGLE)
+    }
+
+    /// Internal function that returns the center of the knob in window coordinates.
+    fn center(&mut self) -> Point {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+
+        Point::new(
+            self.get_config().to_x(size[SIZE_WIDTH] as i32 / 2),
+            self.get_config().to_y(size[SIZE_HEIGHT] as i32 / 2),
+        )
+    }
+
+    /// Internal function that turns the knob according to the position of the mouse while it is
+    /// dragged.
+    fn drag_to_mouse(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        let (start_y, start_value) = match self.drag_start {
+            Some(drag_start) => drag_start,
+            None => return,
+        };
+        let range = (self.max - self.min) as i64;
+        let value = match self.drag_mode {
+            KnobDragMode::Vertical => {
+                let distance = (start_y - self.mouse_position[POINT_Y]) as i64;
+
+                start_value as i64 + distance * range / DRAG_DISTANCE as i64
+            }
+            KnobDragMode::Circular => {
+                let center = self.center();
+                let dx = (self.mouse_position[POINT_X] - center.x()) as f64;
+                let dy = (self.mouse_position[POINT_Y] - center.y()) as f64;
+
+                if dx == 0.0 && dy == 0.0 {
+                    return;
+                }
+
+                let angle = dx.atan2(-dy).to_degrees();
+                let fraction = ((angle - KNOB_START_ANGLE) / (KNOB_END_ANGLE - KNOB_START_ANGLE))
+                    .clamp(0.0, 1.0);
+
+                self.min as i64 + (fraction * range as f64).round() as i64
+            }
+        };
+
+        self.change_value(value, widgets, layouts);
+    }
+}
+
+/// This is the `Widget` implementation of the `KnobWidget`.
+impl Widget for KnobWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let knob_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(224, 224, 224));
+            let track_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(200, 200, 200));
+            let value_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(64, 96, 192));
+            let indicator_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let center = Point::new(
+                bounds[SIZE_WIDTH] as i32 / 2,
+                bounds[SIZE_HEIGHT] as i32 / 2,
+            );
+            let radius = (bounds[SIZE_WIDTH].min(bounds[SIZE_HEIGHT]) / 2).saturating_sub(1);
+            let body_radius = radius.saturating_sub(ARC_WIDTH + ARC_SPACING);
+            let value_angle = self.value_angle();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(track_color);
+                draw_arc(
+                    texture,
+                    center,
+                    radius,
+                    ARC_WIDTH,
+                    KNOB_START_ANGLE,
+                    KNOB_END_ANGLE,
+                );
+
+                texture.set_draw_color(value_color);
+                draw_arc(
+                    texture,
+                    center,
+                    radius,
+                    ARC_WIDTH,
+                    KNOB_START_ANGLE,
+                    value_angle,
+                );
+
+                texture.set_draw_color(knob_color);
+                fill_circle(texture, center, body_radius);
+
+                texture.set_draw_color(border_color);
+                draw_circle(texture, center, body_radius);
+
+                texture.set_draw_color(indicator_color);
+
+                for offset in &[-1, 0, 1] {
+                    let start = Point::new(center.x() + offset, center.y());
+
+                    texture
+                        .draw_line(
+                            point_at_angle(start, body_radius as f64 * 0.25, value_angle),
+                            point_at_angle(start, body_radius as f64 * 0.85, value_angle),
+                        )
+                        .unwrap();
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Turns the knob while the mouse is dragged.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.drag_to_mouse(_widgets, _layouts);
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Turns the knob by one step for each step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let value = self.value as i64 + points[POINT_Y] as i64 * self.step as i64;
+
+        self.change_value(value, _widgets, _layouts);
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Starts turning the knob when it is pressed, and stops when the button is released.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                self.drag_start = Some((self.mouse_position[POINT_Y], self.value));
+
+                if self.drag_mode == KnobDragMode::Circular {
+                    self.drag_to_mouse(_widgets, _layouts);
+                }
+            } else {
+                self.drag_start = None;
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Captures the mouse, so that the knob can be turned beyond the bounds of the `Widget`.
+    fn captures_mouse(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
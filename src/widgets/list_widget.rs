@@ -25,18 +25,29 @@ use crate::render::canvas_helper::CanvasHelper;
 use crate::render::layout_cache::LayoutContainer;
 use crate::render::texture_cache::TextureCache;
 use crate::render::texture_store::TextureStore;
-use crate::render::{Points, Size, POINT_Y, SIZE_WIDTH};
+use crate::render::{Points, Size, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use std::any::Any;
 use std::collections::HashMap;
 use std::path::Path;
 
+/// This is the height of each item in the list.
+const ITEM_HEIGHT: u32 = 30;
+
+/// This is the width of the scroll indicator drawn when the items do not fit vertically.
+const SCROLL_INDICATOR_WIDTH: u32 = 4;
+
 /// This is the callback type that is used when an `on_selected` callback is triggered from this
 /// `Widget`.
 pub type OnSelectedCallbackType =
     Option<Box<dyn FnMut(&mut ListWidget, &[WidgetContainer], &[LayoutContainer], i32)>>;
 
+/// This is the callback type that is used when an `on_item_selected` callback is triggered from this
+/// `Widget`.  It receives the index of the item that was clicked.
+pub type OnItemSelectedCallbackType =
+    Option<Box<dyn FnMut(&mut ListWidget, &[WidgetContainer], &[LayoutContainer], usize)>>;
+
 /// This is the storage object for the `ListWidget`.  It stores the config, properties, callback registry.
 pub struct ListWidget {
     config: WidgetConfig,
@@ -46,8 +57,10 @@ pub struct ListWidget {
     list_items: Vec<String>,
     highlighted_item: i32,
     selected_item: i32,
+    scroll_offset: usize,
     in_bounds: bool,
     on_selected: OnSelectedCallbackType,
+    on_item_selected: OnItemSelectedCallbackType,
 }
 
 /// This is the implementation of the `ListWidget`, a control that displays a list of items that can be
@@ -55,16 +68,22 @@ pub struct ListWidget {
 impl ListWidget {
     /// Creates a new `ListWidget` given the `x, y, w, h` coordinates.
     pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
         Self {
-            config: WidgetConfig::new(points, size),
+            config,
             system_properties: HashMap::new(),
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
             list_items: vec![],
             highlighted_item: -1,
             selected_item: -1,
+            scroll_offset: 0,
             in_bounds: false,
             on_selected: None,
+            on_item_selected: None,
         }
     }
 
@@ -77,6 +96,56 @@ impl ListWidget {
         item_size
     }
 
+    /// Replaces all of the items in the `ListWidget`.  The selection and scroll position are reset.
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.list_items = items;
+        self.selected_item = -1;
+        self.highlighted_item = -1;
+        self.scroll_offset = 0;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Removes all of the items from the `ListWidget`.
+    pub fn clear_items(&mut self) {
+        self.set_items(vec![]);
+    }
+
+    /// Retrieves the items in the `ListWidget`.
+    pub fn get_items(&self) -> &[String] {
+        &self.list_items
+    }
+
+    /// Retrieves the index of the selected item, or `-1` if no item is selected.
+    pub fn get_selected_item(&self) -> i32 {
+        self.selected_item
+    }
+
+    /// Retrieves the index of the first item shown at the top of the `ListWidget`.
+    pub fn get_scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Scrolls the `ListWidget` so that the item at `offset` is shown at the top, as far as the
+    /// number of items allows.
+    pub fn set_scroll_offset(&mut self, offset: usize) {
+        let offset = offset.min(self.max_scroll_offset());
+
+        if offset != self.scroll_offset {
+            self.scroll_offset = offset;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Returns the number of items that fit entirely within the bounds of the `ListWidget`.
+    fn visible_items(&mut self) -> usize {
+        ((self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] / ITEM_HEIGHT) as usize).max(1)
+    }
+
+    /// Returns the largest scroll offset that still fills the `ListWidget` with items.
+    fn max_scroll_offset(&mut self) -> usize {
+        self.list_items.len().saturating_sub(self.visible_items())
+    }
+
     /// Assigns the callback closure that will be used when the `Widget` changes value, based on a selected
     /// item.
     pub fn on_selected<F>(&mut self, callback: F)
@@ -86,6 +155,31 @@ impl ListWidget {
         self.on_selected = Some(Box::new(callback));
     }
 
+    /// Assigns the callback closure that will be used when an item is clicked.  Unlike `on_selected`,
+    /// this is only triggered when an item is selected, not when the selection is cleared.
+    pub fn on_item_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut ListWidget, &[WidgetContainer], &[LayoutContainer], usize) + 'static,
+    {
+        self.on_item_selected = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_item_selected` callback, if an item is selected.
+    fn call_item_selected_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if self.selected_item < 0 {
+            return;
+        }
+
+        if let Some(mut cb) = self.on_item_selected.take() {
+            cb(self, widgets, layouts, self.selected_item as usize);
+            self.on_item_selected = Some(cb);
+        }
+    }
+
     /// Internal function that triggers the `on_selected` callback.  The selected item ID indicates the value
     /// in the `ListWidget` that has been selected.  If the value is set to `-1`, it means the list items
     /// have been de-selected.
@@ -112,10 +206,26 @@ impl Widget for ListWidget {
             let base_color = self.get_color(CONFIG_COLOR_BASE);
             let hover_color = self.get_color(CONFIG_COLOR_HOVER);
             let border_color = self.get_config().get_color(CONFIG_COLOR_BORDER);
-            let list_size = self.list_items.len();
             let highlighted_item = self.highlighted_item;
             let selected_item = self.selected_item;
-            let list_items = self.list_items.clone();
+
+            self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+
+            let first_item = self.scroll_offset;
+            let last_item = (first_item + self.visible_items() + 1).min(self.list_items.len());
+            let scroll_indicator = if self.list_items.len() > self.visible_items() {
+                let track = bounds[SIZE_HEIGHT] as f64;
+                let total = self.list_items.len() as f64;
+
+                Some(Rect::new(
+                    (bounds[SIZE_WIDTH] - SCROLL_INDICATOR_WIDTH) as i32,
+                    (first_item as f64 / total * track) as i32,
+                    SCROLL_INDICATOR_WIDTH,
+                    ((self.visible_items() as f64 / total * track) as u32).max(1),
+                ))
+            } else {
+                None
+            };
 
             let ttf_context = t.get_ttf_context();
             let texture_creator = c.texture_creator();
@@ -125,64 +235,77 @@ impl Widget for ListWidget {
 
             font.set_style(sdl2::ttf::FontStyle::NORMAL);
 
+            let font_textures: Vec<Texture> = (first_item..last_item)
+                .map(|i| {
+                    let text_color = if selected_item == i as i32 {
+                        Color::RGB(255, 255, 255)
+                    } else {
+                        Color::RGB(0, 0, 0)
+                    };
+                    let surface = font
+                        .render(&self.list_items[i].clone())
+                        .blended_wrapped(text_color, bounds[SIZE_WIDTH])
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+
+                    texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap()
+                })
+                .collect();
+
             c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
                 texture.set_draw_color(base_color);
                 texture.clear();
 
-                let list_height: u32 = 30;
-
-                for i in 0..list_size {
-                    let mut text_color = Color::RGB(0, 0, 0);
-                    let mut color = if highlighted_item == i as i32 {
+                for (row, font_texture) in font_textures.iter().enumerate() {
+                    let i = (first_item + row) as i32;
+                    let row_y = (ITEM_HEIGHT * row as u32) as i32;
+                    let color = if selected_item == i {
+                        Color::RGB(0, 0, 0)
+                    } else if highlighted_item == i {
                         hover_color
                     } else {
                         Color::RGB(255, 255, 255)
                     };
 
-                    if selected_item == i as i32 {
-                        color = Color::RGB(0, 0, 0);
-                        text_color = Color::RGB(255, 255, 255);
-                    }
-
                     texture.set_draw_color(color);
                     texture
-                        .fill_rect(Rect::new(
-                            0,
-                            (list_height * i as u32) as i32,
-                            bounds[SIZE_WIDTH],
-                            30,
-                        ))
-                        .unwrap();
-
-                    let surface = font
-                        .render(&list_items[i].clone())
-                        .blended_wrapped(text_color, bounds[SIZE_WIDTH])
-                        .map_err(|e| e.to_string())
-                        .unwrap();
-                    let font_texture = texture_creator
-                        .create_texture_from_surface(&surface)
-                        .map_err(|e| e.to_string())
+                        .fill_rect(Rect::new(0, row_y, bounds[SIZE_WIDTH], ITEM_HEIGHT))
                         .unwrap();
 
                     let TextureQuery { width, height, .. } = font_texture.query();
-                    let texture_y = (list_height * i as u32) as i32 + 3;
+                    let texture_y = row_y + 3;
                     let texture_x = 10;
 
                     texture
                         .copy(
-                            &font_texture,
+                            font_texture,
                             None,
                             Rect::new(texture_x, texture_y, width, height),
                         )
                         .unwrap();
                 }
 
+                if let Some(indicator) = scroll_indicator {
+                    texture.set_draw_color(border_color);
+                    texture.fill_rect(indicator).unwrap();
+                }
+
                 texture.set_draw_color(border_color);
                 texture
                     .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
                     .unwrap();
             })
             .unwrap();
+
+            // Safety: the item textures are only used within this function.
+            for font_texture in font_textures {
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
         }
 
         self.texture_store.get_optional_ref()
@@ -212,7 +335,7 @@ impl Widget for ListWidget {
                 points[POINT_Y] - self.get_config().get_point(CONFIG_ORIGIN)[POINT_Y] as i32;
             let previous_highlighted_item = self.highlighted_item;
 
-            self.highlighted_item = position_y / 30;
+            self.highlighted_item = self.scroll_offset as i32 + position_y / ITEM_HEIGHT as i32;
 
             if self.highlighted_item >= self.list_items.len() as i32 {
                 self.highlighted_item = -1;
@@ -224,6 +347,29 @@ impl Widget for ListWidget {
         }
     }
 
+    /// Scrolls the list by one item per step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let offset = (self.scroll_offset as i32 - points[POINT_Y]).max(0) as usize;
+        let previous_offset = self.scroll_offset;
+
+        self.set_scroll_offset(offset);
+
+        if self.in_bounds && self.scroll_offset != previous_offset && self.highlighted_item >= 0 {
+            self.highlighted_item += self.scroll_offset as i32 - previous_offset as i32;
+
+            if self.highlighted_item >= self.list_items.len() as i32 {
+                self.highlighted_item = -1;
+            }
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
     /// Overrides the `button_clicked` callback to handle toggling.
     fn button_clicked(
         &mut self,
@@ -238,9 +384,17 @@ impl Widget for ListWidget {
             self.get_config().set_invalidated(true);
 
             self.call_selected_callback(_widgets, _layouts);
+            self.call_item_selected_callback(_widgets, _layouts);
         }
     }
 
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
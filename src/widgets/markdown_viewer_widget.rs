@@ -0,0 +1,1001 @@
+// Pushrod Widget Library
+// Markdown Viewer Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::cursor::MouseCursor;
+use crate::render::engine::open_url;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::{Font, FontStyle};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the font that the `MarkdownViewerWidget` draws its text with.
+const MARKDOWN_FONT: &str = "assets/OpenSans-Regular.ttf";
+
+/// This is the number of pixels between the edges of the `MarkdownViewerWidget` and the document.
+const DOCUMENT_PADDING: i32 = 8;
+
+/// This is the number of pixels that each level of a list is indented by.
+const LIST_INDENT: i32 = 20;
+
+/// This is the number of pixels between the edges of a code block and its text.
+const CODE_PADDING: i32 = 6;
+
+/// This is the width of the scroll indicator drawn when the document does not fit vertically.
+const SCROLL_INDICATOR_WIDTH: u32 = 4;
+
+/// This is the number of lines scrolled by each step of the mouse wheel.
+const LINES_PER_SCROLL_STEP: i32 = 3;
+
+/// This is the callback type that is used when an `on_link_clicked` callback is triggered from this
+/// `Widget`.  It receives the URL of the link.
+pub type OnLinkClickedCallbackType = Option<
+    Box<dyn FnMut(&mut MarkdownViewerWidget, &[WidgetContainer], &[LayoutContainer], String)>,
+>;
+
+/// This is the style of a run of inline text.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct SpanStyle {
+    bold: bool,
+    italic: bool,
+    code: bool,
+}
+
+/// This is a run of inline text in a single style, which may be part of a link.
+#[derive(Clone, Debug, PartialEq)]
+struct Span {
+    text: String,
+    style: SpanStyle,
+    link: Option<String>,
+}
+
+/// This is a block of a Markdown document.
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    Heading(usize, Vec<Span>),
+    Paragraph(Vec<Span>),
+    ListItem(String, usize, Vec<Span>),
+    Code(Vec<String>),
+    Rule,
+}
+
+/// This identifies a font by its size and style, so that it is only loaded once per layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct FontKey {
+    size: u16,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+}
+
+/// This is a run of text laid out at a position in the document.
+struct Run {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    text: String,
+    font: FontKey,
+    link: Option<usize>,
+    code: bool,
+}
+
+/// This is the result of laying out a document at a given width.
+#[derive(Default)]
+struct DocumentLayout {
+    runs: Vec<Run>,
+    code_blocks: Vec<Rect>,
+    rules: Vec<i32>,
+    links: Vec<(Rect, String)>,
+    height: i32,
+}
+
+/// Returns whether the emphasis marker at `index`, which is `length` characters long, can open or
+/// close emphasis.  Markers surrounded by spaces, or inside words when using `_`, are literal.
+fn is_emphasis(chars: &[char], index: usize, length: usize) -> bool {
+    let before = index.checked_sub(1).map(|i| chars[i]);
+    let after = chars.get(index + length).copied();
+    let opens = after.is_some_and(|c| !c.is_whitespace());
+    let closes = before.is_some_and(|c| !c.is_whitespace());
+    let inside_word =
+        before.is_some_and(|c| c.is_alphanumeric()) && after.is_some_and(|c| c.is_alphanumeric());
+
+    (opens || closes) && !(chars[index] == '_' && inside_word)
+}
+
+/// Parses inline Markdown: `**bold**`, `*italic*`, `` `code` ``, `[links](url)`, and backslash
+/// escapes.
+fn parse_inline(text: &str) -> Vec<Span> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans: Vec<Span> = vec![];
+    let mut current = String::new();
+    let mut style = SpanStyle::default();
+    let mut index = 0;
+
+    fn flush(current: &mut String, spans: &mut Vec<Span>, style: SpanStyle) {
+        if !current.is_empty() {
+            spans.push(Span {
+                text: std::mem::take(current),
+                style,
+                link: None,
+            });
+        }
+    }
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        match c {
+            '\\' if chars
+                .get(index + 1)
+                .is_some_and(|c| c.is_ascii_punctuation()) =>
+            {
+                current.push(chars[index + 1]);
+                index += 2;
+            }
+            '`' => match chars[index + 1..].iter().position(|c| *c == '`') {
+                Some(length) => {
+                    flush(&mut current, &mut spans, style);
+                    spans.push(Span {
+                        text: chars[index + 1..index + 1 + length].iter().collect(),
+                        style: SpanStyle {
+                            code: true,
+                            ..style
+                        },
+                        link: None,
+                    });
+                    index += length + 2;
+                }
+                None => {
+                    current.push(c);
+                    index += 1;
+                }
+            },
+            '*' | '_' => {
+                let length = if chars.get(index + 1) == Some(&c) {
+                    2
+                } else {
+                    1
+                };
+
+                if is_emphasis(&chars, index, length) {
+                    flush(&mut current, &mut spans, style);
+
+                    if length == 2 {
+                        style.bold = !style.bold;
+                    } else {
+                        style.italic = !style.italic;
+                    }
+                } else {
+                    current.extend(&chars[index..index + length]);
+                }
+
+                index += length;
+            }
+            '[' => {
+                let label_end = chars[index..]
+                    .windows(2)
+                    .position(|pair| pair == [']', '('])
+                    .map(|position| index + position);
+                let url_end = label_end.and_then(|label_end| {
+                    chars[label_end + 2..]
+                        .iter()
+                        .position(|c| *c == ')')
+                        .map(|position| label_end + 2 + position)
+                });
+
+                match (label_end, url_end) {
+                    (Some(label_end), Some(url_end)) => {
+                        let label: String = chars[index + 1..label_end].iter().collect();
+                        let url: String = chars[label_end + 2..url_end].iter().collect();
+
+                        flush(&mut current, &mut spans, style);
+
+                        for mut span in parse_inline(&label) {
+                            span.style.bold |= style.bold;
+                            span.style.italic |= style.italic;
+                            span.link = Some(url.trim().to_string());
+                            spans.push(span);
+                        }
+
+                        index = url_end + 1;
+                    }
+                    _ => {
+                        current.push(c);
+                        index += 1;
+                    }
+                }
+            }
+            _ => {
+                current.push(c);
+                index += 1;
+            }
+        }
+    }
+
+    flush(&mut current, &mut spans, style);
+    spans
+}
+
+/// Returns the marker and the text of a list item, if `line` starts with a bullet (`-`, `*` or
+/// `+`) or a number followed by a period.
+fn list_marker(line: &str) -> Option<(String, &str)> {
+    for bullet in &["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            return Some((String::from("\u{2022}"), rest));
+        }
+    }
+
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+
+    if digits > 0 && line[digits..].starts_with(". ") {
+        Some((String::from(&line[..digits + 1]), &line[digits + 2..]))
+    } else {
+        None
+    }
+}
+
+/// Parses a Markdown document into blocks: `#` headings, paragraphs, bulleted and numbered lists,
+/// fenced code blocks, and horizontal rules.
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<String> = vec![];
+    let mut list_item: Option<(String, usize, Vec<String>)> = None;
+    let mut code: Option<Vec<String>> = None;
+
+    fn finish(
+        blocks: &mut Vec<Block>,
+        paragraph: &mut Vec<String>,
+        list_item: &mut Option<(String, usize, Vec<String>)>,
+    ) {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(parse_inline(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+
+        if let Some((marker, depth, lines)) = list_item.take() {
+            blocks.push(Block::ListItem(
+                marker,
+                depth,
+                parse_inline(&lines.join(" ")),
+            ));
+        }
+    }
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if let Some(lines) = code.as_mut() {
+            if trimmed.starts_with("```") {
+                blocks.push(Block::Code(code.take().unwrap()));
+            } else {
+                lines.push(line.replace('\t', "    "));
+            }
+
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+
+        if trimmed.starts_with("```") {
+            finish(&mut blocks, &mut paragraph, &mut list_item);
+            code = Some(vec![]);
+        } else if trimmed.is_empty() {
+            finish(&mut blocks, &mut paragraph, &mut list_item);
+        } else if trimmed.len() >= 3
+            && (trimmed.chars().all(|c| c == '-')
+                || trimmed.chars().all(|c| c == '*')
+                || trimmed.chars().all(|c| c == '_'))
+        {
+            finish(&mut blocks, &mut paragraph, &mut list_item);
+            blocks.push(Block::Rule);
+        } else if trimmed.starts_with('#') {
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+
+            finish(&mut blocks, &mut paragraph, &mut list_item);
+            blocks.push(Block::Heading(
+                level.min(6),
+                parse_inline(trimmed[level..].trim()),
+            ));
+        } else if let Some((marker, rest)) = list_marker(trimmed) {
+            finish(&mut blocks, &mut paragraph, &mut list_item);
+            list_item = Some((marker, indent / 2, vec![String::from(rest)]));
+        } else if let Some((_, _, lines)) = list_item.as_mut() {
+            lines.push(String::from(trimmed));
+        } else {
+            paragraph.push(String::from(trimmed));
+        }
+    }
+
+    finish(&mut blocks, &mut paragraph, &mut list_item);
+
+    if let Some(lines) = code {
+        blocks.push(Block::Code(lines));
+    }
+
+    blocks
+}
+
+/// Returns the font used for `style` within a block of text of `size` pixels.
+fn font_key(size: u16, style: SpanStyle, link: bool) -> FontKey {
+    FontKey {
+        size,
+        bold: style.bold,
+        italic: style.italic,
+        underline: link,
+    }
+}
+
+/// Returns the font size of a block: headings are larger than the body text.
+fn block_font_size(block: &Block, base_size: u16) -> u16 {
+    let scale = match block {
+        Block::Heading(1, _) => 2.0,
+        Block::Heading(2, _) => 1.6,
+        Block::Heading(3, _) => 1.3,
+        Block::Heading(_, _) => 1.1,
+        _ => 1.0,
+    };
+
+    (base_size as f64 * scale).round() as u16
+}
+
+/// Lays out `spans` between the `left` and `right` pixel columns of the document, wrapping at
+/// spaces, starting at `y`.  Returns the `y` just below the last line.
+fn layout_spans(
+    layout: &mut DocumentLayout,
+    fonts: &HashMap<FontKey, Font>,
+    spans: &[Span],
+    size: u16,
+    heading: bool,
+    (left, right): (i32, i32),
+    y: i32,
+) -> i32 {
+    let line_height = fonts[&font_key(size, SpanStyle::default(), false)]
+        .recommended_line_spacing()
+        .max(1);
+    let mut x = left;
+    let mut y = y;
+
+    for span in spans {
+        let style = SpanStyle {
+            bold: span.style.bold || heading,
+            ..span.style
+        };
+        let key = font_key(size, style, span.link.is_some());
+        let font = &fonts[&key];
+        let mut words: Vec<String> = vec![];
+
+        // Spaces are kept with the word that follows them, so that they are dropped when that word
+        // starts a new line.
+        for (index, word) in span.text.split(' ').enumerate() {
+            words.push(if index == 0 {
+                String::from(word)
+            } else {
+                format!(" {}", word)
+            });
+        }
+
+        for word in words {
+            let width = font.size_of(&word).map(|(w, _)| w as i32).unwrap_or(0);
+            let word = if x + width > right && x > left {
+                x = left;
+                y += line_height;
+                String::from(word.trim_start())
+            } else {
+                word
+            };
+
+            if word.is_empty() {
+                continue;
+            }
+
+            let (width, height) = font.size_of(&word).unwrap_or((0, 0));
+
+            // Consecutive words of the same style on the same line are joined into a single run.
+            // Each run of a link has its own area, so that links wrapped over several lines can
+            // only be clicked on their text.
+            let links = &mut layout.links;
+
+            match layout.runs.last_mut() {
+                Some(run)
+                    if run.y == y
+                        && run.font == key
+                        && run.code == style.code
+                        && run.x + run.width as i32 == x
+                        && run.link.map(|link| links[link].1.as_str()) == span.link.as_deref() =>
+                {
+                    run.text.push_str(&word);
+                    run.width += width;
+
+                    if let Some(link) = run.link {
+                        links[link].0.set_width(run.width);
+                    }
+                }
+                _ => {
+                    let link = span.link.as_ref().map(|url| {
+                        links.push((Rect::new(x, y, width, height), url.clone()));
+                        links.len() - 1
+                    });
+
+                    layout.runs.push(Run {
+                        x,
+                        y,
+                        width,
+                        height,
+                        text: word,
+                        font: key,
+                        link,
+                        code: style.code,
+                    });
+                }
+            }
+
+            x += width as i32;
+        }
+    }
+
+    y + line_height
+}
+
+/// Lays out the `blocks` of a document to fit within `width` pixels.
+fn layout_document(
+    blocks: &[Block],
+    fonts: &HashMap<FontKey, Font>,
+    base_size: u16,
+    width: i32,
+) -> DocumentLayout {
+    let mut layout = DocumentLayout::default();
+    let left = DOCUMENT_PADDING;
+    let right = width - DOCUMENT_PADDING - SCROLL_INDICATOR_WIDTH as i32;
+    let base_line_height = fonts[&font_key(base_size, SpanStyle::default(), false)]
+        .recommended_line_spacing()
+        .max(1);
+    let block_spacing = base_line_height / 2;
+    let mut y = DOCUMENT_PADDING;
+
+    for block in blocks {
+        let size = block_font_size(block, base_size);
+
+        y = match block {
+            Block::Heading(_, spans) => {
+                layout_spans(&mut layout, fonts, spans, size, true, (left, right), y)
+            }
+            Block::Paragraph(spans) => {
+                layout_spans(&mut layout, fonts, spans, size, false, (left, right), y)
+            }
+            Block::ListItem(marker, depth, spans) => {
+                let indent = left + LIST_INDENT * (*depth as i32 + 1);
+                let marker_key = font_key(size, SpanStyle::default(), false);
+                let (marker_width, marker_height) =
+                    fonts[&marker_key].size_of(marker).unwrap_or((0, 0));
+
+                layout.runs.push(Run {
+                    x: indent - marker_width as i32 - 6,
+                    y,
+                    width: marker_width,
+                    height: marker_height,
+                    text: marker.clone(),
+                    font: marker_key,
+                    link: None,
+                    code: false,
+                });
+
+                layout_spans(&mut layout, fonts, spans, size, false, (indent, right), y)
+            }
+            Block::Code(lines) => {
+                let key = font_key(size, SpanStyle::default(), false);
+                let top = y;
+
+                y += CODE_PADDING;
+
+                for line in lines {
+                    if !line.trim().is_empty() {
+                        let (width, height) = fonts[&key].size_of(line).unwrap_or((0, 0));
+
+                        layout.runs.push(Run {
+                            x: left + CODE_PADDING,
+                            y,
+                            width,
+                            height,
+                            text: line.clone(),
+                            font: key,
+                            link: None,
+                            code: false,
+                        });
+                    }
+
+                    y += base_line_height;
+                }
+
+                y += CODE_PADDING;
+                layout.code_blocks.push(Rect::new(
+                    left,
+                    top,
+                    (right - left).max(1) as u32,
+                    (y - top) as u32,
+                ));
+                y
+            }
+            Block::Rule => {
+                layout.rules.push(y + block_spacing / 2);
+                y + block_spacing
+            }
+        } + block_spacing;
+    }
+
+    layout.height = y + DOCUMENT_PADDING;
+    layout
+}
+
+/// Returns every font needed to lay out `blocks`, so that they can be loaded before layout.
+fn fonts_needed(blocks: &[Block], base_size: u16) -> Vec<FontKey> {
+    let mut keys = vec![font_key(base_size, SpanStyle::default(), false)];
+
+    for block in blocks {
+        let size = block_font_size(block, base_size);
+        let heading = matches!(block, Block::Heading(_, _));
+
+        keys.push(font_key(size, SpanStyle::default(), false));
+
+        if let Block::Heading(_, spans) | Block::Paragraph(spans) | Block::ListItem(_, _, spans) =
+            block
+        {
+            for span in spans {
+                let style = SpanStyle {
+                    bold: span.style.bold || heading,
+                    ..span.style
+                };
+
+                keys.push(font_key(size, style, span.link.is_some()));
+            }
+        }
+    }
+
+    keys
+}
+
+/// This is the storage object for the `MarkdownViewerWidget`.  It stores the config, properties,
+/// callback registry, the document and its layout, the scroll position, and the state of the
+/// mouse.
+pub struct MarkdownViewerWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    markdown: String,
+    blocks: Vec<Block>,
+    layout: DocumentLayout,
+    layout_width: i32,
+    layout_font_size: i32,
+    needs_layout: bool,
+    scroll_y: i32,
+    line_height: i32,
+    hovered_link: Option<usize>,
+    pressed_link: Option<usize>,
+    open_in_browser: bool,
+    on_link_clicked: OnLinkClickedCallbackType,
+}
+
+/// This is the implementation of the `MarkdownViewerWidget`, which draws a subset of Markdown:
+/// `#` headings, paragraphs, **bold** and *italic* text, `` `code` `` spans and fenced code blocks,
+/// bulleted and numbered lists, horizontal rules, and `[links](url)`.  Text is wrapped to the width
+/// of this `Widget`, and long documents are scrolled with the mouse wheel.  Text is drawn in
+/// `CONFIG_COLOR_TEXT` with the size in `CONFIG_FONT_SIZE`, or `14` if it is not set.  Links are
+/// underlined in `CONFIG_COLOR_SECONDARY`, and code is drawn over `CONFIG_COLOR_TRACK`.  Clicking a
+/// link triggers the `on_link_clicked` callback, and optionally opens the URL in the system browser.
+impl MarkdownViewerWidget {
+    /// Creates a new `MarkdownViewerWidget` given the `x, y, w, h` coordinates, and the Markdown
+    /// document to show.
+    pub fn new(points: Points, size: Size, markdown: &str) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            markdown: String::from(markdown),
+            blocks: parse_blocks(markdown),
+            layout: DocumentLayout::default(),
+            layout_width: 0,
+            layout_font_size: 0,
+            needs_layout: true,
+            scroll_y: 0,
+            line_height: 20,
+            hovered_link: None,
+            pressed_link: None,
+            open_in_browser: false,
+            on_link_clicked: None,
+        }
+    }
+
+    /// Replaces the Markdown document, scrolling back to the top.
+    pub fn set_markdown(&mut self, markdown: &str) {
+        self.markdown = String::from(markdown);
+        self.blocks = parse_blocks(markdown);
+        self.needs_layout = true;
+        self.scroll_y = 0;
+        self.hovered_link = None;
+        self.pressed_link = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the Markdown document.
+    pub fn get_markdown(&self) -> &str {
+        &self.markdown
+    }
+
+    /// Scrolls the document so that the pixel row `offset` is at the top, clamped to the document.
+    pub fn set_scroll_offset(&mut self, offset: i32) {
+        self.scroll_y = offset;
+        self.clamp_scroll();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the pixel row of the document shown at the top of this `Widget`.
+    pub fn get_scroll_offset(&self) -> i32 {
+        self.scroll_y
+    }
+
+    /// Retrieves the height of the document in pixels.  This is `0` until this `Widget` has been
+    /// drawn for the first time.
+    pub fn get_content_height(&self) -> i32 {
+        self.layout.height
+    }
+
+    /// Sets whether or not clicking a link opens its URL in the system browser, using
+    /// `pushrod::render::engine::open_url`.  This is off by default, so links only trigger the
+    /// `on_link_clicked` callback.
+    pub fn set_open_in_browser(&mut self, open_in_browser: bool) {
+        self.open_in_browser = open_in_browser;
+    }
+
+    /// Indicates whether or not clicking a link opens its URL in the system browser.
+    pub fn get_open_in_browser(&self) -> bool {
+        self.open_in_browser
+    }
+
+    /// Assigns the callback closure that will be used when a link is clicked.
+    pub fn on_link_clicked<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut MarkdownViewerWidget, &[WidgetContainer], &[LayoutContainer], String)
+            + 'static,
+    {
+        self.on_link_clicked = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_link_clicked` callback, then opens the URL if
+    /// requested.
+    fn activate(&mut self, url: String, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if let Some(mut cb) = self.on_link_clicked.take() {
+            cb(self, widgets, layouts, url.clone());
+            self.on_link_clicked = Some(cb);
+        }
+
+        if self.open_in_browser {
+            // There is nothing useful the link can do if no browser could be started.
+            let _ = open_url(&url);
+        }
+    }
+
+    /// Keeps the scroll position within the document.
+    fn clamp_scroll(&mut self) {
+        let height = self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] as i32;
+
+        self.scroll_y = self.scroll_y.min(self.layout.height - height).max(0);
+    }
+
+    /// Returns the index of the link under `points`, if any.
+    fn link_at(&mut self, points: &[i32]) -> Option<usize> {
+        let x = points[POINT_X] - self.get_config().to_x(0);
+        let y = points[POINT_Y] - self.get_config().to_y(0) + self.scroll_y;
+
+        self.layout
+            .links
+            .iter()
+            .position(|(rect, _)| rect.contains_point(Point::new(x, y)))
+    }
+}
+
+/// This is the `Widget` implementation of the `MarkdownViewerWidget`.
+impl Widget for MarkdownViewerWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let link_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(0, 0, 238));
+            let code_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(240, 240, 240));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 14).max(1);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let mut fonts: HashMap<FontKey, Font> = HashMap::new();
+
+            for key in fonts_needed(&self.blocks, font_size as u16) {
+                fonts.entry(key).or_insert_with(|| {
+                    let mut font = ttf_context
+                        .load_font(Path::new(MARKDOWN_FONT), key.size)
+                        .unwrap();
+                    let mut style = FontStyle::NORMAL;
+
+                    if key.bold {
+                        style |= FontStyle::BOLD;
+                    }
+
+                    if key.italic {
+                        style |= FontStyle::ITALIC;
+                    }
+
+                    if key.underline {
+                        style |= FontStyle::UNDERLINE;
+                    }
+
+                    font.set_style(style);
+                    font
+                });
+            }
+
+            // The document is only laid out again when its text, width or font size changes.
+            if self.needs_layout
+                || self.layout_width != bounds[SIZE_WIDTH] as i32
+                || self.layout_font_size != font_size
+            {
+                self.layout = layout_document(
+                    &self.blocks,
+                    &fonts,
+                    font_size as u16,
+                    bounds[SIZE_WIDTH] as i32,
+                );
+                self.layout_width = bounds[SIZE_WIDTH] as i32;
+                self.layout_font_size = font_size;
+                self.line_height = fonts[&font_key(font_size as u16, SpanStyle::default(), false)]
+                    .recommended_line_spacing()
+                    .max(1);
+                self.needs_layout = false;
+            }
+
+            self.clamp_scroll();
+
+            let scroll_y = self.scroll_y;
+            let view_height = bounds[SIZE_HEIGHT] as i32;
+            let visible = |y: i32, height: i32| y + height > scroll_y && y < scroll_y + view_height;
+            let run_textures: Vec<(Rect, bool, Texture)> = self
+                .layout
+                .runs
+                .iter()
+                .filter(|run| visible(run.y, run.height as i32))
+                .filter_map(|run| {
+                    let color = if run.link.is_some() {
+                        link_color
+                    } else {
+                        text_color
+                    };
+                    let surface = fonts[&run.font].render(&run.text).blended(color).ok()?;
+                    let texture = texture_creator.create_texture_from_surface(&surface).ok()?;
+                    let TextureQuery { width, height, .. } = texture.query();
+
+                    Some((
+                        Rect::new(run.x, run.y - scroll_y, width, height),
+                        run.code,
+                        texture,
+                    ))
+                })
+                .collect();
+            let code_blocks: Vec<Rect> = self
+                .layout
+                .code_blocks
+                .iter()
+                .filter(|rect| visible(rect.y(), rect.height() as i32))
+                .map(|rect| Rect::new(rect.x(), rect.y() - scroll_y, rect.width(), rect.height()))
+                .collect();
+            let rules: Vec<i32> = self
+                .layout
+                .rules
+                .iter()
+                .map(|y| y - scroll_y)
+                .filter(|y| *y >= 0 && *y < view_height)
+                .collect();
+            let document_height = self.layout.height.max(1);
+            let scroll_indicator = if document_height > view_height {
+                let track = view_height as f64;
+
+                Some(Rect::new(
+                    (bounds[SIZE_WIDTH] - SCROLL_INDICATOR_WIDTH) as i32,
+                    (scroll_y as f64 / document_height as f64 * track) as i32,
+                    SCROLL_INDICATOR_WIDTH,
+                    ((view_height as f64 / document_height as f64 * track) as u32).max(1),
+                ))
+            } else {
+                None
+            };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(code_color);
+
+                for rect in &code_blocks {
+                    texture.fill_rect(*rect).unwrap();
+                }
+
+                for (rect, code, _) in &run_textures {
+                    if *code {
+                        texture
+                            .fill_rect(Rect::new(
+                                rect.x() - 2,
+                                rect.y(),
+                                rect.width() + 4,
+                                rect.height(),
+                            ))
+                            .unwrap();
+                    }
+                }
+
+                for (rect, _, run_texture) in &run_textures {
+                    texture.copy(run_texture, None, *rect).unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+
+                for y in &rules {
+                    texture
+                        .draw_line(
+                            Point::new(DOCUMENT_PADDING, *y),
+                            Point::new(
+                                bounds[SIZE_WIDTH] as i32
+                                    - DOCUMENT_PADDING
+                                    - SCROLL_INDICATOR_WIDTH as i32,
+                                *y,
+                            ),
+                        )
+                        .unwrap();
+                }
+
+                if let Some(indicator) = scroll_indicator {
+                    texture.fill_rect(indicator).unwrap();
+                }
+            })
+            .unwrap();
+
+            // Safety: the run textures are only used within this function.
+            for (_, _, run_texture) in run_textures {
+                unsafe {
+                    run_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Shows a pointing hand while the mouse is over a link.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let hovered_link = self.link_at(&points);
+
+        if hovered_link != self.hovered_link {
+            let cursor = if hovered_link.is_some() {
+                MouseCursor::Hand
+            } else {
+                MouseCursor::Arrow
+            };
+
+            self.hovered_link = hovered_link;
+            self.get_config()
+                .set_numeric(CONFIG_CURSOR, cursor.to_numeric());
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Restores the mouse cursor when the mouse leaves this `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hovered_link = None;
+        self.pressed_link = None;
+        self.get_config()
+            .set_numeric(CONFIG_CURSOR, MouseCursor::Arrow.to_numeric());
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Scrolls the document by a few lines for each step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let scroll_y = self.scroll_y;
+
+        self.scroll_y -= points[POINT_Y] * self.line_height * LINES_PER_SCROLL_STEP;
+        self.clamp_scroll();
+
+        if scroll_y != self.scroll_y {
+            self.hovered_link = None;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Activates a link when the mouse button is pressed and released over it.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 {
+            if state {
+                self.pressed_link = self.hovered_link;
+            } else if let Some(link) = self.pressed_link.take() {
+                if self.hovered_link == Some(link) {
+                    let url = self.layout.links[link].1.clone();
+
+                    self.activate(url, _widgets, _layouts);
+                }
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Redraws this `Widget` when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_TEXT
+            | CONFIG_COLOR_SECONDARY
+            | CONFIG_COLOR_TRACK
+            | CONFIG_COLOR_BORDER
+            | CONFIG_FONT_SIZE => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
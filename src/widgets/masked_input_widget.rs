@@ -0,0 +1,593 @@
+// Pushrod Widget Library
+// Masked Input Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock;
+use crate::render::input_settings::CaretBlink;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::{render_text_line, TextureCache};
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between the left edge of the `MaskedInputWidget` and its text.
+const TEXT_PADDING: i32 = 6;
+
+/// This is one position in the mask of a `MaskedInputWidget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaskSlot {
+    /// A digit, written as `#` in the mask.
+    Digit,
+
+    /// A letter, written as `A` in the mask.
+    Letter,
+
+    /// A letter or a digit, written as `*` in the mask.
+    AlphaNumeric,
+
+    /// A character that is inserted automatically.  Any other character in the mask is a literal,
+    /// and `\` makes the character after it a literal, so `\#` is a literal `#`.
+    Literal(char),
+}
+
+/// Implementation of the `MaskSlot`.
+impl MaskSlot {
+    /// Parses a mask into its slots.
+    pub fn parse(mask: &str) -> Vec<MaskSlot> {
+        let mut slots = vec![];
+        let mut chars = mask.chars();
+
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                '#' => MaskSlot::Digit,
+                'A' => MaskSlot::Letter,
+                '*' => MaskSlot::AlphaNumeric,
+                '\\' => match chars.next() {
+                    Some(escaped) => MaskSlot::Literal(escaped),
+                    None => MaskSlot::Literal('\\'),
+                },
+                _ => MaskSlot::Literal(c),
+            });
+        }
+
+        slots
+    }
+
+    /// Indicates whether `c` can be entered in this slot.  Nothing can be entered in a literal.
+    pub fn accepts(self, c: char) -> bool {
+        match self {
+            MaskSlot::Digit => c.is_ascii_digit(),
+            MaskSlot::Letter => c.is_alphabetic(),
+            MaskSlot::AlphaNumeric => c.is_alphanumeric(),
+            MaskSlot::Literal(_) => false,
+        }
+    }
+}
+
+/// This is the type of the function that validates the raw value of a complete `MaskedInputWidget`.
+pub type MaskValidatorType = Option<Box<dyn Fn(&str) -> bool>>;
+
+/// This is the callback type that is used when an `on_value_changed` callback is triggered from
+/// this `Widget`.  It receives the raw value, without literals, and whether the value is valid.
+pub type OnMaskedValueChangedCallbackType = Option<
+    Box<dyn FnMut(&mut MaskedInputWidget, &[WidgetContainer], &[LayoutContainer], String, bool)>,
+>;
+
+/// This is the storage object for the `MaskedInputWidget`.  It stores the config, properties,
+/// callback registry, the font name and size, the mask, the characters entered, the cursor, and
+/// the validator.
+pub struct MaskedInputWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    mask: Vec<MaskSlot>,
+    raw: Vec<char>,
+    cursor: usize,
+    placeholder: char,
+    offsets: Vec<u32>,
+    validator: MaskValidatorType,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_value_changed: OnMaskedValueChangedCallbackType,
+}
+
+/// This is the implementation of the `MaskedInputWidget`, a single-line text field for values with
+/// a fixed format, such as phone numbers or dates.  The format is given as a mask, like
+/// `(###) ###-####` or `##/##/####`, in which `#` accepts a digit, `A` a letter, and `*` either
+/// (see `MaskSlot`).  Only characters accepted by the next position can be typed, and the literals
+/// of the mask are inserted automatically, so the user only types the characters of the raw value.
+///
+/// The whole mask is shown, with the positions that have not been entered drawn as placeholders in
+/// `CONFIG_COLOR_SECONDARY`.  The value is valid once every position has been entered, and the
+/// validator, if one is set, accepts the raw value.  The `on_value_changed` callback is triggered
+/// with the raw value and its validity whenever the user changes the value.
+impl MaskedInputWidget {
+    /// Creates a new `MaskedInputWidget`, given the path to the font file, the size of the font in
+    /// pixels, the mask, and the `x, y, w, h` coordinates.
+    pub fn new(font_name: String, font_size: i32, mask: &str, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            mask: MaskSlot::parse(mask),
+            raw: vec![],
+            cursor: 0,
+            placeholder: '_',
+            offsets: vec![0],
+            validator: None,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_value_changed: None,
+        }
+    }
+
+    /// Replaces the mask, clearing the value.
+    pub fn set_mask(&mut self, mask: &str) {
+        self.mask = MaskSlot::parse(mask);
+        self.raw.clear();
+        self.cursor = 0;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the slots of the mask.
+    pub fn get_mask(&self) -> &[MaskSlot] {
+        &self.mask
+    }
+
+    /// Sets the character drawn in positions that have not been entered.  This is `_` by default.
+    pub fn set_placeholder(&mut self, placeholder: char) {
+        self.placeholder = placeholder;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the function that validates the raw value once every position has been entered, such
+    /// as checking that a date exists.
+    pub fn set_validator<F>(&mut self, validator: F)
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.validator = Some(Box::new(validator));
+    }
+
+    /// Replaces the value with the characters of `raw` that fit the mask, in order, skipping any
+    /// that do not, and moving the cursor to the end.  Literals may be included, and are skipped.
+    /// This does not trigger the `on_value_changed` callback.
+    pub fn set_raw_value(&mut self, raw: &str) {
+        self.raw.clear();
+
+        for c in raw.chars() {
+            if let Some(slot) = self.input_slots().get(self.raw.len()) {
+                if slot.accepts(c) {
+                    self.raw.push(c);
+                }
+            }
+        }
+
+        self.cursor = self.raw.len();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the raw value: the characters entered, without the literals of the mask.
+    pub fn get_raw_value(&self) -> String {
+        self.raw.iter().collect()
+    }
+
+    /// Retrieves the value formatted by the mask, up to the last character entered, followed by any
+    /// literals that come right after it.
+    pub fn get_formatted_value(&self) -> String {
+        self.display_text()[..self.filled_length()].iter().collect()
+    }
+
+    /// Indicates whether every position of the mask has been entered.
+    pub fn is_complete(&self) -> bool {
+        self.raw.len() == self.input_slots().len()
+    }
+
+    /// Indicates whether the value is complete, and accepted by the validator, if one is set.
+    pub fn is_valid(&self) -> bool {
+        self.is_complete()
+            && self
+                .validator
+                .as_ref()
+                .is_none_or(|validator| validator(&self.get_raw_value()))
+    }
+
+    /// Assigns the callback closure that will be used when the user changes the value.
+    pub fn on_value_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut MaskedInputWidget, &[WidgetContainer], &[LayoutContainer], String, bool)
+            + 'static,
+    {
+        self.on_value_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_value_changed` callback.
+    fn call_value_changed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_value_changed.take() {
+            let valid = self.is_valid();
+
+            cb(self, widgets, layouts, self.get_raw_value(), valid);
+            self.on_value_changed = Some(cb);
+        }
+    }
+
+    /// Returns the slots of the mask that characters are entered in, leaving out the literals.
+    fn input_slots(&self) -> Vec<MaskSlot> {
+        self.mask
+            .iter()
+            .copied()
+            .filter(|slot| !matches!(slot, MaskSlot::Literal(_)))
+            .collect()
+    }
+
+    /// Returns the whole mask as shown, with the characters entered, and placeholders for the rest.
+    fn display_text(&self) -> Vec<char> {
+        let mut entered = self.raw.iter();
+
+        self.mask
+            .iter()
+            .map(|slot| match slot {
+                MaskSlot::Literal(c) => *c,
+                _ => *entered.next().unwrap_or(&self.placeholder),
+            })
+            .collect()
+    }
+
+    /// Returns the number of characters of the displayed text before the input slot at `index`, or
+    /// the length of the mask if there is no such slot.
+    fn display_position(&self, index: usize) -> usize {
+        self.mask
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| !matches!(slot, MaskSlot::Literal(_)))
+            .nth(index)
+            .map_or(self.mask.len(), |(position, _)| position)
+    }
+
+    /// Returns the number of characters of the displayed text that are entered, or are literals
+    /// inserted before the next character to enter.
+    fn filled_length(&self) -> usize {
+        self.display_position(self.raw.len())
+    }
+
+    /// Inserts `c` at the cursor, returning whether it was inserted.  Characters after the cursor
+    /// move along by one position, so `c` is not inserted if the mask is full, or if any of them
+    /// would no longer fit their new position.
+    fn insert_char(&mut self, c: char) -> bool {
+        let slots = self.input_slots();
+
+        if self.raw.len() >= slots.len() {
+            return false;
+        }
+
+        let mut raw = self.raw.clone();
+
+        raw.insert(self.cursor, c);
+
+        if raw
+            .iter()
+            .zip(slots.iter())
+            .all(|(c, slot)| slot.accepts(*c))
+        {
+            self.raw = raw;
+            self.cursor += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes the character at `index`.  Characters after it move back by one position, and are
+    /// removed from the first one that no longer fits its new position.
+    fn remove_char(&mut self, index: usize) {
+        let slots = self.input_slots();
+
+        self.raw.remove(index);
+
+        if let Some(misfit) = self
+            .raw
+            .iter()
+            .zip(slots.iter())
+            .position(|(c, slot)| !slot.accepts(*c))
+        {
+            self.raw.truncate(misfit);
+        }
+
+        self.cursor = self.cursor.min(self.raw.len());
+    }
+
+    /// Handles a key press, returning whether the value and the cursor changed.
+    fn handle_edit_key(&mut self, keycode: Keycode) -> (bool, bool) {
+        let cursor = self.cursor;
+        let mut value_changed = false;
+
+        match keycode {
+            Keycode::Left => self.cursor = self.cursor.saturating_sub(1),
+            Keycode::Right => self.cursor = (self.cursor + 1).min(self.raw.len()),
+            Keycode::Home => self.cursor = 0,
+            Keycode::End => self.cursor = self.raw.len(),
+            Keycode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.remove_char(self.cursor);
+                value_changed = true;
+            }
+            Keycode::Delete if self.cursor < self.raw.len() => {
+                self.remove_char(self.cursor);
+                value_changed = true;
+            }
+            _ => (),
+        }
+
+        (value_changed, value_changed || cursor != self.cursor)
+    }
+
+    /// Moves the cursor to the entered character closest to the last known mouse position.
+    fn move_cursor_to_mouse(&mut self) {
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(TEXT_PADDING);
+        let position = self
+            .offsets
+            .windows(2)
+            .position(|pair| x < ((pair[0] + pair[1]) / 2) as i32)
+            .unwrap_or(self.offsets.len() - 1);
+
+        self.cursor = (0..=self.raw.len())
+            .find(|index| self.display_position(*index) >= position)
+            .unwrap_or(self.raw.len());
+    }
+}
+
+/// This is the `Widget` implementation of the `MaskedInputWidget`.
+impl Widget for MaskedInputWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let placeholder_color = self.get_color(CONFIG_COLOR_SECONDARY);
+            let display = self.display_text();
+            let filled_length = self.filled_length();
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+            let line_height = font.recommended_line_spacing().max(1);
+
+            self.offsets = (0..=display.len())
+                .map(|end| {
+                    let prefix: String = display[..end].iter().collect();
+
+                    font.size_of(&prefix).map(|(w, _)| w).unwrap_or(0)
+                })
+                .collect();
+
+            let text_y = (bounds[SIZE_HEIGHT] as i32 - line_height) / 2;
+            let max_width = (bounds[SIZE_WIDTH] as i32 - TEXT_PADDING * 2).max(1);
+            let caret = if self.focused && self.caret.is_shown() {
+                Some(TEXT_PADDING + self.offsets[self.display_position(self.cursor)] as i32)
+            } else {
+                None
+            };
+
+            // The entered part and the rest of the mask are drawn separately, as they differ in
+            // color.
+            let filled_x = TEXT_PADDING + self.offsets[filled_length] as i32;
+            let filled: String = display[..filled_length].iter().collect();
+            let remaining: String = display[filled_length..].iter().collect();
+            let filled_texture = render_text_line(&font, &texture_creator, &filled, text_color);
+            let remaining_texture =
+                render_text_line(&font, &texture_creator, &remaining, placeholder_color);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (font_texture, x) in &[
+                    (&filled_texture, TEXT_PADDING),
+                    (&remaining_texture, filled_x),
+                ] {
+                    if let Some(font_texture) = font_texture {
+                        let TextureQuery { width, height, .. } = font_texture.query();
+                        let visible_width = (width as i32).min(TEXT_PADDING + max_width - x);
+
+                        if visible_width > 0 {
+                            texture
+                                .copy(
+                                    font_texture,
+                                    Rect::new(0, 0, visible_width as u32, height),
+                                    Rect::new(*x, text_y, visible_width as u32, height),
+                                )
+                                .unwrap();
+                        }
+                    }
+                }
+
+                texture.set_draw_color(text_color);
+
+                if let Some(x) = caret {
+                    texture
+                        .fill_rect(Rect::new(x, text_y, 1, line_height as u32))
+                        .unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the font textures are only used within this function.
+            for font_texture in vec![filled_texture, remaining_texture]
+                .into_iter()
+                .flatten()
+            {
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while this `Widget` has focus.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Tracks the mouse position, so that clicks can move the cursor.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Moves the cursor to the clicked position.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            self.move_cursor_to_mouse();
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles text input and cursor navigation while this `Widget` has focus.  Characters that do
+    /// not fit the mask at the cursor are ignored.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let (value_changed, cursor_moved) = match event {
+            Event::TextInput { text, .. } => {
+                let mut inserted = false;
+
+                for c in text.chars() {
+                    inserted |= self.insert_char(c);
+                }
+
+                (inserted, inserted)
+            }
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } => self.handle_edit_key(keycode),
+            _ => (false, false),
+        };
+
+        if cursor_moved {
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        if value_changed {
+            self.call_value_changed_callback(_widgets, _layouts);
+        }
+    }
+
+    /// Monitors for changes in the text, colors, or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _)
+            | (CONFIG_COLOR_BASE, _)
+            | (CONFIG_COLOR_BORDER, _)
+            | (CONFIG_COLOR_SECONDARY, _) => self.get_config().set_invalidated(true),
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            (CONFIG_TEXT, Config::Text(text)) => self.set_raw_value(&text),
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus.
+    fn accepts_text_input(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
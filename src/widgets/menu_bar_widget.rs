@@ -0,0 +1,931 @@
+// Pushrod Widget Library
+// Menu Bar Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::Font;
+use sdl2::video::Window;
+
+use crate::render::texture_cache::{render_text_line, TextureCache};
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels on either side of the title of a menu in the menu bar.
+const TITLE_PADDING: u32 = 10;
+
+/// This is the height of a selectable item, or a submenu, in a pull-down menu.
+const ITEM_HEIGHT: u32 = 24;
+
+/// This is the height of a separator in a pull-down menu.
+const SEPARATOR_HEIGHT: u32 = 9;
+
+/// This is the number of pixels on either side of the label of a menu item.
+const ITEM_PADDING: u32 = 16;
+
+/// This is the width of the area on the right-hand side of a pull-down menu that contains the
+/// submenu arrows.
+const ARROW_AREA_WIDTH: u32 = 16;
+
+/// This is the minimum width of a pull-down menu.
+const MIN_MENU_WIDTH: u32 = 120;
+
+/// This is the callback type that is used when an `on_menu_item_selected` callback is triggered from
+/// this `Widget`.  It receives the menu ID of the selected `MenuItem`.
+pub type OnMenuItemSelectedCallbackType =
+    Option<Box<dyn FnMut(&mut MenuBarWidget, &[WidgetContainer], &[LayoutContainer], i32)>>;
+
+/// This is an entry in a pull-down menu of a `MenuBarWidget`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MenuItem {
+    /// An item that can be selected, triggering the `on_menu_item_selected` callback with its menu
+    /// ID.  Disabled items are drawn, but cannot be selected.
+    Item {
+        label: String,
+        menu_id: i32,
+        enabled: bool,
+    },
+
+    /// An item that opens a nested menu when highlighted.
+    Submenu { label: String, items: Vec<MenuItem> },
+
+    /// A horizontal line separating groups of items.
+    Separator,
+}
+
+/// This is the implementation of the `MenuItem`.
+impl MenuItem {
+    /// Creates a selectable `MenuItem`, given its label and menu ID.
+    pub fn item(label: &str, menu_id: i32) -> Self {
+        MenuItem::Item {
+            label: String::from(label),
+            menu_id,
+            enabled: true,
+        }
+    }
+
+    /// Creates a `MenuItem` that is drawn, but cannot be selected.
+    pub fn disabled(label: &str, menu_id: i32) -> Self {
+        MenuItem::Item {
+            label: String::from(label),
+            menu_id,
+            enabled: false,
+        }
+    }
+
+    /// Creates a `MenuItem` that opens a nested menu of `items`.
+    pub fn submenu(label: &str, items: Vec<MenuItem>) -> Self {
+        MenuItem::Submenu {
+            label: String::from(label),
+            items,
+        }
+    }
+
+    /// Creates a separator.
+    pub fn separator() -> Self {
+        MenuItem::Separator
+    }
+
+    /// Retrieves the label of this `MenuItem`, or `None` for a separator.
+    pub fn get_label(&self) -> Option<&str> {
+        match self {
+            MenuItem::Item { label, .. } | MenuItem::Submenu { label, .. } => Some(label),
+            MenuItem::Separator => None,
+        }
+    }
+
+    /// Indicates whether or not this `MenuItem` can be highlighted with the keyboard.
+    fn is_selectable(&self) -> bool {
+        match self {
+            MenuItem::Item { enabled, .. } => *enabled,
+            MenuItem::Submenu { .. } => true,
+            MenuItem::Separator => false,
+        }
+    }
+
+    /// Returns the height of this `MenuItem` in a pull-down menu.
+    fn height(&self) -> u32 {
+        match self {
+            MenuItem::Separator => SEPARATOR_HEIGHT,
+            _ => ITEM_HEIGHT,
+        }
+    }
+}
+
+/// This is a top-level menu in a `MenuBarWidget`: its title, and its items.
+struct Menu {
+    title: String,
+    items: Vec<MenuItem>,
+}
+
+/// This is the storage object for the `MenuBarWidget`.  It stores the config, properties, callback
+/// registry, the menus, their measurements, and the state of the open menus.
+pub struct MenuBarWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    popup_store: TextureStore,
+    menus: Vec<Menu>,
+    title_widths: Vec<u32>,
+    menu_widths: HashMap<Vec<usize>, u32>,
+    measured: bool,
+    open_menu: i32,
+    open_path: Vec<usize>,
+    highlighted_title: i32,
+    highlighted_item: Option<(usize, usize)>,
+    window_size: (u32, u32),
+    mouse_position: Points,
+    on_menu_item_selected: OnMenuItemSelectedCallbackType,
+}
+
+/// This is the implementation of the `MenuBarWidget`, a desktop-style menu bar that is normally
+/// placed along the top of the window.  Clicking on the title of a menu opens it as a pull-down
+/// menu, drawn as a popup above all other `Widget`s.  Submenus open when they are highlighted, to
+/// the right of their parent menu, or to the left if there is no room.
+impl MenuBarWidget {
+    /// Creates a new `MenuBarWidget` given the `x, y, w, h` coordinates.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+        let mut popup_store = TextureStore::default();
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+        popup_store.set_blended(true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            popup_store,
+            menus: vec![],
+            title_widths: vec![],
+            menu_widths: HashMap::new(),
+            measured: false,
+            open_menu: -1,
+            open_path: vec![],
+            highlighted_title: -1,
+            highlighted_item: None,
+            window_size: (0, 0),
+            mouse_position: vec![0, 0],
+            on_menu_item_selected: None,
+        }
+    }
+
+    /// Adds a menu to the right-hand side of the menu bar, given its title and items.  Returns the
+    /// index of the menu.
+    pub fn add_menu(&mut self, title: &str, items: Vec<MenuItem>) -> usize {
+        self.menus.push(Menu {
+            title: String::from(title),
+            items,
+        });
+        self.measured = false;
+        self.get_config().set_invalidated(true);
+
+        self.menus.len() - 1
+    }
+
+    /// Retrieves the number of menus in the menu bar.
+    pub fn get_menu_count(&self) -> usize {
+        self.menus.len()
+    }
+
+    /// Retrieves the items of a menu.
+    pub fn get_menu_items(&self, menu: usize) -> Option<&[MenuItem]> {
+        self.menus.get(menu).map(|x| x.items.as_slice())
+    }
+
+    /// Enables or disables every `MenuItem` with the given menu ID, in all menus and submenus.
+    pub fn set_item_enabled(&mut self, menu_id: i32, enabled: bool) {
+        fn update(items: &mut [MenuItem], id: i32, state: bool) {
+            for item in items.iter_mut() {
+                match item {
+                    MenuItem::Item {
+                        menu_id, enabled, ..
+                    } if *menu_id == id => *enabled = state,
+                    MenuItem::Submenu { items, .. } => update(items, id, state),
+                    _ => (),
+                }
+            }
+        }
+
+        for menu in self.menus.iter_mut() {
+            update(&mut menu.items, menu_id, enabled);
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the index of the open menu, or `-1` if no menu is open.
+    pub fn get_open_menu(&self) -> i32 {
+        self.open_menu
+    }
+
+    /// Assigns the callback closure that will be used when the user selects a `MenuItem`.
+    pub fn on_menu_item_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut MenuBarWidget, &[WidgetContainer], &[LayoutContainer], i32) + 'static,
+    {
+        self.on_menu_item_selected = Some(Box::new(callback));
+    }
+
+    /// Opens a menu, closing any other open menu.
+    fn open(&mut self, menu: usize) {
+        self.open_menu = menu as i32;
+        self.open_path.clear();
+        self.highlighted_item = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Closes the open menu, along with its submenus.
+    fn close(&mut self) {
+        self.close_popup();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Returns the items of the open menu at `depth`, where `0` is the top-level menu, and each
+    /// further level is an open submenu.
+    fn menu_items(&self, depth: usize) -> &[MenuItem] {
+        let mut items = self.menus[self.open_menu as usize].items.as_slice();
+
+        for index in &self.open_path[..depth] {
+            if let MenuItem::Submenu {
+                items: children, ..
+            } = &items[*index]
+            {
+                items = children;
+            }
+        }
+
+        items
+    }
+
+    /// Returns the bounds of each open menu, in window coordinates, starting with the top-level
+    /// menu.
+    fn menu_bounds(&self) -> Vec<Rect> {
+        if self.open_menu < 0 || self.open_menu as usize >= self.title_widths.len() {
+            return vec![];
+        }
+
+        let (window_width, window_height) = self.window_size;
+        let mut key = vec![self.open_menu as usize];
+        let mut bounds: Vec<Rect> = vec![];
+
+        for depth in 0..=self.open_path.len() {
+            let items = self.menu_items(depth);
+            let width = self
+                .menu_widths
+                .get(&key)
+                .copied()
+                .unwrap_or(MIN_MENU_WIDTH);
+            let height = items.iter().map(|x| x.height()).sum::<u32>() + 2;
+            let (mut x, mut y) = match bounds.last() {
+                Some(parent) => {
+                    let index = self.open_path[depth - 1];
+                    let parent_items = self.menu_items(depth - 1);
+                    let offset: u32 = parent_items[..index].iter().map(|x| x.height()).sum();
+
+                    (parent.right() - 2, parent.y() + offset as i32)
+                }
+                None => {
+                    let offset: u32 = self.title_widths[..self.open_menu as usize].iter().sum();
+
+                    (
+                        self.config.to_x(offset as i32),
+                        self.config
+                            .to_y(self.config.get_size(CONFIG_SIZE)[SIZE_HEIGHT] as i32),
+                    )
+                }
+            };
+
+            if window_width > 0 && x + width as i32 > window_width as i32 {
+                x = match bounds.last() {
+                    Some(parent) => parent.x() - width as i32 + 2,
+                    None => window_width as i32 - width as i32,
+                }
+                .max(0);
+            }
+
+            if window_height > 0 && y + height as i32 > window_height as i32 {
+                y = (window_height as i32 - height as i32).max(0);
+            }
+
+            bounds.push(Rect::new(x, y, width, height));
+
+            if depth < self.open_path.len() {
+                key.push(self.open_path[depth]);
+            }
+        }
+
+        bounds
+    }
+
+    /// Returns the depth and index of the `MenuItem` at `x, y` in window coordinates, if any.
+    /// Deeper menus are drawn above their parents, so they are checked first.
+    fn item_at(&self, x: i32, y: i32) -> Option<(usize, usize)> {
+        for (depth, bounds) in self.menu_bounds().iter().enumerate().rev() {
+            if bounds.contains_point((x, y)) {
+                let mut top = bounds.y() + 1;
+
+                for (index, item) in self.menu_items(depth).iter().enumerate() {
+                    if y >= top && y < top + item.height() as i32 {
+                        return Some((depth, index));
+                    }
+
+                    top += item.height() as i32;
+                }
+
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Returns the index of the menu whose title is at `x, y` in window coordinates, or `-1` if
+    /// there is none.
+    fn title_at(&self, x: i32, y: i32) -> i32 {
+        let x = x - self.config.to_x(0);
+        let y = y - self.config.to_y(0);
+
+        if y < 0 || y >= self.config.get_size(CONFIG_SIZE)[SIZE_HEIGHT] as i32 {
+            return -1;
+        }
+
+        let mut left = 0;
+
+        for (index, width) in self.title_widths.iter().enumerate() {
+            if x >= left && x < left + *width as i32 {
+                return index as i32;
+            }
+
+            left += *width as i32;
+        }
+
+        -1
+    }
+
+    /// Highlights a `MenuItem` under the mouse, opening it if it is a submenu, and closing any
+    /// submenus that were opened deeper than it.
+    fn hover_item(&mut self, depth: usize, index: usize) {
+        if self.highlighted_item == Some((depth, index)) {
+            return;
+        }
+
+        self.highlighted_item = Some((depth, index));
+        self.open_path.truncate(depth);
+
+        if let MenuItem::Submenu { .. } = self.menu_items(depth)[index] {
+            self.open_path.push(index);
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Activates a `MenuItem`: a submenu is opened, and a selectable item closes the menus and
+    /// triggers the `on_menu_item_selected` callback.
+    fn activate_item(
+        &mut self,
+        depth: usize,
+        index: usize,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        match self.menu_items(depth)[index] {
+            MenuItem::Item {
+                menu_id,
+                enabled: true,
+                ..
+            } => {
+                self.close();
+
+                if let Some(mut cb) = self.on_menu_item_selected.take() {
+                    cb(self, widgets, layouts, menu_id);
+                    self.on_menu_item_selected = Some(cb);
+                }
+            }
+            MenuItem::Submenu { .. } => {
+                self.open_path.truncate(depth);
+                self.open_path.push(index);
+                self.highlighted_item = self
+                    .next_selectable(depth + 1, -1, 1)
+                    .map(|first| (depth + 1, first));
+                self.get_config().set_invalidated(true);
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the index of the next selectable `MenuItem` in the open menu at `depth`, moving from
+    /// `start` in the direction of `delta`.  The search wraps around the menu.
+    fn next_selectable(&self, depth: usize, start: i32, delta: i32) -> Option<usize> {
+        let items = self.menu_items(depth);
+        let count = items.len() as i32;
+        let mut index = start;
+
+        for _ in 0..count {
+            index = (index + delta).rem_euclid(count);
+
+            if items[index as usize].is_selectable() {
+                return Some(index as usize);
+            }
+        }
+
+        None
+    }
+
+    /// Remembers the size of the window, so that menus can be kept within it.
+    fn update_window_size(&mut self, widgets: &[WidgetContainer]) {
+        if let Ok(mut base_widget) = widgets[0].widget.try_borrow_mut() {
+            let size = base_widget.get_config().get_size(CONFIG_SIZE);
+
+            self.window_size = (size[SIZE_WIDTH], size[SIZE_HEIGHT]);
+        }
+    }
+
+    /// Measures the titles of the menus, and the widths of the menus and their submenus.
+    fn measure(&mut self, font: &Font) {
+        fn measure_items(
+            font: &Font,
+            key: Vec<usize>,
+            items: &[MenuItem],
+            widths: &mut HashMap<Vec<usize>, u32>,
+        ) {
+            let mut width = MIN_MENU_WIDTH;
+
+            for (index, item) in items.iter().enumerate() {
+                if let Some(label) = item.get_label() {
+                    let label_width = font.size_of(label).map_or(0, |(w, _)| w);
+
+                    width = width.max(label_width + ITEM_PADDING * 2 + ARROW_AREA_WIDTH);
+                }
+
+                if let MenuItem::Submenu { items, .. } = item {
+                    let mut child_key = key.clone();
+
+                    child_key.push(index);
+                    measure_items(font, child_key, items, widths);
+                }
+            }
+
+            widths.insert(key, width);
+        }
+
+        self.title_widths = self
+            .menus
+            .iter()
+            .map(|menu| font.size_of(&menu.title).map_or(0, |(w, _)| w) + TITLE_PADDING * 2)
+            .collect();
+        self.menu_widths.clear();
+
+        for (index, menu) in self.menus.iter().enumerate() {
+            measure_items(font, vec![index], &menu.items, &mut self.menu_widths);
+        }
+
+        self.measured = true;
+    }
+}
+
+/// This is the `Widget` implementation of the `MenuBarWidget`.
+impl Widget for MenuBarWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let hover_color = self.get_color(CONFIG_COLOR_HOVER);
+            let selected_color = self.get_color(CONFIG_COLOR_SELECTED);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+
+            if !self.measured {
+                self.measure(&font);
+            }
+
+            let title_widths = self.title_widths.clone();
+            let open_menu = self.open_menu;
+            let highlighted_title = self.highlighted_title;
+            let title_textures: Vec<Option<Texture>> = self
+                .menus
+                .iter()
+                .map(|menu| render_text_line(&font, &texture_creator, &menu.title, text_color))
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                let mut x = 0;
+
+                for (index, (width, title)) in
+                    title_widths.iter().zip(title_textures.iter()).enumerate()
+                {
+                    if index as i32 == open_menu || index as i32 == highlighted_title {
+                        texture.set_draw_color(if index as i32 == open_menu {
+                            selected_color
+                        } else {
+                            hover_color
+                        });
+                        texture
+                            .fill_rect(Rect::new(x, 0, *width, bounds[SIZE_HEIGHT]))
+                            .unwrap();
+                    }
+
+                    if let Some(title) = title {
+                        let TextureQuery { width, height, .. } = title.query();
+
+                        texture
+                            .copy(
+                                title,
+                                None,
+                                Rect::new(
+                                    x + TITLE_PADDING as i32,
+                                    (bounds[SIZE_HEIGHT] as i32 - height as i32) / 2,
+                                    width,
+                                    height,
+                                ),
+                            )
+                            .unwrap();
+                    }
+
+                    x += *width as i32;
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_line(
+                        Point::new(0, bounds[SIZE_HEIGHT] as i32 - 1),
+                        Point::new(bounds[SIZE_WIDTH] as i32, bounds[SIZE_HEIGHT] as i32 - 1),
+                    )
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the title textures are only used within this function.
+            for title in title_textures.into_iter().flatten() {
+                unsafe {
+                    title.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Draws the open menu, and any open submenus.  Submenus are drawn above their parents, and the
+    /// area between the menus is left transparent.
+    fn draw_popup(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        let popup_bounds = self.get_popup_bounds()?;
+        let menu_bounds = self.menu_bounds();
+
+        self.popup_store
+            .create_or_resize_texture(c, popup_bounds.width(), popup_bounds.height());
+
+        let base_color = self.get_color(CONFIG_COLOR_BASE);
+        let border_color = self.get_color(CONFIG_COLOR_BORDER);
+        let hover_color = self.get_color(CONFIG_COLOR_HOVER);
+        let text_color = self.get_color(CONFIG_COLOR_TEXT);
+        let disabled_color = self.get_color(CONFIG_COLOR_SECONDARY);
+        let open_path = self.open_path.clone();
+        let highlighted_item = self.highlighted_item;
+
+        let ttf_context = t.get_ttf_context();
+        let texture_creator = c.texture_creator();
+        let font = ttf_context
+            .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+            .unwrap();
+        let menus: Vec<Vec<(MenuItem, Option<Texture>)>> = (0..menu_bounds.len())
+            .map(|depth| {
+                self.menu_items(depth)
+                    .iter()
+                    .map(|item| {
+                        let color = match item {
+                            MenuItem::Item { enabled: false, .. } => disabled_color,
+                            _ => text_color,
+                        };
+                        let label = item.get_label().and_then(|label| {
+                            render_text_line(&font, &texture_creator, label, color)
+                        });
+
+                        (item.clone(), label)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        c.with_texture_canvas(self.popup_store.get_mut_ref(), |texture| {
+            texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+            texture.clear();
+
+            for (depth, (bounds, items)) in menu_bounds.iter().zip(menus.iter()).enumerate() {
+                let left = bounds.x() - popup_bounds.x();
+                let mut top = bounds.y() - popup_bounds.y() + 1;
+
+                texture.set_draw_color(base_color);
+                texture
+                    .fill_rect(Rect::new(left, top - 1, bounds.width(), bounds.height()))
+                    .unwrap();
+
+                for (index, (item, label)) in items.iter().enumerate() {
+                    let height = item.height();
+                    let is_highlighted = highlighted_item == Some((depth, index))
+                        || open_path.get(depth) == Some(&index);
+
+                    if is_highlighted && item.is_selectable() {
+                        texture.set_draw_color(hover_color);
+                        texture
+                            .fill_rect(Rect::new(left + 1, top, bounds.width() - 2, height))
+                            .unwrap();
+                    }
+
+                    match item {
+                        MenuItem::Separator => {
+                            let y = top + height as i32 / 2;
+
+                            texture.set_draw_color(border_color);
+                            texture
+                                .draw_line(
+                                    Point::new(left + 4, y),
+                                    Point::new(left + bounds.width() as i32 - 5, y),
+                                )
+                                .unwrap();
+                        }
+                        MenuItem::Submenu { .. } => {
+                            let center_x =
+                                left + bounds.width() as i32 - ARROW_AREA_WIDTH as i32 / 2;
+                            let center_y = top + height as i32 / 2;
+
+                            texture.set_draw_color(text_color);
+
+                            for step in 0..4 {
+                                texture
+                                    .draw_line(
+                                        Point::new(center_x - 2 + step, center_y - 3 + step),
+                                        Point::new(center_x - 2 + step, center_y + 3 - step),
+                                    )
+                                    .unwrap();
+                            }
+                        }
+                        _ => (),
+                    }
+
+                    if let Some(label) = label {
+                        let TextureQuery { width, height, .. } = label.query();
+
+                        texture
+                            .copy(
+                                label,
+                                None,
+                                Rect::new(
+                                    left + ITEM_PADDING as i32,
+                                    top + (ITEM_HEIGHT as i32 - height as i32) / 2,
+                                    width,
+                                    height,
+                                ),
+                            )
+                            .unwrap();
+                    }
+
+                    top += height as i32;
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(
+                        left,
+                        bounds.y() - popup_bounds.y(),
+                        bounds.width(),
+                        bounds.height(),
+                    ))
+                    .unwrap();
+            }
+        })
+        .unwrap();
+
+        // Safety: the label textures are only used within this function.
+        for (_, label) in menus.into_iter().flatten() {
+            if let Some(label) = label {
+                unsafe {
+                    label.destroy();
+                }
+            }
+        }
+
+        self.popup_store.get_optional_ref()
+    }
+
+    fn get_popup_bounds(&mut self) -> Option<Rect> {
+        let menu_bounds = self.menu_bounds();
+        let first = *menu_bounds.first()?;
+
+        Some(menu_bounds.iter().fold(first, |bounds, x| bounds.union(*x)))
+    }
+
+    fn close_popup(&mut self) {
+        self.open_menu = -1;
+        self.open_path.clear();
+        self.highlighted_item = None;
+    }
+
+    /// Clears the highlighted menu title when the mouse leaves the `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.highlighted_title != -1 {
+            self.highlighted_title = -1;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the menu title or `MenuItem` under the mouse.  While a menu is open, moving over
+    /// the title of another menu opens that menu instead.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let (x, y) = (points[POINT_X], points[POINT_Y]);
+        let title = self.title_at(x, y);
+
+        self.mouse_position = points.clone();
+
+        if title != self.highlighted_title {
+            self.highlighted_title = title;
+            self.get_config().set_invalidated(true);
+        }
+
+        if self.open_menu >= 0 {
+            if let Some((depth, index)) = self.item_at(x, y) {
+                self.hover_item(depth, index);
+            } else if title >= 0 && title != self.open_menu {
+                self.open(title as usize);
+            }
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Opens or closes a menu when its title is clicked, and activates the `MenuItem` that was
+    /// clicked in an open menu.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let (x, y) = (self.mouse_position[POINT_X], self.mouse_position[POINT_Y]);
+            let title = self.title_at(x, y);
+
+            self.update_window_size(_widgets);
+
+            if let Some((depth, index)) = self.item_at(x, y) {
+                self.activate_item(depth, index, _widgets, _layouts);
+            } else if title >= 0 && title != self.open_menu {
+                self.open(title as usize);
+            } else if title >= 0
+                || !self
+                    .menu_bounds()
+                    .iter()
+                    .any(|bounds| bounds.contains_point((x, y)))
+            {
+                self.close();
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Navigates the menus with the arrow keys, activates the highlighted `MenuItem` with `Return`,
+    /// and closes the menus with `Escape`, while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            if self.menus.is_empty() {
+                return;
+            }
+
+            let menu_count = self.menus.len() as i32;
+
+            if self.open_menu < 0 {
+                if let Keycode::Down | Keycode::Return | Keycode::KpEnter = keycode {
+                    self.update_window_size(_widgets);
+                    self.open(self.highlighted_title.max(0) as usize);
+                    self.highlighted_item = self.next_selectable(0, -1, 1).map(|first| (0, first));
+                }
+
+                return;
+            }
+
+            let depth = self.open_path.len();
+            let highlighted = match self.highlighted_item {
+                Some((highlighted_depth, index)) if highlighted_depth == depth => Some(index),
+                _ => None,
+            };
+
+            match keycode {
+                Keycode::Escape => self.close(),
+                Keycode::Up | Keycode::Down => {
+                    let delta = if keycode == Keycode::Up { -1 } else { 1 };
+                    let start = highlighted.map_or(if delta > 0 { -1 } else { 0 }, |x| x as i32);
+
+                    if let Some(index) = self.next_selectable(depth, start, delta) {
+                        self.highlighted_item = Some((depth, index));
+                        self.get_config().set_invalidated(true);
+                    }
+                }
+                Keycode::Right => match highlighted {
+                    Some(index)
+                        if matches!(self.menu_items(depth)[index], MenuItem::Submenu { .. }) =>
+                    {
+                        self.activate_item(depth, index, _widgets, _layouts);
+                    }
+                    _ => {
+                        self.open(((self.open_menu + 1) % menu_count) as usize);
+                        self.highlighted_item =
+                            self.next_selectable(0, -1, 1).map(|first| (0, first));
+                    }
+                },
+                Keycode::Left => match self.open_path.pop() {
+                    Some(index) => {
+                        self.highlighted_item = Some((depth - 1, index));
+                        self.get_config().set_invalidated(true);
+                    }
+                    None => {
+                        self.open(((self.open_menu + menu_count - 1) % menu_count) as usize);
+                        self.highlighted_item =
+                            self.next_selectable(0, -1, 1).map(|first| (0, first));
+                    }
+                },
+                Keycode::Return | Keycode::KpEnter => {
+                    if let Some(index) = highlighted {
+                        self.activate_item(depth, index, _widgets, _layouts);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Closes the menus when this `Widget` loses focus.
+    fn focus_changed(&mut self, focused: bool) {
+        if !focused && self.open_menu >= 0 {
+            self.close();
+        }
+    }
+
+    /// Releases the backing textures of this `Widget`.  The textures are recreated the next time
+    /// this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.popup_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -62,3 +62,189 @@ pub mod list_widget;
 pub mod tile_widget;
 
 pub mod tab_bar_widget;
+
+/// This is a `TextAreaWidget`, which edits multiple lines of text, wrapping them at word boundaries
+/// and scrolling vertically.
+pub mod text_area_widget;
+
+/// This is a `DropdownWidget`, which displays the selected item, and opens a popup list of items to
+/// choose from when clicked.
+pub mod dropdown_widget;
+
+/// This is a `TreeViewWidget`, which displays a hierarchy of nodes that can be expanded and
+/// collapsed.
+pub mod tree_view_widget;
+
+/// This is a `TableWidget`, which displays rows of data in named columns that can be sorted by
+/// clicking on their headers.
+pub mod table_widget;
+
+/// This is a `TabWidget`, which displays a tab bar, and shows the `Widget`s belonging to the
+/// selected tab.
+pub mod tab_widget;
+
+/// This is a `MenuBarWidget`, which displays a desktop-style menu bar with pull-down menus and
+/// nested submenus.
+pub mod menu_bar_widget;
+
+/// This is a `ScrollBarWidget`, which displays a horizontal or vertical scroll bar with a thumb
+/// that is sized proportionally to the visible area of its content.
+pub mod scroll_bar_widget;
+
+/// This is a `ScrollContainerWidget`, which shows a scrollable area of a larger content area that
+/// its child `Widget`s are positioned in, with scroll bars.
+pub mod scroll_container_widget;
+
+/// This is a `ToastNotificationWidget`, which slides a message into a corner of the window for a
+/// number of milliseconds, and then removes itself.
+pub mod toast_notification_widget;
+
+/// This is a `SpinnerWidget`, which shows a numeric value next to up and down arrow buttons that
+/// step it between a minimum and maximum value.
+pub mod spinner_widget;
+
+/// This is a `ColorPickerWidget`, which selects a color using a hue strip and a saturation/value
+/// square, and shows the selected color in a preview swatch.
+pub mod color_picker_widget;
+
+/// This is a `CalendarWidget`, which shows the days of a month in a grid, and selects a date.
+pub mod calendar_widget;
+
+/// This is a `FileChooserDialog`, a modal dialog built from `ListWidget`, `TextAreaWidget`,
+/// `DropdownWidget` and `PushButtonWidget`s, which chooses a file to open or save.
+pub mod file_chooser_dialog;
+
+/// This is a `KnobWidget`, which is a rotary dial that selects a value between a minimum and a
+/// maximum.
+pub mod knob_widget;
+
+/// This is a `ToggleSwitchWidget`, which is an on/off switch with a thumb that slides along a
+/// pill-shaped track.
+pub mod toggle_switch_widget;
+
+/// This is a `RangeSliderWidget`, which is a slider with two handles that select a low and a high
+/// value.
+pub mod range_slider_widget;
+
+/// This is a `CircularProgressWidget`, which draws progress as an arc that sweeps around a ring.
+pub mod circular_progress_widget;
+
+/// This is a `GaugeWidget`, which is a dashboard-style dial that shows a value with a needle on a
+/// semicircular scale with colored zones.
+pub mod gauge_widget;
+
+/// This is a `SevenSegmentWidget`, which displays decimal and hexadecimal numbers in the style of a
+/// seven-segment display.
+pub mod seven_segment_widget;
+
+/// This is a `RatingWidget`, which is a row of stars that selects a rating.
+pub mod rating_widget;
+
+/// This is a `HyperlinkLabelWidget`, which is a text label styled as a hyperlink, that triggers a
+/// callback or opens a URL when clicked.
+pub mod hyperlink_label_widget;
+
+/// This is an `AccordionWidget`, which stacks titled sections that open and close when their
+/// headers are clicked.
+pub mod accordion_widget;
+
+/// This is a `StatusBarWidget`, which is a bar of text panes that stays anchored to the bottom of
+/// the window.
+pub mod status_bar_widget;
+
+/// This is a `PasswordFieldWidget`, which is a single-line text field that masks the characters
+/// typed, with an optional button that reveals them.
+pub mod password_field_widget;
+
+/// This is a `SearchFieldWidget`, which is a single-line text field with a magnifier icon and a
+/// clear button, that reports changes to its text once the user stops typing.
+pub mod search_field_widget;
+
+/// This is a `ChartWidget`, which draws series of values as a line or bar chart, with axes and
+/// gridlines.
+pub mod chart_widget;
+
+/// This is a `SparklineWidget`, which draws a compact line or area of a rolling window of values,
+/// with no axes.
+pub mod sparkline_widget;
+
+/// This is a `CanvasWidget`, which draws its contents with a user supplied closure.
+pub mod canvas_widget;
+
+/// This is an `AnimatedImageWidget`, which plays the frames of a sprite sheet or an animated GIF.
+pub mod animated_image_widget;
+
+/// This is a `TerminalLogWidget`, which is a scrolling console of timestamped lines of text.
+pub mod terminal_log_widget;
+
+/// This is a `CodeEditorWidget`, which is a multi-line source code editor with line numbers and
+/// syntax highlighting by a pluggable `Tokenizer`.
+pub mod code_editor_widget;
+
+/// This is a `MarkdownViewerWidget`, which draws a scrolling document written in a subset of
+/// Markdown.
+pub mod markdown_viewer_widget;
+
+/// This is a `SegmentedControlWidget`, which is a row of connected segments, exactly one of which is
+/// selected.
+pub mod segmented_control_widget;
+
+/// This is a `VirtualizedListWidget`, which is a list that only draws the rows that are visible, so
+/// that it can show a very large number of rows.
+pub mod virtualized_list_widget;
+
+/// This is a `PropertyGridWidget`, which is an inspector of named properties, with an editor for
+/// each type of value.
+pub mod property_grid_widget;
+
+/// This is a `TimelineWidget`, which shows a time ruler with markers, regions, and a draggable
+/// playhead.
+pub mod timeline_widget;
+
+/// This is a `PieMenuWidget`, which is a radial menu of wedges that opens centered on the mouse.
+pub mod pie_menu_widget;
+
+/// This is an `OnScreenKeyboardWidget`, which is a QWERTY or numeric keyboard drawn on the screen,
+/// that types into the focused `Widget`.
+pub mod on_screen_keyboard_widget;
+
+/// This is a `KeypadWidget`, which is a compact numeric keypad that enters a value into a bound
+/// `Widget`.
+pub mod keypad_widget;
+
+/// This is a `DrawingPadWidget`, which captures freehand strokes, such as signatures.
+pub mod drawing_pad_widget;
+
+/// This is a `HeatmapWidget`, which draws a matrix of values as a grid of cells colored through a
+/// gradient.
+pub mod heatmap_widget;
+
+/// This is an `AudioLevelWidget`, which is a segmented level meter with peak hold, whose level can
+/// be set from another thread.
+pub mod audio_level_widget;
+
+/// This is an `OscilloscopeWidget`, which draws a trace of streaming samples, with optional
+/// phosphor-like persistence.
+pub mod oscilloscope_widget;
+
+/// This is a `CompassWidget`, which draws a heading indicator as a compass card that turns beneath
+/// a fixed lubber mark.
+pub mod compass_widget;
+
+/// This is an `ArtificialHorizonWidget`, which draws an attitude indicator showing pitch and roll.
+pub mod artificial_horizon_widget;
+
+/// This is a `TagInputWidget`, which turns typed text into removable chips.
+pub mod tag_input_widget;
+
+/// This is an `AutoCompleteWidget`, which is a text field that shows a popup list of suggestions as
+/// the user types.
+pub mod auto_complete_widget;
+
+/// This is a `MaskedInputWidget`, which is a text field for values with a fixed format, such as
+/// phone numbers or dates.
+pub mod masked_input_widget;
+
+/// This is a `NumericInputWidget`, which is a text field for numbers with limits and an optional
+/// unit.
+pub mod numeric_input_widget;
@@ -0,0 +1,521 @@
+// Pushrod Widget Library
+// Numeric Input Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock;
+use crate::render::input_settings::{handle_edit_key, CaretBlink};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::{render_text_line, TextureCache};
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between the edges of the `NumericInputWidget` and its text, and
+/// between the text and the unit.
+const TEXT_PADDING: i32 = 6;
+
+/// This is the callback type that is used when an `on_value_committed` callback is triggered from
+/// this `Widget`.  It receives the committed value.
+pub type OnValueCommittedCallbackType =
+    Option<Box<dyn FnMut(&mut NumericInputWidget, &[WidgetContainer], &[LayoutContainer], f64)>>;
+
+/// This is the storage object for the `NumericInputWidget`.  It stores the config, properties,
+/// callback registry, the font name and size, the text being edited, the cursor and scroll
+/// positions, the limits of the value, and its unit.
+pub struct NumericInputWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    text: Vec<char>,
+    cursor: usize,
+    scroll_x: i32,
+    offsets: Vec<u32>,
+    min: Option<f64>,
+    max: Option<f64>,
+    decimal_places: usize,
+    unit: String,
+    commit_pending: bool,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_value_committed: OnValueCommittedCallbackType,
+}
+
+/// This is the implementation of the `NumericInputWidget`, a single-line text field for entering a
+/// number, with an optional unit drawn in `CONFIG_COLOR_SECONDARY` at its right edge.  Only digits
+/// can be typed, along with a decimal point if decimal places are allowed, and a leading minus
+/// sign if negative values are allowed.  No more digits can be typed after the decimal point than
+/// the number of decimal places.
+///
+/// While the text is not a number within the minimum and maximum, the border is drawn in
+/// `CONFIG_COLOR_INVALID` instead of `CONFIG_COLOR_BORDER`, and `get_value` returns `None`.  An
+/// empty field has no value, but is not drawn as invalid.  Pressing `Return`, or moving the focus
+/// away, commits a valid value: the text is written out with the number of decimal places, and the
+/// `on_value_committed` callback is triggered.
+impl NumericInputWidget {
+    /// Creates a new `NumericInputWidget`, given the path to the font file, the size of the font in
+    /// pixels, and the `x, y, w, h` coordinates.  It accepts whole numbers of any size by default.
+    pub fn new(font_name: String, font_size: i32, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            text: vec![],
+            cursor: 0,
+            scroll_x: 0,
+            offsets: vec![0],
+            min: None,
+            max: None,
+            decimal_places: 0,
+            unit: String::new(),
+            commit_pending: false,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_value_committed: None,
+        }
+    }
+
+    /// Sets the value, written out with the number of decimal places.  Values outside of the
+    /// minimum and maximum are shown as invalid.  This does not trigger the `on_value_committed`
+    /// callback.
+    pub fn set_value(&mut self, value: f64) {
+        self.text = self.format_value(value).chars().collect();
+        self.cursor = self.text.len();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the value, or `None` if the field is empty, or its text is not a number within the
+    /// minimum and maximum.
+    pub fn get_value(&self) -> Option<f64> {
+        let text: String = self.text.iter().collect();
+        let value = text.parse::<f64>().ok().filter(|value| value.is_finite())?;
+
+        if self.min.is_some_and(|min| value < min) || self.max.is_some_and(|max| value > max) {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Removes the value, leaving the field empty.
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the text is not a number within the minimum and maximum.  An empty field is
+    /// not invalid.
+    pub fn is_invalid(&self) -> bool {
+        !self.text.is_empty() && self.get_value().is_none()
+    }
+
+    /// Sets the lowest and highest values allowed, or `None` for no limit.  A minus sign can only
+    /// be typed if there is no minimum, or it is below zero.
+    pub fn set_range(&mut self, min: Option<f64>, max: Option<f64>) {
+        self.min = min;
+        self.max = max;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the lowest and highest values allowed.
+    pub fn get_range(&self) -> (Option<f64>, Option<f64>) {
+        (self.min, self.max)
+    }
+
+    /// Sets the number of digits allowed after the decimal point.  At `0`, the default, only whole
+    /// numbers can be typed.  Digits beyond the new number of decimal places are removed.
+    pub fn set_decimal_places(&mut self, decimal_places: usize) {
+        self.decimal_places = decimal_places;
+
+        if let Some(point) = self.text.iter().position(|c| *c == '.') {
+            let end = if decimal_places == 0 {
+                point
+            } else {
+                point + 1 + decimal_places
+            };
+
+            self.text.truncate(end);
+            self.cursor = self.cursor.min(self.text.len());
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the number of digits allowed after the decimal point.
+    pub fn get_decimal_places(&self) -> usize {
+        self.decimal_places
+    }
+
+    /// Sets the unit drawn at the right edge of the field, such as `kg` or `%`.  An empty unit, the
+    /// default, is not drawn.
+    pub fn set_unit(&mut self, unit: &str) {
+        self.unit = String::from(unit);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the unit drawn at the right edge of the field.
+    pub fn get_unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Assigns the callback closure that will be used when a valid value is committed.
+    pub fn on_value_committed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut NumericInputWidget, &[WidgetContainer], &[LayoutContainer], f64) + 'static,
+    {
+        self.on_value_committed = Some(Box::new(callback));
+    }
+
+    /// Internal function that commits the value if it is valid, writing it out with the number of
+    /// decimal places, and triggering the `on_value_committed` callback.
+    fn commit(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        self.commit_pending = false;
+
+        if let Some(value) = self.get_value() {
+            self.set_value(value);
+
+            if let Some(mut cb) = self.on_value_committed.take() {
+                cb(self, widgets, layouts, value);
+                self.on_value_committed = Some(cb);
+            }
+        }
+    }
+
+    /// Returns `value` written out with the number of decimal places.
+    fn format_value(&self, value: f64) -> String {
+        format!("{:.*}", self.decimal_places, value)
+    }
+
+    /// Indicates whether `c` can be inserted at the cursor.
+    fn accepts(&self, c: char) -> bool {
+        let point = self.text.iter().position(|x| *x == '.');
+
+        match c {
+            '-' => {
+                self.cursor == 0
+                    && self.text.first() != Some(&'-')
+                    && self.min.is_none_or(|min| min < 0.0)
+            }
+            '.' => {
+                self.decimal_places > 0
+                    && point.is_none()
+                    && self.text.get(self.cursor) != Some(&'-')
+            }
+            '0'..='9' => {
+                self.text.get(self.cursor) != Some(&'-')
+                    && point.is_none_or(|point| {
+                        self.cursor <= point || self.text.len() - point <= self.decimal_places
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the cursor to the character closest to the last known mouse position.
+    fn move_cursor_to_mouse(&mut self) {
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(TEXT_PADDING) + self.scroll_x;
+
+        self.cursor = self
+            .offsets
+            .windows(2)
+            .position(|pair| x < ((pair[0] + pair[1]) / 2) as i32)
+            .unwrap_or(self.offsets.len() - 1)
+            .min(self.text.len());
+    }
+}
+
+/// This is the `Widget` implementation of the `NumericInputWidget`.
+impl Widget for NumericInputWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let unit_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(128, 128, 128));
+            let border_color = if self.is_invalid() {
+                self.get_color_or(CONFIG_COLOR_INVALID, Color::RGB(220, 0, 0))
+            } else {
+                self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0))
+            };
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+            let line_height = font.recommended_line_spacing().max(1);
+            let unit_texture = render_text_line(&font, &texture_creator, &self.unit, unit_color);
+            let unit_width = unit_texture.as_ref().map_or(0, |unit_texture| {
+                unit_texture.query().width as i32 + TEXT_PADDING
+            });
+            let text_width = (bounds[SIZE_WIDTH] as i32 - TEXT_PADDING * 2 - unit_width).max(1);
+
+            self.offsets = (0..=self.text.len())
+                .map(|end| {
+                    let prefix: String = self.text[..end].iter().collect();
+
+                    font.size_of(&prefix).map(|(w, _)| w).unwrap_or(0)
+                })
+                .collect();
+
+            // The text is scrolled horizontally so that the cursor is always within the field.
+            let caret_x = self.offsets[self.cursor.min(self.text.len())] as i32;
+
+            if caret_x - self.scroll_x >= text_width {
+                self.scroll_x = caret_x - text_width + 1;
+            } else if caret_x < self.scroll_x {
+                self.scroll_x = caret_x;
+            }
+
+            let scroll_x = self.scroll_x;
+            let text_y = (bounds[SIZE_HEIGHT] as i32 - line_height) / 2;
+            let caret = if self.focused && self.caret.is_shown() {
+                Some(TEXT_PADDING + caret_x - scroll_x)
+            } else {
+                None
+            };
+            let text: String = self.text.iter().collect();
+            let font_texture = render_text_line(&font, &texture_creator, &text, text_color);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let Some(font_texture) = &font_texture {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+                    let visible_width = (width as i32 - scroll_x).min(text_width);
+
+                    if visible_width > 0 {
+                        texture
+                            .copy(
+                                font_texture,
+                                Rect::new(scroll_x, 0, visible_width as u32, height),
+                                Rect::new(TEXT_PADDING, text_y, visible_width as u32, height),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                if let Some(unit_texture) = &unit_texture {
+                    let TextureQuery { width, height, .. } = unit_texture.query();
+
+                    texture
+                        .copy(
+                            unit_texture,
+                            None,
+                            Rect::new(
+                                bounds[SIZE_WIDTH] as i32 - TEXT_PADDING - width as i32,
+                                text_y,
+                                width,
+                                height,
+                            ),
+                        )
+                        .unwrap();
+                }
+
+                texture.set_draw_color(text_color);
+
+                if let Some(x) = caret {
+                    texture
+                        .fill_rect(Rect::new(x, text_y, 1, line_height as u32))
+                        .unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the font textures are only used within this function.
+            for font_texture in vec![font_texture, unit_texture].into_iter().flatten() {
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while this `Widget` has focus, and commits the value after the focus has
+    /// moved away.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        if self.commit_pending {
+            self.commit(_widgets, _layouts);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Tracks the mouse position, so that clicks can move the cursor.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Moves the cursor to the clicked position.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            self.move_cursor_to_mouse();
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles numeric input and cursor navigation while this `Widget` has focus, committing the
+    /// value on `Return`.  Characters that cannot be typed at the cursor are ignored.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let cursor_moved = match event {
+            Event::TextInput { text, .. } => {
+                let mut inserted = false;
+
+                for c in text.chars() {
+                    if self.accepts(c) {
+                        self.text.insert(self.cursor, c);
+                        self.cursor += 1;
+                        inserted = true;
+                    }
+                }
+
+                inserted
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Return),
+                ..
+            }
+            | Event::KeyDown {
+                keycode: Some(Keycode::KpEnter),
+                ..
+            } => {
+                self.commit(_widgets, _layouts);
+                true
+            }
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } => handle_edit_key(keycode, &mut self.text, &mut self.cursor).1,
+            _ => false,
+        };
+
+        if cursor_moved {
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Monitors for changes in the text, colors, or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _)
+            | (CONFIG_COLOR_BASE, _)
+            | (CONFIG_COLOR_BORDER, _)
+            | (CONFIG_COLOR_SECONDARY, _)
+            | (CONFIG_COLOR_INVALID, _) => self.get_config().set_invalidated(true),
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            (CONFIG_TEXT, Config::Text(text)) => match text.trim().parse::<f64>() {
+                Ok(value) => self.set_value(value),
+                Err(_) => self.clear(),
+            },
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus, and commits the value on the next tick once
+    /// the focus moves away.
+    fn focus_changed(&mut self, focused: bool) {
+        if self.focused && !focused {
+            self.commit_pending = true;
+        }
+
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus.
+    fn accepts_text_input(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,563 @@
+// Pushrod Widget Library
+// On-Screen Keyboard Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_rounded_rect;
+use crate::render::clock;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the space between keys, and around the edges of the keyboard, in pixels.
+const KEY_GAP: u32 = 4;
+
+/// This is the radius of the corners of each key, in pixels.
+const KEY_RADIUS: u32 = 4;
+
+/// These are the layouts of keys an `OnScreenKeyboardWidget` can show.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyboardLayout {
+    /// A full QWERTY keyboard, with a row of numbers, punctuation, `Shift`, and the arrow keys.
+    Qwerty,
+
+    /// A numeric keypad, with a decimal point and a minus sign.
+    Numeric,
+}
+
+/// This is what pressing a key of the keyboard does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum KeyAction {
+    /// Types the first character, or the second while `Shift` is active.
+    Character(char, char),
+
+    /// Presses a key that does not type, such as `Backspace`.
+    Special(Keycode),
+
+    /// Toggles `Shift` for the next character typed.
+    Shift,
+}
+
+/// This is a key of the keyboard: what it does, and its width, relative to a letter key.
+#[derive(Clone, Copy, Debug)]
+struct KeyDef {
+    action: KeyAction,
+    width: f32,
+}
+
+/// Internal function that returns a key that types `normal`, or `shifted` while `Shift` is active.
+fn character(normal: char, shifted: char) -> KeyDef {
+    KeyDef {
+        action: KeyAction::Character(normal, shifted),
+        width: 1.0,
+    }
+}
+
+/// Internal function that returns a key of `width` that performs `action`.
+fn wide(action: KeyAction, width: f32) -> KeyDef {
+    KeyDef { action, width }
+}
+
+/// Internal function that returns a row of keys typing each character of `normal`, or the matching
+/// character of `shifted` while `Shift` is active.
+fn characters(normal: &str, shifted: &str) -> Vec<KeyDef> {
+    normal
+        .chars()
+        .zip(shifted.chars())
+        .map(|(normal, shifted)| character(normal, shifted))
+        .collect()
+}
+
+/// Internal function that returns the rows of keys of `layout`, from top to bottom.
+fn layout_rows(layout: KeyboardLayout) -> Vec<Vec<KeyDef>> {
+    match layout {
+        KeyboardLayout::Qwerty => {
+            let mut numbers = characters("1234567890", "!@#$%^&*()");
+            let mut top = characters("qwertyuiop-", "QWERTYUIOP_");
+            let mut middle = characters("asdfghjkl'", "ASDFGHJKL\"");
+            let mut bottom = vec![wide(KeyAction::Shift, 1.5)];
+
+            numbers.push(wide(KeyAction::Special(Keycode::Backspace), 1.5));
+            top.push(character('=', '+'));
+            middle.push(wide(KeyAction::Special(Keycode::Return), 1.5));
+            bottom.extend(characters("zxcvbnm,./", "ZXCVBNM<>?"));
+
+            vec![
+                numbers,
+                top,
+                middle,
+                bottom,
+                vec![
+                    wide(KeyAction::Special(Keycode::Left), 1.5),
+                    wide(KeyAction::Character(' ', ' '), 6.0),
+                    wide(KeyAction::Special(Keycode::Right), 1.5),
+                ],
+            ]
+        }
+        KeyboardLayout::Numeric => vec![
+            {
+                let mut row = characters("789", "789");
+
+                row.push(wide(KeyAction::Special(Keycode::Backspace), 1.0));
+                row
+            },
+            characters("456-", "456-"),
+            {
+                let mut row = characters("123", "123");
+
+                row.push(wide(KeyAction::Special(Keycode::Return), 1.0));
+                row
+            },
+            vec![
+                wide(KeyAction::Character('0', '0'), 2.0),
+                character('.', '.'),
+                character(',', ','),
+            ],
+        ],
+    }
+}
+
+/// This is the storage object for the `OnScreenKeyboardWidget`.  It stores the config, properties,
+/// callback registry, the layout of keys, the state of `Shift`, the key under the mouse and the key
+/// being pressed, and the events synthesized by pressing keys.
+pub struct OnScreenKeyboardWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    layout: KeyboardLayout,
+    keys: Vec<KeyDef>,
+    rows: Vec<usize>,
+    shifted: bool,
+    hovered: Option<usize>,
+    pressed: Option<usize>,
+    mouse_position: Points,
+    synthesized_events: Vec<Event>,
+}
+
+/// This is the implementation of the `OnScreenKeyboardWidget`, a keyboard drawn on the screen, for
+/// kiosks and touchscreens that have no physical keyboard.  Pressing a key synthesizes the same
+/// `KeyDown`, `TextInput`, and `KeyUp` events as the physical key would, which the `Engine` sends
+/// to the `Widget` that has keyboard focus, so text fields are typed into as usual.  Pressing the
+/// keyboard does not take the focus away from the text field.
+///
+/// `Shift` applies to the next character typed.  Keys are filled with `CONFIG_COLOR_SECONDARY`,
+/// `CONFIG_COLOR_HOVER` under the mouse, and `CONFIG_COLOR_SELECTED` while pressed or, for `Shift`,
+/// while active.  The keyboard is filled with `CONFIG_COLOR_BASE`, and the keys are outlined in
+/// `CONFIG_COLOR_BORDER`, and labelled in `CONFIG_COLOR_TEXT`, with the size in `CONFIG_FONT_SIZE`,
+/// or `16` if it is not set.
+impl OnScreenKeyboardWidget {
+    /// Creates a new `OnScreenKeyboardWidget` given the `x, y, w, h` coordinates, and the `layout`
+    /// of keys.  The keys are stretched to fill the `Widget`.
+    pub fn new(points: Points, size: Size, layout: KeyboardLayout) -> Self {
+        let mut keyboard = Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            layout,
+            keys: Vec::new(),
+            rows: Vec::new(),
+            shifted: false,
+            hovered: None,
+            pressed: None,
+            mouse_position: vec![0, 0],
+            synthesized_events: Vec::new(),
+        };
+
+        keyboard.set_layout(layout);
+        keyboard
+    }
+
+    /// Changes the layout of keys.
+    pub fn set_layout(&mut self, layout: KeyboardLayout) {
+        let rows = layout_rows(layout);
+
+        self.layout = layout;
+        self.rows = rows.iter().map(|row| row.len()).collect();
+        self.keys = rows.into_iter().flatten().collect();
+        self.hovered = None;
+        self.pressed = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the layout of keys.
+    pub fn get_layout(&self) -> KeyboardLayout {
+        self.layout
+    }
+
+    /// Sets whether `Shift` applies to the next character typed.
+    pub fn set_shifted(&mut self, shifted: bool) {
+        self.shifted = shifted;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether `Shift` applies to the next character typed.
+    pub fn is_shifted(&self) -> bool {
+        self.shifted
+    }
+
+    /// Internal function that returns the bounds of each key, in local coordinates, in the same
+    /// order as the keys.  Each row is centered, and the widest row fills the width of the
+    /// keyboard.
+    fn key_rects(&mut self) -> Vec<Rect> {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let mut row_widths = vec![];
+        let mut first = 0;
+
+        for length in &self.rows {
+            row_widths.push(
+                self.keys[first..first + length]
+                    .iter()
+                    .map(|key| key.width)
+                    .sum::<f32>(),
+            );
+            first += length;
+        }
+
+        let widest = row_widths.iter().cloned().fold(1.0, f32::max);
+        let unit = (bounds[SIZE_WIDTH].saturating_sub(KEY_GAP)) as f32 / widest;
+        let row_count = self.rows.len().max(1) as u32;
+        let row_height = bounds[SIZE_HEIGHT].saturating_sub(KEY_GAP) / row_count;
+        let mut rects = vec![];
+        let mut first = 0;
+
+        for (row, length) in self.rows.iter().enumerate() {
+            let mut x = KEY_GAP as f32 + (widest - row_widths[row]) * unit / 2.0;
+            let y = KEY_GAP as i32 + (row as u32 * row_height) as i32;
+
+            for key in &self.keys[first..first + length] {
+                let width = key.width * unit;
+
+                rects.push(Rect::new(
+                    x.round() as i32,
+                    y,
+                    ((width - KEY_GAP as f32).round() as u32).max(1),
+                    row_height.saturating_sub(KEY_GAP).max(1),
+                ));
+                x += width;
+            }
+
+            first += length;
+        }
+
+        rects
+    }
+
+    /// Internal function that returns the index of the key at the absolute position `points`.
+    fn key_at(&mut self, points: &[i32]) -> Option<usize> {
+        let point = Point::new(
+            points[POINT_X] - self.get_config().to_x(0),
+            points[POINT_Y] - self.get_config().to_y(0),
+        );
+
+        self.key_rects()
+            .iter()
+            .position(|rect| rect.contains_point(point))
+    }
+
+    /// Internal function that returns the label of `key`.
+    fn label(&self, key: &KeyDef) -> String {
+        match key.action {
+            KeyAction::Character(' ', _) => String::from("Space"),
+            KeyAction::Character(normal, shifted) => {
+                if self.shifted {
+                    shifted.to_string()
+                } else {
+                    normal.to_string()
+                }
+            }
+            KeyAction::Special(Keycode::Backspace) => String::from("Bksp"),
+            KeyAction::Special(Keycode::Return) => String::from("Enter"),
+            KeyAction::Special(Keycode::Left) => String::from("<"),
+            KeyAction::Special(Keycode::Right) => String::from(">"),
+            KeyAction::Special(keycode) => keycode.name(),
+            KeyAction::Shift => String::from("Shift"),
+        }
+    }
+
+    /// Internal function that returns the keycode and modifiers of the physical key that `action`
+    /// stands for.
+    fn keycode(&self, action: KeyAction) -> (Option<Keycode>, Mod) {
+        match action {
+            KeyAction::Character(normal, _) => (
+                Keycode::from_i32(normal as i32),
+                if self.shifted {
+                    Mod::LSHIFTMOD
+                } else {
+                    Mod::NOMOD
+                },
+            ),
+            KeyAction::Special(keycode) => (Some(keycode), Mod::NOMOD),
+            KeyAction::Shift => (Some(Keycode::LShift), Mod::NOMOD),
+        }
+    }
+
+    /// Internal function that presses the key at `index`, synthesizing its `KeyDown` event, and
+    /// its `TextInput` event if it types a character.
+    fn press_key(&mut self, index: usize) {
+        let action = self.keys[index].action;
+        let timestamp = clock::now_millis() as u32;
+
+        if action == KeyAction::Shift {
+            self.shifted = !self.shifted;
+            return;
+        }
+
+        let (keycode, keymod) = self.keycode(action);
+
+        self.synthesized_events.push(Event::KeyDown {
+            timestamp,
+            window_id: 0,
+            keycode,
+            scancode: None,
+            keymod,
+            repeat: false,
+        });
+
+        if let KeyAction::Character(normal, shifted) = action {
+            let text = if self.shifted { shifted } else { normal };
+
+            self.synthesized_events.push(Event::TextInput {
+                timestamp,
+                window_id: 0,
+                text: text.to_string(),
+            });
+            self.shifted = false;
+        }
+    }
+
+    /// Internal function that releases the key at `index`, synthesizing its `KeyUp` event.
+    fn release_key(&mut self, index: usize) {
+        let action = self.keys[index].action;
+
+        if action != KeyAction::Shift {
+            let (keycode, keymod) = self.keycode(action);
+
+            self.synthesized_events.push(Event::KeyUp {
+                timestamp: clock::now_millis() as u32,
+                window_id: 0,
+                keycode,
+                scancode: None,
+                keymod,
+                repeat: false,
+            });
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `OnScreenKeyboardWidget`.
+impl Widget for OnScreenKeyboardWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(208, 212, 218));
+            let key_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(255, 255, 255));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(232, 238, 250));
+            let pressed_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(150, 170, 210));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(150, 150, 150));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 16);
+            let rects = self.key_rects();
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+            let keys: Vec<(Rect, Color, Texture, Rect)> = self
+                .keys
+                .iter()
+                .enumerate()
+                .map(|(index, key)| {
+                    let rect = rects[index];
+                    let color = if self.pressed == Some(index)
+                        || (key.action == KeyAction::Shift && self.shifted)
+                    {
+                        pressed_color
+                    } else if self.hovered == Some(index) {
+                        hover_color
+                    } else {
+                        key_color
+                    };
+                    let surface = font
+                        .render(&self.label(key))
+                        .blended(text_color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let label_texture = texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let TextureQuery { width, height, .. } = label_texture.query();
+                    let center = rect.center();
+
+                    (
+                        rect,
+                        color,
+                        label_texture,
+                        Rect::new(
+                            center.x() - width as i32 / 2,
+                            center.y() - height as i32 / 2,
+                            width,
+                            height,
+                        ),
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (rect, color, label_texture, label_rect) in &keys {
+                    let outline = Rect::new(
+                        rect.x() - 1,
+                        rect.y() - 1,
+                        rect.width() + 2,
+                        rect.height() + 2,
+                    );
+
+                    texture.set_draw_color(border_color);
+                    fill_rounded_rect(texture, outline, KEY_RADIUS + 1);
+                    texture.set_draw_color(*color);
+                    fill_rounded_rect(texture, *rect, KEY_RADIUS);
+                    texture.set_clip_rect(*rect);
+                    texture.copy(label_texture, None, *label_rect).unwrap();
+                    texture.set_clip_rect(None);
+                }
+            })
+            .unwrap();
+
+            // Safety: the label textures are only used while drawing this `Widget`.
+            for (_, _, label_texture, _) in keys {
+                unsafe {
+                    label_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Stops highlighting the key under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hovered = None;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the key under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let hovered = self.key_at(&points);
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Presses the key under the mouse when the button is pressed, and releases it when the button
+    /// is released.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                let position = self.mouse_position.clone();
+
+                if let Some(index) = self.key_at(&position) {
+                    self.pressed = Some(index);
+                    self.press_key(index);
+                    self.get_config().set_invalidated(true);
+                }
+            } else if let Some(index) = self.pressed.take() {
+                self.release_key(index);
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Leaves the keyboard focus with the `Widget` being typed into.
+    fn takes_focus_on_click(&mut self) -> bool {
+        false
+    }
+
+    /// Hands the events synthesized by pressing keys to the `Engine`.
+    fn take_synthesized_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.synthesized_events)
+    }
+
+    /// Redraws the keyboard when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_SECONDARY
+            | CONFIG_COLOR_HOVER
+            | CONFIG_COLOR_SELECTED
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_TEXT
+            | CONFIG_FONT_SIZE => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,434 @@
+// Pushrod Widget Library
+// Oscilloscope Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+
+/// This is the number of horizontal divisions of the graticule.
+const HORIZONTAL_DIVISIONS: i32 = 10;
+
+/// This is the number of vertical divisions of the graticule.
+const VERTICAL_DIVISIONS: i32 = 8;
+
+/// This is the storage object for the `OscilloscopeWidget`.  It stores the config, properties,
+/// callback registry, the trace, and the persistence of the trace.
+pub struct OscilloscopeWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    trace: OscilloscopeTrace,
+    persistence: f32,
+    full_redraw: bool,
+}
+
+/// This is the trace of an `OscilloscopeWidget`: the ring buffer of samples, the scales they are
+/// drawn with, and the points last computed from them.  It is kept apart from the `Widget` so that
+/// the points can be computed on a worker thread as its `ParallelTick`.
+struct OscilloscopeTrace {
+    samples: VecDeque<f32>,
+    capacity: usize,
+    samples_per_pixel: f64,
+    amplitude_scale: f32,
+    size: (u32, u32),
+    points: Option<((u32, u32), Vec<Point>)>,
+}
+
+/// This is the implementation of the `OscilloscopeWidget`, which draws a trace of streaming sample
+/// data, such as audio, over a graticule.  Samples are pushed into a ring buffer, which drops the
+/// oldest samples when full, and the newest samples are drawn at the right edge.  The time scale
+/// sets how many samples each pixel spans; when it spans more than one, the trace shows the lowest
+/// and highest sample of each pixel.  The amplitude scale sets the size of the trace: at `1.0`,
+/// samples from `-1.0` to `1.0` span the height of the `Widget`.
+///
+/// With persistence, the previous traces are not cleared, but fade out gradually, like the phosphor
+/// of an analog oscilloscope.  The texture is only drawn again when new samples arrive, or the
+/// scales or colors change.  The screen is `CONFIG_COLOR_BASE`, the graticule is drawn in
+/// `CONFIG_COLOR_SECONDARY`, and the trace in `CONFIG_COLOR_TEXT`.
+///
+/// When the `Engine` is configured with more than one tick thread, the points of the trace are
+/// computed on a worker thread before the `Widget` is drawn.
+impl OscilloscopeWidget {
+    /// Creates a new `OscilloscopeWidget`, given the `x, y, w, h` coordinates, and the number of
+    /// samples kept in its ring buffer.
+    pub fn new(points: Points, size: Size, capacity: usize) -> Self {
+        let capacity = capacity.max(2);
+
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            trace: OscilloscopeTrace {
+                samples: VecDeque::with_capacity(capacity),
+                capacity,
+                samples_per_pixel: 1.0,
+                amplitude_scale: 1.0,
+                size: (0, 0),
+                points: None,
+            },
+            persistence: 0.0,
+            full_redraw: true,
+        }
+    }
+
+    /// Adds `samples` to the ring buffer, dropping the oldest samples if it is full.
+    pub fn push_samples(&mut self, samples: &[f32]) {
+        if samples.is_empty() {
+            return;
+        }
+
+        let trace = &mut self.trace;
+        let start = samples.len().saturating_sub(trace.capacity);
+        let overflow = (trace.samples.len() + samples.len() - start).saturating_sub(trace.capacity);
+
+        trace.samples.drain(..overflow);
+        trace.samples.extend(&samples[start..]);
+        trace.points = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the samples in the ring buffer, from oldest to newest.
+    pub fn get_samples(&self) -> Vec<f32> {
+        self.trace.samples.iter().copied().collect()
+    }
+
+    /// Removes all of the samples, and clears the screen.
+    pub fn clear(&mut self) {
+        self.trace.samples.clear();
+        self.redraw_screen();
+    }
+
+    /// Sets the number of samples kept in the ring buffer, dropping the oldest samples if there are
+    /// more.  The capacity is at least `2`.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.trace.capacity = capacity.max(2);
+
+        let overflow = self.trace.samples.len().saturating_sub(self.trace.capacity);
+
+        self.trace.samples.drain(..overflow);
+        self.redraw_screen();
+    }
+
+    /// Retrieves the number of samples kept in the ring buffer.
+    pub fn get_capacity(&self) -> usize {
+        self.trace.capacity
+    }
+
+    /// Sets the time scale, as the number of samples each pixel spans.  This is `1.0` by default.
+    pub fn set_time_scale(&mut self, samples_per_pixel: f64) {
+        if samples_per_pixel > 0.0 {
+            self.trace.samples_per_pixel = samples_per_pixel;
+            self.redraw_screen();
+        }
+    }
+
+    /// Retrieves the time scale, as the number of samples each pixel spans.
+    pub fn get_time_scale(&self) -> f64 {
+        self.trace.samples_per_pixel
+    }
+
+    /// Sets the amplitude scale.  At `1.0`, the default, samples from `-1.0` to `1.0` span the
+    /// height of the `Widget`.
+    pub fn set_amplitude_scale(&mut self, scale: f32) {
+        self.trace.amplitude_scale = scale;
+        self.redraw_screen();
+    }
+
+    /// Retrieves the amplitude scale.
+    pub fn get_amplitude_scale(&self) -> f32 {
+        self.trace.amplitude_scale
+    }
+
+    /// Sets the persistence of the trace, from `0.0` to `1.0`.  At `0.0`, the default, the screen
+    /// is cleared before each trace is drawn.  Otherwise, previous traces fade by `1.0 -
+    /// persistence` each time new samples are drawn, so higher values leave longer trails.
+    pub fn set_persistence(&mut self, persistence: f32) {
+        self.persistence = persistence.clamp(0.0, 1.0);
+        self.redraw_screen();
+    }
+
+    /// Retrieves the persistence of the trace.
+    pub fn get_persistence(&self) -> f32 {
+        self.persistence
+    }
+
+    /// Internal function that clears the screen, including any persisted traces, and computes the
+    /// trace again the next time the `Widget` is drawn.
+    fn redraw_screen(&mut self) {
+        self.trace.points = None;
+        self.full_redraw = true;
+        self.get_config().set_invalidated(true);
+    }
+}
+
+/// This is the implementation of the `OscilloscopeTrace`.
+impl OscilloscopeTrace {
+    /// Returns the points of the trace for a screen of `width` by `height` pixels, computing them
+    /// if the samples, the scales, or the size have changed since they were last computed.
+    fn points(&mut self, width: u32, height: u32) -> &[Point] {
+        if self.points.as_ref().map(|(size, _)| *size) != Some((width, height)) {
+            self.points = Some(((width, height), self.compute_points(width, height)));
+        }
+
+        &self.points.as_ref().unwrap().1
+    }
+
+    /// Computes the points of the trace for a screen of `width` by `height` pixels.
+    fn compute_points(&self, width: u32, height: u32) -> Vec<Point> {
+        let visible =
+            ((width as f64 * self.samples_per_pixel).ceil() as usize).min(self.samples.len());
+        let first = self.samples.len() - visible;
+        let middle = height as f32 / 2.0;
+        let scale = middle * self.amplitude_scale;
+        let y_of = |sample: f32| (middle - sample * scale).round() as i32;
+        let x_of = |index: usize| {
+            width as i32 - 1 - ((visible - 1 - index) as f64 / self.samples_per_pixel) as i32
+        };
+
+        if self.samples_per_pixel <= 1.0 {
+            return (0..visible)
+                .map(|index| Point::new(x_of(index), y_of(self.samples[first + index])))
+                .collect();
+        }
+
+        // When each pixel spans several samples, the trace runs from the lowest to the highest
+        // sample of each pixel, so that no peaks are lost.
+        let mut points = vec![];
+        let mut index = 0;
+
+        while index < visible {
+            let x = x_of(index);
+            let mut low = self.samples[first + index];
+            let mut high = low;
+
+            index += 1;
+
+            while index < visible && x_of(index) == x {
+                low = low.min(self.samples[first + index]);
+                high = high.max(self.samples[first + index]);
+                index += 1;
+            }
+
+            points.push(Point::new(x, y_of(high)));
+            points.push(Point::new(x, y_of(low)));
+        }
+
+        points
+    }
+}
+
+/// This is the `ParallelTick` implementation of the `OscilloscopeTrace`.
+impl ParallelTick for OscilloscopeTrace {
+    /// Computes the points of the trace for the size of the `Widget`, if they are out of date.
+    fn parallel_tick(&mut self) -> bool {
+        let (width, height) = self.size;
+        let stale = self.points.as_ref().map(|(size, _)| *size) != Some(self.size);
+
+        if stale {
+            self.points(width, height);
+        }
+
+        stale
+    }
+}
+
+/// This is the `Widget` implementation of the `OscilloscopeWidget`.
+impl Widget for OscilloscopeWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            // Persisted traces are lost whenever the texture is recreated.
+            if self.texture_store.get_optional_ref().is_none()
+                || self.texture_store.get_width() != bounds[SIZE_WIDTH]
+                || self.texture_store.get_height() != bounds[SIZE_HEIGHT]
+            {
+                self.full_redraw = true;
+            }
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(0, 24, 0));
+            let graticule_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(0, 80, 0));
+            let trace_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(64, 255, 64));
+            let width = bounds[SIZE_WIDTH] as i32;
+            let height = bounds[SIZE_HEIGHT] as i32;
+            let points = self.trace.points(bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]);
+            let fade = if self.full_redraw || self.persistence <= 0.0 {
+                base_color
+            } else {
+                Color::RGBA(
+                    base_color.r,
+                    base_color.g,
+                    base_color.b,
+                    ((1.0 - self.persistence) * 255.0).round().max(1.0) as u8,
+                )
+            };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_blend_mode(BlendMode::Blend);
+                texture.set_draw_color(fade);
+                texture
+                    .fill_rect(Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]))
+                    .unwrap();
+                texture.set_blend_mode(BlendMode::None);
+
+                texture.set_draw_color(graticule_color);
+
+                for division in 1..HORIZONTAL_DIVISIONS {
+                    let x = width * division / HORIZONTAL_DIVISIONS;
+
+                    texture
+                        .draw_line(Point::new(x, 0), Point::new(x, height - 1))
+                        .unwrap();
+                }
+
+                for division in 1..VERTICAL_DIVISIONS {
+                    let y = height * division / VERTICAL_DIVISIONS;
+
+                    texture
+                        .draw_line(Point::new(0, y), Point::new(width - 1, y))
+                        .unwrap();
+                }
+
+                if points.len() > 1 {
+                    texture.set_draw_color(trace_color);
+                    texture.draw_lines(points).unwrap();
+                }
+            })
+            .unwrap();
+
+            self.full_redraw = false;
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Computes the points of the trace on a worker thread, for the current size of the `Widget`.
+    fn as_parallel_tick(&mut self) -> Option<&mut dyn ParallelTick> {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+        self.trace.size = (bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]);
+        Some(&mut self.trace)
+    }
+
+    /// Redraws the screen when its colors change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE | CONFIG_COLOR_SECONDARY | CONFIG_COLOR_TEXT => self.redraw_screen(),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn, without any persisted traces.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.full_redraw = true;
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::widget_cache::WidgetCache;
+    use crate::render::{make_points, make_points_origin, make_size};
+
+    #[test]
+    fn test_parallel_tick() {
+        let mut widget = OscilloscopeWidget::new(make_points(0, 0), make_size(10, 10), 16);
+
+        widget.push_samples(&[0.0, 1.0, -1.0]);
+
+        assert!(widget.as_parallel_tick().unwrap().parallel_tick());
+        assert!(!widget.as_parallel_tick().unwrap().parallel_tick());
+        assert_eq!(
+            widget.trace.points(10, 10).to_vec(),
+            vec![Point::new(7, 5), Point::new(8, 0), Point::new(9, 10)]
+        );
+
+        widget.set_amplitude_scale(0.5);
+
+        assert!(widget.as_parallel_tick().unwrap().parallel_tick());
+    }
+
+    #[test]
+    fn test_parallel_tick_on_pool() {
+        let mut cache = WidgetCache::new();
+
+        cache.add_widget(
+            Box::new(BaseWidget::new(make_points_origin(), make_size(10, 10))),
+            "base",
+        );
+
+        let scope = cache.add_widget(
+            Box::new(OscilloscopeWidget::new(
+                make_points(0, 0),
+                make_size(10, 10),
+                16,
+            )),
+            "scope",
+        );
+
+        {
+            let mut widget = cache.get_container_by_id(scope.id()).widget.borrow_mut();
+
+            widget
+                .as_any()
+                .downcast_mut::<OscilloscopeWidget>()
+                .unwrap()
+                .push_samples(&[0.5, -0.5]);
+            widget.set_invalidated(false);
+        }
+
+        cache.set_tick_threads(2);
+        cache.tick(&[]);
+
+        let mut widget = cache.get_container_by_id(scope.id()).widget.borrow_mut();
+
+        assert!(widget.get_config().invalidated());
+
+        let scope = widget
+            .as_any()
+            .downcast_mut::<OscilloscopeWidget>()
+            .unwrap();
+
+        assert_eq!(
+            scope.trace.points.as_ref().unwrap().1,
+            vec![Point::new(8, 3), Point::new(9, 8)]
+        );
+    }
+}
@@ -0,0 +1,491 @@
+// Pushrod Widget Library
+// Password Field Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{draw_circle, fill_circle};
+use crate::render::clock;
+use crate::render::input_settings::{handle_edit_key, CaretBlink};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between the border of the `PasswordFieldWidget` and its text.
+const TEXT_PADDING: u32 = 4;
+
+/// This is the default character drawn in place of each character of the password.
+const DEFAULT_MASK: char = '\u{2022}';
+
+/// This is the callback type that is used when an `on_value_changed` callback is triggered from
+/// this `Widget`.  The password is not passed to the callback; use `get_value` to retrieve it.
+pub type OnPasswordChangedCallbackType =
+    Option<Box<dyn FnMut(&mut PasswordFieldWidget, &[WidgetContainer], &[LayoutContainer])>>;
+
+/// This is the storage object for the `PasswordFieldWidget`.  It stores the config, properties,
+/// callback registry, the font name and size, the password being edited, and the cursor and scroll
+/// positions.
+pub struct PasswordFieldWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    value: Vec<char>,
+    cursor: usize,
+    scroll_x: i32,
+    offsets: Vec<u32>,
+    mask: char,
+    revealed: bool,
+    reveal_button: bool,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_value_changed: OnPasswordChangedCallbackType,
+}
+
+/// This is the implementation of the `PasswordFieldWidget`, a single-line text field that draws a
+/// bullet in place of each character typed.  An optional eye button at its right edge reveals the
+/// password while it is toggled on.
+///
+/// The password is never stored in the `WidgetConfig`, so it cannot be read through `get_text`, or
+/// seen in the `Inspector`; it is only available through `get_value`.  For the same reason, the
+/// field does not support copying or cutting its contents.
+impl PasswordFieldWidget {
+    /// Creates a new `PasswordFieldWidget`, given the path to the font file, the size of the font in
+    /// pixels, and the `x, y, w, h` coordinates.
+    pub fn new(font_name: String, font_size: i32, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            value: vec![],
+            cursor: 0,
+            scroll_x: 0,
+            offsets: vec![0],
+            mask: DEFAULT_MASK,
+            revealed: false,
+            reveal_button: true,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_value_changed: None,
+        }
+    }
+
+    /// Replaces the password, moving the cursor to the end.  This does not trigger the
+    /// `on_value_changed` callback.
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.chars().collect();
+        self.cursor = self.value.len();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the password.  This is the only way to read the password from the `Widget`.
+    pub fn get_value(&self) -> String {
+        self.value.iter().collect()
+    }
+
+    /// Sets the character drawn in place of each character of the password.
+    pub fn set_mask(&mut self, mask: char) {
+        self.mask = mask;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the character drawn in place of each character of the password.
+    pub fn get_mask(&self) -> char {
+        self.mask
+    }
+
+    /// Shows or masks the password, as if the eye button had been toggled.
+    pub fn set_revealed(&mut self, revealed: bool) {
+        self.revealed = revealed;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether or not the password is shown.
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    /// Sets whether or not the eye button that reveals the password is shown.  It is shown by
+    /// default.  Hiding the button also masks the password.
+    pub fn set_reveal_button(&mut self, reveal_button: bool) {
+        self.reveal_button = reveal_button;
+
+        if !reveal_button {
+            self.revealed = false;
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether or not the eye button that reveals the password is shown.
+    pub fn get_reveal_button(&self) -> bool {
+        self.reveal_button
+    }
+
+    /// Assigns the callback closure that will be used when the password is changed by the user.
+    pub fn on_value_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut PasswordFieldWidget, &[WidgetContainer], &[LayoutContainer]) + 'static,
+    {
+        self.on_value_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_value_changed` callback.
+    fn call_value_changed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_value_changed.take() {
+            cb(self, widgets, layouts);
+            self.on_value_changed = Some(cb);
+        }
+    }
+
+    /// Returns the width of the eye button, which is square, or `0` if it is not shown.
+    fn reveal_button_width(&mut self) -> u32 {
+        if self.reveal_button {
+            self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT]
+        } else {
+            0
+        }
+    }
+
+    /// Moves the cursor to the character closest to the last known mouse position.
+    fn move_cursor_to_mouse(&mut self) {
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(TEXT_PADDING as i32)
+            + self.scroll_x;
+
+        self.cursor = self
+            .offsets
+            .windows(2)
+            .position(|pair| x < ((pair[0] + pair[1]) / 2) as i32)
+            .unwrap_or(self.offsets.len() - 1)
+            .min(self.value.len());
+    }
+}
+
+/// Draws an eye within `bounds`, crossed out when the password is masked.
+fn draw_eye(c: &mut Canvas<Window>, bounds: Rect, revealed: bool) {
+    let center = bounds.center();
+    let radius = bounds.width().min(bounds.height()) / 4;
+
+    draw_circle(c, center, radius);
+    fill_circle(c, center, radius / 2);
+
+    // The outline of the eye is drawn as two lids meeting at its corners.
+    let corner = radius as i32 * 2;
+
+    for (dx, dy) in &[(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+        c.draw_line(
+            Point::new(center.x() + dx * corner, center.y()),
+            Point::new(
+                center.x() + dx * radius as i32 / 2,
+                center.y() + dy * radius as i32,
+            ),
+        )
+        .unwrap();
+    }
+
+    if !revealed {
+        c.draw_line(
+            Point::new(center.x() - corner, center.y() + corner / 2),
+            Point::new(center.x() + corner, center.y() - corner / 2),
+        )
+        .unwrap();
+    }
+}
+
+/// This is the `Widget` implementation of the `PasswordFieldWidget`.
+impl Widget for PasswordFieldWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let button_width = self.reveal_button_width();
+            let text_width = bounds[SIZE_WIDTH]
+                .saturating_sub(TEXT_PADDING * 2 + button_width)
+                .max(1) as i32;
+            let shown: Vec<char> = if self.revealed {
+                self.value.clone()
+            } else {
+                vec![self.mask; self.value.len()]
+            };
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+            let line_height = font.recommended_line_spacing().max(1);
+
+            self.offsets = (0..=shown.len())
+                .map(|end| {
+                    let prefix: String = shown[..end].iter().collect();
+
+                    font.size_of(&prefix).map(|(w, _)| w).unwrap_or(0)
+                })
+                .collect();
+
+            // The text is scrolled horizontally so that the cursor is always within the field.
+            let caret_x = self.offsets[self.cursor.min(shown.len())] as i32;
+
+            if caret_x - self.scroll_x >= text_width {
+                self.scroll_x = caret_x - text_width + 1;
+            } else if caret_x < self.scroll_x {
+                self.scroll_x = caret_x;
+            }
+
+            let scroll_x = self.scroll_x;
+            let text_y = (bounds[SIZE_HEIGHT] as i32 - line_height) / 2;
+            let caret = if self.focused && self.caret.is_shown() {
+                Some(TEXT_PADDING as i32 + caret_x - scroll_x)
+            } else {
+                None
+            };
+            let font_texture = if shown.is_empty() {
+                None
+            } else {
+                let shown_text: String = shown.iter().collect();
+                let surface = font
+                    .render(&shown_text)
+                    .blended(text_color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+
+                Some(
+                    texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap(),
+                )
+            };
+            let revealed = self.revealed;
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let Some(font_texture) = &font_texture {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+                    let visible_width = (width as i32 - scroll_x).min(text_width);
+
+                    if visible_width > 0 {
+                        texture
+                            .copy(
+                                font_texture,
+                                Rect::new(scroll_x, 0, visible_width as u32, height),
+                                Rect::new(
+                                    TEXT_PADDING as i32,
+                                    text_y,
+                                    visible_width as u32,
+                                    height,
+                                ),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                texture.set_draw_color(text_color);
+
+                if let Some(x) = caret {
+                    texture
+                        .fill_rect(Rect::new(x, text_y, 1, line_height as u32))
+                        .unwrap();
+                }
+
+                if button_width > 0 {
+                    draw_eye(
+                        texture,
+                        Rect::new(
+                            (bounds[SIZE_WIDTH] - button_width) as i32,
+                            0,
+                            button_width,
+                            bounds[SIZE_HEIGHT],
+                        ),
+                        revealed,
+                    );
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            if let Some(font_texture) = font_texture {
+                // Safety: the font texture is only used within this function.
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while this `Widget` has focus.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Tracks the mouse position, so that clicks can move the cursor, or toggle the eye button.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Toggles whether the password is shown when the eye button is clicked, otherwise moves the
+    /// cursor to the clicked position.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH];
+            let button_width = self.reveal_button_width();
+            let button_left = self
+                .get_config()
+                .to_x(width.saturating_sub(button_width) as i32);
+
+            if self.reveal_button && self.mouse_position[POINT_X] >= button_left {
+                self.revealed = !self.revealed;
+            } else {
+                self.move_cursor_to_mouse();
+                self.caret.restart(clock::now_millis());
+            }
+
+            self.get_config().set_invalidated(true);
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles text input and cursor navigation while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let (value_changed, cursor_moved) = match event {
+            Event::TextInput { text, .. } => {
+                for c in text.chars() {
+                    self.value.insert(self.cursor, c);
+                    self.cursor += 1;
+                }
+
+                (true, true)
+            }
+            // Copying and cutting are deliberately not supported.
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } => handle_edit_key(keycode, &mut self.value, &mut self.cursor),
+            _ => (false, false),
+        };
+
+        if cursor_moved {
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        if value_changed {
+            self.call_value_changed_callback(_widgets, _layouts);
+        }
+    }
+
+    /// Monitors for changes in the colors or font size.  The password cannot be set through
+    /// `CONFIG_TEXT`.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _) | (CONFIG_COLOR_BASE, _) | (CONFIG_COLOR_BORDER, _) => {
+                self.get_config().set_invalidated(true)
+            }
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus.
+    fn accepts_text_input(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
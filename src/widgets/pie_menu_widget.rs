@@ -0,0 +1,379 @@
+// Pushrod Widget Library
+// Pie Menu Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{draw_circle, fill_arc, point_at_angle};
+use crate::render::layer::Layer;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{make_points_origin, make_size, Points, POINT_X, POINT_Y};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the callback type that is used when an `on_option_selected` callback is triggered from
+/// this `Widget`.  It receives the index of the option that was chosen.
+pub type OnOptionSelectedCallbackType =
+    Option<Box<dyn FnMut(&mut PieMenuWidget, &[WidgetContainer], &[LayoutContainer], usize)>>;
+
+/// This is the storage object for the `PieMenuWidget`.  It stores the config, properties, callback
+/// registry, the options, the radius of the menu and its center, and the highlighted option.
+pub struct PieMenuWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    options: Vec<String>,
+    radius: u32,
+    inner_radius: u32,
+    highlighted: Option<usize>,
+    armed: bool,
+    on_option_selected: OnOptionSelectedCallbackType,
+}
+
+/// This is the implementation of the `PieMenuWidget`, a radial menu that opens centered on the
+/// mouse, with its options laid out as wedges of a ring, starting at the top and continuing
+/// clockwise.  The wedge under the mouse is highlighted, and releasing the mouse button over it
+/// chooses that option, triggering `on_option_selected`, and closes the menu.
+///
+/// The menu can be used in two ways: opened when a button is pressed, and chosen from by dragging
+/// to a wedge and releasing; or opened with a click, and chosen from with a second click.  Releasing
+/// the button in the center of the menu, without having pressed it there, leaves the menu open,
+/// while clicking in the center closes it without choosing.
+///
+/// The menu is hidden until it is opened with `open_at`, and is drawn on the `Floating` `Layer`.
+/// Wedges are filled with `CONFIG_COLOR_BASE`, or `CONFIG_COLOR_HOVER` when highlighted, separated
+/// and outlined in `CONFIG_COLOR_BORDER`, and labelled in `CONFIG_COLOR_TEXT`, with the size in
+/// `CONFIG_FONT_SIZE`, or `14` if it is not set.
+impl PieMenuWidget {
+    /// Creates a new, hidden `PieMenuWidget` with the given `radius`, and `options`.  The center of
+    /// the ring, within a quarter of the radius, chooses nothing.
+    pub fn new(radius: u32, options: Vec<String>) -> Self {
+        let radius = radius.max(1);
+        let mut config = WidgetConfig::new(make_points_origin(), make_size(radius * 2, radius * 2));
+
+        config.set_numeric(CONFIG_LAYER, Layer::Floating.index() as i32);
+        config.hide();
+
+        let mut texture_store = TextureStore::default();
+
+        texture_store.set_blended(true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store,
+            options,
+            radius,
+            inner_radius: radius / 4,
+            highlighted: None,
+            armed: false,
+            on_option_selected: None,
+        }
+    }
+
+    /// Opens the menu centered on `points`, usually the position of the mouse.
+    pub fn open_at(&mut self, points: Points) {
+        let radius = self.radius as i32;
+
+        self.highlighted = None;
+        self.armed = false;
+        self.get_config().set_point(
+            CONFIG_ORIGIN,
+            points[POINT_X] - radius,
+            points[POINT_Y] - radius,
+        );
+        self.get_config().show();
+    }
+
+    /// Closes the menu without choosing an option.
+    pub fn close(&mut self) {
+        self.highlighted = None;
+        self.armed = false;
+        self.get_config().hide();
+    }
+
+    /// Indicates whether the menu is open.
+    pub fn is_open(&self) -> bool {
+        !self.config.is_hidden()
+    }
+
+    /// Replaces the options of the menu.
+    pub fn set_options(&mut self, options: Vec<String>) {
+        self.options = options;
+        self.highlighted = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the options of the menu.
+    pub fn get_options(&self) -> &[String] {
+        &self.options
+    }
+
+    /// Sets the radius of the center of the menu, in which nothing is chosen.  It is limited to
+    /// the radius of the menu.
+    pub fn set_inner_radius(&mut self, inner_radius: u32) {
+        self.inner_radius = inner_radius.min(self.radius);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the radius of the center of the menu, in which nothing is chosen.
+    pub fn get_inner_radius(&self) -> u32 {
+        self.inner_radius
+    }
+
+    /// Retrieves the index of the option under the mouse, if any.
+    pub fn get_highlighted(&self) -> Option<usize> {
+        self.highlighted
+    }
+
+    /// Assigns the callback closure that will be used when an option is chosen.
+    pub fn on_option_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut PieMenuWidget, &[WidgetContainer], &[LayoutContainer], usize) + 'static,
+    {
+        self.on_option_selected = Some(Box::new(callback));
+    }
+
+    /// Internal function that returns the number of degrees covered by each wedge.
+    fn sweep(&self) -> f64 {
+        360.0 / self.options.len().max(1) as f64
+    }
+
+    /// Internal function that returns the index of the wedge at the absolute position `points`.
+    /// The first wedge is centered at the top of the menu.
+    fn option_at(&mut self, points: &[i32]) -> Option<usize> {
+        if self.options.is_empty() {
+            return None;
+        }
+
+        let radius = self.radius as i32;
+        let dx = points[POINT_X] - self.get_config().to_x(radius);
+        let dy = points[POINT_Y] - self.get_config().to_y(radius);
+        let distance = ((dx * dx + dy * dy) as f64).sqrt();
+
+        if distance < self.inner_radius as f64 || distance > self.radius as f64 {
+            return None;
+        }
+
+        let sweep = self.sweep();
+        let angle = (dx as f64).atan2(-dy as f64).to_degrees() + sweep / 2.0;
+
+        Some((angle.rem_euclid(360.0) / sweep) as usize % self.options.len())
+    }
+}
+
+/// This is the `Widget` implementation of the `PieMenuWidget`.
+impl Widget for PieMenuWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let diameter = self.radius * 2;
+
+            self.texture_store
+                .create_or_resize_texture(c, diameter, diameter);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(245, 245, 245));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(192, 208, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(96, 96, 96));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 14);
+            let radius = self.radius;
+            let inner_radius = self.inner_radius;
+            let center = Point::new(radius as i32, radius as i32);
+            let sweep = self.sweep();
+            let highlighted = self.highlighted;
+            let wedges = self.options.len();
+            let label_radius = (radius + inner_radius) as f64 / 2.0;
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+            let labels: Vec<(Texture, Rect)> = self
+                .options
+                .iter()
+                .enumerate()
+                .filter(|(_, option)| !option.is_empty())
+                .map(|(index, option)| {
+                    let surface = font
+                        .render(option)
+                        .blended(text_color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let label_texture = texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let TextureQuery { width, height, .. } = label_texture.query();
+                    let at = point_at_angle(center, label_radius, index as f64 * sweep);
+
+                    (
+                        label_texture,
+                        Rect::new(
+                            at.x() - width as i32 / 2,
+                            at.y() - height as i32 / 2,
+                            width,
+                            height,
+                        ),
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                texture.clear();
+
+                texture.set_draw_color(base_color);
+                fill_arc(texture, center, radius - 1, inner_radius, 0.0, 360.0);
+
+                if let Some(index) = highlighted {
+                    let start = index as f64 * sweep - sweep / 2.0;
+
+                    texture.set_draw_color(hover_color);
+                    fill_arc(
+                        texture,
+                        center,
+                        radius - 1,
+                        inner_radius,
+                        start,
+                        start + sweep,
+                    );
+                }
+
+                texture.set_draw_color(border_color);
+
+                if wedges > 1 {
+                    for index in 0..wedges {
+                        let angle = index as f64 * sweep - sweep / 2.0;
+
+                        texture
+                            .draw_line(
+                                point_at_angle(center, inner_radius as f64, angle),
+                                point_at_angle(center, (radius - 1) as f64, angle),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                draw_circle(texture, center, radius - 1);
+
+                if inner_radius > 0 {
+                    draw_circle(texture, center, inner_radius);
+                }
+
+                for (label_texture, rect) in &labels {
+                    texture.copy(label_texture, None, *rect).unwrap();
+                }
+            })
+            .unwrap();
+
+            // Safety: the label textures are only used while drawing this `Widget`.
+            for (label_texture, _) in labels {
+                unsafe {
+                    label_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Stops highlighting when the mouse leaves the menu.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.highlighted.is_some() {
+            self.highlighted = None;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the wedge under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let highlighted = self.option_at(&points);
+
+        if highlighted != self.highlighted {
+            self.highlighted = highlighted;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Chooses the highlighted option when the mouse button is released over it.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _state {
+            self.armed = true;
+        } else if let Some(index) = self.highlighted {
+            self.close();
+
+            if let Some(mut cb) = self.on_option_selected.take() {
+                cb(self, _widgets, _layouts, index);
+                self.on_option_selected = Some(cb);
+            }
+        } else if self.armed {
+            self.close();
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Redraws the menu when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE | CONFIG_COLOR_HOVER | CONFIG_COLOR_BORDER | CONFIG_COLOR_TEXT
+            | CONFIG_FONT_SIZE => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
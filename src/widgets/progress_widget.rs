@@ -15,6 +15,7 @@
 
 use crate::render::callbacks::CallbackRegistry;
 use crate::render::layout_cache::LayoutContainer;
+use crate::render::skin::{Skin, SkinPart, SkinState};
 use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
@@ -24,20 +25,31 @@ use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::video::Window;
 
+use crate::render::clock;
 use crate::render::texture_cache::TextureCache;
 use crate::render::texture_store::TextureStore;
 use sdl2::render::{Canvas, Texture};
 use std::any::Any;
 use std::collections::HashMap;
 
+/// This is the number of milliseconds it takes the indeterminate segment to travel from one end of
+/// the bar to the other and back again.
+const MARQUEE_PERIOD: u64 = 2000;
+
+/// This is the width of the indeterminate segment as a fraction of the width of the bar.
+const MARQUEE_WIDTH: f64 = 0.25;
+
 /// This is the storage object for the `ProgressWidget`.  It stores the config, properties, callback registry,
-/// the base widget, and progress from 0 to 100.
+/// the base widget, progress from 0 to 100, and whether or not the progress is indeterminate.
 pub struct ProgressWidget {
     config: WidgetConfig,
     system_properties: HashMap<i32, String>,
     callback_registry: CallbackRegistry,
     texture_store: TextureStore,
     progress: u8,
+    indeterminate: bool,
+    marquee_started: u64,
+    skin: Option<Skin>,
 }
 
 /// Creates a new `ProgressWidget`, which draws a progress bar inside a `BaseWidget`.
@@ -53,6 +65,9 @@ impl ProgressWidget {
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
             progress,
+            indeterminate: false,
+            marquee_started: 0,
+            skin: None,
         }
     }
 
@@ -72,12 +87,51 @@ impl ProgressWidget {
     pub fn get_progress(&mut self) -> u8 {
         self.progress
     }
+
+    /// Switches the widget between determinate and indeterminate mode.  In indeterminate mode, the
+    /// progress value is ignored, and a segment moves back and forth across the bar instead, for
+    /// operations of unknown duration.  The segment starts moving from the left edge of the bar.
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.indeterminate = indeterminate;
+        self.marquee_started = clock::now_millis();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether or not the widget is in indeterminate mode.
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    /// Internal function that returns the horizontal offset and width of the fill, given the
+    /// `width` of the bar.  In indeterminate mode, this is the current position of the moving
+    /// segment.
+    fn fill_span(&self, width: u32) -> (i32, u32) {
+        if !self.indeterminate {
+            return (
+                0,
+                (f64::from(width) * f64::from(self.progress) / 100.0) as u32,
+            );
+        }
+
+        let segment_width = (f64::from(width) * MARQUEE_WIDTH) as u32;
+        let travel = f64::from(width.saturating_sub(segment_width));
+        let phase = (clock::now_millis().saturating_sub(self.marquee_started) % MARQUEE_PERIOD)
+            as f64
+            / MARQUEE_PERIOD as f64;
+        let amount = if phase < 0.5 {
+            phase * 2.0
+        } else {
+            (1.0 - phase) * 2.0
+        };
+
+        ((travel * amount).round() as i32, segment_width)
+    }
 }
 
 /// This is the `Widget` implementation of the `ProgressWidget`.  It contains a `BaseWidget` within
 /// its bounds to draw the base background, then draws the progress fill over the top.
 impl Widget for ProgressWidget {
-    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
         if self.get_config().invalidated() {
             let bounds = self.get_config().get_size(CONFIG_SIZE);
 
@@ -85,20 +139,61 @@ impl Widget for ProgressWidget {
                 .create_or_resize_texture(c, bounds[0] as u32, bounds[1] as u32);
 
             let base_color = self.get_color(CONFIG_COLOR_SECONDARY);
-            let progress_width = (f64::from(self.get_size(CONFIG_SIZE)[0])
-                * (f64::from(self.progress))
-                / 100.0) as u32;
+            let (progress_x, progress_width) = self.fill_span(bounds[0]);
             let progress_height = self.get_size(CONFIG_SIZE)[1] - 2;
             let border_color = self.get_config().get_color(CONFIG_COLOR_BORDER);
             let bounds = self.get_config().get_size(CONFIG_SIZE);
 
+            if let Some(skin) = self.skin.as_ref() {
+                let state = SkinState::from_flags(self.config.is_enabled(), false, false);
+                let track = skin.get_patch(SkinPart::ProgressTrack, state).cloned();
+                let fill = skin.get_patch(SkinPart::ProgressFill, state).cloned();
+
+                c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                    texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                    texture.clear();
+                })
+                .unwrap();
+
+                if let Some(track) = track {
+                    let image = t.get_image(c, track.image.clone());
+
+                    c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                        track.draw(texture, image, Rect::new(0, 0, bounds[0], bounds[1]));
+                    })
+                    .unwrap();
+                }
+
+                if let Some(fill) = fill {
+                    let image = t.get_image(c, fill.image.clone());
+
+                    if progress_width > 0 {
+                        c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                            fill.draw(
+                                texture,
+                                image,
+                                Rect::new(progress_x, 0, progress_width, bounds[1]),
+                            );
+                        })
+                        .unwrap();
+                    }
+                }
+
+                return self.texture_store.get_optional_ref();
+            }
+
             c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
                 texture.set_draw_color(Color::RGB(255, 255, 255));
                 texture.clear();
 
                 texture.set_draw_color(base_color);
                 texture
-                    .fill_rect(Rect::new(1, 1, progress_width, progress_height))
+                    .fill_rect(Rect::new(
+                        1 + progress_x,
+                        1,
+                        progress_width,
+                        progress_height,
+                    ))
                     .unwrap();
 
                 texture.set_draw_color(border_color);
@@ -112,6 +207,32 @@ impl Widget for ProgressWidget {
         self.texture_store.get_optional_ref()
     }
 
+    /// Redraws the moving segment when the widget is in indeterminate mode.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.indeterminate {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    /// Stores the `Skin`, drawing its `SkinPart::ProgressTrack` and `SkinPart::ProgressFill` images
+    /// in place of the standard background, border and fill.
+    fn apply_skin(&mut self, skin: Option<&Skin>) {
+        self.skin = skin
+            .filter(|x| x.has_part(SkinPart::ProgressTrack) || x.has_part(SkinPart::ProgressFill))
+            .cloned();
+        self.texture_store.set_blended(self.skin.is_some());
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
@@ -0,0 +1,1212 @@
+// Pushrod Widget Library
+// Property Grid Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_polygon;
+use crate::render::clock;
+use crate::render::input_settings::CaretBlink;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::FontStyle;
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// This is the height of each row of the grid, in pixels.
+const ROW_HEIGHT: u32 = 24;
+
+/// This is the number of pixels between the edges of a cell and its contents.
+const CELL_PADDING: i32 = 6;
+
+/// This is the indentation of properties that belong to a group, in pixels.
+const GROUP_INDENT: i32 = 16;
+
+/// This is the size of the checkbox and color swatch editors, in pixels.
+const SWATCH_SIZE: u32 = 14;
+
+/// This is the width of the scroll indicator drawn when the rows do not fit vertically.
+const SCROLL_INDICATOR_WIDTH: u32 = 4;
+
+/// This is the color of the text of read-only properties.
+const READ_ONLY_COLOR: Color = Color::RGB(128, 128, 128);
+
+/// This is the value of a property in a `PropertyGridWidget`.  The type of the value determines
+/// the editor used to change it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// A line of text, edited in place.
+    Text(String),
+
+    /// A flag, edited with a checkbox.
+    Bool(bool),
+
+    /// One of a list of options, and the index of the selected option, edited with a drop-down
+    /// list.
+    Choice(Vec<String>, usize),
+
+    /// A color, edited in place as a `#RRGGBB` or `#RRGGBBAA` hex value.
+    Color(Color),
+}
+
+/// This is a property shown by a `PropertyGridWidget`.
+#[derive(Clone, Debug)]
+pub struct Property {
+    /// This is the group the property is shown under, or blank for no group.
+    pub group: String,
+
+    /// This is the name of the property, which identifies it within the grid.
+    pub name: String,
+
+    /// This is the value of the property.
+    pub value: PropertyValue,
+
+    /// This indicates that the value is shown, but cannot be changed.
+    pub read_only: bool,
+}
+
+/// This is the callback type that is used when an `on_property_changed` callback is triggered from
+/// this `Widget`.  It receives the name of the property, and its new value.
+pub type OnPropertyChangedCallbackType = Option<
+    Box<
+        dyn FnMut(
+            &mut PropertyGridWidget,
+            &[WidgetContainer],
+            &[LayoutContainer],
+            String,
+            PropertyValue,
+        ),
+    >,
+>;
+
+/// This is a row of a `PropertyGridWidget`: either the header of a group, or a property, by index.
+#[derive(Clone, Debug, PartialEq)]
+enum GridRow {
+    Group(String),
+    Property(usize),
+}
+
+/// This is the text of a property being edited in place, and the position of the cursor.
+struct TextEdit {
+    property: usize,
+    text: Vec<char>,
+    cursor: usize,
+}
+
+/// This is a single drawing operation, collected before the texture of the grid is drawn, so that
+/// text can be rendered outside of the texture canvas.
+enum DrawOp {
+    Fill(Rect, Color),
+    Outline(Rect, Color),
+    Line(Point, Point, Color),
+    Polygon(Vec<Point>, Color),
+    Text(Texture, Rect, Rect),
+}
+
+/// Formats `color` as a `#RRGGBB` hex value, or `#RRGGBBAA` if it is not opaque.
+fn format_color(color: Color) -> String {
+    if color.a == 255 {
+        format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b)
+    } else {
+        format!(
+            "#{:02X}{:02X}{:02X}{:02X}",
+            color.r, color.g, color.b, color.a
+        )
+    }
+}
+
+/// Parses a `#RRGGBB` or `#RRGGBBAA` hex value, with or without the `#`.
+fn parse_color(text: &str) -> Option<Color> {
+    let hex = text.trim().trim_start_matches('#');
+
+    if (hex.len() != 6 && hex.len() != 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |index: usize| u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok();
+
+    Some(Color::RGBA(
+        channel(0)?,
+        channel(1)?,
+        channel(2)?,
+        if hex.len() == 8 { channel(3)? } else { 255 },
+    ))
+}
+
+/// This is the storage object for the `PropertyGridWidget`.  It stores the config, properties,
+/// callback registry, the properties shown in the grid, the collapsed groups, the scroll position,
+/// and the state of the editors.
+pub struct PropertyGridWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    properties: Vec<Property>,
+    collapsed_groups: HashSet<String>,
+    name_column_width: Option<u32>,
+    scroll_y: usize,
+    selected: Option<usize>,
+    hovered_row: Option<usize>,
+    editing: Option<TextEdit>,
+    open_choice: Option<usize>,
+    hovered_option: Option<usize>,
+    mouse_position: Points,
+    focused: bool,
+    caret: CaretBlink,
+    on_property_changed: OnPropertyChangedCallbackType,
+}
+
+/// This is the implementation of the `PropertyGridWidget`, an inspector that shows a list of named
+/// properties and their values, in two columns.  Properties can be sorted into groups, which are
+/// shown under headers that collapse and expand when clicked.  Each value is changed with an editor
+/// suited to its type: text and colors are edited in place, flags with a checkbox, and choices with
+/// a drop-down list.  Changing a value triggers the `on_property_changed` callback.
+///
+/// While the grid has focus, `Up` and `Down` select a property, `Return` edits it, and `Space`
+/// toggles a flag.  While editing text, `Return` commits the change, and `Escape` cancels it.
+/// Group headers are filled with `CONFIG_COLOR_TRACK`, the selected property with
+/// `CONFIG_COLOR_SELECTED`, and checked checkboxes with `CONFIG_COLOR_SECONDARY`.  Text is drawn in
+/// `CONFIG_COLOR_TEXT`, with the size in `CONFIG_FONT_SIZE`, or `14` if it is not set.
+impl PropertyGridWidget {
+    /// Creates a new, empty `PropertyGridWidget` given the `x, y, w, h` coordinates.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            properties: Vec::new(),
+            collapsed_groups: HashSet::new(),
+            name_column_width: None,
+            scroll_y: 0,
+            selected: None,
+            hovered_row: None,
+            editing: None,
+            open_choice: None,
+            hovered_option: None,
+            mouse_position: vec![0, 0],
+            focused: false,
+            caret: CaretBlink::new(),
+            on_property_changed: None,
+        }
+    }
+
+    /// Adds a property called `name` with `value`, shown under `group`, or with no group if `group`
+    /// is blank.  Groups are shown in the order in which their first property was added.  If a
+    /// property called `name` already exists, its group and value are replaced.
+    pub fn add_property(&mut self, group: &str, name: &str, value: PropertyValue) {
+        match self.property_index(name) {
+            Some(index) => {
+                self.properties[index].group = String::from(group);
+                self.properties[index].value = value;
+            }
+            None => self.properties.push(Property {
+                group: String::from(group),
+                name: String::from(name),
+                value,
+                read_only: false,
+            }),
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Removes the property called `name`, returning `true` if it existed.
+    pub fn remove_property(&mut self, name: &str) -> bool {
+        match self.property_index(name) {
+            Some(index) => {
+                self.close_editors();
+                self.properties.remove(index);
+                self.selected = None;
+                self.hovered_row = None;
+                self.get_config().set_invalidated(true);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all of the properties from the grid.
+    pub fn clear_properties(&mut self) {
+        self.close_editors();
+        self.properties.clear();
+        self.selected = None;
+        self.hovered_row = None;
+        self.scroll_y = 0;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the properties shown in the grid, in the order in which they were added.
+    pub fn get_properties(&self) -> &[Property] {
+        &self.properties
+    }
+
+    /// Sets the value of the property called `name`, without triggering the
+    /// `on_property_changed` callback.  Unknown names are ignored.
+    pub fn set_value(&mut self, name: &str, value: PropertyValue) {
+        if let Some(index) = self.property_index(name) {
+            if self.editing.as_ref().map(|edit| edit.property) == Some(index) {
+                self.editing = None;
+            }
+
+            self.properties[index].value = value;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the value of the property called `name`, if it exists.
+    pub fn get_value(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| &property.value)
+    }
+
+    /// Sets whether the value of the property called `name` can be changed.  Read-only values are
+    /// drawn in gray.
+    pub fn set_read_only(&mut self, name: &str, read_only: bool) {
+        if let Some(index) = self.property_index(name) {
+            self.properties[index].read_only = read_only;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Expands or collapses the properties shown under `group`.  Groups are expanded by default.
+    pub fn set_group_expanded(&mut self, group: &str, expanded: bool) {
+        if expanded {
+            self.collapsed_groups.remove(group);
+        } else {
+            self.collapsed_groups.insert(String::from(group));
+        }
+
+        self.close_editors();
+        self.hovered_row = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the properties shown under `group` are expanded.
+    pub fn is_group_expanded(&self, group: &str) -> bool {
+        !self.collapsed_groups.contains(group)
+    }
+
+    /// Sets the width of the column of property names, in pixels.  By default, the names take up
+    /// two fifths of the width of the grid.
+    pub fn set_name_column_width(&mut self, width: u32) {
+        self.name_column_width = Some(width);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Assigns the callback closure that will be used when the value of a property is changed by
+    /// the user.
+    pub fn on_property_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(
+                &mut PropertyGridWidget,
+                &[WidgetContainer],
+                &[LayoutContainer],
+                String,
+                PropertyValue,
+            ) + 'static,
+    {
+        self.on_property_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that changes the value of the property at `index`, and triggers the
+    /// `on_property_changed` callback if it is different.
+    fn change_value(
+        &mut self,
+        index: usize,
+        value: PropertyValue,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if self.properties[index].value == value {
+            return;
+        }
+
+        self.properties[index].value = value.clone();
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_property_changed.take() {
+            let name = self.properties[index].name.clone();
+
+            cb(self, widgets, layouts, name, value);
+            self.on_property_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that returns the index of the property called `name`.
+    fn property_index(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|property| property.name == name)
+    }
+
+    /// Internal function that returns the rows of the grid: properties with no group first, then
+    /// each group header, followed by its properties if the group is expanded.
+    fn rows(&self) -> Vec<GridRow> {
+        let mut rows: Vec<GridRow> = (0..self.properties.len())
+            .filter(|index| self.properties[*index].group.is_empty())
+            .map(GridRow::Property)
+            .collect();
+        let mut groups: Vec<&str> = vec![];
+
+        for property in &self.properties {
+            if !property.group.is_empty() && !groups.contains(&property.group.as_str()) {
+                groups.push(&property.group);
+            }
+        }
+
+        for group in groups {
+            rows.push(GridRow::Group(String::from(group)));
+
+            if self.is_group_expanded(group) {
+                rows.extend(
+                    (0..self.properties.len())
+                        .filter(|index| self.properties[*index].group == group)
+                        .map(GridRow::Property),
+                );
+            }
+        }
+
+        rows
+    }
+
+    /// Internal function that returns the index of the row under the absolute `y` coordinate.
+    fn row_at(&mut self, y: i32) -> Option<usize> {
+        let y = y - self.get_config().to_y(0) + self.scroll_y as i32;
+
+        if y < 0 {
+            return None;
+        }
+
+        Some(y as usize / ROW_HEIGHT as usize).filter(|row| *row < self.rows().len())
+    }
+
+    /// Internal function that returns the width of the column of property names.
+    fn name_width(&mut self) -> u32 {
+        let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH];
+
+        self.name_column_width
+            .unwrap_or(width * 2 / 5)
+            .min(width.saturating_sub(SCROLL_INDICATOR_WIDTH))
+    }
+
+    /// Internal function that returns the area of the open drop-down list, in local coordinates.
+    /// The list opens below its property, or above it if there is not enough room below.
+    fn choice_popup_rect(&mut self) -> Option<Rect> {
+        let index = self.open_choice?;
+        let options = match &self.properties[index].value {
+            PropertyValue::Choice(options, _) => options.len().max(1) as u32,
+            _ => return None,
+        };
+        let row = self
+            .rows()
+            .iter()
+            .position(|row| *row == GridRow::Property(index))?;
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let name_width = self.name_width();
+        let row_y = (row as u32 * ROW_HEIGHT) as i32 - self.scroll_y as i32;
+        let height = options * ROW_HEIGHT;
+        let y = if row_y + (ROW_HEIGHT + height) as i32 > bounds[SIZE_HEIGHT] as i32
+            && row_y - height as i32 >= 0
+        {
+            row_y - height as i32
+        } else {
+            row_y + ROW_HEIGHT as i32
+        };
+
+        Some(Rect::new(
+            name_width as i32,
+            y,
+            (bounds[SIZE_WIDTH] - SCROLL_INDICATOR_WIDTH - name_width).max(1),
+            height,
+        ))
+    }
+
+    /// Internal function that starts editing the property at `index` with the editor for its type.
+    fn start_editing(
+        &mut self,
+        index: usize,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if self.properties[index].read_only {
+            return;
+        }
+
+        let text = match &self.properties[index].value {
+            PropertyValue::Text(text) => text.clone(),
+            PropertyValue::Color(color) => format_color(*color),
+            PropertyValue::Bool(value) => {
+                let value = PropertyValue::Bool(!value);
+
+                self.change_value(index, value, widgets, layouts);
+                return;
+            }
+            PropertyValue::Choice(_, selected) => {
+                self.open_choice = Some(index);
+                self.hovered_option = Some(*selected);
+                self.get_config().set_invalidated(true);
+                return;
+            }
+        };
+        let text: Vec<char> = text.chars().collect();
+
+        self.editing = Some(TextEdit {
+            property: index,
+            cursor: text.len(),
+            text,
+        });
+        self.caret.restart(clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Internal function that finishes editing text, changing the value of the property.  Colors
+    /// that cannot be parsed are discarded.
+    fn commit_edit(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if let Some(edit) = self.editing.take() {
+            let text: String = edit.text.iter().collect();
+            let value = match &self.properties[edit.property].value {
+                PropertyValue::Color(_) => parse_color(&text).map(PropertyValue::Color),
+                _ => Some(PropertyValue::Text(text)),
+            };
+
+            if let Some(value) = value {
+                self.change_value(edit.property, value, widgets, layouts);
+            }
+
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Internal function that selects the option at `option` of the open drop-down list, and closes
+    /// it.
+    fn choose_option(
+        &mut self,
+        option: usize,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(index) = self.open_choice.take() {
+            if let PropertyValue::Choice(options, _) = &self.properties[index].value {
+                if option < options.len() {
+                    let value = PropertyValue::Choice(options.clone(), option);
+
+                    self.change_value(index, value, widgets, layouts);
+                }
+            }
+
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Internal function that discards any text being edited, and closes any open drop-down list.
+    fn close_editors(&mut self) {
+        if self.editing.is_some() || self.open_choice.is_some() {
+            self.editing = None;
+            self.open_choice = None;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Internal function that moves the selection to the next property in `direction`, skipping
+    /// group headers, and scrolls it into view.
+    fn move_selection(&mut self, direction: i32) {
+        let rows = self.rows();
+        let properties: Vec<usize> = rows
+            .iter()
+            .filter_map(|row| match row {
+                GridRow::Property(index) => Some(*index),
+                GridRow::Group(_) => None,
+            })
+            .collect();
+
+        if properties.is_empty() {
+            return;
+        }
+
+        let position = self
+            .selected
+            .and_then(|selected| properties.iter().position(|index| *index == selected));
+        let position = match position {
+            Some(position) if direction < 0 => position.saturating_sub(1),
+            Some(position) => (position + 1).min(properties.len() - 1),
+            None => 0,
+        };
+        let selected = properties[position];
+        let row = rows
+            .iter()
+            .position(|row| *row == GridRow::Property(selected))
+            .unwrap_or(0);
+        let height = self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] as usize;
+        let top = row * ROW_HEIGHT as usize;
+
+        if top < self.scroll_y {
+            self.scroll_y = top;
+        } else if top + ROW_HEIGHT as usize > self.scroll_y + height {
+            self.scroll_y = (top + ROW_HEIGHT as usize).saturating_sub(height);
+        }
+
+        self.selected = Some(selected);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Internal function that returns the largest scroll offset that still fills the grid.
+    fn max_scroll_offset(&mut self) -> usize {
+        let height = self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] as usize;
+
+        (self.rows().len() * ROW_HEIGHT as usize).saturating_sub(height)
+    }
+}
+
+/// This is the `Widget` implementation of the `PropertyGridWidget`.
+impl Widget for PropertyGridWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(240, 240, 248));
+            let selected_color =
+                self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(192, 208, 255));
+            let header_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(232, 232, 232));
+            let check_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(64, 96, 192));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 14);
+
+            self.scroll_y = self.scroll_y.min(self.max_scroll_offset());
+
+            let rows = self.rows();
+            let width = bounds[SIZE_WIDTH] as i32;
+            let height = bounds[SIZE_HEIGHT] as i32;
+            let name_width = self.name_width() as i32;
+            let value_width = (width - SCROLL_INDICATOR_WIDTH as i32 - name_width).max(1);
+            let scroll_y = self.scroll_y as i32;
+            let first_row = self.scroll_y / ROW_HEIGHT as usize;
+            let last_row =
+                ((self.scroll_y + height as usize) / ROW_HEIGHT as usize + 1).min(rows.len());
+            let popup = self.choice_popup_rect();
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let mut font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+            let mut ops: Vec<DrawOp> = vec![];
+            let caret_offset = self
+                .editing
+                .as_ref()
+                .map(|edit| {
+                    let before: String = edit.text[..edit.cursor].iter().collect();
+
+                    font.size_of(&before).map(|(w, _)| w as i32).unwrap_or(0)
+                })
+                .unwrap_or(0);
+
+            // Renders `text` vertically centered in the row at `y`, starting at `x`, and clipped to
+            // `clip`.
+            let mut text = |ops: &mut Vec<DrawOp>,
+                            text: &str,
+                            bold: bool,
+                            color: Color,
+                            x: i32,
+                            y: i32,
+                            clip: Rect| {
+                if text.is_empty() {
+                    return;
+                }
+
+                font.set_style(if bold {
+                    FontStyle::BOLD
+                } else {
+                    FontStyle::NORMAL
+                });
+
+                let surface = font
+                    .render(text)
+                    .blended(color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+                let text_texture = texture_creator
+                    .create_texture_from_surface(&surface)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+                let TextureQuery { width, height, .. } = text_texture.query();
+
+                ops.push(DrawOp::Text(
+                    text_texture,
+                    Rect::new(
+                        x,
+                        y + (ROW_HEIGHT as i32 - height as i32) / 2,
+                        width,
+                        height,
+                    ),
+                    clip,
+                ));
+            };
+
+            for (row_number, row) in rows.iter().enumerate().take(last_row).skip(first_row) {
+                let y = row_number as i32 * ROW_HEIGHT as i32 - scroll_y;
+                let row_rect = Rect::new(
+                    0,
+                    y,
+                    (width - SCROLL_INDICATOR_WIDTH as i32) as u32,
+                    ROW_HEIGHT,
+                );
+
+                match row {
+                    GridRow::Group(group) => {
+                        let center = Point::new(CELL_PADDING + 4, y + ROW_HEIGHT as i32 / 2);
+                        let arrow = if self.is_group_expanded(group) {
+                            vec![
+                                center.offset(-4, -2),
+                                center.offset(4, -2),
+                                center.offset(0, 3),
+                            ]
+                        } else {
+                            vec![
+                                center.offset(-2, -4),
+                                center.offset(3, 0),
+                                center.offset(-2, 4),
+                            ]
+                        };
+
+                        ops.push(DrawOp::Fill(row_rect, header_color));
+                        ops.push(DrawOp::Polygon(arrow, text_color));
+                        text(
+                            &mut ops,
+                            group,
+                            true,
+                            text_color,
+                            GROUP_INDENT + CELL_PADDING,
+                            y,
+                            row_rect,
+                        );
+                    }
+                    GridRow::Property(index) => {
+                        let property = &self.properties[*index];
+                        let indent = if property.group.is_empty() {
+                            CELL_PADDING
+                        } else {
+                            GROUP_INDENT + CELL_PADDING
+                        };
+                        let name_rect = Rect::new(0, y, name_width.max(1) as u32, ROW_HEIGHT);
+                        let value_rect = Rect::new(name_width, y, value_width as u32, ROW_HEIGHT);
+                        let value_color = if property.read_only {
+                            READ_ONLY_COLOR
+                        } else {
+                            text_color
+                        };
+                        let value_x = name_width + CELL_PADDING;
+                        let swatch = Rect::new(
+                            value_x,
+                            y + (ROW_HEIGHT - SWATCH_SIZE) as i32 / 2,
+                            SWATCH_SIZE,
+                            SWATCH_SIZE,
+                        );
+
+                        if self.selected == Some(*index) {
+                            ops.push(DrawOp::Fill(row_rect, selected_color));
+                        } else if self.hovered_row == Some(row_number) {
+                            ops.push(DrawOp::Fill(row_rect, hover_color));
+                        }
+
+                        text(
+                            &mut ops,
+                            &property.name,
+                            false,
+                            text_color,
+                            indent,
+                            y,
+                            name_rect,
+                        );
+
+                        match (&property.value, &self.editing) {
+                            (_, Some(edit)) if edit.property == *index => {
+                                let caret_x = value_x + caret_offset;
+                                let edited: String = edit.text.iter().collect();
+
+                                ops.push(DrawOp::Fill(value_rect, base_color));
+                                ops.push(DrawOp::Outline(value_rect, check_color));
+                                text(&mut ops, &edited, false, text_color, value_x, y, value_rect);
+
+                                if self.focused && self.caret.is_shown() {
+                                    ops.push(DrawOp::Line(
+                                        Point::new(caret_x, y + 4),
+                                        Point::new(caret_x, y + ROW_HEIGHT as i32 - 5),
+                                        text_color,
+                                    ));
+                                }
+                            }
+                            (PropertyValue::Text(value), _) => {
+                                text(&mut ops, value, false, value_color, value_x, y, value_rect);
+                            }
+                            (PropertyValue::Bool(value), _) => {
+                                ops.push(DrawOp::Fill(swatch, base_color));
+                                ops.push(DrawOp::Outline(swatch, value_color));
+
+                                if *value {
+                                    let inner = Rect::new(
+                                        swatch.x() + 3,
+                                        swatch.y() + 3,
+                                        SWATCH_SIZE - 6,
+                                        SWATCH_SIZE - 6,
+                                    );
+
+                                    ops.push(DrawOp::Fill(
+                                        inner,
+                                        if property.read_only {
+                                            READ_ONLY_COLOR
+                                        } else {
+                                            check_color
+                                        },
+                                    ));
+                                }
+                            }
+                            (PropertyValue::Choice(options, selected), _) => {
+                                let arrow_x = name_width + value_width - CELL_PADDING - 4;
+                                let center = Point::new(arrow_x, y + ROW_HEIGHT as i32 / 2);
+
+                                if let Some(option) = options.get(*selected) {
+                                    text(
+                                        &mut ops,
+                                        option,
+                                        false,
+                                        value_color,
+                                        value_x,
+                                        y,
+                                        value_rect,
+                                    );
+                                }
+
+                                ops.push(DrawOp::Polygon(
+                                    vec![
+                                        center.offset(-4, -2),
+                                        center.offset(4, -2),
+                                        center.offset(0, 3),
+                                    ],
+                                    value_color,
+                                ));
+                            }
+                            (PropertyValue::Color(color), _) => {
+                                ops.push(DrawOp::Fill(swatch, *color));
+                                ops.push(DrawOp::Outline(swatch, value_color));
+                                text(
+                                    &mut ops,
+                                    &format_color(*color),
+                                    false,
+                                    value_color,
+                                    value_x + SWATCH_SIZE as i32 + CELL_PADDING,
+                                    y,
+                                    value_rect,
+                                );
+                            }
+                        }
+
+                        ops.push(DrawOp::Line(
+                            Point::new(name_width, y),
+                            Point::new(name_width, y + ROW_HEIGHT as i32 - 1),
+                            header_color,
+                        ));
+                    }
+                }
+
+                ops.push(DrawOp::Line(
+                    Point::new(0, y + ROW_HEIGHT as i32 - 1),
+                    Point::new(
+                        width - SCROLL_INDICATOR_WIDTH as i32 - 1,
+                        y + ROW_HEIGHT as i32 - 1,
+                    ),
+                    header_color,
+                ));
+            }
+
+            if let (Some(rect), Some(index)) = (popup, self.open_choice) {
+                if let PropertyValue::Choice(options, selected) = &self.properties[index].value {
+                    ops.push(DrawOp::Fill(rect, base_color));
+
+                    for (option_number, option) in options.iter().enumerate() {
+                        let y = rect.y() + (option_number as u32 * ROW_HEIGHT) as i32;
+                        let option_rect = Rect::new(rect.x(), y, rect.width(), ROW_HEIGHT);
+
+                        if self.hovered_option == Some(option_number) {
+                            ops.push(DrawOp::Fill(option_rect, selected_color));
+                        }
+
+                        text(
+                            &mut ops,
+                            option,
+                            option_number == *selected,
+                            text_color,
+                            rect.x() + CELL_PADDING,
+                            y,
+                            option_rect,
+                        );
+                    }
+
+                    ops.push(DrawOp::Outline(rect, border_color));
+                }
+            }
+
+            let document_height = (rows.len() as u32 * ROW_HEIGHT).max(1) as f64;
+
+            if document_height > height as f64 {
+                ops.push(DrawOp::Fill(
+                    Rect::new(
+                        width - SCROLL_INDICATOR_WIDTH as i32,
+                        (scroll_y as f64 / document_height * height as f64) as i32,
+                        SCROLL_INDICATOR_WIDTH,
+                        ((height as f64 / document_height * height as f64) as u32).max(1),
+                    ),
+                    border_color,
+                ));
+            }
+
+            ops.push(DrawOp::Outline(
+                Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]),
+                border_color,
+            ));
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for op in &ops {
+                    match op {
+                        DrawOp::Fill(rect, color) => {
+                            texture.set_draw_color(*color);
+                            texture.fill_rect(*rect).unwrap();
+                        }
+                        DrawOp::Outline(rect, color) => {
+                            texture.set_draw_color(*color);
+                            texture.draw_rect(*rect).unwrap();
+                        }
+                        DrawOp::Line(start, end, color) => {
+                            texture.set_draw_color(*color);
+                            texture.draw_line(*start, *end).unwrap();
+                        }
+                        DrawOp::Polygon(points, color) => {
+                            texture.set_draw_color(*color);
+                            fill_polygon(texture, points);
+                        }
+                        DrawOp::Text(text_texture, rect, clip) => {
+                            texture.set_clip_rect(*clip);
+                            texture.copy(text_texture, None, *rect).unwrap();
+                            texture.set_clip_rect(None);
+                        }
+                    }
+                }
+            })
+            .unwrap();
+
+            // Safety: the text textures are only used while drawing this `Widget`.
+            for op in ops {
+                if let DrawOp::Text(text_texture, _, _) = op {
+                    unsafe {
+                        text_texture.destroy();
+                    }
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while text is being edited.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused
+            && self.editing.is_some()
+            && self.caret.tick(clock::now_millis(), caret_blink_rate)
+        {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Stops highlighting the row under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hovered_row = None;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the row, or the option of an open drop-down list, under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let x = points[POINT_X] - self.get_config().to_x(0);
+        let y = points[POINT_Y] - self.get_config().to_y(0);
+        let hovered_row = self.row_at(points[POINT_Y]);
+        let hovered_option = self
+            .choice_popup_rect()
+            .filter(|rect| rect.contains_point(Point::new(x, y)))
+            .map(|rect| ((y - rect.y()) / ROW_HEIGHT as i32) as usize)
+            .or(self.hovered_option);
+
+        if hovered_row != self.hovered_row || hovered_option != self.hovered_option {
+            self.hovered_row = hovered_row;
+            self.hovered_option = hovered_option;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the grid by one row for each step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let offset = self.scroll_y as i32 - points[POINT_Y] * ROW_HEIGHT as i32;
+        let offset = (offset.max(0) as usize).min(self.max_scroll_offset());
+
+        if offset != self.scroll_y {
+            self.open_choice = None;
+            self.scroll_y = offset;
+            self.hovered_row = None;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Chooses an option of an open drop-down list, toggles a group, or selects a property and
+    /// starts editing its value when the value is clicked.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 && _state {
+            let x = self.mouse_position[POINT_X] - self.get_config().to_x(0);
+            let y = self.mouse_position[POINT_Y] - self.get_config().to_y(0);
+
+            if let Some(rect) = self.choice_popup_rect() {
+                if rect.contains_point(Point::new(x, y)) {
+                    let option = ((y - rect.y()) / ROW_HEIGHT as i32) as usize;
+
+                    self.choose_option(option, _widgets, _layouts);
+                } else {
+                    self.open_choice = None;
+                    self.get_config().set_invalidated(true);
+                }
+            } else {
+                let editing = self.editing.as_ref().map(|edit| edit.property);
+                let row = self
+                    .row_at(self.mouse_position[POINT_Y])
+                    .map(|row| self.rows()[row].clone());
+
+                match row {
+                    Some(GridRow::Property(index)) if editing == Some(index) => (),
+                    Some(GridRow::Property(index)) => {
+                        self.commit_edit(_widgets, _layouts);
+                        self.selected = Some(index);
+                        self.get_config().set_invalidated(true);
+
+                        if x >= self.name_width() as i32 {
+                            self.start_editing(index, _widgets, _layouts);
+                        }
+                    }
+                    Some(GridRow::Group(group)) => {
+                        self.commit_edit(_widgets, _layouts);
+
+                        let expanded = self.is_group_expanded(&group);
+
+                        self.set_group_expanded(&group, !expanded);
+                    }
+                    None => self.commit_edit(_widgets, _layouts),
+                }
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Edits text, navigates an open drop-down list, or moves the selection.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Some(edit) = self.editing.as_mut() {
+            match event {
+                Event::TextInput { text, .. } => {
+                    for c in text.chars() {
+                        edit.text.insert(edit.cursor, c);
+                        edit.cursor += 1;
+                    }
+                }
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } => match keycode {
+                    Keycode::Return | Keycode::KpEnter => {
+                        self.commit_edit(_widgets, _layouts);
+                        return;
+                    }
+                    Keycode::Escape => {
+                        self.close_editors();
+                        return;
+                    }
+                    Keycode::Left => edit.cursor = edit.cursor.saturating_sub(1),
+                    Keycode::Right => edit.cursor = (edit.cursor + 1).min(edit.text.len()),
+                    Keycode::Home => edit.cursor = 0,
+                    Keycode::End => edit.cursor = edit.text.len(),
+                    Keycode::Backspace if edit.cursor > 0 => {
+                        edit.cursor -= 1;
+                        edit.text.remove(edit.cursor);
+                    }
+                    Keycode::Delete if edit.cursor < edit.text.len() => {
+                        edit.text.remove(edit.cursor);
+                    }
+                    _ => return,
+                },
+                _ => return,
+            }
+
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+            return;
+        }
+
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            if let Some(index) = self.open_choice {
+                let options = match &self.properties[index].value {
+                    PropertyValue::Choice(options, _) => options.len(),
+                    _ => 0,
+                };
+                let hovered = self.hovered_option.unwrap_or(0);
+
+                match keycode {
+                    Keycode::Up => self.hovered_option = Some(hovered.saturating_sub(1)),
+                    Keycode::Down => {
+                        self.hovered_option = Some((hovered + 1).min(options.saturating_sub(1)))
+                    }
+                    Keycode::Return | Keycode::KpEnter | Keycode::Space => {
+                        self.choose_option(hovered, _widgets, _layouts)
+                    }
+                    Keycode::Escape => self.open_choice = None,
+                    _ => return,
+                }
+
+                self.get_config().set_invalidated(true);
+                return;
+            }
+
+            match keycode {
+                Keycode::Up => self.move_selection(-1),
+                Keycode::Down => self.move_selection(1),
+                Keycode::Return | Keycode::KpEnter => {
+                    if let Some(index) = self.selected {
+                        self.start_editing(index, _widgets, _layouts);
+                    }
+                }
+                Keycode::Space => {
+                    if let Some(index) = self.selected {
+                        if let PropertyValue::Bool(_) = self.properties[index].value {
+                            self.start_editing(index, _widgets, _layouts);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Shows the caret while the grid has focus, and commits any text being edited when it loses
+    /// focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+
+        if !focused {
+            self.open_choice = None;
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus, and a value is being edited.
+    fn accepts_text_input(&mut self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// Redraws this `Widget` when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_TEXT
+            | CONFIG_COLOR_HOVER
+            | CONFIG_COLOR_SELECTED
+            | CONFIG_COLOR_TRACK
+            | CONFIG_COLOR_SECONDARY
+            | CONFIG_FONT_SIZE => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
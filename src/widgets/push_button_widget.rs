@@ -18,7 +18,7 @@ use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::{
     WidgetConfig, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT,
-    CONFIG_SIZE,
+    CONFIG_FOCUSABLE, CONFIG_SIZE,
 };
 use crate::render::{
     make_points, make_size, Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH,
@@ -28,6 +28,7 @@ use sdl2::render::{Canvas, Texture};
 use sdl2::video::Window;
 
 use crate::render::layout_cache::LayoutContainer;
+use crate::render::skin::{NinePatch, Skin, SkinPart, SkinState};
 use crate::render::texture_cache::TextureCache;
 use crate::render::texture_store::TextureStore;
 use crate::widgets::text_widget::{TextJustify, TextWidget};
@@ -52,6 +53,7 @@ pub struct PushButtonWidget {
     active: bool,
     in_bounds: bool,
     originated: bool,
+    skin: Option<Skin>,
     on_click: OnClickCallbackType,
 }
 
@@ -79,8 +81,12 @@ impl PushButtonWidget {
 
         text_widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
 
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
         Self {
-            config: WidgetConfig::new(points, size),
+            config,
             system_properties: HashMap::new(),
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
@@ -89,11 +95,17 @@ impl PushButtonWidget {
             active: false,
             in_bounds: false,
             originated: false,
+            skin: None,
             on_click: None,
         }
     }
 
     fn draw_hovered(&mut self) {
+        if self.skin.is_some() {
+            self.get_config().set_invalidated(true);
+            return;
+        }
+
         self.base_widget
             .set_color(CONFIG_COLOR_BASE, Color::RGB(0, 0, 0));
         self.text_widget
@@ -104,6 +116,11 @@ impl PushButtonWidget {
     }
 
     fn draw_unhovered(&mut self) {
+        if self.skin.is_some() {
+            self.get_config().set_invalidated(true);
+            return;
+        }
+
         self.base_widget
             .set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
         self.text_widget
@@ -140,6 +157,44 @@ impl Widget for PushButtonWidget {
             self.texture_store
                 .create_or_resize_texture(c, bounds[0] as u32, bounds[1] as u32);
 
+            let state = SkinState::from_flags(
+                self.get_config().is_enabled(),
+                self.in_bounds,
+                self.active && self.in_bounds,
+            );
+            let patch: Option<NinePatch> = self
+                .skin
+                .as_ref()
+                .and_then(|skin| skin.get_patch(SkinPart::Button, state))
+                .cloned();
+
+            if let Some(patch) = patch {
+                let image = t.get_image(c, patch.image.clone());
+
+                c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                    texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                    texture.clear();
+
+                    patch.draw(texture, image, Rect::new(0, 0, bounds[0], bounds[1]));
+                })
+                .unwrap();
+
+                let text_widget_texture = self.text_widget.draw(c, t).unwrap();
+
+                c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                    texture
+                        .copy(
+                            text_widget_texture,
+                            None,
+                            Rect::new(2, 2, bounds[0] - 4, bounds[1] - 4),
+                        )
+                        .unwrap();
+                })
+                .unwrap();
+
+                return self.texture_store.get_optional_ref();
+            }
+
             // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
             // Invalidation is controlled by the top level widget (this box).
             let base_widget_texture = self.base_widget.draw(c, t).unwrap();
@@ -174,7 +229,7 @@ impl Widget for PushButtonWidget {
     /// When a mouse enters the bounds of the `Widget`, this function is triggered.  This function
     /// implementation is **optional**.
     fn mouse_entered(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
-        if self.active {
+        if self.active || self.skin.is_some() {
             self.draw_hovered();
         }
 
@@ -185,7 +240,7 @@ impl Widget for PushButtonWidget {
     /// When a mouse exits the bounds of the `Widget`, this function is triggered.  This function
     /// implementation is **optional**.
     fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
-        if self.active {
+        if self.active || self.skin.is_some() {
             self.draw_unhovered();
         }
 
@@ -232,6 +287,30 @@ impl Widget for PushButtonWidget {
         self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
     }
 
+    /// Releases the backing texture of this `Widget`, along with those of the `Widget`s it is
+    /// composed of.  The textures are recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.base_widget.release_textures();
+        self.text_widget.release_textures();
+        self.set_invalidated(true);
+    }
+
+    /// Stores the `Skin`, drawing its `SkinPart::Button` images behind the text in place of the base
+    /// color and border.
+    fn apply_skin(&mut self, skin: Option<&Skin>) {
+        self.skin = skin.filter(|x| x.has_part(SkinPart::Button)).cloned();
+        self.texture_store.set_blended(self.skin.is_some());
+
+        let text_base = match self.skin {
+            Some(_) => Color::RGBA(0, 0, 0, 0),
+            None => self.get_color(CONFIG_COLOR_BASE),
+        };
+
+        self.text_widget.set_color(CONFIG_COLOR_BASE, text_base);
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
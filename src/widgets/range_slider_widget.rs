@@ -0,0 +1,485 @@
+// Pushrod Widget Library
+// Range Slider Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+use crate::widgets::slider_widget::SliderOrientation::SliderHorizontal;
+use crate::widgets::slider_widget::{draw_thumb, SliderOrientation, ThumbShape};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the callback type that is used when an `on_range_changed` callback is triggered from
+/// this `Widget`.  It receives the new low and high values.
+pub type OnRangeChangedCallbackType = Option<
+    Box<dyn FnMut(&mut RangeSliderWidget, &[WidgetContainer], &[LayoutContainer], u32, u32)>,
+>;
+
+/// These are the parts of a `RangeSliderWidget` that can be dragged.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum RangeSliderDrag {
+    /// The handle of the low value.
+    Low,
+
+    /// The handle of the high value.
+    High,
+
+    /// The span of the track between the two handles, which moves both values together.
+    Span,
+}
+
+/// This is the storage object for the `RangeSliderWidget`.  It stores the config, properties,
+/// callback registry, the range of values, the low and high values, and the state of the drag in
+/// progress.
+pub struct RangeSliderWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    min: u32,
+    max: u32,
+    low: u32,
+    high: u32,
+    orientation: SliderOrientation,
+    in_bounds: bool,
+    dragging: Option<RangeSliderDrag>,
+    drag_anchor: (i32, u32, u32),
+    mouse_position: Points,
+    on_range_changed: OnRangeChangedCallbackType,
+}
+
+/// This is the implementation of the `RangeSliderWidget`, a slider with two handles on one track,
+/// which select a low and a high value between a minimum and maximum.  The low value can never be
+/// greater than the high value.  Either handle can be dragged, and dragging the span of the track
+/// between them moves both values together.  Pressing the track outside of the span moves the
+/// nearest handle to the mouse.  The mouse wheel moves the span.
+///
+/// The handles are drawn like the thumb of a `SliderWidget`, using `CONFIG_THUMB_SIZE`,
+/// `CONFIG_THUMB_SHAPE`, `CONFIG_TRACK_THICKNESS`, and the thumb and track colors.  The span
+/// between the handles is drawn in `CONFIG_COLOR_SELECTED`.
+impl RangeSliderWidget {
+    /// Creates a new `RangeSliderWidget` given the `x, y, w, h` coordinates, the `min` and `max`
+    /// values, the initial `low` and `high` values, and the `orientation` of the slider as drawn.
+    /// The low and high values are clamped to the range, and swapped if `low` is greater than
+    /// `high`.
+    pub fn new(
+        points: Points,
+        size: Size,
+        min: u32,
+        max: u32,
+        low: u32,
+        high: u32,
+        orientation: SliderOrientation,
+    ) -> Self {
+        let mut slider = Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            min: min.min(max),
+            max: max.max(min),
+            low: 0,
+            high: 0,
+            orientation,
+            in_bounds: false,
+            dragging: None,
+            drag_anchor: (0, 0, 0),
+            mouse_position: vec![0, 0],
+            on_range_changed: None,
+        };
+
+        slider.set_values(low, high);
+        slider
+    }
+
+    /// Sets the `low` and `high` values, clamped to the range from `min` to `max`, and swapped if
+    /// `low` is greater than `high`.  This does not trigger the `on_range_changed` callback.
+    pub fn set_values(&mut self, low: u32, high: u32) {
+        self.low = low.min(high).clamp(self.min, self.max);
+        self.high = high.max(low).clamp(self.min, self.max);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the low value.
+    pub fn get_low(&self) -> u32 {
+        self.low
+    }
+
+    /// Retrieves the high value.
+    pub fn get_high(&self) -> u32 {
+        self.high
+    }
+
+    /// Sets the `min` and `max` values.  The low and high values are clamped to the new range.
+    /// This does not trigger the `on_range_changed` callback.
+    pub fn set_bounds(&mut self, min: u32, max: u32) {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.set_values(self.low, self.high);
+    }
+
+    /// Retrieves the minimum value.
+    pub fn get_min(&self) -> u32 {
+        self.min
+    }
+
+    /// Retrieves the maximum value.
+    pub fn get_max(&self) -> u32 {
+        self.max
+    }
+
+    /// Assigns the callback closure that will be used when the user changes the low or high value.
+    pub fn on_range_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut RangeSliderWidget, &[WidgetContainer], &[LayoutContainer], u32, u32)
+            + 'static,
+    {
+        self.on_range_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that changes the low and high values, triggering the `on_range_changed`
+    /// callback if either of them changed.
+    fn change_values(
+        &mut self,
+        low: u32,
+        high: u32,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if low == self.low && high == self.high {
+            return;
+        }
+
+        self.low = low;
+        self.high = high;
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_range_changed.take() {
+            cb(self, widgets, layouts, low, high);
+            self.on_range_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that returns the length of the `Widget` along the slider, its size across
+    /// the slider, and the length of the handles.
+    fn dimensions(&mut self) -> (u32, u32, u32) {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let (length, across) = if self.orientation == SliderHorizontal {
+            (bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT])
+        } else {
+            (bounds[SIZE_HEIGHT], bounds[SIZE_WIDTH])
+        };
+        let thumb_size = (self.get_numeric_or(CONFIG_THUMB_SIZE, 20).max(1) as u32).min(length);
+
+        (length, across, thumb_size)
+    }
+
+    /// Internal function that returns the position along the slider of the start of the handle for
+    /// `value`.
+    fn position_of(&mut self, value: u32) -> i32 {
+        let (length, _, thumb_size) = self.dimensions();
+        let travel = (length - thumb_size) as u64;
+        let range = (self.max - self.min) as u64;
+
+        ((value - self.min) as u64 * travel)
+            .checked_div(range)
+            .unwrap_or(0) as i32
+    }
+
+    /// Internal function that returns the value whose handle is centered on `position` along the
+    /// slider, clamped to the range from `min` to `max`.
+    fn value_at(&mut self, position: i32) -> u32 {
+        let (length, _, thumb_size) = self.dimensions();
+        let travel = (length - thumb_size).max(1) as i64;
+        let range = (self.max - self.min) as i64;
+        let offset = (position - (thumb_size / 2) as i32) as i64;
+        let value = self.min as i64 + (offset * range + travel / 2).div_euclid(travel);
+
+        value.clamp(self.min as i64, self.max as i64) as u32
+    }
+
+    /// Internal function that returns the position of the mouse along the slider, relative to the
+    /// `Widget`.
+    fn mouse_along_slider(&mut self) -> i32 {
+        if self.orientation == SliderHorizontal {
+            self.mouse_position[POINT_X] - self.get_config().to_x(0)
+        } else {
+            self.mouse_position[POINT_Y] - self.get_config().to_y(0)
+        }
+    }
+
+    /// Internal function that returns the bounds of the handle for `value`, relative to the
+    /// `Widget`.
+    fn thumb_bounds(&mut self, value: u32) -> Rect {
+        let (_, across, thumb_size) = self.dimensions();
+        let shape = ThumbShape::from_numeric(self.get_numeric_or(CONFIG_THUMB_SHAPE, 0));
+        let thumb_across = if shape == ThumbShape::Circle {
+            thumb_size.min(across)
+        } else {
+            across
+        };
+        let start = self.position_of(value);
+        let across_start = ((across - thumb_across) / 2) as i32;
+
+        if self.orientation == SliderHorizontal {
+            Rect::new(start, across_start, thumb_size, thumb_across)
+        } else {
+            Rect::new(across_start, start, thumb_across, thumb_size)
+        }
+    }
+
+    /// Internal function that returns the bounds of the track between the centers of the handles
+    /// for `from` and `to`, relative to the `Widget`.
+    fn track_bounds(&mut self, from: u32, to: u32) -> Rect {
+        let (_, across, thumb_size) = self.dimensions();
+        let thickness = self.get_numeric_or(CONFIG_TRACK_THICKNESS, 3).max(1) as u32;
+        let start = self.position_of(from) + (thumb_size / 2) as i32;
+        let end = self.position_of(to) + (thumb_size / 2) as i32;
+        let across_start = (across / 2) as i32 - (thickness / 2) as i32;
+        let length = (end - start).max(1) as u32;
+
+        if self.orientation == SliderHorizontal {
+            Rect::new(start, across_start, length, thickness)
+        } else {
+            Rect::new(across_start, start, thickness, length)
+        }
+    }
+
+    /// Internal function that determines the `Color` of the handle that is being dragged as
+    /// `part`, for the current interaction state.
+    fn thumb_color(&mut self, part: RangeSliderDrag) -> Color {
+        let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+
+        if self.dragging == Some(part) || self.dragging == Some(RangeSliderDrag::Span) {
+            self.get_color_or(CONFIG_COLOR_THUMB_PRESSED, base_color)
+        } else if self.in_bounds {
+            self.get_color_or(CONFIG_COLOR_THUMB_HOVER, base_color)
+        } else {
+            base_color
+        }
+    }
+
+    /// Internal function that moves the part of the slider being dragged to follow the mouse.
+    fn drag_to_mouse(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        let position = self.mouse_along_slider();
+        let value = self.value_at(position);
+
+        match self.dragging {
+            Some(RangeSliderDrag::Low) => {
+                let low = value.min(self.high);
+
+                self.change_values(low, self.high, widgets, layouts);
+            }
+            Some(RangeSliderDrag::High) => {
+                let high = value.max(self.low);
+
+                self.change_values(self.low, high, widgets, layouts);
+            }
+            Some(RangeSliderDrag::Span) => {
+                let (anchor_position, anchor_low, anchor_high) = self.drag_anchor;
+                let delta = value as i64 - self.value_at(anchor_position) as i64;
+                let delta = delta.clamp(
+                    self.min as i64 - anchor_low as i64,
+                    self.max as i64 - anchor_high as i64,
+                );
+
+                self.change_values(
+                    (anchor_low as i64 + delta) as u32,
+                    (anchor_high as i64 + delta) as u32,
+                    widgets,
+                    layouts,
+                );
+            }
+            None => (),
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `RangeSliderWidget`.
+impl Widget for RangeSliderWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let track_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(192, 192, 192));
+            let span_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(64, 96, 192));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let low_color = self.thumb_color(RangeSliderDrag::Low);
+            let high_color = self.thumb_color(RangeSliderDrag::High);
+            let shape = match ThumbShape::from_numeric(self.get_numeric_or(CONFIG_THUMB_SHAPE, 0)) {
+                ThumbShape::Image => ThumbShape::Rect,
+                shape => shape,
+            };
+            let (min, max, low, high) = (self.min, self.max, self.low, self.high);
+            let track_bounds = self.track_bounds(min, max);
+            let span_bounds = self.track_bounds(low, high);
+            let low_bounds = self.thumb_bounds(low);
+            let high_bounds = self.thumb_bounds(high);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(track_color);
+                texture.fill_rect(track_bounds).unwrap();
+
+                texture.set_draw_color(span_color);
+                texture.fill_rect(span_bounds).unwrap();
+
+                draw_thumb(texture, shape, low_bounds, low_color, border_color);
+                draw_thumb(texture, shape, high_bounds, high_color, border_color);
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Highlights the handles while the mouse is over the `Widget`.
+    fn mouse_entered(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = true;
+        self.get_config().set_invalidated(true);
+        self.mouse_entered_callback(_widgets, _layouts);
+    }
+
+    /// Removes the highlight from the handles when the mouse leaves the `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = false;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Moves the handle or span being dragged.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.drag_to_mouse(_widgets, _layouts);
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Moves the span by one for each step of the mouse wheel, keeping its width.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let steps = if self.orientation == SliderHorizontal {
+            points[POINT_X] as i64
+        } else {
+            -points[POINT_Y] as i64
+        };
+        let delta = steps.clamp(
+            self.min as i64 - self.low as i64,
+            self.max as i64 - self.high as i64,
+        );
+
+        self.change_values(
+            (self.low as i64 + delta) as u32,
+            (self.high as i64 + delta) as u32,
+            _widgets,
+            _layouts,
+        );
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Starts dragging the handle or span under the mouse.  Pressing the track outside of the span
+    /// moves the nearest handle to the mouse, and starts dragging it.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state && self.in_bounds {
+                let (_, _, thumb_size) = self.dimensions();
+                let position = self.mouse_along_slider();
+                let (low, high) = (self.low, self.high);
+                let low_start = self.position_of(low);
+                let high_start = self.position_of(high);
+                let on_low = position >= low_start && position < low_start + thumb_size as i32;
+                let on_high = position >= high_start && position < high_start + thumb_size as i32;
+
+                self.dragging = Some(if on_low && on_high {
+                    // The handles overlap, so the one that can move towards the mouse is chosen.
+                    if high == self.max || position < high_start + (thumb_size / 2) as i32 {
+                        RangeSliderDrag::Low
+                    } else {
+                        RangeSliderDrag::High
+                    }
+                } else if on_low {
+                    RangeSliderDrag::Low
+                } else if on_high {
+                    RangeSliderDrag::High
+                } else if position > low_start && position < high_start {
+                    RangeSliderDrag::Span
+                } else if position <= low_start {
+                    RangeSliderDrag::Low
+                } else {
+                    RangeSliderDrag::High
+                });
+                self.drag_anchor = (position, low, high);
+
+                if self.dragging != Some(RangeSliderDrag::Span) && !on_low && !on_high {
+                    self.drag_to_mouse(_widgets, _layouts);
+                }
+            } else if !_state {
+                self.dragging = None;
+            }
+
+            self.get_config().set_invalidated(true);
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Captures the mouse, so that the handles can be dragged beyond the bounds of the `Widget`.
+    fn captures_mouse(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,287 @@
+// Pushrod Widget Library
+// Rating Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{fill_polygon, point_at_angle};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the space around each star, in pixels.
+const STAR_PADDING: u32 = 2;
+
+/// This is the radius of the inner corners of a star, as a fraction of the radius of its points.
+const STAR_INNER_RATIO: f64 = 0.4;
+
+/// This is the callback type that is used when an `on_rating_changed` callback is triggered from
+/// this `Widget`.  It receives the new rating.
+pub type OnRatingChangedCallbackType =
+    Option<Box<dyn FnMut(&mut RatingWidget, &[WidgetContainer], &[LayoutContainer], f32)>>;
+
+/// This is the storage object for the `RatingWidget`.  It stores the config, properties, callback
+/// registry, the number of stars, the committed rating, and the rating under the mouse.
+pub struct RatingWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    stars: u32,
+    rating: f32,
+    half_stars: bool,
+    hover_rating: Option<f32>,
+    on_rating_changed: OnRatingChangedCallbackType,
+}
+
+/// This is the implementation of the `RatingWidget`, a row of stars that selects a rating.  Moving
+/// the mouse over the stars highlights them up to the star under the mouse, and clicking commits
+/// that rating.  With half-star precision, the left half of a star selects half a star.  Unlit
+/// stars are drawn in `CONFIG_COLOR_SECONDARY`, lit stars in `CONFIG_COLOR_SELECTED`, and stars
+/// highlighted under the mouse in `CONFIG_COLOR_HOVER`.
+impl RatingWidget {
+    /// Creates a new `RatingWidget` given the `x, y, w, h` coordinates, the number of `stars`, and
+    /// the initial `rating`, from `0.0` up to the number of stars.  The stars share the width of the
+    /// `Widget` equally.
+    pub fn new(points: Points, size: Size, stars: u32, rating: f32) -> Self {
+        let stars = stars.max(1);
+
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            stars,
+            rating: rating.max(0.0).min(stars as f32),
+            half_stars: false,
+            hover_rating: None,
+            on_rating_changed: None,
+        }
+    }
+
+    /// Sets the rating, from `0.0` up to the number of stars.  Without half-star precision, the
+    /// rating is rounded to a whole star.  This does not trigger the `on_rating_changed` callback.
+    pub fn set_rating(&mut self, rating: f32) {
+        let rating = if self.half_stars {
+            (rating * 2.0).round() / 2.0
+        } else {
+            rating.round()
+        };
+
+        self.rating = rating.max(0.0).min(self.stars as f32);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the current rating.
+    pub fn get_rating(&self) -> f32 {
+        self.rating
+    }
+
+    /// Sets the number of stars.  The rating is limited to the new number of stars.
+    pub fn set_stars(&mut self, stars: u32) {
+        self.stars = stars.max(1);
+        self.rating = self.rating.min(self.stars as f32);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the number of stars.
+    pub fn get_stars(&self) -> u32 {
+        self.stars
+    }
+
+    /// Sets whether or not ratings may be selected in half stars.  This is off by default.
+    pub fn set_half_stars(&mut self, half_stars: bool) {
+        self.half_stars = half_stars;
+    }
+
+    /// Indicates whether or not ratings may be selected in half stars.
+    pub fn get_half_stars(&self) -> bool {
+        self.half_stars
+    }
+
+    /// Assigns the callback closure that will be used when a rating is committed by clicking.
+    pub fn on_rating_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut RatingWidget, &[WidgetContainer], &[LayoutContainer], f32) + 'static,
+    {
+        self.on_rating_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that returns the rating selected by the mouse at the absolute horizontal
+    /// position `x`.
+    fn rating_at(&mut self, x: i32) -> f32 {
+        let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH];
+        let cell_width = (width / self.stars).max(1) as i32;
+        let x = (x - self.get_config().to_x(0)).max(0);
+        let index = (x / cell_width).min(self.stars as i32 - 1);
+        let within = x - index * cell_width;
+
+        if self.half_stars && within < cell_width / 2 {
+            index as f32 + 0.5
+        } else {
+            index as f32 + 1.0
+        }
+    }
+}
+
+/// Returns the corners of a five-pointed star around `center`, with its points at `radius` pixels,
+/// and its first point straight up.
+fn star_points(center: Point, radius: f64) -> Vec<Point> {
+    (0..10)
+        .map(|corner| {
+            let distance = if corner % 2 == 0 {
+                radius
+            } else {
+                radius * STAR_INNER_RATIO
+            };
+
+            point_at_angle(center, distance, corner as f64 * 36.0)
+        })
+        .collect()
+}
+
+/// This is the `Widget` implementation of the `RatingWidget`.
+impl Widget for RatingWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let unlit_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(220, 220, 220));
+            let lit_color = if self.hover_rating.is_some() {
+                self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(255, 220, 110))
+            } else {
+                self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(255, 190, 0))
+            };
+            let shown = self.hover_rating.unwrap_or(self.rating);
+            let cell_width = bounds[SIZE_WIDTH] / self.stars;
+            let radius =
+                (cell_width.min(bounds[SIZE_HEIGHT]) / 2).saturating_sub(STAR_PADDING) as f64;
+            let stars: Vec<(Rect, Vec<Point>, f32)> = (0..self.stars)
+                .map(|index| {
+                    let cell = Rect::new(
+                        (index * cell_width) as i32,
+                        0,
+                        cell_width.max(1),
+                        bounds[SIZE_HEIGHT],
+                    );
+
+                    (
+                        cell,
+                        star_points(cell.center(), radius),
+                        (shown - index as f32).clamp(0.0, 1.0),
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (cell, points, amount) in &stars {
+                    texture.set_draw_color(unlit_color);
+                    fill_polygon(texture, points);
+
+                    if *amount > 0.0 {
+                        texture.set_clip_rect(Rect::new(
+                            cell.x(),
+                            cell.y(),
+                            (cell.width() as f32 * amount).round().max(1.0) as u32,
+                            cell.height(),
+                        ));
+                        texture.set_draw_color(lit_color);
+                        fill_polygon(texture, points);
+                        texture.set_clip_rect(None);
+                    }
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Stops highlighting the stars under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hover_rating = None;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the stars up to the one under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let rating = self.rating_at(points[POINT_X]);
+
+        if self.hover_rating != Some(rating) {
+            self.hover_rating = Some(rating);
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Commits the highlighted rating when the mouse button is clicked.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 && _state {
+            if let Some(rating) = self.hover_rating {
+                self.rating = rating;
+                self.get_config().set_invalidated(true);
+
+                if let Some(mut cb) = self.on_rating_changed.take() {
+                    cb(self, _widgets, _layouts, rating);
+                    self.on_rating_changed = Some(cb);
+                }
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
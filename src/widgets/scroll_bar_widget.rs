@@ -0,0 +1,479 @@
+// Pushrod Widget Library
+// Scroll Bar Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::clock;
+use crate::render::input_settings::KeyRepeat;
+use crate::render::skin::{Skin, SkinPart, SkinState};
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use crate::widgets::scroll_bar_widget::ScrollBarOrientation::ScrollBarHorizontal;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the smallest length of the thumb, in pixels, so that it can still be grabbed when the
+/// content is much larger than the visible area.
+const MIN_THUMB_LENGTH: u32 = 16;
+
+/// This is the callback type that is used when an `on_scroll_position_changed` callback is
+/// triggered from this `Widget`.  It receives the new scroll position.
+pub type OnScrollPositionChangedCallbackType =
+    Option<Box<dyn FnMut(&mut ScrollBarWidget, &[WidgetContainer], &[LayoutContainer], u32)>>;
+
+/// These are the possible scroll bar orientations.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ScrollBarOrientation {
+    /// Indicates a scroll bar that scrolls horizontally.
+    ScrollBarHorizontal,
+
+    /// Indicates a scroll bar that scrolls vertically.
+    ScrollBarVertical,
+}
+
+/// This is the storage object for the `ScrollBarWidget`.  It stores the config, properties, callback
+/// registry, the scroll range and position, and the state of the thumb.
+pub struct ScrollBarWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    orientation: ScrollBarOrientation,
+    total: u32,
+    visible: u32,
+    position: u32,
+    step: u32,
+    in_bounds: bool,
+    thumb_hovered: bool,
+    drag_offset: Option<i32>,
+    page_direction: i32,
+    page_repeat: KeyRepeat,
+    mouse_position: Points,
+    skin: Option<Skin>,
+    on_scroll_position_changed: OnScrollPositionChangedCallbackType,
+}
+
+/// This is the implementation of the `ScrollBarWidget`, a scroll bar with a proportional thumb.  The
+/// scroll bar represents `total` units of content, of which `visible` units are shown at once, so
+/// the scroll position ranges from `0` to `total - visible`, and the length of the thumb reflects the
+/// ratio of `visible` to `total`.  The thumb can be dragged, clicking on the track pages by
+/// `visible` units (repeating while the mouse button is held), and the mouse wheel scrolls by the
+/// step size.
+impl ScrollBarWidget {
+    /// Creates a new `ScrollBarWidget` given the `x, y, w, h` coordinates, and the `orientation` of
+    /// the scroll bar.  The scroll bar initially represents a visible area that shows all of its
+    /// content; use `set_range` to set the size of the content.
+    pub fn new(points: Points, size: Size, orientation: ScrollBarOrientation) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            orientation,
+            total: 1,
+            visible: 1,
+            position: 0,
+            step: 1,
+            in_bounds: false,
+            thumb_hovered: false,
+            drag_offset: None,
+            page_direction: 0,
+            page_repeat: KeyRepeat::new(),
+            mouse_position: vec![0, 0],
+            skin: None,
+            on_scroll_position_changed: None,
+        }
+    }
+
+    /// Sets the `total` size of the content, and the size of the `visible` area, in the same units.
+    /// The scroll position is clamped to the new range.  This does not trigger the
+    /// `on_scroll_position_changed` callback.
+    pub fn set_range(&mut self, total: u32, visible: u32) {
+        self.total = total.max(1);
+        self.visible = visible.clamp(1, self.total);
+        self.position = self.position.min(self.max_position());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the total size of the content.
+    pub fn get_total(&self) -> u32 {
+        self.total
+    }
+
+    /// Retrieves the size of the visible area.
+    pub fn get_visible(&self) -> u32 {
+        self.visible
+    }
+
+    /// Sets the number of units the mouse wheel scrolls by.
+    pub fn set_step(&mut self, step: u32) {
+        self.step = step.max(1);
+    }
+
+    /// Retrieves the number of units the mouse wheel scrolls by.
+    pub fn get_step(&self) -> u32 {
+        self.step
+    }
+
+    /// Sets the scroll position, clamped to the range from `0` to `total - visible`.  This does not
+    /// trigger the `on_scroll_position_changed` callback.
+    pub fn set_position(&mut self, position: u32) {
+        self.position = position.min(self.max_position());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the scroll position.
+    pub fn get_position(&self) -> u32 {
+        self.position
+    }
+
+    /// Retrieves the largest scroll position, `total - visible`.
+    pub fn max_position(&self) -> u32 {
+        self.total - self.visible
+    }
+
+    /// Assigns the callback closure that will be used when the user changes the scroll position.
+    pub fn on_scroll_position_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut ScrollBarWidget, &[WidgetContainer], &[LayoutContainer], u32) + 'static,
+    {
+        self.on_scroll_position_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that changes the scroll position, triggering the
+    /// `on_scroll_position_changed` callback if it changed.
+    fn scroll_to(
+        &mut self,
+        position: i64,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let position = position.clamp(0, self.max_position() as i64) as u32;
+
+        if position == self.position {
+            return;
+        }
+
+        self.position = position;
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_scroll_position_changed.take() {
+            cb(self, widgets, layouts, position);
+            self.on_scroll_position_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that returns the length of the track, and its thickness.
+    fn track_length(&mut self) -> (u32, u32) {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+
+        if self.orientation == ScrollBarHorizontal {
+            (size[SIZE_WIDTH], size[SIZE_HEIGHT])
+        } else {
+            (size[SIZE_HEIGHT], size[SIZE_WIDTH])
+        }
+    }
+
+    /// Internal function that returns the start and length of the thumb along the track.
+    fn thumb_span(&mut self) -> (i32, u32) {
+        let (length, _) = self.track_length();
+        let thumb_length = ((length as u64 * self.visible as u64 / self.total as u64) as u32)
+            .max(MIN_THUMB_LENGTH)
+            .min(length);
+        let travel = length - thumb_length;
+        let start = if self.max_position() == 0 {
+            0
+        } else {
+            (travel as u64 * self.position as u64 / self.max_position() as u64) as i32
+        };
+
+        (start, thumb_length)
+    }
+
+    /// Internal function that calculates the bounds of the thumb, relative to the `Widget`.
+    fn thumb_bounds(&mut self) -> Rect {
+        let (start, thumb_length) = self.thumb_span();
+        let (_, thickness) = self.track_length();
+
+        if self.orientation == ScrollBarHorizontal {
+            Rect::new(start, 0, thumb_length, thickness)
+        } else {
+            Rect::new(0, start, thickness, thumb_length)
+        }
+    }
+
+    /// Internal function that returns the position of the mouse along the track, relative to the
+    /// `Widget`.
+    fn mouse_along_track(&mut self) -> i32 {
+        if self.orientation == ScrollBarHorizontal {
+            self.mouse_position[POINT_X] - self.get_config().to_x(0)
+        } else {
+            self.mouse_position[POINT_Y] - self.get_config().to_y(0)
+        }
+    }
+
+    /// Internal function that pages towards the mouse by one `visible` area, unless the thumb has
+    /// already reached the mouse.
+    fn page(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        let (start, thumb_length) = self.thumb_span();
+        let mouse = self.mouse_along_track();
+
+        if (self.page_direction < 0 && mouse < start)
+            || (self.page_direction > 0 && mouse >= start + thumb_length as i32)
+        {
+            let position = self.position as i64 + self.page_direction as i64 * self.visible as i64;
+
+            self.scroll_to(position, widgets, layouts);
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `ScrollBarWidget`.
+impl Widget for ScrollBarWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let track_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(224, 224, 224));
+            let thumb_color = if self.drag_offset.is_some() {
+                self.get_color_or(CONFIG_COLOR_THUMB_PRESSED, Color::RGB(96, 96, 96))
+            } else if self.thumb_hovered {
+                self.get_color_or(CONFIG_COLOR_THUMB_HOVER, Color::RGB(128, 128, 128))
+            } else {
+                self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(160, 160, 160))
+            };
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, track_color);
+            let thumb_bounds = self.thumb_bounds();
+            let state = SkinState::from_flags(
+                self.config.is_enabled(),
+                self.in_bounds,
+                self.drag_offset.is_some(),
+            );
+            let (track, thumb) = match self.skin.as_ref() {
+                Some(skin) => (
+                    skin.get_patch(SkinPart::ScrollBarTrack, state).cloned(),
+                    skin.get_patch(SkinPart::ScrollBarThumb, state).cloned(),
+                ),
+                None => (None, None),
+            };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(track_color);
+                texture.clear();
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            if let Some(track) = track {
+                let image = t.get_image(c, track.image.clone());
+
+                c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                    track.draw(texture, image, Rect::new(0, 0, bounds[0], bounds[1]));
+                })
+                .unwrap();
+            }
+
+            match thumb {
+                Some(thumb) => {
+                    let image = t.get_image(c, thumb.image.clone());
+
+                    c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                        thumb.draw(texture, image, thumb_bounds);
+                    })
+                    .unwrap();
+                }
+                None => {
+                    c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                        texture.set_draw_color(thumb_color);
+                        texture.fill_rect(thumb_bounds).unwrap();
+                        texture.set_draw_color(border_color);
+                        texture.draw_rect(thumb_bounds).unwrap();
+                    })
+                    .unwrap();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Repeats paging while the mouse button is held down over the track.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.page_repeat.is_active() {
+            let delay = self.get_numeric(CONFIG_KEY_REPEAT_DELAY);
+            let interval = self.get_numeric(CONFIG_KEY_REPEAT_INTERVAL);
+
+            if self
+                .page_repeat
+                .should_repeat(clock::now_millis(), delay, interval)
+            {
+                self.page(_widgets, _layouts);
+            }
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    fn mouse_entered(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = true;
+        self.mouse_entered_callback(_widgets, _layouts);
+    }
+
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = false;
+
+        if self.thumb_hovered {
+            self.thumb_hovered = false;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Drags the thumb while it is held, and highlights it while the mouse is over it.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        let mouse = self.mouse_along_track();
+
+        if let Some(drag_offset) = self.drag_offset {
+            let (length, _) = self.track_length();
+            let (_, thumb_length) = self.thumb_span();
+            let travel = length.saturating_sub(thumb_length).max(1) as i64;
+            let start = (mouse - drag_offset) as i64;
+            let position = (start * self.max_position() as i64 + travel / 2) / travel;
+
+            self.scroll_to(position, _widgets, _layouts);
+        } else {
+            let (start, thumb_length) = self.thumb_span();
+            let thumb_hovered =
+                self.in_bounds && mouse >= start && mouse < start + thumb_length as i32;
+
+            if thumb_hovered != self.thumb_hovered {
+                self.thumb_hovered = thumb_hovered;
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls by the step size for each step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let steps = if self.orientation == ScrollBarHorizontal && points[POINT_X] != 0 {
+            points[POINT_X]
+        } else {
+            -points[POINT_Y]
+        };
+        let position = self.position as i64 + steps as i64 * self.step as i64;
+
+        self.scroll_to(position, _widgets, _layouts);
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Starts dragging the thumb when it is pressed, or pages towards the mouse when the track is
+    /// pressed.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 {
+            if state && self.in_bounds {
+                let mouse = self.mouse_along_track();
+                let (start, thumb_length) = self.thumb_span();
+
+                if mouse < start {
+                    self.page_direction = -1;
+                } else if mouse >= start + thumb_length as i32 {
+                    self.page_direction = 1;
+                } else {
+                    self.drag_offset = Some(mouse - start);
+                    self.get_config().set_invalidated(true);
+                }
+
+                if self.page_direction != 0 {
+                    self.page(_widgets, _layouts);
+                    self.page_repeat.press(clock::now_millis());
+                }
+            } else if !state {
+                if self.drag_offset.take().is_some() {
+                    self.get_config().set_invalidated(true);
+                }
+
+                self.page_direction = 0;
+                self.page_repeat.release();
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Captures the mouse, so that the thumb can be dragged beyond the bounds of the `Widget`.
+    fn captures_mouse(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    /// Stores the `Skin`, drawing its `SkinPart::ScrollBarTrack` and `SkinPart::ScrollBarThumb`
+    /// images in place of the standard track and thumb.
+    fn apply_skin(&mut self, skin: Option<&Skin>) {
+        self.skin = skin
+            .filter(|x| {
+                x.has_part(SkinPart::ScrollBarTrack) || x.has_part(SkinPart::ScrollBarThumb)
+            })
+            .cloned();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
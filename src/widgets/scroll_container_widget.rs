@@ -0,0 +1,516 @@
+// Pushrod Widget Library
+// Scroll Container Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{make_points, make_size, Points, Size, POINT_X, POINT_Y};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::skin::Skin;
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use crate::widgets::scroll_bar_widget::ScrollBarOrientation::{
+    ScrollBarHorizontal, ScrollBarVertical,
+};
+use crate::widgets::scroll_bar_widget::{ScrollBarOrientation, ScrollBarWidget};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the thickness of the scroll bars, in pixels.
+const SCROLL_BAR_SIZE: u32 = 16;
+
+/// This is the number of pixels the content scrolls for each step of the mouse wheel.
+const SCROLL_STEP: u32 = 20;
+
+/// These are the configuration keys that are passed on to the scroll bars, so that they can be
+/// styled by setting them on the `ScrollContainerWidget`.
+const SCROLL_BAR_KEYS: [u8; 7] = [
+    CONFIG_COLOR_TRACK,
+    CONFIG_COLOR_SECONDARY,
+    CONFIG_COLOR_THUMB_HOVER,
+    CONFIG_COLOR_THUMB_PRESSED,
+    CONFIG_COLOR_BORDER,
+    CONFIG_KEY_REPEAT_DELAY,
+    CONFIG_KEY_REPEAT_INTERVAL,
+];
+
+/// This is the storage object for the `ScrollContainerWidget`.  It stores the config, properties,
+/// callback registry, the size of the content, the scroll offset, and its scroll bars.
+pub struct ScrollContainerWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    content_size: Size,
+    scroll_offset: Points,
+    horizontal: ScrollBarWidget,
+    vertical: ScrollBarWidget,
+    hovered_bar: Option<ScrollBarOrientation>,
+    pressed_bar: Option<ScrollBarOrientation>,
+}
+
+/// This is the implementation of the `ScrollContainerWidget`, a viewport that shows a scrollable
+/// area of a larger content area.  `Widget`s are added to the container by making them its children
+/// with `Engine::set_parent`; their origins are then relative to the top-left corner of the content,
+/// they move as the content scrolls, and they are clipped to the visible area.  Scroll bars are
+/// shown along the right and bottom edges whenever the content is larger than the visible area.
+impl ScrollContainerWidget {
+    /// Creates a new `ScrollContainerWidget` given the `x, y, w, h` coordinates.  The content is
+    /// initially the same size as the container; use `set_content_size` to make it scrollable.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut horizontal = ScrollBarWidget::new(
+            make_points(0, 0),
+            make_size(size[0], SCROLL_BAR_SIZE),
+            ScrollBarHorizontal,
+        );
+        let mut vertical = ScrollBarWidget::new(
+            make_points(0, 0),
+            make_size(SCROLL_BAR_SIZE, size[1]),
+            ScrollBarVertical,
+        );
+
+        horizontal.set_step(SCROLL_STEP);
+        vertical.set_step(SCROLL_STEP);
+
+        Self {
+            config: WidgetConfig::new(points, size.clone()),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            content_size: size,
+            scroll_offset: vec![0, 0],
+            horizontal,
+            vertical,
+            hovered_bar: None,
+            pressed_bar: None,
+        }
+    }
+
+    /// Sets the size of the content that the children of this `Widget` are positioned in.  The
+    /// scroll offset is clamped so that it stays within the content.
+    pub fn set_content_size(&mut self, w: u32, h: u32) {
+        self.content_size = make_size(w, h);
+        self.layout_scroll_bars();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the size of the content.
+    pub fn get_content_size(&self) -> Size {
+        self.content_size.clone()
+    }
+
+    /// Scrolls the content so that the point `x, y` of the content is shown at the top-left corner
+    /// of the visible area.  The offset is clamped so that it stays within the content.
+    pub fn set_scroll_offset(&mut self, x: i32, y: i32) {
+        self.scroll_offset = make_points(x, y);
+        self.layout_scroll_bars();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the point of the content that is shown at the top-left corner of the visible area.
+    pub fn get_scroll_offset(&self) -> Points {
+        self.scroll_offset.clone()
+    }
+
+    /// Internal function that returns the visible area of the content, relative to the `Widget`,
+    /// along with whether the horizontal and vertical scroll bars are shown.
+    fn visible_bounds(&mut self) -> (Rect, bool, bool) {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+        let mut show_horizontal = self.content_size[0] > size[0];
+        let show_vertical = self.content_size[1]
+            > size[1].saturating_sub(if show_horizontal { SCROLL_BAR_SIZE } else { 0 });
+
+        if !show_horizontal && show_vertical {
+            show_horizontal = self.content_size[0] > size[0].saturating_sub(SCROLL_BAR_SIZE);
+        }
+
+        let width = size[0].saturating_sub(if show_vertical { SCROLL_BAR_SIZE } else { 0 });
+        let height = size[1].saturating_sub(if show_horizontal { SCROLL_BAR_SIZE } else { 0 });
+
+        (
+            Rect::new(0, 0, width.max(1), height.max(1)),
+            show_horizontal,
+            show_vertical,
+        )
+    }
+
+    /// Internal function that clamps the scroll offset, and positions, sizes, and shows or hides
+    /// the scroll bars to match the visible area.
+    fn layout_scroll_bars(&mut self) {
+        let (visible, show_horizontal, show_vertical) = self.visible_bounds();
+        let x = self.get_config().to_x(0);
+        let y = self.get_config().to_y(0);
+        let max_x = self.content_size[0].saturating_sub(visible.width()) as i32;
+        let max_y = self.content_size[1].saturating_sub(visible.height()) as i32;
+
+        self.scroll_offset[POINT_X] = self.scroll_offset[POINT_X].clamp(0, max_x);
+        self.scroll_offset[POINT_Y] = self.scroll_offset[POINT_Y].clamp(0, max_y);
+
+        for key in SCROLL_BAR_KEYS.iter() {
+            if let Some(value) = self.config.config.get(key).cloned() {
+                self.horizontal
+                    .get_config()
+                    .config
+                    .insert(*key, value.clone());
+                self.vertical.get_config().config.insert(*key, value);
+            }
+        }
+
+        let scroll_offset = self.scroll_offset.clone();
+        let content_size = self.content_size.clone();
+
+        update_scroll_bar(
+            &mut self.horizontal,
+            show_horizontal,
+            make_points(x, y + visible.height() as i32),
+            make_size(visible.width(), SCROLL_BAR_SIZE),
+            content_size[0],
+            visible.width(),
+            scroll_offset[POINT_X] as u32,
+        );
+        update_scroll_bar(
+            &mut self.vertical,
+            show_vertical,
+            make_points(x + visible.width() as i32, y),
+            make_size(SCROLL_BAR_SIZE, visible.height()),
+            content_size[1],
+            visible.height(),
+            scroll_offset[POINT_Y] as u32,
+        );
+    }
+
+    /// Internal function that returns the scroll bar with the given `orientation`.
+    fn scroll_bar(&mut self, orientation: ScrollBarOrientation) -> &mut ScrollBarWidget {
+        if orientation == ScrollBarHorizontal {
+            &mut self.horizontal
+        } else {
+            &mut self.vertical
+        }
+    }
+
+    /// Internal function that returns the visible scroll bar at the window coordinates in `points`,
+    /// if any.
+    fn scroll_bar_at(&mut self, points: &[i32]) -> Option<ScrollBarOrientation> {
+        let point = Point::new(points[POINT_X], points[POINT_Y]);
+
+        [ScrollBarHorizontal, ScrollBarVertical]
+            .iter()
+            .find(|orientation| {
+                let config = self.scroll_bar(**orientation).get_config();
+                let origin = config.get_point(CONFIG_ORIGIN);
+                let size = config.get_size(CONFIG_SIZE);
+
+                !config.is_hidden()
+                    && Rect::new(origin[POINT_X], origin[POINT_Y], size[0], size[1])
+                        .contains_point(point)
+            })
+            .cloned()
+    }
+
+    /// Internal function that applies the positions of the scroll bars to the scroll offset after
+    /// they have handled an event, and invalidates this `Widget` if it, or either of the scroll
+    /// bars, need to be redrawn.
+    fn sync_scroll_bars(&mut self) {
+        let mut scroll_offset = self.scroll_offset.clone();
+
+        if !self.horizontal.get_config().is_hidden() {
+            scroll_offset[POINT_X] = self.horizontal.get_position() as i32;
+        }
+
+        if !self.vertical.get_config().is_hidden() {
+            scroll_offset[POINT_Y] = self.vertical.get_position() as i32;
+        }
+
+        if scroll_offset != self.scroll_offset
+            || self.horizontal.is_invalidated()
+            || self.vertical.is_invalidated()
+        {
+            self.scroll_offset = scroll_offset;
+            self.get_config().set_invalidated(true);
+        }
+    }
+}
+
+/// Positions and sizes a scroll bar of a `ScrollContainerWidget`, hiding it if it is not `shown`,
+/// and updates its range and position.  The scroll bar is only invalidated if something changed.
+fn update_scroll_bar(
+    scroll_bar: &mut ScrollBarWidget,
+    shown: bool,
+    origin: Points,
+    size: Size,
+    total: u32,
+    visible: u32,
+    position: u32,
+) {
+    if shown == scroll_bar.get_config().is_hidden() {
+        if shown {
+            scroll_bar.get_config().show();
+        } else {
+            scroll_bar.get_config().hide();
+        }
+    }
+
+    if scroll_bar.get_config().get_point(CONFIG_ORIGIN) != origin {
+        scroll_bar
+            .get_config()
+            .set_point(CONFIG_ORIGIN, origin[POINT_X], origin[POINT_Y]);
+    }
+
+    if scroll_bar.get_config().get_size(CONFIG_SIZE) != size {
+        scroll_bar
+            .get_config()
+            .set_size(CONFIG_SIZE, size[0], size[1]);
+        scroll_bar.set_invalidated(true);
+    }
+
+    if scroll_bar.get_total() != total.max(1)
+        || scroll_bar.get_visible() != visible.clamp(1, total.max(1))
+    {
+        scroll_bar.set_range(total, visible);
+    }
+
+    if scroll_bar.get_position() != position {
+        scroll_bar.set_position(position);
+    }
+}
+
+/// This is the `Widget` implementation of the `ScrollContainerWidget`.
+impl Widget for ScrollContainerWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        self.layout_scroll_bars();
+
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+            let base_color = if self.get_config().config.contains_key(&CONFIG_COLOR_BASE) {
+                self.get_color(CONFIG_COLOR_BASE)
+            } else {
+                Color::RGB(255, 255, 255)
+            };
+            let corner_color = if self.get_config().config.contains_key(&CONFIG_COLOR_TRACK) {
+                self.get_color(CONFIG_COLOR_TRACK)
+            } else {
+                Color::RGB(224, 224, 224)
+            };
+            let (visible, show_horizontal, show_vertical) = self.visible_bounds();
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if show_horizontal && show_vertical {
+                    texture.set_draw_color(corner_color);
+                    texture
+                        .fill_rect(Rect::new(
+                            visible.width() as i32,
+                            visible.height() as i32,
+                            SCROLL_BAR_SIZE,
+                            SCROLL_BAR_SIZE,
+                        ))
+                        .unwrap();
+                }
+            })
+            .unwrap();
+
+            let x = self.config.to_x(0);
+            let y = self.config.to_y(0);
+
+            for scroll_bar in [&mut self.horizontal, &mut self.vertical].iter_mut() {
+                if scroll_bar.get_config().is_hidden() {
+                    continue;
+                }
+
+                let origin = scroll_bar.get_config().get_point(CONFIG_ORIGIN);
+                let size = scroll_bar.get_config().get_size(CONFIG_SIZE);
+                let destination =
+                    Rect::new(origin[POINT_X] - x, origin[POINT_Y] - y, size[0], size[1]);
+
+                if let Some(scroll_bar_texture) = scroll_bar.draw(c, t) {
+                    c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                        texture.copy(scroll_bar_texture, None, destination).unwrap();
+                    })
+                    .unwrap();
+                }
+
+                scroll_bar.set_invalidated(false);
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Repeats paging while the mouse button is held down over the track of a scroll bar.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.horizontal.tick(_widgets, _layouts);
+        self.vertical.tick(_widgets, _layouts);
+        self.sync_scroll_bars();
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.pressed_bar.is_none() {
+            if let Some(orientation) = self.hovered_bar.take() {
+                self.scroll_bar(orientation)
+                    .mouse_exited(_widgets, _layouts);
+                self.sync_scroll_bars();
+            }
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Sends mouse movement to the scroll bar under the mouse, or to the scroll bar whose thumb is
+    /// being dragged.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.layout_scroll_bars();
+
+        let hovered_bar = self.scroll_bar_at(&points);
+
+        if hovered_bar != self.hovered_bar && self.pressed_bar.is_none() {
+            if let Some(orientation) = self.hovered_bar {
+                self.scroll_bar(orientation)
+                    .mouse_exited(_widgets, _layouts);
+            }
+
+            if let Some(orientation) = hovered_bar {
+                self.scroll_bar(orientation)
+                    .mouse_entered(_widgets, _layouts);
+            }
+
+            self.hovered_bar = hovered_bar;
+        }
+
+        if let Some(orientation) = self.pressed_bar.or(self.hovered_bar) {
+            self.scroll_bar(orientation)
+                .mouse_moved(_widgets, _layouts, points.clone());
+        }
+
+        self.sync_scroll_bars();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the content vertically, or horizontally if the mouse is over the horizontal scroll
+    /// bar, or only the horizontal scroll bar is shown.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.layout_scroll_bars();
+
+        let orientation = if self.hovered_bar == Some(ScrollBarHorizontal)
+            || self.vertical.get_config().is_hidden()
+        {
+            ScrollBarHorizontal
+        } else {
+            ScrollBarVertical
+        };
+
+        if !self.scroll_bar(orientation).get_config().is_hidden() {
+            self.scroll_bar(orientation)
+                .mouse_scrolled(_widgets, _layouts, points.clone());
+            self.sync_scroll_bars();
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Sends button presses over a scroll bar to that scroll bar, and button releases to the scroll
+    /// bar that was pressed.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if state {
+            if let Some(orientation) = self.hovered_bar {
+                self.pressed_bar = Some(orientation);
+                self.scroll_bar(orientation)
+                    .button_clicked(_widgets, _layouts, button, clicks, state);
+            }
+        } else if let Some(orientation) = self.pressed_bar.take() {
+            self.scroll_bar(orientation)
+                .button_clicked(_widgets, _layouts, button, clicks, state);
+
+            if self.hovered_bar != Some(orientation) {
+                self.scroll_bar(orientation)
+                    .mouse_exited(_widgets, _layouts);
+            }
+        }
+
+        self.sync_scroll_bars();
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Captures the mouse while a scroll bar is pressed, so that its thumb can be dragged beyond the
+    /// bounds of this `Widget`.
+    fn captures_mouse(&mut self) -> bool {
+        self.hovered_bar.is_some()
+    }
+
+    /// Returns the visible area of this `Widget`, which excludes the scroll bars, along with the size
+    /// of the content, and the scroll offset.
+    fn get_viewport(&mut self) -> Option<Viewport> {
+        self.layout_scroll_bars();
+
+        let (visible, _, _) = self.visible_bounds();
+
+        Some(Viewport {
+            bounds: visible,
+            content_width: self.content_size[0],
+            content_height: self.content_size[1],
+            scroll_x: self.scroll_offset[POINT_X],
+            scroll_y: self.scroll_offset[POINT_Y],
+        })
+    }
+
+    /// Releases the backing textures of this `Widget` and its scroll bars.  The textures are
+    /// recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.horizontal.release_textures();
+        self.vertical.release_textures();
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    /// Applies the `Skin` to the scroll bars.
+    fn apply_skin(&mut self, skin: Option<&Skin>) {
+        self.horizontal.apply_skin(skin);
+        self.vertical.apply_skin(skin);
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,516 @@
+// Pushrod Widget Library
+// Search Field Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::draw_circle;
+use crate::render::clock;
+use crate::render::input_settings::{handle_edit_key, CaretBlink};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between the icons of the `SearchFieldWidget` and its text.
+const TEXT_PADDING: u32 = 4;
+
+/// This is the default number of milliseconds to wait after the last keystroke before the
+/// `on_search_changed` callback is triggered.
+pub const DEFAULT_SEARCH_DELAY: u64 = 300;
+
+/// This is the callback type that is used when an `on_search_changed` callback is triggered from
+/// this `Widget`.  It receives the text to search for.
+pub type OnSearchChangedCallbackType =
+    Option<Box<dyn FnMut(&mut SearchFieldWidget, &[WidgetContainer], &[LayoutContainer], String)>>;
+
+/// This is the storage object for the `SearchFieldWidget`.  It stores the config, properties,
+/// callback registry, the font name and size, the text being edited, the cursor and scroll
+/// positions, and when the text was last changed.
+pub struct SearchFieldWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    text: Vec<char>,
+    placeholder: String,
+    cursor: usize,
+    scroll_x: i32,
+    offsets: Vec<u32>,
+    delay: u64,
+    changed_at: Option<u64>,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_search_changed: OnSearchChangedCallbackType,
+}
+
+/// This is the implementation of the `SearchFieldWidget`, a single-line text field with a
+/// magnifier icon at its left edge, and a button that clears the text at its right edge.  While
+/// the user types, the `on_search_changed` callback is only triggered once no key has been pressed
+/// for the search delay, so that lists can be filtered live without filtering on every keystroke.
+/// Pressing `Return` triggers the callback immediately, and clicking the clear button or pressing
+/// `Escape` clears the text, and triggers the callback immediately.
+impl SearchFieldWidget {
+    /// Creates a new `SearchFieldWidget`, given the path to the font file, the size of the font in
+    /// pixels, and the `x, y, w, h` coordinates.
+    pub fn new(font_name: String, font_size: i32, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            text: vec![],
+            placeholder: String::from("Search"),
+            cursor: 0,
+            scroll_x: 0,
+            offsets: vec![0],
+            delay: DEFAULT_SEARCH_DELAY,
+            changed_at: None,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_search_changed: None,
+        }
+    }
+
+    /// Replaces the text to search for, moving the cursor to the end.  This does not trigger the
+    /// `on_search_changed` callback.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().collect();
+        self.cursor = self.text.len();
+        self.changed_at = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the text to search for.
+    pub fn get_text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Sets the text shown in `CONFIG_COLOR_SECONDARY` while the field is empty.  This is `Search`
+    /// by default.
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = String::from(placeholder);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the text shown while the field is empty.
+    pub fn get_placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Sets the number of milliseconds to wait after the last keystroke before the
+    /// `on_search_changed` callback is triggered.  A delay of `0` triggers the callback on the next
+    /// tick.
+    pub fn set_delay(&mut self, delay: u64) {
+        self.delay = delay;
+    }
+
+    /// Retrieves the number of milliseconds to wait after the last keystroke before the
+    /// `on_search_changed` callback is triggered.
+    pub fn get_delay(&self) -> u64 {
+        self.delay
+    }
+
+    /// Assigns the callback closure that will be used when the text to search for changes.
+    pub fn on_search_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut SearchFieldWidget, &[WidgetContainer], &[LayoutContainer], String) + 'static,
+    {
+        self.on_search_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_search_changed` callback, and cancels any pending
+    /// one.
+    fn call_search_changed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        self.changed_at = None;
+
+        if let Some(mut cb) = self.on_search_changed.take() {
+            cb(self, widgets, layouts, self.text.iter().collect());
+            self.on_search_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that clears the text, and triggers the `on_search_changed` callback if
+    /// there was any text.
+    fn clear(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if !self.text.is_empty() {
+            self.text.clear();
+            self.cursor = 0;
+            self.get_config().set_invalidated(true);
+            self.call_search_changed_callback(widgets, layouts);
+        }
+    }
+
+    /// Returns the width of each of the icons, which are square.
+    fn icon_width(&mut self) -> u32 {
+        self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT]
+    }
+
+    /// Moves the cursor to the character closest to the last known mouse position.
+    fn move_cursor_to_mouse(&mut self) {
+        let text_left = (self.icon_width() + TEXT_PADDING) as i32;
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(text_left) + self.scroll_x;
+
+        self.cursor = self
+            .offsets
+            .windows(2)
+            .position(|pair| x < ((pair[0] + pair[1]) / 2) as i32)
+            .unwrap_or(self.offsets.len() - 1)
+            .min(self.text.len());
+    }
+}
+
+/// Draws a magnifier icon centered within `bounds`.
+fn draw_magnifier(c: &mut Canvas<Window>, bounds: Rect) {
+    let radius = (bounds.width().min(bounds.height()) / 5).max(2);
+    let center = Point::new(
+        bounds.center().x() - radius as i32 / 2,
+        bounds.center().y() - radius as i32 / 2,
+    );
+    let handle = (radius as f64 * 0.7) as i32;
+
+    draw_circle(c, center, radius);
+
+    for offset in &[0, 1] {
+        c.draw_line(
+            Point::new(center.x() + handle + offset, center.y() + handle),
+            Point::new(center.x() + handle * 2 + offset, center.y() + handle * 2),
+        )
+        .unwrap();
+    }
+}
+
+/// Draws a cross centered within `bounds`.
+fn draw_cross(c: &mut Canvas<Window>, bounds: Rect) {
+    let size = (bounds.width().min(bounds.height()) / 5).max(2) as i32;
+    let center = bounds.center();
+
+    c.draw_line(
+        Point::new(center.x() - size, center.y() - size),
+        Point::new(center.x() + size, center.y() + size),
+    )
+    .unwrap();
+    c.draw_line(
+        Point::new(center.x() - size, center.y() + size),
+        Point::new(center.x() + size, center.y() - size),
+    )
+    .unwrap();
+}
+
+/// This is the `Widget` implementation of the `SearchFieldWidget`.
+impl Widget for SearchFieldWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let placeholder_color = self.get_color(CONFIG_COLOR_SECONDARY);
+            let icon_width = self.icon_width();
+            let text_left = (icon_width + TEXT_PADDING) as i32;
+            let text_width = bounds[SIZE_WIDTH]
+                .saturating_sub(icon_width * 2 + TEXT_PADDING * 2)
+                .max(1) as i32;
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+            let line_height = font.recommended_line_spacing().max(1);
+
+            self.offsets = (0..=self.text.len())
+                .map(|end| {
+                    let prefix: String = self.text[..end].iter().collect();
+
+                    font.size_of(&prefix).map(|(w, _)| w).unwrap_or(0)
+                })
+                .collect();
+
+            // The text is scrolled horizontally so that the cursor is always within the field.
+            let caret_x = self.offsets[self.cursor.min(self.text.len())] as i32;
+
+            if caret_x - self.scroll_x >= text_width {
+                self.scroll_x = caret_x - text_width + 1;
+            } else if caret_x < self.scroll_x {
+                self.scroll_x = caret_x;
+            }
+
+            let scroll_x = self.scroll_x;
+            let text_y = (bounds[SIZE_HEIGHT] as i32 - line_height) / 2;
+            let caret = if self.focused && self.caret.is_shown() {
+                Some(text_left + caret_x - scroll_x)
+            } else {
+                None
+            };
+            let (shown, shown_color, shown_scroll) = if self.text.is_empty() {
+                (self.placeholder.clone(), placeholder_color, 0)
+            } else {
+                (self.text.iter().collect(), text_color, scroll_x)
+            };
+            let font_texture = if shown.is_empty() {
+                None
+            } else {
+                let surface = font
+                    .render(&shown)
+                    .blended(shown_color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+
+                Some(
+                    texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap(),
+                )
+            };
+            let has_text = !self.text.is_empty();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let Some(font_texture) = &font_texture {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+                    let visible_width = (width as i32 - shown_scroll).min(text_width);
+
+                    if visible_width > 0 {
+                        texture
+                            .copy(
+                                font_texture,
+                                Rect::new(shown_scroll, 0, visible_width as u32, height),
+                                Rect::new(text_left, text_y, visible_width as u32, height),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                texture.set_draw_color(text_color);
+
+                if let Some(x) = caret {
+                    texture
+                        .fill_rect(Rect::new(x, text_y, 1, line_height as u32))
+                        .unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                draw_magnifier(texture, Rect::new(0, 0, icon_width, bounds[SIZE_HEIGHT]));
+
+                if has_text {
+                    draw_cross(
+                        texture,
+                        Rect::new(
+                            (bounds[SIZE_WIDTH] - icon_width) as i32,
+                            0,
+                            icon_width,
+                            bounds[SIZE_HEIGHT],
+                        ),
+                    );
+                }
+
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            if let Some(font_texture) = font_texture {
+                // Safety: the font texture is only used within this function.
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while this `Widget` has focus, and triggers the `on_search_changed`
+    /// callback once the search delay has passed since the text was last changed.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        if let Some(changed_at) = self.changed_at {
+            if clock::now_millis().saturating_sub(changed_at) >= self.delay {
+                self.call_search_changed_callback(_widgets, _layouts);
+            }
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Tracks the mouse position, so that clicks can move the cursor, or clear the text.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Clears the text when the clear button is clicked, otherwise moves the cursor to the clicked
+    /// position.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH];
+            let icon_width = self.icon_width();
+            let clear_left = self
+                .get_config()
+                .to_x(width.saturating_sub(icon_width) as i32);
+
+            if !self.text.is_empty() && self.mouse_position[POINT_X] >= clear_left {
+                self.clear(_widgets, _layouts);
+            } else {
+                self.move_cursor_to_mouse();
+                self.caret.restart(clock::now_millis());
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles text input and cursor navigation while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let (text_changed, cursor_moved) = match event {
+            Event::TextInput { text, .. } => {
+                for c in text.chars() {
+                    self.text.insert(self.cursor, c);
+                    self.cursor += 1;
+                }
+
+                (true, true)
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Return),
+                ..
+            }
+            | Event::KeyDown {
+                keycode: Some(Keycode::KpEnter),
+                ..
+            } => {
+                self.call_search_changed_callback(_widgets, _layouts);
+                (false, false)
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Escape),
+                ..
+            } => {
+                self.clear(_widgets, _layouts);
+                (false, false)
+            }
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } => handle_edit_key(keycode, &mut self.text, &mut self.cursor),
+            _ => (false, false),
+        };
+
+        if cursor_moved {
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        if text_changed {
+            self.changed_at = Some(clock::now_millis());
+        }
+    }
+
+    /// Monitors for changes in the text, colors, or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _)
+            | (CONFIG_COLOR_BASE, _)
+            | (CONFIG_COLOR_BORDER, _)
+            | (CONFIG_COLOR_SECONDARY, _) => self.get_config().set_invalidated(true),
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            (CONFIG_TEXT, Config::Text(text)) => self.set_text(&text),
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus.
+    fn accepts_text_input(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,378 @@
+// Pushrod Widget Library
+// Segmented Control Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_rounded_rect;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the radius of the outer corners of the control, in pixels.
+const SEGMENT_CORNER_RADIUS: u32 = 6;
+
+/// This is the callback type that is used when an `on_segment_selected` callback is triggered from
+/// this `Widget`.  It receives the index of the selected segment, starting from `0`.
+pub type OnSegmentSelectedCallbackType = Option<
+    Box<dyn FnMut(&mut SegmentedControlWidget, &[WidgetContainer], &[LayoutContainer], usize)>,
+>;
+
+/// This is the storage object for the `SegmentedControlWidget`.  It stores the config, properties,
+/// callback registry, the labels of the segments, the selected segment, and the segment under the
+/// mouse.
+pub struct SegmentedControlWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    segments: Vec<String>,
+    selected: usize,
+    hovered: Option<usize>,
+    on_segment_selected: OnSegmentSelectedCallbackType,
+}
+
+/// This is the implementation of the `SegmentedControlWidget`, a horizontal row of connected
+/// segments, exactly one of which is selected, like a compact group of radio buttons.  Segments
+/// share the width of the `Widget` equally, and the outer corners of the row are rounded.  The
+/// selected segment is filled with `CONFIG_COLOR_SELECTED`, and its label is drawn in
+/// `CONFIG_COLOR_BASE`; the segment under the mouse is filled with `CONFIG_COLOR_HOVER`.  Clicking a
+/// segment, or pressing `Left` and `Right` while the control has focus, selects a segment and
+/// triggers the `on_segment_selected` callback.
+impl SegmentedControlWidget {
+    /// Creates a new `SegmentedControlWidget` given the `x, y, w, h` coordinates, the labels of the
+    /// `segments`, and the index of the `selected` segment.  Labels are drawn with the size in
+    /// `CONFIG_FONT_SIZE`, or `14` if it is not set.
+    pub fn new(points: Points, size: Size, segments: Vec<String>, selected: usize) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        let mut texture_store = TextureStore::default();
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        // The texture is blended, so that whatever is drawn behind the rounded corners shows
+        // through them.
+        texture_store.set_blended(true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store,
+            selected: selected.min(segments.len().saturating_sub(1)),
+            segments,
+            hovered: None,
+            on_segment_selected: None,
+        }
+    }
+
+    /// Replaces the labels of the segments.  The selection is kept if that segment still exists,
+    /// otherwise the last segment is selected.
+    pub fn set_segments(&mut self, segments: Vec<String>) {
+        self.selected = self.selected.min(segments.len().saturating_sub(1));
+        self.segments = segments;
+        self.hovered = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the labels of the segments.
+    pub fn get_segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Selects the segment at `index`, without triggering the `on_segment_selected` callback.
+    /// Indexes past the last segment are ignored.
+    pub fn set_selected(&mut self, index: usize) {
+        if index < self.segments.len() && index != self.selected {
+            self.selected = index;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the index of the selected segment.
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Assigns the callback closure that will be used when a segment is selected by the user.
+    pub fn on_segment_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut SegmentedControlWidget, &[WidgetContainer], &[LayoutContainer], usize)
+            + 'static,
+    {
+        self.on_segment_selected = Some(Box::new(callback));
+    }
+
+    /// Internal function that selects the segment at `index`, and triggers the
+    /// `on_segment_selected` callback if the selection changed.
+    fn select(&mut self, index: usize, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if index >= self.segments.len() || index == self.selected {
+            return;
+        }
+
+        self.selected = index;
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_segment_selected.take() {
+            cb(self, widgets, layouts, index);
+            self.on_segment_selected = Some(cb);
+        }
+    }
+
+    /// Internal function that returns the left edge of the segment at `index`, in local
+    /// coordinates.  The right edge of the last segment is returned for `index == segments.len()`.
+    fn segment_x(&mut self, index: usize) -> i32 {
+        let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH] as usize;
+
+        (index * width / self.segments.len().max(1)) as i32
+    }
+
+    /// Internal function that returns the segment under the absolute `x` coordinate.
+    fn segment_at(&mut self, x: i32) -> Option<usize> {
+        let x = x - self.get_config().to_x(0);
+
+        (0..self.segments.len()).find(|index| x < self.segment_x(index + 1))
+    }
+}
+
+/// This is the `Widget` implementation of the `SegmentedControlWidget`.
+impl Widget for SegmentedControlWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(96, 96, 96));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(224, 232, 248));
+            let selected_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(64, 96, 192));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 14);
+            let outer = Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]);
+            let inner = Rect::new(
+                1,
+                1,
+                bounds[SIZE_WIDTH].saturating_sub(2).max(1),
+                bounds[SIZE_HEIGHT].saturating_sub(2).max(1),
+            );
+            let edges: Vec<i32> = (0..=self.segments.len())
+                .map(|index| self.segment_x(index))
+                .collect();
+            let selected = self.selected;
+            let hovered = self.hovered.filter(|index| *index != selected);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+            let labels: Vec<(Rect, Texture)> = self
+                .segments
+                .iter()
+                .enumerate()
+                .filter(|(_, label)| !label.is_empty())
+                .map(|(index, label)| {
+                    let color = if index == selected {
+                        base_color
+                    } else {
+                        text_color
+                    };
+                    let surface = font
+                        .render(label)
+                        .blended(color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let label_texture = texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let TextureQuery { width, height, .. } = label_texture.query();
+                    let center = (edges[index] + edges[index + 1]) / 2;
+
+                    (
+                        Rect::new(
+                            center - width as i32 / 2,
+                            (bounds[SIZE_HEIGHT] as i32 - height as i32) / 2,
+                            width,
+                            height,
+                        ),
+                        label_texture,
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                texture.clear();
+
+                texture.set_draw_color(border_color);
+                fill_rounded_rect(texture, outer, SEGMENT_CORNER_RADIUS);
+                texture.set_draw_color(base_color);
+                fill_rounded_rect(texture, inner, SEGMENT_CORNER_RADIUS - 1);
+
+                // Filling the whole row clipped to one segment rounds only the outer corners of the
+                // first and last segments.
+                for (index, color) in hovered
+                    .map(|index| (index, hover_color))
+                    .into_iter()
+                    .chain(Some((selected, selected_color)))
+                {
+                    if index + 1 < edges.len() {
+                        texture.set_clip_rect(Rect::new(
+                            edges[index],
+                            0,
+                            (edges[index + 1] - edges[index]).max(1) as u32,
+                            bounds[SIZE_HEIGHT],
+                        ));
+                        texture.set_draw_color(color);
+                        fill_rounded_rect(texture, inner, SEGMENT_CORNER_RADIUS - 1);
+                        texture.set_clip_rect(None);
+                    }
+                }
+
+                texture.set_draw_color(border_color);
+
+                for x in edges.iter().skip(1).take(edges.len().saturating_sub(2)) {
+                    texture
+                        .draw_line(
+                            Point::new(*x, 1),
+                            Point::new(*x, bounds[SIZE_HEIGHT] as i32 - 2),
+                        )
+                        .unwrap();
+                }
+
+                for (rect, label_texture) in &labels {
+                    texture.copy(label_texture, None, *rect).unwrap();
+                }
+            })
+            .unwrap();
+
+            // Safety: the label textures are only used while drawing this `Widget`.
+            for (_, label_texture) in labels {
+                unsafe {
+                    label_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Stops highlighting the segment under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hovered = None;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the segment under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let hovered = self.segment_at(points[POINT_X]);
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Selects the segment under the mouse when the mouse button is pressed.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 && _state {
+            if let Some(index) = self.hovered {
+                self.select(index, _widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Selects the previous or next segment when `Left` or `Right` is pressed.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            match keycode {
+                Keycode::Left if self.selected > 0 => {
+                    self.select(self.selected - 1, _widgets, _layouts)
+                }
+                Keycode::Right => self.select(self.selected + 1, _widgets, _layouts),
+                _ => (),
+            }
+        }
+    }
+
+    /// Redraws this `Widget` when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_HOVER
+            | CONFIG_COLOR_SELECTED
+            | CONFIG_COLOR_TEXT
+            | CONFIG_FONT_SIZE => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
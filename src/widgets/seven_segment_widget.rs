@@ -0,0 +1,243 @@
+// Pushrod Widget Library
+// Seven Segment Display Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the space around each digit, in pixels.
+const DIGIT_PADDING: u32 = 4;
+
+/// This is the gap left between neighboring segments of a digit, in pixels.
+const SEGMENT_GAP: u32 = 1;
+
+/// These are the segments that are lit for each hexadecimal digit.  Bit `0` is the top segment
+/// (`a`), followed clockwise by the top right (`b`), bottom right (`c`), bottom (`d`), bottom left
+/// (`e`) and top left (`f`) segments, and bit `6` is the middle segment (`g`).
+const HEX_SEGMENTS: [u8; 16] = [
+    0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F, 0x77, 0x7C, 0x39, 0x5E, 0x79, 0x71,
+];
+
+/// This is the segment that is lit for a minus sign.
+const MINUS_SEGMENTS: u8 = 0x40;
+
+/// This is the storage object for the `SevenSegmentWidget`.  It stores the config, properties,
+/// callback registry, the number of digits, the text that is displayed, and whether or not unlit
+/// segments are drawn.
+pub struct SevenSegmentWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    digits: u32,
+    text: String,
+    ghosting: bool,
+}
+
+/// This is the implementation of the `SevenSegmentWidget`, which displays numbers in the style of
+/// a seven-segment display.  Lit segments are drawn in `CONFIG_COLOR_TEXT`, and unlit segments are
+/// ghosted in `CONFIG_COLOR_SECONDARY`, over a `CONFIG_COLOR_BASE` background.  Text is right
+/// aligned, so that numbers line up as they would on an instrument readout.
+impl SevenSegmentWidget {
+    /// Creates a new `SevenSegmentWidget` given the `x, y, w, h` coordinates, and the number of
+    /// `digits` it displays.  The digits share the width of the `Widget` equally.  The display
+    /// initially shows `0`.
+    pub fn new(points: Points, size: Size, digits: u32) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            digits: digits.max(1),
+            text: String::from("0"),
+            ghosting: true,
+        }
+    }
+
+    /// Sets the number of digits the display shows.
+    pub fn set_digits(&mut self, digits: u32) {
+        self.digits = digits.max(1);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the number of digits the display shows.
+    pub fn get_digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// Displays `value` in decimal, with a leading minus sign if it is negative.  If the value has
+    /// more digits than the display, every digit shows a minus sign to indicate an overflow.
+    pub fn set_value(&mut self, value: i64) {
+        let mut text = format!("{}", value);
+
+        if text.len() > self.digits as usize {
+            text = "-".repeat(self.digits as usize);
+        }
+
+        self.set_text(&text);
+    }
+
+    /// Displays `text`, which may contain the hexadecimal digits `0-9`, `A-F` and `a-f`, minus
+    /// signs, and spaces.  Any other character is displayed as a blank digit.  If the text is
+    /// longer than the display, only its rightmost characters are shown.  This is the same as
+    /// setting `CONFIG_TEXT` through the `Widget` trait.
+    pub fn set_text(&mut self, text: &str) {
+        Widget::set_text(self, CONFIG_TEXT, String::from(text));
+    }
+
+    /// Sets whether or not unlit segments are drawn in `CONFIG_COLOR_SECONDARY`, giving the
+    /// appearance of a real display.  This is on by default.
+    pub fn set_ghosting(&mut self, ghosting: bool) {
+        self.ghosting = ghosting;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether or not unlit segments are drawn.
+    pub fn get_ghosting(&self) -> bool {
+        self.ghosting
+    }
+}
+
+/// Returns the segments that are lit to display `character`.
+fn segments_for(character: char) -> u8 {
+    match character {
+        '-' => MINUS_SEGMENTS,
+        _ => character
+            .to_digit(16)
+            .map(|digit| HEX_SEGMENTS[digit as usize])
+            .unwrap_or(0),
+    }
+}
+
+/// Returns the bounds of the seven segments of a digit drawn within `bounds`, in the bit order of
+/// `HEX_SEGMENTS`.
+fn segment_bounds(bounds: Rect) -> [Rect; 7] {
+    let thickness = (bounds.width() / 6).max(2);
+    let (x, y) = (bounds.x(), bounds.y());
+    let (width, height) = (bounds.width() as i32, bounds.height() as i32);
+    let t = thickness as i32;
+    let gap = SEGMENT_GAP as i32;
+    let middle = y + (height - t) / 2;
+    let across = (width - 2 * t - 2 * gap).max(1) as u32;
+    let upper = (middle - y - t - 2 * gap).max(1) as u32;
+    let lower = (y + height - t - middle - t - 2 * gap).max(1) as u32;
+
+    [
+        Rect::new(x + t + gap, y, across, thickness),
+        Rect::new(x + width - t, y + t + gap, thickness, upper),
+        Rect::new(x + width - t, middle + t + gap, thickness, lower),
+        Rect::new(x + t + gap, y + height - t, across, thickness),
+        Rect::new(x, middle + t + gap, thickness, lower),
+        Rect::new(x, y + t + gap, thickness, upper),
+        Rect::new(x + t + gap, middle, across, thickness),
+    ]
+}
+
+/// This is the `Widget` implementation of the `SevenSegmentWidget`.
+impl Widget for SevenSegmentWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(0, 0, 0));
+            let lit_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(255, 32, 32));
+            let unlit_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(48, 16, 16));
+            let digits = self.digits;
+            let cell_width = bounds[SIZE_WIDTH] / digits;
+            let digit_width = cell_width.saturating_sub(DIGIT_PADDING * 2).max(1);
+            let digit_height = bounds[SIZE_HEIGHT].saturating_sub(DIGIT_PADDING * 2).max(1);
+            let characters: Vec<char> = self.text.chars().collect();
+            let skip = characters.len().saturating_sub(digits as usize);
+            let blanks = (digits as usize).saturating_sub(characters.len());
+            let lit: Vec<u8> = std::iter::repeat_n(0, blanks)
+                .chain(characters[skip..].iter().map(|x| segments_for(*x)))
+                .collect();
+            let ghosting = self.ghosting;
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (index, segments) in lit.iter().enumerate() {
+                    let digit_bounds = Rect::new(
+                        (index as u32 * cell_width + DIGIT_PADDING) as i32,
+                        DIGIT_PADDING as i32,
+                        digit_width,
+                        digit_height,
+                    );
+
+                    for (segment, segment_bounds) in segment_bounds(digit_bounds).iter().enumerate()
+                    {
+                        if segments & (1 << segment) != 0 {
+                            texture.set_draw_color(lit_color);
+                        } else if ghosting {
+                            texture.set_draw_color(unlit_color);
+                        } else {
+                            continue;
+                        }
+
+                        texture.fill_rect(*segment_bounds).unwrap();
+                    }
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Monitors for changes in the text or colors.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_TEXT => {
+                if let Config::Text(text) = _v {
+                    self.text = text;
+                    self.get_config().set_invalidated(true);
+                }
+            }
+            CONFIG_COLOR_BASE | CONFIG_COLOR_TEXT | CONFIG_COLOR_SECONDARY => {
+                self.get_config().set_invalidated(true)
+            }
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
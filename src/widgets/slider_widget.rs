@@ -22,13 +22,14 @@ use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
 use sdl2::render::{Canvas, Texture};
 use sdl2::video::Window;
 
-use crate::render::canvas_helper::CanvasHelper;
+use crate::render::canvas_helper::{fill_rounded_rect, CanvasHelper};
 use crate::render::layout_cache::LayoutContainer;
+use crate::render::skin::{NinePatch, Skin, SkinPart, SkinState};
 use crate::render::texture_cache::TextureCache;
 use crate::render::texture_store::TextureStore;
 use crate::widgets::slider_widget::SliderOrientation::{SliderHorizontal, SliderVertical};
 use sdl2::pixels::Color;
-use sdl2::rect::{Point, Rect};
+use sdl2::rect::Rect;
 use std::any::Any;
 use std::collections::HashMap;
 
@@ -47,6 +48,81 @@ pub enum SliderOrientation {
     SliderVertical,
 }
 
+/// These are the shapes the thumb of a `SliderWidget` can be drawn with, set by
+/// `CONFIG_THUMB_SHAPE`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThumbShape {
+    /// A rectangle with square corners.  This is the default.
+    Rect = 0,
+
+    /// A rectangle with rounded corners.
+    Rounded = 1,
+
+    /// A circle, `CONFIG_THUMB_SIZE` pixels in diameter.
+    Circle = 2,
+
+    /// The image stored in `CONFIG_THUMB_IMAGE`, stretched to the bounds of the thumb.
+    Image = 3,
+}
+
+/// This is the implementation of the `ThumbShape`.
+impl ThumbShape {
+    /// Converts a numeric value stored in `CONFIG_THUMB_SHAPE` to a `ThumbShape`.  Unknown values
+    /// are treated as `ThumbShape::Rect`.
+    pub fn from_numeric(value: i32) -> Self {
+        match value {
+            1 => ThumbShape::Rounded,
+            2 => ThumbShape::Circle,
+            3 => ThumbShape::Image,
+            _ => ThumbShape::Rect,
+        }
+    }
+}
+
+/// Draws a slider thumb of the given `ThumbShape`, filled with `fill` and outlined with `border`.
+/// `ThumbShape::Image` thumbs are drawn by the `Widget`, as they require the `TextureCache`.
+pub(crate) fn draw_thumb(
+    c: &mut Canvas<Window>,
+    shape: ThumbShape,
+    bounds: Rect,
+    fill: Color,
+    border: Color,
+) {
+    match shape {
+        ThumbShape::Rect | ThumbShape::Image => {
+            c.set_draw_color(fill);
+            c.fill_rect(bounds).unwrap();
+
+            c.set_draw_color(border);
+            c.draw_rect(bounds).unwrap();
+        }
+        ThumbShape::Rounded | ThumbShape::Circle => {
+            let radius = if shape == ThumbShape::Circle {
+                bounds.width().min(bounds.height()) / 2
+            } else {
+                bounds.width().min(bounds.height()) / 4
+            };
+
+            c.set_draw_color(border);
+            fill_rounded_rect(c, bounds, radius);
+
+            if bounds.width() > 2 && bounds.height() > 2 {
+                c.set_draw_color(fill);
+                fill_rounded_rect(
+                    c,
+                    Rect::new(
+                        bounds.x() + 1,
+                        bounds.y() + 1,
+                        bounds.width() - 2,
+                        bounds.height() - 2,
+                    ),
+                    radius.saturating_sub(1),
+                );
+            }
+        }
+    }
+}
+
 /// This is the storage object for the `SliderWidget`.  It stores the config, properties, callback registry.
 pub struct SliderWidget {
     config: WidgetConfig,
@@ -60,6 +136,7 @@ pub struct SliderWidget {
     in_bounds: bool,
     active: bool,
     originated: bool,
+    skin: Option<Skin>,
     on_value_changed: OnValueChangedCallbackType,
 }
 
@@ -76,8 +153,12 @@ impl SliderWidget {
         current: u32,
         orientation: SliderOrientation,
     ) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
         Self {
-            config: WidgetConfig::new(points, size),
+            config,
             system_properties: HashMap::new(),
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
@@ -88,6 +169,7 @@ impl SliderWidget {
             in_bounds: false,
             active: false,
             originated: false,
+            skin: None,
             on_value_changed: None,
         }
     }
@@ -100,6 +182,145 @@ impl SliderWidget {
         self.on_value_changed = Some(Box::new(callback));
     }
 
+    /// Retrieves the `ThumbShape` used to draw the thumb, set by `CONFIG_THUMB_SHAPE`.
+    pub fn thumb_shape(&mut self) -> ThumbShape {
+        ThumbShape::from_numeric(self.get_numeric_or(CONFIG_THUMB_SHAPE, 0))
+    }
+
+    /// Internal function that determines the thumb `Color` for the current interaction state.
+    fn thumb_color(&mut self) -> Color {
+        let base_color = self.get_color(CONFIG_COLOR_BASE);
+
+        if self.active {
+            self.get_color_or(CONFIG_COLOR_THUMB_PRESSED, base_color)
+        } else if self.in_bounds {
+            self.get_color_or(CONFIG_COLOR_THUMB_HOVER, base_color)
+        } else {
+            base_color
+        }
+    }
+
+    /// Internal function that indicates whether or not the appearance of the `Widget` changes when
+    /// the mouse enters or leaves it.
+    fn has_hover_state(&mut self) -> bool {
+        self.skin.is_some()
+            || self
+                .get_config()
+                .config
+                .contains_key(&CONFIG_COLOR_THUMB_HOVER)
+    }
+
+    /// Internal function that calculates the bounds of the track, relative to the `Widget`.
+    fn track_bounds(&mut self) -> Rect {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let thickness = self.get_numeric_or(CONFIG_TRACK_THICKNESS, 3).max(1) as u32;
+
+        if self.orientation == SliderHorizontal {
+            Rect::new(
+                10,
+                (bounds[SIZE_HEIGHT] / 2) as i32 - (thickness / 2) as i32,
+                bounds[SIZE_WIDTH].saturating_sub(19).max(1),
+                thickness,
+            )
+        } else {
+            Rect::new(
+                (bounds[SIZE_WIDTH] / 2) as i32 - (thickness / 2) as i32,
+                10,
+                thickness,
+                bounds[SIZE_HEIGHT].saturating_sub(19).max(1),
+            )
+        }
+    }
+
+    /// Internal function that calculates the bounds of the slider thumb at the current value,
+    /// relative to the `Widget`.  The thumb is `CONFIG_THUMB_SIZE` pixels long along the slider, and
+    /// fills the `Widget` across it, except for `ThumbShape::Circle`, which is square.
+    fn thumb_bounds(&mut self) -> Rect {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let (length, across) = if self.orientation == SliderHorizontal {
+            (bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT])
+        } else {
+            (bounds[SIZE_HEIGHT], bounds[SIZE_WIDTH])
+        };
+        let thumb_size = (self.get_numeric_or(CONFIG_THUMB_SIZE, 30).max(1) as u32).min(length);
+        let thumb_across = if self.thumb_shape() == ThumbShape::Circle {
+            thumb_size.min(across)
+        } else {
+            across
+        };
+        let full_range = self.max - self.min;
+        let slider_center =
+            ((length as f64 / full_range as f64) * (self.current - self.min) as f64) as u32;
+        let slider_start = slider_center
+            .saturating_sub(thumb_size / 2)
+            .min(length - thumb_size) as i32;
+        let across_start = ((across - thumb_across) / 2) as i32;
+
+        if self.orientation == SliderHorizontal {
+            Rect::new(slider_start, across_start, thumb_size, thumb_across)
+        } else {
+            Rect::new(across_start, slider_start, thumb_across, thumb_size)
+        }
+    }
+
+    /// Internal function that draws the slider using the `NinePatch` images of its `Skin`.
+    fn draw_skinned(
+        &mut self,
+        c: &mut Canvas<Window>,
+        t: &mut TextureCache,
+        track: Option<NinePatch>,
+        thumb: Option<NinePatch>,
+    ) {
+        let bounds = self.get_config().get_size(CONFIG_SIZE);
+        let base_color = self.get_color(CONFIG_COLOR_BASE);
+        let thumb_bounds = self.thumb_bounds();
+
+        c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+            texture.set_draw_color(base_color);
+            texture.clear();
+        })
+        .unwrap();
+
+        if let Some(track) = track {
+            let image = t.get_image(c, track.image.clone());
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                track.draw(texture, image, Rect::new(0, 0, bounds[0], bounds[1]));
+            })
+            .unwrap();
+        }
+
+        match thumb {
+            Some(thumb) => {
+                let image = t.get_image(c, thumb.image.clone());
+
+                c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                    thumb.draw(texture, image, thumb_bounds);
+                })
+                .unwrap();
+            }
+            None => {
+                let thumb_color = self.thumb_color();
+                let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+                let thumb_shape = match self.thumb_shape() {
+                    ThumbShape::Image => ThumbShape::Rect,
+                    shape => shape,
+                };
+
+                c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                    draw_thumb(
+                        texture,
+                        thumb_shape,
+                        thumb_bounds,
+                        thumb_color,
+                        border_color,
+                    );
+                })
+                .unwrap();
+            }
+        }
+    }
+
     /// Internal function that triggers the `on_value_changed` callback.
     fn call_value_changed_callback(
         &mut self,
@@ -118,121 +339,60 @@ impl CanvasHelper for SliderWidget {}
 /// This is the `Widget` implementation of the `SliderWidget`.
 impl Widget for SliderWidget {
     /// Draws the `SliderWidget` contents.
-    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
         if self.get_config().invalidated() {
             let bounds = self.get_config().get_size(CONFIG_SIZE);
 
             self.texture_store
                 .create_or_resize_texture(c, bounds[0] as u32, bounds[1] as u32);
 
-            // Draw base - three lines in the center
-            let half_height = (self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] / 2) as i32;
-            let half_width = (self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH] / 2) as i32;
-            let height = (self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT]) as i32;
-            let width = (self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH]) as i32;
+            if let Some(skin) = self.skin.as_ref() {
+                let state =
+                    SkinState::from_flags(self.config.is_enabled(), self.in_bounds, self.active);
+                let track = skin.get_patch(SkinPart::SliderTrack, state).cloned();
+                let thumb = skin.get_patch(SkinPart::SliderThumb, state).cloned();
+
+                self.draw_skinned(c, t, track, thumb);
+
+                return self.texture_store.get_optional_ref();
+            }
+
             let base_color = self.get_color(CONFIG_COLOR_BASE);
-            let orientation = self.orientation.clone();
-            let min = self.min;
-            let max = self.max;
-            let current = self.current;
+            let track_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(192, 192, 192));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let thumb_color = self.thumb_color();
+            let thumb_shape = self.thumb_shape();
+            let track_bounds = self.track_bounds();
+            let thumb_bounds = self.thumb_bounds();
 
             c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
                 texture.set_draw_color(base_color);
                 texture.clear();
 
-                if orientation == SliderHorizontal {
-                    texture.set_draw_color(Color::RGB(192, 192, 192));
-                    texture
-                        .draw_line(
-                            Point::new(10, half_height),
-                            Point::new(width - 10, half_height),
-                        )
-                        .unwrap();
-
-                    texture
-                        .draw_line(
-                            Point::new(10, half_height - 1),
-                            Point::new(width - 10, half_height - 1),
-                        )
-                        .unwrap();
-
-                    texture
-                        .draw_line(
-                            Point::new(10, half_height + 1),
-                            Point::new(width - 10, half_height + 1),
-                        )
-                        .unwrap();
-
-                    // Draw slider at current value
-                    let full_range = max - min;
-                    let slider_center =
-                        ((width as f64 / full_range as f64) * (current - min) as f64) as u32;
-                    let slider_start = if slider_center >= width as u32 - 15 {
-                        width as u32 - 30
-                    } else if slider_center <= 15 {
-                        0
-                    } else {
-                        slider_center - 15
-                    };
-
-                    texture.set_draw_color(base_color);
-                    texture
-                        .fill_rect(Rect::new(slider_start as i32, 0, 30, bounds[SIZE_HEIGHT]))
-                        .unwrap();
-
-                    texture.set_draw_color(Color::RGB(0, 0, 0));
-                    texture
-                        .draw_rect(Rect::new(slider_start as i32, 0, 30, bounds[SIZE_HEIGHT]))
-                        .unwrap();
-                } else if orientation == SliderVertical {
-                    // Draw base - three lines in the center
-
-                    texture.set_draw_color(Color::RGB(192, 192, 192));
-                    texture
-                        .draw_line(
-                            Point::new(half_width, 10),
-                            Point::new(half_width, height - 10),
-                        )
-                        .unwrap();
-
-                    texture
-                        .draw_line(
-                            Point::new(half_width - 1, 10),
-                            Point::new(half_width - 1, height - 10),
-                        )
-                        .unwrap();
-
-                    texture
-                        .draw_line(
-                            Point::new(half_width + 1, 10),
-                            Point::new(half_width + 1, height - 10),
-                        )
-                        .unwrap();
-
-                    // Draw slider at current value
-                    let full_range = max - min;
-                    let slider_center =
-                        ((height as f64 / full_range as f64) * (current - min) as f64) as u32;
-                    let slider_start = if slider_center >= height as u32 - 15 {
-                        height as u32 - 30
-                    } else if slider_center <= 15 {
-                        0
-                    } else {
-                        slider_center - 15
-                    };
-
-                    texture.set_draw_color(base_color);
-                    texture
-                        .fill_rect(Rect::new(0, slider_start as i32, bounds[SIZE_WIDTH], 30))
-                        .unwrap();
-
-                    texture.set_draw_color(Color::RGB(0, 0, 0));
-                    texture
-                        .draw_rect(Rect::new(0, slider_start as i32, bounds[SIZE_WIDTH], 30))
-                        .unwrap();
+                texture.set_draw_color(track_color);
+                texture.fill_rect(track_bounds).unwrap();
+
+                if thumb_shape != ThumbShape::Image {
+                    draw_thumb(
+                        texture,
+                        thumb_shape,
+                        thumb_bounds,
+                        thumb_color,
+                        border_color,
+                    );
                 }
             })
             .unwrap();
+
+            if thumb_shape == ThumbShape::Image {
+                let image_name = self.get_config().get_text(CONFIG_THUMB_IMAGE);
+                let image = t.get_image(c, image_name);
+
+                c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                    texture.copy(image, None, thumb_bounds).unwrap();
+                })
+                .unwrap();
+            }
         }
 
         self.texture_store.get_optional_ref()
@@ -241,11 +401,19 @@ impl Widget for SliderWidget {
     /// When a mouse enters the bounds of the `Widget`, this function is triggered.
     fn mouse_entered(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
         self.in_bounds = true;
+
+        if self.has_hover_state() {
+            self.get_config().set_invalidated(true);
+        }
     }
 
     /// When a mouse exits the bounds of the `Widget`, this function is triggered.
     fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
         self.in_bounds = false;
+
+        if self.has_hover_state() {
+            self.get_config().set_invalidated(true);
+        }
     }
 
     /// When a mouse is moved in the bounds of this `Widget`, this function is triggered.
@@ -335,6 +503,22 @@ impl Widget for SliderWidget {
         self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
     }
 
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    /// Stores the `Skin`, drawing its `SkinPart::SliderTrack` and `SkinPart::SliderThumb` images in
+    /// place of the standard track lines and thumb.
+    fn apply_skin(&mut self, skin: Option<&Skin>) {
+        self.skin = skin
+            .filter(|x| x.has_part(SkinPart::SliderTrack) || x.has_part(SkinPart::SliderThumb))
+            .cloned();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
@@ -0,0 +1,271 @@
+// Pushrod Widget Library
+// Sparkline Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Point;
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+
+/// This is the storage object for the `SparklineWidget`.  It stores the config, properties,
+/// callback registry, the values in the rolling window, and how they are drawn.
+pub struct SparklineWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    values: VecDeque<f64>,
+    capacity: usize,
+    filled: bool,
+    autoscale: bool,
+    min: f64,
+    max: f64,
+}
+
+/// This is the implementation of the `SparklineWidget`, a compact line drawn from a rolling window
+/// of values, with no axes or labels.  The newest value is drawn at the right edge, and the oldest
+/// values scroll off the left edge as new values are pushed.  The line is drawn in
+/// `CONFIG_COLOR_SECONDARY` over `CONFIG_COLOR_BASE`, and when filled, the area under the line is
+/// drawn in a color halfway between the two.  As no text is drawn, pushing a value every tick only
+/// costs a redraw of this `Widget`'s texture.
+impl SparklineWidget {
+    /// Creates a new `SparklineWidget`, given the `x, y, w, h` coordinates, and the number of values
+    /// kept in its rolling window.
+    pub fn new(points: Points, size: Size, capacity: usize) -> Self {
+        let capacity = capacity.max(2);
+
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+            filled: false,
+            autoscale: true,
+            min: 0.0,
+            max: 1.0,
+        }
+    }
+
+    /// Adds a value at the right edge, dropping the oldest value if the rolling window is full.
+    pub fn push_value(&mut self, value: f64) {
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+
+        self.values.push_back(value);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Replaces all of the values, keeping only the newest ones that fit in the rolling window.
+    pub fn set_values(&mut self, values: &[f64]) {
+        let start = values.len().saturating_sub(self.capacity);
+
+        self.values = values[start..].iter().copied().collect();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the values in the rolling window, from oldest to newest.
+    pub fn get_values(&self) -> Vec<f64> {
+        self.values.iter().copied().collect()
+    }
+
+    /// Removes all of the values.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the number of values kept in the rolling window, dropping the oldest values if there
+    /// are more.  The capacity is at least `2`.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(2);
+
+        while self.values.len() > self.capacity {
+            self.values.pop_front();
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the number of values kept in the rolling window.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Turns the fill of the area under the line on or off.
+    pub fn set_filled(&mut self, filled: bool) {
+        self.filled = filled;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the area under the line is filled.
+    pub fn get_filled(&self) -> bool {
+        self.filled
+    }
+
+    /// Sets a fixed range of values spanning the height of this `Widget`, turning off automatic
+    /// scaling.  Values outside of the range are clipped.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.autoscale = false;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Turns automatic scaling on or off.  When on, the lowest and highest values in the rolling
+    /// window span the height of this `Widget`.  When turned off, the range last set by
+    /// `set_range` is used.
+    pub fn set_autoscale(&mut self, autoscale: bool) {
+        self.autoscale = autoscale;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the values are scaled automatically.
+    pub fn get_autoscale(&self) -> bool {
+        self.autoscale
+    }
+
+    /// Returns the range of values spanning the height of this `Widget`.
+    fn value_range(&self) -> (f64, f64) {
+        if !self.autoscale {
+            return (self.min, self.max);
+        }
+
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::MAX, f64::MIN), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+
+        if min > max {
+            (0.0, 1.0)
+        } else {
+            (min, max)
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `SparklineWidget`.
+impl Widget for SparklineWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let line_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(40, 110, 220));
+            let fill_color = Color::RGB(
+                ((base_color.r as u16 + line_color.r as u16) / 2) as u8,
+                ((base_color.g as u16 + line_color.g as u16) / 2) as u8,
+                ((base_color.b as u16 + line_color.b as u16) / 2) as u8,
+            );
+            let (min, max) = self.value_range();
+            let range = max - min;
+            let width = bounds[SIZE_WIDTH].max(2) as i32;
+            let height = bounds[SIZE_HEIGHT].max(2) as i32;
+            let spacing = (width - 1) as f64 / (self.capacity - 1) as f64;
+            let newest = self.values.len() as f64 - 1.0;
+
+            // A flat line is drawn through the middle, when all of the values are the same.
+            let points: Vec<Point> = self
+                .values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let fraction = if range > 0.0 {
+                        ((value - min) / range).clamp(0.0, 1.0)
+                    } else {
+                        0.5
+                    };
+
+                    Point::new(
+                        width - 1 - ((newest - index as f64) * spacing).round() as i32,
+                        (height - 1) - (fraction * (height - 1) as f64).round() as i32,
+                    )
+                })
+                .collect();
+            let filled = self.filled;
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if filled {
+                    texture.set_draw_color(fill_color);
+
+                    for pair in points.windows(2) {
+                        let (start, end) = (pair[0], pair[1]);
+                        let columns = (end.x() - start.x()).max(1);
+
+                        for x in start.x()..end.x() {
+                            let y = start.y() + (end.y() - start.y()) * (x - start.x()) / columns;
+
+                            texture
+                                .draw_line(Point::new(x, y), Point::new(x, height - 1))
+                                .unwrap();
+                        }
+                    }
+                }
+
+                texture.set_draw_color(line_color);
+
+                if points.len() == 1 {
+                    texture.draw_point(points[0]).unwrap();
+                } else {
+                    texture.draw_lines(points.as_slice()).unwrap();
+                }
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Monitors for changes in the colors.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE | CONFIG_COLOR_SECONDARY => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
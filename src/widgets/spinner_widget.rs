@@ -0,0 +1,428 @@
+// Pushrod Widget Library
+// Spinner Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::clock;
+use crate::render::input_settings::KeyRepeat;
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the width of the up and down arrow buttons, in pixels.
+const BUTTON_WIDTH: u32 = 20;
+
+/// This is the number of pixels between the value and the arrow buttons.
+const VALUE_PADDING: i32 = 6;
+
+/// This is the number of steps that the `PageUp` and `PageDown` keys change the value by.
+const PAGE_STEPS: i32 = 10;
+
+/// This is the callback type that is used when an `on_value_changed` callback is triggered from this
+/// `Widget`.  It receives the new value.
+pub type OnSpinnerValueChangedCallbackType =
+    Option<Box<dyn FnMut(&mut SpinnerWidget, &[WidgetContainer], &[LayoutContainer], i32)>>;
+
+/// This is the storage object for the `SpinnerWidget`.  It stores the config, properties, callback
+/// registry, the value and its bounds, and the state of the arrow buttons.
+pub struct SpinnerWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    min: i32,
+    max: i32,
+    value: i32,
+    step: i32,
+    hovered_button: i32,
+    pressed_button: i32,
+    button_repeat: KeyRepeat,
+    on_value_changed: OnSpinnerValueChangedCallbackType,
+}
+
+/// This is the implementation of the `SpinnerWidget`, a compact numeric stepper that shows a value
+/// next to up and down arrow buttons.  Clicking an arrow changes the value by the step size, and
+/// holding it down repeats the change.  The mouse wheel, and the `Up`, `Down`, `PageUp`,
+/// `PageDown`, `Home`, and `End` keys, also change the value while the `Widget` has focus.
+impl SpinnerWidget {
+    /// Creates a new `SpinnerWidget` given the `x, y, w, h` coordinates, the `min` and `max` values,
+    /// and the initial `value`, which is clamped to the range.
+    pub fn new(points: Points, size: Size, min: i32, max: i32, value: i32) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            min: min.min(max),
+            max: max.max(min),
+            value: value.clamp(min.min(max), max.max(min)),
+            step: 1,
+            hovered_button: 0,
+            pressed_button: 0,
+            button_repeat: KeyRepeat::new(),
+            on_value_changed: None,
+        }
+    }
+
+    /// Sets the `min` and `max` values.  The value is clamped to the new range.  This does not
+    /// trigger the `on_value_changed` callback.
+    pub fn set_range(&mut self, min: i32, max: i32) {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.set_value(self.value);
+    }
+
+    /// Retrieves the minimum value.
+    pub fn get_min(&self) -> i32 {
+        self.min
+    }
+
+    /// Retrieves the maximum value.
+    pub fn get_max(&self) -> i32 {
+        self.max
+    }
+
+    /// Sets the value, clamped to the range from `min` to `max`.  This does not trigger the
+    /// `on_value_changed` callback.
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the value.
+    pub fn get_value(&self) -> i32 {
+        self.value
+    }
+
+    /// Sets the amount that the arrow buttons change the value by.
+    pub fn set_step(&mut self, step: i32) {
+        self.step = step.max(1);
+    }
+
+    /// Retrieves the amount that the arrow buttons change the value by.
+    pub fn get_step(&self) -> i32 {
+        self.step
+    }
+
+    /// Assigns the callback closure that will be used when the user changes the value.
+    pub fn on_value_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut SpinnerWidget, &[WidgetContainer], &[LayoutContainer], i32) + 'static,
+    {
+        self.on_value_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that changes the value, triggering the `on_value_changed` callback if it
+    /// changed.
+    fn change_value(
+        &mut self,
+        value: i64,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let value = value.clamp(self.min as i64, self.max as i64) as i32;
+
+        if value == self.value {
+            return;
+        }
+
+        self.value = value;
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_value_changed.take() {
+            cb(self, widgets, layouts, value);
+            self.on_value_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that changes the value by `steps` steps.
+    fn step_value(&mut self, steps: i32, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        let value = self.value as i64 + steps as i64 * self.step as i64;
+
+        self.change_value(value, widgets, layouts);
+    }
+
+    /// Internal function that returns the arrow button at the window coordinates in `points`: `1`
+    /// for the up button, `-1` for the down button, or `0` if the point is not over either button.
+    fn button_at(&mut self, points: &[i32]) -> i32 {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+        let x = points[POINT_X] - self.get_config().to_x(0);
+        let y = points[POINT_Y] - self.get_config().to_y(0);
+
+        if x < size[SIZE_WIDTH].saturating_sub(BUTTON_WIDTH) as i32
+            || x >= size[SIZE_WIDTH] as i32
+            || y < 0
+            || y >= size[SIZE_HEIGHT] as i32
+        {
+            0
+        } else if y < size[SIZE_HEIGHT] as i32 / 2 {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+/// Draws a filled arrow triangle pointing up (`direction` of `1`) or down (`-1`), centered in
+/// `bounds`.
+fn draw_arrow(c: &mut Canvas<Window>, bounds: Rect, direction: i32) {
+    let half_width = (bounds.width() as i32 / 4).max(2);
+    let height = half_width;
+    let center_x = bounds.x() + bounds.width() as i32 / 2;
+    let top = bounds.y() + (bounds.height() as i32 - height) / 2;
+
+    for row in 0..height {
+        let half = if direction > 0 {
+            row * half_width / height
+        } else {
+            (height - 1 - row) * half_width / height
+        };
+
+        c.draw_line(
+            Point::new(center_x - half, top + row),
+            Point::new(center_x + half, top + row),
+        )
+        .unwrap();
+    }
+}
+
+/// This is the `Widget` implementation of the `SpinnerWidget`.
+impl Widget for SpinnerWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let button_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(224, 224, 224));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(200, 200, 240));
+            let pressed_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(160, 160, 224));
+            let hovered_button = self.hovered_button;
+            let pressed_button = self.pressed_button;
+            let button_x = bounds[SIZE_WIDTH].saturating_sub(BUTTON_WIDTH) as i32;
+            let half_height = bounds[SIZE_HEIGHT] / 2;
+            let up_bounds = Rect::new(button_x, 0, BUTTON_WIDTH, half_height.max(1));
+            let down_bounds = Rect::new(
+                button_x,
+                half_height as i32,
+                BUTTON_WIDTH,
+                (bounds[SIZE_HEIGHT] - half_height).max(1),
+            );
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+            let surface = font
+                .render(&self.value.to_string())
+                .blended(text_color)
+                .map_err(|e| e.to_string())
+                .unwrap();
+            let value_texture = texture_creator
+                .create_texture_from_surface(&surface)
+                .map_err(|e| e.to_string())
+                .unwrap();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                let TextureQuery { width, height, .. } = value_texture.query();
+
+                texture
+                    .copy(
+                        &value_texture,
+                        None,
+                        Rect::new(
+                            button_x - VALUE_PADDING - width as i32,
+                            (bounds[SIZE_HEIGHT] as i32 - height as i32) / 2,
+                            width,
+                            height,
+                        ),
+                    )
+                    .unwrap();
+
+                for (direction, button_bounds) in [(1, up_bounds), (-1, down_bounds)].iter() {
+                    let fill_color = if pressed_button == *direction && hovered_button == *direction
+                    {
+                        pressed_color
+                    } else if hovered_button == *direction {
+                        hover_color
+                    } else {
+                        button_color
+                    };
+
+                    texture.set_draw_color(fill_color);
+                    texture.fill_rect(*button_bounds).unwrap();
+                    texture.set_draw_color(border_color);
+                    texture.draw_rect(*button_bounds).unwrap();
+                    texture.set_draw_color(text_color);
+                    draw_arrow(texture, *button_bounds, *direction);
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the value texture is only used within this function.
+            unsafe {
+                value_texture.destroy();
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Repeats the change of value while an arrow button is held down.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.button_repeat.is_active() && self.hovered_button == self.pressed_button {
+            let delay = self.get_numeric(CONFIG_KEY_REPEAT_DELAY);
+            let interval = self.get_numeric(CONFIG_KEY_REPEAT_INTERVAL);
+
+            if self
+                .button_repeat
+                .should_repeat(clock::now_millis(), delay, interval)
+            {
+                self.step_value(self.pressed_button, _widgets, _layouts);
+            }
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.hovered_button != 0 {
+            self.hovered_button = 0;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the arrow button under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let hovered_button = self.button_at(&points);
+
+        if hovered_button != self.hovered_button {
+            self.hovered_button = hovered_button;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Changes the value by one step for each step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.step_value(points[POINT_Y], _widgets, _layouts);
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Changes the value when an arrow button is pressed, and starts repeating the change while it
+    /// is held down.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 {
+            if state && self.hovered_button != 0 {
+                self.pressed_button = self.hovered_button;
+                self.button_repeat.press(clock::now_millis());
+                self.step_value(self.pressed_button, _widgets, _layouts);
+                self.get_config().set_invalidated(true);
+            } else if !state && self.pressed_button != 0 {
+                self.pressed_button = 0;
+                self.button_repeat.release();
+                self.get_config().set_invalidated(true);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Changes the value with the `Up`, `Down`, `PageUp`, `PageDown`, `Home`, and `End` keys.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            match keycode {
+                Keycode::Up => self.step_value(1, _widgets, _layouts),
+                Keycode::Down => self.step_value(-1, _widgets, _layouts),
+                Keycode::PageUp => self.step_value(PAGE_STEPS, _widgets, _layouts),
+                Keycode::PageDown => self.step_value(-PAGE_STEPS, _widgets, _layouts),
+                Keycode::Home => self.change_value(self.min as i64, _widgets, _layouts),
+                Keycode::End => self.change_value(self.max as i64, _widgets, _layouts),
+                _ => (),
+            }
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,291 @@
+// Pushrod Widget Library
+// Status Bar Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between the text of a pane and its left edge.
+const PANE_PADDING: u32 = 6;
+
+/// These are the ways in which the width of a pane of a `StatusBarWidget` is determined.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PaneWidth {
+    /// The pane is always the given number of pixels wide.
+    Fixed(u32),
+
+    /// The pane shares the width left over by the fixed panes with the other stretch panes, in
+    /// proportion to the given weight.
+    Stretch(u32),
+}
+
+/// This is a single pane of a `StatusBarWidget`: how its width is determined, and its text.
+struct Pane {
+    width: PaneWidth,
+    text: String,
+}
+
+/// This is the storage object for the `StatusBarWidget`.  It stores the config, properties,
+/// callback registry, and the panes.
+pub struct StatusBarWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    panes: Vec<Pane>,
+}
+
+/// This is the implementation of the `StatusBarWidget`, a bar that stays anchored to the bottom of
+/// the window, and shows text in one or more panes, separated by dividers.  When the window is
+/// resized, the bar moves to the new bottom of the window, and stretches to its full width.  The
+/// bar is drawn in `CONFIG_COLOR_SECONDARY`, with its top edge and dividers in
+/// `CONFIG_COLOR_BORDER`, and its text in `CONFIG_COLOR_TEXT`.
+impl StatusBarWidget {
+    /// Creates a new `StatusBarWidget` for a window of `window_width` by `window_height` pixels,
+    /// given the `height` of the bar.  The bar is placed along the bottom of the window, and has no
+    /// panes until `add_pane` is called.
+    pub fn new(window_width: u32, window_height: u32, height: u32) -> Self {
+        Self {
+            config: WidgetConfig::new(
+                vec![0, window_height.saturating_sub(height) as i32],
+                vec![window_width, height],
+            ),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            panes: vec![],
+        }
+    }
+
+    /// Adds an empty pane to the right of the existing panes, given how its width is determined.
+    /// Returns the index of the new pane.
+    pub fn add_pane(&mut self, width: PaneWidth) -> usize {
+        self.panes.push(Pane {
+            width,
+            text: String::new(),
+        });
+        self.get_config().set_invalidated(true);
+
+        self.panes.len() - 1
+    }
+
+    /// Retrieves the number of panes.
+    pub fn get_pane_count(&self) -> usize {
+        self.panes.len()
+    }
+
+    /// Sets the text of a pane.  Text that does not fit within the pane is cut off.
+    pub fn set_pane_text(&mut self, index: usize, text: &str) {
+        if let Some(pane) = self.panes.get_mut(index) {
+            if pane.text != text {
+                pane.text = String::from(text);
+                self.get_config().set_invalidated(true);
+            }
+        }
+    }
+
+    /// Retrieves the text of a pane.
+    pub fn get_pane_text(&self, index: usize) -> Option<&str> {
+        self.panes.get(index).map(|x| x.text.as_str())
+    }
+
+    /// Internal function that returns the left edge and width of each pane, given the width of the
+    /// bar.
+    fn pane_bounds(&self, width: u32) -> Vec<(i32, u32)> {
+        let fixed: u32 = self
+            .panes
+            .iter()
+            .map(|x| match x.width {
+                PaneWidth::Fixed(width) => width,
+                PaneWidth::Stretch(_) => 0,
+            })
+            .sum();
+        let weights: u32 = self
+            .panes
+            .iter()
+            .map(|x| match x.width {
+                PaneWidth::Fixed(_) => 0,
+                PaneWidth::Stretch(weight) => weight,
+            })
+            .sum();
+        let remaining = width.saturating_sub(fixed);
+        let mut left = 0;
+
+        self.panes
+            .iter()
+            .map(|x| {
+                let pane_width = match x.width {
+                    PaneWidth::Fixed(width) => width,
+                    PaneWidth::Stretch(weight) => (remaining as u64 * weight as u64)
+                        .checked_div(weights as u64)
+                        .unwrap_or(0) as u32,
+                };
+                let bounds = (left, pane_width);
+
+                left += pane_width as i32;
+                bounds
+            })
+            .collect()
+    }
+}
+
+/// This is the `Widget` implementation of the `StatusBarWidget`.
+impl Widget for StatusBarWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(224, 224, 224));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(160, 160, 160));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let height = bounds[SIZE_HEIGHT];
+            let pane_bounds = self.pane_bounds(bounds[SIZE_WIDTH]);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+            let text_textures: Vec<Option<Texture>> = self
+                .panes
+                .iter()
+                .map(|pane| {
+                    if pane.text.is_empty() {
+                        return None;
+                    }
+
+                    let surface = font
+                        .render(&pane.text)
+                        .blended(text_color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+
+                    Some(
+                        texture_creator
+                            .create_texture_from_surface(&surface)
+                            .map_err(|e| e.to_string())
+                            .unwrap(),
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (index, ((left, width), text)) in
+                    pane_bounds.iter().zip(text_textures.iter()).enumerate()
+                {
+                    if let Some(text) = text {
+                        let TextureQuery {
+                            width: text_width,
+                            height: text_height,
+                            ..
+                        } = text.query();
+                        let visible_width = text_width.min(width.saturating_sub(PANE_PADDING * 2));
+
+                        if visible_width > 0 {
+                            texture
+                                .copy(
+                                    text,
+                                    Rect::new(0, 0, visible_width, text_height),
+                                    Rect::new(
+                                        left + PANE_PADDING as i32,
+                                        (height as i32 - text_height as i32) / 2,
+                                        visible_width,
+                                        text_height,
+                                    ),
+                                )
+                                .unwrap();
+                        }
+                    }
+
+                    if index > 0 {
+                        texture.set_draw_color(border_color);
+                        texture
+                            .draw_line(Point::new(*left, 3), Point::new(*left, height as i32 - 4))
+                            .unwrap();
+                    }
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_line(
+                        Point::new(0, 0),
+                        Point::new(bounds[SIZE_WIDTH] as i32 - 1, 0),
+                    )
+                    .unwrap();
+            })
+            .unwrap();
+
+            for text in text_textures.into_iter().flatten() {
+                // Safety: the text textures are only used within this function.
+                unsafe {
+                    text.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Moves the bar to the bottom of the resized window, and stretches it to the full width of the
+    /// window.
+    fn window_resized(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        size: Size,
+    ) {
+        let height = self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT];
+
+        self.get_config().set_point(
+            CONFIG_ORIGIN,
+            0,
+            size[SIZE_HEIGHT].saturating_sub(height) as i32,
+        );
+        self.get_config()
+            .set_size(CONFIG_SIZE, size[SIZE_WIDTH], height);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -59,8 +59,12 @@ impl TabBarWidget {
     /// Creates a new `TabBarWidget`, given the `x, y, w, h` coordinates, and the tab items to be shown
     /// in the tab bar area.
     pub fn new(points: Points, size: Size, tab_items: Vec<String>) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
         Self {
-            config: WidgetConfig::new(points, size),
+            config,
             system_properties: HashMap::new(),
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
@@ -289,6 +293,13 @@ impl Widget for TabBarWidget {
         }
     }
 
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
@@ -0,0 +1,480 @@
+// Pushrod Widget Library
+// Tab Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of the tab bar at the top of the `TabWidget`.
+const TAB_HEIGHT: u32 = 30;
+
+/// This is the number of pixels between the name of a tab and the edges of the tab.
+const TAB_PADDING: u32 = 12;
+
+/// This is the callback type that is used when an `on_tab_selected` callback is triggered from this
+/// `Widget`.  It receives the index of the selected tab, starting from `0`.
+pub type OnTabSelectedCallbackType =
+    Option<Box<dyn FnMut(&mut TabWidget, &[WidgetContainer], &[LayoutContainer], usize)>>;
+
+/// This is a single tab of a `TabWidget`: its name, and the IDs of the `Widget`s shown when the tab
+/// is selected.
+struct Tab {
+    name: String,
+    widget_ids: Vec<i32>,
+}
+
+/// This is the storage object for the `TabWidget`.  It stores the config, properties, callback
+/// registry, the tabs and the `Widget`s they own, and the selected and hovered tabs.
+pub struct TabWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    tabs: Vec<Tab>,
+    tab_widths: Vec<u32>,
+    selected_tab: i32,
+    hovered_tab: i32,
+    visibility_changed: bool,
+    mouse_position: Points,
+    on_tab_selected: OnTabSelectedCallbackType,
+}
+
+/// This is the implementation of the `TabWidget`, which draws a tab bar above a content area, and
+/// owns a set of named tabs.  Each tab refers to a list of `Widget` IDs: when a tab is selected,
+/// its `Widget`s, along with all of their children, are shown, and the `Widget`s of all other tabs
+/// are hidden.  The `Widget`s of a tab are typically placed within the content area, below the tab
+/// bar, but this is not required.
+impl TabWidget {
+    /// Creates a new `TabWidget` given the `x, y, w, h` coordinates.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            tabs: vec![],
+            tab_widths: vec![],
+            selected_tab: -1,
+            hovered_tab: -1,
+            visibility_changed: false,
+            mouse_position: vec![0, 0],
+            on_tab_selected: None,
+        }
+    }
+
+    /// Adds a tab, given its name, and the IDs of the `Widget`s that are shown when it is selected.
+    /// The first tab added is selected automatically.  Returns the index of the new tab.
+    pub fn add_tab(&mut self, name: &str, widget_ids: Vec<i32>) -> usize {
+        self.tabs.push(Tab {
+            name: String::from(name),
+            widget_ids,
+        });
+
+        if self.selected_tab == -1 {
+            self.selected_tab = 0;
+        }
+
+        self.tab_widths.clear();
+        self.visibility_changed = true;
+        self.get_config().set_invalidated(true);
+
+        self.tabs.len() - 1
+    }
+
+    /// Adds a `Widget` ID to an existing tab.
+    pub fn add_widget_to_tab(&mut self, tab: usize, widget_id: i32) {
+        if let Some(tab) = self.tabs.get_mut(tab) {
+            tab.widget_ids.push(widget_id);
+            self.visibility_changed = true;
+        }
+    }
+
+    /// Retrieves the number of tabs.
+    pub fn get_tab_count(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Retrieves the name of a tab.
+    pub fn get_tab_name(&self, tab: usize) -> Option<&str> {
+        self.tabs.get(tab).map(|x| x.name.as_str())
+    }
+
+    /// Retrieves the IDs of the `Widget`s owned by a tab.
+    pub fn get_tab_widgets(&self, tab: usize) -> Option<&[i32]> {
+        self.tabs.get(tab).map(|x| x.widget_ids.as_slice())
+    }
+
+    /// Retrieves the index of the selected tab, or `-1` if there are no tabs.
+    pub fn get_selected_tab(&self) -> i32 {
+        self.selected_tab
+    }
+
+    /// Selects a tab.  The `Widget`s of the tab are shown on the next frame.  This does not trigger
+    /// the `on_tab_selected` callback.
+    pub fn set_selected_tab(&mut self, tab: usize) {
+        if tab < self.tabs.len() && tab as i32 != self.selected_tab {
+            self.selected_tab = tab as i32;
+            self.visibility_changed = true;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Assigns the callback closure that will be used when the user selects a tab.
+    pub fn on_tab_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TabWidget, &[WidgetContainer], &[LayoutContainer], usize) + 'static,
+    {
+        self.on_tab_selected = Some(Box::new(callback));
+    }
+
+    /// Selects a tab, updating the visibility of the `Widget`s of each tab, and triggering the
+    /// `on_tab_selected` callback.
+    fn select_tab(&mut self, tab: usize, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if tab as i32 == self.selected_tab {
+            return;
+        }
+
+        self.set_selected_tab(tab);
+        self.update_visibility(widgets);
+
+        if let Some(mut cb) = self.on_tab_selected.take() {
+            cb(self, widgets, layouts, tab);
+            self.on_tab_selected = Some(cb);
+        }
+    }
+
+    /// Shows the `Widget`s of the selected tab, and hides the `Widget`s of all other tabs.  Hidden
+    /// `Widget`s do not hide their children, so each `Widget`'s children are updated as well.
+    fn update_visibility(&mut self, widgets: &[WidgetContainer]) {
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let hidden = index as i32 != self.selected_tab;
+
+            for widget_id in &tab.widget_ids {
+                set_subtree_hidden(widgets, *widget_id, hidden);
+            }
+        }
+
+        self.visibility_changed = false;
+    }
+
+    /// Returns the tab under the given X coordinate, relative to the `Widget`.
+    fn tab_at(&self, x: i32) -> i32 {
+        let mut left = 0;
+
+        for (index, width) in self.tab_widths.iter().enumerate() {
+            if x >= left && x < left + *width as i32 {
+                return index as i32;
+            }
+
+            left += *width as i32 - 1;
+        }
+
+        -1
+    }
+
+    /// Returns the tab under the last known mouse position, or `-1` if the mouse is not over a tab.
+    fn tab_at_mouse(&mut self) -> i32 {
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(0);
+        let y = self.mouse_position[POINT_Y] - self.get_config().to_y(0);
+
+        if y >= 0 && y < TAB_HEIGHT as i32 {
+            self.tab_at(x)
+        } else {
+            -1
+        }
+    }
+}
+
+/// Hides or shows a `Widget`, along with all of its children.  The `Widget` that is currently
+/// handling an event cannot be borrowed, and is skipped.
+pub(crate) fn set_subtree_hidden(widgets: &[WidgetContainer], widget_id: i32, hidden: bool) {
+    if widget_id <= 0 || widget_id as usize >= widgets.len() {
+        return;
+    }
+
+    if let Ok(mut widget) = widgets[widget_id as usize].widget.try_borrow_mut() {
+        if widget.get_config().is_hidden() != hidden {
+            if hidden {
+                widget.get_config().hide();
+            } else {
+                widget.get_config().show();
+            }
+        }
+    }
+
+    for child in widgets
+        .iter()
+        .filter(|x| x.get_parent_id() == widget_id && x.get_widget_id() != widget_id)
+    {
+        set_subtree_hidden(widgets, child.get_widget_id(), hidden);
+    }
+}
+
+/// This is the `Widget` implementation of the `TabWidget`.
+impl Widget for TabWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let hover_color = self.get_color(CONFIG_COLOR_HOVER);
+            let selected_color = self.get_color(CONFIG_COLOR_SELECTED);
+            let tab_color = self.get_color(CONFIG_COLOR_SECONDARY);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let selected_tab = self.selected_tab;
+            let hovered_tab = self.hovered_tab;
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+
+            self.tab_widths = self
+                .tabs
+                .iter()
+                .map(|tab| font.size_of(&tab.name).map_or(0, |(w, _)| w) + TAB_PADDING * 2)
+                .collect();
+
+            let tab_widths = self.tab_widths.clone();
+            let name_textures: Vec<Option<Texture>> = self
+                .tabs
+                .iter()
+                .map(|tab| {
+                    if tab.name.is_empty() {
+                        return None;
+                    }
+
+                    let surface = font
+                        .render(&tab.name)
+                        .blended(text_color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+
+                    Some(
+                        texture_creator
+                            .create_texture_from_surface(&surface)
+                            .map_err(|e| e.to_string())
+                            .unwrap(),
+                    )
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                let mut x = 0;
+
+                for (index, (width, name)) in
+                    tab_widths.iter().zip(name_textures.iter()).enumerate()
+                {
+                    let fill_color = if index as i32 == selected_tab {
+                        selected_color
+                    } else if index as i32 == hovered_tab {
+                        hover_color
+                    } else {
+                        tab_color
+                    };
+
+                    texture.set_draw_color(fill_color);
+                    texture
+                        .fill_rect(Rect::new(x, 0, *width, TAB_HEIGHT))
+                        .unwrap();
+                    texture.set_draw_color(border_color);
+                    texture
+                        .draw_rect(Rect::new(x, 0, *width, TAB_HEIGHT))
+                        .unwrap();
+
+                    if let Some(name) = name {
+                        let TextureQuery { width, height, .. } = name.query();
+
+                        texture
+                            .copy(
+                                name,
+                                None,
+                                Rect::new(
+                                    x + TAB_PADDING as i32,
+                                    (TAB_HEIGHT as i32 - height as i32) / 2,
+                                    width,
+                                    height,
+                                ),
+                            )
+                            .unwrap();
+                    }
+
+                    x += *width as i32 - 1;
+                }
+
+                // The content area is bordered, and joins the selected tab.
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(
+                        0,
+                        TAB_HEIGHT as i32 - 1,
+                        bounds[SIZE_WIDTH],
+                        bounds[SIZE_HEIGHT].saturating_sub(TAB_HEIGHT - 1),
+                    ))
+                    .unwrap();
+
+                if selected_tab >= 0 && (selected_tab as usize) < tab_widths.len() {
+                    let left: i32 = tab_widths[..selected_tab as usize]
+                        .iter()
+                        .map(|width| *width as i32 - 1)
+                        .sum();
+
+                    texture.set_draw_color(selected_color);
+                    texture
+                        .draw_line(
+                            Point::new(left + 1, TAB_HEIGHT as i32 - 1),
+                            Point::new(
+                                left + tab_widths[selected_tab as usize] as i32 - 2,
+                                TAB_HEIGHT as i32 - 1,
+                            ),
+                        )
+                        .unwrap();
+                }
+            })
+            .unwrap();
+
+            for name in name_textures.into_iter().flatten() {
+                // Safety: the name textures are only used within this function.
+                unsafe {
+                    name.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Applies any pending changes to the visibility of the `Widget`s of each tab.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.visibility_changed {
+            self.update_visibility(_widgets);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Clears the highlighted tab when the mouse leaves the `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.hovered_tab != -1 {
+            self.hovered_tab = -1;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the tab under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        let hovered_tab = self.tab_at_mouse();
+
+        if hovered_tab != self.hovered_tab {
+            self.hovered_tab = hovered_tab;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Selects the tab that was clicked, showing its `Widget`s.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let tab = self.tab_at_mouse();
+
+            if tab >= 0 {
+                self.select_tab(tab as usize, _widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Moves between tabs with the `Left` and `Right` keys while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            if self.tabs.is_empty() {
+                return;
+            }
+
+            let tab = match keycode {
+                Keycode::Left => (self.selected_tab - 1).max(0),
+                Keycode::Right => (self.selected_tab + 1).min(self.tabs.len() as i32 - 1),
+                _ => return,
+            };
+
+            self.select_tab(tab as usize, _widgets, _layouts);
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
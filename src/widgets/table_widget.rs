@@ -0,0 +1,648 @@
+// Pushrod Widget Library
+// Table Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureCreator, TextureQuery};
+use sdl2::ttf::Font;
+use sdl2::video::{Window, WindowContext};
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of the header row, and of each row of data.
+const ROW_HEIGHT: u32 = 24;
+
+/// This is the number of pixels between the left edge of a cell and its text.
+const CELL_PADDING: i32 = 4;
+
+/// This is the callback type that is used when an `on_row_selected` callback is triggered from this
+/// `Widget`.  It receives the index of the selected row in the model, regardless of how the rows
+/// are sorted.
+pub type OnRowSelectedCallbackType =
+    Option<Box<dyn FnMut(&mut TableWidget, &[WidgetContainer], &[LayoutContainer], usize)>>;
+
+/// This describes a column of a `TableWidget`: its title, shown in the header row, and its width in
+/// pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableColumn {
+    pub title: String,
+    pub width: u32,
+}
+
+/// This is the implementation of the `TableColumn`.
+impl TableColumn {
+    /// Creates a new `TableColumn`.
+    pub fn new(title: &str, width: u32) -> Self {
+        Self {
+            title: String::from(title),
+            width,
+        }
+    }
+}
+
+/// This is the storage object for the `TableWidget`.  It stores the config, properties, callback
+/// registry, the columns and rows of the table, and the selection, sort and scroll state.
+pub struct TableWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    columns: Vec<TableColumn>,
+    rows: Vec<Vec<String>>,
+    row_order: Vec<usize>,
+    sort_column: Option<(usize, bool)>,
+    selected_row: i32,
+    highlighted_row: i32,
+    scroll_offset: usize,
+    mouse_position: Points,
+    on_row_selected: OnRowSelectedCallbackType,
+}
+
+/// This is the implementation of the `TableWidget`, a data grid that displays rows of text in named
+/// columns, which can be sorted by clicking on a column header.
+impl TableWidget {
+    /// Creates a new `TableWidget` given the `x, y, w, h` coordinates.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            columns: vec![],
+            rows: vec![],
+            row_order: vec![],
+            sort_column: None,
+            selected_row: -1,
+            highlighted_row: -1,
+            scroll_offset: 0,
+            mouse_position: vec![0, 0],
+            on_row_selected: None,
+        }
+    }
+
+    /// Replaces the columns of the table.
+    pub fn set_columns(&mut self, columns: Vec<TableColumn>) {
+        self.columns = columns;
+        self.sort_column = None;
+        self.sort_rows();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Adds a column to the right-hand side of the table.
+    pub fn add_column(&mut self, title: &str, width: u32) {
+        self.columns.push(TableColumn::new(title, width));
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the columns of the table.
+    pub fn get_columns(&self) -> &[TableColumn] {
+        &self.columns
+    }
+
+    /// Sets the width of a column in pixels.
+    pub fn set_column_width(&mut self, column: usize, width: u32) {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.width = width;
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Replaces the rows of the table.  Each row contains the text of each cell, in column order.
+    /// The selection is cleared, and the rows are re-sorted if the table is sorted.
+    pub fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.rows = rows;
+        self.selected_row = -1;
+        self.scroll_offset = 0;
+        self.sort_rows();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Adds a row to the table.  Returns the index of the row in the model.
+    pub fn add_row(&mut self, row: Vec<String>) -> usize {
+        self.rows.push(row);
+        self.sort_rows();
+        self.get_config().set_invalidated(true);
+
+        self.rows.len() - 1
+    }
+
+    /// Retrieves the rows of the table, in model order.
+    pub fn get_rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Retrieves the index in the model of the selected row, or `-1` if no row is selected.
+    pub fn get_selected_row(&self) -> i32 {
+        self.selected_row
+    }
+
+    /// Selects a row by its index in the model.  An index of `-1`, or an index out of range, clears
+    /// the selection.  This does not trigger the `on_row_selected` callback.
+    pub fn set_selected_row(&mut self, row: i32) {
+        self.selected_row = if row >= 0 && (row as usize) < self.rows.len() {
+            row
+        } else {
+            -1
+        };
+        self.reveal_selection();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sorts the table by a column, in ascending or descending order.  Cells that contain numbers
+    /// are compared numerically.
+    pub fn sort_by(&mut self, column: usize, ascending: bool) {
+        self.sort_column = Some((column, ascending));
+        self.sort_rows();
+        self.reveal_selection();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the column the table is sorted by, and whether it is sorted in ascending order, if
+    /// the table is sorted.
+    pub fn get_sort(&self) -> Option<(usize, bool)> {
+        self.sort_column
+    }
+
+    /// Assigns the callback closure that will be used when the user selects a row.
+    pub fn on_row_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TableWidget, &[WidgetContainer], &[LayoutContainer], usize) + 'static,
+    {
+        self.on_row_selected = Some(Box::new(callback));
+    }
+
+    /// Recomputes the order in which the rows are displayed.
+    fn sort_rows(&mut self) {
+        let mut row_order: Vec<usize> = (0..self.rows.len()).collect();
+
+        if let Some((column, ascending)) = self.sort_column {
+            let rows = &self.rows;
+            let empty = String::new();
+
+            row_order.sort_by(|a, b| {
+                let a = rows[*a].get(column).unwrap_or(&empty);
+                let b = rows[*b].get(column).unwrap_or(&empty);
+                let ordering = match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                    (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                    _ => a.cmp(b),
+                };
+
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+
+        self.row_order = row_order;
+    }
+
+    /// Returns the number of data rows that fit entirely within the bounds of the `Widget`, below the
+    /// header row.
+    fn visible_row_count(&mut self) -> usize {
+        ((self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT].saturating_sub(ROW_HEIGHT)
+            / ROW_HEIGHT) as usize)
+            .max(1)
+    }
+
+    /// Scrolls the table so that the selected row is shown.
+    fn reveal_selection(&mut self) {
+        let visible_row_count = self.visible_row_count();
+        let selected_row = self.selected_row;
+
+        if let Some(position) = self
+            .row_order
+            .iter()
+            .position(|row| *row as i32 == selected_row)
+        {
+            if position < self.scroll_offset {
+                self.scroll_offset = position;
+            } else if position >= self.scroll_offset + visible_row_count {
+                self.scroll_offset = position + 1 - visible_row_count;
+            }
+        }
+    }
+
+    /// Selects a row by its display position, triggering the `on_row_selected` callback.
+    fn select_position(
+        &mut self,
+        position: usize,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let row = self.row_order[position];
+
+        self.selected_row = row as i32;
+        self.reveal_selection();
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_row_selected.take() {
+            cb(self, widgets, layouts, row);
+            self.on_row_selected = Some(cb);
+        }
+    }
+
+    /// Returns the column under the given X coordinate, relative to the `Widget`.
+    fn column_at(&self, x: i32) -> Option<usize> {
+        let mut left = 0;
+
+        for (index, column) in self.columns.iter().enumerate() {
+            if x >= left && x < left + column.width as i32 {
+                return Some(index);
+            }
+
+            left += column.width as i32;
+        }
+
+        None
+    }
+
+    /// Returns the display position of the data row under the last known mouse position, or `-1` if
+    /// the mouse is over the header row, or below the last row.
+    fn position_at_mouse(&mut self) -> i32 {
+        let y = self.mouse_position[POINT_Y] - self.get_config().to_y(0) - ROW_HEIGHT as i32;
+        let position = self.scroll_offset as i32 + y / ROW_HEIGHT as i32;
+
+        if y >= 0 && (position as usize) < self.rows.len() {
+            position
+        } else {
+            -1
+        }
+    }
+}
+
+/// Renders the text of a cell to a `Texture`, returning `None` if the text is empty.
+fn render_cell(
+    font: &Font,
+    texture_creator: &TextureCreator<WindowContext>,
+    text: &str,
+    color: Color,
+) -> Option<Texture> {
+    if text.is_empty() {
+        return None;
+    }
+
+    let surface = font
+        .render(text)
+        .blended(color)
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+    Some(
+        texture_creator
+            .create_texture_from_surface(&surface)
+            .map_err(|e| e.to_string())
+            .unwrap(),
+    )
+}
+
+/// Copies the text of a cell into the cell at `x, y`, clipped to the width of the cell.
+fn copy_cell(c: &mut Canvas<Window>, font_texture: &Texture, x: i32, y: i32, cell_width: u32) {
+    let TextureQuery { width, height, .. } = font_texture.query();
+    let width = width.min(cell_width.saturating_sub(CELL_PADDING as u32 * 2));
+
+    if width == 0 {
+        return;
+    }
+
+    c.copy(
+        font_texture,
+        Rect::new(0, 0, width, height),
+        Rect::new(
+            x + CELL_PADDING,
+            y + (ROW_HEIGHT as i32 - height as i32) / 2,
+            width,
+            height,
+        ),
+    )
+    .unwrap();
+}
+
+/// This is the `Widget` implementation of the `TableWidget`.
+impl Widget for TableWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let hover_color = self.get_color(CONFIG_COLOR_HOVER);
+            let selected_color = self.get_color(CONFIG_COLOR_SELECTED);
+            let header_color = self.get_color(CONFIG_COLOR_SECONDARY);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let visible_row_count = self.visible_row_count();
+
+            self.scroll_offset = self
+                .scroll_offset
+                .min(self.rows.len().saturating_sub(visible_row_count));
+
+            let first_position = self.scroll_offset;
+            let last_position = (first_position + visible_row_count + 1).min(self.rows.len());
+            let columns = self.columns.clone();
+            let sort_column = self.sort_column;
+            let selected_row = self.selected_row;
+            let highlighted_row = self.highlighted_row;
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+            let header_textures: Vec<Option<Texture>> = columns
+                .iter()
+                .map(|column| render_cell(&font, &texture_creator, &column.title, text_color))
+                .collect();
+            let row_textures: Vec<(usize, Vec<Option<Texture>>)> = self.row_order
+                [first_position..last_position]
+                .iter()
+                .map(|row| {
+                    let cells = (0..columns.len())
+                        .map(|column| {
+                            let text = self.rows[*row].get(column).map_or("", |x| x.as_str());
+
+                            render_cell(&font, &texture_creator, text, text_color)
+                        })
+                        .collect();
+
+                    (*row, cells)
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (display_row, (row, cells)) in row_textures.iter().enumerate() {
+                    let y = ((display_row as u32 + 1) * ROW_HEIGHT) as i32;
+                    let fill_color = if *row as i32 == selected_row {
+                        Some(selected_color)
+                    } else if (first_position + display_row) as i32 == highlighted_row {
+                        Some(hover_color)
+                    } else {
+                        None
+                    };
+
+                    if let Some(fill_color) = fill_color {
+                        texture.set_draw_color(fill_color);
+                        texture
+                            .fill_rect(Rect::new(0, y, bounds[SIZE_WIDTH], ROW_HEIGHT))
+                            .unwrap();
+                    }
+
+                    let mut x = 0;
+
+                    for (column, cell) in columns.iter().zip(cells.iter()) {
+                        if let Some(cell) = cell {
+                            copy_cell(texture, cell, x, y, column.width);
+                        }
+
+                        x += column.width as i32;
+                    }
+                }
+
+                texture.set_draw_color(header_color);
+                texture
+                    .fill_rect(Rect::new(0, 0, bounds[SIZE_WIDTH], ROW_HEIGHT))
+                    .unwrap();
+
+                let mut x = 0;
+
+                for (index, (column, title)) in
+                    columns.iter().zip(header_textures.iter()).enumerate()
+                {
+                    if let Some(title) = title {
+                        copy_cell(texture, title, x, 0, column.width.saturating_sub(12));
+                    }
+
+                    // The sort indicator points up when ascending, and down when descending.
+                    if let Some((sort_index, ascending)) = sort_column {
+                        if sort_index == index {
+                            let center_x = x + column.width as i32 - 10;
+                            let center_y = ROW_HEIGHT as i32 / 2;
+
+                            texture.set_draw_color(text_color);
+
+                            for step in 0..4 {
+                                let y = if ascending {
+                                    center_y + 2 - step
+                                } else {
+                                    center_y - 2 + step
+                                };
+
+                                texture
+                                    .draw_line(
+                                        Point::new(center_x - 4 + step, y),
+                                        Point::new(center_x + 4 - step, y),
+                                    )
+                                    .unwrap();
+                            }
+                        }
+                    }
+
+                    x += column.width as i32;
+
+                    texture.set_draw_color(border_color);
+                    texture
+                        .draw_line(
+                            Point::new(x - 1, 0),
+                            Point::new(x - 1, bounds[SIZE_HEIGHT] as i32),
+                        )
+                        .unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_line(
+                        Point::new(0, ROW_HEIGHT as i32 - 1),
+                        Point::new(bounds[SIZE_WIDTH] as i32, ROW_HEIGHT as i32 - 1),
+                    )
+                    .unwrap();
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the cell textures are only used within this function.
+            let cell_textures = row_textures
+                .into_iter()
+                .flat_map(|(_, cells)| cells)
+                .chain(header_textures);
+
+            for font_texture in cell_textures.flatten() {
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Clears the highlighted row when the mouse leaves the `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.highlighted_row != -1 {
+            self.highlighted_row = -1;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the row under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        let highlighted_row = self.position_at_mouse();
+
+        if highlighted_row != self.highlighted_row {
+            self.highlighted_row = highlighted_row;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the table by one row per step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let max_offset = self.rows.len().saturating_sub(self.visible_row_count());
+        let offset =
+            ((self.scroll_offset as i32 - points[POINT_Y]).max(0) as usize).min(max_offset);
+
+        if offset != self.scroll_offset {
+            self.scroll_offset = offset;
+            self.highlighted_row = self.position_at_mouse();
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Sorts the table when a column header is clicked, reversing the order if the table is already
+    /// sorted by that column, and selects a row when it is clicked.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let x = self.mouse_position[POINT_X] - self.get_config().to_x(0);
+            let y = self.mouse_position[POINT_Y] - self.get_config().to_y(0);
+
+            if y < ROW_HEIGHT as i32 {
+                if let Some(column) = self.column_at(x) {
+                    let ascending = match self.sort_column {
+                        Some((sort_index, ascending)) if sort_index == column => !ascending,
+                        _ => true,
+                    };
+
+                    self.sort_by(column, ascending);
+                }
+            } else {
+                let position = self.position_at_mouse();
+
+                if position >= 0 {
+                    self.select_position(position as usize, _widgets, _layouts);
+                }
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Moves the selection with the `Up`, `Down`, `Home` and `End` keys while this `Widget` has
+    /// focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            if self.rows.is_empty() {
+                return;
+            }
+
+            let selected_row = self.selected_row;
+            let position = self
+                .row_order
+                .iter()
+                .position(|row| *row as i32 == selected_row);
+            let last_position = self.rows.len() - 1;
+            let new_position = match (keycode, position) {
+                (Keycode::Up, Some(position)) => position.saturating_sub(1),
+                (Keycode::Down, Some(position)) => (position + 1).min(last_position),
+                (Keycode::Home, _) | (Keycode::Up, None) | (Keycode::Down, None) => 0,
+                (Keycode::End, _) => last_position,
+                _ => return,
+            };
+
+            if Some(new_position) != position {
+                self.select_position(new_position, _widgets, _layouts);
+            }
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,601 @@
+// Pushrod Widget Library
+// Tag Input Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_rounded_rect;
+use crate::render::clock;
+use crate::render::input_settings::CaretBlink;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between chips, and between the chips and the edges of the
+/// `TagInputWidget`.
+const CHIP_SPACING: i32 = 4;
+
+/// This is the number of pixels between the edge of a chip and its text or close button.
+const CHIP_PADDING: i32 = 6;
+
+/// This is the narrowest the text being typed is allowed to be before it wraps to the next row.
+const MIN_INPUT_WIDTH: i32 = 40;
+
+/// This is the callback type that is used when an `on_tags_changed` callback is triggered from
+/// this `Widget`.  It receives all of the tags, in the order they were added.
+pub type OnTagsChangedCallbackType = Option<
+    Box<dyn FnMut(&mut TagInputWidget, &[WidgetContainer], &[LayoutContainer], Vec<String>)>,
+>;
+
+/// This is the storage object for the `TagInputWidget`.  It stores the config, properties, callback
+/// registry, the font name and size, the tags, the text being typed, the bounds of each chip, and
+/// the close button under the mouse.
+pub struct TagInputWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    tags: Vec<String>,
+    text: String,
+    placeholder: String,
+    max_tags: Option<usize>,
+    chips: Vec<(Rect, Rect)>,
+    hovered_close: Option<usize>,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_tags_changed: OnTagsChangedCallbackType,
+}
+
+/// This is the implementation of the `TagInputWidget`, a field that turns typed text into tags,
+/// drawn as chips before the text being typed.  Pressing `Return` or typing a comma adds the text
+/// as a tag, with surrounding whitespace removed.  Empty text, and text that is already a tag, are
+/// not added.  Each chip has a close button that removes its tag, and pressing `Backspace` with no
+/// text removes the last tag.  Chips wrap onto further rows when they do not fit on one.
+///
+/// When a maximum number of tags is set, no more text can be typed once it is reached.  The
+/// `on_tags_changed` callback is triggered whenever the user adds or removes a tag, but not when
+/// the tags are changed through code.  Chips are drawn in `CONFIG_COLOR_SELECTED`, and their close
+/// buttons in `CONFIG_COLOR_HOVER` while under the mouse.  The placeholder is drawn in
+/// `CONFIG_COLOR_SECONDARY`.
+impl TagInputWidget {
+    /// Creates a new `TagInputWidget`, given the path to the font file, the size of the font in
+    /// pixels, and the `x, y, w, h` coordinates.
+    pub fn new(font_name: String, font_size: i32, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            tags: vec![],
+            text: String::new(),
+            placeholder: String::from("Add a tag"),
+            max_tags: None,
+            chips: vec![],
+            hovered_close: None,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_tags_changed: None,
+        }
+    }
+
+    /// Replaces all of the tags, skipping empty and repeated tags, and any beyond the maximum
+    /// number of tags.  This does not trigger the `on_tags_changed` callback.
+    pub fn set_tags(&mut self, tags: &[String]) {
+        self.tags.clear();
+
+        for tag in tags {
+            self.add_tag(tag);
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the tags, in the order they were added.
+    pub fn get_tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+
+    /// Adds a tag after the others, with surrounding whitespace removed, returning whether it was
+    /// added.  Empty and repeated tags are not added, nor are tags beyond the maximum number of
+    /// tags.  This does not trigger the `on_tags_changed` callback.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+
+        if tag.is_empty() || self.is_full() || self.tags.iter().any(|x| x == tag) {
+            return false;
+        }
+
+        self.tags.push(String::from(tag));
+        self.get_config().set_invalidated(true);
+        true
+    }
+
+    /// Removes the tag at `index`, returning it, or `None` if there is no such tag.  This does not
+    /// trigger the `on_tags_changed` callback.
+    pub fn remove_tag(&mut self, index: usize) -> Option<String> {
+        if index >= self.tags.len() {
+            return None;
+        }
+
+        self.hovered_close = None;
+        self.get_config().set_invalidated(true);
+        Some(self.tags.remove(index))
+    }
+
+    /// Removes all of the tags.  This does not trigger the `on_tags_changed` callback.
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+        self.hovered_close = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Sets the maximum number of tags, or `None` for no limit, which is the default.  Tags beyond
+    /// the maximum are removed.
+    pub fn set_max_tags(&mut self, max_tags: Option<usize>) {
+        self.max_tags = max_tags;
+
+        if let Some(max_tags) = max_tags {
+            self.tags.truncate(max_tags);
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the maximum number of tags, or `None` if there is no limit.
+    pub fn get_max_tags(&self) -> Option<usize> {
+        self.max_tags
+    }
+
+    /// Retrieves the text being typed, which has not been added as a tag yet.
+    pub fn get_text(&self) -> String {
+        self.text.clone()
+    }
+
+    /// Sets the text shown in `CONFIG_COLOR_SECONDARY` while there are no tags and no text being
+    /// typed.  This is `Add a tag` by default.
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = String::from(placeholder);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the text shown while there are no tags and no text being typed.
+    pub fn get_placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Assigns the callback closure that will be used when the user adds or removes a tag.
+    pub fn on_tags_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TagInputWidget, &[WidgetContainer], &[LayoutContainer], Vec<String>)
+            + 'static,
+    {
+        self.on_tags_changed = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_tags_changed` callback.
+    fn call_tags_changed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_tags_changed.take() {
+            cb(self, widgets, layouts, self.tags.clone());
+            self.on_tags_changed = Some(cb);
+        }
+    }
+
+    /// Indicates whether the maximum number of tags has been reached.
+    fn is_full(&self) -> bool {
+        self.max_tags
+            .is_some_and(|max_tags| self.tags.len() >= max_tags)
+    }
+
+    /// Internal function that adds the text being typed as a tag, returning whether it was added.
+    /// The text is kept if it was not added, unless it was only whitespace.
+    fn commit_text(&mut self) -> bool {
+        let text = self.text.clone();
+
+        if self.add_tag(&text) {
+            self.text.clear();
+            true
+        } else {
+            if text.trim().is_empty() {
+                self.text.clear();
+            }
+
+            false
+        }
+    }
+
+    /// Returns the index of the chip whose close button is under the last known mouse position.
+    fn close_under_mouse(&mut self) -> Option<usize> {
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(0);
+        let y = self.mouse_position[POINT_Y] - self.get_config().to_y(0);
+
+        self.chips
+            .iter()
+            .position(|(_, close)| close.contains_point(Point::new(x, y)))
+    }
+}
+
+/// Draws a cross centered within `bounds`.
+fn draw_cross(c: &mut Canvas<Window>, bounds: Rect) {
+    let size = (bounds.width().min(bounds.height()) / 4).max(2) as i32;
+    let center = bounds.center();
+
+    c.draw_line(
+        Point::new(center.x() - size, center.y() - size),
+        Point::new(center.x() + size, center.y() + size),
+    )
+    .unwrap();
+    c.draw_line(
+        Point::new(center.x() - size, center.y() + size),
+        Point::new(center.x() + size, center.y() - size),
+    )
+    .unwrap();
+}
+
+/// This is the `Widget` implementation of the `TagInputWidget`.
+impl Widget for TagInputWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let placeholder_color =
+                self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(128, 128, 128));
+            let chip_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(210, 225, 250));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(170, 190, 230));
+            let width = bounds[SIZE_WIDTH] as i32;
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+            let line_height = font.recommended_line_spacing().max(1);
+            let chip_height = line_height + 4;
+            let close_size = (chip_height - 8).max(6);
+            let max_text_width = (width - CHIP_SPACING * 2 - CHIP_PADDING * 3 - close_size).max(1);
+            let render = |text: &str, color: Color| {
+                let surface = font
+                    .render(text)
+                    .blended(color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+
+                texture_creator
+                    .create_texture_from_surface(&surface)
+                    .map_err(|e| e.to_string())
+                    .unwrap()
+            };
+
+            // Chips flow from left to right, wrapping onto a new row when the next one does not fit.
+            let mut x = CHIP_SPACING;
+            let mut y = CHIP_SPACING;
+            let mut chips = vec![];
+            let mut labels = vec![];
+
+            for tag in &self.tags {
+                let label = render(tag, text_color);
+                let TextureQuery {
+                    width: text_width,
+                    height: text_height,
+                    ..
+                } = label.query();
+                let text_width = (text_width as i32).min(max_text_width);
+                let chip_width = text_width + CHIP_PADDING * 3 + close_size;
+
+                if x > CHIP_SPACING && x + chip_width > width - CHIP_SPACING {
+                    x = CHIP_SPACING;
+                    y += chip_height + CHIP_SPACING;
+                }
+
+                chips.push((
+                    Rect::new(x, y, chip_width as u32, chip_height as u32),
+                    Rect::new(
+                        x + chip_width - CHIP_PADDING - close_size,
+                        y + (chip_height - close_size) / 2,
+                        close_size as u32,
+                        close_size as u32,
+                    ),
+                ));
+                labels.push((
+                    label,
+                    Rect::new(0, 0, text_width as u32, text_height),
+                    Rect::new(
+                        x + CHIP_PADDING,
+                        y + (chip_height - text_height as i32) / 2,
+                        text_width as u32,
+                        text_height,
+                    ),
+                ));
+                x += chip_width + CHIP_SPACING;
+            }
+
+            let (shown, shown_color) = if self.text.is_empty() && self.tags.is_empty() {
+                (self.placeholder.clone(), placeholder_color)
+            } else {
+                (self.text.clone(), text_color)
+            };
+            let text_width = font.size_of(&self.text).map(|(w, _)| w).unwrap_or(0) as i32;
+
+            if x > CHIP_SPACING && x + text_width.max(MIN_INPUT_WIDTH) > width - CHIP_SPACING {
+                x = CHIP_SPACING;
+                y += chip_height + CHIP_SPACING;
+            }
+
+            let text_x = x + 2;
+            let text_y = y + (chip_height - line_height) / 2;
+            let input_texture = if shown.is_empty() {
+                None
+            } else {
+                Some(render(&shown, shown_color))
+            };
+            let caret = if self.focused && self.caret.is_shown() && !self.is_full() {
+                Some(Rect::new(
+                    text_x + text_width,
+                    text_y,
+                    1,
+                    line_height as u32,
+                ))
+            } else {
+                None
+            };
+            let hovered_close = self.hovered_close;
+
+            self.chips = chips.clone();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (index, ((chip, close), (label, source, destination))) in
+                    chips.iter().zip(labels.iter()).enumerate()
+                {
+                    texture.set_draw_color(chip_color);
+                    fill_rounded_rect(texture, *chip, chip.height() / 2);
+
+                    if hovered_close == Some(index) {
+                        texture.set_draw_color(hover_color);
+                        fill_rounded_rect(texture, *close, close.width() / 2);
+                    }
+
+                    texture.copy(label, *source, *destination).unwrap();
+                    texture.set_draw_color(text_color);
+                    draw_cross(texture, *close);
+                }
+
+                if let Some(input_texture) = &input_texture {
+                    let TextureQuery { width, height, .. } = input_texture.query();
+                    let visible_width =
+                        width.min((bounds[SIZE_WIDTH] as i32 - text_x).max(1) as u32);
+
+                    texture
+                        .copy(
+                            input_texture,
+                            Rect::new(0, 0, visible_width, height),
+                            Rect::new(text_x, text_y, visible_width, height),
+                        )
+                        .unwrap();
+                }
+
+                if let Some(caret) = caret {
+                    texture.set_draw_color(text_color);
+                    texture.fill_rect(caret).unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the label and input textures are only used within this function.
+            for (label, _, _) in labels {
+                unsafe {
+                    label.destroy();
+                }
+            }
+
+            if let Some(input_texture) = input_texture {
+                unsafe {
+                    input_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while this `Widget` has focus.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the close button under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        let hovered_close = self.close_under_mouse();
+
+        if hovered_close != self.hovered_close {
+            self.hovered_close = hovered_close;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Stops highlighting close buttons when the mouse leaves this `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.hovered_close.is_some() {
+            self.hovered_close = None;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Removes a tag when its close button is clicked.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            if let Some(index) = self.close_under_mouse() {
+                self.remove_tag(index);
+                self.call_tags_changed_callback(_widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles text input while this `Widget` has focus, adding a tag on `Return` or a comma.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let mut tags_changed = false;
+
+        match event {
+            Event::TextInput { text, .. } => {
+                for c in text.chars() {
+                    if c == ',' {
+                        tags_changed |= self.commit_text();
+                    } else if !self.is_full() {
+                        self.text.push(c);
+                    }
+                }
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Return),
+                ..
+            }
+            | Event::KeyDown {
+                keycode: Some(Keycode::KpEnter),
+                ..
+            } => tags_changed = self.commit_text(),
+            Event::KeyDown {
+                keycode: Some(Keycode::Backspace),
+                ..
+            } => {
+                if self.text.pop().is_none() && !self.tags.is_empty() {
+                    self.remove_tag(self.tags.len() - 1);
+                    tags_changed = true;
+                }
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::Escape),
+                ..
+            } => self.text.clear(),
+            _ => return,
+        };
+
+        self.caret.restart(clock::now_millis());
+        self.get_config().set_invalidated(true);
+
+        if tags_changed {
+            self.call_tags_changed_callback(_widgets, _layouts);
+        }
+    }
+
+    /// Monitors for changes in the colors, or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _)
+            | (CONFIG_COLOR_BASE, _)
+            | (CONFIG_COLOR_BORDER, _)
+            | (CONFIG_COLOR_SECONDARY, _)
+            | (CONFIG_COLOR_SELECTED, _)
+            | (CONFIG_COLOR_HOVER, _) => self.get_config().set_invalidated(true),
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus.
+    fn accepts_text_input(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,412 @@
+// Pushrod Widget Library
+// Terminal Log Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+
+/// This is the number of pixels between the edges of the `TerminalLogWidget` and its text.
+const LOG_PADDING: i32 = 4;
+
+/// This is the width of the scroll indicator drawn when the lines do not fit vertically.
+const SCROLL_INDICATOR_WIDTH: u32 = 4;
+
+/// This is a line of text in a `TerminalLogWidget`.
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    /// The number of milliseconds after the `TerminalLogWidget` was created that the line was
+    /// appended.
+    pub timestamp: u64,
+
+    /// The text of the line.
+    pub text: String,
+
+    /// The color of the line, or `None` to draw it in `CONFIG_COLOR_TEXT`.
+    pub color: Option<Color>,
+}
+
+/// This is the storage object for the `TerminalLogWidget`.  It stores the config, properties,
+/// callback registry, the font, the lines in the history, and the scroll position.
+pub struct TerminalLogWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+    created_at: u64,
+    show_timestamps: bool,
+    scroll_offset: usize,
+    following: bool,
+    line_height: u32,
+}
+
+/// This is the implementation of the `TerminalLogWidget`, a scrolling console that shows lines of
+/// text as they are appended, each prefixed by the time it was appended.  The history is capped
+/// at a number of lines, beyond which the oldest lines are dropped.  The view follows the newest
+/// line, unless the user has scrolled up with the mouse wheel, in which case it stays put until
+/// the user scrolls back to the bottom, or `scroll_to_bottom` is called.  Timestamps are drawn in
+/// `CONFIG_COLOR_SECONDARY`, and lines without a color of their own in `CONFIG_COLOR_TEXT`.
+impl TerminalLogWidget {
+    /// Creates a new `TerminalLogWidget`, given the path to the font file, the size of the font in
+    /// pixels, the `x, y, w, h` coordinates, and the number of lines kept in the history.
+    pub fn new(
+        font_name: String,
+        font_size: i32,
+        points: Points,
+        size: Size,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            lines: VecDeque::new(),
+            capacity: capacity.max(1),
+            created_at: clock::now_millis(),
+            show_timestamps: true,
+            scroll_offset: 0,
+            following: true,
+            line_height: font_size.max(1) as u32 + 4,
+        }
+    }
+
+    /// Appends text drawn in `CONFIG_COLOR_TEXT`.  Text containing newlines is appended as several
+    /// lines.
+    pub fn append_line(&mut self, text: &str) {
+        self.append(text, None);
+    }
+
+    /// Appends text drawn in `color`.  Text containing newlines is appended as several lines.
+    pub fn append_colored_line(&mut self, text: &str, color: Color) {
+        self.append(text, Some(color));
+    }
+
+    /// Removes all of the lines.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.scroll_offset = 0;
+        self.following = true;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the lines in the history, from oldest to newest.
+    pub fn get_lines(&self) -> Vec<LogLine> {
+        self.lines.iter().cloned().collect()
+    }
+
+    /// Retrieves the number of lines in the history.
+    pub fn get_line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Sets the number of lines kept in the history, dropping the oldest lines if there are more.
+    /// The capacity is at least `1`.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.trim();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the number of lines kept in the history.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Shows or hides the timestamp before each line.
+    pub fn set_show_timestamps(&mut self, show_timestamps: bool) {
+        self.show_timestamps = show_timestamps;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the timestamp before each line is shown.
+    pub fn get_show_timestamps(&self) -> bool {
+        self.show_timestamps
+    }
+
+    /// Scrolls to the newest line, and follows new lines as they are appended.
+    pub fn scroll_to_bottom(&mut self) {
+        self.following = true;
+        self.scroll_offset = self.max_scroll_offset();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether the view follows new lines as they are appended.  This is `false` while the
+    /// user has scrolled up.
+    pub fn is_following(&self) -> bool {
+        self.following
+    }
+
+    /// Retrieves the index of the first line shown.
+    pub fn get_scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Internal function that appends each line of `text` in `color`.
+    fn append(&mut self, text: &str, color: Option<Color>) {
+        let timestamp = clock::now_millis().saturating_sub(self.created_at);
+
+        for line in text.lines() {
+            self.lines.push_back(LogLine {
+                timestamp,
+                text: String::from(line),
+                color,
+            });
+        }
+
+        self.trim();
+
+        if self.following {
+            self.scroll_offset = self.max_scroll_offset();
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Drops the oldest lines beyond the capacity, keeping the lines in view still if the user has
+    /// scrolled up.
+    fn trim(&mut self) {
+        let excess = self.lines.len().saturating_sub(self.capacity);
+
+        self.lines.drain(..excess);
+        self.scroll_offset = self
+            .scroll_offset
+            .saturating_sub(excess)
+            .min(self.max_scroll_offset());
+    }
+
+    /// Returns the number of lines that fit within the bounds of this `Widget`.
+    fn visible_lines(&mut self) -> usize {
+        let height = self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] as i32 - LOG_PADDING * 2;
+
+        (height.max(0) as u32 / self.line_height).max(1) as usize
+    }
+
+    /// Returns the index of the first line shown when scrolled to the bottom.
+    fn max_scroll_offset(&mut self) -> usize {
+        self.lines.len().saturating_sub(self.visible_lines())
+    }
+}
+
+/// Formats a timestamp in milliseconds as seconds, in the style of a kernel log.
+fn format_timestamp(timestamp: u64) -> String {
+    format!("[{:>6}.{:03}] ", timestamp / 1000, timestamp % 1000)
+}
+
+/// This is the `Widget` implementation of the `TerminalLogWidget`.
+impl Widget for TerminalLogWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let timestamp_color = self.get_color(CONFIG_COLOR_SECONDARY);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+
+            self.line_height = font.recommended_line_spacing().max(1) as u32;
+
+            if self.following {
+                self.scroll_offset = self.max_scroll_offset();
+            }
+
+            self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+
+            let visible_lines = self.visible_lines();
+            let first_line = self.scroll_offset;
+            let last_line = (first_line + visible_lines).min(self.lines.len());
+            let timestamp_width = if self.show_timestamps {
+                font.size_of(&format_timestamp(0))
+                    .map(|(w, _)| w)
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+            let scroll_indicator = if self.lines.len() > visible_lines {
+                let track = bounds[SIZE_HEIGHT] as f64;
+                let total = self.lines.len() as f64;
+
+                Some(Rect::new(
+                    (bounds[SIZE_WIDTH] - SCROLL_INDICATOR_WIDTH) as i32,
+                    (first_line as f64 / total * track) as i32,
+                    SCROLL_INDICATOR_WIDTH,
+                    ((visible_lines as f64 / total * track) as u32).max(1),
+                ))
+            } else {
+                None
+            };
+            let render = |text: &str, color: Color| -> Option<Texture> {
+                if text.is_empty() {
+                    return None;
+                }
+
+                let surface = font
+                    .render(text)
+                    .blended(color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+
+                Some(
+                    texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap(),
+                )
+            };
+            let show_timestamps = self.show_timestamps;
+            let line_textures: Vec<(Option<Texture>, Option<Texture>)> = self
+                .lines
+                .range(first_line..last_line)
+                .map(|line| {
+                    let timestamp = if show_timestamps {
+                        render(&format_timestamp(line.timestamp), timestamp_color)
+                    } else {
+                        None
+                    };
+
+                    (
+                        timestamp,
+                        render(&line.text, line.color.unwrap_or(text_color)),
+                    )
+                })
+                .collect();
+            let line_height = self.line_height as i32;
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (row, (timestamp, text)) in line_textures.iter().enumerate() {
+                    let y = LOG_PADDING + row as i32 * line_height;
+
+                    if let Some(timestamp) = timestamp {
+                        let TextureQuery { width, height, .. } = timestamp.query();
+
+                        texture
+                            .copy(timestamp, None, Rect::new(LOG_PADDING, y, width, height))
+                            .unwrap();
+                    }
+
+                    if let Some(text) = text {
+                        let TextureQuery { width, height, .. } = text.query();
+
+                        texture
+                            .copy(
+                                text,
+                                None,
+                                Rect::new(LOG_PADDING + timestamp_width as i32, y, width, height),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                if let Some(indicator) = scroll_indicator {
+                    texture.set_draw_color(border_color);
+                    texture.fill_rect(indicator).unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the line textures are only used within this function.
+            for (timestamp, text) in line_textures {
+                for line_texture in timestamp.into_iter().chain(text) {
+                    unsafe {
+                        line_texture.destroy();
+                    }
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Scrolls the history by a line for each step of the mouse wheel.  Scrolling up stops the view
+    /// from following new lines, and scrolling back to the bottom follows them again.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let max_offset = self.max_scroll_offset();
+        let offset = (self.scroll_offset as i32 - points[POINT_Y]).max(0) as usize;
+
+        self.scroll_offset = offset.min(max_offset);
+        self.following = self.scroll_offset >= max_offset;
+        self.get_config().set_invalidated(true);
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Monitors for changes in the colors or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _)
+            | (CONFIG_COLOR_BASE, _)
+            | (CONFIG_COLOR_BORDER, _)
+            | (CONFIG_COLOR_SECONDARY, _) => self.get_config().set_invalidated(true),
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
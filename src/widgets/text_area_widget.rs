@@ -0,0 +1,684 @@
+// Pushrod Widget Library
+// Text Area Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock;
+use crate::render::input_settings::CaretBlink;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::ttf::Font;
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use sdl2::rect::Rect;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of pixels between the border of the `TextAreaWidget` and its text.
+const TEXT_PADDING: u32 = 4;
+
+/// This is the width of the scroll indicator drawn when the text does not fit vertically.
+const SCROLL_INDICATOR_WIDTH: u32 = 4;
+
+/// This is the callback type that is used when an `on_text_changed` callback is triggered from this
+/// `Widget`.  It receives the new text.
+pub type OnTextChangedCallbackType =
+    Option<Box<dyn FnMut(&mut TextAreaWidget, &[WidgetContainer], &[LayoutContainer], &str)>>;
+
+/// This is the callback type that is used when an `on_cursor_moved` callback is triggered from this
+/// `Widget`.  It receives the line and column of the cursor, both starting at `0`.
+pub type OnCursorMovedCallbackType = Option<
+    Box<dyn FnMut(&mut TextAreaWidget, &[WidgetContainer], &[LayoutContainer], usize, usize)>,
+>;
+
+/// This is the storage object for the `TextAreaWidget`.  It stores the config, properties, callback
+/// registry, the font name and size, the text being edited, and the cursor and scroll positions.
+pub struct TextAreaWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    font_name: String,
+    font_size: i32,
+    text: Vec<char>,
+    cursor: usize,
+    scroll_line: usize,
+    visible_lines: usize,
+    wrapped_lines: Vec<(usize, usize)>,
+    line_offsets: Vec<Vec<u32>>,
+    line_height: i32,
+    reveal_cursor: bool,
+    focused: bool,
+    caret: CaretBlink,
+    mouse_position: Points,
+    on_text_changed: OnTextChangedCallbackType,
+    on_cursor_moved: OnCursorMovedCallbackType,
+}
+
+/// This is the implementation of the `TextAreaWidget`, a multi-line text editor that wraps text at
+/// word boundaries within its bounds, and scrolls vertically.
+impl TextAreaWidget {
+    /// Creates a new `TextAreaWidget`, given the path to the font file, the size of the font in
+    /// pixels, and the `x, y, w, h` coordinates.
+    pub fn new(font_name: String, font_size: i32, points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            font_name,
+            font_size,
+            text: vec![],
+            cursor: 0,
+            scroll_line: 0,
+            visible_lines: 1,
+            wrapped_lines: vec![],
+            line_offsets: vec![],
+            line_height: font_size,
+            reveal_cursor: false,
+            focused: false,
+            caret: CaretBlink::new(),
+            mouse_position: vec![0, 0],
+            on_text_changed: None,
+            on_cursor_moved: None,
+        }
+    }
+
+    /// Replaces the text being edited, moving the cursor to the end of the text.  This does not
+    /// trigger the `on_text_changed` callback.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().collect();
+        self.cursor = self.text.len();
+        self.reveal_cursor = true;
+        self.reset_wrapped_lines();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the text being edited.
+    pub fn get_text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Retrieves the position of the cursor, as a character index into the text.
+    pub fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor to a character index into the text, scrolling it into view.  This does not
+    /// trigger the `on_cursor_moved` callback.
+    pub fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.text.len());
+        self.reveal_cursor = true;
+        self.caret.restart(clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the line and column of the cursor, both starting at `0`.  Lines are separated by
+    /// newlines in the text, and are not affected by word wrapping.
+    pub fn get_cursor_line_column(&self) -> (usize, usize) {
+        let before = &self.text[..self.cursor];
+        let line = before.iter().filter(|c| **c == '\n').count();
+        let column = before.iter().rev().take_while(|c| **c != '\n').count();
+
+        (line, column)
+    }
+
+    /// Retrieves the number of lines in the text, as separated by newlines.
+    pub fn get_line_count(&self) -> usize {
+        self.text.iter().filter(|c| **c == '\n').count() + 1
+    }
+
+    /// Assigns the callback closure that will be used when the text is changed by the user.
+    pub fn on_text_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TextAreaWidget, &[WidgetContainer], &[LayoutContainer], &str) + 'static,
+    {
+        self.on_text_changed = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when the cursor is moved by the user, either
+    /// by navigating, clicking, or editing the text.
+    pub fn on_cursor_moved<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TextAreaWidget, &[WidgetContainer], &[LayoutContainer], usize, usize)
+            + 'static,
+    {
+        self.on_cursor_moved = Some(Box::new(callback));
+    }
+
+    /// Internal function that triggers the `on_text_changed` callback.
+    fn call_text_changed_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_text_changed.take() {
+            let text: String = self.text.iter().collect();
+
+            cb(self, widgets, layouts, &text);
+            self.on_text_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that triggers the `on_cursor_moved` callback.
+    fn call_cursor_moved_callback(
+        &mut self,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        if let Some(mut cb) = self.on_cursor_moved.take() {
+            let (line, column) = self.get_cursor_line_column();
+
+            cb(self, widgets, layouts, line, column);
+            self.on_cursor_moved = Some(cb);
+        }
+    }
+
+    /// Returns the index of the wrapped line containing the cursor.
+    fn cursor_line(&self) -> usize {
+        self.wrapped_lines
+            .iter()
+            .rposition(|(start, _)| *start <= self.cursor)
+            .unwrap_or(0)
+    }
+
+    /// Moves the cursor to the same column of the wrapped line `delta` lines away from the current
+    /// one, clamping to the first and last lines.
+    fn move_lines(&mut self, delta: isize) {
+        if self.wrapped_lines.is_empty() {
+            return;
+        }
+
+        let current = self.cursor_line();
+        let column = self.cursor - self.wrapped_lines[current].0;
+        let target = (current as isize + delta).clamp(0, self.wrapped_lines.len() as isize - 1);
+        let (start, end) = self.wrapped_lines[target as usize];
+
+        self.cursor = (start + column).min(end).min(self.text.len());
+    }
+
+    /// Replaces the wrapped lines with the lines of the text as separated by newlines, after the
+    /// text has changed.  The text is wrapped again the next time this `Widget` is drawn, so until
+    /// then, navigation uses line positions that match the text.
+    fn reset_wrapped_lines(&mut self) {
+        let mut start = 0;
+
+        self.wrapped_lines.clear();
+
+        for (end, c) in self.text.iter().chain(['\n'].iter()).enumerate() {
+            if *c == '\n' {
+                self.wrapped_lines.push((start, end));
+                start = end + 1;
+            }
+        }
+    }
+
+    /// Inserts text at the cursor, moving the cursor past it.
+    fn insert(&mut self, text: &str) {
+        for c in text.chars() {
+            self.text.insert(self.cursor, c);
+            self.cursor += 1;
+        }
+
+        self.reset_wrapped_lines();
+    }
+
+    /// Handles a key press, returning whether the text and the cursor changed.
+    fn handle_edit_key(&mut self, keycode: Keycode) -> (bool, bool) {
+        let cursor = self.cursor;
+        let mut text_changed = false;
+
+        match keycode {
+            Keycode::Left => self.cursor = self.cursor.saturating_sub(1),
+            Keycode::Right => self.cursor = (self.cursor + 1).min(self.text.len()),
+            Keycode::Up => self.move_lines(-1),
+            Keycode::Down => self.move_lines(1),
+            Keycode::PageUp => self.move_lines(-(self.visible_lines as isize)),
+            Keycode::PageDown => self.move_lines(self.visible_lines as isize),
+            Keycode::Home => {
+                if let Some((start, _)) = self.wrapped_lines.get(self.cursor_line()) {
+                    self.cursor = (*start).min(self.text.len());
+                }
+            }
+            Keycode::End => {
+                if let Some((_, end)) = self.wrapped_lines.get(self.cursor_line()) {
+                    self.cursor = (*end).min(self.text.len());
+                }
+            }
+            Keycode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.text.remove(self.cursor);
+                text_changed = true;
+            }
+            Keycode::Delete if self.cursor < self.text.len() => {
+                self.text.remove(self.cursor);
+                text_changed = true;
+            }
+            Keycode::Return | Keycode::KpEnter => {
+                self.insert("\n");
+                text_changed = true;
+            }
+            _ => (),
+        }
+
+        if text_changed {
+            self.reset_wrapped_lines();
+        }
+
+        (text_changed, text_changed || cursor != self.cursor)
+    }
+
+    /// Moves the cursor to the character closest to the last known mouse position.
+    fn move_cursor_to_mouse(&mut self) {
+        if self.wrapped_lines.is_empty() {
+            return;
+        }
+
+        let x = self.mouse_position[POINT_X] - self.get_config().to_x(TEXT_PADDING as i32);
+        let y = self.mouse_position[POINT_Y] - self.get_config().to_y(TEXT_PADDING as i32);
+        let line = (self.scroll_line + (y.max(0) / self.line_height.max(1)) as usize)
+            .min(self.wrapped_lines.len() - 1);
+        let (start, end) = self.wrapped_lines[line];
+        let offsets = line
+            .checked_sub(self.scroll_line)
+            .and_then(|row| self.line_offsets.get(row));
+        let column = match offsets {
+            Some(offsets) => offsets
+                .windows(2)
+                .position(|pair| x < ((pair[0] + pair[1]) / 2) as i32)
+                .unwrap_or(end - start),
+            None => 0,
+        };
+
+        self.cursor = (start + column).min(end);
+    }
+
+    /// Clamps the scroll position to the text, and scrolls the cursor into view if it has moved.
+    fn update_scroll(&mut self) {
+        let max_scroll = self.wrapped_lines.len().saturating_sub(self.visible_lines);
+
+        if self.reveal_cursor {
+            let line = self.cursor_line();
+
+            if line < self.scroll_line {
+                self.scroll_line = line;
+            } else if line >= self.scroll_line + self.visible_lines {
+                self.scroll_line = line + 1 - self.visible_lines;
+            }
+
+            self.reveal_cursor = false;
+        }
+
+        self.scroll_line = self.scroll_line.min(max_scroll);
+    }
+}
+
+/// Returns the width of a single character when rendered with `font`.
+fn char_width(font: &Font, c: char) -> u32 {
+    font.size_of_char(c).map(|(w, _)| w).unwrap_or(0)
+}
+
+/// Wraps text to fit within `max_width` pixels when rendered with `font`, breaking lines at
+/// newlines, and at the last space that fits.  Words that are wider than `max_width` are broken
+/// between characters.  Each character is measured once, and line widths are the sum of their
+/// character widths.  Returns the start and end character index of each wrapped line; newlines and
+/// the spaces at which lines are wrapped are not included.
+pub fn wrap_text(font: &Font, text: &[char], max_width: u32) -> Vec<(usize, usize)> {
+    let widths: Vec<u32> = text.iter().map(|c| char_width(font, *c)).collect();
+    let mut lines = vec![];
+    let mut line_start = 0;
+
+    for (end, c) in text.iter().chain(['\n'].iter()).enumerate() {
+        if *c != '\n' {
+            continue;
+        }
+
+        let mut start = line_start;
+        let mut last_space = None;
+        let mut pos = start;
+        let mut width = 0;
+
+        while pos < end {
+            width += widths[pos];
+
+            if width > max_width && pos > start {
+                let break_at = last_space.filter(|space| *space > start).unwrap_or(pos);
+
+                lines.push((start, break_at));
+                start = if Some(break_at) == last_space {
+                    break_at + 1
+                } else {
+                    break_at
+                };
+                pos = start;
+                last_space = None;
+                width = 0;
+                continue;
+            }
+
+            if text[pos] == ' ' {
+                last_space = Some(pos);
+            }
+
+            pos += 1;
+        }
+
+        lines.push((start, end));
+        line_start = end + 1;
+    }
+
+    lines
+}
+
+/// This is the `Widget` implementation of the `TextAreaWidget`.  Text is wrapped and rendered onto
+/// its `TextureStore` one line at a time, and only the lines in view are drawn.
+impl Widget for TextAreaWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let text_width = bounds[SIZE_WIDTH]
+                .saturating_sub(TEXT_PADDING * 2 + SCROLL_INDICATOR_WIDTH)
+                .max(1);
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&self.font_name), self.font_size as u16)
+                .unwrap();
+
+            self.line_height = font.recommended_line_spacing().max(1);
+            self.visible_lines = ((bounds[SIZE_HEIGHT].saturating_sub(TEXT_PADDING * 2)) as i32
+                / self.line_height)
+                .max(1) as usize;
+            self.wrapped_lines = wrap_text(&font, &self.text, text_width);
+            self.update_scroll();
+
+            let first_line = self.scroll_line;
+            let last_line = (first_line + self.visible_lines).min(self.wrapped_lines.len());
+            let line_height = self.line_height;
+            let cursor_line = self.cursor_line();
+            let mut line_textures = vec![];
+
+            self.line_offsets.clear();
+
+            for line in first_line..last_line {
+                let (start, end) = self.wrapped_lines[line];
+                let mut offsets = vec![0];
+
+                for c in &self.text[start..end] {
+                    offsets.push(offsets[offsets.len() - 1] + char_width(&font, *c));
+                }
+
+                let line_text: String = self.text[start..end].iter().collect();
+
+                if !line_text.is_empty() {
+                    let surface = font
+                        .render(&line_text)
+                        .blended(text_color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let font_texture = texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+
+                    line_textures.push((line - first_line, font_texture));
+                }
+
+                self.line_offsets.push(offsets);
+            }
+
+            let caret = if self.focused && self.caret.is_shown() && cursor_line >= first_line {
+                self.line_offsets
+                    .get(cursor_line - first_line)
+                    .map(|offsets| {
+                        let column = self.cursor - self.wrapped_lines[cursor_line].0;
+
+                        (
+                            offsets[column.min(offsets.len() - 1)] as i32,
+                            (cursor_line - first_line) as i32 * line_height,
+                        )
+                    })
+            } else {
+                None
+            };
+            let scroll_indicator = if self.wrapped_lines.len() > self.visible_lines {
+                let track = bounds[SIZE_HEIGHT] as f64;
+                let total = self.wrapped_lines.len() as f64;
+                let top = (first_line as f64 / total * track) as i32;
+                let height = ((self.visible_lines as f64 / total * track) as u32).max(1);
+
+                Some(Rect::new(
+                    bounds[SIZE_WIDTH].saturating_sub(SCROLL_INDICATOR_WIDTH) as i32,
+                    top,
+                    SCROLL_INDICATOR_WIDTH,
+                    height,
+                ))
+            } else {
+                None
+            };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (row, font_texture) in &line_textures {
+                    let TextureQuery { width, height, .. } = font_texture.query();
+
+                    texture
+                        .copy(
+                            font_texture,
+                            None,
+                            Rect::new(
+                                TEXT_PADDING as i32,
+                                TEXT_PADDING as i32 + *row as i32 * line_height,
+                                width,
+                                height,
+                            ),
+                        )
+                        .unwrap();
+                }
+
+                texture.set_draw_color(text_color);
+
+                if let Some((x, y)) = caret {
+                    texture
+                        .fill_rect(Rect::new(
+                            TEXT_PADDING as i32 + x,
+                            TEXT_PADDING as i32 + y,
+                            1,
+                            line_height as u32,
+                        ))
+                        .unwrap();
+                }
+
+                if let Some(indicator) = scroll_indicator {
+                    texture.set_draw_color(border_color);
+                    texture.fill_rect(indicator).unwrap();
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the line textures are only used within this function.
+            for (_, font_texture) in line_textures {
+                unsafe {
+                    font_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Blinks the caret while this `Widget` has focus.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let caret_blink_rate = self.get_numeric(CONFIG_CARET_BLINK_RATE);
+
+        if self.focused && self.caret.tick(clock::now_millis(), caret_blink_rate) {
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Tracks the mouse position, so that clicks can move the cursor.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the text vertically by one line per step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let scroll_line = (self.scroll_line as i32 - points[POINT_Y]).max(0) as usize;
+
+        if scroll_line != self.scroll_line {
+            self.scroll_line = scroll_line;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Moves the cursor to the clicked position.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let cursor = self.cursor;
+
+            self.move_cursor_to_mouse();
+
+            if cursor != self.cursor {
+                self.caret.restart(clock::now_millis());
+                self.get_config().set_invalidated(true);
+                self.call_cursor_moved_callback(_widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Handles text input and cursor navigation while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        let (text_changed, cursor_moved) = match event {
+            Event::TextInput { text, .. } => {
+                self.insert(&text);
+                (true, true)
+            }
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } => self.handle_edit_key(keycode),
+            _ => (false, false),
+        };
+
+        if cursor_moved {
+            self.reveal_cursor = true;
+            self.caret.restart(clock::now_millis());
+            self.get_config().set_invalidated(true);
+        }
+
+        if text_changed {
+            self.call_text_changed_callback(_widgets, _layouts);
+        }
+
+        if cursor_moved {
+            self.call_cursor_moved_callback(_widgets, _layouts);
+        }
+    }
+
+    /// Monitors for changes in the text, colors, or font size.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_COLOR_TEXT, _) | (CONFIG_COLOR_BASE, _) | (CONFIG_COLOR_BORDER, _) => {
+                self.get_config().set_invalidated(true)
+            }
+            (CONFIG_FONT_SIZE, Config::Numeric(size)) => {
+                self.font_size = size;
+                self.get_config().set_invalidated(true);
+            }
+            (CONFIG_TEXT, Config::Text(text)) => self.set_text(&text),
+            _ => (),
+        };
+    }
+
+    /// Shows the caret while this `Widget` has focus.
+    fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        self.caret.focus_changed(focused, clock::now_millis());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Accepts text input while this `Widget` has focus.
+    fn accepts_text_input(&mut self) -> bool {
+        true
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
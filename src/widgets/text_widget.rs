@@ -20,8 +20,9 @@ use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
 use crate::render::{Points, Size};
 
+use sdl2::pixels::Color;
 use sdl2::render::{Canvas, Texture, TextureQuery};
-use sdl2::ttf::FontStyle;
+use sdl2::ttf::{Font, FontStyle};
 use sdl2::video::Window;
 
 use crate::render::texture_cache::TextureCache;
@@ -33,6 +34,7 @@ use std::path::Path;
 
 /// This enum is used by the `TextWidget`, which controls the justification of the text being
 /// rendered within the bounds of the `Widget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TextJustify {
     /// Left-justified text.
     Left,
@@ -44,8 +46,183 @@ pub enum TextJustify {
     Right,
 }
 
+/// These are the ways a `TextWidget` lays out text that is wider than the `Widget`, set by
+/// `CONFIG_TEXT_WRAP`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextWrap {
+    /// Text is wrapped at spaces onto as many lines as needed.  This is the default.
+    Wrap = 0,
+
+    /// Text is only broken at newlines, and is clipped at the edges of the `Widget`.
+    None = 1,
+
+    /// Text is only broken at newlines, and lines that are too wide are cut short, ending with an
+    /// ellipsis.
+    Ellipsis = 2,
+}
+
+/// This is the implementation of the `TextWrap`.
+impl TextWrap {
+    /// Converts a numeric value stored in `CONFIG_TEXT_WRAP` to a `TextWrap`.  Unknown values are
+    /// treated as `TextWrap::Wrap`.
+    pub fn from_numeric(value: i32) -> Self {
+        match value {
+            1 => TextWrap::None,
+            2 => TextWrap::Ellipsis,
+            _ => TextWrap::Wrap,
+        }
+    }
+
+    /// Converts the `TextWrap` to the numeric value stored in `CONFIG_TEXT_WRAP`.
+    pub fn to_numeric(self) -> i32 {
+        self as i32
+    }
+}
+
+/// This is a run of text within a `TextWidget` that is drawn in its own color and style, so that a
+/// single paragraph can mix, for example, bold, underlined and colored words.
+#[derive(Clone, Debug)]
+pub struct TextSpan {
+    /// This is the text of the span.  A `\n` starts a new line.
+    pub text: String,
+
+    /// This is the color of the text, or `None` to use the `CONFIG_COLOR_TEXT` of the `TextWidget`.
+    pub color: Option<Color>,
+
+    /// This is the style of the text, such as `FontStyle::BOLD | FontStyle::UNDERLINE`.
+    pub style: FontStyle,
+}
+
+/// This is the implementation of the `TextSpan`.
+impl TextSpan {
+    /// Creates a new `TextSpan` with the `text` to draw, its `color`, and its `style`.
+    pub fn new(text: &str, color: Option<Color>, style: FontStyle) -> Self {
+        Self {
+            text: String::from(text),
+            color,
+            style,
+        }
+    }
+}
+
+/// This is the text that ends lines cut short by `TextWrap::Ellipsis`.
+const ELLIPSIS: &str = "\u{2026}";
+
+/// This is a piece of a `TextSpan` laid out on a line: its horizontal offset, width, text, and the
+/// index of the span it came from.
+struct SpanPiece {
+    x: i32,
+    width: u32,
+    text: String,
+    span: usize,
+}
+
+/// Lays out `spans` as lines of pieces no wider than `max_width`, wrapping at spaces.  Each line is
+/// returned with its total width, so that it can be justified.
+fn layout_spans(font: &mut Font, spans: &[TextSpan], max_width: u32) -> Vec<(u32, Vec<SpanPiece>)> {
+    let mut lines: Vec<(u32, Vec<SpanPiece>)> = vec![(0, vec![])];
+
+    for (index, span) in spans.iter().enumerate() {
+        font.set_style(span.style);
+
+        for (line_number, line) in span.text.split('\n').enumerate() {
+            if line_number > 0 {
+                lines.push((0, vec![]));
+            }
+
+            // Spaces are kept with the word that follows them, so that they are dropped when that
+            // word starts a new line.
+            for (word_number, word) in line.split(' ').enumerate() {
+                let mut word = if word_number == 0 {
+                    String::from(word)
+                } else {
+                    format!(" {}", word)
+                };
+                let mut width = font.size_of(&word).map(|(w, _)| w).unwrap_or(0);
+
+                if lines.last().unwrap().0 + width > max_width && lines.last().unwrap().0 > 0 {
+                    lines.push((0, vec![]));
+                    word = String::from(word.trim_start());
+                    width = font.size_of(&word).map(|(w, _)| w).unwrap_or(0);
+                }
+
+                if word.is_empty() {
+                    continue;
+                }
+
+                let (line_width, pieces) = lines.last_mut().unwrap();
+
+                match pieces.last_mut() {
+                    Some(piece) if piece.span == index => {
+                        piece.text.push_str(&word);
+                        piece.width += width;
+                    }
+                    _ => pieces.push(SpanPiece {
+                        x: *line_width as i32,
+                        width,
+                        text: word,
+                        span: index,
+                    }),
+                }
+
+                *line_width += width;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Cuts a laid out `line` short so that it fits within `max_width`, ending it with an ellipsis in
+/// the style of the piece that was cut.  Lines that already fit are returned unchanged.
+fn ellipsize(
+    font: &mut Font,
+    spans: &[TextSpan],
+    (line_width, line): (u32, Vec<SpanPiece>),
+    max_width: u32,
+) -> (u32, Vec<SpanPiece>) {
+    if line_width <= max_width {
+        return (line_width, line);
+    }
+
+    let mut pieces: Vec<SpanPiece> = vec![];
+
+    for mut piece in line {
+        font.set_style(spans[piece.span].style);
+
+        let ellipsis_width = font.size_of(ELLIPSIS).map(|(w, _)| w).unwrap_or(0);
+
+        if piece.x as u32 + piece.width + ellipsis_width <= max_width {
+            pieces.push(piece);
+            continue;
+        }
+
+        while !piece.text.is_empty() {
+            let width = font.size_of(&piece.text).map(|(w, _)| w).unwrap_or(0);
+
+            if piece.x as u32 + width + ellipsis_width <= max_width {
+                break;
+            }
+
+            piece.text.pop();
+        }
+
+        piece.text = format!("{}{}", piece.text.trim_end(), ELLIPSIS);
+        piece.width = font.size_of(&piece.text).map(|(w, _)| w).unwrap_or(0);
+        pieces.push(piece);
+        break;
+    }
+
+    let line_width = pieces
+        .last()
+        .map(|piece| piece.x as u32 + piece.width)
+        .unwrap_or(0);
+
+    (line_width, pieces)
+}
+
 /// This is the storage object for the `TextWidget`.  It stores the config, properties, callback registry,
-/// the font name, style, size, justification, and text message.
+/// the font name, style, size, justification, text message, and the rich text spans, if any.
 pub struct TextWidget {
     config: WidgetConfig,
     system_properties: HashMap<i32, String>,
@@ -56,10 +233,13 @@ pub struct TextWidget {
     font_size: i32,
     justification: TextJustify,
     msg: String,
+    spans: Vec<TextSpan>,
 }
 
 /// Creates a new `TextWidget`, which draws a unit of text on the screen, given the specified font,
-/// size, justification, and layout coordinates.
+/// size, justification, and layout coordinates.  Text is wrapped and aligned within the `Widget` according to
+/// `CONFIG_TEXT_WRAP` and `CONFIG_TEXT_ALIGN`; without them, text is wrapped at spaces, and each
+/// line is justified from the top of the `Widget`.
 impl TextWidget {
     /// Creates a new `TextWidget` object.  Requires the name of the font (the path to the font file),
     /// the style of font (`sdl2::ttf::FontStyle`), the size in pixels of the font, the `TextJustify`
@@ -83,15 +263,34 @@ impl TextWidget {
             font_size,
             justification,
             msg,
+            spans: Vec::new(),
         }
     }
 
-    /// Changes the text displayed in the body of the `Widget`.
+    /// Changes the text displayed in the body of the `Widget`.  This replaces any rich text spans
+    /// with plain text in the font style of the `Widget`.
     pub fn set_text(&mut self, msg: String) {
         self.msg = msg;
+        self.spans.clear();
         self.get_config().set_invalidated(true);
     }
 
+    /// Replaces the text displayed in the body of the `Widget` with rich text: a paragraph of
+    /// `TextSpan`s, each drawn in its own color and style, wrapped to the width of the `Widget`
+    /// and justified as a whole.  The font size is shared by all of the spans.  The text returned
+    /// by `get_text` is the text of all of the spans joined together.
+    pub fn set_spans(&mut self, spans: Vec<TextSpan>) {
+        self.msg = spans.iter().map(|span| span.text.as_str()).collect();
+        self.spans = spans;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the rich text spans displayed in the `TextWidget`, or an empty slice if it displays
+    /// plain text.
+    pub fn get_spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
     /// Retrieves the text currently being displayed in the `TextWidget`.
     pub fn get_text(&self) -> String {
         self.msg.clone()
@@ -110,6 +309,10 @@ impl Widget for TextWidget {
                 .create_or_resize_texture(c, bounds[0] as u32, bounds[1] as u32);
 
             let base_color = self.get_color(CONFIG_COLOR_BASE);
+
+            // A translucent base color lets whatever is drawn behind this `Widget` show through.
+            self.texture_store.set_blended(base_color.a < 255);
+
             let text_max_width = self.get_size(CONFIG_SIZE)[0]
                 - ((self.get_numeric(CONFIG_BORDER_WIDTH) * 2) as u32);
 
@@ -120,40 +323,101 @@ impl Widget for TextWidget {
                 .unwrap();
             let font_color = self.get_color(CONFIG_COLOR_TEXT);
 
-            font.set_style(self.font_style);
+            // Plain text is laid out as a single span in the font style of this `Widget`.
+            let spans = if self.spans.is_empty() {
+                vec![TextSpan::new(&self.msg, None, self.font_style)]
+            } else {
+                self.spans.clone()
+            };
+            let lines = match TextWrap::from_numeric(self.get_numeric(CONFIG_TEXT_WRAP)) {
+                TextWrap::Wrap => layout_spans(&mut font, &spans, text_max_width),
+                TextWrap::None => layout_spans(&mut font, &spans, u32::MAX),
+                TextWrap::Ellipsis => layout_spans(&mut font, &spans, u32::MAX)
+                    .into_iter()
+                    .map(|line| ellipsize(&mut font, &spans, line, text_max_width))
+                    .collect(),
+            };
 
-            let surface = font
-                .render(&self.msg)
-                .blended_wrapped(font_color, text_max_width)
-                .map_err(|e| e.to_string())
-                .unwrap();
-            let font_texture = texture_creator
-                .create_texture_from_surface(&surface)
-                .map_err(|e| e.to_string())
-                .unwrap();
+            // Without `CONFIG_TEXT_ALIGN`, lines are justified by the `TextJustify` of this
+            // `Widget`, starting at the top.
+            let (horizontal, vertical) =
+                if self.get_config().config.contains_key(&CONFIG_TEXT_ALIGN) {
+                    match self.get_compass(CONFIG_TEXT_ALIGN) {
+                        CompassPosition::NW => (TextJustify::Left, CompassPosition::N),
+                        CompassPosition::N => (TextJustify::Center, CompassPosition::N),
+                        CompassPosition::NE => (TextJustify::Right, CompassPosition::N),
+                        CompassPosition::W => (TextJustify::Left, CompassPosition::Center),
+                        CompassPosition::Center => (TextJustify::Center, CompassPosition::Center),
+                        CompassPosition::E => (TextJustify::Right, CompassPosition::Center),
+                        CompassPosition::SW => (TextJustify::Left, CompassPosition::S),
+                        CompassPosition::S => (TextJustify::Center, CompassPosition::S),
+                        CompassPosition::SE => (TextJustify::Right, CompassPosition::S),
+                    }
+                } else {
+                    (self.justification, CompassPosition::N)
+                };
 
-            let TextureQuery { width, height, .. } = font_texture.query();
-            let texture_y = 0;
-            let widget_w = self.get_size(CONFIG_SIZE)[0] as i32;
-            let texture_x = match self.justification {
-                TextJustify::Left => 0,
-                TextJustify::Right => widget_w - width as i32,
-                TextJustify::Center => (widget_w - width as i32) / 2,
+            let widget_w = bounds[0] as i32;
+            let line_height = font.recommended_line_spacing();
+            let text_height = lines.len() as i32 * line_height;
+            let text_y = match vertical {
+                CompassPosition::N => 0,
+                CompassPosition::S => bounds[1] as i32 - text_height,
+                _ => (bounds[1] as i32 - text_height) / 2,
             };
+            let mut pieces: Vec<(Rect, Texture)> = vec![];
+
+            for (line_number, (line_width, line)) in lines.into_iter().enumerate() {
+                let line_x = match horizontal {
+                    TextJustify::Left => 0,
+                    TextJustify::Right => widget_w - line_width as i32,
+                    TextJustify::Center => (widget_w - line_width as i32) / 2,
+                };
+
+                for piece in line {
+                    let span = &spans[piece.span];
+
+                    font.set_style(span.style);
+
+                    let surface = font
+                        .render(&piece.text)
+                        .blended(span.color.unwrap_or(font_color))
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let piece_texture = texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let TextureQuery { width, height, .. } = piece_texture.query();
+
+                    pieces.push((
+                        Rect::new(
+                            line_x + piece.x,
+                            text_y + line_number as i32 * line_height,
+                            width,
+                            height,
+                        ),
+                        piece_texture,
+                    ));
+                }
+            }
 
             c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
                 texture.set_draw_color(base_color);
                 texture.clear();
 
-                texture
-                    .copy(
-                        &font_texture,
-                        None,
-                        Rect::new(texture_x, texture_y, width, height),
-                    )
-                    .unwrap();
+                for (rect, piece_texture) in &pieces {
+                    texture.copy(piece_texture, None, *rect).unwrap();
+                }
             })
             .unwrap();
+
+            // Safety: the piece textures are only used while drawing this `Widget`.
+            for (_, piece_texture) in pieces {
+                unsafe {
+                    piece_texture.destroy();
+                }
+            }
         }
 
         self.texture_store.get_optional_ref()
@@ -164,6 +428,7 @@ impl Widget for TextWidget {
         match _k {
             CONFIG_COLOR_TEXT => self.get_config().set_invalidated(true),
             CONFIG_COLOR_BASE => self.get_config().set_invalidated(true),
+            CONFIG_TEXT_ALIGN | CONFIG_TEXT_WRAP => self.get_config().set_invalidated(true),
             CONFIG_FONT_SIZE => {
                 if let Config::Numeric(size) = _v {
                     self.font_size = size;
@@ -173,6 +438,7 @@ impl Widget for TextWidget {
             CONFIG_TEXT => {
                 if let Config::Text(text) = _v {
                     self.msg = text;
+                    self.spans.clear();
                     self.get_config().set_invalidated(true);
                 }
             }
@@ -181,6 +447,13 @@ impl Widget for TextWidget {
         };
     }
 
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
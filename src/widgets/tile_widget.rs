@@ -78,8 +78,12 @@ impl TileWidget {
         text_widget.set_color(CONFIG_COLOR_BASE, Color::RGBA(255, 255, 255, 255));
         text_widget.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
 
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
         Self {
-            config: WidgetConfig::new(points, size),
+            config,
             system_properties: HashMap::new(),
             callback_registry: CallbackRegistry::new(),
             texture_store: TextureStore::default(),
@@ -238,6 +242,15 @@ impl Widget for TileWidget {
         }
     }
 
+    /// Releases the backing texture of this `Widget`, along with those of the `Widget`s it is
+    /// composed of.  The textures are recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.base_widget.release_textures();
+        self.text_widget.release_textures();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
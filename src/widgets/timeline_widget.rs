@@ -0,0 +1,735 @@
+// Pushrod Widget Library
+// Timeline Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::fill_polygon;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of the time ruler along the top of the timeline, in pixels.
+const RULER_HEIGHT: u32 = 22;
+
+/// This is the smallest distance between labelled ticks on the ruler, in pixels.
+const MIN_TICK_SPACING: f64 = 70.0;
+
+/// These are the intervals between labelled ticks on the ruler, in seconds.  The smallest interval
+/// that keeps the ticks `MIN_TICK_SPACING` apart is used.
+const TICK_INTERVALS: [f64; 17] = [
+    0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0,
+    3600.0,
+];
+
+/// This is the amount the timeline zooms for each step of the mouse wheel.
+const ZOOM_STEP: f64 = 1.25;
+
+/// This is the largest zoom, in pixels per second.
+const MAX_ZOOM: f64 = 2000.0;
+
+/// This is the opacity of the fill of a region that is not selected.
+const REGION_ALPHA: u8 = 96;
+
+/// This is a marker at a point in time on a `TimelineWidget`, drawn as a flagged line.
+#[derive(Clone, Debug)]
+pub struct TimelineMarker {
+    /// This is the time of the marker, in seconds.
+    pub time: f64,
+
+    /// This is the label drawn beside the flag of the marker.
+    pub label: String,
+
+    /// This is the color of the marker, or `None` to use `CONFIG_COLOR_HOVER`.
+    pub color: Option<Color>,
+}
+
+/// This is a span of time on a `TimelineWidget`, drawn as a bar below the ruler.
+#[derive(Clone, Debug)]
+pub struct TimelineRegion {
+    /// This is the start time of the region, in seconds.
+    pub start: f64,
+
+    /// This is the end time of the region, in seconds.
+    pub end: f64,
+
+    /// This is the label drawn inside the region.
+    pub label: String,
+
+    /// This is the color of the region, or `None` to use `CONFIG_COLOR_SECONDARY`.
+    pub color: Option<Color>,
+}
+
+/// This is the callback type that is used when an `on_playhead_moved` callback is triggered from
+/// this `Widget`.  It receives the new time of the playhead, in seconds.
+pub type OnPlayheadMovedCallbackType =
+    Option<Box<dyn FnMut(&mut TimelineWidget, &[WidgetContainer], &[LayoutContainer], f64)>>;
+
+/// This is the callback type that is used when an `on_region_selected` callback is triggered from
+/// this `Widget`.  It receives the index of the region that was clicked.
+pub type OnRegionSelectedCallbackType =
+    Option<Box<dyn FnMut(&mut TimelineWidget, &[WidgetContainer], &[LayoutContainer], usize)>>;
+
+/// This is a single drawing operation, collected before the texture of the timeline is drawn, so
+/// that text can be rendered outside of the texture canvas.
+enum DrawOp {
+    Fill(Rect, Color),
+    Outline(Rect, Color),
+    Line(Point, Point, Color),
+    Polygon(Vec<Point>, Color),
+    Text(Texture, Rect, Rect),
+}
+
+/// Formats `time` in seconds as `m:ss`, or `m:ss.ss` when `precise`.
+fn format_time(time: f64, precise: bool) -> String {
+    let minutes = (time / 60.0).floor();
+    let seconds = time - minutes * 60.0;
+
+    if precise {
+        format!("{}:{:05.2}", minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds.round() as u32)
+    }
+}
+
+/// This is the storage object for the `TimelineWidget`.  It stores the config, properties, callback
+/// registry, the duration, the playhead, the markers and regions, and the visible span of time.
+pub struct TimelineWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    duration: f64,
+    playhead: f64,
+    markers: Vec<TimelineMarker>,
+    regions: Vec<TimelineRegion>,
+    selected_region: Option<usize>,
+    view_start: f64,
+    zoom: Option<f64>,
+    mouse_position: Points,
+    dragging: bool,
+    on_playhead_moved: OnPlayheadMovedCallbackType,
+    on_region_selected: OnRegionSelectedCallbackType,
+}
+
+/// This is the implementation of the `TimelineWidget`, a horizontal view of a span of time, as used
+/// by media and animation tools.  A ruler along the top shows the time, with labelled ticks that
+/// adapt to the zoom.  Below it, regions are drawn as labelled bars, and markers as flagged lines.
+///
+/// Clicking the ruler, or anywhere outside of a region, moves the playhead there, and dragging
+/// moves it further, triggering `on_playhead_moved`.  Clicking a region selects it, triggering
+/// `on_region_selected`.  The mouse wheel zooms in and out around the mouse, and scrolling
+/// horizontally pans the view.
+///
+/// The timeline is filled with `CONFIG_COLOR_BASE`, the ruler with `CONFIG_COLOR_TRACK`, and its
+/// ticks and labels are drawn in `CONFIG_COLOR_TEXT`.  Regions default to
+/// `CONFIG_COLOR_SECONDARY`, markers to `CONFIG_COLOR_HOVER`, and the playhead is drawn in
+/// `CONFIG_COLOR_SELECTED`.
+impl TimelineWidget {
+    /// Creates a new `TimelineWidget` given the `x, y, w, h` coordinates, and the `duration` of the
+    /// timeline in seconds.  The whole duration is shown until the timeline is zoomed.
+    pub fn new(points: Points, size: Size, duration: f64) -> Self {
+        Self {
+            config: WidgetConfig::new(points, size),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            duration: duration.max(0.0),
+            playhead: 0.0,
+            markers: Vec::new(),
+            regions: Vec::new(),
+            selected_region: None,
+            view_start: 0.0,
+            zoom: None,
+            mouse_position: vec![0, 0],
+            dragging: false,
+            on_playhead_moved: None,
+            on_region_selected: None,
+        }
+    }
+
+    /// Sets the duration of the timeline, in seconds.  The playhead and the view are limited to the
+    /// new duration.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.duration = duration.max(0.0);
+        self.playhead = self.playhead.min(self.duration);
+        self.limit_view();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the duration of the timeline, in seconds.
+    pub fn get_duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Moves the playhead to `time`, in seconds, limited to the duration.  This does not trigger
+    /// the `on_playhead_moved` callback.
+    pub fn set_playhead(&mut self, time: f64) {
+        self.playhead = time.max(0.0).min(self.duration);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the time of the playhead, in seconds.
+    pub fn get_playhead(&self) -> f64 {
+        self.playhead
+    }
+
+    /// Adds a marker at `time`, in seconds, with a `label`, and an optional `color`.
+    pub fn add_marker(&mut self, time: f64, label: &str, color: Option<Color>) {
+        self.markers.push(TimelineMarker {
+            time,
+            label: String::from(label),
+            color,
+        });
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the markers on the timeline, in the order in which they were added.
+    pub fn get_markers(&self) -> &[TimelineMarker] {
+        &self.markers
+    }
+
+    /// Removes all of the markers from the timeline.
+    pub fn clear_markers(&mut self) {
+        self.markers.clear();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Adds a region from `start` to `end`, in seconds, with a `label`, and an optional `color`.
+    /// Returns the index of the new region.
+    pub fn add_region(&mut self, start: f64, end: f64, label: &str, color: Option<Color>) -> usize {
+        self.regions.push(TimelineRegion {
+            start: start.min(end),
+            end: start.max(end),
+            label: String::from(label),
+            color,
+        });
+        self.get_config().set_invalidated(true);
+        self.regions.len() - 1
+    }
+
+    /// Retrieves the regions on the timeline, in the order in which they were added.
+    pub fn get_regions(&self) -> &[TimelineRegion] {
+        &self.regions
+    }
+
+    /// Removes all of the regions from the timeline, and clears the selection.
+    pub fn clear_regions(&mut self) {
+        self.regions.clear();
+        self.selected_region = None;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Selects the region at `index`, or clears the selection with `None`.  This does not trigger
+    /// the `on_region_selected` callback.
+    pub fn set_selected_region(&mut self, index: Option<usize>) {
+        self.selected_region = index.filter(|index| *index < self.regions.len());
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the index of the selected region, if any.
+    pub fn get_selected_region(&self) -> Option<usize> {
+        self.selected_region
+    }
+
+    /// Sets the zoom, in pixels per second.  The zoom cannot be smaller than the zoom that fits
+    /// the whole duration into the width of the timeline.
+    pub fn set_zoom(&mut self, pixels_per_second: f64) {
+        self.zoom = Some(pixels_per_second);
+        self.limit_view();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the zoom, in pixels per second.
+    pub fn get_zoom(&mut self) -> f64 {
+        self.pixels_per_second()
+    }
+
+    /// Sets the time shown at the left edge of the timeline, in seconds.
+    pub fn set_view_start(&mut self, time: f64) {
+        self.view_start = time;
+        self.limit_view();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the time shown at the left edge of the timeline, in seconds.
+    pub fn get_view_start(&self) -> f64 {
+        self.view_start
+    }
+
+    /// Assigns the callback closure that will be used when the playhead is moved with the mouse.
+    pub fn on_playhead_moved<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TimelineWidget, &[WidgetContainer], &[LayoutContainer], f64) + 'static,
+    {
+        self.on_playhead_moved = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when a region is clicked.
+    pub fn on_region_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TimelineWidget, &[WidgetContainer], &[LayoutContainer], usize) + 'static,
+    {
+        self.on_region_selected = Some(Box::new(callback));
+    }
+
+    /// Internal function that returns the zoom that fits the whole duration into the width of the
+    /// timeline.
+    fn min_zoom(&mut self) -> f64 {
+        let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH] as f64;
+
+        if self.duration > 0.0 {
+            (width / self.duration).min(MAX_ZOOM)
+        } else {
+            MAX_ZOOM
+        }
+    }
+
+    /// Internal function that returns the zoom, in pixels per second, limited to the smallest and
+    /// largest zoom.
+    fn pixels_per_second(&mut self) -> f64 {
+        let min_zoom = self.min_zoom();
+
+        self.zoom.unwrap_or(min_zoom).max(min_zoom).min(MAX_ZOOM)
+    }
+
+    /// Internal function that keeps the view within the duration of the timeline.
+    fn limit_view(&mut self) {
+        let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH] as f64;
+        let visible = width / self.pixels_per_second();
+
+        self.view_start = self.view_start.min(self.duration - visible).max(0.0);
+    }
+
+    /// Internal function that returns the time at the absolute horizontal position `x`.
+    fn time_at(&mut self, x: i32) -> f64 {
+        let x = (x - self.get_config().to_x(0)) as f64;
+
+        self.view_start + x / self.pixels_per_second()
+    }
+
+    /// Internal function that returns the index of the region at the absolute position `x, y`,
+    /// preferring the region added last where regions overlap.
+    fn region_at(&mut self, x: i32, y: i32) -> Option<usize> {
+        if y - self.get_config().to_y(0) < RULER_HEIGHT as i32 {
+            return None;
+        }
+
+        let time = self.time_at(x);
+
+        self.regions
+            .iter()
+            .rposition(|region| time >= region.start && time <= region.end)
+    }
+
+    /// Internal function that moves the playhead to the absolute horizontal position `x`, and
+    /// triggers the `on_playhead_moved` callback if it moved.
+    fn move_playhead(&mut self, x: i32, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        let time = self.time_at(x).max(0.0).min(self.duration);
+
+        if (time - self.playhead).abs() > f64::EPSILON {
+            self.playhead = time;
+            self.get_config().set_invalidated(true);
+
+            if let Some(mut cb) = self.on_playhead_moved.take() {
+                cb(self, widgets, layouts, time);
+                self.on_playhead_moved = Some(cb);
+            }
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `TimelineWidget`.
+impl Widget for TimelineWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let ruler_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(230, 230, 230));
+            let region_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(96, 144, 224));
+            let marker_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(0, 150, 0));
+            let playhead_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(220, 0, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 11);
+
+            self.limit_view();
+
+            let width = bounds[SIZE_WIDTH] as i32;
+            let height = bounds[SIZE_HEIGHT] as i32;
+            let ruler = RULER_HEIGHT as i32;
+            let zoom = self.pixels_per_second();
+            let view_start = self.view_start;
+            let view_end = view_start + width as f64 / zoom;
+            let x_of = |time: f64| ((time - view_start) * zoom).round() as i32;
+            let interval = TICK_INTERVALS
+                .iter()
+                .cloned()
+                .find(|interval| interval * zoom >= MIN_TICK_SPACING)
+                .unwrap_or(TICK_INTERVALS[TICK_INTERVALS.len() - 1]);
+            let minor_interval = interval / 5.0;
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(
+                    Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                    font_size as u16,
+                )
+                .unwrap();
+            let mut ops: Vec<DrawOp> = vec![];
+
+            // Renders `text` with its top left corner at `x, y`, clipped to `clip`.
+            let text =
+                |ops: &mut Vec<DrawOp>, text: &str, color: Color, x: i32, y: i32, clip: Rect| {
+                    if text.is_empty() {
+                        return;
+                    }
+
+                    let surface = font
+                        .render(text)
+                        .blended(color)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let text_texture = texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap();
+                    let TextureQuery { width, height, .. } = text_texture.query();
+
+                    ops.push(DrawOp::Text(
+                        text_texture,
+                        Rect::new(x, y, width, height),
+                        clip,
+                    ));
+                };
+
+            ops.push(DrawOp::Fill(
+                Rect::new(0, 0, bounds[SIZE_WIDTH], RULER_HEIGHT),
+                ruler_color,
+            ));
+
+            let mut tick = (view_start / minor_interval).floor() as i64;
+
+            while tick as f64 * minor_interval <= view_end {
+                let time = tick as f64 * minor_interval;
+                let x = x_of(time);
+
+                if tick % 5 == 0 {
+                    ops.push(DrawOp::Line(
+                        Point::new(x, ruler / 2),
+                        Point::new(x, ruler - 1),
+                        text_color,
+                    ));
+                    text(
+                        &mut ops,
+                        &format_time(time, interval < 1.0),
+                        text_color,
+                        x + 3,
+                        1,
+                        Rect::new(0, 0, bounds[SIZE_WIDTH], RULER_HEIGHT),
+                    );
+                } else {
+                    ops.push(DrawOp::Line(
+                        Point::new(x, ruler - 5),
+                        Point::new(x, ruler - 1),
+                        text_color,
+                    ));
+                }
+
+                tick += 1;
+            }
+
+            ops.push(DrawOp::Line(
+                Point::new(0, ruler - 1),
+                Point::new(width - 1, ruler - 1),
+                border_color,
+            ));
+
+            let track = Rect::new(0, ruler, bounds[SIZE_WIDTH], (height - ruler).max(1) as u32);
+
+            for (index, region) in self.regions.iter().enumerate() {
+                if region.end < view_start || region.start > view_end {
+                    continue;
+                }
+
+                let left = x_of(region.start).max(-1);
+                let right = x_of(region.end).min(width + 1);
+                let rect = Rect::new(
+                    left,
+                    ruler + 4,
+                    (right - left).max(1) as u32,
+                    (height - ruler - 8).max(1) as u32,
+                );
+                let color = region.color.unwrap_or(region_color);
+                let selected = self.selected_region == Some(index);
+
+                ops.push(DrawOp::Fill(
+                    rect,
+                    Color::RGBA(
+                        color.r,
+                        color.g,
+                        color.b,
+                        if selected { 255 } else { REGION_ALPHA },
+                    ),
+                ));
+                ops.push(DrawOp::Outline(rect, color));
+
+                if selected {
+                    ops.push(DrawOp::Outline(
+                        Rect::new(
+                            rect.x() + 1,
+                            rect.y() + 1,
+                            rect.width().saturating_sub(2).max(1),
+                            rect.height().saturating_sub(2).max(1),
+                        ),
+                        border_color,
+                    ));
+                }
+
+                text(
+                    &mut ops,
+                    &region.label,
+                    text_color,
+                    left.max(0) + 4,
+                    ruler + 6,
+                    rect.intersection(track).unwrap_or(rect),
+                );
+            }
+
+            for marker in &self.markers {
+                if marker.time < view_start || marker.time > view_end {
+                    continue;
+                }
+
+                let x = x_of(marker.time);
+                let color = marker.color.unwrap_or(marker_color);
+
+                ops.push(DrawOp::Line(
+                    Point::new(x, ruler),
+                    Point::new(x, height - 1),
+                    color,
+                ));
+                ops.push(DrawOp::Polygon(
+                    vec![
+                        Point::new(x, ruler),
+                        Point::new(x + 8, ruler + 4),
+                        Point::new(x, ruler + 8),
+                    ],
+                    color,
+                ));
+                text(
+                    &mut ops,
+                    &marker.label,
+                    color,
+                    x + 10,
+                    height - font_size - 6,
+                    track,
+                );
+            }
+
+            if self.playhead >= view_start && self.playhead <= view_end {
+                let x = x_of(self.playhead);
+
+                ops.push(DrawOp::Line(
+                    Point::new(x, 0),
+                    Point::new(x, height - 1),
+                    playhead_color,
+                ));
+                ops.push(DrawOp::Polygon(
+                    vec![Point::new(x - 6, 0), Point::new(x + 6, 0), Point::new(x, 8)],
+                    playhead_color,
+                ));
+            }
+
+            ops.push(DrawOp::Outline(
+                Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]),
+                border_color,
+            ));
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+                texture.set_blend_mode(BlendMode::Blend);
+
+                for op in &ops {
+                    match op {
+                        DrawOp::Fill(rect, color) => {
+                            texture.set_draw_color(*color);
+                            texture.fill_rect(*rect).unwrap();
+                        }
+                        DrawOp::Outline(rect, color) => {
+                            texture.set_draw_color(*color);
+                            texture.draw_rect(*rect).unwrap();
+                        }
+                        DrawOp::Line(start, end, color) => {
+                            texture.set_draw_color(*color);
+                            texture.draw_line(*start, *end).unwrap();
+                        }
+                        DrawOp::Polygon(points, color) => {
+                            texture.set_draw_color(*color);
+                            fill_polygon(texture, points);
+                        }
+                        DrawOp::Text(text_texture, rect, clip) => {
+                            texture.set_clip_rect(*clip);
+                            texture.copy(text_texture, None, *rect).unwrap();
+                            texture.set_clip_rect(None);
+                        }
+                    }
+                }
+
+                texture.set_blend_mode(BlendMode::None);
+            })
+            .unwrap();
+
+            // Safety: the text textures are only used while drawing this `Widget`.
+            for op in ops {
+                if let DrawOp::Text(text_texture, _, _) = op {
+                    unsafe {
+                        text_texture.destroy();
+                    }
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Drags the playhead while the mouse button is held.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        if self.dragging {
+            self.move_playhead(points[POINT_X], _widgets, _layouts);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Zooms around the mouse with the vertical wheel, and pans the view with the horizontal
+    /// wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let width = self.get_config().get_size(CONFIG_SIZE)[SIZE_WIDTH] as f64;
+        let zoom = self.pixels_per_second();
+
+        if points[POINT_Y] != 0 {
+            let anchor = self.time_at(self.mouse_position[POINT_X]);
+            let offset = (anchor - self.view_start) * zoom;
+
+            self.zoom = Some(zoom * ZOOM_STEP.powi(points[POINT_Y]));
+
+            let zoom = self.pixels_per_second();
+
+            self.view_start = anchor - offset / zoom;
+        }
+
+        if points[POINT_X] != 0 {
+            self.view_start += points[POINT_X] as f64 * width / 10.0 / zoom;
+        }
+
+        self.limit_view();
+        self.get_config().set_invalidated(true);
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Selects the region under the mouse, or moves the playhead to the mouse and starts dragging
+    /// it.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                let x = self.mouse_position[POINT_X];
+                let y = self.mouse_position[POINT_Y];
+
+                match self.region_at(x, y) {
+                    Some(index) => {
+                        self.selected_region = Some(index);
+                        self.get_config().set_invalidated(true);
+
+                        if let Some(mut cb) = self.on_region_selected.take() {
+                            cb(self, _widgets, _layouts, index);
+                            self.on_region_selected = Some(cb);
+                        }
+                    }
+                    None => {
+                        self.dragging = true;
+                        self.move_playhead(x, _widgets, _layouts);
+                    }
+                }
+            } else {
+                self.dragging = false;
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Redraws the timeline when its colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_TEXT
+            | CONFIG_COLOR_TRACK
+            | CONFIG_COLOR_SECONDARY
+            | CONFIG_COLOR_HOVER
+            | CONFIG_COLOR_SELECTED
+            | CONFIG_FONT_SIZE => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use crate::render::callbacks::CallbackRegistry;
+use crate::render::clock::now_millis;
 use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::WidgetConfig;
@@ -22,20 +23,12 @@ use crate::render::layout_cache::LayoutContainer;
 use crate::render::{make_points_origin, make_size};
 use std::any::Any;
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 /// This is the callback type that is used when an `on_timeout` callback is triggered from this
 /// `Widget`.
 pub type TimerCallbackType =
     Option<Box<dyn FnMut(&mut TimerWidget, &[WidgetContainer], &[LayoutContainer])>>;
 
-/// Private function used to return the current time in milliseconds.
-fn time_ms() -> u64 {
-    let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-
-    (since_the_epoch.as_secs() * 1_000) + u64::from(since_the_epoch.subsec_millis())
-}
-
 /// This is the storage object for the `TimerWidget`.  It stores the config, properties, callback registry,
 /// an enabled flag, timeout, a last-time-triggered value, and a timeout callback store.
 pub struct TimerWidget {
@@ -61,14 +54,14 @@ impl TimerWidget {
             callback_registry: CallbackRegistry::new(),
             enabled,
             timeout,
-            initiated: time_ms(),
+            initiated: now_millis(),
             on_timeout: None,
         }
     }
 
     /// Re-enables the timer.  This will also reset the elapsed timer.
     pub fn enable(&mut self) {
-        self.initiated = time_ms();
+        self.initiated = now_millis();
         self.enabled = true;
     }
 
@@ -109,10 +102,10 @@ impl Widget for TimerWidget {
             return;
         }
 
-        let elapsed = time_ms() - self.initiated;
+        let elapsed = now_millis() - self.initiated;
 
         if elapsed > self.timeout {
-            self.initiated = time_ms();
+            self.initiated = now_millis();
             self.call_timeout_callback(_widgets, _layouts);
         }
     }
@@ -0,0 +1,302 @@
+// Pushrod Widget Library
+// Toast Notification Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{make_points_origin, Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::clock;
+use crate::render::layer::Layer;
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the number of milliseconds it takes a toast to slide into, or out of, the window.
+const SLIDE_DURATION: u64 = 250;
+
+/// This is the distance between a toast and the edges of the window, in pixels.
+const TOAST_MARGIN: i32 = 16;
+
+/// This is the number of pixels between the left edge of a toast and its message.
+const TOAST_PADDING: i32 = 12;
+
+/// This is the storage object for the `ToastNotificationWidget`.  It stores the config, properties,
+/// callback registry, the message, the corner of the window it is shown in, and the times at which
+/// it was shown and dismissed.
+pub struct ToastNotificationWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    message: String,
+    position: CompassPosition,
+    duration: u64,
+    shown_at: Option<u64>,
+    dismissed_at: Option<u64>,
+}
+
+/// This is the implementation of the `ToastNotificationWidget`, a notification that slides into a
+/// corner of the window, displays a message for a number of milliseconds, slides back out, and then
+/// removes itself from the display list.  Clicking on a toast dismisses it early.  Toasts are drawn
+/// on the `Overlay` `Layer`, above the rest of the application.
+impl ToastNotificationWidget {
+    /// Creates a new `ToastNotificationWidget` of the given `size`, which shows `message` for
+    /// `duration` milliseconds.  The `position` determines where in the window the toast is shown:
+    /// `NW`, `NE`, `SW`, and `SE` are the corners of the window, and it slides in from the nearest
+    /// edge.  The toast starts its timer the first time it receives a `tick`, so it can be added to
+    /// the `Engine` at any time.
+    pub fn new(message: &str, size: Size, position: CompassPosition, duration: u64) -> Self {
+        let mut config = WidgetConfig::new(make_points_origin(), size);
+
+        config.set_numeric(CONFIG_LAYER, Layer::Overlay.index() as i32);
+        config.set_color(CONFIG_COLOR_BASE, Color::RGB(50, 50, 50));
+        config.set_color(CONFIG_COLOR_TEXT, Color::RGB(255, 255, 255));
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            message: String::from(message),
+            position,
+            duration,
+            shown_at: None,
+            dismissed_at: None,
+        }
+    }
+
+    /// Changes the message shown by the toast.
+    pub fn set_message(&mut self, message: &str) {
+        self.message = String::from(message);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the message shown by the toast.
+    pub fn get_message(&self) -> String {
+        self.message.clone()
+    }
+
+    /// Dismisses the toast before its time has elapsed.  It slides out of the window, and is then
+    /// removed.
+    pub fn dismiss(&mut self) {
+        if self.dismissed_at.is_none() {
+            self.dismissed_at = Some(clock::now_millis());
+        }
+    }
+
+    /// Indicates whether or not the toast has been dismissed, either because its time has elapsed,
+    /// or because `dismiss` was called.
+    pub fn is_dismissed(&self) -> bool {
+        self.dismissed_at.is_some()
+    }
+
+    /// Internal function that returns the points at which the toast is hidden, just outside the
+    /// nearest edge of the window, and at which it is fully shown.
+    fn slide_points(&mut self, window_size: &[u32]) -> (Points, Points) {
+        let size = self.get_config().get_size(CONFIG_SIZE);
+        let width = size[SIZE_WIDTH] as i32;
+        let height = size[SIZE_HEIGHT] as i32;
+        let window_width = window_size[SIZE_WIDTH] as i32;
+        let window_height = window_size[SIZE_HEIGHT] as i32;
+        let left = TOAST_MARGIN;
+        let right = window_width - width - TOAST_MARGIN;
+        let center_x = (window_width - width) / 2;
+        let top = TOAST_MARGIN;
+        let bottom = window_height - height - TOAST_MARGIN;
+        let center_y = (window_height - height) / 2;
+
+        match self.position {
+            CompassPosition::NW => (vec![-width, top], vec![left, top]),
+            CompassPosition::W => (vec![-width, center_y], vec![left, center_y]),
+            CompassPosition::SW => (vec![-width, bottom], vec![left, bottom]),
+            CompassPosition::NE => (vec![window_width, top], vec![right, top]),
+            CompassPosition::E => (vec![window_width, center_y], vec![right, center_y]),
+            CompassPosition::SE => (vec![window_width, bottom], vec![right, bottom]),
+            CompassPosition::N => (vec![center_x, -height], vec![center_x, top]),
+            CompassPosition::S => (vec![center_x, window_height], vec![center_x, bottom]),
+            CompassPosition::Center => (vec![center_x, center_y], vec![center_x, center_y]),
+        }
+    }
+}
+
+/// Eases a linear `progress` value between `0.0` and `1.0`, so that sliding decelerates towards
+/// its end.
+fn ease_out(progress: f64) -> f64 {
+    let remaining = 1.0 - progress.clamp(0.0, 1.0);
+
+    1.0 - remaining * remaining
+}
+
+/// This is the `Widget` implementation of the `ToastNotificationWidget`.
+impl Widget for ToastNotificationWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let border_color = if self.get_config().config.contains_key(&CONFIG_COLOR_BORDER) {
+                Some(self.get_color(CONFIG_COLOR_BORDER))
+            } else {
+                None
+            };
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 14)
+                .unwrap();
+            let message_texture = if self.message.is_empty() {
+                None
+            } else {
+                let surface = font
+                    .render(&self.message)
+                    .blended(text_color)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+
+                Some(
+                    texture_creator
+                        .create_texture_from_surface(&surface)
+                        .map_err(|e| e.to_string())
+                        .unwrap(),
+                )
+            };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                if let Some(border_color) = border_color {
+                    texture.set_draw_color(border_color);
+                    texture
+                        .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                        .unwrap();
+                }
+
+                if let Some(message_texture) = &message_texture {
+                    let TextureQuery { width, height, .. } = message_texture.query();
+
+                    texture
+                        .copy(
+                            message_texture,
+                            None,
+                            Rect::new(
+                                TOAST_PADDING,
+                                (bounds[SIZE_HEIGHT] as i32 - height as i32) / 2,
+                                width,
+                                height,
+                            ),
+                        )
+                        .unwrap();
+                }
+            })
+            .unwrap();
+
+            if let Some(message_texture) = message_texture {
+                // Safety: the message texture is only used within this function.
+                unsafe {
+                    message_texture.destroy();
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Slides the toast in and out of the window, dismisses it once its time has elapsed, and
+    /// requests that it be removed once it has slid out of the window.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        let now = clock::now_millis();
+        let shown_at = *self.shown_at.get_or_insert(now);
+
+        if self.dismissed_at.is_none()
+            && now.saturating_sub(shown_at) >= SLIDE_DURATION + self.duration
+        {
+            self.dismissed_at = Some(now);
+        }
+
+        let shown = match self.dismissed_at {
+            Some(dismissed_at) => {
+                let elapsed = now.saturating_sub(dismissed_at);
+
+                if elapsed >= SLIDE_DURATION {
+                    self.get_config().set_toggle(CONFIG_REMOVE, true);
+                }
+
+                1.0 - ease_out(elapsed as f64 / SLIDE_DURATION as f64)
+            }
+            None => ease_out(now.saturating_sub(shown_at) as f64 / SLIDE_DURATION as f64),
+        };
+
+        let window_size = _widgets[0]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .get_size(CONFIG_SIZE);
+        let (hidden_points, shown_points) = self.slide_points(&window_size);
+        let x = hidden_points[POINT_X]
+            + ((shown_points[POINT_X] - hidden_points[POINT_X]) as f64 * shown).round() as i32;
+        let y = hidden_points[POINT_Y]
+            + ((shown_points[POINT_Y] - hidden_points[POINT_Y]) as f64 * shown).round() as i32;
+
+        if self.get_config().get_point(CONFIG_ORIGIN) != vec![x, y] {
+            self.get_config().set_point(CONFIG_ORIGIN, x, y);
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Dismisses the toast when it is clicked.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 && _state {
+            self.dismiss();
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
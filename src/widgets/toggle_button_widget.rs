@@ -92,6 +92,7 @@ impl ToggleButtonWidget {
         text_widget.set_color(CONFIG_COLOR_TEXT, text_color);
 
         config.set_toggle(CONFIG_SELECTED_STATE, selected);
+        config.set_toggle(CONFIG_FOCUSABLE, true);
 
         Self {
             config,
@@ -266,6 +267,15 @@ impl Widget for ToggleButtonWidget {
         self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
     }
 
+    /// Releases the backing texture of this `Widget`, along with those of the `Widget`s it is
+    /// composed of.  The textures are recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.base_widget.release_textures();
+        self.text_widget.release_textures();
+        self.set_invalidated(true);
+    }
+
     default_widget_functions!();
     default_widget_properties!();
     default_widget_callbacks!();
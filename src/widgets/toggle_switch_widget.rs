@@ -0,0 +1,275 @@
+// Pushrod Widget Library
+// Toggle Switch Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas_helper::{draw_circle, fill_circle, fill_rounded_rect};
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture};
+use sdl2::video::Window;
+
+use crate::render::clock;
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// This is the number of milliseconds it takes the thumb to slide from one end of the track to the
+/// other.
+const SLIDE_DURATION: u64 = 150;
+
+/// This is the space between the edge of the track and the thumb, in pixels.
+const THUMB_INSET: u32 = 2;
+
+/// This is the callback type that is used when an `on_toggle` callback is triggered from this
+/// `Widget`.  It receives the new state of the switch.
+pub type OnToggleSwitchCallbackType =
+    Option<Box<dyn FnMut(&mut ToggleSwitchWidget, &[WidgetContainer], &[LayoutContainer], bool)>>;
+
+/// This is the storage object for the `ToggleSwitchWidget`.  It stores the config, properties,
+/// callback registry, the state of the switch, and the position of its thumb.
+pub struct ToggleSwitchWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    selected: bool,
+    in_bounds: bool,
+    originated: bool,
+    thumb_position: f64,
+    slide_start: Option<(u64, f64)>,
+    on_toggle: OnToggleSwitchCallbackType,
+}
+
+/// This is the implementation of the `ToggleSwitchWidget`, an on/off switch drawn as a pill-shaped
+/// track with a round thumb.  Clicking the switch, or pressing `Space` or `Return` while it has
+/// focus, toggles it, and the thumb slides to the other end of the track.  The track is drawn in
+/// `CONFIG_COLOR_TRACK` when the switch is off, and `CONFIG_COLOR_SELECTED` when it is on, and the
+/// thumb is drawn in `CONFIG_COLOR_SECONDARY`.
+impl ToggleSwitchWidget {
+    /// Creates a new `ToggleSwitchWidget` given the `x, y, w, h` coordinates, and whether or not it
+    /// is initially `selected` (switched on.)  The switch is drawn best when it is about twice as
+    /// wide as it is high.
+    pub fn new(points: Points, size: Size, selected: bool) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_SELECTED_STATE, selected);
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            selected,
+            in_bounds: false,
+            originated: false,
+            thumb_position: if selected { 1.0 } else { 0.0 },
+            slide_start: None,
+            on_toggle: None,
+        }
+    }
+
+    /// Switches the `Widget` on or off.  The thumb moves immediately, without sliding, and the
+    /// `on_toggle` callback is not triggered.
+    pub fn set_selected(&mut self, selected: bool) {
+        self.selected = selected;
+        self.thumb_position = if selected { 1.0 } else { 0.0 };
+        self.slide_start = None;
+        self.set_toggle(CONFIG_SELECTED_STATE, selected);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Indicates whether or not the `Widget` is switched on.
+    pub fn is_selected(&self) -> bool {
+        self.selected
+    }
+
+    /// Assigns the callback closure that will be used when the `Widget` is switched on or off.
+    pub fn on_toggle<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut ToggleSwitchWidget, &[WidgetContainer], &[LayoutContainer], bool) + 'static,
+    {
+        self.on_toggle = Some(Box::new(callback));
+    }
+
+    /// Internal function that toggles the switch, starts sliding the thumb to the other end of the
+    /// track, and triggers the `on_toggle` callback.
+    fn toggle(&mut self, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        self.selected = !self.selected;
+        self.slide_start = Some((clock::now_millis(), self.thumb_position));
+        self.set_toggle(CONFIG_SELECTED_STATE, self.selected);
+
+        if let Some(mut cb) = self.on_toggle.take() {
+            cb(self, widgets, layouts, self.selected);
+            self.on_toggle = Some(cb);
+        }
+    }
+}
+
+/// Blends from color `from` to color `to` by `amount`, which ranges from `0.0` to `1.0`.
+fn blend_color(from: Color, to: Color, amount: f64) -> Color {
+    let blend = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * amount).round() as u8;
+
+    Color::RGBA(
+        blend(from.r, to.r),
+        blend(from.g, to.g),
+        blend(from.b, to.b),
+        blend(from.a, to.a),
+    )
+}
+
+/// This is the `Widget` implementation of the `ToggleSwitchWidget`.
+impl Widget for ToggleSwitchWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, _t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let off_color = self.get_color_or(CONFIG_COLOR_TRACK, Color::RGB(200, 200, 200));
+            let on_color = self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(76, 200, 100));
+            let thumb_color = self.get_color_or(CONFIG_COLOR_SECONDARY, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(160, 160, 160));
+            let track_color = blend_color(off_color, on_color, self.thumb_position);
+            let height = bounds[SIZE_HEIGHT];
+            let thumb_radius = (height / 2).saturating_sub(THUMB_INSET);
+            let travel = bounds[SIZE_WIDTH].saturating_sub(height) as f64;
+            let thumb_center = Point::new(
+                (height / 2) as i32 + (travel * self.thumb_position).round() as i32,
+                (height / 2) as i32,
+            );
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                texture.set_draw_color(track_color);
+                fill_rounded_rect(
+                    texture,
+                    Rect::new(0, 0, bounds[SIZE_WIDTH], height),
+                    height / 2,
+                );
+
+                texture.set_draw_color(thumb_color);
+                fill_circle(texture, thumb_center, thumb_radius);
+
+                texture.set_draw_color(border_color);
+                draw_circle(texture, thumb_center, thumb_radius);
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Slides the thumb towards the end of the track for the current state.
+    fn tick(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if let Some((started_at, start_position)) = self.slide_start {
+            let target = if self.selected { 1.0 } else { 0.0 };
+            let progress =
+                clock::now_millis().saturating_sub(started_at) as f64 / SLIDE_DURATION as f64;
+
+            if progress >= 1.0 {
+                self.thumb_position = target;
+                self.slide_start = None;
+            } else {
+                self.thumb_position = start_position + (target - start_position) * progress;
+            }
+
+            self.get_config().set_invalidated(true);
+        }
+
+        self.tick_callback(_widgets, _layouts);
+    }
+
+    /// Tracks that the mouse is over the `Widget`, so that releasing the button toggles it.
+    fn mouse_entered(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = true;
+        self.mouse_entered_callback(_widgets, _layouts);
+    }
+
+    /// Tracks that the mouse has left the `Widget`, so that releasing the button does not toggle
+    /// it.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.in_bounds = false;
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Toggles the switch when the mouse button is pressed and released over it.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                self.originated = true;
+            } else {
+                if self.in_bounds && self.originated {
+                    self.toggle(_widgets, _layouts);
+                }
+
+                self.originated = false;
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Toggles the switch when `Space` or `Return` is pressed.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(Keycode::Space),
+            ..
+        }
+        | Event::KeyDown {
+            keycode: Some(Keycode::Return),
+            ..
+        } = event
+        {
+            self.toggle(_widgets, _layouts);
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
@@ -0,0 +1,641 @@
+// Pushrod Widget Library
+// Tree View Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_X, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the height of each row of the tree.
+const ROW_HEIGHT: u32 = 24;
+
+/// This is the number of pixels each level of the tree is indented by.
+const INDENT_WIDTH: i32 = 16;
+
+/// This is the callback type that is used when an `on_node_expanded`, `on_node_collapsed` or
+/// `on_node_selected` callback is triggered from this `Widget`.  It receives the path of the node: the
+/// index of each node from the top level down.
+pub type OnNodeCallbackType =
+    Option<Box<dyn FnMut(&mut TreeViewWidget, &[WidgetContainer], &[LayoutContainer], &[usize])>>;
+
+/// This is a node in the model of a `TreeViewWidget`: a label, and a list of child nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode {
+    /// The text drawn for this node.
+    pub label: String,
+
+    /// The child nodes of this node.
+    pub children: Vec<TreeNode>,
+
+    /// Whether or not the children of this node are shown.
+    pub expanded: bool,
+}
+
+/// This is the implementation of the `TreeNode`.
+impl TreeNode {
+    /// Creates a new, collapsed `TreeNode` with no children.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: String::from(label),
+            children: vec![],
+            expanded: false,
+        }
+    }
+
+    /// Adds a child node, returning this node, so that calls can be chained.
+    pub fn with_child(mut self, child: TreeNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Sets whether or not this node is expanded, returning this node, so that calls can be chained.
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Indicates whether or not this node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// This is the storage object for the `TreeViewWidget`.  It stores the config, properties, callback
+/// registry, the tree of nodes, and the selection and scroll state.
+pub struct TreeViewWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    nodes: Vec<TreeNode>,
+    selected_path: Option<Vec<usize>>,
+    highlighted_row: i32,
+    scroll_offset: usize,
+    mouse_position: Points,
+    on_node_expanded: OnNodeCallbackType,
+    on_node_collapsed: OnNodeCallbackType,
+    on_node_selected: OnNodeCallbackType,
+}
+
+/// This is the implementation of the `TreeViewWidget`, a hierarchical list of nodes that can be
+/// expanded and collapsed.
+impl TreeViewWidget {
+    /// Creates a new `TreeViewWidget` given the `x, y, w, h` coordinates.
+    pub fn new(points: Points, size: Size) -> Self {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            nodes: vec![],
+            selected_path: None,
+            highlighted_row: -1,
+            scroll_offset: 0,
+            mouse_position: vec![0, 0],
+            on_node_expanded: None,
+            on_node_collapsed: None,
+            on_node_selected: None,
+        }
+    }
+
+    /// Replaces the top-level nodes of the tree.  The selection is cleared.
+    pub fn set_nodes(&mut self, nodes: Vec<TreeNode>) {
+        self.nodes = nodes;
+        self.selected_path = None;
+        self.scroll_offset = 0;
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Adds a top-level node to the tree.
+    pub fn add_node(&mut self, node: TreeNode) {
+        self.nodes.push(node);
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the top-level nodes of the tree.
+    pub fn get_nodes(&self) -> &[TreeNode] {
+        &self.nodes
+    }
+
+    /// Retrieves the node at `path`, if it exists.
+    pub fn get_node(&self, path: &[usize]) -> Option<&TreeNode> {
+        let (first, rest) = path.split_first()?;
+
+        rest.iter()
+            .try_fold(self.nodes.get(*first)?, |node, index| {
+                node.children.get(*index)
+            })
+    }
+
+    /// Retrieves a mutable reference to the node at `path`, if it exists.  Invalidate the `Widget`
+    /// after modifying the node.
+    pub fn get_node_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode> {
+        let (first, rest) = path.split_first()?;
+
+        rest.iter()
+            .try_fold(self.nodes.get_mut(*first)?, |node, index| {
+                node.children.get_mut(*index)
+            })
+    }
+
+    /// Retrieves the path of the selected node, if a node is selected.
+    pub fn get_selected_path(&self) -> Option<&[usize]> {
+        self.selected_path.as_deref()
+    }
+
+    /// Selects the node at `path`, expanding its parents so that it is shown.  This does not trigger
+    /// the `on_node_selected` callback.
+    pub fn set_selected_path(&mut self, path: &[usize]) {
+        if self.get_node(path).is_none() {
+            return;
+        }
+
+        for depth in 1..path.len() {
+            if let Some(parent) = self.get_node_mut(&path[..depth]) {
+                parent.expanded = true;
+            }
+        }
+
+        self.selected_path = Some(path.to_vec());
+        self.reveal_selection();
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Expands or collapses the node at `path`.  This does not trigger the `on_node_expanded` or
+    /// `on_node_collapsed` callbacks.
+    pub fn set_expanded(&mut self, path: &[usize], expanded: bool) {
+        if let Some(node) = self.get_node_mut(path) {
+            node.expanded = expanded;
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Assigns the callback closure that will be used when the user expands a node.
+    pub fn on_node_expanded<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TreeViewWidget, &[WidgetContainer], &[LayoutContainer], &[usize]) + 'static,
+    {
+        self.on_node_expanded = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when the user collapses a node.
+    pub fn on_node_collapsed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TreeViewWidget, &[WidgetContainer], &[LayoutContainer], &[usize]) + 'static,
+    {
+        self.on_node_collapsed = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when the user selects a node.
+    pub fn on_node_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TreeViewWidget, &[WidgetContainer], &[LayoutContainer], &[usize]) + 'static,
+    {
+        self.on_node_selected = Some(Box::new(callback));
+    }
+
+    /// Returns the path of each node that is shown, in the order in which they are drawn.
+    fn visible_rows(&self) -> Vec<Vec<usize>> {
+        fn collect(nodes: &[TreeNode], prefix: &[usize], rows: &mut Vec<Vec<usize>>) {
+            for (index, node) in nodes.iter().enumerate() {
+                let mut path = prefix.to_vec();
+
+                path.push(index);
+                rows.push(path.clone());
+
+                if node.expanded {
+                    collect(&node.children, &path, rows);
+                }
+            }
+        }
+
+        let mut rows = vec![];
+
+        collect(&self.nodes, &[], &mut rows);
+
+        rows
+    }
+
+    /// Returns the number of rows that fit entirely within the bounds of the `Widget`.
+    fn visible_row_count(&mut self) -> usize {
+        ((self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] / ROW_HEIGHT) as usize).max(1)
+    }
+
+    /// Scrolls the tree so that the selected node is shown.
+    fn reveal_selection(&mut self) {
+        let rows = self.visible_rows();
+        let visible_row_count = self.visible_row_count();
+        let selected_row = match &self.selected_path {
+            Some(path) => rows.iter().position(|row| row == path),
+            None => None,
+        };
+
+        if let Some(row) = selected_row {
+            if row < self.scroll_offset {
+                self.scroll_offset = row;
+            } else if row >= self.scroll_offset + visible_row_count {
+                self.scroll_offset = row + 1 - visible_row_count;
+            }
+        }
+    }
+
+    /// Expands or collapses a node, triggering the appropriate callback if its state changed.
+    fn toggle(
+        &mut self,
+        path: &[usize],
+        expanded: bool,
+        widgets: &[WidgetContainer],
+        layouts: &[LayoutContainer],
+    ) {
+        let changed = match self.get_node_mut(path) {
+            Some(node) if !node.is_leaf() && node.expanded != expanded => {
+                node.expanded = expanded;
+                true
+            }
+            _ => false,
+        };
+
+        if !changed {
+            return;
+        }
+
+        // Collapsing a node that contains the selection moves the selection to the node itself.
+        if !expanded {
+            if let Some(selected_path) = &self.selected_path {
+                if selected_path.len() > path.len() && selected_path.starts_with(path) {
+                    self.selected_path = Some(path.to_vec());
+                }
+            }
+        }
+
+        self.get_config().set_invalidated(true);
+
+        let callback = if expanded {
+            self.on_node_expanded.take()
+        } else {
+            self.on_node_collapsed.take()
+        };
+
+        if let Some(mut cb) = callback {
+            cb(self, widgets, layouts, path);
+
+            if expanded {
+                self.on_node_expanded = Some(cb);
+            } else {
+                self.on_node_collapsed = Some(cb);
+            }
+        }
+    }
+
+    /// Selects a node, triggering the `on_node_selected` callback if the selection changed.
+    fn select(&mut self, path: &[usize], widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if self.selected_path.as_deref() == Some(path) {
+            return;
+        }
+
+        self.selected_path = Some(path.to_vec());
+        self.reveal_selection();
+        self.get_config().set_invalidated(true);
+
+        if let Some(mut cb) = self.on_node_selected.take() {
+            cb(self, widgets, layouts, path);
+            self.on_node_selected = Some(cb);
+        }
+    }
+
+    /// Returns the row under the last known mouse position, or `-1` if there is none.
+    fn row_at_mouse(&mut self) -> i32 {
+        let y = self.mouse_position[POINT_Y] - self.get_config().to_y(0);
+        let row = self.scroll_offset as i32 + y / ROW_HEIGHT as i32;
+
+        if y >= 0 && (row as usize) < self.visible_rows().len() {
+            row
+        } else {
+            -1
+        }
+    }
+}
+
+/// This is the `Widget` implementation of the `TreeViewWidget`.
+impl Widget for TreeViewWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color(CONFIG_COLOR_BASE);
+            let border_color = self.get_color(CONFIG_COLOR_BORDER);
+            let hover_color = self.get_color(CONFIG_COLOR_HOVER);
+            let selected_color = self.get_color(CONFIG_COLOR_SELECTED);
+            let text_color = self.get_color(CONFIG_COLOR_TEXT);
+            let rows = self.visible_rows();
+            let visible_row_count = self.visible_row_count();
+
+            self.scroll_offset = self
+                .scroll_offset
+                .min(rows.len().saturating_sub(visible_row_count));
+
+            let first_row = self.scroll_offset;
+            let last_row = (first_row + visible_row_count + 1).min(rows.len());
+            let highlighted_row = self.highlighted_row;
+            let selected_path = self.selected_path.clone();
+
+            let ttf_context = t.get_ttf_context();
+            let texture_creator = c.texture_creator();
+            let font = ttf_context
+                .load_font(Path::new(&String::from("assets/OpenSans-Regular.ttf")), 16)
+                .unwrap();
+            let row_contents: Vec<(usize, bool, bool, Option<Texture>)> = rows[first_row..last_row]
+                .iter()
+                .map(|path| {
+                    let node = self.get_node(path).unwrap();
+                    let font_texture = if node.label.is_empty() {
+                        None
+                    } else {
+                        let surface = font
+                            .render(&node.label)
+                            .blended(text_color)
+                            .map_err(|e| e.to_string())
+                            .unwrap();
+
+                        Some(
+                            texture_creator
+                                .create_texture_from_surface(&surface)
+                                .map_err(|e| e.to_string())
+                                .unwrap(),
+                        )
+                    };
+
+                    (path.len() - 1, node.is_leaf(), node.expanded, font_texture)
+                })
+                .collect();
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (row, (depth, is_leaf, expanded, font_texture)) in
+                    row_contents.iter().enumerate()
+                {
+                    let index = first_row + row;
+                    let y = (row as u32 * ROW_HEIGHT) as i32;
+                    let x = *depth as i32 * INDENT_WIDTH;
+
+                    if selected_path.as_ref() == Some(&rows[index]) {
+                        texture.set_draw_color(selected_color);
+                        texture
+                            .fill_rect(Rect::new(0, y, bounds[SIZE_WIDTH], ROW_HEIGHT))
+                            .unwrap();
+                    } else if highlighted_row == index as i32 {
+                        texture.set_draw_color(hover_color);
+                        texture
+                            .fill_rect(Rect::new(0, y, bounds[SIZE_WIDTH], ROW_HEIGHT))
+                            .unwrap();
+                    }
+
+                    // The expansion arrow points right when collapsed, and down when expanded.
+                    if !is_leaf {
+                        let center_x = x + INDENT_WIDTH / 2;
+                        let center_y = y + ROW_HEIGHT as i32 / 2;
+
+                        texture.set_draw_color(text_color);
+
+                        for step in 0..4 {
+                            let (start, end) = if *expanded {
+                                (
+                                    Point::new(center_x - 4 + step, center_y - 2 + step),
+                                    Point::new(center_x + 4 - step, center_y - 2 + step),
+                                )
+                            } else {
+                                (
+                                    Point::new(center_x - 2 + step, center_y - 4 + step),
+                                    Point::new(center_x - 2 + step, center_y + 4 - step),
+                                )
+                            };
+
+                            texture.draw_line(start, end).unwrap();
+                        }
+                    }
+
+                    if let Some(font_texture) = font_texture {
+                        let TextureQuery { width, height, .. } = font_texture.query();
+
+                        texture
+                            .copy(
+                                font_texture,
+                                None,
+                                Rect::new(
+                                    x + INDENT_WIDTH + 2,
+                                    y + (ROW_HEIGHT as i32 - height as i32) / 2,
+                                    width,
+                                    height,
+                                ),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                texture.set_draw_color(border_color);
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[0], bounds[1]))
+                    .unwrap();
+            })
+            .unwrap();
+
+            // Safety: the label textures are only used within this function.
+            for (_, _, _, font_texture) in row_contents {
+                if let Some(font_texture) = font_texture {
+                    unsafe {
+                        font_texture.destroy();
+                    }
+                }
+            }
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Clears the highlighted row when the mouse leaves the `Widget`.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        if self.highlighted_row != -1 {
+            self.highlighted_row = -1;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the row under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        self.mouse_position = points.clone();
+
+        let highlighted_row = self.row_at_mouse();
+
+        if highlighted_row != self.highlighted_row {
+            self.highlighted_row = highlighted_row;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the tree by one row per step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let max_offset = self
+            .visible_rows()
+            .len()
+            .saturating_sub(self.visible_row_count());
+        let offset =
+            ((self.scroll_offset as i32 - points[POINT_Y]).max(0) as usize).min(max_offset);
+
+        if offset != self.scroll_offset {
+            self.scroll_offset = offset;
+            self.highlighted_row = self.row_at_mouse();
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Toggles a node when its expansion arrow is clicked, or it is double-clicked, and selects the
+    /// clicked node.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        button: u8,
+        clicks: u8,
+        state: bool,
+    ) {
+        if button == 1 && state {
+            let row = self.row_at_mouse();
+
+            if row >= 0 {
+                let path = self.visible_rows()[row as usize].clone();
+                let x = self.mouse_position[POINT_X] - self.get_config().to_x(0);
+                let arrow_x = (path.len() as i32 - 1) * INDENT_WIDTH;
+                let expanded = self.get_node(&path).is_some_and(|node| node.expanded);
+
+                if (x >= arrow_x && x < arrow_x + INDENT_WIDTH) || clicks == 2 {
+                    self.toggle(&path, !expanded, _widgets, _layouts);
+                }
+
+                self.select(&path, _widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, button, clicks, state);
+    }
+
+    /// Moves the selection with the `Up` and `Down` keys, and collapses and expands nodes with the
+    /// `Left` and `Right` keys, while this `Widget` has focus.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            let rows = self.visible_rows();
+
+            if rows.is_empty() {
+                return;
+            }
+
+            let selected_row = self
+                .selected_path
+                .as_ref()
+                .and_then(|path| rows.iter().position(|row| row == path));
+            let path = match selected_row {
+                Some(row) => rows[row].clone(),
+                None => {
+                    self.select(&rows[0], _widgets, _layouts);
+                    return;
+                }
+            };
+            let (is_leaf, expanded) = self
+                .get_node(&path)
+                .map_or((true, false), |node| (node.is_leaf(), node.expanded));
+            let row = selected_row.unwrap();
+
+            match keycode {
+                Keycode::Up if row > 0 => self.select(&rows[row - 1], _widgets, _layouts),
+                Keycode::Down if row + 1 < rows.len() => {
+                    self.select(&rows[row + 1], _widgets, _layouts)
+                }
+                Keycode::Left if expanded => self.toggle(&path, false, _widgets, _layouts),
+                Keycode::Left if path.len() > 1 => {
+                    self.select(&path[..path.len() - 1], _widgets, _layouts)
+                }
+                Keycode::Right if !is_leaf && !expanded => {
+                    self.toggle(&path, true, _widgets, _layouts)
+                }
+                Keycode::Right if expanded && !is_leaf => {
+                    self.select(&rows[row + 1], _widgets, _layouts)
+                }
+                Keycode::Return | Keycode::KpEnter | Keycode::Space if !is_leaf => {
+                    self.toggle(&path, !expanded, _widgets, _layouts)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Releases the backing texture of this `Widget`.  The texture is recreated the next time this
+    /// `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
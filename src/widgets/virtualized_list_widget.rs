@@ -0,0 +1,623 @@
+// Pushrod Widget Library
+// Virtualized List Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::layout_cache::LayoutContainer;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::{Points, Size, POINT_Y, SIZE_HEIGHT, SIZE_WIDTH};
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureQuery};
+use sdl2::video::Window;
+
+use crate::render::texture_cache::TextureCache;
+use crate::render::texture_store::TextureStore;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// This is the default height of each row, in pixels.
+pub const DEFAULT_ROW_HEIGHT: u32 = 24;
+
+/// This is the width of the scroll indicator drawn when the rows do not fit vertically.
+const SCROLL_INDICATOR_WIDTH: u32 = 4;
+
+/// This is the number of pixels between the sides of a row and its text.
+const ROW_PADDING: i32 = 8;
+
+/// This is the number of rows scrolled by each step of the mouse wheel.
+const ROWS_PER_SCROLL_STEP: i32 = 3;
+
+/// This is the content of a single row of a `VirtualizedListWidget`, as returned by its row
+/// provider.
+#[derive(Clone, Debug, Default)]
+pub struct RowData {
+    /// This is the text drawn at the left of the row.
+    pub text: String,
+
+    /// This is the text drawn at the right of the row, such as a size or a date.  Nothing is drawn
+    /// when this is blank.
+    pub detail: String,
+
+    /// This is the color of the text, or `None` to use the `CONFIG_COLOR_TEXT` of the list.
+    pub color: Option<Color>,
+}
+
+/// This is the implementation of the `RowData`.
+impl RowData {
+    /// Creates a new `RowData` that only shows `text`.
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: String::from(text),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `RowData` that shows `text` at the left of the row, and `detail` at the right.
+    pub fn with_detail(text: &str, detail: &str) -> Self {
+        Self {
+            text: String::from(text),
+            detail: String::from(detail),
+            color: None,
+        }
+    }
+}
+
+/// This is the function type that supplies the content of each row of a `VirtualizedListWidget`,
+/// given the index of the row.
+pub type RowProviderFunction = Box<dyn Fn(usize) -> RowData>;
+
+/// This is the callback type that is used when an `on_row_selected` callback is triggered from
+/// this `Widget`.  It receives the index of the selected row.
+pub type OnRowSelectedCallbackType = Option<
+    Box<dyn FnMut(&mut VirtualizedListWidget, &[WidgetContainer], &[LayoutContainer], usize)>,
+>;
+
+/// This is the storage object for the `VirtualizedListWidget`.  It stores the config, properties,
+/// callback registry, the number of rows and the function that provides them, the textures of the
+/// visible rows, the scroll position, and the selected and hovered rows.
+pub struct VirtualizedListWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    texture_store: TextureStore,
+    row_count: usize,
+    row_provider: RowProviderFunction,
+    row_height: u32,
+    row_textures: HashMap<usize, TextureStore>,
+    spare_textures: Vec<TextureStore>,
+    scroll_y: usize,
+    selected_row: Option<usize>,
+    hovered_row: Option<usize>,
+    on_row_selected: OnRowSelectedCallbackType,
+}
+
+/// This is the implementation of the `VirtualizedListWidget`, a list that can show a very large
+/// number of rows.  Rows are not stored by the list: each row is requested from the row provider
+/// when it scrolls into view, and drawn once into a texture of its own, which is kept for as long
+/// as the row stays visible.  When a row scrolls out of view, its texture is recycled for the next
+/// row that scrolls in, so the number of textures only depends on the height of the list.  Call
+/// `refresh` or `refresh_row` when the data behind the rows changes.
+///
+/// Rows are drawn over `CONFIG_COLOR_BASE`, with their text in `CONFIG_COLOR_TEXT` using the size in
+/// `CONFIG_FONT_SIZE`, or `14` if it is not set.  The selected row is filled with
+/// `CONFIG_COLOR_SELECTED`, and the row under the mouse with `CONFIG_COLOR_HOVER`.  Rows are
+/// selected by clicking them, or with the arrow, `Page Up`, `Page Down`, `Home` and `End` keys
+/// while the list has focus.
+impl VirtualizedListWidget {
+    /// Creates a new `VirtualizedListWidget` given the `x, y, w, h` coordinates, the number of rows,
+    /// and the function that provides the content of each row.
+    pub fn new<F>(points: Points, size: Size, row_count: usize, row_provider: F) -> Self
+    where
+        F: Fn(usize) -> RowData + 'static,
+    {
+        let mut config = WidgetConfig::new(points, size);
+
+        config.set_toggle(CONFIG_FOCUSABLE, true);
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            texture_store: TextureStore::default(),
+            row_count,
+            row_provider: Box::new(row_provider),
+            row_height: DEFAULT_ROW_HEIGHT,
+            row_textures: HashMap::new(),
+            spare_textures: Vec::new(),
+            scroll_y: 0,
+            selected_row: None,
+            hovered_row: None,
+            on_row_selected: None,
+        }
+    }
+
+    /// Replaces the function that provides the content of each row, and redraws all of the rows.
+    pub fn set_row_provider<F>(&mut self, row_provider: F)
+    where
+        F: Fn(usize) -> RowData + 'static,
+    {
+        self.row_provider = Box::new(row_provider);
+        self.refresh();
+    }
+
+    /// Sets the number of rows in the list, and redraws all of the rows.  The selection is cleared
+    /// if the selected row no longer exists.
+    pub fn set_row_count(&mut self, row_count: usize) {
+        self.row_count = row_count;
+        self.selected_row = self.selected_row.filter(|row| *row < row_count);
+        self.hovered_row = None;
+        self.refresh();
+    }
+
+    /// Retrieves the number of rows in the list.
+    pub fn get_row_count(&self) -> usize {
+        self.row_count
+    }
+
+    /// Sets the height of each row, in pixels.
+    pub fn set_row_height(&mut self, row_height: u32) {
+        self.row_height = row_height.max(1);
+        self.refresh();
+    }
+
+    /// Retrieves the height of each row, in pixels.
+    pub fn get_row_height(&self) -> u32 {
+        self.row_height
+    }
+
+    /// Requests the content of every visible row from the row provider again, the next time the
+    /// list is drawn.
+    pub fn refresh(&mut self) {
+        let rows: Vec<usize> = self.row_textures.keys().copied().collect();
+
+        for row in rows {
+            self.recycle_row(row);
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Requests the content of the row at `index` from the row provider again, the next time the
+    /// list is drawn.
+    pub fn refresh_row(&mut self, index: usize) {
+        if self.row_textures.contains_key(&index) {
+            self.recycle_row(index);
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Selects the row at `index`, or clears the selection if `None`, and scrolls the row into view.
+    /// This does not trigger the `on_row_selected` callback.
+    pub fn set_selected_row(&mut self, index: Option<usize>) {
+        self.selected_row = index.filter(|row| *row < self.row_count);
+
+        if let Some(row) = self.selected_row {
+            self.scroll_to_row(row);
+        }
+
+        self.get_config().set_invalidated(true);
+    }
+
+    /// Retrieves the index of the selected row, if any.
+    pub fn get_selected_row(&self) -> Option<usize> {
+        self.selected_row
+    }
+
+    /// Scrolls the list as little as possible to show the whole of the row at `index`.
+    pub fn scroll_to_row(&mut self, index: usize) {
+        let height = self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] as usize;
+        let top = index * self.row_height as usize;
+        let bottom = top + self.row_height as usize;
+
+        if top < self.scroll_y {
+            self.set_scroll_offset(top);
+        } else if bottom > self.scroll_y + height {
+            self.set_scroll_offset(bottom.saturating_sub(height));
+        }
+    }
+
+    /// Scrolls the list so that the pixel row `offset` is at the top, as far as the number of rows
+    /// allows.
+    pub fn set_scroll_offset(&mut self, offset: usize) {
+        let offset = offset.min(self.max_scroll_offset());
+
+        if offset != self.scroll_y {
+            self.scroll_y = offset;
+            self.hovered_row = None;
+            self.get_config().set_invalidated(true);
+        }
+    }
+
+    /// Retrieves the pixel row shown at the top of the list.
+    pub fn get_scroll_offset(&self) -> usize {
+        self.scroll_y
+    }
+
+    /// Retrieves the number of row textures currently held by the list, including those kept for
+    /// reuse.  This stays roughly constant however many rows the list has.
+    pub fn get_texture_count(&self) -> usize {
+        self.row_textures.len() + self.spare_textures.len()
+    }
+
+    /// Assigns the callback closure that will be used when a row is selected by the user.
+    pub fn on_row_selected<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut VirtualizedListWidget, &[WidgetContainer], &[LayoutContainer], usize)
+            + 'static,
+    {
+        self.on_row_selected = Some(Box::new(callback));
+    }
+
+    /// Internal function that selects the row at `index`, scrolls it into view, and triggers the
+    /// `on_row_selected` callback.
+    fn select(&mut self, index: usize, widgets: &[WidgetContainer], layouts: &[LayoutContainer]) {
+        if index >= self.row_count {
+            return;
+        }
+
+        self.set_selected_row(Some(index));
+
+        if let Some(mut cb) = self.on_row_selected.take() {
+            cb(self, widgets, layouts, index);
+            self.on_row_selected = Some(cb);
+        }
+    }
+
+    /// Internal function that moves the texture of the row at `index` to the spare textures.
+    fn recycle_row(&mut self, index: usize) {
+        if let Some(texture) = self.row_textures.remove(&index) {
+            self.spare_textures.push(texture);
+        }
+    }
+
+    /// Internal function that returns the number of rows that fit entirely within the list.
+    fn visible_rows(&mut self) -> usize {
+        (self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] / self.row_height).max(1) as usize
+    }
+
+    /// Internal function that returns the largest scroll offset that still fills the list.
+    fn max_scroll_offset(&mut self) -> usize {
+        let height = self.get_config().get_size(CONFIG_SIZE)[SIZE_HEIGHT] as usize;
+
+        (self.row_count * self.row_height as usize).saturating_sub(height)
+    }
+}
+
+/// This is the `Widget` implementation of the `VirtualizedListWidget`.
+impl Widget for VirtualizedListWidget {
+    fn draw(&mut self, c: &mut Canvas<Window>, t: &mut TextureCache) -> Option<&Texture> {
+        if self.get_config().invalidated() {
+            let bounds = self.get_config().get_size(CONFIG_SIZE);
+
+            self.texture_store
+                .create_or_resize_texture(c, bounds[0], bounds[1]);
+
+            let base_color = self.get_color_or(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+            let border_color = self.get_color_or(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+            let hover_color = self.get_color_or(CONFIG_COLOR_HOVER, Color::RGB(232, 232, 240));
+            let selected_color =
+                self.get_color_or(CONFIG_COLOR_SELECTED, Color::RGB(192, 208, 255));
+            let text_color = self.get_color_or(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+            let font_size = self.get_numeric_or(CONFIG_FONT_SIZE, 14);
+            let row_width = bounds[SIZE_WIDTH]
+                .saturating_sub(SCROLL_INDICATOR_WIDTH)
+                .max(1);
+            let row_height = self.row_height;
+
+            self.scroll_y = self.scroll_y.min(self.max_scroll_offset());
+
+            let first_row = self.scroll_y / row_height as usize;
+            let last_row = ((self.scroll_y + bounds[SIZE_HEIGHT] as usize) / row_height as usize
+                + 1)
+            .min(self.row_count);
+
+            // Rows that scrolled out of view give their textures to the rows that scrolled in.
+            // Rows drawn before the list was resized are drawn again.
+            let hidden_rows: Vec<usize> = self
+                .row_textures
+                .iter()
+                .filter(|(row, row_texture)| {
+                    **row < first_row
+                        || **row >= last_row
+                        || row_texture.get_width() != row_width
+                        || row_texture.get_height() != row_height
+                })
+                .map(|(row, _)| *row)
+                .collect();
+
+            for row in hidden_rows {
+                self.recycle_row(row);
+            }
+
+            let missing_rows: Vec<usize> = (first_row..last_row)
+                .filter(|row| !self.row_textures.contains_key(row))
+                .collect();
+
+            if !missing_rows.is_empty() {
+                let ttf_context = t.get_ttf_context();
+                let texture_creator = c.texture_creator();
+                let font = ttf_context
+                    .load_font(
+                        Path::new(&String::from("assets/OpenSans-Regular.ttf")),
+                        font_size as u16,
+                    )
+                    .unwrap();
+
+                for row in missing_rows {
+                    let data = (self.row_provider)(row);
+                    let color = data.color.unwrap_or(text_color);
+                    let mut row_texture = self.spare_textures.pop().unwrap_or_else(|| {
+                        let mut row_texture = TextureStore::default();
+
+                        row_texture.set_blended(true);
+                        row_texture
+                    });
+                    let labels: Vec<(Texture, bool)> = [(&data.text, false), (&data.detail, true)]
+                        .iter()
+                        .filter(|(text, _)| !text.is_empty())
+                        .map(|(text, right)| {
+                            let surface = font
+                                .render(text)
+                                .blended(color)
+                                .map_err(|e| e.to_string())
+                                .unwrap();
+
+                            (
+                                texture_creator
+                                    .create_texture_from_surface(&surface)
+                                    .map_err(|e| e.to_string())
+                                    .unwrap(),
+                                *right,
+                            )
+                        })
+                        .collect();
+
+                    row_texture.create_or_resize_texture(c, row_width, row_height);
+
+                    c.with_texture_canvas(row_texture.get_mut_ref(), |texture| {
+                        texture.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                        texture.clear();
+
+                        for (label, right) in &labels {
+                            let TextureQuery { width, height, .. } = label.query();
+                            let x = if *right {
+                                row_width as i32 - ROW_PADDING - width as i32
+                            } else {
+                                ROW_PADDING
+                            };
+
+                            texture
+                                .copy(
+                                    label,
+                                    None,
+                                    Rect::new(
+                                        x,
+                                        (row_height as i32 - height as i32) / 2,
+                                        width,
+                                        height,
+                                    ),
+                                )
+                                .unwrap();
+                        }
+                    })
+                    .unwrap();
+
+                    // Safety: the label textures are only used while drawing the row.
+                    for (label, _) in labels {
+                        unsafe {
+                            label.destroy();
+                        }
+                    }
+
+                    self.row_textures.insert(row, row_texture);
+                }
+            }
+
+            // Only enough spare textures for a screenful of rows are kept for reuse.
+            let spare_limit = self.visible_rows() + 2;
+
+            self.spare_textures.truncate(spare_limit);
+
+            let scroll_y = self.scroll_y as i32;
+            let row_y = |row: usize| (row * row_height as usize) as i32 - scroll_y;
+            let highlights: Vec<(Rect, Color)> = [
+                (self.hovered_row, hover_color),
+                (self.selected_row, selected_color),
+            ]
+            .iter()
+            .filter_map(|(row, color)| {
+                row.filter(|row| *row >= first_row && *row < last_row)
+                    .map(|row| (Rect::new(0, row_y(row), row_width, row_height), *color))
+            })
+            .collect();
+            let row_textures = &mut self.row_textures;
+            let document_height = (self.row_count * row_height as usize).max(1) as f64;
+            let scroll_indicator = if document_height > bounds[SIZE_HEIGHT] as f64 {
+                let track = bounds[SIZE_HEIGHT] as f64;
+
+                Some(Rect::new(
+                    row_width as i32,
+                    (self.scroll_y as f64 / document_height * track) as i32,
+                    SCROLL_INDICATOR_WIDTH,
+                    ((track / document_height * track) as u32).max(1),
+                ))
+            } else {
+                None
+            };
+
+            c.with_texture_canvas(self.texture_store.get_mut_ref(), |texture| {
+                texture.set_draw_color(base_color);
+                texture.clear();
+
+                for (rect, color) in &highlights {
+                    texture.set_draw_color(*color);
+                    texture.fill_rect(*rect).unwrap();
+                }
+
+                for row in first_row..last_row {
+                    if let Some(row_texture) = row_textures
+                        .get_mut(&row)
+                        .and_then(|row_texture| row_texture.get_optional_ref())
+                    {
+                        texture
+                            .copy(
+                                row_texture,
+                                None,
+                                Rect::new(0, row_y(row), row_width, row_height),
+                            )
+                            .unwrap();
+                    }
+                }
+
+                texture.set_draw_color(border_color);
+
+                if let Some(indicator) = scroll_indicator {
+                    texture.fill_rect(indicator).unwrap();
+                }
+
+                texture
+                    .draw_rect(Rect::new(0, 0, bounds[SIZE_WIDTH], bounds[SIZE_HEIGHT]))
+                    .unwrap();
+            })
+            .unwrap();
+        }
+
+        self.texture_store.get_optional_ref()
+    }
+
+    /// Stops highlighting the row under the mouse.
+    fn mouse_exited(&mut self, _widgets: &[WidgetContainer], _layouts: &[LayoutContainer]) {
+        self.hovered_row = None;
+        self.get_config().set_invalidated(true);
+        self.mouse_exited_callback(_widgets, _layouts);
+    }
+
+    /// Highlights the row under the mouse.
+    fn mouse_moved(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let y = points[POINT_Y] - self.get_config().to_y(0) + self.scroll_y as i32;
+        let hovered_row =
+            Some(y.max(0) as usize / self.row_height as usize).filter(|row| *row < self.row_count);
+
+        if hovered_row != self.hovered_row {
+            self.hovered_row = hovered_row;
+            self.get_config().set_invalidated(true);
+        }
+
+        self.mouse_moved_callback(_widgets, _layouts, points);
+    }
+
+    /// Scrolls the list by a few rows for each step of the mouse wheel.
+    fn mouse_scrolled(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        points: Points,
+    ) {
+        let offset =
+            self.scroll_y as i32 - points[POINT_Y] * self.row_height as i32 * ROWS_PER_SCROLL_STEP;
+
+        self.set_scroll_offset(offset.max(0) as usize);
+        self.mouse_scrolled_callback(_widgets, _layouts, points);
+    }
+
+    /// Selects the row under the mouse when the mouse button is pressed.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 && _state {
+            if let Some(row) = self.hovered_row {
+                self.select(row, _widgets, _layouts);
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _layouts, _button, _clicks, _state);
+    }
+
+    /// Moves the selection with the arrow, `Page Up`, `Page Down`, `Home` and `End` keys.
+    fn other_event(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _layouts: &[LayoutContainer],
+        event: Event,
+    ) {
+        if self.row_count == 0 {
+            return;
+        }
+
+        if let Event::KeyDown {
+            keycode: Some(keycode),
+            ..
+        } = event
+        {
+            let last_row = self.row_count - 1;
+            let page = self.visible_rows();
+            let row = match (keycode, self.selected_row) {
+                (Keycode::Up, Some(row)) => row.saturating_sub(1),
+                (Keycode::Down, Some(row)) => (row + 1).min(last_row),
+                (Keycode::PageUp, Some(row)) => row.saturating_sub(page),
+                (Keycode::PageDown, Some(row)) => (row + page).min(last_row),
+                (Keycode::Up, None) | (Keycode::Down, None) | (Keycode::Home, _) => 0,
+                (Keycode::PageUp, None) | (Keycode::PageDown, None) => 0,
+                (Keycode::End, _) => last_row,
+                _ => return,
+            };
+
+            if Some(row) != self.selected_row {
+                self.select(row, _widgets, _layouts);
+            }
+        }
+    }
+
+    /// Redraws all of the rows when the colors or font size change.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_TEXT | CONFIG_FONT_SIZE => self.refresh(),
+            CONFIG_COLOR_BASE
+            | CONFIG_COLOR_BORDER
+            | CONFIG_COLOR_HOVER
+            | CONFIG_COLOR_SELECTED => self.get_config().set_invalidated(true),
+            _ => (),
+        };
+    }
+
+    /// Releases the backing texture of this `Widget`, and the textures of all of its rows.  The
+    /// textures are recreated the next time this `Widget` is drawn.
+    fn release_textures(&mut self) {
+        self.texture_store.release();
+        self.row_textures.clear();
+        self.spare_textures.clear();
+        self.set_invalidated(true);
+    }
+
+    default_widget_functions!();
+    default_widget_properties!();
+    default_widget_callbacks!();
+}